@@ -30,6 +30,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         account.username()
     );
 
+    // Get unread notifications
+    let notifications = rest.account.get_notifications(None).await?;
+    println!("Got unread notifications. Len: {}", notifications.len());
+
     // Futures Isolated endpoints
 
     // Get all open trades
@@ -270,7 +274,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Withdraw funds from cross margin account
     let position = rest
         .futures_cross
-        .withdraw(NonZeroU64::try_from(position.margin())?)
+        .withdraw(NonZeroU64::try_from(position.margin().as_u64())?)
         .await?;
     println!(
         "Withdrew from cross account. New margin: {}",
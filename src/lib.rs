@@ -15,6 +15,15 @@ pub mod rest;
 /// Stream API implementations.
 pub mod stream;
 
+/// Prometheus metrics of REST requests, order round trips and Stream reconnections, available
+/// with the `prometheus` feature.
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+
+/// Testing utilities for downstream applications, available with the `testing` feature.
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 mod shared;
 
 mod sealed {
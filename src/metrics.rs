@@ -0,0 +1,205 @@
+use std::{sync::OnceLock, time::Duration};
+
+use http::Method;
+use prometheus::{HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, Result};
+
+use crate::shared::rest::error::RestApiError;
+
+/// Paths of the order placement endpoints, whose latency is also reported as order round-trip
+/// time, with the market they belong to.
+const ORDER_PATHS: [(&str, &str); 2] = [
+    ("/futures/isolated/trade", "isolated"),
+    ("/futures/cross/order", "cross"),
+];
+
+static METRICS: OnceLock<SdkMetrics> = OnceLock::new();
+
+/// Prometheus metrics recorded by all SDK clients of the process.
+///
+/// | Metric | Type | Labels |
+/// |--------|------|--------|
+/// | `lnm_rest_request_duration_seconds` | histogram | `method`, `path` |
+/// | `lnm_rest_request_errors_total` | counter | `method`, `path`, `error` |
+/// | `lnm_order_round_trip_seconds` | histogram | `market` |
+/// | `lnm_stream_reconnects_total` | counter | |
+///
+/// The `error` label holds the HTTP status code of error responses, or `transport` for requests
+/// that failed without a response.
+///
+/// Metrics are always recorded, and exposed once [`register`]ed in a [`Registry`].
+#[derive(Debug, Clone)]
+pub struct SdkMetrics {
+    request_duration: HistogramVec,
+    request_errors: IntCounterVec,
+    order_round_trip: HistogramVec,
+    stream_reconnects: IntCounter,
+}
+
+impl SdkMetrics {
+    fn new() -> Self {
+        let request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "lnm_rest_request_duration_seconds",
+                "Duration of LNM REST API requests, from sending to receiving the full response.",
+            ),
+            &["method", "path"],
+        )
+        .expect("metric options must be valid");
+
+        let request_errors = IntCounterVec::new(
+            Opts::new(
+                "lnm_rest_request_errors_total",
+                "Number of LNM REST API requests that failed.",
+            ),
+            &["method", "path", "error"],
+        )
+        .expect("metric options must be valid");
+
+        let order_round_trip = HistogramVec::new(
+            HistogramOpts::new(
+                "lnm_order_round_trip_seconds",
+                "Round-trip time of LNM order placement requests.",
+            ),
+            &["market"],
+        )
+        .expect("metric options must be valid");
+
+        let stream_reconnects = IntCounter::new(
+            "lnm_stream_reconnects_total",
+            "Number of successful LNM Stream API reconnections.",
+        )
+        .expect("metric options must be valid");
+
+        Self {
+            request_duration,
+            request_errors,
+            order_round_trip,
+            stream_reconnects,
+        }
+    }
+
+    /// Registers all SDK metrics in `registry`.
+    pub fn register(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.request_duration.clone()))?;
+        registry.register(Box::new(self.request_errors.clone()))?;
+        registry.register(Box::new(self.order_round_trip.clone()))?;
+        registry.register(Box::new(self.stream_reconnects.clone()))
+    }
+
+    /// Duration of REST requests, by method and path.
+    pub fn request_duration(&self) -> &HistogramVec {
+        &self.request_duration
+    }
+
+    /// Failed REST requests, by method, path and error.
+    pub fn request_errors(&self) -> &IntCounterVec {
+        &self.request_errors
+    }
+
+    /// Round-trip time of order placement requests, by market.
+    pub fn order_round_trip(&self) -> &HistogramVec {
+        &self.order_round_trip
+    }
+
+    /// Successful Stream API reconnections.
+    pub fn stream_reconnects(&self) -> &IntCounter {
+        &self.stream_reconnects
+    }
+}
+
+/// Returns the process-wide SDK metrics.
+pub fn sdk_metrics() -> &'static SdkMetrics {
+    METRICS.get_or_init(SdkMetrics::new)
+}
+
+/// Registers the SDK metrics in `registry`.
+///
+/// # Examples
+///
+/// ```
+/// lnm_sdk::metrics::register(prometheus::default_registry())?;
+/// # Ok::<(), prometheus::Error>(())
+/// ```
+pub fn register(registry: &Registry) -> Result<()> {
+    sdk_metrics().register(registry)
+}
+
+pub(crate) fn observe_request(
+    method: &Method,
+    path: &str,
+    elapsed: Duration,
+    error: Option<&RestApiError>,
+) {
+    let metrics = sdk_metrics();
+    let secs = elapsed.as_secs_f64();
+
+    metrics
+        .request_duration
+        .with_label_values(&[method.as_str(), path])
+        .observe(secs);
+
+    if let Some(error) = error {
+        let label = error
+            .status()
+            .map(|status| status.as_u16().to_string())
+            .unwrap_or_else(|| "transport".to_string());
+
+        metrics
+            .request_errors
+            .with_label_values(&[method.as_str(), path, label.as_str()])
+            .inc();
+    }
+
+    if *method == Method::POST
+        && let Some((_, market)) = ORDER_PATHS
+            .iter()
+            .find(|(order_path, _)| path.ends_with(order_path))
+    {
+        metrics
+            .order_round_trip
+            .with_label_values(&[*market])
+            .observe(secs);
+    }
+}
+
+pub(crate) fn inc_stream_reconnects() {
+    sdk_metrics().stream_reconnects.inc();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_and_observe() {
+        let registry = Registry::new();
+        register(&registry).unwrap();
+        assert!(register(&registry).is_err());
+
+        observe_request(
+            &Method::POST,
+            "/v3/futures/cross/order",
+            Duration::from_millis(20),
+            None,
+        );
+        inc_stream_reconnects();
+
+        let names: Vec<_> = registry
+            .gather()
+            .iter()
+            .map(|family| family.name().to_string())
+            .collect();
+
+        assert!(names.contains(&"lnm_rest_request_duration_seconds".to_string()));
+        assert!(names.contains(&"lnm_order_round_trip_seconds".to_string()));
+        assert!(names.contains(&"lnm_stream_reconnects_total".to_string()));
+        assert!(sdk_metrics().stream_reconnects().get() >= 1);
+        assert!(
+            sdk_metrics()
+                .order_round_trip()
+                .with_label_values(&["cross"])
+                .get_sample_count()
+                >= 1
+        );
+    }
+}
@@ -0,0 +1,475 @@
+//! Amending resting cross limit orders.
+//!
+//! LN Markets doesn't support modifying a resting order, so an [`AmendableOrder`] amends its price
+//! or quantity by canceling the working order and placing a replacement. Every revision is placed
+//! with a client ID sharing a common prefix, so the lineage of the order can be traced back.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::shared::{
+    models::{
+        client_id::ClientId,
+        price::Price,
+        quantity::order::OrderQuantity,
+        trade::{TradeExecution, TradeSide},
+    },
+    rest::error::RestApiError,
+};
+
+use super::{LnmFuturesApi, models::CrossOrder};
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum AmendValidationError {
+    #[error("Client ID prefix must be at most {max} characters, got {len}")]
+    PrefixTooLong { len: usize, max: usize },
+
+    #[error("Amendment doesn't change the price nor the quantity")]
+    NoChange,
+
+    #[error("Order has no working revision to amend")]
+    NotWorking,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum AmendError {
+    #[error("Invalid amendment: {0}")]
+    Validation(#[from] AmendValidationError),
+
+    #[error(transparent)]
+    RestApi(#[from] RestApiError),
+
+    #[error("Revision {} was canceled, but its replacement couldn't be placed: {source}", canceled.id())]
+    ReplaceFailed {
+        canceled: Box<CrossOrder>,
+        source: RestApiError,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, AmendError>;
+
+#[derive(Debug)]
+struct AmendState {
+    quantity: OrderQuantity,
+    price: Price,
+    revisions: Vec<ClientId>,
+    working: Option<CrossOrder>,
+    filled: bool,
+    canceled: bool,
+}
+
+/// Cross limit order whose price and quantity can be amended while it rests on the book.
+///
+/// Revisions are placed as limit orders with client IDs `{prefix}-{n}`, `n` starting at 1. An
+/// amendment cancels the working revision and places the next one, under a single lock, so
+/// concurrent amendments never leave more than one revision on the book. Fills must be reported
+/// with [`on_filled`](Self::on_filled), e.g. from Stream API cross order updates.
+///
+/// Tick size and quantity bounds are enforced by [`Price`] and [`OrderQuantity`], so amended
+/// values are validated when they are built.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: std::sync::Arc<lnm_sdk::rest::v3::RestClient>) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::{
+///     amend::AmendableOrder,
+///     models::{OrderQuantity, Price, TradeSide},
+/// };
+///
+/// let order = AmendableOrder::new(
+///     rest,
+///     "bid",
+///     TradeSide::Buy,
+///     OrderQuantity::try_from(1_000)?,
+///     Price::try_from(95_000)?,
+/// )?;
+///
+/// // Places `bid-1`
+/// order.place().await?;
+///
+/// // Cancels `bid-1` and places `bid-2` at the new price
+/// order.amend(Some(Price::try_from(95_500)?), None).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AmendableOrder {
+    api: Arc<dyn LnmFuturesApi>,
+    prefix: String,
+    side: TradeSide,
+    state: Mutex<AmendState>,
+    // Serializes placing, replacing and canceling revisions, so at most one is ever working
+    send_lock: tokio::sync::Mutex<()>,
+}
+
+impl AmendableOrder {
+    /// Maximum length of the client ID prefix, leaving room for the revision number.
+    pub const MAX_PREFIX_LEN: usize = ClientId::MAX_LEN - 8;
+
+    /// Creates an order of `quantity` at `price`. Nothing is placed until
+    /// [`place`](Self::place) is called.
+    pub fn new(
+        api: Arc<dyn LnmFuturesApi>,
+        prefix: impl Into<String>,
+        side: TradeSide,
+        quantity: OrderQuantity,
+        price: Price,
+    ) -> std::result::Result<Self, AmendValidationError> {
+        let prefix = prefix.into();
+
+        if prefix.len() > Self::MAX_PREFIX_LEN {
+            return Err(AmendValidationError::PrefixTooLong {
+                len: prefix.len(),
+                max: Self::MAX_PREFIX_LEN,
+            });
+        }
+
+        Ok(Self {
+            api,
+            prefix,
+            side,
+            state: Mutex::new(AmendState {
+                quantity,
+                price,
+                revisions: Vec::new(),
+                working: None,
+                filled: false,
+                canceled: false,
+            }),
+            send_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Client ID prefix shared by the revisions.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn side(&self) -> TradeSide {
+        self.side
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, AmendState> {
+        self.state
+            .lock()
+            .expect("`AmendableOrder` mutex can't be poisoned")
+    }
+
+    /// Quantity of the latest revision.
+    pub fn quantity(&self) -> OrderQuantity {
+        self.lock_state().quantity
+    }
+
+    /// Limit price of the latest revision.
+    pub fn price(&self) -> Price {
+        self.lock_state().price
+    }
+
+    /// Revision currently on the book, if any.
+    pub fn working(&self) -> Option<CrossOrder> {
+        self.lock_state().working.clone()
+    }
+
+    /// Client IDs of the revisions placed so far, oldest first.
+    pub fn lineage(&self) -> Vec<ClientId> {
+        self.lock_state().revisions.clone()
+    }
+
+    /// Returns `true` once the working revision is filled.
+    pub fn is_filled(&self) -> bool {
+        self.lock_state().filled
+    }
+
+    /// Returns `true` if the order was canceled with [`cancel`](Self::cancel).
+    pub fn is_canceled(&self) -> bool {
+        self.lock_state().canceled
+    }
+
+    /// Returns `true` if `client_id` belongs to one of the revisions of this order.
+    pub fn owns(&self, client_id: &ClientId) -> bool {
+        client_id
+            .as_str()
+            .strip_prefix(self.prefix.as_str())
+            .and_then(|suffix| suffix.strip_prefix('-'))
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+    }
+
+    fn next_client_id(&self, state: &AmendState) -> ClientId {
+        ClientId::try_from(format!("{}-{}", self.prefix, state.revisions.len() + 1))
+            .expect("must be a valid `ClientId`, as the prefix length is bounded")
+    }
+
+    async fn place_revision(
+        &self,
+        quantity: OrderQuantity,
+        price: Price,
+        client_id: ClientId,
+    ) -> std::result::Result<CrossOrder, RestApiError> {
+        let order = self
+            .api
+            .place_cross_order(
+                self.side,
+                quantity,
+                TradeExecution::Limit(price),
+                Some(client_id.clone()),
+            )
+            .await?;
+
+        let mut state = self.lock_state();
+        state.quantity = quantity;
+        state.price = price;
+        state.revisions.push(client_id);
+        state.working = Some(order.clone());
+
+        Ok(order)
+    }
+
+    /// Places the first revision if none was placed yet, and the order wasn't canceled.
+    ///
+    /// Returns the revision placed, if any.
+    pub async fn place(&self) -> Result<Option<CrossOrder>> {
+        let _send_guard = self.send_lock.lock().await;
+
+        let (quantity, price, client_id) = {
+            let state = self.lock_state();
+
+            if !state.revisions.is_empty() || state.canceled {
+                return Ok(None);
+            }
+
+            (state.quantity, state.price, self.next_client_id(&state))
+        };
+
+        let order = self.place_revision(quantity, price, client_id).await?;
+
+        Ok(Some(order))
+    }
+
+    /// Amends the price and/or quantity of the working revision, by canceling it and placing the
+    /// next revision. `None` keeps the current value.
+    ///
+    /// If canceling fails, e.g. because the revision was filled in the meantime, it remains
+    /// [`working`](Self::working) and [`AmendError::RestApi`] is returned. If the replacement
+    /// can't be placed after the cancel, nothing is left working and
+    /// [`AmendError::ReplaceFailed`] is returned.
+    pub async fn amend(
+        &self,
+        price: Option<Price>,
+        quantity: Option<OrderQuantity>,
+    ) -> Result<CrossOrder> {
+        let _send_guard = self.send_lock.lock().await;
+
+        let (working, quantity, price, client_id) = {
+            let state = self.lock_state();
+
+            let Some(working) = state.working.clone() else {
+                return Err(AmendValidationError::NotWorking.into());
+            };
+
+            let quantity = quantity.unwrap_or(state.quantity);
+            let price = price.unwrap_or(state.price);
+
+            if quantity == state.quantity && price == state.price {
+                return Err(AmendValidationError::NoChange.into());
+            }
+
+            (working, quantity, price, self.next_client_id(&state))
+        };
+
+        let canceled = self.api.cancel_cross_order(working.id()).await?;
+        self.lock_state().working = None;
+
+        self.place_revision(quantity, price, client_id)
+            .await
+            .map_err(|source| AmendError::ReplaceFailed {
+                canceled: Box::new(canceled),
+                source,
+            })
+    }
+
+    /// Records the fill of the order with `id`.
+    ///
+    /// Returns `true` if it was the working revision. Fills of other orders are ignored.
+    pub fn on_filled(&self, id: Uuid) -> bool {
+        let mut state = self.lock_state();
+
+        if state
+            .working
+            .take_if(|working| working.id() == id)
+            .is_none()
+        {
+            return false;
+        }
+        state.filled = true;
+
+        true
+    }
+
+    /// Stops the order from being placed or amended, and cancels the working revision, if any.
+    ///
+    /// Returns the canceled revision. If canceling fails, e.g. because the revision was filled in
+    /// the meantime, it remains [`working`](Self::working) so its fill can still be recorded.
+    pub async fn cancel(&self) -> Result<Option<CrossOrder>> {
+        let _send_guard = self.send_lock.lock().await;
+
+        let working = {
+            let mut state = self.lock_state();
+            state.canceled = true;
+            state.working.clone()
+        };
+
+        let Some(working) = working else {
+            return Ok(None);
+        };
+
+        let canceled = self.api.cancel_cross_order(working.id()).await?;
+        self.lock_state().working = None;
+
+        Ok(Some(canceled))
+    }
+}
+
+impl fmt::Debug for AmendableOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock_state();
+
+        f.debug_struct("AmendableOrder")
+            .field("prefix", &self.prefix)
+            .field("side", &self.side)
+            .field("quantity", &state.quantity)
+            .field("price", &state.price)
+            .field("revisions", &state.revisions.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::*;
+    use crate::shared::rest::error::ErrorResponseContext;
+    use crate::testing::stub::StubApi;
+
+    /// Returns placed revisions as open orders, failing cancels of the orders in `filled`.
+    fn book_api(filled: Arc<Mutex<Vec<Uuid>>>) -> StubApi {
+        let placed = Arc::new(Mutex::new(Vec::<CrossOrder>::new()));
+        let book = placed.clone();
+
+        StubApi::new()
+            .with_place_cross_order(move |side, quantity, execution, client_id| {
+                let TradeExecution::Limit(price) = execution else {
+                    panic!("revisions must be limit orders");
+                };
+
+                let order: CrossOrder = serde_json::from_value(json!({
+                    "id": Uuid::new_v4(),
+                    "type": "limit",
+                    "side": side,
+                    "quantity": quantity,
+                    "price": price,
+                    "tradingFee": 0,
+                    "createdAt": "2025-01-01T00:00:00.000Z",
+                    "filledAt": null,
+                    "canceledAt": null,
+                    "open": true,
+                    "filled": false,
+                    "canceled": false,
+                    "clientId": client_id,
+                }))
+                .unwrap();
+
+                placed.lock().unwrap().push(order.clone());
+                Ok(order)
+            })
+            .with_cancel_cross_order(move |id| {
+                if filled.lock().unwrap().contains(&id) {
+                    return Err(RestApiError::ErrorResponse(Box::new(
+                        ErrorResponseContext {
+                            method: Method::DELETE,
+                            path: "/v3/futures/cross/order".to_string(),
+                            status: StatusCode::BAD_REQUEST,
+                            request_id: None,
+                            correlation_id: None,
+                            retry_after: None,
+                            text: "order already filled".to_string(),
+                        },
+                    )));
+                }
+                let placed = book.lock().unwrap();
+                Ok(placed.iter().find(|o| o.id() == id).unwrap().clone())
+            })
+    }
+
+    fn quantity(value: u32) -> OrderQuantity {
+        OrderQuantity::try_from(value).unwrap()
+    }
+
+    fn price(value: u32) -> Price {
+        Price::try_from(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_amendable_order_replaces_revisions() {
+        let filled = Arc::new(Mutex::new(Vec::new()));
+        let api = Arc::new(book_api(filled.clone()));
+
+        let order = AmendableOrder::new(
+            api.clone(),
+            "bid",
+            TradeSide::Buy,
+            quantity(1_000),
+            price(95_000),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            order.amend(Some(price(95_500)), None).await,
+            Err(AmendError::Validation(AmendValidationError::NotWorking))
+        ));
+
+        let first = order.place().await.unwrap().unwrap();
+        assert_eq!(first.client_id().unwrap().as_str(), "bid-1");
+        assert!(order.place().await.unwrap().is_none());
+
+        assert!(matches!(
+            order
+                .amend(Some(price(95_000)), Some(quantity(1_000)))
+                .await,
+            Err(AmendError::Validation(AmendValidationError::NoChange))
+        ));
+
+        let second = order.amend(Some(price(95_500)), None).await.unwrap();
+        assert_eq!(second.client_id().unwrap().as_str(), "bid-2");
+        assert_eq!(second.price(), price(95_500));
+        assert_eq!(second.quantity(), quantity(1_000));
+        assert_eq!(order.working().unwrap().id(), second.id());
+
+        // The working revision filled before it could be canceled
+        filled.lock().unwrap().push(second.id());
+        assert!(matches!(
+            order.amend(None, Some(quantity(500))).await,
+            Err(AmendError::RestApi(_))
+        ));
+        assert_eq!(order.working().unwrap().id(), second.id());
+        assert_eq!(order.quantity(), quantity(1_000));
+
+        assert!(!order.on_filled(first.id()));
+        assert!(order.on_filled(second.id()));
+        assert!(order.is_filled());
+        assert!(order.working().is_none());
+
+        let lineage = order.lineage();
+        assert_eq!(lineage.len(), 2);
+        assert!(lineage.iter().all(|client_id| order.owns(client_id)));
+        assert!(!order.owns(&ClientId::try_from("bid-x").unwrap()));
+    }
+}
@@ -0,0 +1,328 @@
+use std::num::NonZeroU64;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::shared::{
+    models::{
+        client_id::ClientId,
+        instrument::Instrument,
+        leverage::Leverage,
+        price::Price,
+        quantity::order::OrderQuantity,
+        trade::{TradeExecution, TradeSide, TradeSize},
+    },
+    rest::error::{RestApiError, Result},
+};
+
+use super::{
+    RestClient,
+    models::{
+        account::Account,
+        page::Page,
+        ticker::Ticker,
+        trade::{CrossOrder, CrossPosition, Trade},
+    },
+};
+
+/// Object-safe trait with the core futures trading operations of [LNM's v3 API].
+///
+/// Implemented by [`RestClient`]. Unlike the repository traits, this trait is not sealed:
+/// application code can depend on `Arc<dyn LnmFuturesApi>` and be unit-tested against test doubles,
+/// including mocks generated with [`mockall`](https://docs.rs/mockall) via `#[automock]`.
+///
+/// Each method mirrors the repository method of the same name, which documents the request,
+/// validation and required permissions.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::sync::Arc;
+///
+/// use lnm_sdk::rest::v3::{LnmFuturesApi, RestClient, RestClientConfig};
+///
+/// async fn close_all(api: Arc<dyn LnmFuturesApi>) -> Result<(), Box<dyn std::error::Error>> {
+///     for trade in api.get_running_trades().await? {
+///         api.close_trade(trade.id()).await?;
+///     }
+///     Ok(())
+/// }
+///
+/// let rest = RestClient::with_credentials(RestClientConfig::default(), "key", "secret", "pphrase")?;
+/// close_all(Arc::new(rest)).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [LNM's v3 API]: https://api.lnmarkets.com/v3/
+#[async_trait]
+pub trait LnmFuturesApi: Send + Sync {
+    /// Instrument traded through this API.
+    ///
+    /// Default: [`Instrument::FuturesInverseBtcUsd`], the only market currently listed.
+    fn instrument(&self) -> Instrument {
+        Instrument::FuturesInverseBtcUsd
+    }
+
+    /// See [`FuturesDataRepository::get_ticker`](super::FuturesDataRepository::get_ticker).
+    async fn get_ticker(&self) -> Result<Ticker>;
+
+    /// See [`AccountRepository::get_account`](super::AccountRepository::get_account).
+    async fn get_account(&self) -> Result<Account>;
+
+    /// See
+    /// [`FuturesIsolatedRepository::get_open_trades`](super::FuturesIsolatedRepository::get_open_trades).
+    async fn get_open_trades(&self) -> Result<Vec<Trade>>;
+
+    /// See
+    /// [`FuturesIsolatedRepository::get_running_trades`](super::FuturesIsolatedRepository::get_running_trades).
+    async fn get_running_trades(&self) -> Result<Vec<Trade>>;
+
+    /// See
+    /// [`FuturesIsolatedRepository::get_closed_trades`](super::FuturesIsolatedRepository::get_closed_trades).
+    ///
+    /// Default: fails with [`RestApiError::UnsupportedOperation`], for implementations that don't
+    /// keep trade history.
+    async fn get_closed_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        let _ = (from, to, limit, cursor);
+        Err(RestApiError::UnsupportedOperation("get_closed_trades"))
+    }
+
+    /// See
+    /// [`FuturesIsolatedRepository::get_canceled_trades`](super::FuturesIsolatedRepository::get_canceled_trades).
+    ///
+    /// Default: fails with [`RestApiError::UnsupportedOperation`], for implementations that don't
+    /// keep trade history.
+    async fn get_canceled_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        let _ = (from, to, limit, cursor);
+        Err(RestApiError::UnsupportedOperation("get_canceled_trades"))
+    }
+
+    /// See [`FuturesIsolatedRepository::new_trade`](super::FuturesIsolatedRepository::new_trade).
+    #[allow(clippy::too_many_arguments)]
+    async fn new_trade(
+        &self,
+        side: TradeSide,
+        size: TradeSize,
+        leverage: Leverage,
+        execution: TradeExecution,
+        stoploss: Option<Price>,
+        takeprofit: Option<Price>,
+        client_id: Option<ClientId>,
+    ) -> Result<Trade>;
+
+    /// See [`FuturesIsolatedRepository::close_trade`](super::FuturesIsolatedRepository::close_trade).
+    async fn close_trade(&self, id: Uuid) -> Result<Trade>;
+
+    /// See
+    /// [`FuturesIsolatedRepository::cancel_trade`](super::FuturesIsolatedRepository::cancel_trade).
+    async fn cancel_trade(&self, id: Uuid) -> Result<Trade>;
+
+    /// See
+    /// [`FuturesIsolatedRepository::add_margin_to_trade`](super::FuturesIsolatedRepository::add_margin_to_trade).
+    async fn add_margin_to_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade>;
+
+    /// See
+    /// [`FuturesIsolatedRepository::cash_in_trade`](super::FuturesIsolatedRepository::cash_in_trade).
+    async fn cash_in_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade>;
+
+    /// See
+    /// [`FuturesIsolatedRepository::update_stoploss`](super::FuturesIsolatedRepository::update_stoploss).
+    async fn update_stoploss(&self, id: Uuid, value: Option<Price>) -> Result<Trade>;
+
+    /// See
+    /// [`FuturesIsolatedRepository::update_takeprofit`](super::FuturesIsolatedRepository::update_takeprofit).
+    async fn update_takeprofit(&self, id: Uuid, value: Option<Price>) -> Result<Trade>;
+
+    /// See [`FuturesCrossRepository::get_position`](super::FuturesCrossRepository::get_position).
+    async fn get_cross_position(&self) -> Result<CrossPosition>;
+
+    /// See
+    /// [`FuturesCrossRepository::get_open_orders`](super::FuturesCrossRepository::get_open_orders).
+    async fn get_open_cross_orders(&self) -> Result<Vec<CrossOrder>>;
+
+    /// See
+    /// [`FuturesCrossRepository::get_filled_orders`](super::FuturesCrossRepository::get_filled_orders).
+    ///
+    /// Default: fails with [`RestApiError::UnsupportedOperation`], for implementations that don't
+    /// keep order history.
+    async fn get_filled_cross_orders(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<CrossOrder>> {
+        let _ = (from, to, limit, cursor);
+        Err(RestApiError::UnsupportedOperation(
+            "get_filled_cross_orders",
+        ))
+    }
+
+    /// See [`FuturesCrossRepository::place_order`](super::FuturesCrossRepository::place_order).
+    async fn place_cross_order(
+        &self,
+        side: TradeSide,
+        quantity: OrderQuantity,
+        execution: TradeExecution,
+        client_id: Option<ClientId>,
+    ) -> Result<CrossOrder>;
+
+    /// See [`FuturesCrossRepository::cancel_order`](super::FuturesCrossRepository::cancel_order).
+    async fn cancel_cross_order(&self, id: Uuid) -> Result<CrossOrder>;
+
+    /// See
+    /// [`FuturesCrossRepository::close_position`](super::FuturesCrossRepository::close_position).
+    async fn close_cross_position(&self) -> Result<CrossOrder>;
+
+    /// See [`FuturesCrossRepository::deposit`](super::FuturesCrossRepository::deposit).
+    ///
+    /// Default: fails with [`RestApiError::UnsupportedOperation`], for implementations that don't
+    /// manage cross margin.
+    async fn deposit_cross_margin(&self, amount: NonZeroU64) -> Result<CrossPosition> {
+        let _ = amount;
+        Err(RestApiError::UnsupportedOperation("deposit_cross_margin"))
+    }
+}
+
+#[async_trait]
+impl LnmFuturesApi for RestClient {
+    async fn get_ticker(&self) -> Result<Ticker> {
+        self.futures_data.get_ticker().await
+    }
+
+    async fn get_account(&self) -> Result<Account> {
+        self.account.get_account().await
+    }
+
+    async fn get_open_trades(&self) -> Result<Vec<Trade>> {
+        self.futures_isolated.get_open_trades().await
+    }
+
+    async fn get_running_trades(&self) -> Result<Vec<Trade>> {
+        self.futures_isolated.get_running_trades().await
+    }
+
+    async fn get_closed_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        self.futures_isolated
+            .get_closed_trades(from, to, limit, cursor)
+            .await
+    }
+
+    async fn get_canceled_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        self.futures_isolated
+            .get_canceled_trades(from, to, limit, cursor)
+            .await
+    }
+
+    async fn new_trade(
+        &self,
+        side: TradeSide,
+        size: TradeSize,
+        leverage: Leverage,
+        execution: TradeExecution,
+        stoploss: Option<Price>,
+        takeprofit: Option<Price>,
+        client_id: Option<ClientId>,
+    ) -> Result<Trade> {
+        self.futures_isolated
+            .new_trade(
+                side, size, leverage, execution, stoploss, takeprofit, client_id,
+            )
+            .await
+    }
+
+    async fn close_trade(&self, id: Uuid) -> Result<Trade> {
+        self.futures_isolated.close_trade(id).await
+    }
+
+    async fn cancel_trade(&self, id: Uuid) -> Result<Trade> {
+        self.futures_isolated.cancel_trade(id).await
+    }
+
+    async fn add_margin_to_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade> {
+        self.futures_isolated.add_margin_to_trade(id, amount).await
+    }
+
+    async fn cash_in_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade> {
+        self.futures_isolated.cash_in_trade(id, amount).await
+    }
+
+    async fn update_stoploss(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        self.futures_isolated.update_stoploss(id, value).await
+    }
+
+    async fn update_takeprofit(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        self.futures_isolated.update_takeprofit(id, value).await
+    }
+
+    async fn get_cross_position(&self) -> Result<CrossPosition> {
+        self.futures_cross.get_position().await
+    }
+
+    async fn get_open_cross_orders(&self) -> Result<Vec<CrossOrder>> {
+        self.futures_cross.get_open_orders().await
+    }
+
+    async fn get_filled_cross_orders(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<CrossOrder>> {
+        self.futures_cross
+            .get_filled_orders(from, to, limit, cursor)
+            .await
+    }
+
+    async fn place_cross_order(
+        &self,
+        side: TradeSide,
+        quantity: OrderQuantity,
+        execution: TradeExecution,
+        client_id: Option<ClientId>,
+    ) -> Result<CrossOrder> {
+        self.futures_cross
+            .place_order(side, quantity, execution, client_id)
+            .await
+    }
+
+    async fn cancel_cross_order(&self, id: Uuid) -> Result<CrossOrder> {
+        self.futures_cross.cancel_order(id).await
+    }
+
+    async fn close_cross_position(&self) -> Result<CrossOrder> {
+        self.futures_cross.close_position().await
+    }
+
+    async fn deposit_cross_margin(&self, amount: NonZeroU64) -> Result<CrossPosition> {
+        self.futures_cross.deposit(amount).await
+    }
+}
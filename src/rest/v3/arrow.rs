@@ -0,0 +1,415 @@
+//! Conversion of trade, candle and funding history into [Arrow](https://arrow.apache.org)
+//! [`RecordBatch`]es, available with the `arrow` feature.
+//!
+//! Each model maps to a fixed schema, with one row per item and one column per field. Timestamps
+//! are millisecond precision UTC timestamps, prices and leverages are `Float64`, sats amounts are
+//! `Int64`, and IDs are strings. Record batches can be handed directly to Arrow-based tools, such
+//! as Polars (via `DataFrame::try_from`), DataFusion or Parquet writers, without row-by-row
+//! conversion.
+
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, TimestampMillisecondArray,
+    UInt64Array,
+};
+use chrono::{DateTime, Utc};
+
+pub use arrow_array::RecordBatch;
+
+use super::models::{
+    CrossFunding, CrossOrder, FundingSettlement, IsolatedFunding, OhlcCandle, Price, Trade,
+    TradeExecutionType, TradeSide,
+};
+
+/// Conversion of a slice of models into an Arrow [`RecordBatch`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::arrow::ToRecordBatch;
+///
+/// let closed = rest.futures_isolated.get_closed_trades(None, None, None, None).await?;
+/// let batch = closed.data().to_record_batch();
+///
+/// println!("{} trades, columns: {:?}", batch.num_rows(), batch.schema().fields());
+/// # Ok(())
+/// # }
+/// ```
+pub trait ToRecordBatch {
+    /// Returns a record batch with one row per item.
+    fn to_record_batch(&self) -> RecordBatch;
+}
+
+/// Column of a record batch: name, values and whether it can hold nulls.
+type Column = (&'static str, ArrayRef, bool);
+
+fn build(columns: Vec<Column>) -> RecordBatch {
+    RecordBatch::try_from_iter_with_nullable(columns)
+        .expect("columns are built from the same items, so their lengths match")
+}
+
+fn timestamps(values: impl IntoIterator<Item = Option<DateTime<Utc>>>) -> ArrayRef {
+    let millis = values
+        .into_iter()
+        .map(|time| time.map(|time| time.timestamp_millis()));
+
+    Arc::new(TimestampMillisecondArray::from_iter(millis).with_timezone("UTC"))
+}
+
+fn strings<S: AsRef<str>>(values: impl IntoIterator<Item = Option<S>>) -> ArrayRef {
+    Arc::new(StringArray::from_iter(values))
+}
+
+fn floats(values: impl IntoIterator<Item = Option<f64>>) -> ArrayRef {
+    Arc::new(Float64Array::from_iter(values))
+}
+
+fn ints(values: impl IntoIterator<Item = i64>) -> ArrayRef {
+    Arc::new(Int64Array::from_iter_values(values))
+}
+
+fn uints(values: impl IntoIterator<Item = u64>) -> ArrayRef {
+    Arc::new(UInt64Array::from_iter_values(values))
+}
+
+fn bools(values: impl IntoIterator<Item = bool>) -> ArrayRef {
+    Arc::new(BooleanArray::from_iter(values.into_iter().map(Some)))
+}
+
+fn side_str(side: TradeSide) -> &'static str {
+    match side {
+        TradeSide::Buy => "buy",
+        TradeSide::Sell => "sell",
+    }
+}
+
+fn type_str(trade_type: TradeExecutionType) -> &'static str {
+    match trade_type {
+        TradeExecutionType::Market => "market",
+        TradeExecutionType::Limit => "limit",
+        TradeExecutionType::Liquidation => "liquidation",
+    }
+}
+
+impl ToRecordBatch for [Trade] {
+    /// Columns: `id`, `type`, `side`, `quantity`, `margin`, `leverage`, `price`, `liquidation`,
+    /// `stoploss`, `takeprofit`, `exit_price`, `pl`, `opening_fee`, `closing_fee`,
+    /// `maintenance_margin`, `sum_funding_fees`, `open`, `running`, `canceled`, `closed`,
+    /// `created_at`, `filled_at`, `closed_at` and `client_id`.
+    fn to_record_batch(&self) -> RecordBatch {
+        let price = |price: Option<Price>| price.map(|price| price.as_f64());
+
+        build(vec![
+            (
+                "id",
+                strings(self.iter().map(|t| Some(t.id().to_string()))),
+                false,
+            ),
+            (
+                "type",
+                strings(self.iter().map(|t| Some(type_str(t.trade_type())))),
+                false,
+            ),
+            (
+                "side",
+                strings(self.iter().map(|t| Some(side_str(t.side())))),
+                false,
+            ),
+            (
+                "quantity",
+                uints(self.iter().map(|t| t.quantity().as_u64())),
+                false,
+            ),
+            (
+                "margin",
+                uints(self.iter().map(|t| t.margin().as_u64())),
+                false,
+            ),
+            (
+                "leverage",
+                floats(self.iter().map(|t| Some(t.leverage().as_f64()))),
+                false,
+            ),
+            (
+                "price",
+                floats(self.iter().map(|t| price(Some(t.price())))),
+                false,
+            ),
+            (
+                "liquidation",
+                floats(self.iter().map(|t| price(Some(t.liquidation())))),
+                false,
+            ),
+            (
+                "stoploss",
+                floats(self.iter().map(|t| price(t.stoploss()))),
+                true,
+            ),
+            (
+                "takeprofit",
+                floats(self.iter().map(|t| price(t.takeprofit()))),
+                true,
+            ),
+            (
+                "exit_price",
+                floats(self.iter().map(|t| price(t.exit_price()))),
+                true,
+            ),
+            ("pl", ints(self.iter().map(|t| t.pl().as_i64())), false),
+            (
+                "opening_fee",
+                ints(self.iter().map(|t| t.opening_fee().as_i64())),
+                false,
+            ),
+            (
+                "closing_fee",
+                ints(self.iter().map(|t| t.closing_fee().as_i64())),
+                false,
+            ),
+            (
+                "maintenance_margin",
+                ints(self.iter().map(|t| t.maintenance_margin().as_i64())),
+                false,
+            ),
+            (
+                "sum_funding_fees",
+                ints(self.iter().map(|t| t.sum_funding_fees().as_i64())),
+                false,
+            ),
+            ("open", bools(self.iter().map(Trade::open)), false),
+            ("running", bools(self.iter().map(Trade::running)), false),
+            ("canceled", bools(self.iter().map(Trade::canceled)), false),
+            ("closed", bools(self.iter().map(Trade::closed)), false),
+            (
+                "created_at",
+                timestamps(self.iter().map(|t| Some(t.created_at()))),
+                false,
+            ),
+            (
+                "filled_at",
+                timestamps(self.iter().map(Trade::filled_at)),
+                true,
+            ),
+            (
+                "closed_at",
+                timestamps(self.iter().map(Trade::closed_at)),
+                true,
+            ),
+            (
+                "client_id",
+                strings(self.iter().map(|t| t.client_id().map(|id| id.as_str()))),
+                true,
+            ),
+        ])
+    }
+}
+
+impl ToRecordBatch for [CrossOrder] {
+    /// Columns: `id`, `type`, `side`, `quantity`, `price`, `trading_fee`, `open`, `filled`,
+    /// `canceled`, `created_at`, `filled_at`, `canceled_at` and `client_id`.
+    fn to_record_batch(&self) -> RecordBatch {
+        build(vec![
+            (
+                "id",
+                strings(self.iter().map(|o| Some(o.id().to_string()))),
+                false,
+            ),
+            (
+                "type",
+                strings(self.iter().map(|o| Some(type_str(o.trade_type())))),
+                false,
+            ),
+            (
+                "side",
+                strings(self.iter().map(|o| Some(side_str(o.side())))),
+                false,
+            ),
+            (
+                "quantity",
+                uints(self.iter().map(|o| o.quantity().as_u64())),
+                false,
+            ),
+            (
+                "price",
+                floats(self.iter().map(|o| Some(o.price().as_f64()))),
+                false,
+            ),
+            (
+                "trading_fee",
+                ints(self.iter().map(|o| o.trading_fee().as_i64())),
+                false,
+            ),
+            ("open", bools(self.iter().map(CrossOrder::open)), false),
+            ("filled", bools(self.iter().map(CrossOrder::filled)), false),
+            (
+                "canceled",
+                bools(self.iter().map(CrossOrder::canceled)),
+                false,
+            ),
+            (
+                "created_at",
+                timestamps(self.iter().map(|o| Some(o.created_at()))),
+                false,
+            ),
+            (
+                "filled_at",
+                timestamps(self.iter().map(CrossOrder::filled_at)),
+                true,
+            ),
+            (
+                "canceled_at",
+                timestamps(self.iter().map(CrossOrder::canceled_at)),
+                true,
+            ),
+            (
+                "client_id",
+                strings(self.iter().map(|o| o.client_id().map(|id| id.as_str()))),
+                true,
+            ),
+        ])
+    }
+}
+
+impl ToRecordBatch for [OhlcCandle] {
+    /// Columns: `time`, `open`, `high`, `low`, `close` and `volume`.
+    fn to_record_batch(&self) -> RecordBatch {
+        build(vec![
+            (
+                "time",
+                timestamps(self.iter().map(|c| Some(c.time()))),
+                false,
+            ),
+            (
+                "open",
+                floats(self.iter().map(|c| Some(c.open().as_f64()))),
+                false,
+            ),
+            (
+                "high",
+                floats(self.iter().map(|c| Some(c.high().as_f64()))),
+                false,
+            ),
+            (
+                "low",
+                floats(self.iter().map(|c| Some(c.low().as_f64()))),
+                false,
+            ),
+            (
+                "close",
+                floats(self.iter().map(|c| Some(c.close().as_f64()))),
+                false,
+            ),
+            ("volume", uints(self.iter().map(OhlcCandle::volume)), false),
+        ])
+    }
+}
+
+impl ToRecordBatch for [IsolatedFunding] {
+    /// Columns: `time`, `settlement_id`, `trade_id` and `fee`.
+    fn to_record_batch(&self) -> RecordBatch {
+        build(vec![
+            (
+                "time",
+                timestamps(self.iter().map(|f| Some(f.time()))),
+                false,
+            ),
+            (
+                "settlement_id",
+                strings(self.iter().map(|f| Some(f.settlement_id().to_string()))),
+                false,
+            ),
+            (
+                "trade_id",
+                strings(self.iter().map(|f| Some(f.trade_id().to_string()))),
+                false,
+            ),
+            ("fee", ints(self.iter().map(|f| f.fee().as_i64())), false),
+        ])
+    }
+}
+
+impl ToRecordBatch for [CrossFunding] {
+    /// Columns: `time`, `settlement_id` and `fee`.
+    fn to_record_batch(&self) -> RecordBatch {
+        build(vec![
+            (
+                "time",
+                timestamps(self.iter().map(|f| Some(f.time()))),
+                false,
+            ),
+            (
+                "settlement_id",
+                strings(self.iter().map(|f| Some(f.settlement_id().to_string()))),
+                false,
+            ),
+            ("fee", ints(self.iter().map(|f| f.fee().as_i64())), false),
+        ])
+    }
+}
+
+impl ToRecordBatch for [FundingSettlement] {
+    /// Columns: `id`, `time`, `fixing_price` and `funding_rate`.
+    fn to_record_batch(&self) -> RecordBatch {
+        build(vec![
+            (
+                "id",
+                strings(self.iter().map(|s| Some(s.id().to_string()))),
+                false,
+            ),
+            (
+                "time",
+                timestamps(self.iter().map(|s| Some(s.time()))),
+                false,
+            ),
+            (
+                "fixing_price",
+                floats(self.iter().map(|s| Some(s.fixing_price()))),
+                false,
+            ),
+            (
+                "funding_rate",
+                floats(self.iter().map(|s| Some(s.funding_rate()))),
+                false,
+            ),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_schema::DataType;
+
+    use super::*;
+    use crate::testing::fixtures;
+
+    #[test]
+    fn test_trades_to_record_batch() {
+        let trades = [fixtures::running_trade(), fixtures::closed_trade()];
+        let batch = trades.to_record_batch();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 24);
+
+        let pl = batch.column_by_name("pl").unwrap();
+        assert_eq!(pl.data_type(), &DataType::Int64);
+        assert_eq!(
+            pl.as_primitive::<arrow_array::types::Int64Type>().value(1),
+            trades[1].pl().as_i64()
+        );
+
+        let closed_at = batch.column_by_name("closed_at").unwrap();
+        assert!(closed_at.is_null(0));
+        assert_eq!(
+            closed_at
+                .as_primitive::<arrow_array::types::TimestampMillisecondType>()
+                .value(1),
+            trades[1].closed_at().unwrap().timestamp_millis()
+        );
+
+        let empty: [Trade; 0] = [];
+        assert_eq!(empty.to_record_batch().schema(), batch.schema());
+    }
+}
@@ -0,0 +1,687 @@
+//! Structured audit records of order actions.
+//!
+//! Once an [`AuditSink`] is attached with [`RestClient::with_audit_sink`], every order
+//! placement, modification, cancellation and close sent through the client's futures
+//! repositories produces an [`AuditRecord`], whether the request succeeds or fails.
+
+use std::{fmt, future::Future, num::NonZeroU64, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::shared::{
+    clock::Clock,
+    models::{
+        client_id::ClientId,
+        cross_leverage::CrossLeverage,
+        leverage::Leverage,
+        price::Price,
+        quantity::order::OrderQuantity,
+        trade::{TradeExecution, TradeSide, TradeSize},
+    },
+    rest::error::Result,
+};
+
+use super::{
+    RestClient,
+    models::{
+        CrossFunding, CrossOrder, CrossPosition, CrossTransfer, IsolatedFunding, Page, Trade,
+    },
+    repositories::{FuturesCrossRepository, FuturesIsolatedRepository},
+};
+
+/// Kind of order action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Place,
+    Modify,
+    Cancel,
+    Close,
+}
+
+/// Futures market an order action targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditMarket {
+    Isolated,
+    Cross,
+}
+
+/// Outcome of an order action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum AuditOutcome {
+    Success,
+    Failure { error: String },
+}
+
+/// Structured record of a single order action.
+///
+/// Serializes to a flat JSON object, suitable for append-only audit logs.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRecord {
+    actor: String,
+    requested_at: DateTime<Utc>,
+    completed_at: DateTime<Utc>,
+    market: AuditMarket,
+    action: AuditAction,
+    operation: &'static str,
+    parameters: Value,
+    order_ids: Vec<Uuid>,
+    outcome: AuditOutcome,
+}
+
+impl AuditRecord {
+    /// Actor the client was attached to the sink with (who).
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+
+    /// Time the action was requested (when).
+    pub fn requested_at(&self) -> DateTime<Utc> {
+        self.requested_at
+    }
+
+    /// Time the response, or failure, was received.
+    pub fn completed_at(&self) -> DateTime<Utc> {
+        self.completed_at
+    }
+
+    /// Market the action targets.
+    pub fn market(&self) -> AuditMarket {
+        self.market
+    }
+
+    /// Kind of action (what).
+    pub fn action(&self) -> AuditAction {
+        self.action
+    }
+
+    /// Name of the repository method that performed the action, e.g. `new_trade`.
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// Request parameters of the action.
+    pub fn parameters(&self) -> &Value {
+        &self.parameters
+    }
+
+    /// IDs of the affected trades or orders. Known beforehand for actions on a given ID, taken
+    /// from the response otherwise.
+    pub fn order_ids(&self) -> &[Uuid] {
+        &self.order_ids
+    }
+
+    /// Outcome of the action (result).
+    pub fn outcome(&self) -> &AuditOutcome {
+        &self.outcome
+    }
+
+    pub fn as_data_str(&self) -> String {
+        let order_ids = self
+            .order_ids
+            .iter()
+            .map(Uuid::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let outcome = match &self.outcome {
+            AuditOutcome::Success => "success".to_string(),
+            AuditOutcome::Failure { error } => format!("failure ({error})"),
+        };
+
+        format!(
+            "actor: {}\nrequested_at: {}\ncompleted_at: {}\nmarket: {:?}\naction: {:?}\noperation: {}\nparameters: {}\norder_ids: [{}]\noutcome: {}",
+            self.actor,
+            self.requested_at.to_rfc3339(),
+            self.completed_at.to_rfc3339(),
+            self.market,
+            self.action,
+            self.operation,
+            self.parameters,
+            order_ids,
+            outcome
+        )
+    }
+}
+
+impl fmt::Display for AuditRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AuditRecord:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Destination of [`AuditRecord`]s.
+///
+/// Called inline, after each order action completes, so implementations should hand records off
+/// quickly (e.g. to a channel or a buffered writer). Implemented for closures.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use lnm_sdk::rest::v3::{RestClient, RestClientConfig, audit::AuditRecord};
+///
+/// let rest = RestClient::new(RestClientConfig::default())
+///     .unwrap()
+///     .with_audit_sink(
+///         "strategy-1",
+///         Arc::new(|record: &AuditRecord| {
+///             println!("{}", serde_json::to_string(record).unwrap())
+///         }),
+///     );
+/// ```
+pub trait AuditSink: Send + Sync {
+    fn record(&self, record: &AuditRecord);
+}
+
+impl<F> AuditSink for F
+where
+    F: Fn(&AuditRecord) + Send + Sync,
+{
+    fn record(&self, record: &AuditRecord) {
+        self(record)
+    }
+}
+
+/// Responses that identify the trades or orders affected by an action.
+trait AuditSubject {
+    fn order_ids(&self) -> Vec<Uuid>;
+}
+
+impl AuditSubject for Trade {
+    fn order_ids(&self) -> Vec<Uuid> {
+        vec![self.id()]
+    }
+}
+
+impl AuditSubject for CrossOrder {
+    fn order_ids(&self) -> Vec<Uuid> {
+        vec![self.id()]
+    }
+}
+
+impl<T: AuditSubject> AuditSubject for Vec<T> {
+    fn order_ids(&self) -> Vec<Uuid> {
+        self.iter().flat_map(AuditSubject::order_ids).collect()
+    }
+}
+
+impl AuditSubject for CrossPosition {
+    fn order_ids(&self) -> Vec<Uuid> {
+        Vec::new()
+    }
+}
+
+fn execution_value(execution: TradeExecution) -> Value {
+    match execution {
+        TradeExecution::Market => json!({ "type": "market" }),
+        TradeExecution::Limit(price) => json!({ "type": "limit", "price": price }),
+    }
+}
+
+#[derive(Clone)]
+struct Auditor {
+    actor: Arc<str>,
+    sink: Arc<dyn AuditSink>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Auditor {
+    async fn audit<T, F>(
+        &self,
+        market: AuditMarket,
+        action: AuditAction,
+        operation: &'static str,
+        id: Option<Uuid>,
+        parameters: Value,
+        request: F,
+    ) -> Result<T>
+    where
+        T: AuditSubject,
+        F: Future<Output = Result<T>>,
+    {
+        let requested_at = self.clock.now();
+        let result = request.await;
+
+        let (order_ids, outcome) = match &result {
+            Ok(response) => (response.order_ids(), AuditOutcome::Success),
+            Err(e) => (
+                id.into_iter().collect(),
+                AuditOutcome::Failure {
+                    error: e.to_string(),
+                },
+            ),
+        };
+
+        self.sink.record(&AuditRecord {
+            actor: self.actor.to_string(),
+            requested_at,
+            completed_at: self.clock.now(),
+            market,
+            action,
+            operation,
+            parameters,
+            order_ids,
+            outcome,
+        });
+
+        result
+    }
+}
+
+struct AuditedFuturesIsolatedRepository {
+    inner: Arc<dyn FuturesIsolatedRepository>,
+    auditor: Auditor,
+}
+
+impl crate::sealed::Sealed for AuditedFuturesIsolatedRepository {}
+
+#[async_trait]
+impl FuturesIsolatedRepository for AuditedFuturesIsolatedRepository {
+    async fn add_margin_to_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade> {
+        self.auditor
+            .audit(
+                AuditMarket::Isolated,
+                AuditAction::Modify,
+                "add_margin_to_trade",
+                Some(id),
+                json!({ "id": id, "amount": amount }),
+                self.inner.add_margin_to_trade(id, amount),
+            )
+            .await
+    }
+
+    async fn cancel_all_trades(&self) -> Result<Vec<Trade>> {
+        self.auditor
+            .audit(
+                AuditMarket::Isolated,
+                AuditAction::Cancel,
+                "cancel_all_trades",
+                None,
+                json!({}),
+                self.inner.cancel_all_trades(),
+            )
+            .await
+    }
+
+    async fn cancel_trade(&self, id: Uuid) -> Result<Trade> {
+        self.auditor
+            .audit(
+                AuditMarket::Isolated,
+                AuditAction::Cancel,
+                "cancel_trade",
+                Some(id),
+                json!({ "id": id }),
+                self.inner.cancel_trade(id),
+            )
+            .await
+    }
+
+    async fn cash_in_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade> {
+        self.auditor
+            .audit(
+                AuditMarket::Isolated,
+                AuditAction::Modify,
+                "cash_in_trade",
+                Some(id),
+                json!({ "id": id, "amount": amount }),
+                self.inner.cash_in_trade(id, amount),
+            )
+            .await
+    }
+
+    async fn close_trade(&self, id: Uuid) -> Result<Trade> {
+        self.auditor
+            .audit(
+                AuditMarket::Isolated,
+                AuditAction::Close,
+                "close_trade",
+                Some(id),
+                json!({ "id": id }),
+                self.inner.close_trade(id),
+            )
+            .await
+    }
+
+    async fn get_open_trades(&self) -> Result<Vec<Trade>> {
+        self.inner.get_open_trades().await
+    }
+
+    async fn get_running_trades(&self) -> Result<Vec<Trade>> {
+        self.inner.get_running_trades().await
+    }
+
+    async fn get_closed_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        self.inner.get_closed_trades(from, to, limit, cursor).await
+    }
+
+    async fn get_canceled_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        self.inner
+            .get_canceled_trades(from, to, limit, cursor)
+            .await
+    }
+
+    async fn update_takeprofit(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        self.auditor
+            .audit(
+                AuditMarket::Isolated,
+                AuditAction::Modify,
+                "update_takeprofit",
+                Some(id),
+                json!({ "id": id, "value": value }),
+                self.inner.update_takeprofit(id, value),
+            )
+            .await
+    }
+
+    async fn update_stoploss(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        self.auditor
+            .audit(
+                AuditMarket::Isolated,
+                AuditAction::Modify,
+                "update_stoploss",
+                Some(id),
+                json!({ "id": id, "value": value }),
+                self.inner.update_stoploss(id, value),
+            )
+            .await
+    }
+
+    async fn update_leverage(
+        &self,
+        trade: &Trade,
+        leverage: Leverage,
+        market_price: Price,
+    ) -> Result<Trade> {
+        self.auditor
+            .audit(
+                AuditMarket::Isolated,
+                AuditAction::Modify,
+                "update_leverage",
+                Some(trade.id()),
+                json!({
+                    "id": trade.id(),
+                    "leverage": leverage,
+                    "market_price": market_price,
+                }),
+                self.inner.update_leverage(trade, leverage, market_price),
+            )
+            .await
+    }
+
+    async fn new_trade(
+        &self,
+        side: TradeSide,
+        size: TradeSize,
+        leverage: Leverage,
+        execution: TradeExecution,
+        stoploss: Option<Price>,
+        takeprofit: Option<Price>,
+        client_id: Option<ClientId>,
+    ) -> Result<Trade> {
+        let parameters = json!({
+            "side": side,
+            "size": size,
+            "leverage": leverage,
+            "execution": execution_value(execution),
+            "stoploss": stoploss,
+            "takeprofit": takeprofit,
+            "client_id": client_id,
+        });
+
+        self.auditor
+            .audit(
+                AuditMarket::Isolated,
+                AuditAction::Place,
+                "new_trade",
+                None,
+                parameters,
+                self.inner.new_trade(
+                    side, size, leverage, execution, stoploss, takeprofit, client_id,
+                ),
+            )
+            .await
+    }
+
+    async fn get_funding_fees(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<IsolatedFunding>> {
+        self.inner.get_funding_fees(from, to, limit, cursor).await
+    }
+}
+
+struct AuditedFuturesCrossRepository {
+    inner: Arc<dyn FuturesCrossRepository>,
+    auditor: Auditor,
+}
+
+impl crate::sealed::Sealed for AuditedFuturesCrossRepository {}
+
+#[async_trait]
+impl FuturesCrossRepository for AuditedFuturesCrossRepository {
+    async fn cancel_all_orders(&self) -> Result<Vec<CrossOrder>> {
+        self.auditor
+            .audit(
+                AuditMarket::Cross,
+                AuditAction::Cancel,
+                "cancel_all_orders",
+                None,
+                json!({}),
+                self.inner.cancel_all_orders(),
+            )
+            .await
+    }
+
+    async fn cancel_order(&self, id: Uuid) -> Result<CrossOrder> {
+        self.auditor
+            .audit(
+                AuditMarket::Cross,
+                AuditAction::Cancel,
+                "cancel_order",
+                Some(id),
+                json!({ "id": id }),
+                self.inner.cancel_order(id),
+            )
+            .await
+    }
+
+    async fn place_order(
+        &self,
+        side: TradeSide,
+        quantity: OrderQuantity,
+        execution: TradeExecution,
+        client_id: Option<ClientId>,
+    ) -> Result<CrossOrder> {
+        let parameters = json!({
+            "side": side,
+            "quantity": quantity,
+            "execution": execution_value(execution),
+            "client_id": client_id,
+        });
+
+        self.auditor
+            .audit(
+                AuditMarket::Cross,
+                AuditAction::Place,
+                "place_order",
+                None,
+                parameters,
+                self.inner.place_order(side, quantity, execution, client_id),
+            )
+            .await
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<CrossOrder>> {
+        self.inner.get_open_orders().await
+    }
+
+    async fn get_position(&self) -> Result<CrossPosition> {
+        self.inner.get_position().await
+    }
+
+    async fn get_filled_orders(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<CrossOrder>> {
+        self.inner.get_filled_orders(from, to, limit, cursor).await
+    }
+
+    async fn close_position(&self) -> Result<CrossOrder> {
+        self.auditor
+            .audit(
+                AuditMarket::Cross,
+                AuditAction::Close,
+                "close_position",
+                None,
+                json!({}),
+                self.inner.close_position(),
+            )
+            .await
+    }
+
+    async fn get_funding_fees(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<CrossFunding>> {
+        self.inner.get_funding_fees(from, to, limit, cursor).await
+    }
+
+    async fn get_transfers(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<CrossTransfer>> {
+        self.inner.get_transfers(from, to, limit, cursor).await
+    }
+
+    async fn deposit(&self, amount: NonZeroU64) -> Result<CrossPosition> {
+        self.inner.deposit(amount).await
+    }
+
+    async fn set_leverage(&self, leverage: CrossLeverage) -> Result<CrossPosition> {
+        self.auditor
+            .audit(
+                AuditMarket::Cross,
+                AuditAction::Modify,
+                "set_leverage",
+                None,
+                json!({ "leverage": leverage }),
+                self.inner.set_leverage(leverage),
+            )
+            .await
+    }
+
+    async fn withdraw(&self, amount: NonZeroU64) -> Result<CrossPosition> {
+        self.inner.withdraw(amount).await
+    }
+}
+
+pub(super) fn attach(rest: &mut RestClient, actor: Arc<str>, sink: Arc<dyn AuditSink>) {
+    let auditor = Auditor {
+        actor,
+        sink,
+        clock: rest.clock().clone(),
+    };
+
+    rest.futures_isolated = Arc::new(AuditedFuturesIsolatedRepository {
+        inner: rest.futures_isolated.clone(),
+        auditor: auditor.clone(),
+    });
+    rest.futures_cross = Arc::new(AuditedFuturesCrossRepository {
+        inner: rest.futures_cross.clone(),
+        auditor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::rest::v3::RestClientConfig;
+
+    #[tokio::test]
+    async fn test_failed_actions_are_recorded() {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink_records = records.clone();
+
+        // No credentials, so requests fail before being sent
+        let rest = RestClient::new(RestClientConfig::default().with_rate_limiter_active(false))
+            .unwrap()
+            .with_audit_sink(
+                "tester",
+                Arc::new(move |record: &AuditRecord| {
+                    sink_records.lock().unwrap().push(record.clone())
+                }),
+            );
+
+        let id = Uuid::from_u128(7);
+        assert!(rest.futures_isolated.cancel_trade(id).await.is_err());
+        assert!(
+            rest.futures_cross
+                .place_order(
+                    TradeSide::Buy,
+                    OrderQuantity::try_from(1).unwrap(),
+                    TradeExecution::Market,
+                    None,
+                )
+                .await
+                .is_err()
+        );
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+
+        let cancel = &records[0];
+        assert_eq!(cancel.actor(), "tester");
+        assert_eq!(cancel.market(), AuditMarket::Isolated);
+        assert_eq!(cancel.action(), AuditAction::Cancel);
+        assert_eq!(cancel.operation(), "cancel_trade");
+        assert_eq!(cancel.order_ids(), &[id]);
+        assert!(matches!(cancel.outcome(), AuditOutcome::Failure { .. }));
+
+        let place = &records[1];
+        assert_eq!(place.action(), AuditAction::Place);
+        assert_eq!(place.parameters()["side"], "buy");
+        assert_eq!(place.parameters()["execution"]["type"], "market");
+        assert!(place.order_ids().is_empty());
+
+        let json = serde_json::to_value(place).unwrap();
+        assert_eq!(json["outcome"]["status"], "failure");
+        assert_eq!(json["market"], "cross");
+    }
+}
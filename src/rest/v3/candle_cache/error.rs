@@ -0,0 +1,34 @@
+use std::{io, path::PathBuf, result};
+
+use thiserror::Error;
+
+use crate::shared::rest::error::RestApiError;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum CandleCacheError {
+    #[error("Candle cache IO error. Path: {path}, error: {e}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        e: io::Error,
+    },
+
+    #[error("Candle series serialization failed. Error: {0}")]
+    SeriesSerialize(#[source] serde_json::Error),
+
+    #[error("Candle series at {path} could not be parsed. Error: {e}")]
+    SeriesParse {
+        path: PathBuf,
+        #[source]
+        e: serde_json::Error,
+    },
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    #[error(transparent)]
+    RestApi(#[from] RestApiError),
+}
+
+pub type Result<T> = result::Result<T, CandleCacheError>;
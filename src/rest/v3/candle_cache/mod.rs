@@ -0,0 +1,339 @@
+//! Local cache of candle history, so repeated backtests don't re-download identical data.
+//!
+//! [`CandleCache`] keeps, for each [`OhlcRange`], the candles fetched so far along with the time
+//! intervals they fully cover. Range queries only hit the API for the parts of the requested
+//! interval that are not covered yet. Persistence is delegated to a [`CandleStore`].
+
+use std::{collections::BTreeMap, num::NonZeroU64};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::models::ohlc::{OhlcCandle, OhlcRange};
+
+use super::RestClient;
+
+mod error;
+mod storage;
+
+pub use error::{CandleCacheError, Result};
+pub use storage::{CandleStore, FileCandleStore, MemoryCandleStore, StateStoreCandleStore};
+
+const FETCH_LIMIT: NonZeroU64 = NonZeroU64::new(1000).expect("must be non-zero");
+
+/// Upper bound of the duration of a candle of the given range.
+fn max_candle_duration(range: OhlcRange) -> Duration {
+    match range {
+        OhlcRange::OneMinute => Duration::minutes(1),
+        OhlcRange::ThreeMinutes => Duration::minutes(3),
+        OhlcRange::FiveMinutes => Duration::minutes(5),
+        OhlcRange::TenMinutes => Duration::minutes(10),
+        OhlcRange::FifteenMinutes => Duration::minutes(15),
+        OhlcRange::ThirtyMinutes => Duration::minutes(30),
+        OhlcRange::FortyFiveMinutes => Duration::minutes(45),
+        OhlcRange::OneHour => Duration::hours(1),
+        OhlcRange::TwoHours => Duration::hours(2),
+        OhlcRange::ThreeHours => Duration::hours(3),
+        OhlcRange::FourHours => Duration::hours(4),
+        OhlcRange::OneDay => Duration::days(1),
+        OhlcRange::OneWeek => Duration::weeks(1),
+        OhlcRange::OneMonth => Duration::days(31),
+        OhlcRange::ThreeMonths => Duration::days(92),
+    }
+}
+
+/// Candles of a single [`OhlcRange`], with the half-open time intervals they fully cover.
+///
+/// Within a covered interval, every candle returned by the API is present, so the absence of a
+/// candle reflects a gap in the exchange's history rather than missing data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CandleSeries {
+    covered: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    candles: Vec<OhlcCandle>,
+}
+
+impl CandleSeries {
+    /// Covered `[from, to)` intervals, sorted and disjoint.
+    pub fn covered(&self) -> &[(DateTime<Utc>, DateTime<Utc>)] {
+        &self.covered
+    }
+
+    /// All cached candles, sorted by time.
+    pub fn candles(&self) -> &[OhlcCandle] {
+        &self.candles
+    }
+
+    /// Cached candles with `from <= time < to`, sorted by time.
+    pub fn candles_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> &[OhlcCandle] {
+        let start = self.candles.partition_point(|candle| candle.time() < from);
+        let end = self.candles.partition_point(|candle| candle.time() < to);
+
+        &self.candles[start..end.max(start)]
+    }
+
+    /// Parts of `[from, to)` that are not covered yet, sorted.
+    pub fn missing(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let mut missing = Vec::new();
+        let mut cursor = from;
+
+        for &(start, end) in &self.covered {
+            if cursor >= to {
+                break;
+            }
+            if end <= cursor {
+                continue;
+            }
+            if start > cursor {
+                missing.push((cursor, start.min(to)));
+            }
+            cursor = cursor.max(end);
+        }
+
+        if cursor < to {
+            missing.push((cursor, to));
+        }
+
+        missing
+    }
+
+    /// Adds `candles`, replacing cached candles with the same time, and marks `[from, to)` as
+    /// covered.
+    fn insert(&mut self, from: DateTime<Utc>, to: DateTime<Utc>, candles: Vec<OhlcCandle>) {
+        let mut by_time: BTreeMap<_, _> = self
+            .candles
+            .drain(..)
+            .map(|candle| (candle.time(), candle))
+            .collect();
+        by_time.extend(candles.into_iter().map(|candle| (candle.time(), candle)));
+        self.candles = by_time.into_values().collect();
+
+        if from >= to {
+            return;
+        }
+
+        self.covered.push((from, to));
+        self.covered.sort_by_key(|(start, _)| *start);
+
+        let mut merged: Vec<(DateTime<Utc>, DateTime<Utc>)> =
+            Vec::with_capacity(self.covered.len());
+        for (start, end) in self.covered.drain(..) {
+            match merged.last_mut() {
+                Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        self.covered = merged;
+    }
+}
+
+/// Read-through cache of candle history, backed by a pluggable [`CandleStore`].
+///
+/// Intervals are only marked as covered up to the last complete candle, so candles still being
+/// formed are fetched again on the next query.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use chrono::{Duration, Utc};
+/// use lnm_sdk::rest::v3::{
+///     candle_cache::{CandleCache, FileCandleStore},
+///     models::OhlcRange,
+/// };
+///
+/// let cache = CandleCache::new(FileCandleStore::new(".candles"));
+/// let to = Utc::now();
+/// let from = to - Duration::days(30);
+///
+/// // Only the first call downloads the history
+/// let candles = cache.get_candles(&rest, OhlcRange::OneHour, from, to).await?;
+/// let candles = cache.get_candles(&rest, OhlcRange::OneHour, from, to).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CandleCache<S: CandleStore> {
+    store: S,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl<S: CandleStore> CandleCache<S> {
+    /// Creates a cache persisted to `store`.
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the cached candles of `range` with `from <= time < to`, without making requests.
+    pub async fn cached(
+        &self,
+        range: OhlcRange,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<OhlcCandle>> {
+        let series = self.store.load(range).await?;
+        Ok(series.candles_between(from, to).to_vec())
+    }
+
+    /// Returns the candles of `range` with `from <= time < to`, fetching the intervals that are
+    /// not cached yet from the API and storing them.
+    pub async fn get_candles(
+        &self,
+        rest: &RestClient,
+        range: OhlcRange,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<OhlcCandle>> {
+        let _guard = self.lock.lock().await;
+
+        let mut series = self.store.load(range).await?;
+        let missing = series.missing(from, to);
+
+        if !missing.is_empty() {
+            let complete_before = rest.clock().now() - max_candle_duration(range);
+
+            for (gap_from, gap_to) in missing {
+                let candles = fetch_candles(rest, range, gap_from, gap_to).await?;
+                series.insert(gap_from, gap_to.min(complete_before), candles);
+            }
+
+            self.store.save(range, &series).await?;
+        }
+
+        Ok(series.candles_between(from, to).to_vec())
+    }
+}
+
+async fn fetch_candles(
+    rest: &RestClient,
+    range: OhlcRange,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<OhlcCandle>> {
+    let mut candles = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let page = rest
+            .futures_data
+            .get_candles(Some(from), Some(to), Some(FETCH_LIMIT), Some(range), cursor)
+            .await?;
+        let next_cursor = page.next_cursor();
+        let data = Vec::from(page);
+
+        if data.is_empty() {
+            break;
+        }
+        candles.extend(
+            data.into_iter()
+                .filter(|candle| candle.time() >= from && candle.time() < to),
+        );
+
+        match next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    Ok(candles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn candle(t: &str) -> OhlcCandle {
+        serde_json::from_value(serde_json::json!({
+            "time": t,
+            "open": 100_000,
+            "high": 101_000,
+            "low": 99_000,
+            "close": 100_500,
+            "volume": 10,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_series_missing_and_insert() {
+        let mut series = CandleSeries::default();
+        let t0 = time("2025-05-01T00:00:00Z");
+        let t1 = time("2025-05-01T01:00:00Z");
+        let t2 = time("2025-05-01T02:00:00Z");
+        let t3 = time("2025-05-01T03:00:00Z");
+
+        assert_eq!(series.missing(t0, t3), vec![(t0, t3)]);
+
+        series.insert(t1, t2, vec![candle("2025-05-01T01:00:00Z")]);
+        assert_eq!(series.missing(t0, t3), vec![(t0, t1), (t2, t3)]);
+        assert!(series.missing(t1, t2).is_empty());
+
+        series.insert(t0, t1, vec![candle("2025-05-01T00:00:00Z")]);
+        series.insert(t2, t3, vec![candle("2025-05-01T02:00:00Z")]);
+        assert_eq!(series.covered(), &[(t0, t3)]);
+        assert!(series.missing(t0, t3).is_empty());
+
+        assert_eq!(series.candles_between(t0, t3).len(), 3);
+        assert_eq!(series.candles_between(t1, t2).len(), 1);
+        assert!(series.candles_between(t2, t1).is_empty());
+    }
+
+    #[test]
+    fn test_series_insert_replaces_same_time() {
+        let mut series = CandleSeries::default();
+        let t0 = time("2025-05-01T00:00:00Z");
+        let t1 = time("2025-05-01T01:00:00Z");
+
+        series.insert(t0, t0, vec![candle("2025-05-01T00:00:00Z")]);
+        series.insert(t0, t1, vec![candle("2025-05-01T00:00:00Z")]);
+
+        assert_eq!(series.candles().len(), 1);
+        assert_eq!(series.covered(), &[(t0, t1)]);
+    }
+
+    #[tokio::test]
+    async fn test_file_store_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("lnm-candle-cache-{}", uuid::Uuid::new_v4()));
+        let store = FileCandleStore::new(&dir);
+
+        assert_eq!(
+            store.load(OhlcRange::OneHour).await.unwrap(),
+            CandleSeries::default()
+        );
+
+        let mut series = CandleSeries::default();
+        series.insert(
+            time("2025-05-01T00:00:00Z"),
+            time("2025-05-01T01:00:00Z"),
+            vec![candle("2025-05-01T00:00:00Z")],
+        );
+        store.save(OhlcRange::OneHour, &series).await.unwrap();
+
+        assert_eq!(store.load(OhlcRange::OneHour).await.unwrap(), series);
+        assert!(
+            store
+                .load(OhlcRange::OneDay)
+                .await
+                .unwrap()
+                .candles()
+                .is_empty()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
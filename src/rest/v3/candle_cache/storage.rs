@@ -0,0 +1,211 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use tokio::fs;
+
+use crate::{rest::v3::state_store::StateStore, shared::models::ohlc::OhlcRange};
+
+use super::{
+    CandleSeries,
+    error::{CandleCacheError, Result},
+};
+
+/// Persistent storage for [`CandleCache`](super::CandleCache) series, one per [`OhlcRange`].
+#[async_trait]
+pub trait CandleStore: Send + Sync {
+    /// Loads the series stored for `range`, or an empty series if there is none.
+    async fn load(&self, range: OhlcRange) -> Result<CandleSeries>;
+
+    /// Replaces the series stored for `range`.
+    async fn save(&self, range: OhlcRange, series: &CandleSeries) -> Result<()>;
+}
+
+/// [`CandleStore`] backed by a directory, with one JSON file per [`OhlcRange`].
+///
+/// Files are written to a temporary path and renamed, so an interrupted write never leaves a
+/// partial series behind.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lnm_sdk::rest::v3::candle_cache::{CandleCache, FileCandleStore};
+///
+/// let cache = CandleCache::new(FileCandleStore::new(".candles"));
+/// ```
+#[derive(Debug)]
+pub struct FileCandleStore {
+    dir: PathBuf,
+}
+
+impl FileCandleStore {
+    /// Creates a store using the directory at `dir`. The directory is created on the first save.
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Directory of the stored series.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn series_path(&self, range: OhlcRange) -> PathBuf {
+        self.dir.join(format!("candles-{range}.json"))
+    }
+}
+
+#[async_trait]
+impl CandleStore for FileCandleStore {
+    async fn load(&self, range: OhlcRange) -> Result<CandleSeries> {
+        let path = self.series_path(range);
+
+        let content = match fs::read(&path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(CandleSeries::default());
+            }
+            Err(e) => return Err(CandleCacheError::Io { path, e }),
+        };
+
+        serde_json::from_slice(&content).map_err(|e| CandleCacheError::SeriesParse { path, e })
+    }
+
+    async fn save(&self, range: OhlcRange, series: &CandleSeries) -> Result<()> {
+        let content = serde_json::to_vec(series).map_err(CandleCacheError::SeriesSerialize)?;
+
+        fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| CandleCacheError::Io {
+                path: self.dir.clone(),
+                e,
+            })?;
+
+        let path = self.series_path(range);
+        let tmp_path = path.with_extension("json.tmp");
+
+        fs::write(&tmp_path, content)
+            .await
+            .map_err(|e| CandleCacheError::Io {
+                path: tmp_path.clone(),
+                e,
+            })?;
+
+        fs::rename(&tmp_path, &path)
+            .await
+            .map_err(|e| CandleCacheError::Io { path, e })
+    }
+}
+
+/// In-memory [`CandleStore`], not persisted across restarts. Useful for tests.
+#[derive(Debug, Default)]
+pub struct MemoryCandleStore {
+    series: Mutex<HashMap<OhlcRange, CandleSeries>>,
+}
+
+impl MemoryCandleStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CandleStore for MemoryCandleStore {
+    async fn load(&self, range: OhlcRange) -> Result<CandleSeries> {
+        Ok(self
+            .series
+            .lock()
+            .expect("`series` mutex can't be poisoned")
+            .get(&range)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn save(&self, range: OhlcRange, series: &CandleSeries) -> Result<()> {
+        self.series
+            .lock()
+            .expect("`series` mutex can't be poisoned")
+            .insert(range, series.clone());
+        Ok(())
+    }
+}
+
+/// [`CandleStore`] backed by a [`StateStore`], with one key per [`OhlcRange`].
+///
+/// Series are stored as JSON at `{prefix}{range}`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lnm_sdk::rest::v3::{
+///     candle_cache::{CandleCache, StateStoreCandleStore},
+///     state_store::FileStateStore,
+/// };
+///
+/// let cache = CandleCache::new(StateStoreCandleStore::new(
+///     FileStateStore::new(".state"),
+///     "candles/",
+/// ));
+/// ```
+#[derive(Debug)]
+pub struct StateStoreCandleStore<S: StateStore> {
+    store: S,
+    prefix: String,
+}
+
+impl<S: StateStore> StateStoreCandleStore<S> {
+    /// Creates a store keeping series in `store`, under keys starting with `prefix`.
+    pub fn new(store: S, prefix: impl Into<String>) -> Self {
+        Self {
+            store,
+            prefix: prefix.into(),
+        }
+    }
+
+    /// Returns the underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn series_key(&self, range: OhlcRange) -> String {
+        format!("{}{range}", self.prefix)
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> CandleStore for StateStoreCandleStore<S> {
+    async fn load(&self, range: OhlcRange) -> Result<CandleSeries> {
+        let key = self.series_key(range);
+
+        let Some(value) = self
+            .store
+            .get(&key)
+            .await
+            .map_err(|e| CandleCacheError::Storage(e.to_string()))?
+        else {
+            return Ok(CandleSeries::default());
+        };
+
+        serde_json::from_slice(&value).map_err(|e| {
+            CandleCacheError::Storage(format!("series {key} could not be parsed: {e}"))
+        })
+    }
+
+    async fn save(&self, range: OhlcRange, series: &CandleSeries) -> Result<()> {
+        let value = serde_json::to_vec(series).map_err(CandleCacheError::SeriesSerialize)?;
+
+        self.store
+            .put(&self.series_key(range), &value)
+            .await
+            .map_err(|e| CandleCacheError::Storage(e.to_string()))
+    }
+}
@@ -1,6 +1,12 @@
-use std::{num::NonZero, time::Duration};
+use std::{collections::BTreeSet, num::NonZero, sync::Arc, time::Duration};
 
-use crate::shared::rest::lnm::rate_limit::RateLimiterConfig;
+use crate::shared::{
+    clock::{Clock, SystemClock},
+    models::{network::BitcoinNetwork, rounding::RoundingPolicy},
+    rest::lnm::{rate_limit::RateLimiterConfig, scope::ApiScope},
+};
+
+use super::models::limits::ExchangeLimits;
 
 /// Configuration for the v3 REST API client.
 ///
@@ -8,10 +14,20 @@ use crate::shared::rest::lnm::rate_limit::RateLimiterConfig;
 #[derive(Clone, Debug)]
 pub struct RestClientConfig {
     endpoint: String,
+    network: BitcoinNetwork,
+    exchange_limits: ExchangeLimits,
     timeout: Duration,
     rate_limiter_active: bool,
     rate_limit_auth_requests_per_second: u32,
     rate_limit_unauth_requests_per_second: u32,
+    debug_logging: bool,
+    maintenance_pause: bool,
+    maintenance_probe_interval: Duration,
+    stale_price_threshold: Option<Duration>,
+    expected_api_version: Option<String>,
+    clock: Arc<dyn Clock>,
+    api_scopes: Option<BTreeSet<ApiScope>>,
+    rounding_policy: RoundingPolicy,
 }
 
 impl RestClientConfig {
@@ -52,6 +68,16 @@ impl RestClientConfig {
         &self.endpoint
     }
 
+    /// Returns the Bitcoin network of the LN Markets environment targeted by the endpoint.
+    pub fn network(&self) -> BitcoinNetwork {
+        self.network
+    }
+
+    /// Returns the exchange limits requests are validated against.
+    pub fn exchange_limits(&self) -> &ExchangeLimits {
+        &self.exchange_limits
+    }
+
     /// Returns the request timeout duration.
     pub fn timeout(&self) -> Duration {
         self.timeout
@@ -76,6 +102,49 @@ impl RestClientConfig {
         self.rate_limit_unauth_requests_per_second
     }
 
+    /// Returns whether redacted request/response debug logging is enabled.
+    pub fn debug_logging(&self) -> bool {
+        self.debug_logging
+    }
+
+    /// Returns whether order submissions are paused while the exchange is in maintenance.
+    pub fn maintenance_pause(&self) -> bool {
+        self.maintenance_pause
+    }
+
+    /// Returns the minimum interval between health probes while order submissions are paused.
+    ///
+    /// Only used when [`maintenance_pause`](Self::maintenance_pause) is `true`.
+    pub fn maintenance_probe_interval(&self) -> Duration {
+        self.maintenance_probe_interval
+    }
+
+    /// Returns the maximum age of the latest observed price before market orders are rejected, if
+    /// the stale price guard is enabled.
+    pub fn stale_price_threshold(&self) -> Option<Duration> {
+        self.stale_price_threshold
+    }
+
+    /// Returns the API version the client is pinned to, if any.
+    pub fn expected_api_version(&self) -> Option<&str> {
+        self.expected_api_version.as_deref()
+    }
+
+    /// Returns the clock the client reads the current time from.
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Returns the scopes granted to the API key, if declared.
+    pub fn api_scopes(&self) -> Option<&BTreeSet<ApiScope>> {
+        self.api_scopes.as_ref()
+    }
+
+    /// Returns the rounding policy used by the client's calculations.
+    pub fn rounding_policy(&self) -> RoundingPolicy {
+        self.rounding_policy
+    }
+
     /// Sets the REST API endpoint.
     ///
     /// Default: `https://api.lnmarkets.com/v3`
@@ -84,6 +153,25 @@ impl RestClientConfig {
         self
     }
 
+    /// Sets the Bitcoin network of the LN Markets environment targeted by the endpoint.
+    ///
+    /// Used to reject invoices and addresses for a different network locally, before any request
+    /// is sent. Must be set accordingly when pointing the client to a non-mainnet endpoint.
+    ///
+    /// Default: [`BitcoinNetwork::Mainnet`]
+    pub fn with_network(mut self, network: BitcoinNetwork) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Sets the exchange limits requests are validated against, before being sent.
+    ///
+    /// Default: [`ExchangeLimits::default`]
+    pub fn with_exchange_limits(mut self, exchange_limits: ExchangeLimits) -> Self {
+        self.exchange_limits = exchange_limits;
+        self
+    }
+
     /// Sets the request timeout duration.
     ///
     /// Default: `20` seconds
@@ -119,6 +207,116 @@ impl RestClientConfig {
         self.rate_limit_unauth_requests_per_second = rps.get();
         self
     }
+
+    /// Enables or disables redacted request/response debug logging.
+    ///
+    /// When enabled, full request and response bodies are emitted via the [`log`] crate, at the
+    /// `debug` level under the `lnm_sdk::rest` target. API keys, passphrases, signatures and
+    /// Lightning invoices are redacted. Logging can also be toggled at runtime via
+    /// [`RestClient::set_debug_logging`](super::RestClient::set_debug_logging).
+    ///
+    /// Default: `false`
+    pub fn with_debug_logging(mut self, enabled: bool) -> Self {
+        self.debug_logging = enabled;
+        self
+    }
+
+    /// Enables or disables pausing order submissions while the exchange is in maintenance.
+    ///
+    /// When enabled, a response signaling maintenance (see
+    /// [`RestApiError::is_maintenance`](super::error::RestApiError::is_maintenance)) pauses the
+    /// client. While paused, new trades and cross orders are rejected locally with
+    /// [`RestApiError::ExchangeInMaintenance`](super::error::RestApiError::ExchangeInMaintenance),
+    /// and the API is pinged at most once per
+    /// [`maintenance_probe_interval`](Self::maintenance_probe_interval). The client resumes
+    /// automatically on the first successful response.
+    ///
+    /// Default: `false`
+    pub fn with_maintenance_pause(mut self, enabled: bool) -> Self {
+        self.maintenance_pause = enabled;
+        self
+    }
+
+    /// Enables rejecting market orders locally when the latest observed price is older than
+    /// `threshold`.
+    ///
+    /// Prices (or feed heartbeats) are reported to the client with
+    /// [`RestClient::observe_price`](super::RestClient::observe_price), usually from a Stream
+    /// subscription. Once enabled, new isolated trades and cross orders with market execution are
+    /// rejected with [`RestApiError::StalePrice`](super::error::RestApiError::StalePrice) until a
+    /// price is observed, and whenever the latest one is older than `threshold`. This prevents
+    /// trading on frozen data after a silent feed failure. Limit orders are not affected.
+    ///
+    /// Default: `None`
+    pub fn with_stale_price_threshold(mut self, threshold: Duration) -> Self {
+        self.stale_price_threshold = Some(threshold);
+        self
+    }
+
+    /// Pins the API version the client expects.
+    ///
+    /// Responses reporting a different version in their `API-Version` or `X-API-Version` header
+    /// raise an [`ApiNotice::VersionMismatch`](super::ApiNotice::VersionMismatch), passed to the
+    /// handler set with [`RestClient::set_api_notice_handler`](super::RestClient::set_api_notice_handler),
+    /// or logged as a warning.
+    ///
+    /// Default: `None`
+    pub fn with_expected_api_version(mut self, version: impl ToString) -> Self {
+        self.expected_api_version = Some(version.to_string());
+        self
+    }
+
+    /// Sets the minimum interval between health probes while order submissions are paused.
+    ///
+    /// Only used when [`maintenance_pause`](Self::maintenance_pause) is `true`.
+    ///
+    /// Default: `10` seconds
+    pub fn with_maintenance_probe_interval(mut self, interval: Duration) -> Self {
+        self.maintenance_probe_interval = interval;
+        self
+    }
+
+    /// Sets the clock the client reads the current time from.
+    ///
+    /// Used for request signing timestamps, `Retry-After` dates, maintenance probes, and the
+    /// timestamps of composite helpers such as [`RestClient::status`](super::RestClient::status).
+    /// Mainly useful to test time-dependent logic deterministically.
+    ///
+    /// Default: [`SystemClock`]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Declares the scopes granted to the API key.
+    ///
+    /// Authenticated requests to endpoints whose required scope is not declared are rejected
+    /// locally with [`RestApiError::MissingScope`](super::error::RestApiError::MissingScope),
+    /// before being sent. Scopes can also be set at runtime via
+    /// [`RestClient::set_api_scopes`](super::RestClient::set_api_scopes).
+    ///
+    /// Default: `None`, no scope checks are performed
+    pub fn with_api_scopes(mut self, scopes: impl IntoIterator<Item = ApiScope>) -> Self {
+        self.api_scopes = Some(scopes.into_iter().collect());
+        self
+    }
+
+    /// Sets the rounding policy used by the client's calculations, namely the collateral
+    /// adjustments of
+    /// [`FuturesIsolatedRepository::update_leverage`](super::FuturesIsolatedRepository::update_leverage).
+    ///
+    /// Standalone calculators, such as [`Margin::calculate`](super::models::Margin::calculate)
+    /// and [`evaluate_order_fee`](super::models::trade_util::evaluate_order_fee), always round as the
+    /// exchange does. Estimates with the configured policy are available through
+    /// [`RestClient::rounding_policy`](super::RestClient::rounding_policy), and the `_rounded`
+    /// variants of the calculators, such as
+    /// [`evaluate_open_trade_params_rounded`](super::models::trade_util::evaluate_open_trade_params_rounded).
+    ///
+    /// Default: [`RoundingPolicy::EXCHANGE`]
+    pub fn with_rounding_policy(mut self, rounding_policy: RoundingPolicy) -> Self {
+        self.rounding_policy = rounding_policy;
+        self
+    }
 }
 
 impl RateLimiterConfig for RestClientConfig {
@@ -135,10 +333,20 @@ impl Default for RestClientConfig {
     fn default() -> Self {
         Self {
             endpoint: "https://api.lnmarkets.com/v3".to_string(),
+            network: BitcoinNetwork::Mainnet,
+            exchange_limits: ExchangeLimits::default(),
             timeout: Duration::from_secs(20),
             rate_limiter_active: true,
             rate_limit_auth_requests_per_second: 5,
             rate_limit_unauth_requests_per_second: 1,
+            debug_logging: false,
+            maintenance_pause: false,
+            maintenance_probe_interval: Duration::from_secs(10),
+            stale_price_threshold: None,
+            expected_api_version: None,
+            clock: Arc::new(SystemClock),
+            api_scopes: None,
+            rounding_policy: RoundingPolicy::EXCHANGE,
         }
     }
 }
@@ -0,0 +1,436 @@
+//! Dead man's switch canceling orders when the process or its Stream connection goes silent.
+//!
+//! A [`DeadManSwitch`] expects regular [heartbeats](DeadManSwitch::heartbeat) from the process,
+//! and can follow the status of a Stream connection. If no heartbeat is received for longer than
+//! the heartbeat timeout, or the connection stays down for longer than the disconnect timeout,
+//! the switch trips: all open isolated trades and cross orders are canceled through the REST API,
+//! and positions are optionally flattened. This protects unattended bots from leaving orders on
+//! the book after hanging or losing market data.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{
+    sync::broadcast::{Receiver, error::RecvError},
+    task::JoinHandle,
+    time::{self, Instant},
+};
+
+use crate::{
+    shared::rest::error::RestApiError,
+    stream::v1::{StreamConnectionStatus, models::StreamUpdate},
+};
+
+use super::{
+    LnmFuturesApi,
+    models::{CrossOrder, Trade},
+};
+
+/// Reason a [`DeadManSwitch`] tripped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TripReason {
+    /// No heartbeat was received within the heartbeat timeout.
+    HeartbeatTimeout,
+    /// The Stream connection stayed down for longer than the disconnect timeout.
+    StreamDisconnected,
+}
+
+impl TripReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HeartbeatTimeout => "heartbeat timeout",
+            Self::StreamDisconnected => "stream disconnected",
+        }
+    }
+}
+
+impl fmt::Display for TripReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Outcome of [`DeadManSwitch::trip`].
+///
+/// Every action is attempted even if earlier ones fail, so failures are collected instead of
+/// returned.
+#[derive(Debug, Default)]
+pub struct TripReport {
+    canceled_trades: Vec<Trade>,
+    canceled_orders: Vec<CrossOrder>,
+    closed_trades: Vec<Trade>,
+    closed_position: Option<CrossOrder>,
+    errors: Vec<RestApiError>,
+}
+
+impl TripReport {
+    /// Open isolated trades canceled.
+    pub fn canceled_trades(&self) -> &[Trade] {
+        &self.canceled_trades
+    }
+
+    /// Open cross orders canceled.
+    pub fn canceled_orders(&self) -> &[CrossOrder] {
+        &self.canceled_orders
+    }
+
+    /// Running isolated trades closed, if flattening is enabled.
+    pub fn closed_trades(&self) -> &[Trade] {
+        &self.closed_trades
+    }
+
+    /// Order closing the cross position, if flattening is enabled and a position was open.
+    pub fn closed_position(&self) -> Option<&CrossOrder> {
+        self.closed_position.as_ref()
+    }
+
+    /// Errors returned by the API while tripping.
+    pub fn errors(&self) -> &[RestApiError] {
+        &self.errors
+    }
+
+    /// Returns `true` if every action succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+#[derive(Debug)]
+struct SwitchState {
+    last_heartbeat: Instant,
+    disconnected_since: Option<Instant>,
+    tripped: bool,
+}
+
+/// Watchdog canceling all open orders, and optionally flattening positions, when the process stops
+/// heartbeating or its Stream connection stays down.
+///
+/// Once tripped, the switch doesn't trip again until re-armed by the next heartbeat.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(
+/// #     rest: std::sync::Arc<lnm_sdk::rest::v3::RestClient>,
+/// #     conn: lnm_sdk::stream::v1::StreamConnection,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// use std::{sync::Arc, time::Duration};
+///
+/// use lnm_sdk::rest::v3::dead_man_switch::DeadManSwitch;
+///
+/// let switch = Arc::new(
+///     DeadManSwitch::new(rest, Duration::from_secs(30))
+///         .with_disconnect_timeout(Duration::from_secs(10))
+///         .with_flatten(true),
+/// );
+///
+/// let _monitor = switch.spawn_stream_monitor(conn.receiver().await?);
+/// let _watchdog = switch.spawn(Duration::from_secs(1));
+///
+/// loop {
+///     // Trading logic...
+///     switch.heartbeat();
+///     tokio::time::sleep(Duration::from_secs(5)).await;
+/// }
+/// # }
+/// ```
+pub struct DeadManSwitch {
+    api: Arc<dyn LnmFuturesApi>,
+    heartbeat_timeout: Duration,
+    disconnect_timeout: Option<Duration>,
+    flatten: bool,
+    state: Mutex<SwitchState>,
+}
+
+impl DeadManSwitch {
+    /// Creates a switch tripping if no heartbeat is received for `heartbeat_timeout`. The
+    /// creation counts as the first heartbeat.
+    pub fn new(api: Arc<dyn LnmFuturesApi>, heartbeat_timeout: Duration) -> Self {
+        Self {
+            api,
+            heartbeat_timeout,
+            disconnect_timeout: None,
+            flatten: false,
+            state: Mutex::new(SwitchState {
+                last_heartbeat: Instant::now(),
+                disconnected_since: None,
+                tripped: false,
+            }),
+        }
+    }
+
+    /// Sets how long the Stream connection can stay down before the switch trips. Only applies
+    /// to connection statuses fed with [`record_connection_status`](Self::record_connection_status)
+    /// or [`spawn_stream_monitor`](Self::spawn_stream_monitor).
+    ///
+    /// Default: `None`, the connection status is ignored
+    pub fn with_disconnect_timeout(mut self, timeout: Duration) -> Self {
+        self.disconnect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether tripping also closes running isolated trades and the cross position.
+    ///
+    /// Default: `false`, only open orders are canceled
+    pub fn with_flatten(mut self, flatten: bool) -> Self {
+        self.flatten = flatten;
+        self
+    }
+
+    pub fn heartbeat_timeout(&self) -> Duration {
+        self.heartbeat_timeout
+    }
+
+    pub fn disconnect_timeout(&self) -> Option<Duration> {
+        self.disconnect_timeout
+    }
+
+    pub fn flatten(&self) -> bool {
+        self.flatten
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, SwitchState> {
+        self.state
+            .lock()
+            .expect("`DeadManSwitch` mutex can't be poisoned")
+    }
+
+    /// Signals that the process is alive, re-arming the switch if it tripped.
+    pub fn heartbeat(&self) {
+        let mut state = self.lock_state();
+        state.last_heartbeat = Instant::now();
+        state.tripped = false;
+    }
+
+    /// Records the current status of the Stream connection.
+    pub fn record_connection_status(&self, status: &StreamConnectionStatus) {
+        let mut state = self.lock_state();
+
+        if status.is_connected() {
+            state.disconnected_since = None;
+        } else if state.disconnected_since.is_none() {
+            state.disconnected_since = Some(Instant::now());
+        }
+    }
+
+    /// Returns `true` if the switch tripped and wasn't re-armed yet.
+    pub fn is_tripped(&self) -> bool {
+        self.lock_state().tripped
+    }
+
+    /// Returns the reason the switch should trip now, if any. Always `None` once tripped, until
+    /// re-armed.
+    pub fn check(&self) -> Option<TripReason> {
+        let state = self.lock_state();
+
+        if state.tripped {
+            return None;
+        }
+
+        if state.last_heartbeat.elapsed() > self.heartbeat_timeout {
+            return Some(TripReason::HeartbeatTimeout);
+        }
+
+        if let (Some(timeout), Some(since)) = (self.disconnect_timeout, state.disconnected_since)
+            && since.elapsed() > timeout
+        {
+            return Some(TripReason::StreamDisconnected);
+        }
+
+        None
+    }
+
+    /// Trips the switch, canceling all open isolated trades and cross orders, and closing running
+    /// isolated trades and the cross position if flattening is enabled.
+    ///
+    /// **Required permissions**: `futures:isolated:read`, `futures:isolated:write`,
+    /// `futures:cross:read`, `futures:cross:write`
+    pub async fn trip(&self) -> TripReport {
+        self.lock_state().tripped = true;
+
+        let mut report = TripReport::default();
+
+        match self.api.get_open_trades().await {
+            Ok(trades) => {
+                for trade in trades {
+                    match self.api.cancel_trade(trade.id()).await {
+                        Ok(trade) => report.canceled_trades.push(trade),
+                        Err(e) => report.errors.push(e),
+                    }
+                }
+            }
+            Err(e) => report.errors.push(e),
+        }
+
+        match self.api.get_open_cross_orders().await {
+            Ok(orders) => {
+                for order in orders {
+                    match self.api.cancel_cross_order(order.id()).await {
+                        Ok(order) => report.canceled_orders.push(order),
+                        Err(e) => report.errors.push(e),
+                    }
+                }
+            }
+            Err(e) => report.errors.push(e),
+        }
+
+        if !self.flatten {
+            return report;
+        }
+
+        match self.api.get_running_trades().await {
+            Ok(trades) => {
+                for trade in trades {
+                    match self.api.close_trade(trade.id()).await {
+                        Ok(trade) => report.closed_trades.push(trade),
+                        Err(e) => report.errors.push(e),
+                    }
+                }
+            }
+            Err(e) => report.errors.push(e),
+        }
+
+        match self.api.get_cross_position().await {
+            Ok(position) if position.quantity() != 0 => {
+                match self.api.close_cross_position().await {
+                    Ok(order) => report.closed_position = Some(order),
+                    Err(e) => report.errors.push(e),
+                }
+            }
+            Ok(_) => {}
+            Err(e) => report.errors.push(e),
+        }
+
+        report
+    }
+
+    /// Spawns a task checking the switch every `check_interval`, and tripping it when due.
+    ///
+    /// Trips are logged. The task stops when the returned handle is aborted, or when the switch is
+    /// dropped.
+    pub fn spawn(self: &Arc<Self>, check_interval: Duration) -> JoinHandle<()> {
+        let switch = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            loop {
+                time::sleep(check_interval).await;
+
+                let Some(switch) = switch.upgrade() else {
+                    return;
+                };
+                let Some(reason) = switch.check() else {
+                    continue;
+                };
+
+                log::warn!("Dead man's switch tripped: {reason}");
+
+                let report = switch.trip().await;
+                for e in report.errors() {
+                    log::error!("Dead man's switch action failed: {e}");
+                }
+            }
+        })
+    }
+
+    /// Spawns a task recording the connection statuses received on `receiver`.
+    ///
+    /// The task stops when the stream is closed, or when the returned handle is aborted. A closed
+    /// stream is recorded as disconnected.
+    pub fn spawn_stream_monitor(
+        self: &Arc<Self>,
+        mut receiver: Receiver<StreamUpdate>,
+    ) -> JoinHandle<()> {
+        let switch = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            loop {
+                let result = receiver.recv().await;
+
+                let Some(switch) = switch.upgrade() else {
+                    return;
+                };
+
+                match result {
+                    Ok(StreamUpdate::ConnectionStatus(status)) => {
+                        switch.record_connection_status(&status);
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => {
+                        switch.record_connection_status(&StreamConnectionStatus::Disconnected);
+                        return;
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl fmt::Debug for DeadManSwitch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeadManSwitch")
+            .field("heartbeat_timeout", &self.heartbeat_timeout)
+            .field("disconnect_timeout", &self.disconnect_timeout)
+            .field("flatten", &self.flatten)
+            .field("state", &*self.lock_state())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::stub::StubApi;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_switch_trips_on_heartbeat_timeout() {
+        // One open and one running trade, one open cross order and a running position
+        let switch = DeadManSwitch::new(Arc::new(StubApi::new()), Duration::from_secs(30));
+
+        time::advance(Duration::from_secs(20)).await;
+        assert_eq!(switch.check(), None);
+        switch.heartbeat();
+
+        time::advance(Duration::from_secs(31)).await;
+        assert_eq!(switch.check(), Some(TripReason::HeartbeatTimeout));
+
+        let report = switch.trip().await;
+        assert!(report.is_complete());
+        assert_eq!(report.canceled_trades().len(), 1);
+        assert_eq!(report.canceled_orders().len(), 1);
+        assert!(report.closed_trades().is_empty());
+        assert!(report.closed_position().is_none());
+
+        // Doesn't trip again until re-armed
+        assert!(switch.is_tripped());
+        assert_eq!(switch.check(), None);
+        switch.heartbeat();
+        assert!(!switch.is_tripped());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_switch_trips_on_stream_disconnect_and_flattens() {
+        let switch = Arc::new(
+            DeadManSwitch::new(Arc::new(StubApi::new()), Duration::from_secs(60))
+                .with_disconnect_timeout(Duration::from_secs(10))
+                .with_flatten(true),
+        );
+
+        switch.record_connection_status(&StreamConnectionStatus::Reconnecting);
+        time::advance(Duration::from_secs(5)).await;
+        switch.record_connection_status(&StreamConnectionStatus::Connected);
+        time::advance(Duration::from_secs(10)).await;
+        assert_eq!(switch.check(), None);
+
+        switch.record_connection_status(&StreamConnectionStatus::Disconnected);
+        time::advance(Duration::from_secs(11)).await;
+        assert_eq!(switch.check(), Some(TripReason::StreamDisconnected));
+
+        let report = switch.trip().await;
+        assert!(report.is_complete());
+        assert_eq!(report.closed_trades().len(), 1);
+        assert!(report.closed_position().is_some());
+    }
+}
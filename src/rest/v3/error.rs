@@ -1,17 +1,22 @@
 use thiserror::Error;
+use uuid::Uuid;
 
 pub use crate::shared::{
     models::error::{
+        Bech32DecodeError, BitcoinAddressValidationError, Bolt11InvoiceValidationError,
         ClientIdValidationError, CrossLeverageValidationError, CrossQuantityValidationError,
-        LeverageValidationError, MarginValidationError, OhlcRangeParseError,
-        PercentageCappedValidationError, PercentageValidationError, PriceValidationError,
-        QuantityValidationError, TradeValidationError,
+        CurrencyParseError, InstrumentParseError, LeverageValidationError, MarginValidationError,
+        OhlcRangeParseError, PercentageCappedValidationError, PercentageValidationError,
+        PriceValidationError, QuantityValidationError, ResampleValidationError,
+        TradeValidationError,
     },
-    rest::error::RestApiError,
+    rest::error::{ErrorResponseContext, RestApiError},
 };
 
 pub use super::models::error::{
-    CrossExposureValidationError, FuturesIsolatedTradeRequestValidationError,
+    CrossExposureValidationError, CrossPositionCloseValidationError, ExchangeLimitsValidationError,
+    ExposureLimitsValidationError, FuturesIsolatedTradeRequestValidationError,
+    WithdrawalRequestValidationError,
 };
 
 #[derive(Error, Debug)]
@@ -20,6 +25,34 @@ pub enum RestApiV3Error {
     #[error("Invalid futures isolated trade request error: {0}")]
     FuturesIsolatedTradeRequestValidation(FuturesIsolatedTradeRequestValidationError),
 
+    #[error("Invalid cross position close error: {0}")]
+    CrossPositionCloseValidation(CrossPositionCloseValidationError),
+
+    #[error("Exchange limits validation error: {0}")]
+    ExchangeLimitsValidation(ExchangeLimitsValidationError),
+
+    #[error("Trade validation error: {0}")]
+    TradeValidation(TradeValidationError),
+
+    #[error("Invalid withdrawal request error: {0}")]
+    WithdrawalRequestValidation(WithdrawalRequestValidationError),
+
     #[error("Unexpected 'ping' response error: {0}")]
     UnexpectedPingResponse(String),
+
+    #[error("Cross position wasn't flat after closing order {order_id} was placed")]
+    PositionNotClosed { order_id: Uuid },
+}
+
+impl RestApiV3Error {
+    pub(crate) fn is_validation_error(&self) -> bool {
+        matches!(
+            self,
+            Self::FuturesIsolatedTradeRequestValidation(_)
+                | Self::CrossPositionCloseValidation(_)
+                | Self::ExchangeLimitsValidation(_)
+                | Self::TradeValidation(_)
+                | Self::WithdrawalRequestValidation(_)
+        )
+    }
 }
@@ -0,0 +1,161 @@
+//! Lean request path for latency-sensitive order placement.
+//!
+//! Requests sent through a [`FastLane`] share the connection pool, credentials and rate limiter
+//! of the [`RestClient`](super::RestClient) they were created from, but skip its optional
+//! middleware: request timing, Prometheus metrics, OpenTelemetry tracing, debug logging, API
+//! version tracking and maintenance pauses. Requests are still validated against the client's
+//! exchange limits, granted scopes and stale price threshold, rate limited and signed. Every other
+//! request keeps going through the full middleware of the client.
+
+use std::sync::Arc;
+
+use reqwest::Method;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::shared::rest::{error::Result, lnm::base::LnmRestBase};
+
+use super::{
+    lnm::{
+        futures_cross::place_order_body, futures_isolated::new_trade_body, path::RestPathV3,
+        signature::SignatureGeneratorV3,
+    },
+    models::{
+        ClientId, CrossOrder, ExchangeLimits, Leverage, OrderQuantity, Price, Trade,
+        TradeExecution, TradeSide, TradeSize,
+    },
+};
+
+/// Handle placing and canceling orders with minimal added latency, created with
+/// [`RestClient::fast_lane`](super::RestClient::fast_lane).
+///
+/// Cheap to clone. See the [module docs](self) for which middleware is skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::models::{Leverage, OrderQuantity, TradeExecution, TradeSide, TradeSize};
+///
+/// let fast = rest.fast_lane();
+///
+/// let trade = fast
+///     .new_trade(
+///         TradeSide::Buy,
+///         TradeSize::Quantity(OrderQuantity::try_from(100)?),
+///         Leverage::try_from(10)?,
+///         TradeExecution::Market,
+///         None,
+///         None,
+///         None,
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FastLane {
+    base: Arc<LnmRestBase<SignatureGeneratorV3>>,
+    limits: ExchangeLimits,
+}
+
+impl FastLane {
+    pub(super) fn new(
+        base: Arc<LnmRestBase<SignatureGeneratorV3>>,
+        limits: ExchangeLimits,
+    ) -> Self {
+        Self { base, limits }
+    }
+
+    /// Creates a new isolated trade. Equivalent to
+    /// [`FuturesIsolatedRepository::new_trade`](super::FuturesIsolatedRepository::new_trade),
+    /// without waiting for maintenance windows to end.
+    ///
+    /// **Required permissions**: `futures:isolated:write`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new_trade(
+        &self,
+        side: TradeSide,
+        size: TradeSize,
+        leverage: Leverage,
+        execution: TradeExecution,
+        stoploss: Option<Price>,
+        takeprofit: Option<Price>,
+        client_id: Option<ClientId>,
+    ) -> Result<Trade> {
+        let body = new_trade_body(
+            &self.limits,
+            side,
+            size,
+            leverage,
+            execution,
+            stoploss,
+            takeprofit,
+            client_id,
+        )?;
+
+        self.base.check_price_freshness(execution)?;
+        self.base
+            .make_fast_request_with_body(Method::POST, RestPathV3::FuturesIsolatedTrade, body)
+            .await
+    }
+
+    /// Cancels an open isolated trade.
+    ///
+    /// **Required permissions**: `futures:isolated:write`
+    pub async fn cancel_trade(&self, id: Uuid) -> Result<Trade> {
+        self.base
+            .make_fast_request_with_body(
+                Method::POST,
+                RestPathV3::FuturesIsolatedTradeCancel,
+                json!({ "id": id }),
+            )
+            .await
+    }
+
+    /// Closes a running isolated trade.
+    ///
+    /// **Required permissions**: `futures:isolated:write`
+    pub async fn close_trade(&self, id: Uuid) -> Result<Trade> {
+        self.base
+            .make_fast_request_with_body(
+                Method::POST,
+                RestPathV3::FuturesIsolatedTradeClose,
+                json!({ "id": id }),
+            )
+            .await
+    }
+
+    /// Places a new cross order. Equivalent to
+    /// [`FuturesCrossRepository::place_order`](super::FuturesCrossRepository::place_order),
+    /// without waiting for maintenance windows to end.
+    ///
+    /// **Required permissions**: `futures:cross:write`
+    pub async fn place_cross_order(
+        &self,
+        side: TradeSide,
+        quantity: OrderQuantity,
+        execution: TradeExecution,
+        client_id: Option<ClientId>,
+    ) -> Result<CrossOrder> {
+        let body = place_order_body(&self.limits, side, quantity, execution, client_id)?;
+
+        self.base.check_price_freshness(execution)?;
+        self.base
+            .make_fast_request_with_body(Method::POST, RestPathV3::FuturesCrossOrder, body)
+            .await
+    }
+
+    /// Cancels an open cross order.
+    ///
+    /// **Required permissions**: `futures:cross:write`
+    pub async fn cancel_cross_order(&self, id: Uuid) -> Result<CrossOrder> {
+        self.base
+            .make_fast_request_with_body(
+                Method::POST,
+                RestPathV3::FuturesCrossOrderCancel,
+                json!({ "id": id }),
+            )
+            .await
+    }
+}
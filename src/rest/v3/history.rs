@@ -0,0 +1,675 @@
+//! Concurrent download of long trade, order, candle and oracle price histories, and delta sync of
+//! trade history.
+
+use std::{future::Future, num::NonZeroU64, num::NonZeroUsize, pin::Pin, sync::Arc};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::{sync::Semaphore, task::JoinSet};
+use uuid::Uuid;
+
+use crate::shared::rest::error::Result;
+
+use super::{
+    RestClient,
+    models::{CrossOrder, Index, LastPrice, OhlcCandle, OhlcRange, Page, Trade},
+};
+
+/// Page size requested for each history request.
+const PAGE_LIMIT: NonZeroU64 = NonZeroU64::new(1000).expect("must be non-zero");
+
+/// Downloads long histories by splitting the requested time range into windows, and fetching the
+/// pages of several windows concurrently.
+///
+/// Requests go through the client's rate limiter like any other request, so concurrency speeds
+/// up downloads while staying within the configured limits. Results are merged in chronological
+/// order, with items returned by more than one window deduplicated.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use std::num::NonZeroUsize;
+///
+/// use chrono::{TimeDelta, Utc};
+/// use lnm_sdk::rest::v3::{history::HistoryDownloader, models::OhlcRange};
+///
+/// let downloader = HistoryDownloader::new(rest).with_concurrency(NonZeroUsize::new(8).unwrap());
+///
+/// let to = Utc::now();
+/// let candles = downloader
+///     .candles(OhlcRange::OneMinute, to - TimeDelta::days(365), to)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HistoryDownloader {
+    rest: RestClient,
+    concurrency: NonZeroUsize,
+    window: TimeDelta,
+}
+
+impl HistoryDownloader {
+    /// Creates a downloader with a concurrency of 4 and windows of 7 days.
+    pub fn new(rest: RestClient) -> Self {
+        Self {
+            rest,
+            concurrency: NonZeroUsize::new(4).expect("must be non-zero"),
+            window: TimeDelta::days(7),
+        }
+    }
+
+    /// Sets the maximum number of windows fetched concurrently.
+    pub fn with_concurrency(mut self, concurrency: NonZeroUsize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets the duration of the windows the time range is split into. Non-positive durations are
+    /// ignored.
+    pub fn with_window(mut self, window: TimeDelta) -> Self {
+        if window > TimeDelta::zero() {
+            self.window = window;
+        }
+        self
+    }
+
+    /// Downloads the closed isolated trades with `from <= time <= to`, sorted by creation time.
+    ///
+    /// **Required permissions**: `futures:isolated:read`
+    pub async fn closed_trades(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Trade>> {
+        let rest = self.rest.clone();
+        let fetch = move |from, to, cursor| {
+            let rest = rest.clone();
+            async move {
+                rest.futures_isolated
+                    .get_closed_trades(Some(from), Some(to), Some(PAGE_LIMIT), cursor)
+                    .await
+            }
+        };
+
+        self.download(from, to, fetch, |trade: &Trade| {
+            (trade.created_at(), trade.id())
+        })
+        .await
+    }
+
+    /// Downloads the canceled isolated trades with `from <= time <= to`, sorted by creation time.
+    ///
+    /// **Required permissions**: `futures:isolated:read`
+    pub async fn canceled_trades(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Trade>> {
+        let rest = self.rest.clone();
+        let fetch = move |from, to, cursor| {
+            let rest = rest.clone();
+            async move {
+                rest.futures_isolated
+                    .get_canceled_trades(Some(from), Some(to), Some(PAGE_LIMIT), cursor)
+                    .await
+            }
+        };
+
+        self.download(from, to, fetch, |trade: &Trade| {
+            (trade.created_at(), trade.id())
+        })
+        .await
+    }
+
+    /// Downloads the filled cross orders with `from <= time <= to`, sorted by creation time.
+    ///
+    /// **Required permissions**: `futures:cross:read`
+    pub async fn filled_orders(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<CrossOrder>> {
+        let rest = self.rest.clone();
+        let fetch = move |from, to, cursor| {
+            let rest = rest.clone();
+            async move {
+                rest.futures_cross
+                    .get_filled_orders(Some(from), Some(to), Some(PAGE_LIMIT), cursor)
+                    .await
+            }
+        };
+
+        self.download(from, to, fetch, |order: &CrossOrder| {
+            (order.created_at(), order.id())
+        })
+        .await
+    }
+
+    /// Returns a [`HistoryPager`] over the closed isolated trades with `from <= time <= to`, to
+    /// process them one page at a time instead of holding the full history in memory.
+    ///
+    /// **Required permissions**: `futures:isolated:read`
+    pub fn closed_trades_pager(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> HistoryPager<Trade> {
+        let rest = self.rest.clone();
+
+        HistoryPager::new(move |cursor| {
+            let rest = rest.clone();
+            Box::pin(async move {
+                rest.futures_isolated
+                    .get_closed_trades(Some(from), Some(to), Some(PAGE_LIMIT), cursor)
+                    .await
+            })
+        })
+    }
+
+    /// Returns a [`HistoryPager`] over the filled cross orders with `from <= time <= to`.
+    ///
+    /// **Required permissions**: `futures:cross:read`
+    pub fn filled_orders_pager(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> HistoryPager<CrossOrder> {
+        let rest = self.rest.clone();
+
+        HistoryPager::new(move |cursor| {
+            let rest = rest.clone();
+            Box::pin(async move {
+                rest.futures_cross
+                    .get_filled_orders(Some(from), Some(to), Some(PAGE_LIMIT), cursor)
+                    .await
+            })
+        })
+    }
+
+    /// Returns a [`HistoryPager`] over the candles of `range` with `from <= time <= to`.
+    pub fn candles_pager(
+        &self,
+        range: OhlcRange,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> HistoryPager<OhlcCandle> {
+        let rest = self.rest.clone();
+
+        HistoryPager::new(move |cursor| {
+            let rest = rest.clone();
+            Box::pin(async move {
+                rest.futures_data
+                    .get_candles(Some(from), Some(to), Some(PAGE_LIMIT), Some(range), cursor)
+                    .await
+            })
+        })
+    }
+
+    /// Downloads the candles of `range` with `from <= time <= to`, sorted by time.
+    pub async fn candles(
+        &self,
+        range: OhlcRange,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<OhlcCandle>> {
+        let rest = self.rest.clone();
+        let fetch = move |from, to, cursor| {
+            let rest = rest.clone();
+            async move {
+                rest.futures_data
+                    .get_candles(Some(from), Some(to), Some(PAGE_LIMIT), Some(range), cursor)
+                    .await
+            }
+        };
+
+        self.download(from, to, fetch, OhlcCandle::time).await
+    }
+
+    /// Downloads the index price history with `from <= time <= to`, sorted by time.
+    ///
+    /// The index is the reference price used for funding and settlement, unlike the last traded
+    /// price. See [`resample`](super::models::resample) to align it to arbitrary intervals.
+    pub async fn index(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<Index>> {
+        let rest = self.rest.clone();
+        let fetch = move |from, to, cursor| {
+            let rest = rest.clone();
+            async move {
+                let samples = rest
+                    .oracle
+                    .get_index(Some(from), Some(to), Some(PAGE_LIMIT), cursor)
+                    .await?;
+                Ok(sample_page(samples, cursor, Index::time))
+            }
+        };
+
+        self.download(from, to, fetch, Index::time).await
+    }
+
+    /// Downloads the last traded price history with `from <= time <= to`, sorted by time.
+    pub async fn last_prices(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<LastPrice>> {
+        let rest = self.rest.clone();
+        let fetch = move |from, to, cursor| {
+            let rest = rest.clone();
+            async move {
+                let samples = rest
+                    .oracle
+                    .get_last_price(Some(from), Some(to), Some(PAGE_LIMIT), cursor)
+                    .await?;
+                Ok(sample_page(samples, cursor, LastPrice::time))
+            }
+        };
+
+        self.download(from, to, fetch, LastPrice::time).await
+    }
+
+    async fn download<T, K, F, Fut>(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        fetch: F,
+        key: impl Fn(&T) -> K,
+    ) -> Result<Vec<T>>
+    where
+        T: Send + 'static,
+        K: Ord,
+        F: Fn(DateTime<Utc>, DateTime<Utc>, Option<DateTime<Utc>>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Page<T>>> + Send + 'static,
+    {
+        let windows = split_windows(from, to, self.window);
+        let mut items = download_windows(windows, self.concurrency, fetch).await?;
+
+        items.sort_by_key(|item| key(item));
+        items.dedup_by(|a, b| key(a) == key(b));
+
+        Ok(items)
+    }
+}
+
+type PageFuture<T> = Pin<Box<dyn Future<Output = Result<Page<T>>> + Send>>;
+
+/// Sequential reader of a paginated history, yielding one page of items at a time.
+///
+/// Only the current page is held in memory, so exports of very large histories run in bounded
+/// memory. Created by the `*_pager` methods of [`HistoryDownloader`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use chrono::{TimeDelta, Utc};
+/// use lnm_sdk::rest::v3::history::HistoryDownloader;
+///
+/// let to = Utc::now();
+/// let mut pager = HistoryDownloader::new(rest).closed_trades_pager(to - TimeDelta::days(365), to);
+///
+/// while let Some(trades) = pager.next_page().await? {
+///     for trade in trades {
+///         println!("{},{}", trade.id(), trade.pl());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct HistoryPager<T> {
+    fetch: Box<dyn FnMut(Option<DateTime<Utc>>) -> PageFuture<T> + Send>,
+    cursor: Option<DateTime<Utc>>,
+    done: bool,
+}
+
+impl<T> HistoryPager<T> {
+    fn new(fetch: impl FnMut(Option<DateTime<Utc>>) -> PageFuture<T> + Send + 'static) -> Self {
+        Self {
+            fetch: Box::new(fetch),
+            cursor: None,
+            done: false,
+        }
+    }
+
+    /// Fetches the next page of items, or returns `None` once the history is exhausted.
+    ///
+    /// On error, the pager is left at the failed page, so calling this method again retries it.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<T>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let page = (self.fetch)(self.cursor).await?;
+        let next_cursor = page.next_cursor();
+        let data = Vec::from(page);
+
+        match next_cursor {
+            Some(next) if !data.is_empty() => self.cursor = Some(next),
+            _ => self.done = true,
+        }
+
+        if data.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(data))
+    }
+}
+
+/// Position reached by a delta sync of trade history, to be persisted between runs.
+///
+/// Holds the closing time of the most recent synced trade, along with the IDs of all synced trades
+/// closed at that time, so trades sharing a timestamp with the cursor are neither skipped nor
+/// returned twice.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncCursor {
+    time: DateTime<Utc>,
+    ids: Vec<Uuid>,
+}
+
+impl SyncCursor {
+    /// Closing time of the most recent synced trade.
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+
+    /// IDs of the synced trades closed at [`time`](Self::time).
+    pub fn ids(&self) -> &[Uuid] {
+        &self.ids
+    }
+
+    /// Returns the items newer than `cursor`, sorted by key, and the cursor after them.
+    fn advance<T>(
+        cursor: Option<&SyncCursor>,
+        mut items: Vec<T>,
+        key: impl Fn(&T) -> (DateTime<Utc>, Uuid),
+    ) -> (Vec<T>, Option<SyncCursor>) {
+        items.retain(|item| {
+            let (time, id) = key(item);
+            cursor.is_none_or(|cursor| {
+                time > cursor.time || (time == cursor.time && !cursor.ids.contains(&id))
+            })
+        });
+        items.sort_by_key(|item| key(item));
+        items.dedup_by(|a, b| key(a) == key(b));
+
+        let Some((last_time, _)) = items.last().map(&key) else {
+            return (items, cursor.cloned());
+        };
+
+        let mut ids = match cursor {
+            Some(cursor) if cursor.time == last_time => cursor.ids.clone(),
+            _ => Vec::new(),
+        };
+        ids.extend(
+            items
+                .iter()
+                .map(&key)
+                .filter(|(time, _)| *time == last_time)
+                .map(|(_, id)| id),
+        );
+
+        let cursor = SyncCursor {
+            time: last_time,
+            ids,
+        };
+
+        (items, Some(cursor))
+    }
+}
+
+/// Fetches the closed isolated trades newer than `cursor`, or all of them if `None`.
+pub(super) async fn sync_closed_trades(
+    rest: &RestClient,
+    cursor: Option<&SyncCursor>,
+) -> Result<(Vec<Trade>, Option<SyncCursor>)> {
+    let from = cursor.map(SyncCursor::time);
+    let mut trades = Vec::new();
+    let mut page_cursor = None;
+
+    loop {
+        let page = rest
+            .futures_isolated
+            .get_closed_trades(from, None, Some(PAGE_LIMIT), page_cursor)
+            .await?;
+        let next_cursor = page.next_cursor();
+        let data = Vec::from(page);
+
+        if data.is_empty() {
+            break;
+        }
+        trades.extend(data);
+
+        match next_cursor {
+            Some(next) => page_cursor = Some(next),
+            None => break,
+        }
+    }
+
+    Ok(SyncCursor::advance(cursor, trades, |trade: &Trade| {
+        (trade.closed_at().unwrap_or(trade.created_at()), trade.id())
+    }))
+}
+
+/// Wraps oracle samples, which aren't returned in pages, into a [`Page`]. A full batch continues
+/// from the time of its earliest sample, unless that wouldn't move the cursor.
+fn sample_page<T>(
+    samples: Vec<T>,
+    cursor: Option<DateTime<Utc>>,
+    time: impl Fn(&T) -> DateTime<Utc>,
+) -> Page<T> {
+    let next_cursor = (samples.len() as u64 == PAGE_LIMIT.get())
+        .then(|| samples.iter().map(time).min())
+        .flatten()
+        .filter(|next| Some(*next) != cursor);
+
+    Page::new(samples, next_cursor)
+}
+
+/// Splits `[from, to]` into consecutive windows of at most `window`.
+fn split_windows(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    window: TimeDelta,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut windows = Vec::new();
+    let mut start = from;
+
+    while start < to {
+        let end = (start + window).min(to);
+        windows.push((start, end));
+        start = end;
+    }
+
+    windows
+}
+
+/// Fetches all pages of each window, with at most `concurrency` windows in flight, and returns
+/// the items of all windows in window order.
+async fn download_windows<T, F, Fut>(
+    windows: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    concurrency: NonZeroUsize,
+    fetch: F,
+) -> Result<Vec<T>>
+where
+    T: Send + 'static,
+    F: Fn(DateTime<Utc>, DateTime<Utc>, Option<DateTime<Utc>>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Page<T>>> + Send + 'static,
+{
+    let fetch = Arc::new(fetch);
+    let permits = Arc::new(Semaphore::new(concurrency.get()));
+    let mut tasks: JoinSet<Result<(usize, Vec<T>)>> = JoinSet::new();
+
+    for (index, (from, to)) in windows.iter().copied().enumerate() {
+        let fetch = fetch.clone();
+        let permits = permits.clone();
+
+        tasks.spawn(async move {
+            let _permit = permits
+                .acquire_owned()
+                .await
+                .expect("semaphore is never closed");
+
+            let mut items = Vec::new();
+            let mut cursor = None;
+
+            loop {
+                let page = fetch(from, to, cursor).await?;
+                let next_cursor = page.next_cursor();
+                let data = Vec::from(page);
+
+                if data.is_empty() {
+                    break;
+                }
+                items.extend(data);
+
+                match next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+
+            Ok((index, items))
+        });
+    }
+
+    let mut results: Vec<Option<Vec<T>>> = windows.iter().map(|_| None).collect();
+
+    while let Some(joined) = tasks.join_next().await {
+        let (index, items) = joined.expect("download task can't panic")?;
+        results[index] = Some(items);
+    }
+
+    Ok(results.into_iter().flatten().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_split_windows() {
+        let from = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let to = from + TimeDelta::hours(25);
+
+        let windows = split_windows(from, to, TimeDelta::hours(10));
+
+        assert_eq!(
+            windows,
+            vec![
+                (from, from + TimeDelta::hours(10)),
+                (from + TimeDelta::hours(10), from + TimeDelta::hours(20)),
+                (from + TimeDelta::hours(20), to),
+            ]
+        );
+        assert!(split_windows(to, from, TimeDelta::hours(1)).is_empty());
+    }
+
+    #[test]
+    fn test_sample_page() {
+        let t0 = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let time = |sample: &DateTime<Utc>| *sample;
+
+        let full: Vec<_> = (0..PAGE_LIMIT.get() as i64)
+            .map(|n| t0 + TimeDelta::seconds(n))
+            .collect();
+        assert_eq!(
+            sample_page(full.clone(), None, time).next_cursor(),
+            Some(t0)
+        );
+        // A full batch that doesn't move the cursor ends the pagination
+        assert_eq!(sample_page(full, Some(t0), time).next_cursor(), None);
+        assert_eq!(sample_page(vec![t0], None, time).next_cursor(), None);
+    }
+
+    #[test]
+    fn test_sync_cursor_advance() {
+        let t0 = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let t1 = t0 + TimeDelta::seconds(1);
+        let id = |n: u128| Uuid::from_u128(n);
+        let key = |item: &(DateTime<Utc>, Uuid)| *item;
+
+        let (items, cursor) = SyncCursor::advance(None, vec![(t1, id(2)), (t0, id(1))], key);
+        assert_eq!(items, vec![(t0, id(1)), (t1, id(2))]);
+        let cursor = cursor.unwrap();
+        assert_eq!((cursor.time(), cursor.ids()), (t1, &[id(2)][..]));
+
+        // Records at the cursor time are only returned if not synced yet
+        let (items, cursor) = SyncCursor::advance(
+            Some(&cursor),
+            vec![(t0, id(1)), (t1, id(2)), (t1, id(3))],
+            key,
+        );
+        assert_eq!(items, vec![(t1, id(3))]);
+        let cursor = cursor.unwrap();
+        assert_eq!(cursor.ids(), &[id(2), id(3)]);
+
+        let (items, unchanged) = SyncCursor::advance(Some(&cursor), vec![(t1, id(3))], key);
+        assert!(items.is_empty());
+        assert_eq!(unchanged, Some(cursor));
+    }
+
+    #[tokio::test]
+    async fn test_history_pager() {
+        let cursor = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let mut pager = HistoryPager::<i64>::new(move |page_cursor| {
+            let page = match page_cursor {
+                None => json!({ "data": [1, 2], "nextCursor": cursor }),
+                Some(_) => json!({ "data": [3], "nextCursor": null }),
+            };
+            Box::pin(async move { Ok(serde_json::from_value(page).unwrap()) })
+        });
+
+        assert_eq!(pager.next_page().await.unwrap(), Some(vec![1, 2]));
+        assert_eq!(pager.next_page().await.unwrap(), Some(vec![3]));
+        assert_eq!(pager.next_page().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_download_windows_merges_in_order() {
+        let from = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let windows = split_windows(from, from + TimeDelta::hours(4), TimeDelta::hours(1));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let fetch = {
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+
+            move |start: DateTime<Utc>, _to, cursor: Option<DateTime<Utc>>| {
+                let in_flight = in_flight.clone();
+                let max_in_flight = max_in_flight.clone();
+
+                async move {
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(current, Ordering::SeqCst);
+
+                    // Later windows respond first
+                    let hour = (start - from).num_hours();
+                    tokio::time::sleep(std::time::Duration::from_millis((40 - hour * 10) as u64))
+                        .await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                    // Two pages per window
+                    let page = match cursor {
+                        None => json!({ "data": [hour * 10], "nextCursor": start }),
+                        Some(_) => json!({ "data": [hour * 10 + 1], "nextCursor": null }),
+                    };
+                    Ok(serde_json::from_value::<Page<i64>>(page).unwrap())
+                }
+            }
+        };
+
+        let items = download_windows(windows, NonZeroUsize::new(2).unwrap(), fetch)
+            .await
+            .unwrap();
+
+        assert_eq!(items, vec![0, 1, 10, 11, 20, 21, 30, 31]);
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 2);
+    }
+}
@@ -0,0 +1,419 @@
+//! Iceberg execution of large cross limit orders.
+//!
+//! An [`IcebergOrder`] works a large limit order by keeping a single visible slice of at most a
+//! fixed quantity on the book, and placing the next slice once the previous one fills, until the
+//! total quantity is filled. Slices are placed with client IDs sharing a common prefix, so they
+//! can be traced back to their parent order.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::shared::{
+    models::{
+        client_id::ClientId,
+        price::Price,
+        quantity::order::OrderQuantity,
+        trade::{TradeExecution, TradeSide},
+    },
+    rest::error::Result,
+};
+
+use super::{LnmFuturesApi, models::CrossOrder};
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum IcebergValidationError {
+    #[error("Visible quantity {visible} is above the total quantity {total}")]
+    VisibleAboveTotal { visible: u64, total: u64 },
+
+    #[error("Client ID prefix must be at most {max} characters, got {len}")]
+    PrefixTooLong { len: usize, max: usize },
+}
+
+#[derive(Debug, Default)]
+struct IcebergState {
+    filled: u64,
+    slices: usize,
+    working: Option<CrossOrder>,
+    canceled: bool,
+}
+
+/// Large cross limit order worked in visible slices.
+///
+/// Slices are placed as limit orders at the parent's price, with client IDs `{prefix}-{n}`, `n`
+/// starting at 1. Fills must be reported with [`on_filled`](Self::on_filled), e.g. from Stream API
+/// cross order updates, which places the next slice.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(
+/// #     rest: std::sync::Arc<lnm_sdk::rest::v3::RestClient>,
+/// #     mut receiver: tokio::sync::broadcast::Receiver<lnm_sdk::stream::v1::models::StreamUpdate>,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::{
+///     rest::v3::{
+///         iceberg::IcebergOrder,
+///         models::{OrderQuantity, Price, TradeSide},
+///     },
+///     stream::v1::models::StreamUpdate,
+/// };
+///
+/// let iceberg = IcebergOrder::new(
+///     rest,
+///     "iceberg-1",
+///     TradeSide::Buy,
+///     OrderQuantity::try_from(50_000)?,
+///     OrderQuantity::try_from(1_000)?,
+///     Price::try_from(95_000)?,
+/// )?;
+///
+/// // Places the first slice
+/// iceberg.replenish().await?;
+///
+/// while !iceberg.is_complete() {
+///     let Ok(StreamUpdate::FuturesInverseBtcUsdCrossOrders(event)) = receiver.recv().await else {
+///         continue;
+///     };
+///
+///     if event.event() == "filled"
+///         && let Some(id) = event.order().id()
+///     {
+///         iceberg.on_filled(id).await?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct IcebergOrder {
+    api: Arc<dyn LnmFuturesApi>,
+    prefix: String,
+    side: TradeSide,
+    total: OrderQuantity,
+    visible: OrderQuantity,
+    price: Price,
+    state: Mutex<IcebergState>,
+    // Serializes placing and canceling slices, so at most one slice is ever working
+    send_lock: tokio::sync::Mutex<()>,
+}
+
+impl IcebergOrder {
+    /// Maximum length of the client ID prefix, leaving room for the slice number.
+    pub const MAX_PREFIX_LEN: usize = ClientId::MAX_LEN - 8;
+
+    /// Creates an iceberg order of `total` quantity at `price`, showing at most `visible` at a
+    /// time. No slice is placed until [`replenish`](Self::replenish) is called.
+    pub fn new(
+        api: Arc<dyn LnmFuturesApi>,
+        prefix: impl Into<String>,
+        side: TradeSide,
+        total: OrderQuantity,
+        visible: OrderQuantity,
+        price: Price,
+    ) -> std::result::Result<Self, IcebergValidationError> {
+        let prefix = prefix.into();
+
+        if prefix.len() > Self::MAX_PREFIX_LEN {
+            return Err(IcebergValidationError::PrefixTooLong {
+                len: prefix.len(),
+                max: Self::MAX_PREFIX_LEN,
+            });
+        }
+
+        if visible.as_u64() > total.as_u64() {
+            return Err(IcebergValidationError::VisibleAboveTotal {
+                visible: visible.as_u64(),
+                total: total.as_u64(),
+            });
+        }
+
+        Ok(Self {
+            api,
+            prefix,
+            side,
+            total,
+            visible,
+            price,
+            state: Mutex::new(IcebergState::default()),
+            send_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Client ID prefix shared by the slices.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn side(&self) -> TradeSide {
+        self.side
+    }
+
+    /// Total quantity to be filled.
+    pub fn total(&self) -> OrderQuantity {
+        self.total
+    }
+
+    /// Maximum quantity of each slice.
+    pub fn visible(&self) -> OrderQuantity {
+        self.visible
+    }
+
+    /// Limit price of the slices.
+    pub fn price(&self) -> Price {
+        self.price
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, IcebergState> {
+        self.state
+            .lock()
+            .expect("`IcebergOrder` mutex can't be poisoned")
+    }
+
+    /// Quantity filled so far.
+    pub fn filled(&self) -> u64 {
+        self.lock_state().filled
+    }
+
+    /// Quantity not filled yet, including the working slice.
+    pub fn remaining(&self) -> u64 {
+        self.total.as_u64() - self.filled()
+    }
+
+    /// Slice currently on the book, if any.
+    pub fn working(&self) -> Option<CrossOrder> {
+        self.lock_state().working.clone()
+    }
+
+    /// Returns `true` once the total quantity is filled.
+    pub fn is_complete(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns `true` if the order was canceled with [`cancel`](Self::cancel).
+    pub fn is_canceled(&self) -> bool {
+        self.lock_state().canceled
+    }
+
+    /// Returns `true` if `client_id` belongs to one of the slices of this order.
+    pub fn owns(&self, client_id: &ClientId) -> bool {
+        client_id
+            .as_str()
+            .strip_prefix(self.prefix.as_str())
+            .and_then(|suffix| suffix.strip_prefix('-'))
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+    }
+
+    /// Places the next slice if none is working, and the order is neither complete nor canceled.
+    ///
+    /// Returns the slice placed, if any.
+    pub async fn replenish(&self) -> Result<Option<CrossOrder>> {
+        let _send_guard = self.send_lock.lock().await;
+
+        let (quantity, client_id) = {
+            let state = self.lock_state();
+            let remaining = self.total.as_u64() - state.filled;
+
+            if state.working.is_some() || state.canceled || remaining == 0 {
+                return Ok(None);
+            }
+
+            let quantity = OrderQuantity::try_from(remaining.min(self.visible.as_u64()))
+                .expect("must be a valid `OrderQuantity`, between 1 and `visible`");
+            let client_id = ClientId::try_from(format!("{}-{}", self.prefix, state.slices + 1))
+                .expect("must be a valid `ClientId`, as the prefix length is bounded");
+
+            (quantity, client_id)
+        };
+
+        let order = self
+            .api
+            .place_cross_order(
+                self.side,
+                quantity,
+                TradeExecution::Limit(self.price),
+                Some(client_id),
+            )
+            .await?;
+
+        let mut state = self.lock_state();
+        state.slices += 1;
+        state.working = Some(order.clone());
+
+        Ok(Some(order))
+    }
+
+    /// Records the fill of the order with `id`, and places the next slice if it was the working
+    /// one.
+    ///
+    /// Returns the slice placed, if any. Fills of other orders are ignored.
+    pub async fn on_filled(&self, id: Uuid) -> Result<Option<CrossOrder>> {
+        {
+            let _send_guard = self.send_lock.lock().await;
+            let mut state = self.lock_state();
+
+            let Some(working) = state.working.take_if(|working| working.id() == id) else {
+                return Ok(None);
+            };
+            state.filled += working.quantity().as_u64();
+        }
+
+        self.replenish().await
+    }
+
+    /// Stops placing slices, and cancels the working slice, if any.
+    ///
+    /// Returns the canceled slice. If canceling fails, e.g. because the slice was filled in the
+    /// meantime, it remains [`working`](Self::working) so its fill can still be recorded.
+    pub async fn cancel(&self) -> Result<Option<CrossOrder>> {
+        let _send_guard = self.send_lock.lock().await;
+
+        let working = {
+            let mut state = self.lock_state();
+            state.canceled = true;
+            state.working.clone()
+        };
+
+        let Some(working) = working else {
+            return Ok(None);
+        };
+
+        let canceled = self.api.cancel_cross_order(working.id()).await?;
+        self.lock_state().working = None;
+
+        Ok(Some(canceled))
+    }
+}
+
+impl fmt::Debug for IcebergOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IcebergOrder")
+            .field("prefix", &self.prefix)
+            .field("side", &self.side)
+            .field("total", &self.total)
+            .field("visible", &self.visible)
+            .field("price", &self.price)
+            .field("filled", &self.filled())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::{faults::ApiCall, stub::StubApi};
+
+    /// Records placed slices, returned as open orders.
+    fn book_api() -> StubApi {
+        let placed = Arc::new(Mutex::new(Vec::<CrossOrder>::new()));
+        let book = placed.clone();
+
+        StubApi::new()
+            .with_place_cross_order(move |side, quantity, execution, client_id| {
+                let TradeExecution::Limit(price) = execution else {
+                    panic!("slices must be limit orders");
+                };
+
+                let order: CrossOrder = serde_json::from_value(json!({
+                    "id": Uuid::new_v4(),
+                    "type": "limit",
+                    "side": side,
+                    "quantity": quantity,
+                    "price": price,
+                    "tradingFee": 0,
+                    "createdAt": "2025-01-01T00:00:00.000Z",
+                    "filledAt": null,
+                    "canceledAt": null,
+                    "open": true,
+                    "filled": false,
+                    "canceled": false,
+                    "clientId": client_id,
+                }))
+                .unwrap();
+
+                placed.lock().unwrap().push(order.clone());
+                Ok(order)
+            })
+            .with_cancel_cross_order(move |id| {
+                let placed = book.lock().unwrap();
+                Ok(placed.iter().find(|o| o.id() == id).unwrap().clone())
+            })
+    }
+
+    fn quantity(value: u32) -> OrderQuantity {
+        OrderQuantity::try_from(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_iceberg_replenishes_slices() {
+        let api = Arc::new(book_api());
+        let price = Price::try_from(95_000).unwrap();
+
+        assert!(matches!(
+            IcebergOrder::new(
+                api.clone(),
+                "ice",
+                TradeSide::Buy,
+                quantity(100),
+                quantity(200),
+                price
+            ),
+            Err(IcebergValidationError::VisibleAboveTotal { .. })
+        ));
+
+        let iceberg = IcebergOrder::new(
+            api.clone(),
+            "ice",
+            TradeSide::Buy,
+            quantity(2_500),
+            quantity(1_000),
+            price,
+        )
+        .unwrap();
+
+        let first = iceberg.replenish().await.unwrap().unwrap();
+        assert_eq!(first.client_id().unwrap().as_str(), "ice-1");
+        assert!(iceberg.owns(first.client_id().unwrap()));
+        assert!(!iceberg.owns(&ClientId::try_from("ice-x").unwrap()));
+        assert!(!iceberg.owns(&ClientId::try_from("iceberg-1").unwrap()));
+
+        // A slice is already working
+        assert!(iceberg.replenish().await.unwrap().is_none());
+        // Unrelated fill
+        assert!(iceberg.on_filled(Uuid::nil()).await.unwrap().is_none());
+
+        let second = iceberg.on_filled(first.id()).await.unwrap().unwrap();
+        assert_eq!(second.client_id().unwrap().as_str(), "ice-2");
+        assert_eq!(iceberg.filled(), 1_000);
+
+        let third = iceberg.on_filled(second.id()).await.unwrap().unwrap();
+        assert_eq!(third.quantity(), quantity(500));
+        assert_eq!(iceberg.remaining(), 500);
+
+        assert!(iceberg.on_filled(third.id()).await.unwrap().is_none());
+        assert!(iceberg.is_complete());
+        assert!(iceberg.working().is_none());
+        assert_eq!(api.call_count(ApiCall::PlaceCrossOrder), 3);
+
+        let iceberg = IcebergOrder::new(
+            api.clone(),
+            "ice2",
+            TradeSide::Sell,
+            quantity(2_000),
+            quantity(1_000),
+            price,
+        )
+        .unwrap();
+
+        let first = iceberg.replenish().await.unwrap().unwrap();
+        assert_eq!(iceberg.cancel().await.unwrap().unwrap().id(), first.id());
+        assert!(iceberg.is_canceled());
+        assert!(iceberg.replenish().await.unwrap().is_none());
+    }
+}
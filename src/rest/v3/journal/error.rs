@@ -0,0 +1,44 @@
+use std::{io, path::PathBuf, result};
+
+use thiserror::Error;
+
+use crate::shared::{models::client_id::ClientId, rest::error::RestApiError};
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum JournalError {
+    #[error("Journal IO error. Path: {path}, error: {e}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        e: io::Error,
+    },
+
+    #[error("Journal entry serialization failed. Error: {0}")]
+    EntrySerialize(#[source] serde_json::Error),
+
+    #[error("Journal entry at line {line} could not be parsed. Error: {e}")]
+    EntryParse {
+        line: usize,
+        #[source]
+        e: serde_json::Error,
+    },
+
+    #[error("Client ID {0} was already used by a journaled order")]
+    DuplicateClientId(ClientId),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    #[error(transparent)]
+    RestApi(#[from] RestApiError),
+}
+
+pub type Result<T> = result::Result<T, JournalError>;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum StopOrderValidationError {
+    #[error("Stop orders must use market execution")]
+    NotMarketExecution,
+}
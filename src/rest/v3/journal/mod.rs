@@ -0,0 +1,1491 @@
+//! Write-ahead journal of order intents and outcomes, for crash recovery.
+//!
+//! [`OrderJournal::submit`] durably records each order intent, keyed by its [`ClientId`], before
+//! sending it, and records the outcome once known. After a crash, [`OrderJournal::recover`]
+//! resolves intents with unknown outcomes against the API, so orders are neither duplicated nor
+//! forgotten on restart.
+//!
+//! The journal also persists client-side [stop orders](StopOrder), market orders sent once a
+//! price condition is met. Armed stops are reloaded by [`OrderJournal::open`], and sent by
+//! [`OrderJournal::observe_price`] when triggered.
+
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Duration, Utc};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::shared::{
+    clock::{Clock, SystemClock},
+    models::{
+        client_id::ClientId,
+        condition::PriceReference,
+        leverage::Leverage,
+        margin::Margin,
+        price::Price,
+        quantity::order::OrderQuantity,
+        trade::{TradeExecution, TradeSide, TradeSize},
+    },
+    rest::error::RestApiError,
+};
+
+use super::{
+    LnmFuturesApi,
+    models::{CrossOrder, Page, Trade},
+};
+
+mod error;
+mod stop;
+mod storage;
+
+pub use error::{JournalError, Result, StopOrderValidationError};
+pub use stop::{ArmedStop, StopOrder};
+pub use storage::{
+    FileJournalStorage, JournalStorage, MemoryJournalStorage, StateStoreJournalStorage,
+};
+
+/// Margin subtracted from the oldest pending intent when fetching history during recovery, to
+/// account for clock skew between the client and the server.
+const RECOVERY_CLOCK_SKEW: Duration = Duration::minutes(1);
+
+/// Order to be placed, as recorded in the journal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "IntentRecord", try_from = "IntentRecord")]
+pub enum OrderIntent {
+    /// New isolated trade.
+    Isolated {
+        side: TradeSide,
+        size: TradeSize,
+        leverage: Leverage,
+        execution: TradeExecution,
+        stoploss: Option<Price>,
+        takeprofit: Option<Price>,
+    },
+    /// New cross order.
+    Cross {
+        side: TradeSide,
+        quantity: OrderQuantity,
+        execution: TradeExecution,
+    },
+}
+
+impl OrderIntent {
+    /// Sends the order through `api`, attaching `client_id` to it.
+    pub(crate) async fn send(
+        self,
+        api: &dyn LnmFuturesApi,
+        client_id: ClientId,
+    ) -> crate::shared::rest::error::Result<PlacedOrder> {
+        match self {
+            OrderIntent::Isolated {
+                side,
+                size,
+                leverage,
+                execution,
+                stoploss,
+                takeprofit,
+            } => api
+                .new_trade(
+                    side,
+                    size,
+                    leverage,
+                    execution,
+                    stoploss,
+                    takeprofit,
+                    Some(client_id),
+                )
+                .await
+                .map(PlacedOrder::Trade),
+            OrderIntent::Cross {
+                side,
+                quantity,
+                execution,
+            } => api
+                .place_cross_order(side, quantity, execution, Some(client_id))
+                .await
+                .map(PlacedOrder::CrossOrder),
+        }
+    }
+}
+
+/// Serialized representation of [`OrderIntent`], using only primitive-backed models.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "market", rename_all = "camelCase")]
+enum IntentRecord {
+    #[serde(rename_all = "camelCase")]
+    Isolated {
+        side: TradeSide,
+        quantity: Option<OrderQuantity>,
+        margin: Option<Margin>,
+        leverage: Leverage,
+        price: Option<Price>,
+        stoploss: Option<Price>,
+        takeprofit: Option<Price>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Cross {
+        side: TradeSide,
+        quantity: OrderQuantity,
+        price: Option<Price>,
+    },
+}
+
+fn execution_price(execution: TradeExecution) -> Option<Price> {
+    match execution {
+        TradeExecution::Market => None,
+        TradeExecution::Limit(price) => Some(price),
+    }
+}
+
+fn execution_from_price(price: Option<Price>) -> TradeExecution {
+    price.map_or(TradeExecution::Market, TradeExecution::Limit)
+}
+
+impl From<OrderIntent> for IntentRecord {
+    fn from(intent: OrderIntent) -> Self {
+        match intent {
+            OrderIntent::Isolated {
+                side,
+                size,
+                leverage,
+                execution,
+                stoploss,
+                takeprofit,
+            } => {
+                let (quantity, margin) = match size {
+                    TradeSize::Quantity(quantity) => (Some(quantity), None),
+                    TradeSize::Margin(margin) => (None, Some(margin)),
+                };
+
+                IntentRecord::Isolated {
+                    side,
+                    quantity,
+                    margin,
+                    leverage,
+                    price: execution_price(execution),
+                    stoploss,
+                    takeprofit,
+                }
+            }
+            OrderIntent::Cross {
+                side,
+                quantity,
+                execution,
+            } => IntentRecord::Cross {
+                side,
+                quantity,
+                price: execution_price(execution),
+            },
+        }
+    }
+}
+
+impl TryFrom<IntentRecord> for OrderIntent {
+    type Error = String;
+
+    fn try_from(record: IntentRecord) -> std::result::Result<Self, Self::Error> {
+        match record {
+            IntentRecord::Isolated {
+                side,
+                quantity,
+                margin,
+                leverage,
+                price,
+                stoploss,
+                takeprofit,
+            } => {
+                let size = match (quantity, margin) {
+                    (Some(quantity), None) => TradeSize::Quantity(quantity),
+                    (None, Some(margin)) => TradeSize::Margin(margin),
+                    _ => return Err("exactly one of `quantity` or `margin` must be set".into()),
+                };
+
+                Ok(OrderIntent::Isolated {
+                    side,
+                    size,
+                    leverage,
+                    execution: execution_from_price(price),
+                    stoploss,
+                    takeprofit,
+                })
+            }
+            IntentRecord::Cross {
+                side,
+                quantity,
+                price,
+            } => Ok(OrderIntent::Cross {
+                side,
+                quantity,
+                execution: execution_from_price(price),
+            }),
+        }
+    }
+}
+
+/// Event recorded in the journal for an order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum JournalEvent {
+    /// The order is about to be sent.
+    Intent(OrderIntent),
+    /// The order was accepted by the API.
+    Placed { id: Uuid },
+    /// The order was not placed.
+    Failed { reason: String },
+    /// A stop order was armed. Followed by [`Intent`](Self::Intent) once triggered.
+    Armed(StopOrder),
+    /// An armed stop order was canceled before being triggered.
+    Disarmed,
+}
+
+/// Single journal entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    client_id: ClientId,
+    recorded_at: DateTime<Utc>,
+    event: JournalEvent,
+}
+
+impl JournalEntry {
+    /// Creates an entry recorded at `recorded_at`.
+    pub fn new_at(client_id: ClientId, event: JournalEvent, recorded_at: DateTime<Utc>) -> Self {
+        Self {
+            client_id,
+            recorded_at,
+            event,
+        }
+    }
+
+    /// Client ID of the order.
+    pub fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    /// Timestamp when the entry was recorded.
+    pub fn recorded_at(&self) -> DateTime<Utc> {
+        self.recorded_at
+    }
+
+    /// Recorded event.
+    pub fn event(&self) -> &JournalEvent {
+        &self.event
+    }
+}
+
+/// Order intent whose outcome is not known yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingOrder {
+    client_id: ClientId,
+    intent: OrderIntent,
+    recorded_at: DateTime<Utc>,
+}
+
+impl PendingOrder {
+    /// Client ID of the order.
+    pub fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    /// Order intent.
+    pub fn intent(&self) -> &OrderIntent {
+        &self.intent
+    }
+
+    /// Timestamp when the intent was recorded.
+    pub fn recorded_at(&self) -> DateTime<Utc> {
+        self.recorded_at
+    }
+}
+
+/// Order placed through [`OrderJournal::submit`].
+#[derive(Debug, Clone)]
+pub enum PlacedOrder {
+    Trade(Trade),
+    CrossOrder(CrossOrder),
+}
+
+impl PlacedOrder {
+    /// ID assigned to the order by the API.
+    pub fn id(&self) -> Uuid {
+        match self {
+            Self::Trade(trade) => trade.id(),
+            Self::CrossOrder(order) => order.id(),
+        }
+    }
+}
+
+/// Outcome of [`OrderJournal::recover`].
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryReport {
+    placed: Vec<(ClientId, Uuid)>,
+    not_placed: Vec<PendingOrder>,
+    unresolved: Vec<PendingOrder>,
+}
+
+impl RecoveryReport {
+    /// Pending orders found on the API, with their IDs. Recorded as placed.
+    pub fn placed(&self) -> &[(ClientId, Uuid)] {
+        &self.placed
+    }
+
+    /// Pending orders not found on the API. Recorded as failed, and safe to submit again with a
+    /// new client ID.
+    pub fn not_placed(&self) -> &[PendingOrder] {
+        &self.not_placed
+    }
+
+    /// Pending cross limit orders not found on the API. Recorded as failed, but they may have been
+    /// placed and then canceled, possibly after a partial fill, since canceled cross orders can't
+    /// be listed. The cross position should be checked before submitting them again.
+    pub fn unresolved(&self) -> &[PendingOrder] {
+        &self.unresolved
+    }
+}
+
+#[derive(Debug, Clone)]
+enum OrderState {
+    Armed(ArmedStop),
+    Pending(PendingOrder),
+    Placed,
+    Failed,
+    Disarmed,
+}
+
+/// Returns `true` if the error guarantees the order was not placed.
+///
+/// Validation errors happen before the request is sent, and client error responses are
+/// rejections. Timeouts (`408`), transport errors and server errors leave the outcome unknown.
+fn is_definite_rejection(error: &RestApiError) -> bool {
+    error.is_validation_error()
+        || matches!(error, RestApiError::MissingRequestCredentials)
+        || error
+            .status()
+            .is_some_and(|status| status.is_client_error() && status != StatusCode::REQUEST_TIMEOUT)
+}
+
+/// Fetches all pages of a paginated endpoint, starting at `from`.
+async fn collect_pages<T, F, Fut>(from: DateTime<Utc>, mut fetch: F) -> Result<Vec<T>>
+where
+    F: FnMut(Option<DateTime<Utc>>, Option<DateTime<Utc>>) -> Fut,
+    Fut: Future<Output = crate::shared::rest::error::Result<Page<T>>>,
+{
+    let mut items = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let page = fetch(Some(from), cursor).await?;
+        let next_cursor = page.next_cursor();
+        let data = Vec::from(page);
+
+        if data.is_empty() {
+            break;
+        }
+        items.extend(data);
+
+        match next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    Ok(items)
+}
+
+/// Write-ahead journal of order intents and outcomes, keyed by [`ClientId`].
+///
+/// Every order sent through [`submit`](Self::submit) has its intent persisted before the request
+/// is sent, and its outcome persisted once known. If the process crashes while an order is in
+/// flight, its intent remains pending, and [`recover`](Self::recover) resolves it against the API
+/// on restart.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::{
+///     journal::{FileJournalStorage, OrderIntent, OrderJournal},
+///     models::{ClientId, Leverage, Margin, TradeExecution, TradeSide, TradeSize},
+/// };
+///
+/// let journal = OrderJournal::open(FileJournalStorage::new("orders.journal")).await?;
+///
+/// // On startup, resolve orders left in flight by a previous run
+/// let report = journal.recover(&rest).await?;
+/// for pending in report.not_placed() {
+///     println!("Order {} was never placed", pending.client_id());
+/// }
+/// for pending in report.unresolved() {
+///     println!("Order {} may have been placed and canceled", pending.client_id());
+/// }
+///
+/// let intent = OrderIntent::Isolated {
+///     side: TradeSide::Buy,
+///     size: TradeSize::Margin(Margin::try_from(10_000)?),
+///     leverage: Leverage::try_from(10)?,
+///     execution: TradeExecution::Market,
+///     stoploss: None,
+///     takeprofit: None,
+/// };
+///
+/// let placed = journal
+///     .submit(&rest, ClientId::try_from("order-1")?, intent)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct OrderJournal<S: JournalStorage> {
+    storage: S,
+    orders: Mutex<HashMap<ClientId, OrderState>>,
+    last_prices: Mutex<HashMap<PriceReference, Price>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<S: JournalStorage> OrderJournal<S> {
+    /// Opens a journal, loading the entries persisted in `storage`.
+    pub async fn open(storage: S) -> Result<Self> {
+        let mut orders = HashMap::new();
+
+        for entry in storage.load().await? {
+            let JournalEntry {
+                client_id,
+                recorded_at,
+                event,
+            } = entry;
+
+            let state = match event {
+                JournalEvent::Intent(intent) => {
+                    if orders
+                        .get(&client_id)
+                        .is_some_and(|state| !matches!(state, OrderState::Armed(_)))
+                    {
+                        continue;
+                    }
+                    OrderState::Pending(PendingOrder {
+                        client_id: client_id.clone(),
+                        intent,
+                        recorded_at,
+                    })
+                }
+                JournalEvent::Armed(stop) => {
+                    if orders.contains_key(&client_id) {
+                        continue;
+                    }
+                    OrderState::Armed(ArmedStop {
+                        client_id: client_id.clone(),
+                        stop,
+                        armed_at: recorded_at,
+                    })
+                }
+                JournalEvent::Placed { .. } => OrderState::Placed,
+                JournalEvent::Failed { .. } => OrderState::Failed,
+                JournalEvent::Disarmed => OrderState::Disarmed,
+            };
+
+            orders.insert(client_id, state);
+        }
+
+        Ok(Self {
+            storage,
+            orders: Mutex::new(orders),
+            last_prices: Mutex::new(HashMap::new()),
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    /// Sets the clock entries are timestamped with.
+    ///
+    /// Default: [`SystemClock`]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the underlying storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Returns `true` if `client_id` was already used by a journaled order.
+    pub fn contains(&self, client_id: &ClientId) -> bool {
+        self.orders
+            .lock()
+            .expect("`orders` mutex can't be poisoned")
+            .contains_key(client_id)
+    }
+
+    /// Returns the orders whose outcome is not known yet, oldest first.
+    pub fn pending(&self) -> Vec<PendingOrder> {
+        let mut pending: Vec<PendingOrder> = self
+            .orders
+            .lock()
+            .expect("`orders` mutex can't be poisoned")
+            .values()
+            .filter_map(|state| match state {
+                OrderState::Pending(pending) => Some(pending.clone()),
+                _ => None,
+            })
+            .collect();
+
+        pending.sort_by_key(|pending| pending.recorded_at);
+        pending
+    }
+
+    /// Returns the stop orders armed and not triggered yet, oldest first.
+    pub fn armed_stops(&self) -> Vec<ArmedStop> {
+        let mut armed: Vec<ArmedStop> = self
+            .orders
+            .lock()
+            .expect("`orders` mutex can't be poisoned")
+            .values()
+            .filter_map(|state| match state {
+                OrderState::Armed(armed) => Some(armed.clone()),
+                _ => None,
+            })
+            .collect();
+
+        armed.sort_by_key(|armed| armed.armed_at);
+        armed
+    }
+
+    async fn record_outcome(&self, client_id: ClientId, event: JournalEvent) -> Result<()> {
+        let state = match &event {
+            JournalEvent::Placed { .. } => OrderState::Placed,
+            _ => OrderState::Failed,
+        };
+
+        self.storage
+            .append(&JournalEntry::new_at(
+                client_id.clone(),
+                event,
+                self.clock.now(),
+            ))
+            .await?;
+
+        self.orders
+            .lock()
+            .expect("`orders` mutex can't be poisoned")
+            .insert(client_id, state);
+
+        Ok(())
+    }
+
+    /// Journals `intent` under `client_id`, sends it through `api`, and journals the outcome.
+    ///
+    /// The client ID is attached to the order, and can't be reused. If the request fails without
+    /// a definite rejection (e.g. on timeouts or server errors), the order remains pending until
+    /// resolved by [`recover`](Self::recover).
+    pub async fn submit(
+        &self,
+        api: &dyn LnmFuturesApi,
+        client_id: ClientId,
+        intent: OrderIntent,
+    ) -> Result<PlacedOrder> {
+        let recorded_at = self.clock.now();
+
+        match self
+            .orders
+            .lock()
+            .expect("`orders` mutex can't be poisoned")
+            .entry(client_id.clone())
+        {
+            Entry::Occupied(_) => return Err(JournalError::DuplicateClientId(client_id)),
+            Entry::Vacant(vacant) => {
+                vacant.insert(OrderState::Pending(PendingOrder {
+                    client_id: client_id.clone(),
+                    intent: intent.clone(),
+                    recorded_at,
+                }));
+            }
+        }
+
+        self.send_pending(api, client_id, intent, recorded_at, None)
+            .await
+    }
+
+    /// Journals the intent of an order already marked as pending, sends it through `api`, and
+    /// journals the outcome. If the intent can't be journaled, the order's `previous` state is
+    /// restored.
+    async fn send_pending(
+        &self,
+        api: &dyn LnmFuturesApi,
+        client_id: ClientId,
+        intent: OrderIntent,
+        recorded_at: DateTime<Utc>,
+        previous: Option<OrderState>,
+    ) -> Result<PlacedOrder> {
+        let entry = JournalEntry::new_at(
+            client_id.clone(),
+            JournalEvent::Intent(intent.clone()),
+            recorded_at,
+        );
+
+        if let Err(e) = self.storage.append(&entry).await {
+            let mut orders = self
+                .orders
+                .lock()
+                .expect("`orders` mutex can't be poisoned");
+            match previous {
+                Some(state) => orders.insert(client_id, state),
+                None => orders.remove(&client_id),
+            };
+            return Err(e);
+        }
+
+        let result = intent.send(api, client_id.clone()).await;
+
+        match result {
+            Ok(placed) => {
+                self.record_outcome(client_id, JournalEvent::Placed { id: placed.id() })
+                    .await?;
+                Ok(placed)
+            }
+            Err(e) => {
+                if is_definite_rejection(&e) {
+                    let reason = e.to_string();
+                    self.record_outcome(client_id, JournalEvent::Failed { reason })
+                        .await?;
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Journals `stop` under `client_id`, to be sent by [`observe_price`](Self::observe_price)
+    /// once its condition is met.
+    ///
+    /// The client ID is attached to the order when sent, and can't be reused.
+    pub async fn arm_stop(&self, client_id: ClientId, stop: StopOrder) -> Result<()> {
+        let entry = JournalEntry::new_at(
+            client_id.clone(),
+            JournalEvent::Armed(stop.clone()),
+            self.clock.now(),
+        );
+
+        match self
+            .orders
+            .lock()
+            .expect("`orders` mutex can't be poisoned")
+            .entry(client_id.clone())
+        {
+            Entry::Occupied(_) => return Err(JournalError::DuplicateClientId(client_id)),
+            Entry::Vacant(vacant) => {
+                vacant.insert(OrderState::Armed(ArmedStop {
+                    client_id: client_id.clone(),
+                    stop,
+                    armed_at: entry.recorded_at,
+                }));
+            }
+        }
+
+        if let Err(e) = self.storage.append(&entry).await {
+            self.orders
+                .lock()
+                .expect("`orders` mutex can't be poisoned")
+                .remove(&client_id);
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Cancels the stop order armed under `client_id`. Returns `false` if no stop order is armed
+    /// under it, including when it was already triggered.
+    pub async fn disarm_stop(&self, client_id: &ClientId) -> Result<bool> {
+        let armed = {
+            let mut orders = self
+                .orders
+                .lock()
+                .expect("`orders` mutex can't be poisoned");
+
+            match orders.get(client_id) {
+                Some(OrderState::Armed(_)) => {
+                    orders.insert(client_id.clone(), OrderState::Disarmed)
+                }
+                _ => return Ok(false),
+            }
+        };
+
+        let entry =
+            JournalEntry::new_at(client_id.clone(), JournalEvent::Disarmed, self.clock.now());
+
+        if let Err(e) = self.storage.append(&entry).await {
+            if let Some(armed) = armed {
+                self.orders
+                    .lock()
+                    .expect("`orders` mutex can't be poisoned")
+                    .insert(client_id.clone(), armed);
+            }
+            return Err(e);
+        }
+
+        Ok(true)
+    }
+
+    /// Evaluates the armed stop orders on the `reference` series against `price`, and sends the
+    /// triggered ones through `api`, oldest first.
+    ///
+    /// Should be called with every price observed, e.g. from Stream API updates. Crossing
+    /// conditions are evaluated against the previous price observed on the same series, so they
+    /// can't be met by the first price observed after opening the journal.
+    ///
+    /// Triggered orders are journaled like orders sent through [`submit`](Self::submit). Returns
+    /// the client ID and outcome of each triggered order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(
+    /// #     rest: lnm_sdk::rest::v3::RestClient,
+    /// #     mut receiver: tokio::sync::broadcast::Receiver<lnm_sdk::stream::v1::models::StreamUpdate>,
+    /// # ) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::{
+    ///     journal::{FileJournalStorage, OrderIntent, OrderJournal, StopOrder},
+    ///     models::{
+    ///         ClientId, OrderQuantity, Price, PriceCondition, PriceReference, TradeExecution,
+    ///         TradeSide,
+    ///     },
+    /// };
+    ///
+    /// let journal = OrderJournal::open(FileJournalStorage::new("orders.journal")).await?;
+    ///
+    /// // Sell if the index price drops below 90,000
+    /// let stop = StopOrder::new(
+    ///     PriceCondition::crosses_below(PriceReference::Index, Price::try_from(90_000)?),
+    ///     OrderIntent::Cross {
+    ///         side: TradeSide::Sell,
+    ///         quantity: OrderQuantity::try_from(1_000)?,
+    ///         execution: TradeExecution::Market,
+    ///     },
+    /// )?;
+    /// journal.arm_stop(ClientId::try_from("stop-1")?, stop).await?;
+    ///
+    /// while let Ok(update) = receiver.recv().await {
+    ///     for reference in [PriceReference::Last, PriceReference::Index] {
+    ///         let Some(price) = update.price(reference) else {
+    ///             continue;
+    ///         };
+    ///
+    ///         for (client_id, result) in journal.observe_price(&rest, reference, price).await {
+    ///             println!("Stop order {client_id} triggered: {:?}", result.map(|o| o.id()));
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn observe_price(
+        &self,
+        api: &dyn LnmFuturesApi,
+        reference: PriceReference,
+        price: Price,
+    ) -> Vec<(ClientId, Result<PlacedOrder>)> {
+        let previous = self
+            .last_prices
+            .lock()
+            .expect("`last_prices` mutex can't be poisoned")
+            .insert(reference, price);
+        let recorded_at = self.clock.now();
+
+        // Mark triggered stops as pending under the lock, so each is sent at most once
+        let triggered = {
+            let mut orders = self
+                .orders
+                .lock()
+                .expect("`orders` mutex can't be poisoned");
+
+            let mut triggered: Vec<ArmedStop> = orders
+                .values()
+                .filter_map(|state| match state {
+                    OrderState::Armed(armed)
+                        if armed.stop.condition().reference() == reference
+                            && armed.stop.condition().is_met(previous, price) =>
+                    {
+                        Some(armed.clone())
+                    }
+                    _ => None,
+                })
+                .collect();
+            triggered.sort_by_key(|armed| armed.armed_at);
+
+            for armed in &triggered {
+                orders.insert(
+                    armed.client_id.clone(),
+                    OrderState::Pending(PendingOrder {
+                        client_id: armed.client_id.clone(),
+                        intent: armed.stop.intent().clone(),
+                        recorded_at,
+                    }),
+                );
+            }
+
+            triggered
+        };
+
+        let mut results = Vec::with_capacity(triggered.len());
+
+        for armed in triggered {
+            let client_id = armed.client_id.clone();
+            let intent = armed.stop.intent().clone();
+            let result = self
+                .send_pending(
+                    api,
+                    client_id.clone(),
+                    intent,
+                    recorded_at,
+                    Some(OrderState::Armed(armed)),
+                )
+                .await;
+
+            results.push((client_id, result));
+        }
+
+        results
+    }
+
+    /// Resolves pending orders against the API.
+    ///
+    /// Pending isolated orders are looked up by client ID among open, running, closed and
+    /// canceled trades, and pending cross orders among open and filled cross orders, through
+    /// `api`. Orders found are recorded as placed, and orders not found are recorded as failed.
+    /// Should be called on startup, before any new order is submitted.
+    ///
+    /// Canceled cross orders can't be listed, so a cross limit order that was placed and then
+    /// canceled is not found. Such orders are reported as
+    /// [`unresolved`](RecoveryReport::unresolved) rather than
+    /// [`not_placed`](RecoveryReport::not_placed).
+    ///
+    /// **Required permissions**: `futures:isolated:read`, `futures:cross:read`
+    pub async fn recover(&self, api: &dyn LnmFuturesApi) -> Result<RecoveryReport> {
+        let pending = self.pending();
+        let Some(oldest) = pending.first() else {
+            return Ok(RecoveryReport::default());
+        };
+        let from = oldest.recorded_at - RECOVERY_CLOCK_SKEW;
+
+        let has_isolated = pending
+            .iter()
+            .any(|p| matches!(p.intent, OrderIntent::Isolated { .. }));
+        let has_cross = pending
+            .iter()
+            .any(|p| matches!(p.intent, OrderIntent::Cross { .. }));
+
+        let mut found: HashMap<ClientId, Uuid> = HashMap::new();
+
+        if has_isolated {
+            let mut trades = api.get_open_trades().await?;
+            trades.extend(api.get_running_trades().await?);
+            trades.extend(
+                collect_pages(from, |from, cursor| {
+                    api.get_closed_trades(from, None, None, cursor)
+                })
+                .await?,
+            );
+            trades.extend(
+                collect_pages(from, |from, cursor| {
+                    api.get_canceled_trades(from, None, None, cursor)
+                })
+                .await?,
+            );
+
+            for trade in trades {
+                if let Some(client_id) = trade.client_id() {
+                    found.insert(client_id.clone(), trade.id());
+                }
+            }
+        }
+
+        if has_cross {
+            let mut orders = api.get_open_cross_orders().await?;
+            orders.extend(
+                collect_pages(from, |from, cursor| {
+                    api.get_filled_cross_orders(from, None, None, cursor)
+                })
+                .await?,
+            );
+
+            for order in orders {
+                if let Some(client_id) = order.client_id() {
+                    found.insert(client_id.clone(), order.id());
+                }
+            }
+        }
+
+        let mut report = RecoveryReport::default();
+
+        for pending in pending {
+            let client_id = pending.client_id.clone();
+
+            match found.get(&client_id) {
+                Some(&id) => {
+                    self.record_outcome(client_id.clone(), JournalEvent::Placed { id })
+                        .await?;
+                    report.placed.push((client_id, id));
+                }
+                None => {
+                    let unresolved = matches!(
+                        pending.intent,
+                        OrderIntent::Cross {
+                            execution: TradeExecution::Limit(_),
+                            ..
+                        }
+                    );
+                    let reason = if unresolved {
+                        "not found during recovery, may have been canceled"
+                    } else {
+                        "not found during recovery"
+                    };
+
+                    self.record_outcome(
+                        client_id,
+                        JournalEvent::Failed {
+                            reason: reason.to_string(),
+                        },
+                    )
+                    .await?;
+
+                    if unresolved {
+                        report.unresolved.push(pending);
+                    } else {
+                        report.not_placed.push(pending);
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, num::NonZeroU64};
+
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        rest::v3::{
+            RestClient, RestClientConfig,
+            models::{Account, CrossPosition, Ticker},
+            state_store::{MemoryStateStore, StateStore},
+        },
+        shared::{models::condition::PriceCondition, rest::error::Result as RestResult},
+        testing::fixtures,
+    };
+
+    /// Serves the configured trades and cross orders, with history split in pages.
+    #[derive(Default)]
+    struct HistoryApi {
+        open_trades: Vec<Trade>,
+        closed_trades: Vec<Page<Trade>>,
+        open_cross_orders: Vec<CrossOrder>,
+        filled_cross_orders: Vec<Page<CrossOrder>>,
+    }
+
+    /// Returns the page following the one with `cursor` as next cursor, or the first page.
+    fn page_at<T: Clone>(pages: &[Page<T>], cursor: Option<DateTime<Utc>>) -> Page<T> {
+        let index = match cursor {
+            Some(cursor) => {
+                pages
+                    .iter()
+                    .position(|page| page.next_cursor() == Some(cursor))
+                    .expect("cursor of a served page")
+                    + 1
+            }
+            None => 0,
+        };
+
+        pages
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| Page::new(Vec::new(), None))
+    }
+
+    #[async_trait]
+    impl LnmFuturesApi for HistoryApi {
+        async fn get_ticker(&self) -> RestResult<Ticker> {
+            unimplemented!()
+        }
+
+        async fn get_account(&self) -> RestResult<Account> {
+            unimplemented!()
+        }
+
+        async fn get_open_trades(&self) -> RestResult<Vec<Trade>> {
+            Ok(self.open_trades.clone())
+        }
+
+        async fn get_running_trades(&self) -> RestResult<Vec<Trade>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_closed_trades(
+            &self,
+            _: Option<DateTime<Utc>>,
+            _: Option<DateTime<Utc>>,
+            _: Option<NonZeroU64>,
+            cursor: Option<DateTime<Utc>>,
+        ) -> RestResult<Page<Trade>> {
+            Ok(page_at(&self.closed_trades, cursor))
+        }
+
+        async fn get_canceled_trades(
+            &self,
+            _: Option<DateTime<Utc>>,
+            _: Option<DateTime<Utc>>,
+            _: Option<NonZeroU64>,
+            _: Option<DateTime<Utc>>,
+        ) -> RestResult<Page<Trade>> {
+            Ok(Page::new(Vec::new(), None))
+        }
+
+        async fn new_trade(
+            &self,
+            _: TradeSide,
+            _: TradeSize,
+            _: Leverage,
+            _: TradeExecution,
+            _: Option<Price>,
+            _: Option<Price>,
+            _: Option<ClientId>,
+        ) -> RestResult<Trade> {
+            unimplemented!()
+        }
+
+        async fn close_trade(&self, _: Uuid) -> RestResult<Trade> {
+            unimplemented!()
+        }
+
+        async fn cancel_trade(&self, _: Uuid) -> RestResult<Trade> {
+            unimplemented!()
+        }
+
+        async fn add_margin_to_trade(&self, _: Uuid, _: NonZeroU64) -> RestResult<Trade> {
+            unimplemented!()
+        }
+
+        async fn cash_in_trade(&self, _: Uuid, _: NonZeroU64) -> RestResult<Trade> {
+            unimplemented!()
+        }
+
+        async fn update_stoploss(&self, _: Uuid, _: Option<Price>) -> RestResult<Trade> {
+            unimplemented!()
+        }
+
+        async fn update_takeprofit(&self, _: Uuid, _: Option<Price>) -> RestResult<Trade> {
+            unimplemented!()
+        }
+
+        async fn get_cross_position(&self) -> RestResult<CrossPosition> {
+            unimplemented!()
+        }
+
+        async fn get_open_cross_orders(&self) -> RestResult<Vec<CrossOrder>> {
+            Ok(self.open_cross_orders.clone())
+        }
+
+        async fn get_filled_cross_orders(
+            &self,
+            _: Option<DateTime<Utc>>,
+            _: Option<DateTime<Utc>>,
+            _: Option<NonZeroU64>,
+            cursor: Option<DateTime<Utc>>,
+        ) -> RestResult<Page<CrossOrder>> {
+            Ok(page_at(&self.filled_cross_orders, cursor))
+        }
+
+        async fn place_cross_order(
+            &self,
+            _: TradeSide,
+            _: OrderQuantity,
+            _: TradeExecution,
+            _: Option<ClientId>,
+        ) -> RestResult<CrossOrder> {
+            unimplemented!()
+        }
+
+        async fn cancel_cross_order(&self, _: Uuid) -> RestResult<CrossOrder> {
+            unimplemented!()
+        }
+
+        async fn close_cross_position(&self) -> RestResult<CrossOrder> {
+            unimplemented!()
+        }
+    }
+
+    fn trade(id: u128, client_id: &str) -> Trade {
+        let mut trade = fixtures::trade_json();
+        trade["id"] = json!(Uuid::from_u128(id));
+        trade["clientId"] = json!(client_id);
+        serde_json::from_value(trade).unwrap()
+    }
+
+    fn cross_order(id: u128, client_id: &str) -> CrossOrder {
+        let mut order = fixtures::cross_order_json();
+        order["id"] = json!(Uuid::from_u128(id));
+        order["clientId"] = json!(client_id);
+        serde_json::from_value(order).unwrap()
+    }
+
+    /// Opens a journal with the intents of `isolated` and `cross` orders pending.
+    async fn pending_journal(
+        isolated: &[&str],
+        cross: &[&str],
+    ) -> OrderJournal<MemoryJournalStorage> {
+        let storage = MemoryJournalStorage::new();
+        let intents = isolated
+            .iter()
+            .map(|id| (id, isolated_intent()))
+            .chain(cross.iter().map(|id| (id, cross_intent())));
+        for (id, intent) in intents {
+            let entry =
+                JournalEntry::new_at(client_id(id), JournalEvent::Intent(intent), Utc::now());
+            storage.append(&entry).await.unwrap();
+        }
+
+        OrderJournal::open(storage).await.unwrap()
+    }
+
+    fn isolated_intent() -> OrderIntent {
+        OrderIntent::Isolated {
+            side: TradeSide::Buy,
+            size: TradeSize::Margin(Margin::try_from(10_000).unwrap()),
+            leverage: Leverage::try_from(10).unwrap(),
+            execution: TradeExecution::Limit(Price::try_from(100_000).unwrap()),
+            stoploss: None,
+            takeprofit: Some(Price::try_from(110_000).unwrap()),
+        }
+    }
+
+    fn cross_intent() -> OrderIntent {
+        OrderIntent::Cross {
+            side: TradeSide::Sell,
+            quantity: OrderQuantity::try_from(100).unwrap(),
+            execution: TradeExecution::Market,
+        }
+    }
+
+    fn client_id(value: &str) -> ClientId {
+        ClientId::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_entry_serde_round_trip() {
+        for event in [
+            JournalEvent::Intent(isolated_intent()),
+            JournalEvent::Intent(cross_intent()),
+            JournalEvent::Placed { id: Uuid::nil() },
+            JournalEvent::Failed {
+                reason: "rejected".into(),
+            },
+            JournalEvent::Armed(
+                StopOrder::new(
+                    PriceCondition::below(PriceReference::Last, Price::try_from(90_000).unwrap()),
+                    cross_intent(),
+                )
+                .unwrap(),
+            ),
+            JournalEvent::Disarmed,
+        ] {
+            let entry = JournalEntry::new_at(client_id("order-1"), event, Utc::now());
+            let json = serde_json::to_string(&entry).unwrap();
+
+            assert_eq!(serde_json::from_str::<JournalEntry>(&json).unwrap(), entry);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_folds_outcomes() {
+        let storage = MemoryJournalStorage::new();
+        for entry in [
+            JournalEntry::new_at(
+                client_id("a"),
+                JournalEvent::Intent(isolated_intent()),
+                Utc::now(),
+            ),
+            JournalEntry::new_at(
+                client_id("b"),
+                JournalEvent::Intent(cross_intent()),
+                Utc::now(),
+            ),
+            JournalEntry::new_at(
+                client_id("a"),
+                JournalEvent::Placed { id: Uuid::nil() },
+                Utc::now(),
+            ),
+        ] {
+            storage.append(&entry).await.unwrap();
+        }
+
+        let journal = OrderJournal::open(storage).await.unwrap();
+        let pending = journal.pending();
+
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].client_id(), &client_id("b"));
+        assert_eq!(pending[0].intent(), &cross_intent());
+        assert!(journal.contains(&client_id("a")));
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_reused_client_id() {
+        let storage = MemoryJournalStorage::new();
+        let entry = JournalEntry::new_at(
+            client_id("a"),
+            JournalEvent::Intent(isolated_intent()),
+            Utc::now(),
+        );
+        storage.append(&entry).await.unwrap();
+
+        let journal = OrderJournal::open(storage).await.unwrap();
+        let rest = RestClient::new(RestClientConfig::default()).unwrap();
+
+        let result = journal.submit(&rest, client_id("a"), cross_intent()).await;
+
+        assert!(matches!(result, Err(JournalError::DuplicateClientId(_))));
+        assert_eq!(journal.storage().load().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stop_orders() {
+        let condition =
+            PriceCondition::crosses_below(PriceReference::Index, Price::try_from(90_000).unwrap());
+        let stop = StopOrder::new(condition, cross_intent()).unwrap();
+
+        assert!(matches!(
+            StopOrder::new(condition, isolated_intent()),
+            Err(StopOrderValidationError::NotMarketExecution)
+        ));
+
+        let journal = OrderJournal::open(MemoryJournalStorage::new())
+            .await
+            .unwrap();
+        journal
+            .arm_stop(client_id("stop-a"), stop.clone())
+            .await
+            .unwrap();
+        journal
+            .arm_stop(client_id("stop-b"), stop.clone())
+            .await
+            .unwrap();
+        assert!(matches!(
+            journal.arm_stop(client_id("stop-a"), stop.clone()).await,
+            Err(JournalError::DuplicateClientId(_))
+        ));
+        assert!(journal.disarm_stop(&client_id("stop-b")).await.unwrap());
+        assert!(!journal.disarm_stop(&client_id("stop-b")).await.unwrap());
+
+        // Armed stops survive reopening the journal
+        let storage = MemoryJournalStorage::new();
+        for entry in journal.storage().load().await.unwrap() {
+            storage.append(&entry).await.unwrap();
+        }
+        let journal = OrderJournal::open(storage).await.unwrap();
+        let armed = journal.armed_stops();
+        assert_eq!(armed.len(), 1);
+        assert_eq!(armed[0].client_id(), &client_id("stop-a"));
+        assert_eq!(armed[0].stop(), &stop);
+
+        // No credentials, so the triggered order is rejected before being sent
+        let rest = RestClient::new(RestClientConfig::default()).unwrap();
+        let price = |value: i32| Price::try_from(value).unwrap();
+
+        let reference = PriceReference::Index;
+        assert!(
+            journal
+                .observe_price(&rest, reference, price(91_000))
+                .await
+                .is_empty()
+        );
+        assert!(
+            journal
+                .observe_price(&rest, PriceReference::Last, price(89_000))
+                .await
+                .is_empty()
+        );
+
+        let triggered = journal.observe_price(&rest, reference, price(89_000)).await;
+        assert_eq!(triggered.len(), 1);
+        assert_eq!(triggered[0].0, client_id("stop-a"));
+        assert!(triggered[0].1.is_err());
+
+        assert!(journal.armed_stops().is_empty());
+        assert!(journal.pending().is_empty());
+        assert!(
+            journal
+                .observe_price(&rest, reference, price(91_000))
+                .await
+                .is_empty()
+        );
+        assert!(
+            journal
+                .observe_price(&rest, reference, price(89_000))
+                .await
+                .is_empty()
+        );
+
+        let entries = journal.storage().load().await.unwrap();
+        assert!(matches!(
+            entries.last().unwrap().event(),
+            JournalEvent::Failed { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_state_store_storage_continues_sequence() {
+        let store = Arc::new(MemoryStateStore::new());
+        let entries: Vec<_> = ["a", "b", "c"]
+            .into_iter()
+            .map(|id| {
+                JournalEntry::new_at(
+                    client_id(id),
+                    JournalEvent::Intent(cross_intent()),
+                    Utc::now(),
+                )
+            })
+            .collect();
+
+        let storage = StateStoreJournalStorage::new(store.clone(), "journal/");
+        storage.append(&entries[0]).await.unwrap();
+        storage.append(&entries[1]).await.unwrap();
+
+        // A new storage over the same store appends after the stored entries
+        let storage = StateStoreJournalStorage::new(store.clone(), "journal/");
+        storage.append(&entries[2]).await.unwrap();
+
+        assert_eq!(storage.load().await.unwrap(), entries);
+        assert!(store.scan("other/").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_file_storage_ignores_torn_last_line() {
+        let path = env::temp_dir().join(format!("lnm-sdk-journal-{}.jsonl", Uuid::new_v4()));
+        let storage = FileJournalStorage::new(&path);
+
+        assert!(storage.load().await.unwrap().is_empty());
+
+        let entry = JournalEntry::new_at(
+            client_id("a"),
+            JournalEvent::Intent(cross_intent()),
+            Utc::now(),
+        );
+        storage.append(&entry).await.unwrap();
+
+        let mut content = fs::read_to_string(&path).unwrap();
+        content.push_str(r#"{"clientId":"b","recordedAt":"#);
+        fs::write(&path, content).unwrap();
+
+        assert_eq!(storage.load().await.unwrap(), vec![entry]);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recover_resolves_pending_orders() {
+        let journal = pending_journal(&["iso-a"], &["cross-b"]).await;
+        let api = HistoryApi {
+            open_trades: vec![trade(1, "iso-a"), trade(2, "other")],
+            ..Default::default()
+        };
+
+        let report = journal.recover(&api).await.unwrap();
+
+        assert_eq!(report.placed(), [(client_id("iso-a"), Uuid::from_u128(1))]);
+        assert_eq!(report.not_placed().len(), 1);
+        assert_eq!(report.not_placed()[0].client_id(), &client_id("cross-b"));
+        assert!(journal.pending().is_empty());
+
+        let entries = journal.storage().load().await.unwrap();
+        let events: Vec<_> = entries[2..]
+            .iter()
+            .map(|entry| (entry.client_id().clone(), entry.event().clone()))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (
+                    client_id("iso-a"),
+                    JournalEvent::Placed {
+                        id: Uuid::from_u128(1)
+                    }
+                ),
+                (
+                    client_id("cross-b"),
+                    JournalEvent::Failed {
+                        reason: "not found during recovery".to_string()
+                    }
+                ),
+            ]
+        );
+
+        // Nothing left to recover
+        let report = journal.recover(&api).await.unwrap();
+        assert!(report.placed().is_empty());
+        assert!(report.not_placed().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recover_follows_pagination() {
+        let journal = pending_journal(&["iso-c"], &["cross-d"]).await;
+        let cursor = |hour| Utc::now() - Duration::hours(hour);
+        let api = HistoryApi {
+            closed_trades: vec![
+                Page::new(vec![trade(3, "other-1")], Some(cursor(2))),
+                Page::new(vec![trade(4, "iso-c")], None),
+            ],
+            open_cross_orders: vec![cross_order(5, "other-2")],
+            filled_cross_orders: vec![
+                Page::new(vec![cross_order(6, "other-3")], Some(cursor(3))),
+                Page::new(vec![cross_order(7, "other-4")], Some(cursor(4))),
+                Page::new(vec![cross_order(8, "cross-d")], None),
+            ],
+            ..Default::default()
+        };
+
+        let report = journal.recover(&api).await.unwrap();
+
+        assert_eq!(
+            report.placed(),
+            [
+                (client_id("iso-c"), Uuid::from_u128(4)),
+                (client_id("cross-d"), Uuid::from_u128(8)),
+            ]
+        );
+        assert!(report.not_placed().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recover_reports_canceled_cross_limit_orders_as_unresolved() {
+        let storage = MemoryJournalStorage::new();
+        let intent = OrderIntent::Cross {
+            side: TradeSide::Buy,
+            quantity: OrderQuantity::try_from(100).unwrap(),
+            execution: TradeExecution::Limit(Price::try_from(90_000).unwrap()),
+        };
+        let entry = JournalEntry::new_at(
+            client_id("cross-limit"),
+            JournalEvent::Intent(intent),
+            Utc::now(),
+        );
+        storage.append(&entry).await.unwrap();
+        let journal = OrderJournal::open(storage).await.unwrap();
+
+        // Placed, then canceled, so neither open nor filled
+        let report = journal.recover(&HistoryApi::default()).await.unwrap();
+
+        assert!(report.placed().is_empty());
+        assert!(report.not_placed().is_empty());
+        assert_eq!(report.unresolved().len(), 1);
+        assert_eq!(
+            report.unresolved()[0].client_id(),
+            &client_id("cross-limit")
+        );
+        assert!(journal.pending().is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::models::{
+    client_id::ClientId, condition::PriceCondition, trade::TradeExecution,
+};
+
+use super::{OrderIntent, StopOrderValidationError};
+
+/// Client-side stop-market order: a market order sent once a price condition is met.
+///
+/// Armed with [`OrderJournal::arm_stop`](super::OrderJournal::arm_stop), which persists it in the
+/// journal so it survives restarts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopOrder {
+    condition: PriceCondition,
+    intent: OrderIntent,
+}
+
+impl StopOrder {
+    /// Creates a stop order sending `intent` once `condition` is met.
+    ///
+    /// Returns an error if `intent` is not a market order.
+    pub fn new(
+        condition: PriceCondition,
+        intent: OrderIntent,
+    ) -> Result<Self, StopOrderValidationError> {
+        let execution = match &intent {
+            OrderIntent::Isolated { execution, .. } | OrderIntent::Cross { execution, .. } => {
+                *execution
+            }
+        };
+
+        if !matches!(execution, TradeExecution::Market) {
+            return Err(StopOrderValidationError::NotMarketExecution);
+        }
+
+        Ok(Self { condition, intent })
+    }
+
+    /// Condition triggering the order.
+    pub fn condition(&self) -> &PriceCondition {
+        &self.condition
+    }
+
+    /// Market order sent when triggered.
+    pub fn intent(&self) -> &OrderIntent {
+        &self.intent
+    }
+}
+
+/// Stop order armed in the journal and not triggered yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArmedStop {
+    pub(super) client_id: ClientId,
+    pub(super) stop: StopOrder,
+    pub(super) armed_at: DateTime<Utc>,
+}
+
+impl ArmedStop {
+    /// Client ID the order is sent with when triggered.
+    pub fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    /// Armed stop order.
+    pub fn stop(&self) -> &StopOrder {
+        &self.stop
+    }
+
+    /// Timestamp when the stop order was armed.
+    pub fn armed_at(&self) -> DateTime<Utc> {
+        self.armed_at
+    }
+}
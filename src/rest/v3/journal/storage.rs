@@ -0,0 +1,258 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use tokio::{
+    fs::{self, OpenOptions},
+    io::AsyncWriteExt,
+};
+
+use crate::rest::v3::state_store::StateStore;
+
+use super::{
+    JournalEntry,
+    error::{JournalError, Result},
+};
+
+/// Durable storage for [`OrderJournal`](super::OrderJournal) entries.
+///
+/// Implementations must persist each entry before [`append`](Self::append) returns, and return
+/// all persisted entries, in append order, from [`load`](Self::load).
+#[async_trait]
+pub trait JournalStorage: Send + Sync {
+    /// Durably appends an entry.
+    async fn append(&self, entry: &JournalEntry) -> Result<()>;
+
+    /// Loads all entries, in the order they were appended.
+    async fn load(&self) -> Result<Vec<JournalEntry>>;
+}
+
+/// [`JournalStorage`] backed by a JSON Lines file.
+///
+/// Each entry is written as a single line and synced to disk before `append` returns. A truncated
+/// last line, left by a crash in the middle of a write, is ignored on load.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::journal::{FileJournalStorage, OrderJournal};
+///
+/// let journal = OrderJournal::open(FileJournalStorage::new("orders.journal")).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileJournalStorage {
+    path: PathBuf,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl FileJournalStorage {
+    /// Creates a storage using the file at `path`. The file is created on the first append.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Path of the journal file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn io_error(&self, e: std::io::Error) -> JournalError {
+        JournalError::Io {
+            path: self.path.clone(),
+            e,
+        }
+    }
+}
+
+#[async_trait]
+impl JournalStorage for FileJournalStorage {
+    async fn append(&self, entry: &JournalEntry) -> Result<()> {
+        let mut line = serde_json::to_vec(entry).map_err(JournalError::EntrySerialize)?;
+        line.push(b'\n');
+
+        let _guard = self.lock.lock().await;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| self.io_error(e))?;
+
+        file.write_all(&line).await.map_err(|e| self.io_error(e))?;
+        file.sync_data().await.map_err(|e| self.io_error(e))
+    }
+
+    async fn load(&self) -> Result<Vec<JournalEntry>> {
+        let _guard = self.lock.lock().await;
+
+        let content = match fs::read_to_string(&self.path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(self.io_error(e)),
+        };
+
+        let complete = content.ends_with('\n');
+        let lines: Vec<&str> = content.lines().collect();
+        let mut entries = Vec::with_capacity(lines.len());
+
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                // Torn write of the last entry
+                Err(_) if !complete && i == lines.len() - 1 => break,
+                Err(e) => return Err(JournalError::EntryParse { line: i + 1, e }),
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+/// In-memory [`JournalStorage`], not persisted across restarts. Useful for tests.
+#[derive(Debug, Default)]
+pub struct MemoryJournalStorage {
+    entries: Mutex<Vec<JournalEntry>>,
+}
+
+impl MemoryJournalStorage {
+    /// Creates an empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl JournalStorage for MemoryJournalStorage {
+    async fn append(&self, entry: &JournalEntry) -> Result<()> {
+        self.entries
+            .lock()
+            .expect("`entries` mutex can't be poisoned")
+            .push(entry.clone());
+
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Vec<JournalEntry>> {
+        Ok(self
+            .entries
+            .lock()
+            .expect("`entries` mutex can't be poisoned")
+            .clone())
+    }
+}
+
+/// [`JournalStorage`] backed by a [`StateStore`], with one key per entry.
+///
+/// Entries are stored at `{prefix}{sequence}`, the sequence number being zero-padded so entries
+/// scan in append order.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::{
+///     journal::{OrderJournal, StateStoreJournalStorage},
+///     state_store::MemoryStateStore,
+/// };
+///
+/// let storage = StateStoreJournalStorage::new(MemoryStateStore::new(), "journal/");
+/// let journal = OrderJournal::open(storage).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StateStoreJournalStorage<S: StateStore> {
+    store: S,
+    prefix: String,
+    // Sequence number of the next entry, loaded from the store on the first append
+    next_seq: tokio::sync::Mutex<Option<u64>>,
+}
+
+impl<S: StateStore> StateStoreJournalStorage<S> {
+    /// Creates a storage keeping entries in `store`, under keys starting with `prefix`.
+    pub fn new(store: S, prefix: impl Into<String>) -> Self {
+        Self {
+            store,
+            prefix: prefix.into(),
+            next_seq: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Returns the underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Prefix of the keys of the records.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn storage_error(e: impl std::fmt::Display) -> JournalError {
+        JournalError::Storage(e.to_string())
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> JournalStorage for StateStoreJournalStorage<S> {
+    async fn append(&self, entry: &JournalEntry) -> Result<()> {
+        let value = serde_json::to_vec(entry).map_err(JournalError::EntrySerialize)?;
+
+        let mut next_seq = self.next_seq.lock().await;
+
+        let seq = match *next_seq {
+            Some(seq) => seq,
+            None => {
+                let stored = self
+                    .store
+                    .scan(&self.prefix)
+                    .await
+                    .map_err(Self::storage_error)?;
+
+                stored
+                    .last()
+                    .and_then(|(key, _)| key[self.prefix.len()..].parse::<u64>().ok())
+                    .map_or(0, |last| last + 1)
+            }
+        };
+
+        self.store
+            .put(&format!("{}{seq:020}", self.prefix), &value)
+            .await
+            .map_err(Self::storage_error)?;
+        *next_seq = Some(seq + 1);
+
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Vec<JournalEntry>> {
+        let stored = self
+            .store
+            .scan(&self.prefix)
+            .await
+            .map_err(Self::storage_error)?;
+
+        let mut entries = Vec::with_capacity(stored.len());
+        for (key, value) in stored {
+            let entry = serde_json::from_slice(&value).map_err(|e| {
+                JournalError::Storage(format!("entry {key} could not be parsed: {e}"))
+            })?;
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+}
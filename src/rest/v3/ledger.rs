@@ -0,0 +1,307 @@
+//! Ledger of balance changes derived from deposits, withdrawals, fees and realized PL.
+//!
+//! Accounting systems usually need a single ordered list of the movements of the sats balance.
+//! LN Markets reports them across separate endpoints (withdrawals, trades, cross orders and
+//! funding fees), which [`BalanceLedger`] merges into chronological [`BalanceChange`]s, each with
+//! the running balance after it was applied.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::models::{
+    CrossFunding, CrossOrder, IsolatedFunding, LightningWithdrawal, OnchainWithdrawal, Trade,
+};
+
+/// Origin of a [`BalanceChange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "camelCase")]
+pub enum BalanceChangeSource {
+    Deposit,
+    /// Withdrawal, identified by the withdrawal ID.
+    Withdrawal(Uuid),
+    /// Opening or closing fee of an isolated trade, or trading fee of a cross order, identified
+    /// by the trade or order ID.
+    TradingFee(Uuid),
+    /// Funding fee, identified by the funding settlement ID.
+    FundingFee(Uuid),
+    /// PL realized when closing an isolated trade, identified by the trade ID.
+    RealizedPl(Uuid),
+}
+
+/// Single movement of the sats balance, with the running balance after it was applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    time: DateTime<Utc>,
+    source: BalanceChangeSource,
+    amount: i64,
+    balance: i64,
+}
+
+impl BalanceChange {
+    /// Timestamp of the change.
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+
+    /// Origin of the change.
+    pub fn source(&self) -> BalanceChangeSource {
+        self.source
+    }
+
+    /// Signed amount of the change, in sats. Positive when credited, negative when debited.
+    pub fn amount(&self) -> i64 {
+        self.amount
+    }
+
+    /// Running balance after the change was applied, in sats.
+    ///
+    /// Can be negative if the ledger is missing earlier credits, such as deposits not added to it.
+    pub fn balance(&self) -> i64 {
+        self.balance
+    }
+}
+
+/// Builder of an ordered ledger of [`BalanceChange`]s, starting from an opening balance.
+///
+/// Changes are ordered by time. Changes with the same time keep the order they were added in.
+/// Entries with a zero amount are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::ledger::BalanceLedger;
+///
+/// let closed = rest.futures_isolated.get_closed_trades(None, None, None, None).await?;
+/// let funding = rest.futures_isolated.get_funding_fees(None, None, None, None).await?;
+///
+/// let changes = BalanceLedger::new(0)
+///     .with_trades(closed.data())
+///     .with_isolated_funding(funding.data())
+///     .build();
+///
+/// for change in changes {
+///     println!(
+///         "{} {:?}: {} sats, balance {} sats",
+///         change.time(),
+///         change.source(),
+///         change.amount(),
+///         change.balance()
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BalanceLedger {
+    opening_balance: i64,
+    entries: Vec<(DateTime<Utc>, BalanceChangeSource, i64)>,
+}
+
+impl BalanceLedger {
+    /// Creates an empty ledger, starting from `opening_balance` sats.
+    pub fn new(opening_balance: i64) -> Self {
+        Self {
+            opening_balance,
+            entries: Vec::new(),
+        }
+    }
+
+    fn with_entry(mut self, time: DateTime<Utc>, source: BalanceChangeSource, amount: i64) -> Self {
+        if amount != 0 {
+            self.entries.push((time, source, amount));
+        }
+        self
+    }
+
+    /// Adds a deposit of `sats`.
+    pub fn with_deposit(self, time: DateTime<Utc>, sats: u64) -> Self {
+        self.with_entry(time, BalanceChangeSource::Deposit, sats as i64)
+    }
+
+    /// Adds a withdrawal of `sats`, identified by `id`.
+    pub fn with_withdrawal(self, time: DateTime<Utc>, id: Uuid, sats: u64) -> Self {
+        self.with_entry(time, BalanceChangeSource::Withdrawal(id), -(sats as i64))
+    }
+
+    /// Adds a Lightning withdrawal, debiting its amount when it was created.
+    ///
+    /// The routing fees actually paid are not reported by the API, so the `max_fees` reserve is
+    /// not included.
+    pub fn with_lightning_withdrawal(self, withdrawal: &LightningWithdrawal) -> Self {
+        self.with_withdrawal(
+            withdrawal.created_at(),
+            withdrawal.id(),
+            withdrawal.amount().as_u64(),
+        )
+    }
+
+    /// Adds an on-chain withdrawal, debiting its amount when it was created.
+    pub fn with_onchain_withdrawal(self, withdrawal: &OnchainWithdrawal) -> Self {
+        self.with_withdrawal(
+            withdrawal.created_at(),
+            withdrawal.id(),
+            withdrawal.amount().as_u64(),
+        )
+    }
+
+    /// Adds the fees and realized PL of isolated trades.
+    ///
+    /// The opening fee is debited when the trade was filled. For closed trades, the closing fee
+    /// and the PL are applied when the trade was closed. Open (unfilled) and canceled trades are
+    /// ignored. Funding fees are added separately, with
+    /// [`with_isolated_funding`](Self::with_isolated_funding).
+    pub fn with_trades<'a>(mut self, trades: impl IntoIterator<Item = &'a Trade>) -> Self {
+        for trade in trades {
+            if !trade.running() && !trade.closed() {
+                continue;
+            }
+
+            let id = trade.id();
+            let filled_at = trade.filled_at().unwrap_or(trade.created_at());
+
+            self = self.with_entry(
+                filled_at,
+                BalanceChangeSource::TradingFee(id),
+                -trade.opening_fee().as_i64(),
+            );
+
+            if trade.closed() {
+                let closed_at = trade.closed_at().unwrap_or(filled_at);
+
+                self = self
+                    .with_entry(
+                        closed_at,
+                        BalanceChangeSource::TradingFee(id),
+                        -trade.closing_fee().as_i64(),
+                    )
+                    .with_entry(
+                        closed_at,
+                        BalanceChangeSource::RealizedPl(id),
+                        trade.pl().as_i64(),
+                    );
+            }
+        }
+
+        self
+    }
+
+    /// Adds the trading fees of filled cross orders, debited when they were filled. Unfilled
+    /// orders are ignored.
+    pub fn with_cross_orders<'a>(
+        mut self,
+        orders: impl IntoIterator<Item = &'a CrossOrder>,
+    ) -> Self {
+        for order in orders {
+            if let Some(filled_at) = order.filled_at() {
+                self = self.with_entry(
+                    filled_at,
+                    BalanceChangeSource::TradingFee(order.id()),
+                    -order.trading_fee().as_i64(),
+                );
+            }
+        }
+
+        self
+    }
+
+    /// Adds isolated margin funding fees. Fees paid are debited, and fees received credited.
+    pub fn with_isolated_funding<'a>(
+        mut self,
+        funding: impl IntoIterator<Item = &'a IsolatedFunding>,
+    ) -> Self {
+        for entry in funding {
+            self = self.with_entry(
+                entry.time(),
+                BalanceChangeSource::FundingFee(entry.settlement_id()),
+                -entry.fee().as_i64(),
+            );
+        }
+
+        self
+    }
+
+    /// Adds cross margin funding fees. Fees paid are debited, and fees received credited.
+    pub fn with_cross_funding<'a>(
+        mut self,
+        funding: impl IntoIterator<Item = &'a CrossFunding>,
+    ) -> Self {
+        for entry in funding {
+            self = self.with_entry(
+                entry.time(),
+                BalanceChangeSource::FundingFee(entry.settlement_id()),
+                -entry.fee().as_i64(),
+            );
+        }
+
+        self
+    }
+
+    /// Returns the balance changes, oldest first, with the running balance after each of them.
+    pub fn build(&self) -> Vec<BalanceChange> {
+        let mut entries = self.entries.clone();
+        entries.sort_by_key(|(time, _, _)| *time);
+
+        let mut balance = self.opening_balance;
+
+        entries
+            .into_iter()
+            .map(|(time, source, amount)| {
+                balance += amount;
+                BalanceChange {
+                    time,
+                    source,
+                    amount,
+                    balance,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rest::v3::models::Sats, testing::fixtures};
+
+    fn time(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_balance_ledger() {
+        let closed = fixtures::closed_trade();
+        let canceled = fixtures::canceled_trade();
+        let order = fixtures::filled_cross_order();
+
+        let changes = BalanceLedger::new(1_000)
+            .with_deposit(time("2025-05-12T00:00:00Z"), 500_000)
+            .with_trades([&closed, &canceled])
+            .with_cross_orders([&order])
+            .with_withdrawal(time("2025-06-01T00:00:00Z"), Uuid::nil(), 100_000)
+            .build();
+
+        let sources: Vec<_> = changes.iter().map(BalanceChange::source).collect();
+        assert_eq!(
+            sources,
+            [
+                BalanceChangeSource::Deposit,
+                BalanceChangeSource::TradingFee(closed.id()),
+                BalanceChangeSource::TradingFee(closed.id()),
+                BalanceChangeSource::RealizedPl(closed.id()),
+                BalanceChangeSource::TradingFee(order.id()),
+                BalanceChangeSource::Withdrawal(Uuid::nil()),
+            ]
+        );
+
+        let expected = Sats::new(1_000 + 500_000) - closed.opening_fee() - closed.closing_fee()
+            + closed.pl()
+            - order.trading_fee()
+            - Sats::new(100_000);
+        assert_eq!(changes.last().unwrap().balance(), expected.as_i64());
+        assert_eq!(changes[0].balance(), 501_000);
+        assert_eq!(changes[3].amount(), closed.pl().as_i64());
+    }
+}
@@ -2,11 +2,15 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use reqwest::Method;
+use serde::de::IgnoredAny;
 
-use crate::shared::rest::{error::Result, lnm::base::LnmRestBase};
+use crate::shared::rest::{error::Result, lnm::base::LnmRestBase, query::QueryParams};
 
 use super::{
-    super::{models::account::Account, repositories::AccountRepository},
+    super::{
+        models::{account::Account, notification::Notification},
+        repositories::AccountRepository,
+    },
     path::RestPathV3,
     signature::SignatureGeneratorV3,
 };
@@ -30,6 +34,32 @@ impl AccountRepository for LnmAccountRepository {
             .make_request_without_params(Method::GET, RestPathV3::Account, true)
             .await
     }
+
+    async fn get_notifications(&self, read: Option<bool>) -> Result<Vec<Notification>> {
+        let query_params = QueryParams::new().with_opt("read", read);
+
+        self.base
+            .make_request_with_query_params(
+                Method::GET,
+                RestPathV3::AccountNotifications,
+                query_params,
+                true,
+            )
+            .await
+    }
+
+    async fn mark_notifications_read(&self) -> Result<()> {
+        let _: IgnoredAny = self
+            .base
+            .make_request_without_params(
+                Method::POST,
+                RestPathV3::AccountNotificationsReadAll,
+                true,
+            )
+            .await?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
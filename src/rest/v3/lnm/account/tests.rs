@@ -24,6 +24,7 @@ fn init_repository_from_env(rate_limiter: Option<RateLimiter>) -> LnmAccountRepo
         passphrase,
         SignatureGeneratorV3::new(secret),
         rate_limiter,
+        config.clock().clone(),
     )
     .expect("Can create `LnmApiBase`");
 
@@ -49,6 +50,20 @@ async fn test_api() {
     // Start tests
 
     let _ = time_test!("test_get_account", repo.get_account().await);
+
+    let _ = time_test!(
+        "test_get_notifications",
+        repo.get_notifications(None)
+            .await
+            .expect("must get notifications")
+    );
+
+    let _ = time_test!(
+        "test_get_notifications (read)",
+        repo.get_notifications(Some(true))
+            .await
+            .expect("must get read notifications")
+    );
 }
 
 // Fires 30 concurrent `get_account` requests through a rate-limited client.
@@ -1,7 +1,7 @@
 use std::{num::NonZeroU64, sync::Arc};
 
 use async_trait::async_trait;
-use chrono::{DateTime, SecondsFormat, Utc};
+use chrono::{DateTime, Utc};
 use hyper::Method;
 use serde_json::json;
 use uuid::Uuid;
@@ -13,13 +13,15 @@ use crate::shared::{
         quantity::order::OrderQuantity,
         trade::{TradeExecution, TradeSide},
     },
-    rest::{error::Result, lnm::base::LnmRestBase},
+    rest::{error::Result, lnm::base::LnmRestBase, query::QueryParams},
 };
 
 use super::{
     super::{
+        error::RestApiV3Error,
         models::{
             funding::CrossFunding,
+            limits::ExchangeLimits,
             page::Page,
             trade::{CrossOrder, CrossPosition, FuturesCrossOrderBody},
             transfer::CrossTransfer,
@@ -32,14 +34,34 @@ use super::{
 
 pub(in crate::rest::v3) struct LnmFuturesCrossRepository {
     base: Arc<LnmRestBase<SignatureGeneratorV3>>,
+    limits: ExchangeLimits,
 }
 
 impl LnmFuturesCrossRepository {
-    pub fn new(base: Arc<LnmRestBase<SignatureGeneratorV3>>) -> Self {
-        Self { base }
+    pub fn new(base: Arc<LnmRestBase<SignatureGeneratorV3>>, limits: ExchangeLimits) -> Self {
+        Self { base, limits }
     }
 }
 
+/// Validates a new cross order against `limits` and builds its request body. Shared with
+/// [`FastLane`](crate::rest::v3::FastLane).
+pub(in crate::rest::v3) fn place_order_body(
+    limits: &ExchangeLimits,
+    side: TradeSide,
+    quantity: OrderQuantity,
+    execution: TradeExecution,
+    client_id: Option<ClientId>,
+) -> Result<FuturesCrossOrderBody> {
+    limits
+        .validate_quantity(quantity)
+        .and_then(|_| limits.validate_execution(execution))
+        .map_err(RestApiV3Error::ExchangeLimitsValidation)?;
+
+    Ok(FuturesCrossOrderBody::new(
+        side, quantity, execution, client_id,
+    ))
+}
+
 impl crate::sealed::Sealed for LnmFuturesCrossRepository {}
 
 #[async_trait]
@@ -72,7 +94,12 @@ impl FuturesCrossRepository for LnmFuturesCrossRepository {
         execution: TradeExecution,
         client_id: Option<ClientId>,
     ) -> Result<CrossOrder> {
-        let body = FuturesCrossOrderBody::new(side, quantity, execution, client_id);
+        let body = place_order_body(&self.limits, side, quantity, execution, client_id)?;
+
+        self.base.check_price_freshness(execution)?;
+        self.base
+            .check_maintenance(RestPathV3::UtilitiesPing)
+            .await?;
 
         self.base
             .make_request_with_body(Method::POST, RestPathV3::FuturesCrossOrder, body, true)
@@ -98,23 +125,7 @@ impl FuturesCrossRepository for LnmFuturesCrossRepository {
         limit: Option<NonZeroU64>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Page<CrossOrder>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
 
         self.base
             .make_request_with_query_params(
@@ -139,23 +150,7 @@ impl FuturesCrossRepository for LnmFuturesCrossRepository {
         limit: Option<NonZeroU64>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Page<CrossFunding>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
 
         self.base
             .make_request_with_query_params(
@@ -174,23 +169,7 @@ impl FuturesCrossRepository for LnmFuturesCrossRepository {
         limit: Option<NonZeroU64>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Page<CrossTransfer>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
 
         self.base
             .make_request_with_query_params(
@@ -3,7 +3,7 @@ use std::{env, time::Instant};
 use dotenvy::dotenv;
 
 use crate::shared::models::{
-    client_id::ClientId, cross_leverage::CrossLeverage, price::PercentageCapped,
+    amount::Sats, client_id::ClientId, cross_leverage::CrossLeverage, price::PercentageCapped,
     quantity::order::OrderQuantity, trade::TradeExecutionType,
 };
 
@@ -38,11 +38,12 @@ fn init_repositories_from_env() -> (LnmFuturesCrossRepository, LnmFuturesDataRep
         passphrase,
         SignatureGeneratorV3::new(secret),
         None,
+        config.clock().clone(),
     )
     .expect("Can create `LnmApiBase`");
 
     (
-        LnmFuturesCrossRepository::new(base.clone()),
+        LnmFuturesCrossRepository::new(base.clone(), config.exchange_limits().clone()),
         LnmFuturesDataRepository::new(base),
     )
 }
@@ -73,7 +74,7 @@ async fn test_create_long_order_limit(
     assert!(placed_order.open());
     assert!(!placed_order.filled());
     assert!(!placed_order.canceled());
-    assert_eq!(placed_order.trading_fee(), 0);
+    assert_eq!(placed_order.trading_fee(), Sats::ZERO);
     assert!(placed_order.filled_at().is_none());
     assert!(placed_order.canceled_at().is_none());
     assert_eq!(placed_order.client_id(), client_id.as_ref());
@@ -107,7 +108,7 @@ async fn test_create_short_order_limit(
     assert!(placed_order.open());
     assert!(!placed_order.filled());
     assert!(!placed_order.canceled());
-    assert_eq!(placed_order.trading_fee(), 0);
+    assert_eq!(placed_order.trading_fee(), Sats::ZERO);
     assert!(placed_order.filled_at().is_none());
     assert!(placed_order.canceled_at().is_none());
     assert!(placed_order.client_id().is_none());
@@ -132,7 +133,7 @@ async fn test_create_long_order_market(repo: &LnmFuturesCrossRepository) -> Cros
     assert!(!placed_order.open());
     assert!(placed_order.filled());
     assert!(!placed_order.canceled());
-    assert!(placed_order.trading_fee() > 0);
+    assert!(placed_order.trading_fee().is_positive());
     assert!(placed_order.filled_at().is_some());
     assert!(placed_order.canceled_at().is_none());
     assert!(placed_order.client_id().is_none());
@@ -157,7 +158,7 @@ async fn test_create_short_order_market(repo: &LnmFuturesCrossRepository) -> Cro
     assert!(!placed_order.open());
     assert!(placed_order.filled());
     assert!(!placed_order.canceled());
-    assert!(placed_order.trading_fee() > 0);
+    assert!(placed_order.trading_fee().is_positive());
     assert!(placed_order.filled_at().is_some());
     assert!(placed_order.canceled_at().is_none());
     assert!(placed_order.client_id().is_none());
@@ -200,11 +201,11 @@ fn assert_exposure_matches_position(position: &CrossPosition) {
             assert_ne!(position.quantity(), 0);
             assert_eq!(
                 exposure.running_margin().as_u64(),
-                position.running_margin()
+                position.running_margin().as_u64()
             );
             assert_eq!(
                 exposure.maintenance_margin().as_u64(),
-                position.maintenance_margin()
+                position.maintenance_margin().as_u64()
             );
             assert_eq!(Some(exposure.liquidation()), position.liquidation());
         }
@@ -226,7 +227,7 @@ async fn test_close_position(repo: &LnmFuturesCrossRepository, exp_side: TradeSi
     assert_eq!(closing_order.trade_type(), TradeExecutionType::Market);
     assert_eq!(closing_order.side(), exp_side);
     assert_eq!(closing_order.quantity(), OrderQuantity::MIN);
-    assert!(closing_order.trading_fee() > 0);
+    assert!(closing_order.trading_fee().is_positive());
     assert!(!closing_order.open());
     assert!(closing_order.filled());
     assert!(closing_order.filled_at().is_some());
@@ -286,7 +287,7 @@ async fn test_deposit(
 
     assert_eq!(
         updated_cross_position.margin(),
-        cross_position.margin() + deposit_amount
+        cross_position.margin() + Sats::from(deposit_amount)
     );
     assert_exposure_matches_position(&updated_cross_position);
 
@@ -305,7 +306,7 @@ async fn test_withdrawal(
 
     assert_eq!(
         updated_cross_position.margin(),
-        cross_position.margin() - withdrawal_amount
+        cross_position.margin() - Sats::from(withdrawal_amount)
     );
     assert_exposure_matches_position(&updated_cross_position);
 
@@ -326,8 +327,8 @@ async fn test_get_transfers(
     let withdrawal = transfers.data().first().expect("must have withdrawal");
     let deposit = transfers.data().last().expect("must have deposit");
 
-    assert_eq!(withdrawal.amount(), -(withdrawal_amount as i64));
-    assert_eq!(deposit.amount(), deposit_amount as i64);
+    assert_eq!(withdrawal.amount(), -Sats::from(withdrawal_amount));
+    assert_eq!(deposit.amount(), Sats::from(deposit_amount));
 }
 
 async fn test_get_funding_fees(repo: &LnmFuturesCrossRepository) {
@@ -376,8 +377,8 @@ async fn test_api() {
         );
     }
 
-    if cross_position.margin() < 4_000 {
-        let deposit_amount = 4_000 - cross_position.margin();
+    if cross_position.margin() < Sats::new(4_000) {
+        let deposit_amount = 4_000 - cross_position.margin().as_u64();
         time_test!(
             "test_deposit",
             test_deposit(&repo, cross_position, deposit_amount).await
@@ -469,7 +470,7 @@ async fn test_api() {
         test_deposit(&repo, cross_position, deposit_amount).await
     );
 
-    let withdrawal_amount = cross_position.margin();
+    let withdrawal_amount = cross_position.margin().as_u64();
     time_test!(
         "test_withdrawal",
         test_withdrawal(&repo, cross_position, withdrawal_amount).await
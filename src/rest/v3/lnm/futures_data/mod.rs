@@ -1,12 +1,12 @@
 use std::{num::NonZeroU64, sync::Arc};
 
 use async_trait::async_trait;
-use chrono::{DateTime, SecondsFormat, Utc};
+use chrono::{DateTime, Utc};
 use reqwest::{self, Method};
 
 use crate::shared::{
     models::ohlc::{OhlcCandle, OhlcRange},
-    rest::{error::Result, lnm::base::LnmRestBase},
+    rest::{error::Result, lnm::base::LnmRestBase, query::QueryParams},
 };
 
 use super::{
@@ -39,23 +39,7 @@ impl FuturesDataRepository for LnmFuturesDataRepository {
         limit: Option<NonZeroU64>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Page<FundingSettlement>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
 
         self.base
             .make_request_with_query_params(
@@ -81,26 +65,12 @@ impl FuturesDataRepository for LnmFuturesDataRepository {
         range: Option<OhlcRange>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Page<OhlcCandle>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(range) = range {
-            query_params.push(("range", range.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::new()
+            .with_datetime_opt("from", from)
+            .with_datetime_opt("to", to)
+            .with_opt("limit", limit)
+            .with_opt("range", range)
+            .with_datetime_opt("cursor", cursor);
 
         self.base
             .make_request_with_query_params(
@@ -18,6 +18,7 @@ fn init_repository_from_env_with_rate_limiter(
         config.timeout(),
         config.endpoint().to_string(),
         rate_limiter,
+        config.clock().clone(),
     )
     .expect("must create `LnmApiBase`");
 
@@ -29,8 +30,13 @@ fn init_repository_from_env() -> LnmFuturesDataRepository {
 
     let config = RestClientConfig::default();
 
-    let base = LnmRestBase::new(config.timeout(), config.endpoint().to_string(), None)
-        .expect("must create `LnmApiBase`");
+    let base = LnmRestBase::new(
+        config.timeout(),
+        config.endpoint().to_string(),
+        None,
+        config.clock().clone(),
+    )
+    .expect("must create `LnmApiBase`");
 
     LnmFuturesDataRepository::new(base)
 }
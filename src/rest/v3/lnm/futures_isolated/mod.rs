@@ -1,7 +1,7 @@
-use std::{num::NonZeroU64, sync::Arc};
+use std::{cmp::Ordering, num::NonZeroU64, sync::Arc};
 
 use async_trait::async_trait;
-use chrono::{DateTime, SecondsFormat, Utc};
+use chrono::{DateTime, Utc};
 use reqwest::Method;
 use serde_json::json;
 use uuid::Uuid;
@@ -11,9 +11,10 @@ use crate::shared::{
         client_id::ClientId,
         leverage::Leverage,
         price::Price,
-        trade::{TradeExecution, TradeSide, TradeSize},
+        rounding::RoundingPolicy,
+        trade::{TradeExecution, TradeSide, TradeSize, util as trade_util},
     },
-    rest::{error::Result, lnm::base::LnmRestBase},
+    rest::{error::Result, lnm::base::LnmRestBase, query::QueryParams},
 };
 
 use super::{
@@ -21,6 +22,7 @@ use super::{
         error::RestApiV3Error,
         models::{
             funding::IsolatedFunding,
+            limits::ExchangeLimits,
             page::Page,
             trade::{FuturesIsolatedTradeRequestBody, Trade},
         },
@@ -32,14 +34,49 @@ use super::{
 
 pub(in crate::rest::v3) struct LnmFuturesIsolatedRepository {
     base: Arc<LnmRestBase<SignatureGeneratorV3>>,
+    limits: ExchangeLimits,
+    rounding: RoundingPolicy,
 }
 
 impl LnmFuturesIsolatedRepository {
-    pub fn new(base: Arc<LnmRestBase<SignatureGeneratorV3>>) -> Self {
-        Self { base }
+    pub fn new(
+        base: Arc<LnmRestBase<SignatureGeneratorV3>>,
+        limits: ExchangeLimits,
+        rounding: RoundingPolicy,
+    ) -> Self {
+        Self {
+            base,
+            limits,
+            rounding,
+        }
     }
 }
 
+/// Validates a new trade against `limits` and builds its request body. Shared with
+/// [`FastLane`](crate::rest::v3::FastLane).
+#[allow(clippy::too_many_arguments)]
+pub(in crate::rest::v3) fn new_trade_body(
+    limits: &ExchangeLimits,
+    side: TradeSide,
+    size: TradeSize,
+    leverage: Leverage,
+    execution: TradeExecution,
+    stoploss: Option<Price>,
+    takeprofit: Option<Price>,
+    client_id: Option<ClientId>,
+) -> Result<FuturesIsolatedTradeRequestBody> {
+    limits
+        .validate_trade(&size, leverage, execution, stoploss, takeprofit)
+        .map_err(RestApiV3Error::ExchangeLimitsValidation)?;
+
+    let body = FuturesIsolatedTradeRequestBody::new(
+        leverage, stoploss, takeprofit, side, client_id, size, execution,
+    )
+    .map_err(RestApiV3Error::FuturesIsolatedTradeRequestValidation)?;
+
+    Ok(body)
+}
+
 impl crate::sealed::Sealed for LnmFuturesIsolatedRepository {}
 
 #[async_trait]
@@ -121,23 +158,7 @@ impl FuturesIsolatedRepository for LnmFuturesIsolatedRepository {
         limit: Option<NonZeroU64>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Page<Trade>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
 
         self.base
             .make_request_with_query_params(
@@ -156,23 +177,7 @@ impl FuturesIsolatedRepository for LnmFuturesIsolatedRepository {
         limit: Option<NonZeroU64>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Page<Trade>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
 
         self.base
             .make_request_with_query_params(
@@ -185,6 +190,12 @@ impl FuturesIsolatedRepository for LnmFuturesIsolatedRepository {
     }
 
     async fn update_takeprofit(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        if let Some(price) = value {
+            self.limits
+                .validate_price(price)
+                .map_err(RestApiV3Error::ExchangeLimitsValidation)?;
+        }
+
         let body = match value {
             Some(price) => json!({ "id": id, "value": price }),
             None => json!({ "id": id, "value": 0 }),
@@ -201,6 +212,12 @@ impl FuturesIsolatedRepository for LnmFuturesIsolatedRepository {
     }
 
     async fn update_stoploss(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        if let Some(price) = value {
+            self.limits
+                .validate_price(price)
+                .map_err(RestApiV3Error::ExchangeLimitsValidation)?;
+        }
+
         let body = match value {
             Some(price) => json!({ "id": id, "value": price }),
             None => json!({ "id": id, "value": 0 }),
@@ -216,6 +233,58 @@ impl FuturesIsolatedRepository for LnmFuturesIsolatedRepository {
             .await
     }
 
+    async fn update_leverage(
+        &self,
+        trade: &Trade,
+        leverage: Leverage,
+        market_price: Price,
+    ) -> Result<Trade> {
+        let delta = trade_util::evaluate_collateral_delta_for_leverage_rounded(
+            trade.side(),
+            trade.quantity(),
+            trade.margin(),
+            trade.price(),
+            leverage,
+            market_price,
+            self.rounding,
+        )
+        .map_err(RestApiV3Error::TradeValidation)?;
+
+        match delta.cmp(&0) {
+            Ordering::Greater => {
+                let amount = NonZeroU64::new(delta.unsigned_abs()).expect("must be positive");
+
+                let _ = trade_util::evaluate_added_margin(
+                    trade.side(),
+                    trade.quantity(),
+                    trade.price(),
+                    trade.margin(),
+                    amount,
+                )
+                .map_err(RestApiV3Error::TradeValidation)?;
+
+                self.add_margin_to_trade(trade.id(), amount).await
+            }
+            Ordering::Less => {
+                let amount = NonZeroU64::new(delta.unsigned_abs()).expect("must be positive");
+
+                let _ = trade_util::evaluate_cash_in(
+                    trade.side(),
+                    trade.quantity(),
+                    trade.margin(),
+                    trade.price(),
+                    trade.stoploss(),
+                    market_price,
+                    amount,
+                )
+                .map_err(RestApiV3Error::TradeValidation)?;
+
+                self.cash_in_trade(trade.id(), amount).await
+            }
+            Ordering::Equal => Ok(trade.clone()),
+        }
+    }
+
     async fn new_trade(
         &self,
         side: TradeSide,
@@ -226,10 +295,21 @@ impl FuturesIsolatedRepository for LnmFuturesIsolatedRepository {
         takeprofit: Option<Price>,
         client_id: Option<ClientId>,
     ) -> Result<Trade> {
-        let body = FuturesIsolatedTradeRequestBody::new(
-            leverage, stoploss, takeprofit, side, client_id, size, execution,
-        )
-        .map_err(RestApiV3Error::FuturesIsolatedTradeRequestValidation)?;
+        let body = new_trade_body(
+            &self.limits,
+            side,
+            size,
+            leverage,
+            execution,
+            stoploss,
+            takeprofit,
+            client_id,
+        )?;
+
+        self.base.check_price_freshness(execution)?;
+        self.base
+            .check_maintenance(RestPathV3::UtilitiesPing)
+            .await?;
 
         self.base
             .make_request_with_body(Method::POST, RestPathV3::FuturesIsolatedTrade, body, true)
@@ -243,23 +323,7 @@ impl FuturesIsolatedRepository for LnmFuturesIsolatedRepository {
         limit: Option<NonZeroU64>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Page<IsolatedFunding>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
 
         self.base
             .make_request_with_query_params(
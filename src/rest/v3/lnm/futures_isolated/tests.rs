@@ -3,6 +3,7 @@ use std::{env, time::Instant};
 use dotenvy::dotenv;
 
 use crate::shared::models::{
+    amount::Sats,
     client_id::ClientId,
     margin::Margin,
     price::{Percentage, PercentageCapped},
@@ -35,11 +36,16 @@ fn init_repositories_from_env() -> (LnmFuturesIsolatedRepository, LnmFuturesData
         passphrase,
         SignatureGeneratorV3::new(secret),
         None,
+        config.clock().clone(),
     )
     .expect("Can create `LnmApiBase`");
 
     (
-        LnmFuturesIsolatedRepository::new(base.clone()),
+        LnmFuturesIsolatedRepository::new(
+            base.clone(),
+            config.exchange_limits().clone(),
+            config.rounding_policy(),
+        ),
         LnmFuturesDataRepository::new(base),
     )
 }
@@ -87,8 +93,8 @@ async fn test_create_short_trade_quantity_limit(
     assert!(!created_trade.closed());
     assert!(!created_trade.canceled());
 
-    assert_eq!(created_trade.opening_fee(), 0);
-    assert_eq!(created_trade.closing_fee(), 0);
+    assert_eq!(created_trade.opening_fee(), Sats::ZERO);
+    assert_eq!(created_trade.closing_fee(), Sats::ZERO);
 
     assert!(created_trade.filled_at().is_none());
     assert!(created_trade.closed_at().is_none());
@@ -144,8 +150,8 @@ async fn test_create_long_trade_margin_limit(
     assert!(!created_trade.closed());
     assert!(!created_trade.canceled());
 
-    assert_eq!(created_trade.opening_fee(), 0);
-    assert_eq!(created_trade.closing_fee(), 0);
+    assert_eq!(created_trade.opening_fee(), Sats::ZERO);
+    assert_eq!(created_trade.closing_fee(), Sats::ZERO);
 
     assert!(created_trade.filled_at().is_none());
     assert!(created_trade.closed_at().is_none());
@@ -271,8 +277,8 @@ async fn test_create_long_trade_quantity_market(
     assert!(!created_trade.closed());
     assert!(!created_trade.canceled());
 
-    assert!(created_trade.opening_fee() > 0);
-    assert_eq!(created_trade.closing_fee(), 0);
+    assert!(created_trade.opening_fee().is_positive());
+    assert_eq!(created_trade.closing_fee(), Sats::ZERO);
 
     assert!(created_trade.filled_at().is_some());
     assert!(created_trade.closed_at().is_none());
@@ -306,7 +312,7 @@ async fn test_cash_in(repo: &LnmFuturesIsolatedRepository, trade: Trade) -> Trad
 
     let target_leverage = Leverage::try_from(2).unwrap();
     let target_margin = Margin::calculate(trade.quantity(), trade.price(), target_leverage);
-    let amount = trade.margin().as_u64() - target_margin.as_u64() + trade.pl().max(0) as u64;
+    let amount = trade.margin().as_u64() - target_margin.as_u64() + trade.pl().as_u64();
     let amount = amount.try_into().unwrap();
 
     let updated_trade = repo
@@ -363,8 +369,8 @@ async fn test_create_short_trade_margin_market(
     assert!(!created_trade.closed());
     assert!(!created_trade.canceled());
 
-    assert!(created_trade.opening_fee() > 0);
-    assert_eq!(created_trade.closing_fee(), 0);
+    assert!(created_trade.opening_fee().is_positive());
+    assert_eq!(created_trade.closing_fee(), Sats::ZERO);
 
     assert!(created_trade.filled_at().is_some());
     assert!(created_trade.closed_at().is_none());
@@ -3,6 +3,7 @@ pub(super) mod futures_cross;
 pub(super) mod futures_data;
 pub(super) mod futures_isolated;
 pub(super) mod oracle;
-mod path;
+pub(super) mod path;
 pub(super) mod signature;
 pub(super) mod utilities;
+pub(super) mod withdrawals;
@@ -1,12 +1,12 @@
 use std::{num::NonZeroU64, sync::Arc};
 
 use async_trait::async_trait;
-use chrono::{DateTime, SecondsFormat, Utc};
+use chrono::{DateTime, Utc};
 use hyper::Method;
 
 use crate::shared::{
     models::oracle::{Index, LastPrice},
-    rest::{error::Result, lnm::base::LnmRestBase},
+    rest::{error::Result, lnm::base::LnmRestBase, query::QueryParams},
 };
 
 use super::{
@@ -34,23 +34,7 @@ impl OracleRepository for LnmOracleRepository {
         limit: Option<NonZeroU64>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Vec<Index>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
 
         self.base
             .make_request_with_query_params(
@@ -69,23 +53,7 @@ impl OracleRepository for LnmOracleRepository {
         limit: Option<NonZeroU64>,
         cursor: Option<DateTime<Utc>>,
     ) -> Result<Vec<LastPrice>> {
-        let mut query_params = Vec::new();
-
-        if let Some(from) = from {
-            query_params.push(("from", from.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(to) = to {
-            query_params.push(("to", to.to_rfc3339_opts(SecondsFormat::Millis, true)));
-        }
-        if let Some(limit) = limit {
-            query_params.push(("limit", limit.to_string()));
-        }
-        if let Some(cursor) = cursor {
-            query_params.push((
-                "cursor",
-                cursor.to_rfc3339_opts(SecondsFormat::Millis, true),
-            ));
-        }
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
 
         self.base
             .make_request_with_query_params(
@@ -10,8 +10,13 @@ fn init_repository_from_env() -> LnmOracleRepository {
 
     let config = RestClientConfig::default();
 
-    let base = LnmRestBase::new(config.timeout(), config.endpoint().to_string(), None)
-        .expect("Can create `LnmApiBase`");
+    let base = LnmRestBase::new(
+        config.timeout(),
+        config.endpoint().to_string(),
+        None,
+        config.clock().clone(),
+    )
+    .expect("Can create `LnmApiBase`");
 
     LnmOracleRepository::new(base)
 }
@@ -1,4 +1,6 @@
-use crate::shared::rest::lnm::base::RestPath;
+use reqwest::Method;
+
+use crate::shared::rest::lnm::{base::RestPath, scope::ApiScope};
 
 #[derive(Clone)]
 pub(in crate::rest::v3) enum RestPathV3 {
@@ -33,6 +35,13 @@ pub(in crate::rest::v3) enum RestPathV3 {
     FuturesDataTicker,
     FuturesDataGetCandles,
     Account,
+    AccountNotifications,
+    AccountNotificationsReadAll,
+    WithdrawalsOnchain,
+    WithdrawalsLightning,
+    WithdrawalsGetInternal,
+    WithdrawalsGetOnchain,
+    WithdrawalsGetLightning,
     OracleIndex,
     OracleLastPrice,
 }
@@ -77,8 +86,87 @@ impl RestPath for RestPathV3 {
             RestPathV3::FuturesDataTicker => "/futures/ticker".into(),
             RestPathV3::FuturesDataGetCandles => "/futures/candles".into(),
             RestPathV3::Account => "/account".into(),
+            RestPathV3::AccountNotifications => "/account/notifications".into(),
+            RestPathV3::AccountNotificationsReadAll => "/account/notifications/read-all".into(),
+            RestPathV3::WithdrawalsOnchain => "/account/withdraw/on-chain".into(),
+            RestPathV3::WithdrawalsLightning => "/account/withdraw/lightning".into(),
+            RestPathV3::WithdrawalsGetInternal => "/account/withdrawals/internal".into(),
+            RestPathV3::WithdrawalsGetOnchain => "/account/withdrawals/on-chain".into(),
+            RestPathV3::WithdrawalsGetLightning => "/account/withdrawals/lightning".into(),
             RestPathV3::OracleIndex => "/oracle/index".into(),
             RestPathV3::OracleLastPrice => "/oracle/last-price".into(),
         }
     }
+
+    fn required_scope(&self, method: &Method) -> Option<ApiScope> {
+        let write = method != Method::GET;
+
+        let scope = match self {
+            RestPathV3::UtilitiesPing
+            | RestPathV3::UtilitiesTime
+            | RestPathV3::FuturesDataFundingSettlements
+            | RestPathV3::FuturesDataTicker
+            | RestPathV3::FuturesDataGetCandles
+            | RestPathV3::OracleIndex
+            | RestPathV3::OracleLastPrice => return None,
+            RestPathV3::FuturesIsolatedTrade
+            | RestPathV3::FuturesIsolatedTradeAddMargin
+            | RestPathV3::FuturesIsolatedTradeCancel
+            | RestPathV3::FuturesIsolatedTradeCashIn
+            | RestPathV3::FuturesIsolatedTradeClose
+            | RestPathV3::FuturesIsolatedTradeTakeprofit
+            | RestPathV3::FuturesIsolatedTradeStoploss
+            | RestPathV3::FuturesIsolatedTradesCancelAll
+            | RestPathV3::FuturesIsolatedTradesOpen
+            | RestPathV3::FuturesIsolatedTradesRunning
+            | RestPathV3::FuturesIsolatedTradesClosed
+            | RestPathV3::FuturesIsolatedTradesCanceled
+            | RestPathV3::FuturesIsolatedFundingFees => {
+                if write {
+                    ApiScope::FuturesIsolatedWrite
+                } else {
+                    ApiScope::FuturesIsolatedRead
+                }
+            }
+            RestPathV3::FuturesCrossOrder
+            | RestPathV3::FuturesCrossOrderCancel
+            | RestPathV3::FuturesCrossOrdersCancelAll
+            | RestPathV3::FuturesCrossOrdersOpen
+            | RestPathV3::FuturesCrossOrdersFilled
+            | RestPathV3::FuturesCrossPosition
+            | RestPathV3::FuturesCrossPositionClose
+            | RestPathV3::FuturesCrossPositionSetLeverage
+            | RestPathV3::FuturesCrossDeposit
+            | RestPathV3::FuturesCrossWithdraw
+            | RestPathV3::FuturesCrossGetTransfers
+            | RestPathV3::FuturesCrossFundingFees => {
+                if write {
+                    ApiScope::FuturesCrossWrite
+                } else {
+                    ApiScope::FuturesCrossRead
+                }
+            }
+            RestPathV3::Account => ApiScope::AccountRead,
+            RestPathV3::AccountNotifications | RestPathV3::AccountNotificationsReadAll => {
+                if write {
+                    ApiScope::AccountNotificationsWrite
+                } else {
+                    ApiScope::AccountNotificationsRead
+                }
+            }
+            RestPathV3::WithdrawalsOnchain
+            | RestPathV3::WithdrawalsLightning
+            | RestPathV3::WithdrawalsGetInternal
+            | RestPathV3::WithdrawalsGetOnchain
+            | RestPathV3::WithdrawalsGetLightning => {
+                if write {
+                    ApiScope::AccountWithdrawalsWrite
+                } else {
+                    ApiScope::AccountWithdrawalsRead
+                }
+            }
+        };
+
+        Some(scope)
+    }
 }
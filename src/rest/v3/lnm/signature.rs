@@ -5,6 +5,7 @@ use reqwest::{self, Method, Url};
 use sha2::Sha256;
 
 use crate::shared::rest::{
+    canonical,
     error::{RestApiError, Result},
     lnm::base::SignatureGenerator,
 };
@@ -29,33 +30,7 @@ impl SignatureGenerator for SignatureGeneratorV3 {
         url: &Url,
         body: Option<&String>,
     ) -> Result<String> {
-        let timestamp_str = timestamp.timestamp_millis().to_string();
-
-        // In v3, query params must be prefixed with '?'
-        let query_with_prefix = url
-            .query()
-            .map(|q| {
-                if q.is_empty() {
-                    String::new()
-                } else {
-                    format!("?{q}")
-                }
-            })
-            .unwrap_or_default();
-
-        let params_str = match *method {
-            Method::POST | Method::PUT => body.map(|v| v.as_str()).unwrap_or(""),
-            Method::GET | Method::DELETE => query_with_prefix.as_str(),
-            _ => "",
-        };
-
-        let prehash = format!(
-            "{}{}{}{}",
-            timestamp_str,
-            method.as_str().to_lowercase(), // Differs from v2
-            url.path(),
-            params_str
-        );
+        let prehash = canonical::signing_payload(timestamp, method, url, body.map(String::as_str));
 
         let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
             .map_err(RestApiError::InvalidSecretHmac)?;
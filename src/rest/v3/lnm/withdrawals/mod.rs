@@ -0,0 +1,146 @@
+use std::{num::NonZeroU64, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+
+use crate::shared::{
+    models::{address::BitcoinAddress, invoice::Bolt11Invoice, network::BitcoinNetwork},
+    rest::{error::Result, lnm::base::LnmRestBase, query::QueryParams},
+};
+
+use super::{
+    super::{
+        error::RestApiV3Error,
+        models::{
+            limits::ExchangeLimits,
+            page::Page,
+            withdrawal::{
+                InternalWithdrawal, LightningWithdrawal, LightningWithdrawalRequestBody,
+                OnchainWithdrawal, OnchainWithdrawalRequestBody,
+            },
+        },
+        repositories::WithdrawalsRepository,
+    },
+    path::RestPathV3,
+    signature::SignatureGeneratorV3,
+};
+
+pub(in crate::rest::v3) struct LnmWithdrawalsRepository {
+    base: Arc<LnmRestBase<SignatureGeneratorV3>>,
+    network: BitcoinNetwork,
+    limits: ExchangeLimits,
+}
+
+impl LnmWithdrawalsRepository {
+    pub fn new(
+        base: Arc<LnmRestBase<SignatureGeneratorV3>>,
+        network: BitcoinNetwork,
+        limits: ExchangeLimits,
+    ) -> Self {
+        Self {
+            base,
+            network,
+            limits,
+        }
+    }
+}
+
+impl crate::sealed::Sealed for LnmWithdrawalsRepository {}
+
+#[async_trait]
+impl WithdrawalsRepository for LnmWithdrawalsRepository {
+    async fn get_internal_withdrawals(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<InternalWithdrawal>> {
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
+
+        self.base
+            .make_request_with_query_params(
+                Method::GET,
+                RestPathV3::WithdrawalsGetInternal,
+                query_params,
+                true,
+            )
+            .await
+    }
+
+    async fn get_onchain_withdrawals(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<OnchainWithdrawal>> {
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
+
+        self.base
+            .make_request_with_query_params(
+                Method::GET,
+                RestPathV3::WithdrawalsGetOnchain,
+                query_params,
+                true,
+            )
+            .await
+    }
+
+    async fn get_lightning_withdrawals(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<LightningWithdrawal>> {
+        let query_params = QueryParams::paginated(from, to, limit, cursor);
+
+        self.base
+            .make_request_with_query_params(
+                Method::GET,
+                RestPathV3::WithdrawalsGetLightning,
+                query_params,
+                true,
+            )
+            .await
+    }
+
+    async fn withdrawal_onchain(
+        &self,
+        address: BitcoinAddress,
+        amount: NonZeroU64,
+    ) -> Result<OnchainWithdrawal> {
+        self.limits
+            .validate_withdrawal(amount.get())
+            .map_err(RestApiV3Error::ExchangeLimitsValidation)?;
+
+        let body = OnchainWithdrawalRequestBody::new(self.network, address, amount)
+            .map_err(RestApiV3Error::WithdrawalRequestValidation)?;
+
+        self.base
+            .make_request_with_body(Method::POST, RestPathV3::WithdrawalsOnchain, body, true)
+            .await
+    }
+
+    async fn withdrawal_lightning(
+        &self,
+        invoice: Bolt11Invoice,
+        max_fees: Option<NonZeroU64>,
+    ) -> Result<LightningWithdrawal> {
+        let now = self.base.clock().now();
+        let body = LightningWithdrawalRequestBody::new(self.network, invoice, max_fees, now)
+            .map_err(RestApiV3Error::WithdrawalRequestValidation)?;
+
+        if let Some(amount) = body.amount_sats() {
+            self.limits
+                .validate_withdrawal(amount)
+                .map_err(RestApiV3Error::ExchangeLimitsValidation)?;
+        }
+
+        self.base
+            .make_request_with_body(Method::POST, RestPathV3::WithdrawalsLightning, body, true)
+            .await
+    }
+}
@@ -1,34 +1,100 @@
-use std::sync::Arc;
+use std::{
+    collections::BTreeSet,
+    num::NonZeroU64,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use tokio::time;
+use uuid::Uuid;
 
 use crate::shared::rest::{
-    error::Result,
+    canonical,
+    error::{RestApiError, Result},
     lnm::{base::LnmRestBase, rate_limit::RateLimiter},
+    timing,
 };
 
+pub mod amend;
+mod api;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod audit;
+pub mod candle_cache;
 mod config;
+pub mod dead_man_switch;
 pub mod error;
+pub mod fast_lane;
+pub mod history;
+pub mod iceberg;
+pub mod journal;
+pub mod ledger;
 mod lnm;
 pub mod models;
+pub mod order_queue;
+pub mod quoter;
+pub mod reconcile;
+pub mod reporting;
 mod repositories;
+pub mod safe_close;
+pub mod sans_io;
+pub mod snapshot;
+pub mod state_store;
+pub mod tax;
+pub mod withdrawal_journal;
 
+pub use crate::shared::clock::{Clock, SystemClock};
+pub use crate::shared::rest::{
+    lnm::api_version::{ApiNotice, ApiNoticeHandler},
+    lnm::rate_limit::{LocalRateLimit, RateLimitBucketStatus, RateLimitStatus, ServerRateLimit},
+    lnm::scope::ApiScope,
+    query::QueryParams,
+    raw::WithRaw,
+    timing::{RequestTiming, Timed},
+};
+pub use api::LnmFuturesApi;
 pub use config::RestClientConfig;
+use error::{CrossPositionCloseValidationError, RestApiV3Error};
+use fast_lane::FastLane;
 use lnm::{
     account::LnmAccountRepository, futures_cross::LnmFuturesCrossRepository,
     futures_data::LnmFuturesDataRepository, futures_isolated::LnmFuturesIsolatedRepository,
     oracle::LnmOracleRepository, signature::SignatureGeneratorV3,
-    utilities::LnmUtilitiesRepository,
+    utilities::LnmUtilitiesRepository, withdrawals::LnmWithdrawalsRepository,
+};
+use models::{
+    BalanceView, CrossPosition, ExchangeHealth, ExchangeLimits, FlipSize, NetExposure,
+    OrderQuantity, Position, RoundingPolicy, Trade, TradeExecution,
 };
+use reconcile::{ExpectedState, StateDiff};
 pub use repositories::{
     AccountRepository, FuturesCrossRepository, FuturesDataRepository, FuturesIsolatedRepository,
-    OracleRepository, UtilitiesRepository,
+    OracleRepository, UtilitiesRepository, WithdrawalsRepository,
 };
+use snapshot::Snapshot;
+
+/// Interval between checks of the cross position while [`RestClient::flip_position`] waits for it
+/// to be flat.
+const FLIP_CLOSE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Maximum time [`RestClient::flip_position`] waits for the cross position to be flat.
+const FLIP_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Client for interacting with the [LNM's v3 API] via REST.
 ///
 /// Some endpoints require credentials with specific permissions. Such requirements will be
 /// mentioned in the corresponding method's documentation".
 ///
+/// All shared state (transport, credentials and rate limiter) lives behind an [`Arc`], so cloning
+/// the client is cheap, and clones can be moved across tasks while sharing the same underlying
+/// connection pool and rate limiter.
+///
 /// [LNM's v3 API]: https://api.lnmarkets.com/v3/
+#[derive(Clone)]
 pub struct RestClient {
     /// Indicates whether LNM credentials were provided during client initialization.
     ///
@@ -39,53 +105,822 @@ pub struct RestClient {
     /// Methods for interacting with [LNM's v3 API]'s REST Utilities endpoints.
     ///
     /// [LNM's v3 API]: https://api.lnmarkets.com/v3/
-    pub utilities: Box<dyn UtilitiesRepository>,
+    pub utilities: Arc<dyn UtilitiesRepository>,
 
     /// Methods for interacting with [LNM's v3 API]'s REST Futures Isolated endpoints.
     ///
     /// [LNM's v3 API]: https://api.lnmarkets.com/v3/
-    pub futures_isolated: Box<dyn FuturesIsolatedRepository>,
+    pub futures_isolated: Arc<dyn FuturesIsolatedRepository>,
 
     /// Methods for interacting with [LNM's v3 API]'s REST Futures Cross endpoints.
     ///
     /// [LNM's v3 API]: https://api.lnmarkets.com/v3/
-    pub futures_cross: Box<dyn FuturesCrossRepository>,
+    pub futures_cross: Arc<dyn FuturesCrossRepository>,
 
     /// Methods for interacting with [LNM's v3 API]'s REST Futures Data endpoints.
     ///
     /// [LNM's v3 API]: https://api.lnmarkets.com/v3/
-    pub futures_data: Box<dyn FuturesDataRepository>,
+    pub futures_data: Arc<dyn FuturesDataRepository>,
 
     /// Methods for interacting with [LNM's v3 API]'s REST Account endpoints.
     ///
     /// [LNM's v3 API]: https://api.lnmarkets.com/v3/
-    pub account: Box<dyn AccountRepository>,
+    pub account: Arc<dyn AccountRepository>,
+
+    /// Methods for interacting with [LNM's v3 API]'s REST Withdrawals endpoints.
+    ///
+    /// [LNM's v3 API]: https://api.lnmarkets.com/v3/
+    pub withdrawals: Arc<dyn WithdrawalsRepository>,
 
     /// Methods for interacting with [LNM's v3 API]'s REST Oracle endpoints.
     ///
     /// [LNM's v3 API]: https://api.lnmarkets.com/v3/
-    pub oracle: Box<dyn OracleRepository>,
+    pub oracle: Arc<dyn OracleRepository>,
+
+    exchange_limits: ExchangeLimits,
+    rounding_policy: RoundingPolicy,
+    base: Arc<LnmRestBase<SignatureGeneratorV3>>,
 }
 
 impl RestClient {
-    fn new_inner(base: Arc<LnmRestBase<SignatureGeneratorV3>>) -> Arc<Self> {
+    fn new_inner(base: Arc<LnmRestBase<SignatureGeneratorV3>>, config: &RestClientConfig) -> Self {
+        base.set_debug_logging(config.debug_logging());
+        base.set_expected_api_version(config.expected_api_version().map(str::to_string));
+        base.set_granted_scopes(config.api_scopes().cloned());
+        base.set_maintenance_probe_interval(
+            config
+                .maintenance_pause()
+                .then(|| config.maintenance_probe_interval()),
+        );
+        base.set_stale_price_threshold(config.stale_price_threshold());
+
         let has_credentials = base.has_credentials();
-        let utilities = Box::new(LnmUtilitiesRepository::new(base.clone()));
-        let futures_isolated = Box::new(LnmFuturesIsolatedRepository::new(base.clone()));
-        let futures_cross = Box::new(LnmFuturesCrossRepository::new(base.clone()));
-        let futures_data = Box::new(LnmFuturesDataRepository::new(base.clone()));
-        let account = Box::new(LnmAccountRepository::new(base.clone()));
-        let oracle = Box::new(LnmOracleRepository::new(base));
-
-        Arc::new(Self {
+        let utilities = Arc::new(LnmUtilitiesRepository::new(base.clone()));
+        let futures_isolated = Arc::new(LnmFuturesIsolatedRepository::new(
+            base.clone(),
+            config.exchange_limits().clone(),
+            config.rounding_policy(),
+        ));
+        let futures_cross = Arc::new(LnmFuturesCrossRepository::new(
+            base.clone(),
+            config.exchange_limits().clone(),
+        ));
+        let futures_data = Arc::new(LnmFuturesDataRepository::new(base.clone()));
+        let account = Arc::new(LnmAccountRepository::new(base.clone()));
+        let withdrawals = Arc::new(LnmWithdrawalsRepository::new(
+            base.clone(),
+            config.network(),
+            config.exchange_limits().clone(),
+        ));
+        let oracle = Arc::new(LnmOracleRepository::new(base.clone()));
+
+        Self {
             has_credentials,
             utilities,
             futures_isolated,
             futures_cross,
             futures_data,
             account,
+            withdrawals,
             oracle,
+            exchange_limits: config.exchange_limits().clone(),
+            rounding_policy: config.rounding_policy(),
+            base,
+        }
+    }
+
+    /// Returns the exchange limits requests are validated against, as configured via
+    /// [`RestClientConfig::with_exchange_limits`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// let limits = rest.exchange_limits();
+    ///
+    /// println!("Max quantity: {}", limits.max_quantity());
+    /// println!("Max leverage: {}", limits.max_leverage());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn exchange_limits(&self) -> &ExchangeLimits {
+        &self.exchange_limits
+    }
+
+    /// Returns the rounding policy used by the client's calculations, as configured via
+    /// [`RestClientConfig::with_rounding_policy`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::{OrderQuantity, PercentageCapped, Price};
+    ///
+    /// let fee = rest.rounding_policy().order_fee(
+    ///     PercentageCapped::try_from(0.1)?,
+    ///     OrderQuantity::try_from(1_000)?,
+    ///     Price::try_from(100_000)?,
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rounding_policy(&self) -> RoundingPolicy {
+        self.rounding_policy
+    }
+
+    /// Sends an arbitrary request to [LNM's v3 API], and deserializes the response into `T`.
+    ///
+    /// Escape hatch for endpoints not yet covered by the SDK. `path` is relative to the configured
+    /// endpoint (e.g. `/futures/ticker`). The request is signed if the client was created with
+    /// credentials, and goes through the same rate limiter as other requests. `body` is only sent
+    /// with `POST` and `PUT` requests.
+    ///
+    /// Responses that are not needed can be deserialized into [`serde::de::IgnoredAny`], and
+    /// responses of unknown shape into [`serde_json::Value`]. To keep the exact JSON returned by
+    /// the API alongside the typed model, use [`WithRaw<T>`](WithRaw).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::{QueryParams, sans_io::Method};
+    ///
+    /// let notifications: serde_json::Value = rest
+    ///     .raw_request(
+    ///         Method::GET,
+    ///         "/account/notifications",
+    ///         QueryParams::new().with("read", false),
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [LNM's v3 API]: https://api.lnmarkets.com/v3/
+    pub async fn raw_request<T>(
+        &self,
+        method: Method,
+        path: &str,
+        query_params: QueryParams,
+        body: Option<serde_json::Value>,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let body = body.map(|body| canonical::json_body(&body)).transpose()?;
+
+        self.base
+            .make_request_with_query_params_and_body(
+                method,
+                path.to_string(),
+                query_params,
+                body,
+                self.has_credentials,
+            )
+            .await
+    }
+
+    /// Awaits `request`, returning its output with the [timing breakdown](RequestTiming) of each
+    /// REST request it performed.
+    ///
+    /// Works with any client method, such as repository calls and composite helpers, as long as
+    /// the requests are performed on the current task. Useful to diagnose where latency on the
+    /// order path is spent: rate limiter queue, signing, network and server processing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::{Leverage, Margin, TradeExecution, TradeSide, TradeSize};
+    ///
+    /// let timed = rest
+    ///     .timed(rest.futures_isolated.new_trade(
+    ///         TradeSide::Buy,
+    ///         TradeSize::Margin(Margin::try_from(10_000)?),
+    ///         Leverage::try_from(10)?,
+    ///         TradeExecution::Market,
+    ///         None,
+    ///         None,
+    ///         None,
+    ///     ))
+    ///     .await?;
+    ///
+    /// if let Some(timing) = timed.last_timing() {
+    ///     println!(
+    ///         "total {:?}, queue {:?}, signing {:?}, round trip {:?}, server {:?}",
+    ///         timing.total(),
+    ///         timing.queue_wait(),
+    ///         timing.signing(),
+    ///         timing.round_trip(),
+    ///         timing.server_processing(),
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn timed<F, T>(&self, request: F) -> Result<Timed<T>>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let (result, timings) = timing::collect(request).await;
+
+        result.map(|value| Timed::new(value, timings))
+    }
+
+    /// Pings the API, returning an error if it can't be reached or doesn't respond as expected.
+    ///
+    /// Shorthand for [`UtilitiesRepository::ping`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// rest.ping().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ping(&self) -> Result<()> {
+        self.utilities.ping().await
+    }
+
+    /// Checks the health of the API, for readiness probes and pre-trade checks.
+    ///
+    /// The API is pinged, and the outcome is classified as operational, degraded (slow response,
+    /// server-side or rate limit error), maintenance (see
+    /// [`RestApiError::is_maintenance`](error::RestApiError::is_maintenance)) or unreachable. This
+    /// method doesn't fail; request errors are reported in the returned [`ExchangeHealth`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) {
+    /// let health = rest.status().await;
+    ///
+    /// println!("{health}");
+    /// # }
+    /// ```
+    pub async fn status(&self) -> ExchangeHealth {
+        let checked_at = self.clock().now();
+        let started_at = Instant::now();
+        let result = self.utilities.ping().await;
+
+        ExchangeHealth::from_check(&result, started_at.elapsed(), checked_at)
+    }
+
+    /// Prepares the client for low-latency requests, so the first order after startup doesn't pay
+    /// cold-start penalties.
+    ///
+    /// The API is pinged, resolving DNS and opening a pooled connection (TCP and TLS handshakes)
+    /// that later requests reuse, and a request is signed without being sent, to initialize the
+    /// signer. The connection is shared by all clones of the client.
+    ///
+    /// Idle pooled connections are closed after 90 seconds, so this method should be called
+    /// shortly before latency-sensitive requests, or periodically while idle.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// rest.warm_up().await?;
+    ///
+    /// // The first order now reuses the warmed connection
+    /// let ticker = rest.futures_data.get_ticker().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn warm_up(&self) -> Result<()> {
+        self.base.prime_signer()?;
+        self.utilities.ping().await
+    }
+
+    /// Returns the clock the client reads the current time from.
+    ///
+    /// See [`RestClientConfig::with_clock`].
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        self.base.clock()
+    }
+
+    /// Returns the scopes granted to the API key, if declared.
+    pub fn api_scopes(&self) -> Option<BTreeSet<ApiScope>> {
+        self.base.granted_scopes()
+    }
+
+    /// Declares the scopes granted to the API key, or removes the declaration.
+    ///
+    /// See [`RestClientConfig::with_api_scopes`]. The declaration is shared by all clones of the
+    /// client.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::{ApiScope, error::RestApiError};
+    ///
+    /// rest.set_api_scopes(Some([ApiScope::AccountRead, ApiScope::FuturesCrossRead].into()));
+    ///
+    /// let result = rest.futures_cross.cancel_all_orders().await;
+    /// assert!(matches!(result, Err(RestApiError::MissingScope { .. })));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_api_scopes(&self, scopes: Option<BTreeSet<ApiScope>>) {
+        self.base.set_granted_scopes(scopes);
+    }
+
+    /// Returns the API version reported by the last response that included an `API-Version` or
+    /// `X-API-Version` header, if any.
+    pub fn api_version(&self) -> Option<String> {
+        self.base.api_version()
+    }
+
+    /// Sets the handler invoked with every [`ApiNotice`] raised from response headers, or removes
+    /// it.
+    ///
+    /// Notices are raised for endpoints flagged with a `Deprecation` header, and for responses
+    /// reporting an API version different from the one pinned with
+    /// [`RestClientConfig::with_expected_api_version`]. Without a handler, notices are logged as
+    /// warnings under the `lnm_sdk::rest` target, once per endpoint. The handler is shared by all
+    /// clones of the client.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(rest: lnm_sdk::rest::v3::RestClient) {
+    /// use std::sync::Arc;
+    ///
+    /// use lnm_sdk::rest::v3::ApiNotice;
+    ///
+    /// rest.set_api_notice_handler(Some(Arc::new(|notice: &ApiNotice| {
+    ///     eprintln!("LNM API notice: {notice}");
+    /// })));
+    /// # }
+    /// ```
+    pub fn set_api_notice_handler(&self, handler: Option<ApiNoticeHandler>) {
+        self.base.set_api_notice_handler(handler);
+    }
+
+    /// Returns the time the exchange was first observed in maintenance, if order submissions are
+    /// currently paused.
+    ///
+    /// Always `None` unless enabled via [`RestClientConfig::with_maintenance_pause`].
+    pub fn maintenance_paused_since(&self) -> Option<DateTime<Utc>> {
+        self.base.maintenance_paused_since()
+    }
+
+    /// Records a price, or a heartbeat of the price feed, observed at `time`.
+    ///
+    /// Feeds the stale price guard enabled via [`RestClientConfig::with_stale_price_threshold`].
+    /// Observations older than the latest one are ignored. Shared by all clones of the client.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(
+    /// #     rest: lnm_sdk::rest::v3::RestClient,
+    /// #     mut updates: tokio::sync::broadcast::Receiver<lnm_sdk::stream::v1::models::StreamUpdate>,
+    /// # ) {
+    /// use lnm_sdk::stream::v1::models::StreamUpdate;
+    ///
+    /// while let Ok(update) = updates.recv().await {
+    ///     if let StreamUpdate::FuturesInverseBtcUsdLastPrice(price) = update {
+    ///         rest.observe_price(price.time());
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn observe_price(&self, time: DateTime<Utc>) {
+        self.base.observe_price(time);
+    }
+
+    /// Returns the time of the latest price observed with [`observe_price`](Self::observe_price),
+    /// if any.
+    pub fn last_price_seen_at(&self) -> Option<DateTime<Utc>> {
+        self.base.last_price_seen_at()
+    }
+
+    /// Attaches an [`AuditSink`](audit::AuditSink) to the client, returning it.
+    ///
+    /// Every order placement, modification, cancellation and close made through
+    /// [`futures_isolated`](Self::futures_isolated) and [`futures_cross`](Self::futures_cross)
+    /// (and [`LnmFuturesApi`]) is reported to `sink` as an [`AuditRecord`](audit::AuditRecord)
+    /// attributed to `actor`, once its outcome is known. Clones made after this call share the
+    /// sink.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(rest: lnm_sdk::rest::v3::RestClient) {
+    /// use std::sync::Arc;
+    ///
+    /// use lnm_sdk::rest::v3::audit::AuditRecord;
+    ///
+    /// let rest = rest.with_audit_sink(
+    ///     "market-maker",
+    ///     Arc::new(|record: &AuditRecord| log::info!(target: "audit", "{record}")),
+    /// );
+    /// # }
+    /// ```
+    pub fn with_audit_sink(
+        mut self,
+        actor: impl Into<Arc<str>>,
+        sink: Arc<dyn audit::AuditSink>,
+    ) -> Self {
+        audit::attach(&mut self, actor.into(), sink);
+        self
+    }
+
+    /// Returns the account balance in sats, BTC and USD.
+    ///
+    /// The USD value is computed at the latest index price from the oracle, falling back to the
+    /// ticker's index price, timestamped at the time of the response, if the oracle returns no
+    /// data.
+    ///
+    /// **Required permissions**: `account:read`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// let balance = rest.balance_view().await?;
+    ///
+    /// println!("{:.2} USD at {}", balance.usd(), balance.rate());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn balance_view(&self) -> Result<BalanceView> {
+        let account = self.account.get_account().await?;
+        let index = self
+            .oracle
+            .get_index(None, None, Some(NonZeroU64::MIN), None)
+            .await?
+            .into_iter()
+            .max_by_key(|index| index.time());
+
+        let view = match index {
+            Some(index) => BalanceView::from_index(account.balance().as_u64(), &index),
+            None => {
+                let ticker = self.futures_data.get_ticker().await?;
+                BalanceView::new(
+                    account.balance().as_u64(),
+                    ticker.index(),
+                    self.clock().now(),
+                )
+            }
+        };
+
+        Ok(view)
+    }
+
+    /// Returns the long and short exposure netted across running isolated trades and the cross
+    /// position.
+    ///
+    /// Can be checked against per-side caps with
+    /// [`ExposureLimits`](models::ExposureLimits).
+    ///
+    /// **Required permissions**: `futures:isolated:read`, `futures:cross:read`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::{ExposureLimits, OrderQuantity, TradeSide};
+    ///
+    /// let limits = ExposureLimits::default()
+    ///     .with_max_net_long(50_000)
+    ///     .with_max_net_short(20_000);
+    ///
+    /// let exposure = rest.net_exposure().await?;
+    /// limits.check_order(&exposure, TradeSide::Buy, OrderQuantity::try_from(1_000)?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn net_exposure(&self) -> Result<NetExposure> {
+        let trades = self.futures_isolated.get_running_trades().await?;
+        let position = self.futures_cross.get_position().await?;
+
+        Ok(NetExposure::from_positions(&trades, Some(&position)))
+    }
+
+    /// Returns the running isolated trades and the cross position, tagged by
+    /// [margin mode](models::MarginMode).
+    ///
+    /// The cross position is omitted when flat.
+    ///
+    /// **Required permissions**: `futures:isolated:read`, `futures:cross:read`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::MarginMode;
+    ///
+    /// let positions = rest.positions().await?;
+    /// let isolated = positions
+    ///     .iter()
+    ///     .filter(|position| position.margin_mode() == MarginMode::Isolated)
+    ///     .count();
+    ///
+    /// println!("{isolated} isolated trades, {} positions", positions.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn positions(&self) -> Result<Vec<Position>> {
+        let trades = self.futures_isolated.get_running_trades().await?;
+        let position = self.futures_cross.get_position().await?;
+
+        let mut positions: Vec<Position> = trades.into_iter().map(Position::from).collect();
+        if position.quantity() != 0 {
+            positions.push(position.into());
+        }
+
+        Ok(positions)
+    }
+
+    /// Closes part of the cross position, returning the remaining position.
+    ///
+    /// Validates that `position_id` identifies the current cross position and that `quantity`
+    /// doesn't exceed its size, then places a market order on the opposite side for `quantity`.
+    /// Closing the full size leaves the position flat.
+    ///
+    /// **Required permissions**: `futures:cross:read`, `futures:cross:write`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::OrderQuantity;
+    ///
+    /// let position = rest.futures_cross.get_position().await?;
+    ///
+    /// // Take profit on half of the position
+    /// let half = OrderQuantity::try_from(position.quantity().unsigned_abs() / 2)?;
+    /// let remaining = rest.close_partial(position.id(), half).await?;
+    ///
+    /// println!("Remaining quantity: {}", remaining.quantity());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close_partial(
+        &self,
+        position_id: Uuid,
+        quantity: OrderQuantity,
+    ) -> Result<CrossPosition> {
+        let position = self.futures_cross.get_position().await?;
+        if position.id() != position_id {
+            return Err(RestApiV3Error::CrossPositionCloseValidation(
+                CrossPositionCloseValidationError::PositionMismatch {
+                    expected: position_id,
+                    actual: position.id(),
+                },
+            )
+            .into());
+        }
+
+        let side = position
+            .reduce_side(quantity)
+            .map_err(RestApiV3Error::CrossPositionCloseValidation)?;
+
+        self.futures_cross
+            .place_order(side, quantity, TradeExecution::Market, None)
+            .await?;
+
+        self.futures_cross.get_position().await
+    }
+
+    /// Closes the cross position and opens one of the opposite side, returning the new position.
+    ///
+    /// The opposite position is sized according to `size`. It is only opened once the position
+    /// is flat. After the closing order is placed, the position is checked every 250 ms for up to
+    /// 5 seconds. If it isn't flat by then, [`RestApiV3Error::PositionNotClosed`] is returned and
+    /// no new order is placed.
+    ///
+    /// When [`RestApiV3Error::PositionNotClosed`] is returned, the closing market order was
+    /// placed but not observed to fill in time. It may still fill, so the account holds the
+    /// original position, either partially or fully closed, and no opposite position. The cross
+    /// position should be checked before retrying.
+    ///
+    /// **Required permissions**: `futures:cross:read`, `futures:cross:write`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::FlipSize;
+    ///
+    /// let position = rest.flip_position(FlipSize::Same).await?;
+    ///
+    /// println!("New position quantity: {}", position.quantity());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn flip_position(&self, size: FlipSize) -> Result<CrossPosition> {
+        let position = self.futures_cross.get_position().await?;
+        let (side, quantity) = position
+            .flip_order(size)
+            .map_err(RestApiV3Error::CrossPositionCloseValidation)?;
+
+        let close = self.futures_cross.close_position().await?;
+
+        let flat = time::timeout(FLIP_CLOSE_TIMEOUT, async {
+            loop {
+                if self.futures_cross.get_position().await?.quantity() == 0 {
+                    return Ok::<_, RestApiError>(());
+                }
+                time::sleep(FLIP_CLOSE_POLL_INTERVAL).await;
+            }
         })
+        .await;
+
+        match flat {
+            Ok(result) => result?,
+            Err(_) => {
+                return Err(RestApiV3Error::PositionNotClosed {
+                    order_id: close.id(),
+                }
+                .into());
+            }
+        }
+
+        self.futures_cross
+            .place_order(side, quantity, TradeExecution::Market, None)
+            .await?;
+
+        self.futures_cross.get_position().await
+    }
+
+    /// Returns the closed isolated trades newer than `cursor`, sorted by closing time, along with
+    /// the cursor to pass on the next call.
+    ///
+    /// Pass `None` on the first sync to fetch the full history. The returned cursor is `None` only
+    /// if no trades were ever closed. Cursors are serializable, so they can be persisted between
+    /// runs of periodic sync jobs.
+    ///
+    /// **Required permissions**: `futures:isolated:read`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::history::SyncCursor;
+    ///
+    /// let stored: Option<SyncCursor> = None; // Load from storage
+    ///
+    /// let (trades, cursor) = rest.sync_trades_since(stored.as_ref()).await?;
+    /// println!("{} new closed trades", trades.len());
+    ///
+    /// // Persist `cursor` for the next run
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sync_trades_since(
+        &self,
+        cursor: Option<&history::SyncCursor>,
+    ) -> Result<(Vec<Trade>, Option<history::SyncCursor>)> {
+        history::sync_closed_trades(self, cursor).await
+    }
+
+    /// Compares a caller-supplied snapshot of the expected trading state against the live API.
+    ///
+    /// Fetches the open and running isolated trades, and the open cross orders and cross
+    /// position, for the markets set in `expected`. Returns the missing, unexpected and
+    /// mismatched items, to be resolved before a bot resumes trading after a restart.
+    ///
+    /// **Required permissions**: `futures:isolated:read`, `futures:cross:read`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::reconcile::ExpectedState;
+    ///
+    /// // Expect no isolated trades, no open cross orders and a flat cross position
+    /// let expected = ExpectedState::new()
+    ///     .with_isolated([])
+    ///     .with_cross([], 0);
+    ///
+    /// let diff = rest.reconcile(&expected).await?;
+    /// if !diff.is_consistent() {
+    ///     println!("Unexpected trades: {:?}", diff.unexpected_trades());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reconcile(&self, expected: &ExpectedState) -> Result<StateDiff> {
+        reconcile::reconcile(self, expected).await
+    }
+
+    /// Captures the account, open and running isolated trades, open cross orders and cross
+    /// position.
+    ///
+    /// The requests are sent concurrently, so the parts of the snapshot are as close together in
+    /// time as possible. Snapshots can be compared with [`Snapshot::diff`], e.g. to alert on
+    /// drift between two polls.
+    ///
+    /// **Required permissions**: `account:read`, `futures:isolated:read`, `futures:cross:read`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// let before = rest.snapshot().await?;
+    /// // ...
+    /// let after = rest.snapshot().await?;
+    ///
+    /// let diff = before.diff(&after);
+    /// if let Some((from, to)) = diff.balance() {
+    ///     println!("Balance changed from {from} to {to} sats");
+    /// }
+    /// println!("New trades: {}", diff.added_trades().len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn snapshot(&self) -> Result<Snapshot> {
+        snapshot::snapshot(self).await
+    }
+
+    /// Returns a [`FastLane`] handle for latency-sensitive order placement and cancellation.
+    ///
+    /// The handle shares the connection pool, credentials and rate limiter of the client, but its
+    /// requests skip the optional middleware (timing, metrics, tracing, debug logging, API version
+    /// tracking and maintenance pauses). Requests sent through the client itself are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::{OrderQuantity, TradeExecution, TradeSide};
+    ///
+    /// let fast = rest.fast_lane();
+    ///
+    /// let order = fast
+    ///     .place_cross_order(
+    ///         TradeSide::Sell,
+    ///         OrderQuantity::try_from(50)?,
+    ///         TradeExecution::Market,
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fast_lane(&self) -> FastLane {
+        FastLane::new(self.base.clone(), self.exchange_limits.clone())
+    }
+
+    /// Returns the current rate limit status, per class of requests (authenticated and
+    /// unauthenticated).
+    ///
+    /// Combines the state of the client-side rate limiter, if active, with the rate limit headers
+    /// of the last response of each class. Shared by all clones of the client. Doesn't perform any
+    /// request.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(rest: lnm_sdk::rest::v3::RestClient) {
+    /// let status = rest.rate_limit_status();
+    ///
+    /// let low_on_quota = status
+    ///     .authenticated()
+    ///     .server()
+    ///     .and_then(|server| server.remaining())
+    ///     .is_some_and(|remaining| remaining < 5);
+    ///
+    /// if low_on_quota || !status.authenticated().available_in().is_zero() {
+    ///     // Defer non-critical work
+    /// }
+    /// # }
+    /// ```
+    pub fn rate_limit_status(&self) -> RateLimitStatus {
+        self.base.rate_limit_status()
+    }
+
+    /// Returns whether redacted request/response debug logging is currently enabled.
+    ///
+    /// See [`RestClientConfig::with_debug_logging`].
+    pub fn debug_logging(&self) -> bool {
+        self.base.debug_logging()
+    }
+
+    /// Enables or disables redacted request/response debug logging at runtime.
+    ///
+    /// Requests and responses are emitted via the [`log`] crate, at the `debug` level under the
+    /// `lnm_sdk::rest` target, with credentials, signatures and invoices redacted. Takes effect
+    /// for subsequent requests, including those made through repositories already in use.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// rest.set_debug_logging(true);
+    /// let _ = rest.account.get_account().await;
+    /// rest.set_debug_logging(false);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_debug_logging(&self, enabled: bool) {
+        self.base.set_debug_logging(enabled);
     }
 
     /// Creates a new unauthenticated REST client.
@@ -103,7 +938,7 @@ impl RestClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn new(config: impl Into<RestClientConfig>) -> Result<Arc<Self>> {
+    pub fn new(config: impl Into<RestClientConfig>) -> Result<Self> {
         let config = config.into();
         let rate_limiter = config
             .rate_limiter_active()
@@ -112,9 +947,10 @@ impl RestClient {
             config.timeout(),
             config.endpoint().to_string(),
             rate_limiter,
+            config.clock().clone(),
         )?;
 
-        Ok(Self::new_inner(base))
+        Ok(Self::new_inner(base, &config))
     }
 
     /// Creates a new authenticated REST client with credentials.
@@ -142,7 +978,7 @@ impl RestClient {
         key: impl ToString,
         secret: impl ToString,
         passphrase: impl ToString,
-    ) -> Result<Arc<Self>> {
+    ) -> Result<Self> {
         let config = config.into();
         let rate_limiter = config
             .rate_limiter_active()
@@ -154,8 +990,280 @@ impl RestClient {
             passphrase.to_string(),
             SignatureGeneratorV3::new(secret.to_string()),
             rate_limiter,
+            config.clock().clone(),
         )?;
 
-        Ok(Self::new_inner(base))
+        Ok(Self::new_inner(base, &config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::Future, time::Duration};
+
+    use crate::shared::rest::error::RestApiError;
+
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    fn assert_send_future<F: Future + Send>(_: F) {}
+
+    #[test]
+    fn test_rest_client_is_send_sync() {
+        assert_send_sync::<RestClient>();
+        assert_send_sync::<RestClientConfig>();
+    }
+
+    #[test]
+    fn test_rest_client_futures_are_send() {
+        let rest = RestClient::new(RestClientConfig::default()).expect("must create client");
+
+        // Futures are only created and dropped, never polled.
+        assert_send_future(rest.utilities.ping());
+        assert_send_future(rest.futures_isolated.get_running_trades());
+        assert_send_future(rest.futures_cross.get_position());
+        assert_send_future(rest.futures_data.get_ticker());
+        assert_send_future(rest.account.get_account());
+        assert_send_future(rest.oracle.get_last_price(None, None, None, None));
+        assert_send_future(rest.balance_view());
+        assert_send_future(rest.status());
+    }
+
+    #[test]
+    fn test_rate_limit_status_before_requests() {
+        let rest = RestClient::new(RestClientConfig::default()).expect("must create client");
+        let status = rest.rate_limit_status();
+
+        let local = status.authenticated().local().expect("limiter is active");
+        assert_eq!(local.interval(), Duration::from_millis(200));
+        assert_eq!(status.unauthenticated().available_in(), Duration::ZERO);
+        assert!(status.authenticated().server().is_none());
+
+        let rest = RestClient::new(RestClientConfig::default().with_rate_limiter_active(false))
+            .expect("must create client");
+        assert!(rest.rate_limit_status().authenticated().local().is_none());
+    }
+
+    #[test]
+    fn test_rest_client_as_futures_api_object() {
+        let rest = RestClient::new(RestClientConfig::default()).expect("must create client");
+        let api: Arc<dyn LnmFuturesApi> = Arc::new(rest);
+
+        assert_send_sync::<Arc<dyn LnmFuturesApi>>();
+        assert_send_future(api.get_ticker());
+        assert_send_future(api.get_cross_position());
+    }
+
+    #[tokio::test]
+    async fn test_missing_scope_rejected_locally() {
+        let config = RestClientConfig::default()
+            .with_rate_limiter_active(false)
+            .with_api_scopes([ApiScope::FuturesCrossRead]);
+        let rest = RestClient::with_credentials(config, "key", "secret", "pphrase")
+            .expect("must create client");
+
+        let error = rest.futures_cross.cancel_all_orders().await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            RestApiError::MissingScope {
+                scope: ApiScope::FuturesCrossWrite,
+                ..
+            }
+        ));
+        assert!(error.is_auth_error());
+        assert_eq!(
+            error.endpoint(),
+            Some((&Method::POST, "/v3/futures/cross/orders/cancel-all"))
+        );
+
+        rest.set_api_scopes(None);
+        assert!(rest.api_scopes().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stale_price_rejects_market_orders() {
+        use models::{OrderQuantity, TradeSide};
+
+        let config = RestClientConfig::default()
+            .with_rate_limiter_active(false)
+            .with_stale_price_threshold(Duration::from_secs(5));
+        let rest = RestClient::with_credentials(config, "key", "secret", "pphrase")
+            .expect("must create client");
+        let quantity = OrderQuantity::try_from(100).unwrap();
+
+        let error = rest
+            .futures_cross
+            .place_order(TradeSide::Buy, quantity, TradeExecution::Market, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            RestApiError::StalePrice {
+                last_seen: None,
+                ..
+            }
+        ));
+
+        let last_seen = Utc::now() - chrono::TimeDelta::seconds(10);
+        rest.observe_price(last_seen);
+        assert_eq!(rest.last_price_seen_at(), Some(last_seen));
+
+        let error = rest
+            .fast_lane()
+            .place_cross_order(TradeSide::Sell, quantity, TradeExecution::Market, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            RestApiError::StalePrice { last_seen: Some(time), .. } if time == last_seen
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_timed_request() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/v3", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nServer-Timing: app;dur=1.5\r\nContent-Length: 2\r\n\r\n{}",
+                )
+                .await
+                .unwrap();
+        });
+
+        let config = RestClientConfig::default()
+            .with_endpoint(endpoint)
+            .with_rate_limiter_active(false);
+        let rest = RestClient::new(config).expect("must create client");
+
+        let timed = rest
+            .timed(rest.raw_request::<serde_json::Value>(
+                Method::GET,
+                "/futures/ticker",
+                QueryParams::new(),
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(timed.value(), &serde_json::json!({}));
+        assert_eq!(timed.timings().len(), 1);
+
+        let timing = timed.last_timing().unwrap();
+        assert_eq!(timing.method(), Method::GET);
+        assert_eq!(timing.path(), "/v3/futures/ticker");
+        assert_eq!(
+            timing.server_processing(),
+            Some(Duration::from_micros(1_500))
+        );
+        assert!(timing.total() >= timing.round_trip());
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_reuses_connection() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/v3", listener.local_addr().unwrap());
+
+        // Serves both requests on a single accepted connection
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            for _ in 0..2 {
+                let _ = socket.read(&mut buf).await.unwrap();
+                socket
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n\"pong\"")
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let config = RestClientConfig::default()
+            .with_endpoint(endpoint)
+            .with_rate_limiter_active(false);
+        let rest = RestClient::with_credentials(config, "key", "secret", "pphrase")
+            .expect("must create client");
+
+        rest.warm_up().await.unwrap();
+        rest.ping().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fast_lane_skips_timing() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/v3", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 2048];
+            let read = socket.read(&mut buf).await.unwrap();
+            let body = serde_json::json!({
+                "id": Uuid::nil(),
+                "type": "limit",
+                "side": "buy",
+                "quantity": 1_000,
+                "price": 95_000,
+                "tradingFee": 0,
+                "createdAt": "2025-05-12T07:30:05.657Z",
+                "filledAt": null,
+                "canceledAt": "2025-05-12T08:30:05.657Z",
+                "open": false,
+                "filled": false,
+                "canceled": true,
+                "clientId": null,
+            })
+            .to_string();
+            socket
+                .write_all(
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .unwrap();
+
+            String::from_utf8_lossy(&buf[..read]).to_lowercase()
+        });
+
+        let config = RestClientConfig::default()
+            .with_endpoint(endpoint)
+            .with_rate_limiter_active(false);
+        let rest = RestClient::with_credentials(config, "key", "secret", "pphrase")
+            .expect("must create client");
+
+        let timed = rest
+            .timed(rest.fast_lane().cancel_cross_order(Uuid::nil()))
+            .await
+            .unwrap();
+
+        assert!(timed.value().canceled());
+        assert!(timed.timings().is_empty());
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("post /v3/futures/cross/order/cancel "));
+        assert!(request.contains("lnm-access-signature"));
     }
 }
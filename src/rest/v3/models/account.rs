@@ -3,6 +3,11 @@ use std::fmt;
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::shared::models::{
+    amount::{Sats, Usd},
+    serde_formats,
+};
+
 /// LN Markets account information.
 ///
 /// # Examples
@@ -20,7 +25,7 @@ use uuid::Uuid;
 /// println!("Username: {}", account.username());
 /// println!("Email: {}", account.email());
 /// println!("Balance: {} sats", account.balance());
-/// println!("Synthetic USD balance: {} cents", account.synthetic_usd_balance());
+/// println!("Synthetic USD balance: {} USD", account.synthetic_usd_balance());
 /// println!("Fee tier: {}", account.fee_tier());
 ///
 /// if let Some(public_key) = account.linking_public_key() {
@@ -35,9 +40,16 @@ pub struct Account {
     id: Uuid,
     username: String,
     email: String,
+    #[serde(
+        alias = "synthetic_usd_balance",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     synthetic_usd_balance: u64,
+    #[serde(deserialize_with = "serde_formats::amount_string::deserialize")]
     balance: u64,
+    #[serde(alias = "fee_tier")]
     fee_tier: u64,
+    #[serde(alias = "linking_public_key")]
     linking_public_key: Option<String>,
 }
 
@@ -90,7 +102,7 @@ impl Account {
         &self.email
     }
 
-    /// Returns the synthetic USD balance (in cents).
+    /// Returns the synthetic USD balance. Reported by the API in cents.
     ///
     /// # Examples
     ///
@@ -98,12 +110,12 @@ impl Account {
     /// # fn example(account: lnm_sdk::rest::v3::models::Account) -> Result<(), Box<dyn std::error::Error>> {
     /// let synthetic_balance = account.synthetic_usd_balance();
     ///
-    /// println!("Synthetic USD balance: {} cents", synthetic_balance);
+    /// println!("Synthetic USD balance: {} USD", synthetic_balance);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn synthetic_usd_balance(&self) -> u64 {
-        self.synthetic_usd_balance
+    pub fn synthetic_usd_balance(&self) -> Usd {
+        Usd::from_cents(self.synthetic_usd_balance as i64)
     }
 
     /// Returns the account balance in satoshis.
@@ -118,8 +130,8 @@ impl Account {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn balance(&self) -> u64 {
-        self.balance
+    pub fn balance(&self) -> Sats {
+        Sats::from(self.balance)
     }
 
     /// Returns the fee tier for this account.
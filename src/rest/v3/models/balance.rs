@@ -0,0 +1,117 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::shared::models::{SATS_PER_BTC, oracle::Index, price::Price};
+
+/// Account balance presented in sats, BTC and USD.
+///
+/// The USD value is computed at an index price, whose timestamp is kept alongside, so UIs can
+/// show how fresh the conversion is.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// let balance = rest.balance_view().await?;
+///
+/// println!(
+///     "{} sats / {:.8} BTC / {:.2} USD (rate from {})",
+///     balance.sats(),
+///     balance.btc(),
+///     balance.usd(),
+///     balance.rate_time()
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceView {
+    sats: u64,
+    rate: Price,
+    rate_time: DateTime<Utc>,
+}
+
+impl BalanceView {
+    /// Creates a view of a balance of `sats`, converted to USD at `rate`, observed at
+    /// `rate_time`.
+    pub fn new(sats: u64, rate: Price, rate_time: DateTime<Utc>) -> Self {
+        Self {
+            sats,
+            rate,
+            rate_time,
+        }
+    }
+
+    /// Creates a view of a balance of `sats`, converted to USD at the given index price.
+    pub fn from_index(sats: u64, index: &Index) -> Self {
+        Self::new(sats, index.index(), index.time())
+    }
+
+    /// Balance in satoshis.
+    pub fn sats(&self) -> u64 {
+        self.sats
+    }
+
+    /// Balance in BTC.
+    pub fn btc(&self) -> f64 {
+        self.sats as f64 / SATS_PER_BTC
+    }
+
+    /// Balance in USD, at [`rate`](Self::rate).
+    pub fn usd(&self) -> f64 {
+        self.btc() * self.rate.as_f64()
+    }
+
+    /// Index price (USD per BTC) used for the USD conversion.
+    pub fn rate(&self) -> Price {
+        self.rate
+    }
+
+    /// Timestamp of the index price used for the USD conversion.
+    pub fn rate_time(&self) -> DateTime<Utc> {
+        self.rate_time
+    }
+
+    pub fn as_data_str(&self) -> String {
+        format!(
+            "sats: {}\nbtc: {:.8}\nusd: {:.2}\nrate: {}\nrate_time: {}",
+            self.sats,
+            self.btc(),
+            self.usd(),
+            self.rate,
+            self.rate_time.to_rfc3339()
+        )
+    }
+}
+
+impl fmt::Display for BalanceView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BalanceView:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_view_conversions() {
+        let index: Index = serde_json::from_value(serde_json::json!({
+            "time": "2025-05-12T07:30:05.657Z",
+            "index": 100_000,
+        }))
+        .unwrap();
+
+        let view = BalanceView::from_index(250_000, &index);
+
+        assert_eq!(view.sats(), 250_000);
+        assert!((view.btc() - 0.0025).abs() < 1e-12);
+        assert!((view.usd() - 250.).abs() < 1e-9);
+        assert_eq!(view.rate_time(), index.time());
+    }
+}
@@ -1,9 +1,14 @@
+use chrono::{DateTime, Utc};
 use thiserror::Error;
+use uuid::Uuid;
 
 use crate::shared::models::{
     cross_leverage::CrossLeverage,
     error::{CrossQuantityValidationError, MarginValidationError, QuantityValidationError},
-    quantity::cross::CrossQuantity,
+    leverage::Leverage,
+    network::BitcoinNetwork,
+    price::Price,
+    quantity::{cross::CrossQuantity, order::OrderQuantity},
 };
 
 #[derive(Debug, Error)]
@@ -46,4 +51,104 @@ pub enum FuturesIsolatedTradeRequestValidationError {
 
     #[error("Take profit must be higher than the entry price")]
     TakeProfitLowerThanPrice,
+
+    #[error("Stop loss ({stoploss}) is beyond the estimated liquidation price ({liquidation})")]
+    StopLossBeyondLiquidation { stoploss: Price, liquidation: Price },
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum WithdrawalRequestValidationError {
+    #[error("Invoice is for {invoice_network}, but the client is configured for {client_network}")]
+    InvoiceNetworkMismatch {
+        invoice_network: BitcoinNetwork,
+        client_network: BitcoinNetwork,
+    },
+
+    #[error("Address {address} is not valid for {client_network}, the client's configured network")]
+    WrongNetworkAddress {
+        address: String,
+        client_network: BitcoinNetwork,
+    },
+
+    #[error("Invoice must specify an amount")]
+    InvoiceMissingAmount,
+
+    #[error("Invoice expired at {expires_at}")]
+    InvoiceExpired { expires_at: DateTime<Utc> },
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CrossPositionCloseValidationError {
+    #[error("Cross position {actual} doesn't match the requested position {expected}")]
+    PositionMismatch { expected: Uuid, actual: Uuid },
+
+    #[error("No cross position is open")]
+    NoOpenPosition,
+
+    #[error("Quantity {quantity} exceeds the open cross position size {position}")]
+    QuantityAbovePosition {
+        quantity: OrderQuantity,
+        position: u64,
+    },
+
+    #[error("[QuantityValidation] {0}")]
+    QuantityValidation(#[from] QuantityValidationError),
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ExchangeLimitsValidationError {
+    #[error("Quantity {quantity} is below the exchange minimum {min}")]
+    QuantityBelowMin {
+        quantity: OrderQuantity,
+        min: OrderQuantity,
+    },
+
+    #[error("Quantity {quantity} is above the exchange maximum {max}")]
+    QuantityAboveMax {
+        quantity: OrderQuantity,
+        max: OrderQuantity,
+    },
+
+    #[error("Quantity {quantity} is not a multiple of the exchange quantity step {step}")]
+    QuantityNotMultipleOfStep { quantity: OrderQuantity, step: u64 },
+
+    #[error("Leverage {leverage} is above the exchange maximum {max}")]
+    LeverageAboveMax { leverage: Leverage, max: Leverage },
+
+    #[error("Price {price} is not a multiple of the exchange tick {tick}")]
+    PriceNotMultipleOfTick { price: Price, tick: f64 },
+
+    #[error("Price {price} has more than the {decimals} decimal places allowed by the exchange")]
+    PriceTooPrecise { price: Price, decimals: u32 },
+
+    #[error("Withdrawal amount {amount} is below the exchange minimum {min}")]
+    WithdrawalBelowMin { amount: u64, min: u64 },
+
+    #[error("Minimum quantity {min} is above the maximum quantity {max}")]
+    QuantityRangeInverted {
+        min: OrderQuantity,
+        max: OrderQuantity,
+    },
+
+    #[error("Quantity step must be positive")]
+    InvalidQuantityStep,
+
+    #[error("Price tick {tick} is not a positive multiple of {}", Price::TICK)]
+    InvalidPriceTick { tick: f64 },
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ExposureLimitsValidationError {
+    #[error("Net long exposure {exposure} USD is above the maximum {max} USD")]
+    NetLongAboveMax { exposure: u64, max: u64 },
+
+    #[error("Net short exposure {exposure} USD is above the maximum {max} USD")]
+    NetShortAboveMax { exposure: u64, max: u64 },
+
+    #[error("Gross exposure {exposure} USD is above the maximum {max} USD")]
+    GrossAboveMax { exposure: u64, max: u64 },
 }
@@ -0,0 +1,310 @@
+use std::fmt;
+
+use crate::shared::models::{quantity::order::OrderQuantity, trade::TradeSide};
+
+use super::{
+    error::ExposureLimitsValidationError,
+    trade::{CrossPosition, Trade},
+};
+
+/// Long and short exposure, in USD, netted across isolated trades and the cross position.
+///
+/// Only running isolated trades count towards the exposure. Open trades (unfilled limit orders)
+/// and closed trades are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// let exposure = rest.net_exposure().await?;
+///
+/// println!(
+///     "Long: {} USD, short: {} USD, net: {} USD",
+///     exposure.long(),
+///     exposure.short(),
+///     exposure.net()
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetExposure {
+    long: u64,
+    short: u64,
+}
+
+impl NetExposure {
+    /// Creates an exposure of `long` and `short` USD.
+    pub fn new(long: u64, short: u64) -> Self {
+        Self { long, short }
+    }
+
+    /// Nets the exposure of the running `trades` and the cross `position`, if any.
+    pub fn from_positions<'a>(
+        trades: impl IntoIterator<Item = &'a Trade>,
+        position: Option<&CrossPosition>,
+    ) -> Self {
+        let mut exposure = Self::default();
+
+        for trade in trades.into_iter().filter(|trade| trade.running()) {
+            exposure = exposure.with_order(trade.side(), trade.quantity());
+        }
+
+        if let Some(position) = position {
+            let quantity = position.quantity();
+            let size = quantity.unsigned_abs();
+            if quantity > 0 {
+                exposure.long += size;
+            } else {
+                exposure.short += size;
+            }
+        }
+
+        exposure
+    }
+
+    /// Gross long exposure, in USD.
+    pub fn long(&self) -> u64 {
+        self.long
+    }
+
+    /// Gross short exposure, in USD.
+    pub fn short(&self) -> u64 {
+        self.short
+    }
+
+    /// Net exposure, in USD. Positive when net long, negative when net short.
+    pub fn net(&self) -> i64 {
+        self.long as i64 - self.short as i64
+    }
+
+    /// Sum of the long and short exposure, in USD.
+    pub fn gross(&self) -> u64 {
+        self.long + self.short
+    }
+
+    /// Side of the net exposure, or `None` if flat.
+    pub fn net_side(&self) -> Option<TradeSide> {
+        match self.net() {
+            net if net > 0 => Some(TradeSide::Buy),
+            net if net < 0 => Some(TradeSide::Sell),
+            _ => None,
+        }
+    }
+
+    /// Returns the exposure after adding an order of `quantity` on `side`.
+    pub fn with_order(mut self, side: TradeSide, quantity: OrderQuantity) -> Self {
+        match side {
+            TradeSide::Buy => self.long += quantity.as_u64(),
+            TradeSide::Sell => self.short += quantity.as_u64(),
+        }
+        self
+    }
+
+    pub fn as_data_str(&self) -> String {
+        format!(
+            "long: {}\nshort: {}\nnet: {}",
+            self.long,
+            self.short,
+            self.net()
+        )
+    }
+}
+
+impl fmt::Display for NetExposure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NetExposure:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-side caps on the exposure, in USD, checked against a [`NetExposure`].
+///
+/// Long and short caps apply to the net exposure on that side, so a short trade reduces the
+/// exposure counted against the long cap. The gross cap applies to the sum of both sides.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{ExposureLimits, NetExposure, OrderQuantity, TradeSide};
+///
+/// let limits = ExposureLimits::default().with_max_net_long(10_000);
+/// let exposure = NetExposure::new(9_000, 500);
+///
+/// let buy = OrderQuantity::try_from(2_000).unwrap();
+/// assert!(limits.check_order(&exposure, TradeSide::Buy, buy).is_err());
+///
+/// // Orders reducing the net exposure pass the net caps
+/// assert!(limits.check_order(&exposure, TradeSide::Sell, buy).is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExposureLimits {
+    max_net_long: Option<u64>,
+    max_net_short: Option<u64>,
+    max_gross: Option<u64>,
+}
+
+impl ExposureLimits {
+    /// Maximum net long exposure, in USD.
+    pub fn max_net_long(&self) -> Option<u64> {
+        self.max_net_long
+    }
+
+    /// Maximum net short exposure, in USD.
+    pub fn max_net_short(&self) -> Option<u64> {
+        self.max_net_short
+    }
+
+    /// Maximum gross exposure, in USD.
+    pub fn max_gross(&self) -> Option<u64> {
+        self.max_gross
+    }
+
+    /// Sets the maximum net long exposure, in USD.
+    ///
+    /// Default: `None`, unlimited
+    pub fn with_max_net_long(mut self, max: u64) -> Self {
+        self.max_net_long = Some(max);
+        self
+    }
+
+    /// Sets the maximum net short exposure, in USD.
+    ///
+    /// Default: `None`, unlimited
+    pub fn with_max_net_short(mut self, max: u64) -> Self {
+        self.max_net_short = Some(max);
+        self
+    }
+
+    /// Sets the maximum gross exposure, in USD.
+    ///
+    /// Default: `None`, unlimited
+    pub fn with_max_gross(mut self, max: u64) -> Self {
+        self.max_gross = Some(max);
+        self
+    }
+
+    /// Checks `exposure` against the caps.
+    pub fn check(&self, exposure: &NetExposure) -> Result<(), ExposureLimitsValidationError> {
+        let net = exposure.net();
+
+        if let Some(max) = self.max_net_long
+            && net > 0
+            && net.unsigned_abs() > max
+        {
+            return Err(ExposureLimitsValidationError::NetLongAboveMax {
+                exposure: net.unsigned_abs(),
+                max,
+            });
+        }
+
+        if let Some(max) = self.max_net_short
+            && net < 0
+            && net.unsigned_abs() > max
+        {
+            return Err(ExposureLimitsValidationError::NetShortAboveMax {
+                exposure: net.unsigned_abs(),
+                max,
+            });
+        }
+
+        if let Some(max) = self.max_gross
+            && exposure.gross() > max
+        {
+            return Err(ExposureLimitsValidationError::GrossAboveMax {
+                exposure: exposure.gross(),
+                max,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks the exposure resulting from adding an order of `quantity` on `side` to `exposure`.
+    ///
+    /// Orders reducing the net exposure without flipping its side pass the net caps even if the
+    /// current exposure exceeds them, so positions above the caps can always be reduced.
+    pub fn check_order(
+        &self,
+        exposure: &NetExposure,
+        side: TradeSide,
+        quantity: OrderQuantity,
+    ) -> Result<(), ExposureLimitsValidationError> {
+        let projected = exposure.with_order(side, quantity);
+
+        let reduces_net = projected.net().signum() == exposure.net().signum()
+            && projected.net().unsigned_abs() < exposure.net().unsigned_abs();
+        if reduces_net {
+            let gross_only = Self {
+                max_gross: self.max_gross,
+                ..Default::default()
+            };
+            return gross_only.check(&projected);
+        }
+
+        self.check(&projected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exposure_limits() {
+        let limits = ExposureLimits::default()
+            .with_max_net_long(1_000)
+            .with_max_net_short(500)
+            .with_max_gross(5_000);
+
+        let exposure = NetExposure::new(2_000, 1_200);
+        assert_eq!(exposure.net(), 800);
+        assert_eq!(exposure.net_side(), Some(TradeSide::Buy));
+        assert!(limits.check(&exposure).is_ok());
+
+        let quantity = |value: u32| OrderQuantity::try_from(value).unwrap();
+
+        assert!(matches!(
+            limits.check_order(&exposure, TradeSide::Buy, quantity(300)),
+            Err(ExposureLimitsValidationError::NetLongAboveMax {
+                exposure: 1_100,
+                max: 1_000
+            })
+        ));
+        assert!(
+            limits
+                .check_order(&exposure, TradeSide::Sell, quantity(1_300))
+                .is_ok()
+        );
+        assert!(matches!(
+            limits.check_order(&exposure, TradeSide::Sell, quantity(1_400)),
+            Err(ExposureLimitsValidationError::NetShortAboveMax {
+                exposure: 600,
+                max: 500
+            })
+        ));
+        assert!(matches!(
+            limits.check_order(&exposure, TradeSide::Buy, quantity(2_000)),
+            Err(ExposureLimitsValidationError::NetLongAboveMax { .. })
+        ));
+
+        // Above the net long cap, sells still pass while they reduce the net exposure
+        let exposure = NetExposure::new(3_000, 0);
+        assert!(limits.check(&exposure).is_err());
+        assert!(
+            limits
+                .check_order(&exposure, TradeSide::Sell, quantity(100))
+                .is_ok()
+        );
+        assert!(matches!(
+            limits.check_order(&exposure, TradeSide::Sell, quantity(2_500)),
+            Err(ExposureLimitsValidationError::GrossAboveMax {
+                exposure: 5_500,
+                max: 5_000
+            })
+        ));
+    }
+}
@@ -4,6 +4,8 @@ use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::shared::models::amount::Sats;
+
 /// Information about a given funding fee that was paid or received, corresponding to a cross
 /// margin position.
 ///
@@ -30,6 +32,7 @@ use uuid::Uuid;
 #[serde(rename_all = "camelCase")]
 pub struct CrossFunding {
     time: DateTime<Utc>,
+    #[serde(alias = "settlement_id")]
     settlement_id: Uuid,
     fee: i64,
 }
@@ -46,8 +49,8 @@ impl CrossFunding {
     }
 
     /// Funding fee amount in satoshis.
-    pub fn fee(&self) -> i64 {
-        self.fee
+    pub fn fee(&self) -> Sats {
+        Sats::from(self.fee)
     }
 
     pub fn as_data_str(&self) -> String {
@@ -97,7 +100,9 @@ impl fmt::Display for CrossFunding {
 #[serde(rename_all = "camelCase")]
 pub struct IsolatedFunding {
     time: DateTime<Utc>,
+    #[serde(alias = "settlement_id")]
     settlement_id: Uuid,
+    #[serde(alias = "trade_id")]
     trade_id: Uuid,
     fee: i64,
 }
@@ -119,8 +124,8 @@ impl IsolatedFunding {
     }
 
     /// Funding fee amount in satoshis.
-    pub fn fee(&self) -> i64 {
-        self.fee
+    pub fn fee(&self) -> Sats {
+        Sats::from(self.fee)
     }
 
     pub fn as_data_str(&self) -> String {
@@ -171,7 +176,9 @@ impl fmt::Display for IsolatedFunding {
 pub struct FundingSettlement {
     id: Uuid,
     time: DateTime<Utc>,
+    #[serde(alias = "fixing_price")]
     fixing_price: f64,
+    #[serde(alias = "funding_rate")]
     funding_rate: f64,
 }
 
@@ -0,0 +1,184 @@
+use std::{fmt, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+use crate::shared::rest::error::RestApiError;
+
+/// Latency above which a successful health check reports the exchange as degraded.
+const DEGRADED_LATENCY: Duration = Duration::from_secs(2);
+
+/// Health of the exchange API, as observed by a health check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExchangeStatus {
+    /// The API responded successfully and promptly.
+    Operational,
+    /// The API responded slowly, or with a server-side or rate limit error.
+    Degraded,
+    /// The API reported being under maintenance.
+    Maintenance,
+    /// The API could not be reached.
+    Unreachable,
+}
+
+impl ExchangeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Operational => "operational",
+            Self::Degraded => "degraded",
+            Self::Maintenance => "maintenance",
+            Self::Unreachable => "unreachable",
+        }
+    }
+}
+
+impl fmt::Display for ExchangeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Result of a health check of the exchange API.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::models::ExchangeStatus;
+///
+/// let health = rest.status().await;
+///
+/// if health.status() != ExchangeStatus::Operational {
+///     println!("Exchange is {}, skipping trading", health.status());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExchangeHealth {
+    status: ExchangeStatus,
+    latency: Duration,
+    checked_at: DateTime<Utc>,
+    error: Option<String>,
+}
+
+impl ExchangeHealth {
+    pub(in crate::rest::v3) fn from_check(
+        result: &Result<(), RestApiError>,
+        latency: Duration,
+        checked_at: DateTime<Utc>,
+    ) -> Self {
+        let status = match result {
+            Ok(()) if latency > DEGRADED_LATENCY => ExchangeStatus::Degraded,
+            Ok(()) => ExchangeStatus::Operational,
+            Err(e) if e.is_maintenance() => ExchangeStatus::Maintenance,
+            Err(e) if e.status().is_some() => ExchangeStatus::Degraded,
+            Err(_) => ExchangeStatus::Unreachable,
+        };
+
+        Self {
+            status,
+            latency,
+            checked_at,
+            error: result.as_ref().err().map(ToString::to_string),
+        }
+    }
+
+    /// Observed health status.
+    pub fn status(&self) -> ExchangeStatus {
+        self.status
+    }
+
+    /// Returns `true` if the exchange is [`Operational`](ExchangeStatus::Operational).
+    pub fn is_operational(&self) -> bool {
+        self.status == ExchangeStatus::Operational
+    }
+
+    /// Time taken by the health check request.
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// Time the health check was performed.
+    pub fn checked_at(&self) -> DateTime<Utc> {
+        self.checked_at
+    }
+
+    /// Error returned by the health check request, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn as_data_str(&self) -> String {
+        format!(
+            "status: {}\nlatency: {:?}\nchecked_at: {}\nerror: {}",
+            self.status,
+            self.latency,
+            self.checked_at.to_rfc3339(),
+            self.error.as_deref().unwrap_or("none")
+        )
+    }
+}
+
+impl fmt::Display for ExchangeHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Exchange Health:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Method, StatusCode};
+
+    use super::*;
+    use crate::shared::rest::error::ErrorResponseContext;
+
+    fn error_response(status: StatusCode, text: &str) -> RestApiError {
+        RestApiError::ErrorResponse(Box::new(ErrorResponseContext {
+            method: Method::GET,
+            path: "/v3/ping".to_string(),
+            status,
+            request_id: None,
+            correlation_id: None,
+            retry_after: None,
+            text: text.to_string(),
+        }))
+    }
+
+    #[test]
+    fn test_health_from_check() {
+        let now = Utc::now();
+        let fast = Duration::from_millis(50);
+
+        let status = |result: Result<(), RestApiError>, latency| {
+            ExchangeHealth::from_check(&result, latency, now).status()
+        };
+
+        assert_eq!(status(Ok(()), fast), ExchangeStatus::Operational);
+        assert_eq!(
+            status(Ok(()), Duration::from_secs(3)),
+            ExchangeStatus::Degraded
+        );
+        assert_eq!(
+            status(
+                Err(error_response(StatusCode::SERVICE_UNAVAILABLE, "")),
+                fast
+            ),
+            ExchangeStatus::Maintenance
+        );
+        assert_eq!(
+            status(
+                Err(error_response(StatusCode::BAD_GATEWAY, "upstream error")),
+                fast
+            ),
+            ExchangeStatus::Degraded
+        );
+        assert_eq!(
+            status(Err(RestApiError::UrlParse("bad".to_string())), fast),
+            ExchangeStatus::Unreachable
+        );
+    }
+}
@@ -0,0 +1,448 @@
+use crate::shared::models::{
+    leverage::Leverage,
+    price::Price,
+    quantity::order::OrderQuantity,
+    trade::{TradeExecution, TradeSize},
+};
+
+use super::error::ExchangeLimitsValidationError;
+
+/// Trading and withdrawal limits enforced by the exchange.
+///
+/// LN Markets' v3 API doesn't expose an endpoint with instrument metadata, so the defaults mirror
+/// the limits enforced by the SDK's validated types ([`OrderQuantity`], [`Leverage`] and
+/// [`Price`]). If the exchange tightens its limits, or an account is subject to different ones,
+/// updated values can be provided via
+/// [`RestClientConfig::with_exchange_limits`](crate::rest::v3::RestClientConfig::with_exchange_limits)
+/// so requests violating them are rejected locally, before being sent.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{ExchangeLimits, Leverage, OrderQuantity};
+///
+/// let limits = ExchangeLimits::default()
+///     .with_max_quantity(OrderQuantity::try_from(100_000).unwrap())
+///     .unwrap()
+///     .with_max_leverage(Leverage::try_from(50).unwrap());
+///
+/// assert!(limits.validate_quantity(OrderQuantity::try_from(1_000).unwrap()).is_ok());
+/// assert!(limits.validate_quantity(OrderQuantity::try_from(200_000).unwrap()).is_err());
+/// assert!(limits.validate_leverage(Leverage::try_from(75).unwrap()).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeLimits {
+    min_quantity: OrderQuantity,
+    max_quantity: OrderQuantity,
+    max_leverage: Leverage,
+    quantity_step: u64,
+    price_tick: f64,
+    price_decimals: u32,
+    min_withdrawal: u64,
+}
+
+impl ExchangeLimits {
+    /// Returns the minimum order quantity (USD).
+    pub fn min_quantity(&self) -> OrderQuantity {
+        self.min_quantity
+    }
+
+    /// Returns the maximum order quantity (USD).
+    pub fn max_quantity(&self) -> OrderQuantity {
+        self.max_quantity
+    }
+
+    /// Returns the maximum leverage.
+    pub fn max_leverage(&self) -> Leverage {
+        self.max_leverage
+    }
+
+    /// Returns the order quantity step (USD). Quantities must be multiples of it.
+    pub fn quantity_step(&self) -> u64 {
+        self.quantity_step
+    }
+
+    /// Returns the price tick size (USD).
+    pub fn price_tick(&self) -> f64 {
+        self.price_tick
+    }
+
+    /// Returns the maximum number of decimal places allowed in prices.
+    pub fn price_decimals(&self) -> u32 {
+        self.price_decimals
+    }
+
+    /// Returns the minimum withdrawal amount (sats).
+    pub fn min_withdrawal(&self) -> u64 {
+        self.min_withdrawal
+    }
+
+    /// Sets the minimum order quantity (USD).
+    ///
+    /// Default: [`OrderQuantity::MIN`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExchangeLimitsValidationError::QuantityRangeInverted`] if `min_quantity` is above
+    /// the maximum order quantity.
+    pub fn with_min_quantity(
+        mut self,
+        min_quantity: OrderQuantity,
+    ) -> Result<Self, ExchangeLimitsValidationError> {
+        if min_quantity > self.max_quantity {
+            return Err(ExchangeLimitsValidationError::QuantityRangeInverted {
+                min: min_quantity,
+                max: self.max_quantity,
+            });
+        }
+
+        self.min_quantity = min_quantity;
+        Ok(self)
+    }
+
+    /// Sets the maximum order quantity (USD).
+    ///
+    /// Default: [`OrderQuantity::MAX`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExchangeLimitsValidationError::QuantityRangeInverted`] if `max_quantity` is below
+    /// the minimum order quantity.
+    pub fn with_max_quantity(
+        mut self,
+        max_quantity: OrderQuantity,
+    ) -> Result<Self, ExchangeLimitsValidationError> {
+        if max_quantity < self.min_quantity {
+            return Err(ExchangeLimitsValidationError::QuantityRangeInverted {
+                min: self.min_quantity,
+                max: max_quantity,
+            });
+        }
+
+        self.max_quantity = max_quantity;
+        Ok(self)
+    }
+
+    /// Sets the maximum leverage.
+    ///
+    /// Default: [`Leverage::MAX`]
+    pub fn with_max_leverage(mut self, max_leverage: Leverage) -> Self {
+        self.max_leverage = max_leverage;
+        self
+    }
+
+    /// Sets the order quantity step (USD).
+    ///
+    /// Default: `1`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExchangeLimitsValidationError::InvalidQuantityStep`] if `quantity_step` is zero.
+    pub fn with_quantity_step(
+        mut self,
+        quantity_step: u64,
+    ) -> Result<Self, ExchangeLimitsValidationError> {
+        if quantity_step == 0 {
+            return Err(ExchangeLimitsValidationError::InvalidQuantityStep);
+        }
+
+        self.quantity_step = quantity_step;
+        Ok(self)
+    }
+
+    /// Sets the price tick size (USD).
+    ///
+    /// Default: [`Price::TICK`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExchangeLimitsValidationError::InvalidPriceTick`] if `price_tick` is not a
+    /// positive multiple of [`Price::TICK`].
+    pub fn with_price_tick(
+        mut self,
+        price_tick: f64,
+    ) -> Result<Self, ExchangeLimitsValidationError> {
+        if !(price_tick > 0. && is_multiple_of(price_tick, Price::TICK)) {
+            return Err(ExchangeLimitsValidationError::InvalidPriceTick { tick: price_tick });
+        }
+
+        self.price_tick = price_tick;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of decimal places allowed in prices.
+    ///
+    /// Default: `1`, the precision of [`Price::TICK`]
+    pub fn with_price_decimals(mut self, price_decimals: u32) -> Self {
+        self.price_decimals = price_decimals;
+        self
+    }
+
+    /// Sets the minimum withdrawal amount (sats).
+    ///
+    /// Default: `1`
+    pub fn with_min_withdrawal(mut self, min_withdrawal: u64) -> Self {
+        self.min_withdrawal = min_withdrawal;
+        self
+    }
+
+    /// Validates an order quantity against the quantity limits and step.
+    pub fn validate_quantity(
+        &self,
+        quantity: OrderQuantity,
+    ) -> Result<(), ExchangeLimitsValidationError> {
+        if quantity < self.min_quantity {
+            return Err(ExchangeLimitsValidationError::QuantityBelowMin {
+                quantity,
+                min: self.min_quantity,
+            });
+        }
+
+        if quantity > self.max_quantity {
+            return Err(ExchangeLimitsValidationError::QuantityAboveMax {
+                quantity,
+                max: self.max_quantity,
+            });
+        }
+
+        if !quantity.as_u64().is_multiple_of(self.quantity_step) {
+            return Err(ExchangeLimitsValidationError::QuantityNotMultipleOfStep {
+                quantity,
+                step: self.quantity_step,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validates a leverage against the maximum leverage.
+    pub fn validate_leverage(
+        &self,
+        leverage: Leverage,
+    ) -> Result<(), ExchangeLimitsValidationError> {
+        if leverage > self.max_leverage {
+            return Err(ExchangeLimitsValidationError::LeverageAboveMax {
+                leverage,
+                max: self.max_leverage,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validates that a price has no more decimal places than allowed, and is a multiple of the
+    /// price tick.
+    pub fn validate_price(&self, price: Price) -> Result<(), ExchangeLimitsValidationError> {
+        if !is_multiple_of(price.as_f64(), 10f64.powi(-(self.price_decimals as i32))) {
+            return Err(ExchangeLimitsValidationError::PriceTooPrecise {
+                price,
+                decimals: self.price_decimals,
+            });
+        }
+
+        if !is_multiple_of(price.as_f64(), self.price_tick) {
+            return Err(ExchangeLimitsValidationError::PriceNotMultipleOfTick {
+                price,
+                tick: self.price_tick,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validates the limit price of a trade execution, if any.
+    pub fn validate_execution(
+        &self,
+        execution: TradeExecution,
+    ) -> Result<(), ExchangeLimitsValidationError> {
+        match execution {
+            TradeExecution::Market => Ok(()),
+            TradeExecution::Limit(price) => self.validate_price(price),
+        }
+    }
+
+    /// Validates the quantity (if sized by quantity), leverage, execution, stoploss and takeprofit
+    /// of a new isolated trade.
+    pub(crate) fn validate_trade(
+        &self,
+        size: &TradeSize,
+        leverage: Leverage,
+        execution: TradeExecution,
+        stoploss: Option<Price>,
+        takeprofit: Option<Price>,
+    ) -> Result<(), ExchangeLimitsValidationError> {
+        if let TradeSize::Quantity(quantity) = size {
+            self.validate_quantity(*quantity)?;
+        }
+        self.validate_leverage(leverage)?;
+        self.validate_execution(execution)?;
+        for price in [stoploss, takeprofit].into_iter().flatten() {
+            self.validate_price(price)?;
+        }
+
+        Ok(())
+    }
+
+    /// Validates a withdrawal amount (sats) against the minimum withdrawal amount.
+    pub fn validate_withdrawal(&self, amount: u64) -> Result<(), ExchangeLimitsValidationError> {
+        if amount < self.min_withdrawal {
+            return Err(ExchangeLimitsValidationError::WithdrawalBelowMin {
+                amount,
+                min: self.min_withdrawal,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn is_multiple_of(value: f64, step: f64) -> bool {
+    let steps = value / step;
+    (steps - steps.round()).abs() < 1e-9
+}
+
+impl Default for ExchangeLimits {
+    fn default() -> Self {
+        Self {
+            min_quantity: OrderQuantity::MIN,
+            max_quantity: OrderQuantity::MAX,
+            max_leverage: Leverage::MAX,
+            quantity_step: 1,
+            price_tick: Price::TICK,
+            price_decimals: 1,
+            min_withdrawal: 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_limits_accept_valid_values() {
+        let limits = ExchangeLimits::default();
+
+        assert!(limits.validate_quantity(OrderQuantity::MIN).is_ok());
+        assert!(limits.validate_quantity(OrderQuantity::MAX).is_ok());
+        assert!(limits.validate_leverage(Leverage::MAX).is_ok());
+        assert!(
+            limits
+                .validate_price(Price::try_from(100_000.5).unwrap())
+                .is_ok()
+        );
+        assert!(limits.validate_withdrawal(1).is_ok());
+    }
+
+    #[test]
+    fn test_custom_limits_reject_out_of_range_values() {
+        let limits = ExchangeLimits::default()
+            .with_min_quantity(OrderQuantity::try_from(10).unwrap())
+            .unwrap()
+            .with_max_quantity(OrderQuantity::try_from(1_000).unwrap())
+            .unwrap()
+            .with_max_leverage(Leverage::try_from(25).unwrap())
+            .with_price_tick(5.)
+            .unwrap()
+            .with_min_withdrawal(1_000);
+
+        assert!(matches!(
+            limits.validate_quantity(OrderQuantity::try_from(5).unwrap()),
+            Err(ExchangeLimitsValidationError::QuantityBelowMin { .. })
+        ));
+        assert!(matches!(
+            limits.validate_quantity(OrderQuantity::try_from(1_001).unwrap()),
+            Err(ExchangeLimitsValidationError::QuantityAboveMax { .. })
+        ));
+        assert!(matches!(
+            limits.validate_leverage(Leverage::try_from(26).unwrap()),
+            Err(ExchangeLimitsValidationError::LeverageAboveMax { .. })
+        ));
+        assert!(matches!(
+            limits.validate_execution(TradeExecution::Limit(Price::try_from(100_002.5).unwrap())),
+            Err(ExchangeLimitsValidationError::PriceNotMultipleOfTick { .. })
+        ));
+        assert!(
+            limits
+                .validate_execution(TradeExecution::Limit(Price::try_from(100_005).unwrap()))
+                .is_ok()
+        );
+        assert!(matches!(
+            limits.validate_withdrawal(999),
+            Err(ExchangeLimitsValidationError::WithdrawalBelowMin { .. })
+        ));
+    }
+
+    #[test]
+    fn test_price_tick_must_be_multiple_of_base_tick() {
+        for tick in [0.3, -1., 0.] {
+            assert!(matches!(
+                ExchangeLimits::default().with_price_tick(tick),
+                Err(ExchangeLimitsValidationError::InvalidPriceTick { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_inverted_quantity_range_is_rejected() {
+        let limits = ExchangeLimits::default()
+            .with_max_quantity(OrderQuantity::try_from(1_000).unwrap())
+            .unwrap();
+
+        assert!(matches!(
+            limits
+                .clone()
+                .with_min_quantity(OrderQuantity::try_from(1_001).unwrap()),
+            Err(ExchangeLimitsValidationError::QuantityRangeInverted { .. })
+        ));
+        assert_eq!(
+            limits
+                .with_min_quantity(OrderQuantity::try_from(1_000).unwrap())
+                .unwrap()
+                .min_quantity(),
+            OrderQuantity::try_from(1_000).unwrap()
+        );
+
+        let limits = ExchangeLimits::default()
+            .with_min_quantity(OrderQuantity::try_from(100).unwrap())
+            .unwrap();
+        assert!(matches!(
+            limits.with_max_quantity(OrderQuantity::try_from(50).unwrap()),
+            Err(ExchangeLimitsValidationError::QuantityRangeInverted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_precision_limits() {
+        let limits = ExchangeLimits::default()
+            .with_quantity_step(10)
+            .unwrap()
+            .with_price_decimals(0);
+        assert_eq!(limits.quantity_step(), 10);
+        assert_eq!(limits.price_decimals(), 0);
+
+        assert!(
+            limits
+                .validate_quantity(OrderQuantity::try_from(20).unwrap())
+                .is_ok()
+        );
+        assert!(matches!(
+            limits.validate_quantity(OrderQuantity::try_from(25).unwrap()),
+            Err(ExchangeLimitsValidationError::QuantityNotMultipleOfStep { step: 10, .. })
+        ));
+        assert!(
+            limits
+                .validate_price(Price::try_from(100_000).unwrap())
+                .is_ok()
+        );
+        assert!(matches!(
+            limits.validate_price(Price::try_from(100_000.5).unwrap()),
+            Err(ExchangeLimitsValidationError::PriceTooPrecise { decimals: 0, .. })
+        ));
+
+        assert!(matches!(
+            ExchangeLimits::default().with_quantity_step(0),
+            Err(ExchangeLimitsValidationError::InvalidQuantityStep)
+        ));
+    }
+}
@@ -1,32 +1,58 @@
 pub(in crate::rest::v3) mod account;
+pub(in crate::rest::v3) mod balance;
 pub(in crate::rest::v3) mod error;
+pub(in crate::rest::v3) mod exposure;
 pub(in crate::rest::v3) mod funding;
+pub(in crate::rest::v3) mod health;
+pub(in crate::rest::v3) mod limits;
+pub(in crate::rest::v3) mod notification;
 pub(in crate::rest::v3) mod page;
 pub(in crate::rest::v3) mod ticker;
 pub(in crate::rest::v3) mod trade;
 pub(in crate::rest::v3) mod transfer;
+pub(in crate::rest::v3) mod withdrawal;
 
 pub use uuid::Uuid;
 
 pub use crate::shared::models::{
     SATS_PER_BTC,
+    address::BitcoinAddress,
+    amount::{Sats, Usd},
     client_id::ClientId,
+    condition::{PriceCondition, PriceReference, PriceTrigger},
     cross_leverage::CrossLeverage,
+    currency::Currency,
+    instrument::Instrument,
+    interner::{IdInterner, Interned},
+    invoice::Bolt11Invoice,
     leverage::Leverage,
     margin::Margin,
+    network::BitcoinNetwork,
     ohlc::{OhlcCandle, OhlcRange},
     oracle::{Index, LastPrice},
     price::{Percentage, PercentageCapped, Price},
     quantity::{Quantity, cross::CrossQuantity, order::OrderQuantity},
+    resample::{PriceSample, ResampledPrice, resample},
+    rounding::{RoundingMode, RoundingPolicy},
+    serde_formats,
     ticker::TickerPrice,
     trade::{
-        TradeExecution, TradeExecutionType, TradeSide, TradeSize, TradeStatus, util as trade_util,
+        MarginMode, TradeExecution, TradeExecutionType, TradeSide, TradeSize, TradeStatus,
+        util as trade_util,
     },
 };
 
 pub use account::Account;
+pub use balance::BalanceView;
+pub use exposure::{ExposureLimits, NetExposure};
 pub use funding::{CrossFunding, FundingSettlement, IsolatedFunding};
+pub use health::{ExchangeHealth, ExchangeStatus};
+pub use limits::ExchangeLimits;
+pub use notification::Notification;
 pub use page::Page;
 pub use ticker::Ticker;
-pub use trade::{CrossExposure, CrossExposureRunning, CrossOrder, CrossPosition, Trade};
+pub use trade::{
+    CrossExposure, CrossExposureRunning, CrossOrder, CrossPosition, FlipSize, Position, Trade,
+};
 pub use transfer::CrossTransfer;
+pub use withdrawal::{InternalWithdrawal, LightningWithdrawal, OnchainWithdrawal};
@@ -0,0 +1,198 @@
+//! Account notifications, as returned by the REST API.
+//!
+//! LN Markets' v3 stream API doesn't provide a notifications topic, so notifications are not
+//! available as WebSocket events and must be polled via the REST API.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+/// A notification delivered to the account, such as a margin call, a liquidation warning or a
+/// deposit confirmation.
+///
+/// The shape of the notification payload depends on the notification event, so it is exposed as
+/// raw JSON via [`Notification::data`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::models::Notification;
+///
+/// let notifications: Vec<Notification> = rest
+///     .account
+///     .get_notifications(None)
+///     .await?;
+///
+/// for notification in &notifications {
+///     println!("Notification ID: {}", notification.id());
+///     println!("Event: {}", notification.event());
+///     println!("Read: {}", notification.read());
+///     println!("Created at: {}", notification.created_at());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    id: Uuid,
+    event: String,
+    #[serde(default)]
+    data: Value,
+    #[serde(default)]
+    read: bool,
+    #[serde(alias = "created_at")]
+    created_at: DateTime<Utc>,
+}
+
+impl Notification {
+    /// Unique identifier for the notification.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(notification: lnm_sdk::rest::v3::models::Notification) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Notification ID: {}", notification.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Name of the event that triggered the notification.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(notification: lnm_sdk::rest::v3::models::Notification) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Event: {}", notification.event());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    /// Event specific payload of the notification.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(notification: lnm_sdk::rest::v3::models::Notification) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Data: {}", notification.data());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn data(&self) -> &Value {
+        &self.data
+    }
+
+    /// Whether the notification was already marked as read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(notification: lnm_sdk::rest::v3::models::Notification) -> Result<(), Box<dyn std::error::Error>> {
+    /// if !notification.read() {
+    ///     println!("Unread notification: {}", notification.event());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read(&self) -> bool {
+        self.read
+    }
+
+    /// Timestamp when the notification was created.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(notification: lnm_sdk::rest::v3::models::Notification) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Created at: {}", notification.created_at());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn as_data_str(&self) -> String {
+        format!(
+            "id: {}\nevent: {}\ndata: {}\nread: {}\ncreated_at: {}",
+            self.id,
+            self.event,
+            self.data,
+            self.read,
+            self.created_at.to_rfc3339()
+        )
+    }
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Notification:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_notification_deserializes_raw_data() {
+        let json = r#"{
+            "id": "4f0c7c9a-3b2e-4d3a-9f51-2a1d5b0e8c77",
+            "event": "margin_call",
+            "data": {
+                "tradeId": "be4f36fe-55ea-4f77-838d-d1df26f216e1",
+                "marginLevel": 0.25,
+                "price": 77055
+            },
+            "read": false,
+            "createdAt": "2026-04-22T11:07:19.867Z"
+        }"#;
+
+        let notification: Notification = serde_json::from_str(json).expect("must deserialize");
+
+        assert_eq!(notification.event(), "margin_call");
+        assert_eq!(
+            notification.data(),
+            &json!({
+                "tradeId": "be4f36fe-55ea-4f77-838d-d1df26f216e1",
+                "marginLevel": 0.25,
+                "price": 77055
+            })
+        );
+        assert!(!notification.read());
+        assert_eq!(
+            notification.created_at().to_rfc3339(),
+            "2026-04-22T11:07:19.867+00:00"
+        );
+    }
+
+    #[test]
+    fn test_notification_defaults_missing_data_and_read() {
+        let json = r#"{
+            "id": "4f0c7c9a-3b2e-4d3a-9f51-2a1d5b0e8c77",
+            "event": "deposit_confirmed",
+            "created_at": "2026-04-22T11:07:19.867Z"
+        }"#;
+
+        let notification: Notification = serde_json::from_str(json).expect("must deserialize");
+
+        assert_eq!(notification.data(), &Value::Null);
+        assert!(!notification.read());
+    }
+}
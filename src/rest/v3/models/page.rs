@@ -3,15 +3,22 @@ use serde::Deserialize;
 
 /// Generic paginated response structure.
 ///
-/// Contains a vector of items and an optional cursor for fetching the next page.
+/// Contains a vector of items and an optional cursor for fetching the next page. The API doesn't
+/// report the total number of items, so whether more pages are available is only known through
+/// [`has_more`](Self::has_more).
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Page<I> {
     data: Vec<I>,
+    #[serde(alias = "next_cursor")]
     next_cursor: Option<DateTime<Utc>>,
 }
 
 impl<I> Page<I> {
+    pub(in crate::rest::v3) fn new(data: Vec<I>, next_cursor: Option<DateTime<Utc>>) -> Self {
+        Self { data, next_cursor }
+    }
+
     /// Vector of items in this page.
     ///
     /// # Examples
@@ -48,6 +55,56 @@ impl<I> Page<I> {
     pub fn next_cursor(&self) -> Option<DateTime<Utc>> {
         self.next_cursor
     }
+
+    /// Returns `true` if more items can be fetched with [`next_cursor`](Self::next_cursor).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use lnm_sdk::rest::v3::models::{Page, Trade};
+    /// # fn example(page: Page<Trade>) -> Result<(), Box<dyn std::error::Error>> {
+    /// if page.has_more() {
+    ///     println!("Showing {} items, more available", page.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn has_more(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+
+    /// Number of items in this page.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if this page has no items.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns an iterator over the items in this page.
+    pub fn iter(&self) -> std::slice::Iter<'_, I> {
+        self.data.iter()
+    }
+}
+
+impl<I> IntoIterator for Page<I> {
+    type Item = I;
+    type IntoIter = std::vec::IntoIter<I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, I> IntoIterator for &'a Page<I> {
+    type Item = &'a I;
+    type IntoIter = std::slice::Iter<'a, I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
 }
 
 impl<I> From<Page<I>> for Vec<I> {
@@ -55,3 +112,26 @@ impl<I> From<Page<I>> for Vec<I> {
         value.data
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_metadata() {
+        let page: Page<u64> =
+            serde_json::from_str(r#"{ "data": [1, 2], "nextCursor": "2025-01-01T00:00:00Z" }"#)
+                .unwrap();
+
+        assert!(page.has_more());
+        assert_eq!(page.len(), 2);
+        assert_eq!((&page).into_iter().sum::<u64>(), 3);
+
+        let page: Page<u64> =
+            serde_json::from_str(r#"{ "data": [], "nextCursor": null }"#).unwrap();
+
+        assert!(!page.has_more());
+        assert!(page.is_empty());
+        assert_eq!(page.into_iter().count(), 0);
+    }
+}
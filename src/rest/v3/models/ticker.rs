@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 pub(in crate::rest::v3) use crate::shared::models::ticker::TickerPrice;
-use crate::shared::models::{price::Price, serde_util};
+use crate::shared::models::{condition::PriceReference, price::Price, serde_util};
 
 /// Real-time ticker data for Bitcoin futures from LN Markets.
 ///
@@ -34,24 +34,40 @@ use crate::shared::models::{price::Price, serde_util};
 #[serde(rename_all = "camelCase")]
 pub struct Ticker {
     index: Price,
+    #[serde(alias = "last_price")]
     last_price: Price,
     prices: Vec<TickerPrice>,
+    #[serde(alias = "funding_rate")]
     funding_rate: f64,
-    #[serde(deserialize_with = "serde_util::datetime_rfc3339_or_millis::deserialize")]
+    #[serde(
+        deserialize_with = "serde_util::datetime_rfc3339_or_millis::deserialize",
+        alias = "funding_time"
+    )]
     funding_time: DateTime<Utc>,
 }
 
 impl Ticker {
-    /// Get the index price.
+    /// Get the index price, computed by the oracle from spot exchanges.
+    ///
+    /// This is the price LNM uses to compute PnL, and to trigger liquidations, stoplosses and
+    /// takeprofits.
     pub fn index(&self) -> Price {
         self.index
     }
 
-    /// Get the last price.
+    /// Get the last traded price of the futures contract.
     pub fn last_price(&self) -> Price {
         self.last_price
     }
 
+    /// Get the price of the given reference series.
+    pub fn price(&self, reference: PriceReference) -> Price {
+        match reference {
+            PriceReference::Last => self.last_price,
+            PriceReference::Index => self.index,
+        }
+    }
+
     /// Get the ticker prices.
     pub fn prices(&self) -> &[TickerPrice] {
         &self.prices
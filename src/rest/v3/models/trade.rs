@@ -6,6 +6,7 @@ use uuid::Uuid;
 
 use crate::shared::models::{
     SATS_PER_BTC,
+    amount::Sats,
     client_id::ClientId,
     cross_leverage::CrossLeverage,
     error::MarginValidationError,
@@ -14,13 +15,17 @@ use crate::shared::models::{
     price::Price,
     quantity::cross::CrossQuantity,
     quantity::order::OrderQuantity,
-    serde_util,
+    serde_formats, serde_util,
     trade::{
-        TradeExecution, TradeExecutionType, TradeSide, TradeSize, util::est_liquidation_from_margin,
+        MarginMode, TradeExecution, TradeExecutionType, TradeSide, TradeSize,
+        util::{est_liquidation_from_leverage, est_liquidation_from_margin},
     },
 };
 
-use super::error::{CrossExposureValidationError, FuturesIsolatedTradeRequestValidationError};
+use super::error::{
+    CrossExposureValidationError, CrossPositionCloseValidationError,
+    FuturesIsolatedTradeRequestValidationError,
+};
 
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -52,10 +57,8 @@ impl FuturesIsolatedTradeRequestBody {
         trade_execution: TradeExecution,
     ) -> Result<Self, FuturesIsolatedTradeRequestValidationError> {
         if let TradeExecution::Limit(price) = trade_execution {
-            if let TradeSize::Margin(margin) = &size {
-                // Implied `OrderQuantity` must be valid
-                let _ = OrderQuantity::try_calculate(*margin, price, leverage)?;
-            }
+            // Implied `OrderQuantity` must be valid
+            let (quantity, _) = size.to_quantity_and_margin(price, leverage)?;
 
             if let Some(stoploss) = stoploss
                 && stoploss >= price
@@ -68,6 +71,24 @@ impl FuturesIsolatedTradeRequestBody {
             {
                 return Err(FuturesIsolatedTradeRequestValidationError::TakeProfitLowerThanPrice);
             }
+
+            if let Some(stoploss) = stoploss {
+                // The exchange rejects stoplosses that would only trigger after liquidation
+                let liquidation = est_liquidation_from_leverage(side, quantity, price, leverage);
+                let beyond_liquidation = match side {
+                    TradeSide::Buy => stoploss < liquidation,
+                    TradeSide::Sell => stoploss > liquidation,
+                };
+
+                if beyond_liquidation {
+                    return Err(
+                        FuturesIsolatedTradeRequestValidationError::StopLossBeyondLiquidation {
+                            stoploss,
+                            liquidation,
+                        },
+                    );
+                }
+            }
         }
 
         let (trade_type, price) = match trade_execution {
@@ -130,8 +151,20 @@ pub struct Trade {
     #[serde(rename = "type")]
     trade_type: TradeExecutionType,
     side: TradeSide,
+    #[serde(
+        alias = "opening_fee",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     opening_fee: u64,
+    #[serde(
+        alias = "closing_fee",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     closing_fee: u64,
+    #[serde(
+        alias = "maintenance_margin",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     maintenance_margin: i64,
     quantity: OrderQuantity,
     margin: Margin,
@@ -142,21 +175,30 @@ pub struct Trade {
     stoploss: Option<Price>,
     #[serde(with = "serde_util::price_option")]
     takeprofit: Option<Price>,
-    #[serde(with = "serde_util::price_option")]
+    #[serde(with = "serde_util::price_option", alias = "exit_price")]
     exit_price: Option<Price>,
+    #[serde(deserialize_with = "serde_formats::amount_string::deserialize")]
     pl: i64,
+    #[serde(alias = "created_at")]
     created_at: DateTime<Utc>,
+    #[serde(alias = "filled_at")]
     filled_at: Option<DateTime<Utc>>,
+    #[serde(alias = "closed_at")]
     closed_at: Option<DateTime<Utc>>,
-    #[serde(with = "serde_util::price_option")]
+    #[serde(with = "serde_util::price_option", alias = "entry_price")]
     entry_price: Option<Price>,
+    #[serde(alias = "entry_margin")]
     entry_margin: Option<Margin>,
     open: bool,
     running: bool,
     canceled: bool,
     closed: bool,
+    #[serde(
+        alias = "sum_funding_fees",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     sum_funding_fees: i64,
-    #[serde(with = "serde_util::client_id_option")]
+    #[serde(with = "serde_util::client_id_option", alias = "client_id")]
     client_id: Option<ClientId>,
 }
 
@@ -177,6 +219,11 @@ impl Trade {
         self.id
     }
 
+    /// Returns the margin mode of the trade, always [`MarginMode::Isolated`].
+    pub fn margin_mode(&self) -> MarginMode {
+        MarginMode::Isolated
+    }
+
     /// Returns the execution type (Market, Limit, or Liquidation).
     ///
     /// # Examples
@@ -222,8 +269,8 @@ impl Trade {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn opening_fee(&self) -> u64 {
-        self.opening_fee
+    pub fn opening_fee(&self) -> Sats {
+        Sats::from(self.opening_fee)
     }
 
     /// Returns the closing fee that was charged when the trade was closed (in satoshis), or zero
@@ -239,8 +286,8 @@ impl Trade {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn closing_fee(&self) -> u64 {
-        self.closing_fee
+    pub fn closing_fee(&self) -> Sats {
+        Sats::from(self.closing_fee)
     }
 
     /// Returns the maintenance margin requirement (in satoshis).
@@ -255,8 +302,8 @@ impl Trade {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn maintenance_margin(&self) -> i64 {
-        self.maintenance_margin
+    pub fn maintenance_margin(&self) -> Sats {
+        Sats::from(self.maintenance_margin)
     }
 
     /// Returns the quantity (notional value in USD) of the trade.
@@ -398,7 +445,7 @@ impl Trade {
     /// # fn example(trade: lnm_sdk::rest::v3::models::Trade) -> Result<(), Box<dyn std::error::Error>> {
     /// let pl = trade.pl();
     ///
-    /// if pl > 0 {
+    /// if pl.is_positive() {
     ///     println!("Profit: {} sats", pl);
     /// } else {
     ///     println!("Loss: {} sats", pl.abs());
@@ -406,8 +453,8 @@ impl Trade {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn pl(&self) -> i64 {
-        self.pl
+    pub fn pl(&self) -> Sats {
+        Sats::from(self.pl)
     }
 
     /// Returns the timestamp when the trade was created.
@@ -568,8 +615,8 @@ impl Trade {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn sum_funding_fees(&self) -> i64 {
-        self.sum_funding_fees
+    pub fn sum_funding_fees(&self) -> Sats {
+        Sats::from(self.sum_funding_fees)
     }
 
     /// Returns the client-provided identifier for this trade.
@@ -711,14 +758,21 @@ pub struct CrossOrder {
     side: TradeSide,
     quantity: OrderQuantity,
     price: Price,
+    #[serde(
+        alias = "trading_fee",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     trading_fee: u64,
+    #[serde(alias = "created_at")]
     created_at: DateTime<Utc>,
+    #[serde(alias = "filled_at")]
     filled_at: Option<DateTime<Utc>>,
+    #[serde(alias = "canceled_at")]
     canceled_at: Option<DateTime<Utc>>,
     open: bool,
     filled: bool,
     canceled: bool,
-    #[serde(with = "serde_util::client_id_option")]
+    #[serde(with = "serde_util::client_id_option", alias = "client_id")]
     client_id: Option<ClientId>,
 }
 
@@ -815,8 +869,8 @@ impl CrossOrder {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn trading_fee(&self) -> u64 {
-        self.trading_fee
+    pub fn trading_fee(&self) -> Sats {
+        Sats::from(self.trading_fee)
     }
 
     /// Returns the timestamp when the order was created.
@@ -1156,17 +1210,47 @@ impl CrossExposure {
 #[serde(rename_all = "camelCase")]
 pub struct CrossPosition {
     id: Uuid,
+    #[serde(deserialize_with = "serde_formats::amount_string::deserialize")]
     margin: u64,
     quantity: i64,
     leverage: CrossLeverage,
+    #[serde(alias = "entry_price")]
     entry_price: Option<Price>,
+    #[serde(
+        alias = "running_margin",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     running_margin: u64,
+    #[serde(
+        alias = "initial_margin",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     initial_margin: u64,
+    #[serde(
+        alias = "maintenance_margin",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     maintenance_margin: u64,
     liquidation: Option<Price>,
+    #[serde(
+        alias = "trading_fees",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     trading_fees: u64,
+    #[serde(
+        alias = "funding_fees",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     funding_fees: i64,
+    #[serde(
+        alias = "total_pl",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     total_pl: i64,
+    #[serde(
+        alias = "delta_pl",
+        deserialize_with = "serde_formats::amount_string::deserialize"
+    )]
     delta_pl: i64,
 }
 
@@ -1199,8 +1283,8 @@ impl CrossPosition {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn margin(&self) -> u64 {
-        self.margin
+    pub fn margin(&self) -> Sats {
+        Sats::from(self.margin)
     }
 
     /// Returns the signed quantity (notional value in USD) of the position.
@@ -1222,6 +1306,61 @@ impl CrossPosition {
         self.quantity
     }
 
+    /// Returns the margin mode of the position, always [`MarginMode::Cross`].
+    pub fn margin_mode(&self) -> MarginMode {
+        MarginMode::Cross
+    }
+
+    /// Validates closing `quantity` of the position, returning the side of the order that reduces
+    /// it.
+    pub(in crate::rest::v3) fn reduce_side(
+        &self,
+        quantity: OrderQuantity,
+    ) -> Result<TradeSide, CrossPositionCloseValidationError> {
+        let position = self.quantity.unsigned_abs();
+        if position == 0 {
+            return Err(CrossPositionCloseValidationError::NoOpenPosition);
+        }
+        if quantity.as_u64() > position {
+            return Err(CrossPositionCloseValidationError::QuantityAbovePosition {
+                quantity,
+                position,
+            });
+        }
+
+        let side = if self.quantity > 0 {
+            TradeSide::Sell
+        } else {
+            TradeSide::Buy
+        };
+
+        Ok(side)
+    }
+
+    /// Validates flipping the position, returning the side and quantity of the order that opens
+    /// the opposite position once this one is closed.
+    pub(in crate::rest::v3) fn flip_order(
+        &self,
+        size: FlipSize,
+    ) -> Result<(TradeSide, OrderQuantity), CrossPositionCloseValidationError> {
+        let position = self.quantity.unsigned_abs();
+        if position == 0 {
+            return Err(CrossPositionCloseValidationError::NoOpenPosition);
+        }
+
+        let quantity = match size {
+            FlipSize::Same => OrderQuantity::try_from(position)?,
+            FlipSize::Quantity(quantity) => quantity,
+        };
+        let side = if self.quantity > 0 {
+            TradeSide::Sell
+        } else {
+            TradeSide::Buy
+        };
+
+        Ok((side, quantity))
+    }
+
     /// Returns the cross-margin market exposure derived from this position.
     ///
     /// This converts the signed position quantity into a neutral or running exposure. A positive
@@ -1315,8 +1454,8 @@ impl CrossPosition {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn running_margin(&self) -> u64 {
-        self.running_margin
+    pub fn running_margin(&self) -> Sats {
+        Sats::from(self.running_margin)
     }
 
     /// Returns the initial margin of the position (in satoshis).
@@ -1331,8 +1470,8 @@ impl CrossPosition {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn initial_margin(&self) -> u64 {
-        self.initial_margin
+    pub fn initial_margin(&self) -> Sats {
+        Sats::from(self.initial_margin)
     }
 
     /// Returns the maintenance margin requirement (in satoshis).
@@ -1347,8 +1486,8 @@ impl CrossPosition {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn maintenance_margin(&self) -> u64 {
-        self.maintenance_margin
+    pub fn maintenance_margin(&self) -> Sats {
+        Sats::from(self.maintenance_margin)
     }
 
     /// Returns the liquidation price at which the position will be automatically closed, if any.
@@ -1380,8 +1519,8 @@ impl CrossPosition {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn trading_fees(&self) -> u64 {
-        self.trading_fees
+    pub fn trading_fees(&self) -> Sats {
+        Sats::from(self.trading_fees)
     }
 
     /// Returns the net funding fees for this position in satoshis.
@@ -1400,8 +1539,8 @@ impl CrossPosition {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn funding_fees(&self) -> i64 {
-        self.funding_fees
+    pub fn funding_fees(&self) -> Sats {
+        Sats::from(self.funding_fees)
     }
 
     /// Returns the total profit/loss in satoshis.
@@ -1412,7 +1551,7 @@ impl CrossPosition {
     /// # fn example(position: lnm_sdk::rest::v3::models::CrossPosition) -> Result<(), Box<dyn std::error::Error>> {
     /// let total_pl = position.total_pl();
     ///
-    /// if total_pl > 0 {
+    /// if total_pl.is_positive() {
     ///     println!("Total profit: {} sats", total_pl);
     /// } else {
     ///     println!("Total loss: {} sats", total_pl.abs());
@@ -1420,8 +1559,8 @@ impl CrossPosition {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn total_pl(&self) -> i64 {
-        self.total_pl
+    pub fn total_pl(&self) -> Sats {
+        Sats::from(self.total_pl)
     }
 
     /// Returns the delta profit/loss in satoshis since last update.
@@ -1432,7 +1571,7 @@ impl CrossPosition {
     /// # fn example(position: lnm_sdk::rest::v3::models::CrossPosition) -> Result<(), Box<dyn std::error::Error>> {
     /// let delta_pl = position.delta_pl();
     ///
-    /// if delta_pl > 0 {
+    /// if delta_pl.is_positive() {
     ///     println!("P/L change: +{} sats", delta_pl);
     /// } else {
     ///     println!("P/L change: {} sats", delta_pl);
@@ -1440,8 +1579,8 @@ impl CrossPosition {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn delta_pl(&self) -> i64 {
-        self.delta_pl
+    pub fn delta_pl(&self) -> Sats {
+        Sats::from(self.delta_pl)
     }
 
     pub fn as_data_str(&self) -> String {
@@ -1481,6 +1620,126 @@ impl fmt::Display for CrossPosition {
     }
 }
 
+/// A position of either margin mode, as returned by
+/// [`RestClient::positions`](crate::rest::v3::RestClient::positions).
+///
+/// Allows handling isolated trades and the cross position together, matching on the margin mode.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::models::Position;
+///
+/// for position in rest.positions().await? {
+///     match &position {
+///         Position::Isolated(trade) => println!("Isolated trade, margin: {}", trade.margin()),
+///         Position::Cross(cross) => println!("Cross position, margin: {}", cross.margin()),
+///     }
+///     println!("{} {:?} {} USD", position.margin_mode(), position.side(), position.quantity());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub enum Position {
+    Isolated(Trade),
+    Cross(CrossPosition),
+}
+
+impl Position {
+    /// Returns the margin mode of the position.
+    pub fn margin_mode(&self) -> MarginMode {
+        match self {
+            Position::Isolated(trade) => trade.margin_mode(),
+            Position::Cross(position) => position.margin_mode(),
+        }
+    }
+
+    /// Returns the ID of the isolated trade or cross position.
+    pub fn id(&self) -> Uuid {
+        match self {
+            Position::Isolated(trade) => trade.id(),
+            Position::Cross(position) => position.id(),
+        }
+    }
+
+    /// Returns the side of the position, or `None` if it is a flat cross position.
+    pub fn side(&self) -> Option<TradeSide> {
+        match self {
+            Position::Isolated(trade) => Some(trade.side()),
+            Position::Cross(position) => match position.quantity() {
+                quantity if quantity > 0 => Some(TradeSide::Buy),
+                quantity if quantity < 0 => Some(TradeSide::Sell),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the absolute size of the position, in USD.
+    pub fn quantity(&self) -> u64 {
+        match self {
+            Position::Isolated(trade) => trade.quantity().as_u64(),
+            Position::Cross(position) => position.quantity().unsigned_abs(),
+        }
+    }
+
+    /// Returns the margin of the position, in satoshis.
+    pub fn margin(&self) -> Sats {
+        match self {
+            Position::Isolated(trade) => Sats::from(trade.margin().as_u64()),
+            Position::Cross(position) => position.margin(),
+        }
+    }
+
+    /// Returns the isolated trade, if the position is isolated.
+    pub fn as_isolated(&self) -> Option<&Trade> {
+        match self {
+            Position::Isolated(trade) => Some(trade),
+            Position::Cross(_) => None,
+        }
+    }
+
+    /// Returns the cross position, if the position is cross.
+    pub fn as_cross(&self) -> Option<&CrossPosition> {
+        match self {
+            Position::Isolated(_) => None,
+            Position::Cross(position) => Some(position),
+        }
+    }
+}
+
+impl From<Trade> for Position {
+    fn from(trade: Trade) -> Self {
+        Position::Isolated(trade)
+    }
+}
+
+impl From<CrossPosition> for Position {
+    fn from(position: CrossPosition) -> Self {
+        Position::Cross(position)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Position::Isolated(trade) => trade.fmt(f),
+            Position::Cross(position) => position.fmt(f),
+        }
+    }
+}
+
+/// Size of the opposite position opened by
+/// [`RestClient::flip_position`](crate::rest::v3::RestClient::flip_position).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlipSize {
+    /// Same quantity as the closed position.
+    Same,
+    /// Explicit quantity.
+    Quantity(OrderQuantity),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1653,4 +1912,148 @@ mod tests {
         let order: CrossOrder = serde_json::from_str(json).expect("must deserialize");
         assert_eq!(order.trade_type(), TradeExecutionType::Liquidation);
     }
+
+    #[test]
+    fn test_cross_order_deserializes_snake_case_fields() {
+        let json = r#"{
+            "id": "be4f36fe-55ea-4f77-838d-d1df26f216e1",
+            "type": "limit",
+            "side": "sell",
+            "quantity": 10,
+            "price": 77055,
+            "trading_fee": 12,
+            "created_at": "2026-04-22T11:07:19.867Z",
+            "filled_at": null,
+            "canceled_at": null,
+            "open": true,
+            "filled": false,
+            "canceled": false,
+            "client_id": "my-order"
+        }"#;
+
+        let order: CrossOrder = serde_json::from_str(json).expect("must deserialize");
+        assert_eq!(order.trading_fee(), Sats::new(12));
+        assert_eq!(order.filled_at(), None);
+        assert_eq!(order.client_id().unwrap().as_str(), "my-order");
+    }
+
+    #[test]
+    fn test_cross_order_deserializes_string_amounts() {
+        let json = r#"{
+            "id": "be4f36fe-55ea-4f77-838d-d1df26f216e1",
+            "type": "limit",
+            "side": "sell",
+            "quantity": 10,
+            "price": 77055,
+            "tradingFee": "12",
+            "createdAt": "2026-04-22T11:07:19.867Z",
+            "filledAt": null,
+            "canceledAt": null,
+            "open": true,
+            "filled": false,
+            "canceled": false,
+            "clientId": null
+        }"#;
+
+        let order: CrossOrder = serde_json::from_str(json).expect("must deserialize");
+        assert_eq!(order.trading_fee(), Sats::new(12));
+    }
+
+    #[test]
+    fn test_trade_request_rejects_stoploss_beyond_liquidation() {
+        let price = Price::try_from(100_000).unwrap();
+        let request = |stoploss: i32| {
+            FuturesIsolatedTradeRequestBody::new(
+                Leverage::try_from(10.0).unwrap(),
+                Some(Price::try_from(stoploss).unwrap()),
+                None,
+                TradeSide::Buy,
+                None,
+                TradeSize::from(OrderQuantity::try_from(1_000).unwrap()),
+                TradeExecution::Limit(price),
+            )
+        };
+
+        assert!(request(95_000).is_ok());
+        assert!(matches!(
+            request(90_000),
+            Err(FuturesIsolatedTradeRequestValidationError::StopLossBeyondLiquidation {
+                liquidation,
+                ..
+            }) if liquidation > Price::try_from(90_000).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_cross_position_reduce_side() {
+        let quantity = |value: u64| OrderQuantity::try_from(value).unwrap();
+        let price = Some(Price::try_from(100_000).unwrap());
+
+        assert_eq!(
+            cross_position(500, 0, price)
+                .reduce_side(quantity(200))
+                .unwrap(),
+            TradeSide::Sell
+        );
+        assert_eq!(
+            cross_position(-500, 0, price)
+                .reduce_side(quantity(500))
+                .unwrap(),
+            TradeSide::Buy
+        );
+        assert!(matches!(
+            cross_position(500, 0, price).reduce_side(quantity(501)),
+            Err(CrossPositionCloseValidationError::QuantityAbovePosition { position: 500, .. })
+        ));
+        assert!(matches!(
+            cross_position(0, 0, None).reduce_side(quantity(1)),
+            Err(CrossPositionCloseValidationError::NoOpenPosition)
+        ));
+    }
+
+    #[test]
+    fn test_cross_position_flip_order() {
+        let price = Some(Price::try_from(100_000).unwrap());
+
+        assert_eq!(
+            cross_position(-300, 0, price)
+                .flip_order(FlipSize::Same)
+                .unwrap(),
+            (TradeSide::Buy, OrderQuantity::try_from(300).unwrap())
+        );
+
+        let quantity = OrderQuantity::try_from(1_000).unwrap();
+        assert_eq!(
+            cross_position(300, 0, price)
+                .flip_order(FlipSize::Quantity(quantity))
+                .unwrap(),
+            (TradeSide::Sell, quantity)
+        );
+        assert!(matches!(
+            cross_position(0, 0, None).flip_order(FlipSize::Same),
+            Err(CrossPositionCloseValidationError::NoOpenPosition)
+        ));
+    }
+
+    #[test]
+    fn test_position_margin_mode() {
+        let trade = crate::testing::fixtures::running_trade();
+        let isolated = Position::from(trade.clone());
+        assert_eq!(isolated.margin_mode(), MarginMode::Isolated);
+        assert_eq!(isolated.id(), trade.id());
+        assert_eq!(isolated.side(), Some(trade.side()));
+        assert_eq!(isolated.quantity(), trade.quantity().as_u64());
+        assert!(isolated.as_isolated().is_some());
+        assert!(isolated.as_cross().is_none());
+
+        let cross = Position::from(cross_position(-300, 5_000, None));
+        assert_eq!(cross.margin_mode(), MarginMode::Cross);
+        assert_eq!(cross.side(), Some(TradeSide::Sell));
+        assert_eq!(cross.quantity(), 300);
+        assert_eq!(cross.margin(), Sats::new(5_000));
+        assert!(cross.as_cross().is_some());
+
+        assert_eq!(Position::from(cross_position(0, 0, None)).side(), None);
+        assert_eq!(MarginMode::Cross.to_string(), "cross");
+    }
 }
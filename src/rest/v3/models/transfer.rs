@@ -4,6 +4,8 @@ use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::shared::models::amount::Sats;
+
 /// A transfer between the isolated account and the cross-margin account.
 ///
 /// Represents a transfer of funds that moves collateral between the user's isolated futures
@@ -25,7 +27,7 @@ use uuid::Uuid;
 ///
 /// for transfer in transfers.data() {
 ///     println!("Transfer ID: {}", transfer.id());
-///     if transfer.amount() > 0 {
+///     if transfer.amount().is_positive() {
 ///         println!("Deposit: {} sats", transfer.amount());
 ///     } else {
 ///         println!("Withdrawal: {} sats", transfer.amount().abs());
@@ -68,8 +70,8 @@ impl CrossTransfer {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn amount(&self) -> i64 {
-        self.amount
+    pub fn amount(&self) -> Sats {
+        Sats::from(self.amount)
     }
 
     /// Timestamp when the cross transfer occurred.
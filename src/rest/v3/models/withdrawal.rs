@@ -0,0 +1,533 @@
+use std::{fmt, num::NonZeroU64};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::shared::models::{
+    address::BitcoinAddress, amount::Sats, invoice::Bolt11Invoice, network::BitcoinNetwork,
+};
+
+use super::error::WithdrawalRequestValidationError;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(in crate::rest::v3) struct LightningWithdrawalRequestBody {
+    invoice: Bolt11Invoice,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_fees: Option<NonZeroU64>,
+}
+
+impl LightningWithdrawalRequestBody {
+    /// Validates the invoice against the client `network`, and its expiry at `now`.
+    pub fn new(
+        network: BitcoinNetwork,
+        invoice: Bolt11Invoice,
+        max_fees: Option<NonZeroU64>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, WithdrawalRequestValidationError> {
+        if invoice.network() != network {
+            return Err(WithdrawalRequestValidationError::InvoiceNetworkMismatch {
+                invoice_network: invoice.network(),
+                client_network: network,
+            });
+        }
+
+        if invoice.amount_msat().is_none() {
+            return Err(WithdrawalRequestValidationError::InvoiceMissingAmount);
+        }
+
+        if invoice.is_expired_at(now) {
+            return Err(WithdrawalRequestValidationError::InvoiceExpired {
+                expires_at: invoice.expires_at(),
+            });
+        }
+
+        Ok(Self { invoice, max_fees })
+    }
+
+    pub fn amount_sats(&self) -> Option<u64> {
+        self.invoice.amount_sats()
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub(in crate::rest::v3) struct OnchainWithdrawalRequestBody {
+    address: BitcoinAddress,
+    amount: NonZeroU64,
+}
+
+impl OnchainWithdrawalRequestBody {
+    pub fn new(
+        network: BitcoinNetwork,
+        address: BitcoinAddress,
+        amount: NonZeroU64,
+    ) -> Result<Self, WithdrawalRequestValidationError> {
+        if !address.is_valid_for_network(network) {
+            return Err(WithdrawalRequestValidationError::WrongNetworkAddress {
+                address: address.to_string(),
+                client_network: network,
+            });
+        }
+
+        Ok(Self { address, amount })
+    }
+}
+
+/// A Lightning withdrawal returned from the LN Markets API.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::models::{Bolt11Invoice, LightningWithdrawal};
+///
+/// let invoice = Bolt11Invoice::try_from("lnbc...")?;
+/// let withdrawal: LightningWithdrawal = rest
+///     .withdrawals
+///     .withdrawal_lightning(invoice, None)
+///     .await?;
+///
+/// println!("Withdrawal ID: {}", withdrawal.id());
+/// println!("Amount: {} sats", withdrawal.amount());
+/// println!("Max fees: {} sats", withdrawal.max_fees());
+/// println!("Payment hash: {}", withdrawal.payment_hash());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LightningWithdrawal {
+    id: Uuid,
+    amount: u64,
+    #[serde(alias = "max_fees")]
+    max_fees: u64,
+    #[serde(alias = "payment_hash")]
+    payment_hash: String,
+    #[serde(alias = "created_at")]
+    created_at: DateTime<Utc>,
+}
+
+impl LightningWithdrawal {
+    /// Unique identifier for the withdrawal.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::LightningWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Withdrawal ID: {}", withdrawal.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Withdrawn amount in satoshis.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::LightningWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Amount: {} sats", withdrawal.amount());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn amount(&self) -> Sats {
+        Sats::from(self.amount)
+    }
+
+    /// Amount in satoshis reserved from the balance to pay routing fees.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::LightningWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Max fees: {} sats", withdrawal.max_fees());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_fees(&self) -> Sats {
+        Sats::from(self.max_fees)
+    }
+
+    /// Payment hash of the paid invoice.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::LightningWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Payment hash: {}", withdrawal.payment_hash());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn payment_hash(&self) -> &str {
+        &self.payment_hash
+    }
+
+    /// Timestamp when the withdrawal was created.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::LightningWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Created at: {}", withdrawal.created_at());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn as_data_str(&self) -> String {
+        format!(
+            "id: {}\namount: {}\nmax_fees: {}\npayment_hash: {}\ncreated_at: {}",
+            self.id,
+            self.amount,
+            self.max_fees,
+            self.payment_hash,
+            self.created_at.to_rfc3339()
+        )
+    }
+}
+
+impl fmt::Display for LightningWithdrawal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Lightning Withdrawal:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An on-chain withdrawal request returned from the LN Markets API.
+///
+/// On-chain withdrawal requests are reviewed and processed asynchronously.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use std::num::NonZeroU64;
+///
+/// use lnm_sdk::rest::v3::models::{BitcoinAddress, OnchainWithdrawal};
+///
+/// let address = BitcoinAddress::try_from("bc1q...")?;
+/// let withdrawal: OnchainWithdrawal = rest
+///     .withdrawals
+///     .withdrawal_onchain(address, NonZeroU64::new(100_000).unwrap())
+///     .await?;
+///
+/// println!("Withdrawal ID: {}", withdrawal.id());
+/// println!("Address: {}", withdrawal.address());
+/// println!("Amount: {} sats", withdrawal.amount());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OnchainWithdrawal {
+    id: Uuid,
+    address: String,
+    amount: u64,
+    #[serde(alias = "created_at")]
+    created_at: DateTime<Utc>,
+}
+
+impl OnchainWithdrawal {
+    /// Unique identifier for the withdrawal.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::OnchainWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Withdrawal ID: {}", withdrawal.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Destination address of the withdrawal.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::OnchainWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Address: {}", withdrawal.address());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Withdrawn amount in satoshis.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::OnchainWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Amount: {} sats", withdrawal.amount());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn amount(&self) -> Sats {
+        Sats::from(self.amount)
+    }
+
+    /// Timestamp when the withdrawal was requested.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::OnchainWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Created at: {}", withdrawal.created_at());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn as_data_str(&self) -> String {
+        format!(
+            "id: {}\naddress: {}\namount: {}\ncreated_at: {}",
+            self.id,
+            self.address,
+            self.amount,
+            self.created_at.to_rfc3339()
+        )
+    }
+}
+
+impl fmt::Display for OnchainWithdrawal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "On-chain Withdrawal:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An internal withdrawal (a transfer to another LN Markets user) returned from the LN Markets
+/// API.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::models::{InternalWithdrawal, Page};
+///
+/// let withdrawals: Page<InternalWithdrawal> = rest
+///     .withdrawals
+///     .get_internal_withdrawals(None, None, None, None)
+///     .await?;
+///
+/// for withdrawal in withdrawals.iter() {
+///     println!("Withdrawal ID: {}", withdrawal.id());
+///     println!("Amount: {} sats", withdrawal.amount());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InternalWithdrawal {
+    id: Uuid,
+    amount: u64,
+    #[serde(default, alias = "to_username")]
+    to_username: Option<String>,
+    #[serde(alias = "created_at")]
+    created_at: DateTime<Utc>,
+}
+
+impl InternalWithdrawal {
+    /// Unique identifier for the withdrawal.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::InternalWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Withdrawal ID: {}", withdrawal.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Withdrawn amount in satoshis.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::InternalWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Amount: {} sats", withdrawal.amount());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn amount(&self) -> Sats {
+        Sats::from(self.amount)
+    }
+
+    /// Username of the receiving LN Markets user, if reported.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::InternalWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some(username) = withdrawal.to_username() {
+    ///     println!("To: {username}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_username(&self) -> Option<&str> {
+        self.to_username.as_deref()
+    }
+
+    /// Timestamp when the withdrawal was made.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn example(withdrawal: lnm_sdk::rest::v3::models::InternalWithdrawal) -> Result<(), Box<dyn std::error::Error>> {
+    /// println!("Created at: {}", withdrawal.created_at());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn as_data_str(&self) -> String {
+        format!(
+            "id: {}\namount: {}\nto_username: {}\ncreated_at: {}",
+            self.id,
+            self.amount,
+            self.to_username.as_deref().unwrap_or("-"),
+            self.created_at.to_rfc3339()
+        )
+    }
+}
+
+impl fmt::Display for InternalWithdrawal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Internal Withdrawal:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC_INVOICE: &str = "lnbc2500u1pvjluezsp5zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zygspp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdq5xysxxatsyp3k7enxv4jsxqzpu9qrsgquk0rl77nj30yxdy8j9vdx85fkpmdla2087ne0xh8nhedh8w27kyke0lp53ut353s06fv3qfegext0eh0ymjpf39tuven09sam30g4vgpfna3rh";
+
+    const SPEC_DONATION_INVOICE: &str = "lnbc1pvjluezsp5zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zygspp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdpl2pkx2ctnv5sxxmmwwd5kgetjypeh2ursdae8g6twvus8g6rfwvs8qun0dfjkxaq9qrsgq357wnc5r2ueh7ck6q93dj32dlqnls087fxdwk8qakdyafkq3yap9us6v52vjjsrvywa6rt52cm9r9zqt8r2t7mlcwspyetp5h2tztugp9lfyql";
+
+    #[test]
+    fn test_lightning_withdrawal_rejects_network_mismatch() {
+        let invoice = Bolt11Invoice::try_from(SPEC_INVOICE).unwrap();
+
+        let error = LightningWithdrawalRequestBody::new(
+            BitcoinNetwork::Testnet,
+            invoice.clone(),
+            None,
+            invoice.timestamp(),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            WithdrawalRequestValidationError::InvoiceNetworkMismatch {
+                invoice_network: BitcoinNetwork::Mainnet,
+                client_network: BitcoinNetwork::Testnet,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_lightning_withdrawal_rejects_missing_amount() {
+        let invoice = Bolt11Invoice::try_from(SPEC_DONATION_INVOICE).unwrap();
+
+        let error = LightningWithdrawalRequestBody::new(
+            BitcoinNetwork::Mainnet,
+            invoice.clone(),
+            None,
+            invoice.timestamp(),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            WithdrawalRequestValidationError::InvoiceMissingAmount
+        ));
+    }
+
+    #[test]
+    fn test_lightning_withdrawal_rejects_expired_invoice() {
+        let invoice = Bolt11Invoice::try_from(SPEC_INVOICE).unwrap();
+        let expires_at = invoice.expires_at();
+
+        // Expiry is checked against the given time, not the system clock
+        let before_expiry = expires_at - chrono::TimeDelta::seconds(1);
+        let body = LightningWithdrawalRequestBody::new(
+            BitcoinNetwork::Mainnet,
+            invoice.clone(),
+            None,
+            before_expiry,
+        )
+        .unwrap();
+        assert_eq!(body.amount_sats(), invoice.amount_sats());
+
+        let error =
+            LightningWithdrawalRequestBody::new(BitcoinNetwork::Mainnet, invoice, None, expires_at)
+                .unwrap_err();
+
+        assert!(matches!(
+            error,
+            WithdrawalRequestValidationError::InvoiceExpired { expires_at: e } if e == expires_at
+        ));
+    }
+
+    #[test]
+    fn test_onchain_withdrawal_rejects_wrong_network_address() {
+        let address =
+            BitcoinAddress::try_from("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").unwrap();
+        let amount = NonZeroU64::new(100_000).unwrap();
+
+        let error = OnchainWithdrawalRequestBody::new(BitcoinNetwork::Testnet, address, amount)
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            WithdrawalRequestValidationError::WrongNetworkAddress {
+                client_network: BitcoinNetwork::Testnet,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_onchain_withdrawal_accepts_matching_network_address() {
+        let address =
+            BitcoinAddress::try_from("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").unwrap();
+        let amount = NonZeroU64::new(100_000).unwrap();
+
+        let body =
+            OnchainWithdrawalRequestBody::new(BitcoinNetwork::Mainnet, address, amount).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&body).unwrap(),
+            r#"{"address":"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4","amount":100000}"#
+        );
+    }
+}
@@ -0,0 +1,463 @@
+//! Outbound queue holding order submissions during brief outages.
+//!
+//! [`OrderQueue::submit`] sends orders right away while the exchange is reachable. When it isn't,
+//! because the connection can't be established, the exchange is in maintenance or requests are
+//! being rate limited, orders are held instead of failing, and sent in submission order once
+//! [`OrderQueue::flush`] finds the exchange reachable again. Orders held for longer than the
+//! configured maximum age are dropped and reported to an expiry callback, so stale orders are
+//! never placed at prices that are no longer relevant. Outcomes of queued orders once sent are
+//! reported to an outcome callback.
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use tokio::{task::JoinHandle, time};
+
+use crate::shared::{
+    clock::{Clock, SystemClock},
+    models::client_id::ClientId,
+    rest::error::{RestApiError, Result},
+};
+
+use super::{
+    LnmFuturesApi,
+    journal::{OrderIntent, PlacedOrder},
+};
+
+/// Returns `true` if the error guarantees the order didn't reach the exchange, and was rejected
+/// because the exchange is currently unavailable.
+fn is_unavailable(error: &RestApiError) -> bool {
+    let connect_failed = matches!(error, RestApiError::SendFailed { e, .. } if e.is_connect());
+
+    connect_failed || error.is_maintenance() || error.is_rate_limited()
+}
+
+/// Order held by an [`OrderQueue`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedOrder {
+    client_id: ClientId,
+    intent: OrderIntent,
+    queued_at: DateTime<Utc>,
+}
+
+impl QueuedOrder {
+    /// Client ID attached to the order.
+    pub fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    pub fn intent(&self) -> &OrderIntent {
+        &self.intent
+    }
+
+    /// Timestamp when the order was submitted to the queue.
+    pub fn queued_at(&self) -> DateTime<Utc> {
+        self.queued_at
+    }
+}
+
+/// Outcome of [`OrderQueue::submit`].
+#[derive(Debug, Clone)]
+pub enum Submission {
+    /// The order was placed right away.
+    Placed(Box<PlacedOrder>),
+    /// The exchange is unavailable, or earlier orders are still queued. The order will be sent
+    /// by a later [`flush`](OrderQueue::flush).
+    Queued,
+}
+
+/// Outcome of [`OrderQueue::flush`].
+#[derive(Debug, Default)]
+pub struct FlushReport {
+    sent: Vec<(QueuedOrder, Result<PlacedOrder>)>,
+    expired: Vec<QueuedOrder>,
+    remaining: usize,
+}
+
+impl FlushReport {
+    /// Queued orders sent, with their outcomes, in submission order.
+    pub fn sent(&self) -> &[(QueuedOrder, Result<PlacedOrder>)] {
+        &self.sent
+    }
+
+    /// Queued orders dropped for exceeding the maximum age.
+    pub fn expired(&self) -> &[QueuedOrder] {
+        &self.expired
+    }
+
+    /// Number of orders still queued, because the exchange remained unavailable.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+/// Callback invoked with each order dropped for exceeding the maximum age.
+pub type ExpiredOrderHandler = Arc<dyn Fn(&QueuedOrder) + Send + Sync>;
+
+/// Callback invoked with each queued order sent by a flush, and its outcome.
+pub type SentOrderHandler = Arc<dyn Fn(&QueuedOrder, &Result<PlacedOrder>) + Send + Sync>;
+
+/// Outbound queue holding order submissions while the exchange is unavailable.
+///
+/// Orders are sent in submission order: while any order is queued, new submissions are queued
+/// behind it. Only errors guaranteeing the order wasn't received (connection failures,
+/// maintenance and rate limiting) cause an order to be held. Any other outcome is final, and
+/// returned to the caller.
+///
+/// A [`ClientId`] is required for every order, so that orders can be matched with their
+/// outcomes, and recovered with an [`OrderJournal`](super::journal::OrderJournal) if needed.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use std::{sync::Arc, time::Duration};
+///
+/// use lnm_sdk::rest::v3::{
+///     journal::OrderIntent,
+///     models::{ClientId, OrderQuantity, TradeExecution, TradeSide},
+///     order_queue::{OrderQueue, Submission},
+/// };
+///
+/// let queue = Arc::new(
+///     OrderQueue::new(Arc::new(rest), Duration::from_secs(30))
+///         .with_on_expired(Arc::new(|order| {
+///             println!("Order {} expired before it could be sent", order.client_id())
+///         }))
+///         .with_on_sent(Arc::new(|order, result| match result {
+///             Ok(placed) => println!("Queued order {} placed as {}", order.client_id(), placed.id()),
+///             Err(e) => println!("Queued order {} failed: {e}", order.client_id()),
+///         })),
+/// );
+/// let _flusher = queue.spawn_flusher(Duration::from_secs(1));
+///
+/// let intent = OrderIntent::Cross {
+///     side: TradeSide::Buy,
+///     quantity: OrderQuantity::try_from(100)?,
+///     execution: TradeExecution::Market,
+/// };
+///
+/// match queue.submit(ClientId::try_from("order-1")?, intent).await? {
+///     Submission::Placed(order) => println!("Placed {}", order.id()),
+///     Submission::Queued => println!("Exchange unavailable, order queued"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct OrderQueue {
+    api: Arc<dyn LnmFuturesApi>,
+    max_age: Duration,
+    queue: Mutex<VecDeque<QueuedOrder>>,
+    // Serializes sending, so orders are never sent out of order or twice
+    send_lock: tokio::sync::Mutex<()>,
+    on_expired: Option<ExpiredOrderHandler>,
+    on_sent: Option<SentOrderHandler>,
+    clock: Arc<dyn Clock>,
+}
+
+impl OrderQueue {
+    /// Creates an empty queue sending orders through `api`, and holding them for at most
+    /// `max_age`.
+    pub fn new(api: Arc<dyn LnmFuturesApi>, max_age: Duration) -> Self {
+        Self {
+            api,
+            max_age,
+            queue: Mutex::new(VecDeque::new()),
+            send_lock: tokio::sync::Mutex::new(()),
+            on_expired: None,
+            on_sent: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Sets the callback invoked with each order dropped for exceeding the maximum age.
+    ///
+    /// Default: `None`
+    pub fn with_on_expired(mut self, on_expired: ExpiredOrderHandler) -> Self {
+        self.on_expired = Some(on_expired);
+        self
+    }
+
+    /// Sets the callback invoked with each queued order sent by a flush, and its outcome. Orders
+    /// placed right away by [`submit`](Self::submit) are returned to the caller instead.
+    ///
+    /// Default: `None`
+    pub fn with_on_sent(mut self, on_sent: SentOrderHandler) -> Self {
+        self.on_sent = Some(on_sent);
+        self
+    }
+
+    /// Sets the clock the age of queued orders is measured with.
+    ///
+    /// Default: [`SystemClock`]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    fn lock_queue(&self) -> std::sync::MutexGuard<'_, VecDeque<QueuedOrder>> {
+        self.queue
+            .lock()
+            .expect("`OrderQueue` mutex can't be poisoned")
+    }
+
+    /// Returns the queued orders, oldest first.
+    pub fn queued(&self) -> Vec<QueuedOrder> {
+        self.lock_queue().iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock_queue().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock_queue().is_empty()
+    }
+
+    /// Sends `intent` under `client_id`, or queues it if the exchange is unavailable or earlier
+    /// orders are still queued.
+    ///
+    /// Errors other than unavailability are returned as is, and the order is not queued.
+    pub async fn submit(&self, client_id: ClientId, intent: OrderIntent) -> Result<Submission> {
+        let _send_guard = self.send_lock.lock().await;
+
+        let order = QueuedOrder {
+            client_id,
+            intent,
+            queued_at: self.clock.now(),
+        };
+
+        if !self.is_empty() {
+            self.lock_queue().push_back(order);
+            return Ok(Submission::Queued);
+        }
+
+        match order
+            .intent
+            .clone()
+            .send(self.api.as_ref(), order.client_id.clone())
+            .await
+        {
+            Ok(placed) => Ok(Submission::Placed(Box::new(placed))),
+            Err(e) if is_unavailable(&e) => {
+                self.lock_queue().push_back(order);
+                Ok(Submission::Queued)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Drops queued orders older than the maximum age, then sends the remaining ones in
+    /// submission order, stopping at the first one rejected because the exchange is still
+    /// unavailable.
+    pub async fn flush(&self) -> FlushReport {
+        let _send_guard = self.send_lock.lock().await;
+        let mut report = FlushReport::default();
+
+        loop {
+            let Some(order) = self.lock_queue().pop_front() else {
+                break;
+            };
+
+            let age = (self.clock.now() - order.queued_at)
+                .to_std()
+                .unwrap_or_default();
+            if age > self.max_age {
+                if let Some(on_expired) = &self.on_expired {
+                    on_expired(&order);
+                }
+                report.expired.push(order);
+                continue;
+            }
+
+            let result = order
+                .intent
+                .clone()
+                .send(self.api.as_ref(), order.client_id.clone())
+                .await;
+
+            match result {
+                Err(e) if is_unavailable(&e) => {
+                    self.lock_queue().push_front(order);
+                    break;
+                }
+                result => {
+                    if let Some(on_sent) = &self.on_sent {
+                        on_sent(&order, &result);
+                    }
+                    report.sent.push((order, result));
+                }
+            }
+        }
+
+        report.remaining = self.len();
+        report
+    }
+
+    /// Spawns a task flushing the queue every `interval` while it holds orders.
+    ///
+    /// Outcomes of sent orders are reported to the outcome callback and logged, and expired orders
+    /// are reported to the expiry callback. The task stops when the returned handle is aborted, or when the queue is dropped.
+    pub fn spawn_flusher(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let queue = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            loop {
+                time::sleep(interval).await;
+
+                let Some(queue) = queue.upgrade() else {
+                    return;
+                };
+                if queue.is_empty() {
+                    continue;
+                }
+
+                for (order, result) in queue.flush().await.sent() {
+                    match result {
+                        Ok(placed) => log::info!(
+                            "Queued order {} placed with ID {}",
+                            order.client_id,
+                            placed.id()
+                        ),
+                        Err(e) => log::warn!("Queued order {} failed: {e}", order.client_id),
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl fmt::Debug for OrderQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderQueue")
+            .field("max_age", &self.max_age)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use chrono::TimeDelta;
+    use http::{Method, StatusCode};
+
+    use super::*;
+    use crate::shared::rest::error::ErrorResponseContext;
+    use crate::{
+        rest::v3::models::{OrderQuantity, TradeExecution, TradeSide},
+        testing::{clock::MockClock, faults::ApiCall, fixtures, stub::StubApi},
+    };
+
+    /// Fails placing orders with `503 Service Unavailable` while `unavailable` is set.
+    fn outage_api(unavailable: Arc<AtomicBool>) -> StubApi {
+        StubApi::new().with_place_cross_order(move |_, _, _, _| {
+            if unavailable.load(Ordering::SeqCst) {
+                return Err(RestApiError::ErrorResponse(Box::new(
+                    ErrorResponseContext {
+                        method: Method::POST,
+                        path: "/v3/futures/cross/order".to_string(),
+                        status: StatusCode::SERVICE_UNAVAILABLE,
+                        request_id: None,
+                        correlation_id: None,
+                        retry_after: None,
+                        text: "maintenance".to_string(),
+                    },
+                )));
+            }
+
+            Ok(fixtures::filled_cross_order())
+        })
+    }
+
+    fn intent() -> OrderIntent {
+        OrderIntent::Cross {
+            side: TradeSide::Buy,
+            quantity: OrderQuantity::try_from(100).unwrap(),
+            execution: TradeExecution::Market,
+        }
+    }
+
+    fn client_id(value: &str) -> ClientId {
+        ClientId::try_from(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_queue_holds_orders_during_outage() {
+        let unavailable = Arc::new(AtomicBool::new(false));
+        let api = Arc::new(outage_api(unavailable.clone()));
+        let clock = MockClock::default();
+        let expired = Arc::new(Mutex::new(Vec::new()));
+        let expired_handler = expired.clone();
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let sent_handler = sent.clone();
+
+        let queue = OrderQueue::new(api.clone(), Duration::from_secs(30))
+            .with_clock(Arc::new(clock.clone()))
+            .with_on_expired(Arc::new(move |order: &QueuedOrder| {
+                expired_handler
+                    .lock()
+                    .unwrap()
+                    .push(order.client_id().clone());
+            }))
+            .with_on_sent(Arc::new(move |order: &QueuedOrder, result| {
+                sent_handler
+                    .lock()
+                    .unwrap()
+                    .push((order.client_id().clone(), result.is_ok()));
+            }));
+
+        assert!(matches!(
+            queue.submit(client_id("order-1"), intent()).await.unwrap(),
+            Submission::Placed(_)
+        ));
+
+        unavailable.store(true, Ordering::SeqCst);
+        for id in ["order-2", "order-3"] {
+            assert!(matches!(
+                queue.submit(client_id(id), intent()).await.unwrap(),
+                Submission::Queued
+            ));
+        }
+
+        // Still unavailable, nothing is sent
+        let report = queue.flush().await;
+        assert!(report.sent().is_empty());
+        assert_eq!(report.remaining(), 2);
+
+        // Once available again, new submissions queue behind held orders
+        clock.advance(TimeDelta::seconds(20));
+        unavailable.store(false, Ordering::SeqCst);
+        assert!(matches!(
+            queue.submit(client_id("order-4"), intent()).await.unwrap(),
+            Submission::Queued
+        ));
+
+        // Orders older than the maximum age expire
+        clock.advance(TimeDelta::seconds(15));
+        let report = queue.flush().await;
+
+        assert_eq!(
+            *expired.lock().unwrap(),
+            vec![client_id("order-2"), client_id("order-3")]
+        );
+        assert_eq!(report.expired().len(), 2);
+        assert_eq!(report.sent().len(), 1);
+        assert_eq!(report.sent()[0].0.client_id(), &client_id("order-4"));
+        assert!(report.sent()[0].1.is_ok());
+        assert_eq!(report.remaining(), 0);
+        assert_eq!(*sent.lock().unwrap(), vec![(client_id("order-4"), true)]);
+        // Two orders placed, plus one failed attempt on submission and one on flush
+        assert_eq!(api.call_count(ApiCall::PlaceCrossOrder), 4);
+    }
+}
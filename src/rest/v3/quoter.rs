@@ -0,0 +1,354 @@
+//! Symmetric bid/ask quoting around the mid price.
+//!
+//! A [`Quoter`] keeps one bid and one ask cross limit order resting at a fixed offset below and
+//! above the mid price. Quotes are replaced once the mid price moves by at least the requote
+//! threshold, or once either side fills, so small price moves don't churn orders.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::shared::{
+    models::{
+        error::PriceValidationError,
+        price::Price,
+        quantity::order::OrderQuantity,
+        trade::{TradeExecution, TradeSide},
+    },
+    rest::error::RestApiError,
+};
+
+use super::{LnmFuturesApi, models::CrossOrder};
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum QuoterValidationError {
+    #[error("Quote offset must be a positive number of USD, got {0}")]
+    InvalidOffset(f64),
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum QuoterError {
+    #[error("Quote price error: {0}")]
+    QuotePrice(#[from] PriceValidationError),
+
+    #[error(transparent)]
+    RestApi(#[from] RestApiError),
+}
+
+pub type Result<T> = std::result::Result<T, QuoterError>;
+
+#[derive(Debug, Default)]
+struct QuoterState {
+    mid: Option<Price>,
+    bid: Option<CrossOrder>,
+    ask: Option<CrossOrder>,
+}
+
+/// Market-making helper keeping symmetric bid/ask cross limit orders around the mid price.
+///
+/// [`update`](Self::update) should be called with every new mid price, e.g. from
+/// [`TickerPrice::mid_price`](crate::rest::v3::models::TickerPrice::mid_price). Fills must be
+/// reported with [`on_filled`](Self::on_filled), so the next update requotes both sides.
+///
+/// Cancels and replacements are sent through the provided API one at a time. When it is a
+/// [`RestClient`](super::RestClient) configured with
+/// [`with_rate_limiter`](super::RestClientConfig::with_rate_limiter), they are paced by its rate
+/// limiter.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: std::sync::Arc<lnm_sdk::rest::v3::RestClient>) -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
+///
+/// use lnm_sdk::rest::v3::{models::OrderQuantity, quoter::Quoter};
+///
+/// let quoter = Quoter::new(rest.clone(), OrderQuantity::try_from(100)?, 25.)?
+///     .with_requote_threshold(10.);
+///
+/// loop {
+///     let ticker = rest.futures_data.get_ticker().await?;
+///     if let Some(prices) = ticker.prices().first() {
+///         quoter.update(prices.mid_price()).await?;
+///     }
+///
+///     tokio::time::sleep(Duration::from_secs(1)).await;
+/// }
+/// # }
+/// ```
+pub struct Quoter {
+    api: Arc<dyn LnmFuturesApi>,
+    quantity: OrderQuantity,
+    offset: f64,
+    requote_threshold: f64,
+    state: Mutex<QuoterState>,
+    // Serializes cancels and placements, so at most one quote per side is ever resting
+    send_lock: tokio::sync::Mutex<()>,
+}
+
+impl Quoter {
+    /// Creates a quoter placing orders of `quantity` at `offset` USD below and above the mid
+    /// price. No order is placed until [`update`](Self::update) is called.
+    pub fn new(
+        api: Arc<dyn LnmFuturesApi>,
+        quantity: OrderQuantity,
+        offset: f64,
+    ) -> std::result::Result<Self, QuoterValidationError> {
+        if !offset.is_finite() || offset <= 0. {
+            return Err(QuoterValidationError::InvalidOffset(offset));
+        }
+
+        Ok(Self {
+            api,
+            quantity,
+            offset,
+            requote_threshold: Price::TICK,
+            state: Mutex::new(QuoterState::default()),
+            send_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Sets the minimum move of the mid price, in USD, triggering a requote. Negative values are
+    /// treated as `0`, requoting on every update.
+    ///
+    /// Default: [`Price::TICK`]
+    pub fn with_requote_threshold(mut self, threshold: f64) -> Self {
+        self.requote_threshold = threshold.max(0.);
+        self
+    }
+
+    /// Quantity of each quote.
+    pub fn quantity(&self) -> OrderQuantity {
+        self.quantity
+    }
+
+    /// Distance of each quote from the mid price, in USD.
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    pub fn requote_threshold(&self) -> f64 {
+        self.requote_threshold
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, QuoterState> {
+        self.state.lock().expect("`Quoter` mutex can't be poisoned")
+    }
+
+    /// Mid price the current quotes were placed around, if any.
+    pub fn mid(&self) -> Option<Price> {
+        self.lock_state().mid
+    }
+
+    /// Resting bid, if any.
+    pub fn bid(&self) -> Option<CrossOrder> {
+        self.lock_state().bid.clone()
+    }
+
+    /// Resting ask, if any.
+    pub fn ask(&self) -> Option<CrossOrder> {
+        self.lock_state().ask.clone()
+    }
+
+    /// Requotes around `mid` if the mid price moved by at least the requote threshold since the
+    /// last quotes, or if either side isn't resting. Remaining quotes are canceled before new ones
+    /// are placed.
+    ///
+    /// Returns `true` if the quotes were replaced.
+    pub async fn update(&self, mid: Price) -> Result<bool> {
+        let _send_guard = self.send_lock.lock().await;
+
+        {
+            let state = self.lock_state();
+            let moved = state.mid.is_none_or(|quoted| {
+                (mid.as_f64() - quoted.as_f64()).abs() >= self.requote_threshold
+            });
+
+            if !moved && state.bid.is_some() && state.ask.is_some() {
+                return Ok(false);
+            }
+        }
+
+        let bid_price = Price::round_down(mid.as_f64() - self.offset)?;
+        let ask_price = Price::round_up(mid.as_f64() + self.offset)?;
+
+        self.cancel_quotes().await?;
+        self.lock_state().mid = Some(mid);
+
+        let bid = self.place(TradeSide::Buy, bid_price).await?;
+        self.lock_state().bid = Some(bid);
+
+        let ask = self.place(TradeSide::Sell, ask_price).await?;
+        self.lock_state().ask = Some(ask);
+
+        Ok(true)
+    }
+
+    async fn place(&self, side: TradeSide, price: Price) -> Result<CrossOrder> {
+        let order = self
+            .api
+            .place_cross_order(side, self.quantity, TradeExecution::Limit(price), None)
+            .await?;
+
+        Ok(order)
+    }
+
+    /// Cancels the resting quotes. A quote is kept if canceling it fails, so its fill can still be
+    /// recorded.
+    async fn cancel_quotes(&self) -> Result<()> {
+        let (bid, ask) = {
+            let state = self.lock_state();
+            (state.bid.clone(), state.ask.clone())
+        };
+
+        if let Some(bid) = bid {
+            self.api.cancel_cross_order(bid.id()).await?;
+            self.lock_state().bid = None;
+        }
+
+        if let Some(ask) = ask {
+            self.api.cancel_cross_order(ask.id()).await?;
+            self.lock_state().ask = None;
+        }
+
+        Ok(())
+    }
+
+    /// Records the fill of the order with `id`, returning its side if it was one of the quotes.
+    ///
+    /// The other side is requoted together with the filled one on the next
+    /// [`update`](Self::update).
+    pub fn on_filled(&self, id: Uuid) -> Option<TradeSide> {
+        let mut state = self.lock_state();
+
+        if state.bid.take_if(|bid| bid.id() == id).is_some() {
+            return Some(TradeSide::Buy);
+        }
+        if state.ask.take_if(|ask| ask.id() == id).is_some() {
+            return Some(TradeSide::Sell);
+        }
+
+        None
+    }
+
+    /// Cancels the resting quotes. Quotes are placed again on the next [`update`](Self::update).
+    pub async fn cancel_all(&self) -> Result<()> {
+        let _send_guard = self.send_lock.lock().await;
+
+        self.cancel_quotes().await?;
+        self.lock_state().mid = None;
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Quoter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Quoter")
+            .field("quantity", &self.quantity)
+            .field("offset", &self.offset)
+            .field("requote_threshold", &self.requote_threshold)
+            .field("mid", &self.mid())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::{faults::ApiCall, stub::StubApi};
+
+    /// Returns placed quotes as open orders, recording the IDs of canceled ones in `canceled`.
+    fn book_api(canceled: Arc<Mutex<Vec<Uuid>>>) -> StubApi {
+        let placed = Arc::new(Mutex::new(Vec::<CrossOrder>::new()));
+        let book = placed.clone();
+
+        StubApi::new()
+            .with_place_cross_order(move |side, quantity, execution, _| {
+                let TradeExecution::Limit(price) = execution else {
+                    panic!("quotes must be limit orders");
+                };
+
+                let order: CrossOrder = serde_json::from_value(json!({
+                    "id": Uuid::new_v4(),
+                    "type": "limit",
+                    "side": side,
+                    "quantity": quantity,
+                    "price": price,
+                    "tradingFee": 0,
+                    "createdAt": "2025-01-01T00:00:00.000Z",
+                    "filledAt": null,
+                    "canceledAt": null,
+                    "open": true,
+                    "filled": false,
+                    "canceled": false,
+                    "clientId": null,
+                }))
+                .unwrap();
+
+                placed.lock().unwrap().push(order.clone());
+                Ok(order)
+            })
+            .with_cancel_cross_order(move |id| {
+                canceled.lock().unwrap().push(id);
+
+                let placed = book.lock().unwrap();
+                Ok(placed.iter().find(|o| o.id() == id).unwrap().clone())
+            })
+    }
+
+    fn price(value: i32) -> Price {
+        Price::try_from(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_quoter_requotes_on_threshold_and_fills() {
+        let canceled = Arc::new(Mutex::new(Vec::new()));
+        let api = Arc::new(book_api(canceled.clone()));
+        let quantity = OrderQuantity::try_from(100).unwrap();
+
+        assert!(matches!(
+            Quoter::new(api.clone(), quantity, 0.),
+            Err(QuoterValidationError::InvalidOffset(_))
+        ));
+
+        let quoter = Quoter::new(api.clone(), quantity, 25.)
+            .unwrap()
+            .with_requote_threshold(10.);
+
+        assert!(quoter.update(price(100_000)).await.unwrap());
+        assert_eq!(quoter.bid().unwrap().price(), price(99_975));
+        assert_eq!(quoter.ask().unwrap().price(), price(100_025));
+
+        // Below the threshold
+        assert!(!quoter.update(price(100_005)).await.unwrap());
+        assert_eq!(api.call_count(ApiCall::PlaceCrossOrder), 2);
+
+        let first_bid = quoter.bid().unwrap();
+        assert!(quoter.update(price(100_010)).await.unwrap());
+        assert_eq!(quoter.mid(), Some(price(100_010)));
+        assert_eq!(quoter.bid().unwrap().price(), price(99_985));
+        assert_eq!(api.call_count(ApiCall::CancelCrossOrder), 2);
+        assert!(canceled.lock().unwrap().contains(&first_bid.id()));
+
+        // A fill requotes both sides, even without a price move
+        let ask = quoter.ask().unwrap();
+        assert_eq!(quoter.on_filled(Uuid::nil()), None);
+        assert_eq!(quoter.on_filled(ask.id()), Some(TradeSide::Sell));
+        assert!(quoter.update(price(100_010)).await.unwrap());
+        assert_eq!(api.call_count(ApiCall::CancelCrossOrder), 3);
+        assert_eq!(api.call_count(ApiCall::PlaceCrossOrder), 6);
+
+        quoter.cancel_all().await.unwrap();
+        assert!(quoter.bid().is_none() && quoter.ask().is_none());
+        assert_eq!(quoter.mid(), None);
+    }
+}
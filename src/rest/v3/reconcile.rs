@@ -0,0 +1,331 @@
+//! Comparison of an expected trading state against the live API, for safe restarts.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::shared::{
+    models::{quantity::order::OrderQuantity, trade::TradeSide},
+    rest::error::Result,
+};
+
+use super::{
+    RestClient,
+    models::{CrossOrder, CrossPosition, Trade},
+};
+
+/// Expected side and quantity of a trade or cross order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpectedOrder {
+    side: TradeSide,
+    quantity: OrderQuantity,
+}
+
+impl ExpectedOrder {
+    pub fn new(side: TradeSide, quantity: OrderQuantity) -> Self {
+        Self { side, quantity }
+    }
+
+    pub fn side(&self) -> TradeSide {
+        self.side
+    }
+
+    pub fn quantity(&self) -> OrderQuantity {
+        self.quantity
+    }
+
+    fn matches(&self, side: TradeSide, quantity: OrderQuantity) -> bool {
+        self.side == side && self.quantity == quantity
+    }
+}
+
+/// Caller-supplied snapshot of the expected state, compared against the live API by
+/// [`RestClient::reconcile`].
+///
+/// Only the markets explicitly set are compared. Setting a market with no orders asserts that
+/// there are none.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::{
+///     models::{OrderQuantity, TradeSide, Uuid},
+///     reconcile::{ExpectedOrder, ExpectedState},
+/// };
+///
+/// let trade_id = Uuid::nil();
+/// let expected = ExpectedState::new()
+///     .with_isolated([(
+///         trade_id,
+///         ExpectedOrder::new(TradeSide::Buy, OrderQuantity::try_from(100).unwrap()),
+///     )])
+///     .with_cross([], 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExpectedState {
+    isolated: Option<HashMap<Uuid, ExpectedOrder>>,
+    cross: Option<(HashMap<Uuid, ExpectedOrder>, i64)>,
+}
+
+impl ExpectedState {
+    /// Creates a snapshot that doesn't compare any market.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the expected open and running isolated trades, by ID.
+    pub fn with_isolated(
+        mut self,
+        trades: impl IntoIterator<Item = (Uuid, ExpectedOrder)>,
+    ) -> Self {
+        self.isolated = Some(trades.into_iter().collect());
+        self
+    }
+
+    /// Sets the expected open cross orders, by ID, and the expected signed quantity of the cross
+    /// position (positive when long, negative when short).
+    pub fn with_cross(
+        mut self,
+        open_orders: impl IntoIterator<Item = (Uuid, ExpectedOrder)>,
+        position_quantity: i64,
+    ) -> Self {
+        self.cross = Some((open_orders.into_iter().collect(), position_quantity));
+        self
+    }
+}
+
+/// Live item whose side or quantity differs from the expectation.
+#[derive(Debug, Clone)]
+pub struct Mismatch<T> {
+    expected: ExpectedOrder,
+    actual: T,
+}
+
+impl<T> Mismatch<T> {
+    pub fn expected(&self) -> &ExpectedOrder {
+        &self.expected
+    }
+
+    pub fn actual(&self) -> &T {
+        &self.actual
+    }
+}
+
+/// Differences between an [`ExpectedState`] and the live API.
+#[derive(Debug, Clone, Default)]
+pub struct StateDiff {
+    missing_trades: Vec<(Uuid, ExpectedOrder)>,
+    unexpected_trades: Vec<Trade>,
+    mismatched_trades: Vec<Mismatch<Trade>>,
+    missing_cross_orders: Vec<(Uuid, ExpectedOrder)>,
+    unexpected_cross_orders: Vec<CrossOrder>,
+    mismatched_cross_orders: Vec<Mismatch<CrossOrder>>,
+    cross_position_mismatch: Option<(i64, CrossPosition)>,
+}
+
+impl StateDiff {
+    /// Returns `true` if the live state matches the expected state.
+    pub fn is_consistent(&self) -> bool {
+        self.missing_trades.is_empty()
+            && self.unexpected_trades.is_empty()
+            && self.mismatched_trades.is_empty()
+            && self.missing_cross_orders.is_empty()
+            && self.unexpected_cross_orders.is_empty()
+            && self.mismatched_cross_orders.is_empty()
+            && self.cross_position_mismatch.is_none()
+    }
+
+    /// Expected isolated trades that are neither open nor running.
+    pub fn missing_trades(&self) -> &[(Uuid, ExpectedOrder)] {
+        &self.missing_trades
+    }
+
+    /// Open or running isolated trades that were not expected.
+    pub fn unexpected_trades(&self) -> &[Trade] {
+        &self.unexpected_trades
+    }
+
+    /// Isolated trades whose side or quantity differs from the expectation.
+    pub fn mismatched_trades(&self) -> &[Mismatch<Trade>] {
+        &self.mismatched_trades
+    }
+
+    /// Expected cross orders that are not open.
+    pub fn missing_cross_orders(&self) -> &[(Uuid, ExpectedOrder)] {
+        &self.missing_cross_orders
+    }
+
+    /// Open cross orders that were not expected.
+    pub fn unexpected_cross_orders(&self) -> &[CrossOrder] {
+        &self.unexpected_cross_orders
+    }
+
+    /// Cross orders whose side or quantity differs from the expectation.
+    pub fn mismatched_cross_orders(&self) -> &[Mismatch<CrossOrder>] {
+        &self.mismatched_cross_orders
+    }
+
+    /// Expected quantity and live cross position, if their quantities differ.
+    pub fn cross_position_mismatch(&self) -> Option<(i64, &CrossPosition)> {
+        self.cross_position_mismatch
+            .as_ref()
+            .map(|(expected, position)| (*expected, position))
+    }
+}
+
+/// Splits `live` items into unexpected and mismatched ones, returning the expected items that
+/// were not found.
+fn diff_orders<T>(
+    expected: &HashMap<Uuid, ExpectedOrder>,
+    live: Vec<T>,
+    key: impl Fn(&T) -> (Uuid, TradeSide, OrderQuantity),
+    unexpected: &mut Vec<T>,
+    mismatched: &mut Vec<Mismatch<T>>,
+) -> Vec<(Uuid, ExpectedOrder)> {
+    let mut remaining = expected.clone();
+
+    for item in live {
+        let (id, side, quantity) = key(&item);
+
+        match remaining.remove(&id) {
+            None => unexpected.push(item),
+            Some(expected) if !expected.matches(side, quantity) => mismatched.push(Mismatch {
+                expected,
+                actual: item,
+            }),
+            Some(_) => {}
+        }
+    }
+
+    let mut missing: Vec<_> = remaining.into_iter().collect();
+    missing.sort_by_key(|(id, _)| *id);
+    missing
+}
+
+pub(super) fn diff(
+    expected: &ExpectedState,
+    trades: Vec<Trade>,
+    cross: Option<(Vec<CrossOrder>, CrossPosition)>,
+) -> StateDiff {
+    let mut diff = StateDiff::default();
+
+    if let Some(expected_trades) = &expected.isolated {
+        diff.missing_trades = diff_orders(
+            expected_trades,
+            trades,
+            |trade| (trade.id(), trade.side(), trade.quantity()),
+            &mut diff.unexpected_trades,
+            &mut diff.mismatched_trades,
+        );
+    }
+
+    if let (Some((expected_orders, expected_quantity)), Some((orders, position))) =
+        (&expected.cross, cross)
+    {
+        diff.missing_cross_orders = diff_orders(
+            expected_orders,
+            orders,
+            |order| (order.id(), order.side(), order.quantity()),
+            &mut diff.unexpected_cross_orders,
+            &mut diff.mismatched_cross_orders,
+        );
+
+        if position.quantity() != *expected_quantity {
+            diff.cross_position_mismatch = Some((*expected_quantity, position));
+        }
+    }
+
+    diff
+}
+
+pub(super) async fn reconcile(rest: &RestClient, expected: &ExpectedState) -> Result<StateDiff> {
+    let mut trades = Vec::new();
+    if expected.isolated.is_some() {
+        trades = rest.futures_isolated.get_open_trades().await?;
+        trades.extend(rest.futures_isolated.get_running_trades().await?);
+    }
+
+    let cross = if expected.cross.is_some() {
+        let orders = rest.futures_cross.get_open_orders().await?;
+        let position = rest.futures_cross.get_position().await?;
+        Some((orders, position))
+    } else {
+        None
+    };
+
+    Ok(diff(expected, trades, cross))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures;
+
+    fn quantity(value: u64) -> OrderQuantity {
+        OrderQuantity::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_diff_isolated() {
+        let open = fixtures::open_trade();
+        let running = fixtures::running_trade();
+        let missing_id = Uuid::from_u128(42);
+
+        let expected = ExpectedState::new().with_isolated([
+            (
+                open.id(),
+                ExpectedOrder::new(open.side(), quantity(open.quantity().as_u64() + 1)),
+            ),
+            (
+                missing_id,
+                ExpectedOrder::new(TradeSide::Sell, quantity(10)),
+            ),
+        ]);
+
+        let result = diff(&expected, vec![open.clone()], None);
+
+        assert!(!result.is_consistent());
+        assert_eq!(result.missing_trades().len(), 1);
+        assert_eq!(result.missing_trades()[0].0, missing_id);
+        assert_eq!(result.mismatched_trades().len(), 1);
+        assert_eq!(result.mismatched_trades()[0].actual().id(), open.id());
+        assert!(result.unexpected_trades().is_empty());
+
+        let result = diff(&ExpectedState::new().with_isolated([]), vec![running], None);
+
+        assert_eq!(result.unexpected_trades().len(), 1);
+    }
+
+    #[test]
+    fn test_diff_cross() {
+        let order = fixtures::open_cross_order();
+        let position = fixtures::running_position();
+
+        let expected = ExpectedState::new().with_cross(
+            [(
+                order.id(),
+                ExpectedOrder::new(order.side(), order.quantity()),
+            )],
+            position.quantity(),
+        );
+        let diff_ok = diff(
+            &expected,
+            Vec::new(),
+            Some((vec![order.clone()], position.clone())),
+        );
+        assert!(diff_ok.is_consistent());
+
+        let expected = ExpectedState::new().with_cross([], 0);
+        let diff = diff(&expected, Vec::new(), Some((vec![order], position)));
+
+        assert_eq!(diff.unexpected_cross_orders().len(), 1);
+        assert_eq!(diff.cross_position_mismatch().map(|(e, _)| e), Some(0));
+    }
+
+    #[test]
+    fn test_untracked_markets_are_ignored() {
+        let diff = diff(&ExpectedState::new(), vec![fixtures::running_trade()], None);
+
+        assert!(diff.is_consistent());
+    }
+}
@@ -0,0 +1,303 @@
+//! Aggregation of PnL, fees and funding over calendar periods, for dashboards and alerting.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc};
+
+use super::models::{CrossFunding, CrossOrder, Trade};
+
+/// Calendar period used to bucket report items. Periods are aligned to UTC midnight, weeks start
+/// on Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl ReportPeriod {
+    /// Returns the start of the period containing `time`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::DateTime;
+    /// use lnm_sdk::rest::v3::reporting::ReportPeriod;
+    ///
+    /// let time: DateTime<chrono::Utc> = "2025-05-15T13:45:00Z".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     ReportPeriod::Weekly.start_of(time).to_rfc3339(),
+    ///     "2025-05-12T00:00:00+00:00"
+    /// );
+    /// assert_eq!(
+    ///     ReportPeriod::Monthly.start_of(time).to_rfc3339(),
+    ///     "2025-05-01T00:00:00+00:00"
+    /// );
+    /// ```
+    pub fn start_of(self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let date = time.date_naive();
+
+        let start = match self {
+            Self::Daily => date,
+            Self::Weekly => date - Days::new(date.weekday().num_days_from_monday() as u64),
+            Self::Monthly => NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+                .expect("first day of month must be valid"),
+        };
+
+        start.and_time(Default::default()).and_utc()
+    }
+
+    /// Returns the start of the period following the one starting at `start`.
+    pub fn next_start(self, start: DateTime<Utc>) -> DateTime<Utc> {
+        let next = match self {
+            Self::Daily => start.checked_add_days(Days::new(1)),
+            Self::Weekly => start.checked_add_days(Days::new(7)),
+            Self::Monthly => start.checked_add_months(Months::new(1)),
+        };
+
+        next.expect("period end must be in range")
+    }
+}
+
+/// PnL, fees and funding aggregated over a single period, in sats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PnlReport {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    realized_pl: i64,
+    unrealized_pl: i64,
+    trading_fees: u64,
+    funding_fees: i64,
+    closed_trades: usize,
+}
+
+impl PnlReport {
+    fn new(period: ReportPeriod, start: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            end: period.next_start(start),
+            realized_pl: 0,
+            unrealized_pl: 0,
+            trading_fees: 0,
+            funding_fees: 0,
+            closed_trades: 0,
+        }
+    }
+
+    /// Start of the period (inclusive).
+    pub fn start(&self) -> DateTime<Utc> {
+        self.start
+    }
+
+    /// End of the period (exclusive).
+    pub fn end(&self) -> DateTime<Utc> {
+        self.end
+    }
+
+    /// PL of the trades closed during the period, excluding fees.
+    pub fn realized_pl(&self) -> i64 {
+        self.realized_pl
+    }
+
+    /// PL of the trades still running, attributed to the period containing the `as_of` time of
+    /// the report.
+    pub fn unrealized_pl(&self) -> i64 {
+        self.unrealized_pl
+    }
+
+    /// Opening, closing and cross trading fees paid during the period.
+    pub fn trading_fees(&self) -> u64 {
+        self.trading_fees
+    }
+
+    /// Net funding fees of the period. Positive when paid, negative when received.
+    pub fn funding_fees(&self) -> i64 {
+        self.funding_fees
+    }
+
+    /// Number of trades closed during the period.
+    pub fn closed_trades(&self) -> usize {
+        self.closed_trades
+    }
+
+    /// Realized and unrealized PL, net of trading and funding fees.
+    pub fn net_pl(&self) -> i64 {
+        self.realized_pl + self.unrealized_pl - self.trading_fees as i64 - self.funding_fees
+    }
+}
+
+/// Builds [`PnlReport`]s from trade history, one per period with activity.
+///
+/// Closed isolated trades are attributed to the period they were closed in, with their opening
+/// and closing fees and funding fees. Running trades are attributed to the period containing
+/// `as_of`, with their current PL as unrealized PL. Cross margin activity can be added from
+/// filled cross orders (trading fees) and cross funding entries.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use chrono::Utc;
+/// use lnm_sdk::rest::v3::reporting::{PnlReportBuilder, ReportPeriod};
+///
+/// let closed = rest.futures_isolated.get_closed_trades(None, None, None, None).await?;
+/// let running = rest.futures_isolated.get_running_trades().await?;
+///
+/// let reports = PnlReportBuilder::new(ReportPeriod::Daily, Utc::now())
+///     .with_trades(closed.data())
+///     .with_trades(&running)
+///     .build();
+///
+/// for report in reports {
+///     println!("{}: net PL {} sats", report.start().date_naive(), report.net_pl());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PnlReportBuilder {
+    period: ReportPeriod,
+    as_of: DateTime<Utc>,
+    reports: BTreeMap<DateTime<Utc>, PnlReport>,
+}
+
+impl PnlReportBuilder {
+    /// Creates a builder for the given period. Running trades are reported as of `as_of`.
+    pub fn new(period: ReportPeriod, as_of: DateTime<Utc>) -> Self {
+        Self {
+            period,
+            as_of,
+            reports: BTreeMap::new(),
+        }
+    }
+
+    fn report_mut(&mut self, time: DateTime<Utc>) -> &mut PnlReport {
+        let start = self.period.start_of(time);
+        let period = self.period;
+
+        self.reports
+            .entry(start)
+            .or_insert_with(|| PnlReport::new(period, start))
+    }
+
+    /// Adds isolated trades. Trades that are open (not filled) or canceled are ignored.
+    pub fn with_trades<'a>(mut self, trades: impl IntoIterator<Item = &'a Trade>) -> Self {
+        for trade in trades {
+            if trade.closed() {
+                let closed_at = trade.closed_at().unwrap_or(self.as_of);
+                let report = self.report_mut(closed_at);
+
+                report.realized_pl += trade.pl().as_i64();
+                report.trading_fees += (trade.opening_fee() + trade.closing_fee()).as_u64();
+                report.funding_fees += trade.sum_funding_fees().as_i64();
+                report.closed_trades += 1;
+            } else if trade.running() {
+                let as_of = self.as_of;
+                let report = self.report_mut(as_of);
+
+                report.unrealized_pl += trade.pl().as_i64();
+                report.trading_fees += trade.opening_fee().as_u64();
+                report.funding_fees += trade.sum_funding_fees().as_i64();
+            }
+        }
+
+        self
+    }
+
+    /// Adds the trading fees of filled cross orders. Unfilled orders are ignored.
+    pub fn with_cross_orders<'a>(
+        mut self,
+        orders: impl IntoIterator<Item = &'a CrossOrder>,
+    ) -> Self {
+        for order in orders {
+            if let Some(filled_at) = order.filled_at() {
+                self.report_mut(filled_at).trading_fees += order.trading_fee().as_u64();
+            }
+        }
+
+        self
+    }
+
+    /// Adds cross margin funding fees.
+    pub fn with_cross_funding<'a>(
+        mut self,
+        funding: impl IntoIterator<Item = &'a CrossFunding>,
+    ) -> Self {
+        for entry in funding {
+            self.report_mut(entry.time()).funding_fees += entry.fee().as_i64();
+        }
+
+        self
+    }
+
+    /// Returns the reports of all periods with activity, oldest first.
+    pub fn build(self) -> Vec<PnlReport> {
+        self.reports.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures;
+
+    fn time(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_period_boundaries() {
+        let t = time("2024-12-31T23:59:59Z");
+
+        assert_eq!(
+            ReportPeriod::Daily.start_of(t),
+            time("2024-12-31T00:00:00Z")
+        );
+        assert_eq!(
+            ReportPeriod::Weekly.start_of(t),
+            time("2024-12-30T00:00:00Z")
+        );
+        assert_eq!(
+            ReportPeriod::Monthly.start_of(t),
+            time("2024-12-01T00:00:00Z")
+        );
+        assert_eq!(
+            ReportPeriod::Monthly.next_start(time("2024-12-01T00:00:00Z")),
+            time("2025-01-01T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_build_reports() {
+        let closed = fixtures::closed_trade();
+        let running = fixtures::running_trade();
+        let canceled = fixtures::canceled_trade();
+        let as_of = time("2025-06-02T10:00:00Z");
+
+        let reports = PnlReportBuilder::new(ReportPeriod::Monthly, as_of)
+            .with_trades([&closed, &running, &canceled])
+            .with_cross_orders([&fixtures::filled_cross_order()])
+            .build();
+
+        assert_eq!(reports.len(), 2);
+
+        let may = &reports[0];
+        assert_eq!(may.start(), time("2025-05-01T00:00:00Z"));
+        assert_eq!(may.end(), time("2025-06-01T00:00:00Z"));
+        assert_eq!(may.realized_pl(), closed.pl().as_i64());
+        assert_eq!(may.closed_trades(), 1);
+        assert_eq!(
+            may.trading_fees(),
+            (closed.opening_fee() + closed.closing_fee()).as_u64() + 100
+        );
+        assert_eq!(
+            may.net_pl(),
+            (closed.pl() - closed.opening_fee() - closed.closing_fee()).as_i64() - 100
+        );
+
+        let june = &reports[1];
+        assert_eq!(june.unrealized_pl(), running.pl().as_i64());
+        assert_eq!(june.closed_trades(), 0);
+    }
+}
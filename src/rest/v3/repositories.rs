@@ -6,8 +6,10 @@ use uuid::Uuid;
 
 use crate::shared::{
     models::{
+        address::BitcoinAddress,
         client_id::ClientId,
         cross_leverage::CrossLeverage,
+        invoice::Bolt11Invoice,
         leverage::Leverage,
         ohlc::{OhlcCandle, OhlcRange},
         oracle::{Index, LastPrice},
@@ -21,10 +23,12 @@ use crate::shared::{
 use super::models::{
     account::Account,
     funding::{CrossFunding, FundingSettlement, IsolatedFunding},
+    notification::Notification,
     page::Page,
     ticker::Ticker,
     trade::{CrossOrder, CrossPosition, Trade},
     transfer::CrossTransfer,
+    withdrawal::{InternalWithdrawal, LightningWithdrawal, OnchainWithdrawal},
 };
 
 /// Methods for interacting with [LNM's v3 API]'s REST Utilities endpoints.
@@ -297,6 +301,39 @@ pub trait FuturesIsolatedRepository: crate::sealed::Sealed + Send + Sync {
     /// ```
     async fn update_stoploss(&self, id: Uuid, value: Option<Price>) -> Result<Trade>;
 
+    /// Adjust the leverage of a running trade. Leverage is decreased by adding margin, and
+    /// increased by cashing-in (first from the trade's PL, if any, then from its margin). Returns
+    /// the updated trade, with its new margin, leverage and liquidation price.
+    ///
+    /// The adjustment is validated locally against the trade's current figures at `market_price`
+    /// before any request is sent.
+    ///
+    /// **Required permissions**: `futures:isolated:write`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient, trade: lnm_sdk::rest::v3::models::Trade) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::{Leverage, Trade};
+    ///
+    /// let ticker = rest.futures_data.get_ticker().await?;
+    /// let updated_trade: Trade = rest
+    ///     .futures_isolated
+    ///     .update_leverage(&trade, Leverage::try_from(5)?, ticker.index())
+    ///     .await?;
+    ///
+    /// println!("New leverage: {}", updated_trade.leverage());
+    /// println!("New liquidation: {}", updated_trade.liquidation());
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn update_leverage(
+        &self,
+        trade: &Trade,
+        leverage: Leverage,
+        market_price: Price,
+    ) -> Result<Trade>;
+
     /// Place a new isolated trade.
     ///
     /// **Required permissions**: `futures:isolated:write`
@@ -782,20 +819,38 @@ pub trait AccountRepository: crate::sealed::Sealed + Send + Sync {
     //     todo!()
     // }
 
-    // /// Get notifications for the current user. By default returns unread notifications. Use the
-    // /// read parameter to filter by read status.
-    // ///
-    // /// **Required permissions**: `account:notifications:read`
-    // async fn get_notifications(&self) -> Result<()> {
-    //     todo!()
-    // }
+    /// Get notifications for the current user, such as margin calls, liquidation warnings and
+    /// deposit confirmations. By default returns unread notifications. Use the `read` parameter to
+    /// filter by read status.
+    ///
+    /// **Required permissions**: `account:notifications:read`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::Notification;
+    ///
+    /// let unread: Vec<Notification> = rest.account.get_notifications(None).await?;
+    /// let read: Vec<Notification> = rest.account.get_notifications(Some(true)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_notifications(&self, read: Option<bool>) -> Result<Vec<Notification>>;
 
-    // /// Mark all notifications as read for the current user.
-    // ///
-    // /// **Required permissions**: `account:notifications:write`
-    // async fn mark_notifications_read(&self) -> Result<()> {
-    //     todo!()
-    // }
+    /// Mark all notifications as read for the current user.
+    ///
+    /// **Required permissions**: `account:notifications:write`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// rest.account.mark_notifications_read().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn mark_notifications_read(&self) -> Result<()>;
 }
 
 /// Methods for interacting with [LNM's v3 API]'s REST Deposits endpoints.
@@ -840,29 +895,82 @@ pub trait DepositsRepository: crate::sealed::Sealed + Send + Sync {
 /// This trait is sealed and not meant to be implemented outside of `lnm-sdk`.
 ///
 /// [LNM's v3 API]: https://docs.lnmarkets.com/api/#overview
-#[allow(dead_code)] // TODO
 #[async_trait]
 pub trait WithdrawalsRepository: crate::sealed::Sealed + Send + Sync {
-    /// Get internal withdrawals.
+    /// Get the history of internal withdrawals (transfers to other LN Markets users).
     ///
     /// **Required permissions**: `account:withdrawals:read`
-    async fn get_internal_withdrawals(&self) -> Result<()> {
-        todo!()
-    }
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::{InternalWithdrawal, Page};
+    ///
+    /// let withdrawals: Page<InternalWithdrawal> = rest
+    ///     .withdrawals
+    ///     .get_internal_withdrawals(None, None, None, None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_internal_withdrawals(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<InternalWithdrawal>>;
 
-    /// Get multiple on-chain withdrawals.
+    /// Get the history of on-chain withdrawal requests.
     ///
     /// **Required permissions**: `account:withdrawals:read`
-    async fn get_onchain_withdrawals(&self) -> Result<()> {
-        todo!()
-    }
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::{OnchainWithdrawal, Page};
+    ///
+    /// let withdrawals: Page<OnchainWithdrawal> = rest
+    ///     .withdrawals
+    ///     .get_onchain_withdrawals(None, None, None, None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_onchain_withdrawals(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<OnchainWithdrawal>>;
 
-    /// Get multiple Lightning withdrawals.
+    /// Get the history of Lightning withdrawals.
     ///
     /// **Required permissions**: `account:withdrawals:read`
-    async fn get_lightning_withdrawals(&self) -> Result<()> {
-        todo!()
-    }
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use lnm_sdk::rest::v3::models::{LightningWithdrawal, Page};
+    ///
+    /// let withdrawals: Page<LightningWithdrawal> = rest
+    ///     .withdrawals
+    ///     .get_lightning_withdrawals(None, None, None, None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_lightning_withdrawals(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<LightningWithdrawal>>;
 
     /// Create a new internal withdrawal.
     ///
@@ -871,22 +979,68 @@ pub trait WithdrawalsRepository: crate::sealed::Sealed + Send + Sync {
         todo!()
     }
 
-    /// Request a new on-chain withdrawal. The withdrawal request will be reviewed and processed
-    /// asynchronously.
+    /// Request a new on-chain withdrawal of `amount` sats. The withdrawal request will be reviewed
+    /// and processed asynchronously.
+    ///
+    /// Addresses that are not valid for the
+    /// [configured network](crate::rest::v3::RestClientConfig::with_network) are rejected locally
+    /// before the request is sent.
     ///
     /// **Required permissions**: `account:withdrawals:write`
-    async fn withdrawal_onchain(&self) -> Result<()> {
-        todo!()
-    }
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::num::NonZeroU64;
+    ///
+    /// use lnm_sdk::rest::v3::models::{BitcoinAddress, OnchainWithdrawal};
+    ///
+    /// let address = BitcoinAddress::try_from("bc1q...")?;
+    /// let withdrawal: OnchainWithdrawal = rest
+    ///     .withdrawals
+    ///     .withdrawal_onchain(address, NonZeroU64::new(100_000).unwrap())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn withdrawal_onchain(
+        &self,
+        address: BitcoinAddress,
+        amount: NonZeroU64,
+    ) -> Result<OnchainWithdrawal>;
 
     /// Request a new Lightning withdrawal. The `max_fees` amount will be reserved from the user's
     /// balance to pay routing fees. Any unused portion of this reserve will be returned to the
     /// user's balance after the withdrawal completes.
     ///
+    /// The invoice is validated locally before the request is sent. Invoices for a network other
+    /// than the [configured one](crate::rest::v3::RestClientConfig::with_network), without an
+    /// amount, or already expired are rejected.
+    ///
     /// **Required permissions**: `account:withdrawals:write`
-    async fn withdrawal_lightning(&self) -> Result<()> {
-        todo!()
-    }
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::num::NonZeroU64;
+    ///
+    /// use lnm_sdk::rest::v3::models::{Bolt11Invoice, LightningWithdrawal};
+    ///
+    /// let invoice = Bolt11Invoice::try_from("lnbc...")?;
+    /// let withdrawal: LightningWithdrawal = rest
+    ///     .withdrawals
+    ///     .withdrawal_lightning(invoice, Some(NonZeroU64::new(100).unwrap()))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn withdrawal_lightning(
+        &self,
+        invoice: Bolt11Invoice,
+        max_fees: Option<NonZeroU64>,
+    ) -> Result<LightningWithdrawal>;
 }
 
 /// Methods for interacting with [LNM's v3 API]'s REST Oracle endpoints.
@@ -0,0 +1,593 @@
+//! Closing isolated trades safely, with a definitive outcome.
+//!
+//! Closing a running trade takes more than a single request: its stoploss and takeprofit must be
+//! removed first, so that they can't trigger while the close is in flight, its size must be
+//! checked against the one the application expects, and the close must be confirmed, since a
+//! failed or timed out request doesn't mean that the trade is still running. [`PositionCloser`]
+//! performs the whole sequence, and only returns once the trade is known to be closed or running.
+
+use std::{sync::Arc, time::Duration};
+
+use thiserror::Error;
+use tokio::{
+    sync::broadcast::{Receiver, error::RecvError},
+    time,
+};
+use uuid::Uuid;
+
+use crate::{
+    shared::{
+        models::{price::Price, quantity::order::OrderQuantity},
+        rest::error::RestApiError,
+    },
+    stream::v1::models::StreamUpdate,
+};
+
+use super::{LnmFuturesApi, models::Trade};
+
+/// Isolated trade event sent when a trade is closed.
+const CLOSED_EVENT: &str = "closed";
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SafeCloseError {
+    /// The trade was not closed, as its size differs from the expected one. If its stoploss and
+    /// takeprofit had already been removed, they were re-applied, and `restore_error` holds the
+    /// error raised if that failed.
+    #[error("Trade {} has a quantity of {}, expected {expected}", trade.id(), trade.quantity())]
+    SizeMismatch {
+        trade: Box<Trade>,
+        expected: OrderQuantity,
+        restore_error: Option<Box<RestApiError>>,
+    },
+
+    /// The close was not submitted. If the stoploss or takeprofit of the trade had already been
+    /// removed, they were re-applied, and `restore_error` holds the error raised if that failed.
+    #[error("Trade couldn't be prepared for closing. Error: {e}")]
+    NotSubmitted {
+        #[source]
+        e: RestApiError,
+        restore_error: Option<Box<RestApiError>>,
+    },
+
+    /// The close was submitted, but the trade is still running. Its stoploss and takeprofit were
+    /// re-applied, and `restore_error` holds the error raised if that failed.
+    #[error("Trade {} is still running after being closed", trade.id())]
+    StillRunning {
+        trade: Box<Trade>,
+        #[source]
+        source: Option<RestApiError>,
+        restore_error: Option<Box<RestApiError>>,
+    },
+
+    /// The close was submitted, but neither the stream nor the REST API could confirm whether the
+    /// trade is still running.
+    #[error("Trade {id} may have been closed, outcome unknown. Error: {e}")]
+    OutcomeUnknown {
+        id: Uuid,
+        #[source]
+        e: RestApiError,
+    },
+}
+
+impl SafeCloseError {
+    /// Error raised while re-applying the original stoploss and takeprofit of a trade that is
+    /// still running, if that failed. The trade is then left without them.
+    pub fn restore_error(&self) -> Option<&RestApiError> {
+        match self {
+            Self::SizeMismatch { restore_error, .. }
+            | Self::NotSubmitted { restore_error, .. }
+            | Self::StillRunning { restore_error, .. } => restore_error.as_deref(),
+            Self::OutcomeUnknown { .. } => None,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, SafeCloseError>;
+
+/// Source of the confirmation that a trade was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseConfirmation {
+    /// A `closed` event of the trade was received from the stream.
+    Stream,
+    /// The trade was no longer listed among the running trades by the REST API.
+    Rest,
+}
+
+/// Definitive outcome of [`PositionCloser::close_position_safely`].
+#[derive(Debug)]
+pub enum CloseOutcome {
+    /// The trade was closed by this operation.
+    Closed {
+        /// Trade returned by the close request, if it succeeded.
+        trade: Option<Box<Trade>>,
+        confirmation: CloseConfirmation,
+    },
+    /// The trade was not running when the operation started, or was closed by its stoploss,
+    /// takeprofit, a liquidation or another client before the close was accepted.
+    NotRunning,
+}
+
+/// Closes running isolated trades, removing their attached orders first and confirming the close
+/// through the stream, or the REST API if no confirmation is received in time.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(
+/// #     rest: std::sync::Arc<lnm_sdk::rest::v3::RestClient>,
+/// #     conn: lnm_sdk::stream::v1::StreamConnection,
+/// #     trade_id: uuid::Uuid,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::{
+///     rest::v3::safe_close::{CloseOutcome, PositionCloser},
+///     stream::v1::models::StreamTopic,
+/// };
+///
+/// conn.subscribe(vec![StreamTopic::FuturesInverseBtcUsdIsolatedTrades])
+///     .await?;
+/// let mut rx = conn.receiver().await?;
+///
+/// let closer = PositionCloser::new(rest);
+/// match closer.close_position_safely(trade_id, None, &mut rx).await? {
+///     CloseOutcome::Closed { confirmation, .. } => println!("Closed, confirmed by {confirmation:?}"),
+///     CloseOutcome::NotRunning => println!("Trade was not running"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct PositionCloser {
+    api: Arc<dyn LnmFuturesApi>,
+    confirmation_timeout: Duration,
+}
+
+impl PositionCloser {
+    pub fn new(api: Arc<dyn LnmFuturesApi>) -> Self {
+        Self {
+            api,
+            confirmation_timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Sets how long to wait for the stream to confirm the close, before checking the running
+    /// trades through the REST API.
+    ///
+    /// Default: 10 seconds
+    pub fn with_confirmation_timeout(mut self, timeout: Duration) -> Self {
+        self.confirmation_timeout = timeout;
+        self
+    }
+
+    pub fn confirmation_timeout(&self) -> Duration {
+        self.confirmation_timeout
+    }
+
+    /// Closes the running trade `trade_id`.
+    ///
+    /// The stoploss and takeprofit of the trade are removed, its quantity is checked against
+    /// `expected` (if any), and the close is submitted. The close is then confirmed by a `closed`
+    /// event received from `updates`, which must be subscribed to the isolated trades topic
+    /// before calling this method, or by the running trades listed by the REST API once the
+    /// confirmation timeout elapses.
+    ///
+    /// If the operation fails while the trade is still running, its original stoploss and
+    /// takeprofit are re-applied before the error is returned (see
+    /// [`SafeCloseError::restore_error`]).
+    pub async fn close_position_safely(
+        &self,
+        trade_id: Uuid,
+        expected: Option<OrderQuantity>,
+        updates: &mut Receiver<StreamUpdate>,
+    ) -> Result<CloseOutcome> {
+        let Some(mut trade) =
+            self.running_trade(trade_id)
+                .await
+                .map_err(|e| SafeCloseError::NotSubmitted {
+                    e,
+                    restore_error: None,
+                })?
+        else {
+            return Ok(CloseOutcome::NotRunning);
+        };
+        if let Some(expected) = mismatched(&trade, expected) {
+            return Err(SafeCloseError::SizeMismatch {
+                trade: Box::new(trade),
+                expected,
+                restore_error: None,
+            });
+        }
+
+        // Attached orders removed so far, to be re-applied if the trade isn't closed
+        let mut removed = Attached::default();
+
+        if let Some(stoploss) = trade.stoploss() {
+            match self.api.update_stoploss(trade_id, None).await {
+                Ok(updated) => {
+                    trade = updated;
+                    removed.stoploss = Some(stoploss);
+                }
+                Err(e) => {
+                    return Err(SafeCloseError::NotSubmitted {
+                        e,
+                        restore_error: None,
+                    });
+                }
+            }
+        }
+        if let Some(takeprofit) = trade.takeprofit() {
+            match self.api.update_takeprofit(trade_id, None).await {
+                Ok(updated) => {
+                    trade = updated;
+                    removed.takeprofit = Some(takeprofit);
+                }
+                Err(e) => {
+                    let restore_error = self.restore(trade_id, removed).await;
+                    return Err(SafeCloseError::NotSubmitted { e, restore_error });
+                }
+            }
+        }
+
+        // The stoploss or takeprofit may have triggered before being removed
+        if !trade.running() {
+            return Ok(CloseOutcome::NotRunning);
+        }
+        if let Some(expected) = mismatched(&trade, expected) {
+            let restore_error = self.restore(trade_id, removed).await;
+            return Err(SafeCloseError::SizeMismatch {
+                trade: Box::new(trade),
+                expected,
+                restore_error,
+            });
+        }
+
+        let (closed, error) = match self.api.close_trade(trade_id).await {
+            Ok(closed) => (Some(closed), None),
+            Err(e) => (None, Some(e)),
+        };
+
+        // A rejected close can't be confirmed by the stream, so the trade is checked right away
+        let rejected = error
+            .as_ref()
+            .is_some_and(|e| e.status().is_some() && !e.is_retryable());
+
+        if !rejected && self.wait_for_close(trade_id, updates).await {
+            return Ok(CloseOutcome::Closed {
+                trade: closed.map(Box::new),
+                confirmation: CloseConfirmation::Stream,
+            });
+        }
+
+        match self.running_trade(trade_id).await {
+            Ok(None) if rejected => Ok(CloseOutcome::NotRunning),
+            Ok(None) => Ok(CloseOutcome::Closed {
+                trade: closed.map(Box::new),
+                confirmation: CloseConfirmation::Rest,
+            }),
+            Ok(Some(trade)) => {
+                let restore_error = self.restore(trade_id, removed).await;
+                Err(SafeCloseError::StillRunning {
+                    trade: Box::new(trade),
+                    source: error,
+                    restore_error,
+                })
+            }
+            Err(e) => Err(SafeCloseError::OutcomeUnknown { id: trade_id, e }),
+        }
+    }
+
+    /// Re-applies the `removed` stoploss and takeprofit of trade `id`, returning the first error
+    /// raised, if any.
+    async fn restore(&self, id: Uuid, removed: Attached) -> Option<Box<RestApiError>> {
+        let mut restore_error = None;
+
+        if let Some(stoploss) = removed.stoploss
+            && let Err(e) = self.api.update_stoploss(id, Some(stoploss)).await
+        {
+            restore_error.get_or_insert(Box::new(e));
+        }
+        if let Some(takeprofit) = removed.takeprofit
+            && let Err(e) = self.api.update_takeprofit(id, Some(takeprofit)).await
+        {
+            restore_error.get_or_insert(Box::new(e));
+        }
+
+        restore_error
+    }
+
+    async fn running_trade(&self, id: Uuid) -> std::result::Result<Option<Trade>, RestApiError> {
+        let trades = self.api.get_running_trades().await?;
+
+        Ok(trades.into_iter().find(|trade| trade.id() == id))
+    }
+
+    /// Waits for the `closed` event of trade `id`, returning `false` if none was received within
+    /// the confirmation timeout.
+    async fn wait_for_close(&self, id: Uuid, updates: &mut Receiver<StreamUpdate>) -> bool {
+        let wait = async {
+            loop {
+                match updates.recv().await {
+                    Ok(StreamUpdate::FuturesInverseBtcUsdIsolatedTrades(event))
+                        if event.event() == CLOSED_EVENT && event.trade().id() == Some(id) =>
+                    {
+                        return true;
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return false,
+                }
+            }
+        };
+
+        time::timeout(self.confirmation_timeout, wait)
+            .await
+            .unwrap_or(false)
+    }
+}
+
+/// Stoploss and takeprofit of a trade.
+#[derive(Debug, Clone, Copy, Default)]
+struct Attached {
+    stoploss: Option<Price>,
+    takeprofit: Option<Price>,
+}
+
+/// Returns the expected quantity, if the trade doesn't match it.
+fn mismatched(trade: &Trade, expected: Option<OrderQuantity>) -> Option<OrderQuantity> {
+    expected.filter(|expected| trade.quantity() != *expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use http::{Method, StatusCode};
+    use serde_json::json;
+    use tokio::sync::broadcast;
+
+    use super::*;
+    use crate::shared::rest::error::ErrorResponseContext;
+    use crate::{
+        shared::models::price::Price,
+        testing::{faults::ApiCall, fixtures, stub::StubApi},
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum CloseBehavior {
+        Close,
+        /// Rejected, as the trade was already closed
+        Reject,
+        /// Timed out, but closed by the server
+        TimeoutClosed,
+        /// Timed out before reaching the server
+        TimeoutRunning,
+    }
+
+    /// Single trade held by the stub, removed once closed.
+    #[derive(Clone)]
+    struct Book(Arc<Mutex<Option<serde_json::Value>>>);
+
+    impl Book {
+        fn new(stoploss: f64) -> Self {
+            let mut trade = fixtures::trade_json();
+            trade["stoploss"] = json!(stoploss);
+
+            Self(Arc::new(Mutex::new(Some(trade))))
+        }
+
+        fn current(&self) -> Option<Trade> {
+            let trade = self.0.lock().unwrap().clone()?;
+            Some(serde_json::from_value(trade).unwrap())
+        }
+    }
+
+    fn error_response(status: StatusCode) -> RestApiError {
+        RestApiError::ErrorResponse(Box::new(ErrorResponseContext {
+            method: Method::POST,
+            path: "/v3/futures/isolated/trade/close".to_string(),
+            status,
+            request_id: None,
+            correlation_id: None,
+            retry_after: None,
+            text: String::new(),
+        }))
+    }
+
+    /// Closes the trade in `book` according to `behavior`, optionally failing to restore its
+    /// stop loss.
+    fn close_api(book: &Book, behavior: CloseBehavior, fail_restore: bool) -> StubApi {
+        let running = book.clone();
+        let closing = book.clone();
+        let updating = book.clone();
+
+        StubApi::new()
+            .with_get_running_trades(move || Ok(running.current().into_iter().collect()))
+            .with_close_trade(move |_| {
+                if behavior != CloseBehavior::TimeoutRunning {
+                    closing.0.lock().unwrap().take();
+                }
+                match behavior {
+                    CloseBehavior::Close => Ok(fixtures::closed_trade()),
+                    CloseBehavior::Reject => Err(error_response(StatusCode::NOT_FOUND)),
+                    CloseBehavior::TimeoutClosed | CloseBehavior::TimeoutRunning => {
+                        Err(error_response(StatusCode::GATEWAY_TIMEOUT))
+                    }
+                }
+            })
+            .with_update_stoploss(move |_, value| {
+                if value.is_some() && fail_restore {
+                    return Err(error_response(StatusCode::BAD_REQUEST));
+                }
+                if let Some(trade) = updating.0.lock().unwrap().as_mut() {
+                    trade["stoploss"] = json!(value.map_or(0., |price| price.as_f64()));
+                }
+                Ok(updating.current().unwrap())
+            })
+    }
+
+    fn trade_id() -> Uuid {
+        fixtures::running_trade().id()
+    }
+
+    fn closed_event(id: Uuid) -> StreamUpdate {
+        StreamUpdate::FuturesInverseBtcUsdIsolatedTrades(
+            serde_json::from_value(json!({
+                "pair": "btc_usd",
+                "event": CLOSED_EVENT,
+                "trade": { "id": id },
+            }))
+            .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_close_confirmed_by_stream() {
+        let book = Book::new(90_000.);
+        let api = Arc::new(close_api(&book, CloseBehavior::Close, false));
+        let closer = PositionCloser::new(api.clone());
+        let (tx, mut rx) = broadcast::channel(8);
+
+        tx.send(closed_event(Uuid::new_v4())).unwrap();
+        tx.send(closed_event(trade_id())).unwrap();
+
+        let outcome = closer
+            .close_position_safely(trade_id(), None, &mut rx)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            outcome,
+            CloseOutcome::Closed {
+                trade: Some(_),
+                confirmation: CloseConfirmation::Stream
+            }
+        ));
+        assert_eq!(
+            api.calls(),
+            [
+                ApiCall::GetRunningTrades,
+                ApiCall::UpdateStoploss,
+                ApiCall::CloseTrade,
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_timed_out_close_checked_through_rest() {
+        let (_tx, mut rx) = broadcast::channel(8);
+
+        let book = Book::new(0.);
+        let api = Arc::new(close_api(&book, CloseBehavior::TimeoutClosed, false));
+        let outcome = PositionCloser::new(api.clone())
+            .close_position_safely(trade_id(), None, &mut rx)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            outcome,
+            CloseOutcome::Closed {
+                trade: None,
+                confirmation: CloseConfirmation::Rest
+            }
+        ));
+        assert_eq!(
+            api.calls(),
+            [
+                ApiCall::GetRunningTrades,
+                ApiCall::CloseTrade,
+                ApiCall::GetRunningTrades,
+            ]
+        );
+
+        let book = Book::new(0.);
+        let api = Arc::new(close_api(&book, CloseBehavior::TimeoutRunning, false));
+        let result = PositionCloser::new(api.clone())
+            .close_position_safely(trade_id(), None, &mut rx)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(SafeCloseError::StillRunning {
+                source: Some(_),
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_close_of_closed_trade() {
+        let book = Book::new(0.);
+        let api = Arc::new(close_api(&book, CloseBehavior::Reject, false));
+        let closer = PositionCloser::new(api.clone());
+        let (_tx, mut rx) = broadcast::channel(8);
+
+        let outcome = closer
+            .close_position_safely(trade_id(), None, &mut rx)
+            .await
+            .unwrap();
+
+        assert!(matches!(outcome, CloseOutcome::NotRunning));
+        assert_eq!(
+            api.calls(),
+            [
+                ApiCall::GetRunningTrades,
+                ApiCall::CloseTrade,
+                ApiCall::GetRunningTrades,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_size_mismatch_is_not_closed() {
+        let book = Book::new(90_000.);
+        let api = Arc::new(close_api(&book, CloseBehavior::Close, false));
+        let closer = PositionCloser::new(api.clone());
+        let (_tx, mut rx) = broadcast::channel(8);
+
+        let expected = OrderQuantity::try_from(500).unwrap();
+        let result = closer
+            .close_position_safely(trade_id(), Some(expected), &mut rx)
+            .await;
+
+        assert!(matches!(result, Err(SafeCloseError::SizeMismatch { .. })));
+        assert_eq!(api.calls(), [ApiCall::GetRunningTrades]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_still_running_trade_is_restored() {
+        let (_tx, mut rx) = broadcast::channel(8);
+
+        let book = Book::new(90_000.);
+        let api = Arc::new(close_api(&book, CloseBehavior::TimeoutRunning, false));
+        let result = PositionCloser::new(api.clone())
+            .close_position_safely(trade_id(), None, &mut rx)
+            .await;
+
+        let error = result.unwrap_err();
+        assert!(matches!(error, SafeCloseError::StillRunning { .. }));
+        assert!(error.restore_error().is_none());
+        assert_eq!(
+            api.calls(),
+            [
+                ApiCall::GetRunningTrades,
+                ApiCall::UpdateStoploss,
+                ApiCall::CloseTrade,
+                ApiCall::GetRunningTrades,
+                ApiCall::UpdateStoploss,
+            ]
+        );
+        assert_eq!(
+            book.current().unwrap().stoploss(),
+            Some(Price::try_from(90_000.).unwrap())
+        );
+
+        let book = Book::new(90_000.);
+        let api = Arc::new(close_api(&book, CloseBehavior::TimeoutRunning, true));
+        let result = PositionCloser::new(api.clone())
+            .close_position_safely(trade_id(), None, &mut rx)
+            .await;
+
+        let error = result.unwrap_err();
+        assert!(matches!(error, SafeCloseError::StillRunning { .. }));
+        assert!(error.restore_error().is_some());
+        assert_eq!(book.current().unwrap().stoploss(), None);
+    }
+}
@@ -0,0 +1,500 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+pub use http::{self, Method, Request, Response, Uri};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::shared::rest::{
+    canonical,
+    error::Result,
+    lnm::base::{self, LnmRestRequestBuilder},
+    query::QueryParams,
+};
+
+use super::{RestClientConfig, lnm::signature::SignatureGeneratorV3};
+
+/// Sans-IO layer for [LNM's v3 API].
+///
+/// Builds fully signed [`http::Request`] values and parses [`http::Response`] values into models,
+/// without performing any IO. Useful for embedders that need to drive the protocol with their own
+/// networking stack. Requests built by this layer are not rate limited, and the configured
+/// timeout is not applied.
+///
+/// Signed requests can also be [presigned](SansIoClient::presign), to be sent by a party that
+/// holds no API secret.
+///
+/// For most use cases, [`RestClient`](super::RestClient) should be preferred.
+///
+/// # Examples
+///
+/// ```
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::{
+///     QueryParams, RestClientConfig,
+///     sans_io::{Method, Response, SansIoClient},
+/// };
+///
+/// let sans_io = SansIoClient::new(RestClientConfig::default());
+///
+/// let request = sans_io.build_request(Method::GET, "/futures/ticker", &QueryParams::new(), false)?;
+/// let (method, uri) = (request.method().clone(), request.uri().clone());
+///
+/// // Send `request` with any HTTP client, then build an `http::Response` from the result.
+/// let response = Response::builder()
+///     .status(200)
+///     .body(br#"{ "lastPrice": 100000 }"#.to_vec())?;
+///
+/// let ticker: serde_json::Value = sans_io.parse_response(&method, &uri, response)?;
+/// assert_eq!(ticker["lastPrice"], 100000);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [LNM's v3 API]: https://api.lnmarkets.com/v3/
+pub struct SansIoClient {
+    requests: LnmRestRequestBuilder<SignatureGeneratorV3>,
+}
+
+impl SansIoClient {
+    /// Creates a new sans-IO client without credentials.
+    ///
+    /// Only the `endpoint` and `clock` of the configuration are used.
+    pub fn new(config: impl Into<RestClientConfig>) -> Self {
+        let config = config.into();
+
+        Self {
+            requests: LnmRestRequestBuilder::new(
+                config.endpoint().to_string(),
+                config.clock().clone(),
+            ),
+        }
+    }
+
+    /// Creates a new sans-IO client with credentials, able to build signed requests.
+    ///
+    /// Only the `endpoint` and `clock` of the configuration are used.
+    pub fn with_credentials(
+        config: impl Into<RestClientConfig>,
+        key: impl ToString,
+        secret: impl ToString,
+        passphrase: impl ToString,
+    ) -> Self {
+        let config = config.into();
+
+        Self {
+            requests: LnmRestRequestBuilder::with_credentials(
+                config.endpoint().to_string(),
+                key.to_string(),
+                passphrase.to_string(),
+                SignatureGeneratorV3::new(secret.to_string()),
+                config.clock().clone(),
+            ),
+        }
+    }
+
+    /// Indicates whether credentials were provided, and signed requests can be built.
+    pub fn has_credentials(&self) -> bool {
+        self.requests.has_credentials()
+    }
+
+    /// Builds a request with the given query parameters and no body.
+    ///
+    /// `path` is relative to the configured endpoint (e.g. `/futures/ticker`). When
+    /// `authenticated` is `true`, the request is signed with the current time, and must be sent
+    /// promptly.
+    pub fn build_request(
+        &self,
+        method: Method,
+        path: &str,
+        query_params: &QueryParams,
+        authenticated: bool,
+    ) -> Result<Request<Vec<u8>>> {
+        let url = self
+            .requests
+            .build_url_with_query_params(path.to_string(), query_params)?;
+
+        self.requests
+            .build_request(method, url, None, authenticated)
+    }
+
+    /// Builds a request with the given JSON body.
+    ///
+    /// `path` is relative to the configured endpoint (e.g. `/futures/isolated/trade`). When
+    /// `authenticated` is `true`, the request is signed with the current time, and must be sent
+    /// promptly.
+    pub fn build_request_with_body<B>(
+        &self,
+        method: Method,
+        path: &str,
+        body: &B,
+        authenticated: bool,
+    ) -> Result<Request<Vec<u8>>>
+    where
+        B: Serialize,
+    {
+        let url = self.requests.build_url(path.to_string())?;
+        let body = canonical::json_body(body)?;
+
+        self.requests
+            .build_request(method, url, Some(body), authenticated)
+    }
+
+    /// Builds a signed request to be sent by a different party, such as a browser or an embedded
+    /// device that holds no API secret.
+    ///
+    /// `path` is relative to the configured endpoint. The request is signed with the current time
+    /// of the configured [`Clock`](super::Clock), and is marked as expiring after `validity`. The
+    /// server independently rejects signatures with stale timestamps, so `validity` should be
+    /// kept short (a few seconds).
+    ///
+    /// Fails with [`RestApiError::MissingRequestCredentials`] if the client has no credentials.
+    ///
+    /// [`RestApiError::MissingRequestCredentials`]: super::error::RestApiError::MissingRequestCredentials
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    ///
+    /// use lnm_sdk::rest::v3::{
+    ///     QueryParams, RestClientConfig,
+    ///     sans_io::{Method, SansIoClient},
+    /// };
+    ///
+    /// let sans_io =
+    ///     SansIoClient::with_credentials(RestClientConfig::default(), "key", "secret", "pphrase");
+    ///
+    /// let presigned = sans_io.presign(
+    ///     Method::GET,
+    ///     "/account",
+    ///     &QueryParams::new(),
+    ///     Duration::from_secs(5),
+    /// )?;
+    ///
+    /// // Hand the presigned request to the constrained client, e.g. as JSON
+    /// let json = serde_json::to_string(&presigned)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn presign(
+        &self,
+        method: Method,
+        path: &str,
+        query_params: &QueryParams,
+        validity: Duration,
+    ) -> Result<PresignedRequest> {
+        let request = self.build_request(method, path, query_params, true)?;
+
+        Ok(PresignedRequest::new(request, validity))
+    }
+
+    /// Builds a signed request with the given JSON body, to be sent by a different party.
+    ///
+    /// See [`presign`](Self::presign).
+    pub fn presign_with_body<B>(
+        &self,
+        method: Method,
+        path: &str,
+        body: &B,
+        validity: Duration,
+    ) -> Result<PresignedRequest>
+    where
+        B: Serialize,
+    {
+        let request = self.build_request_with_body(method, path, body, true)?;
+
+        Ok(PresignedRequest::new(request, validity))
+    }
+
+    /// Parses a response into the given model.
+    ///
+    /// `method` and `uri` are those of the request the response corresponds to, and are used to
+    /// provide context on errors. Unsuccessful responses are returned as
+    /// [`RestApiError::ErrorResponse`](super::error::RestApiError::ErrorResponse). Errors don't
+    /// include the correlation ID of the request, which can be read from its `X-Correlation-Id`
+    /// header. `Retry-After` dates are resolved against the configured [`Clock`](super::Clock).
+    pub fn parse_response<T>(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        response: Response<Vec<u8>>,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let path = uri.path();
+        let (parts, body) = response.into_parts();
+        let text = String::from_utf8_lossy(&body).into_owned();
+
+        let text = base::check_response(
+            method,
+            path,
+            None,
+            parts.status,
+            &parts.headers,
+            text,
+            self.requests.clock().now(),
+        )?;
+
+        base::deserialize_response(method, path, None, text)
+    }
+}
+
+/// Signed request built by [`SansIoClient::presign`], to be sent as is by a party that holds no
+/// API secret.
+///
+/// Serializes to JSON with the method, full URL, headers (including the authentication headers)
+/// and body of the request, plus its signing and expiry times.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresignedRequest {
+    method: String,
+    url: String,
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+    signed_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl PresignedRequest {
+    fn new(request: Request<Vec<u8>>, validity: Duration) -> Self {
+        let headers: BTreeMap<String, String> = request
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+
+        // Authenticated requests always carry the signing timestamp
+        let signed_at = headers
+            .get("lnm-access-timestamp")
+            .and_then(|millis| millis.parse().ok())
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap_or_else(Utc::now);
+        let expires_at = chrono::Duration::from_std(validity)
+            .ok()
+            .and_then(|validity| signed_at.checked_add_signed(validity))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        let (parts, body) = request.into_parts();
+
+        Self {
+            method: parts.method.to_string(),
+            url: parts.uri.to_string(),
+            headers,
+            body: (!body.is_empty()).then(|| String::from_utf8_lossy(&body).into_owned()),
+            signed_at,
+            expires_at,
+        }
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Full URL of the request, including query parameters.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Headers to send with the request, including the authentication headers.
+    pub fn headers(&self) -> &BTreeMap<String, String> {
+        &self.headers
+    }
+
+    /// JSON body of the request, if any.
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    /// Time the request was signed.
+    pub fn signed_at(&self) -> DateTime<Utc> {
+        self.signed_at
+    }
+
+    /// Time after which the request should no longer be sent.
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    /// Returns whether the request is expired at `now`.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{TimeDelta, TimeZone};
+
+    use super::*;
+    use crate::{rest::v3::Clock, shared::rest::error::RestApiError, testing::clock::MockClock};
+
+    #[test]
+    fn test_build_unauthenticated_request() {
+        let sans_io = SansIoClient::new(RestClientConfig::default());
+
+        let request = sans_io
+            .build_request(
+                Method::GET,
+                "futures/ticker",
+                &QueryParams::new().with("limit", 10),
+                false,
+            )
+            .unwrap();
+
+        assert_eq!(request.method(), Method::GET);
+        assert_eq!(
+            request.uri().to_string(),
+            "https://api.lnmarkets.com/v3/futures/ticker?limit=10"
+        );
+        assert_eq!(request.headers().len(), 1);
+        assert!(base::correlation_id(request.headers()).is_some());
+        assert!(request.body().is_empty());
+    }
+
+    #[test]
+    fn test_build_signed_request_with_body() {
+        let sans_io =
+            SansIoClient::with_credentials(RestClientConfig::default(), "key", "secret", "pphrase");
+
+        let request = sans_io
+            .build_request_with_body(
+                Method::POST,
+                "/futures/isolated/trade/close",
+                &serde_json::json!({ "id": "abc" }),
+                true,
+            )
+            .unwrap();
+
+        let headers = request.headers();
+        assert_eq!(headers["lnm-access-key"], "key");
+        assert_eq!(headers["lnm-access-passphrase"], "pphrase");
+        assert!(headers.contains_key("lnm-access-signature"));
+        assert!(headers.contains_key("lnm-access-timestamp"));
+        assert_eq!(headers["content-type"], "application/json");
+        assert_eq!(request.body(), br#"{"id":"abc"}"#);
+    }
+
+    #[test]
+    fn test_signed_request_timestamp_follows_clock() {
+        let clock = MockClock::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+        let config = RestClientConfig::default().with_clock(Arc::new(clock.clone()));
+        let sans_io = SansIoClient::with_credentials(config, "key", "secret", "pphrase");
+
+        let build = || {
+            sans_io
+                .build_request(Method::GET, "/account", &QueryParams::new(), true)
+                .unwrap()
+        };
+
+        let first = build();
+        assert_eq!(
+            first.headers()["lnm-access-timestamp"],
+            clock.now().timestamp_millis().to_string()
+        );
+        let again = build();
+        assert_eq!(
+            again.headers()["lnm-access-signature"],
+            first.headers()["lnm-access-signature"],
+            "signing is deterministic"
+        );
+        assert_ne!(
+            base::correlation_id(again.headers()),
+            base::correlation_id(first.headers()),
+            "correlation IDs are generated per request"
+        );
+
+        clock.advance(TimeDelta::seconds(1));
+        let second = build();
+        assert_eq!(
+            second.headers()["lnm-access-timestamp"],
+            clock.now().timestamp_millis().to_string()
+        );
+        assert_ne!(
+            second.headers()["lnm-access-signature"],
+            first.headers()["lnm-access-signature"]
+        );
+    }
+
+    #[test]
+    fn test_presign_request() {
+        let clock = MockClock::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+        let config = RestClientConfig::default().with_clock(Arc::new(clock.clone()));
+        let sans_io = SansIoClient::with_credentials(config, "key", "secret", "pphrase");
+
+        let presigned = sans_io
+            .presign_with_body(
+                Method::POST,
+                "/futures/isolated/trade/close",
+                &serde_json::json!({ "id": "abc" }),
+                Duration::from_secs(5),
+            )
+            .unwrap();
+
+        assert_eq!(presigned.method(), "POST");
+        assert_eq!(
+            presigned.url(),
+            "https://api.lnmarkets.com/v3/futures/isolated/trade/close"
+        );
+        assert_eq!(presigned.headers()["lnm-access-key"], "key");
+        assert!(presigned.headers().contains_key("lnm-access-signature"));
+        assert_eq!(presigned.body(), Some(r#"{"id":"abc"}"#));
+        assert_eq!(presigned.signed_at(), clock.now());
+        assert_eq!(presigned.expires_at(), clock.now() + TimeDelta::seconds(5));
+
+        assert!(!presigned.is_expired_at(clock.now()));
+        clock.advance(TimeDelta::seconds(5));
+        assert!(presigned.is_expired_at(clock.now()));
+
+        let json = serde_json::to_string(&presigned).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PresignedRequest>(&json).unwrap(),
+            presigned
+        );
+
+        let unauthenticated = SansIoClient::new(RestClientConfig::default());
+        assert!(matches!(
+            unauthenticated.presign(
+                Method::GET,
+                "/account",
+                &QueryParams::new(),
+                Duration::from_secs(5)
+            ),
+            Err(RestApiError::MissingRequestCredentials)
+        ));
+    }
+
+    #[test]
+    fn test_build_authenticated_request_without_credentials() {
+        let sans_io = SansIoClient::new(RestClientConfig::default());
+
+        let result = sans_io.build_request(Method::GET, "/account", &QueryParams::new(), true);
+
+        assert!(matches!(
+            result,
+            Err(RestApiError::MissingRequestCredentials)
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_response() {
+        let uri: Uri = "https://api.lnmarkets.com/v3/account".parse().unwrap();
+        let response = Response::builder()
+            .status(401)
+            .header("x-request-id", "req-1")
+            .body(b"Unauthorized".to_vec())
+            .unwrap();
+
+        let sans_io = SansIoClient::new(RestClientConfig::default());
+        let error = sans_io
+            .parse_response::<serde_json::Value>(&Method::GET, &uri, response)
+            .unwrap_err();
+
+        assert!(error.is_auth_error());
+        assert_eq!(error.request_id(), Some("req-1"));
+        assert_eq!(error.endpoint(), Some((&Method::GET, "/v3/account")));
+    }
+}
@@ -0,0 +1,332 @@
+//! Snapshots of the full account state, and typed diffs between them, for monitoring and drift
+//! alerts.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::shared::{clock::Clock, rest::error::Result};
+
+use super::{
+    LnmFuturesApi, RestClient,
+    models::{Account, CrossOrder, CrossPosition, Sats, Trade, Usd},
+};
+
+/// Account, isolated trades, cross orders and cross position, fetched as close together as
+/// possible with [`RestClient::snapshot`].
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    started_at: DateTime<Utc>,
+    completed_at: DateTime<Utc>,
+    account: Account,
+    trades: Vec<Trade>,
+    cross_orders: Vec<CrossOrder>,
+    cross_position: CrossPosition,
+}
+
+impl Snapshot {
+    pub(super) fn new(
+        started_at: DateTime<Utc>,
+        completed_at: DateTime<Utc>,
+        account: Account,
+        trades: Vec<Trade>,
+        cross_orders: Vec<CrossOrder>,
+        cross_position: CrossPosition,
+    ) -> Self {
+        Self {
+            started_at,
+            completed_at,
+            account,
+            trades,
+            cross_orders,
+            cross_position,
+        }
+    }
+
+    /// Timestamp when the requests of the snapshot were sent.
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// Timestamp when the responses of all the requests of the snapshot were received.
+    pub fn completed_at(&self) -> DateTime<Utc> {
+        self.completed_at
+    }
+
+    /// Account, including the balances.
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    /// Open and running isolated trades.
+    pub fn trades(&self) -> &[Trade] {
+        &self.trades
+    }
+
+    /// Open cross orders.
+    pub fn cross_orders(&self) -> &[CrossOrder] {
+        &self.cross_orders
+    }
+
+    /// Cross position.
+    pub fn cross_position(&self) -> &CrossPosition {
+        &self.cross_position
+    }
+
+    /// Returns the changes from this snapshot to the `newer` one.
+    ///
+    /// Trades that were closed or canceled between the snapshots are reported as removed, since
+    /// snapshots only hold open and running trades.
+    pub fn diff(&self, newer: &Snapshot) -> SnapshotDiff {
+        let mut diff = SnapshotDiff::default();
+
+        let (from, to) = (self.account.balance(), newer.account.balance());
+        if from != to {
+            diff.balance = Some((from, to));
+        }
+
+        let (from, to) = (
+            self.account.synthetic_usd_balance(),
+            newer.account.synthetic_usd_balance(),
+        );
+        if from != to {
+            diff.synthetic_usd_balance = Some((from, to));
+        }
+
+        let mut old_trades: HashMap<Uuid, &Trade> = self
+            .trades
+            .iter()
+            .map(|trade| (trade.id(), trade))
+            .collect();
+        for trade in &newer.trades {
+            match old_trades.remove(&trade.id()) {
+                None => diff.added_trades.push(trade.clone()),
+                Some(old) if trade_state(old) != trade_state(trade) => {
+                    diff.changed_trades.push((old.clone(), trade.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed_trades = self
+            .trades
+            .iter()
+            .filter(|trade| old_trades.contains_key(&trade.id()))
+            .cloned()
+            .collect();
+
+        let mut old_orders: HashMap<Uuid, &CrossOrder> = self
+            .cross_orders
+            .iter()
+            .map(|order| (order.id(), order))
+            .collect();
+        for order in &newer.cross_orders {
+            if old_orders.remove(&order.id()).is_none() {
+                diff.added_cross_orders.push(order.clone());
+            }
+        }
+        diff.removed_cross_orders = self
+            .cross_orders
+            .iter()
+            .filter(|order| old_orders.contains_key(&order.id()))
+            .cloned()
+            .collect();
+
+        if position_state(&self.cross_position) != position_state(&newer.cross_position) {
+            diff.cross_position = Some((self.cross_position.clone(), newer.cross_position.clone()));
+        }
+
+        diff
+    }
+}
+
+/// Fields of a trade whose changes are reported by [`Snapshot::diff`].
+fn trade_state(trade: &Trade) -> impl PartialEq + use<> {
+    (
+        trade.running(),
+        trade.quantity(),
+        trade.margin(),
+        trade.leverage(),
+        trade.price(),
+        trade.stoploss(),
+        trade.takeprofit(),
+    )
+}
+
+/// Fields of a cross position whose changes are reported by [`Snapshot::diff`].
+fn position_state(position: &CrossPosition) -> impl PartialEq + use<> {
+    (
+        position.id(),
+        position.quantity(),
+        position.margin(),
+        position.leverage(),
+        position.entry_price(),
+    )
+}
+
+/// Changes between two [`Snapshot`]s, returned by [`Snapshot::diff`].
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotDiff {
+    balance: Option<(Sats, Sats)>,
+    synthetic_usd_balance: Option<(Usd, Usd)>,
+    added_trades: Vec<Trade>,
+    removed_trades: Vec<Trade>,
+    changed_trades: Vec<(Trade, Trade)>,
+    added_cross_orders: Vec<CrossOrder>,
+    removed_cross_orders: Vec<CrossOrder>,
+    cross_position: Option<(CrossPosition, CrossPosition)>,
+}
+
+impl SnapshotDiff {
+    /// Returns `true` if no changes were found.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.synthetic_usd_balance.is_none()
+            && self.added_trades.is_empty()
+            && self.removed_trades.is_empty()
+            && self.changed_trades.is_empty()
+            && self.added_cross_orders.is_empty()
+            && self.removed_cross_orders.is_empty()
+            && self.cross_position.is_none()
+    }
+
+    /// Previous and new balance, if the balance changed.
+    pub fn balance(&self) -> Option<(Sats, Sats)> {
+        self.balance
+    }
+
+    /// Previous and new synthetic USD balance, if it changed.
+    pub fn synthetic_usd_balance(&self) -> Option<(Usd, Usd)> {
+        self.synthetic_usd_balance
+    }
+
+    /// Isolated trades opened since the older snapshot.
+    pub fn added_trades(&self) -> &[Trade] {
+        &self.added_trades
+    }
+
+    /// Isolated trades of the older snapshot that are no longer open or running.
+    pub fn removed_trades(&self) -> &[Trade] {
+        &self.removed_trades
+    }
+
+    /// Previous and new state of isolated trades that were filled, or whose quantity, margin,
+    /// leverage, price, stoploss or takeprofit changed.
+    pub fn changed_trades(&self) -> &[(Trade, Trade)] {
+        &self.changed_trades
+    }
+
+    /// Cross orders placed since the older snapshot.
+    pub fn added_cross_orders(&self) -> &[CrossOrder] {
+        &self.added_cross_orders
+    }
+
+    /// Cross orders of the older snapshot that are no longer open.
+    pub fn removed_cross_orders(&self) -> &[CrossOrder] {
+        &self.removed_cross_orders
+    }
+
+    /// Previous and new cross position, if its ID, quantity, margin, leverage or entry price
+    /// changed.
+    pub fn cross_position(&self) -> Option<(&CrossPosition, &CrossPosition)> {
+        self.cross_position.as_ref().map(|(from, to)| (from, to))
+    }
+}
+
+pub(super) async fn snapshot(rest: &RestClient) -> Result<Snapshot> {
+    let started_at = rest.clock().now();
+
+    let (account, open_trades, running_trades, cross_orders, cross_position) = tokio::try_join!(
+        rest.account.get_account(),
+        rest.futures_isolated.get_open_trades(),
+        rest.futures_isolated.get_running_trades(),
+        rest.futures_cross.get_open_orders(),
+        rest.futures_cross.get_position(),
+    )?;
+
+    let mut trades = open_trades;
+    trades.extend(running_trades);
+
+    Ok(Snapshot::new(
+        started_at,
+        rest.clock().now(),
+        account,
+        trades,
+        cross_orders,
+        cross_position,
+    ))
+}
+
+/// Takes a [`Snapshot`] through any [`LnmFuturesApi`] implementation.
+pub(crate) async fn snapshot_from_api(
+    api: &dyn LnmFuturesApi,
+    clock: &dyn Clock,
+) -> Result<Snapshot> {
+    let started_at = clock.now();
+
+    let (account, open_trades, running_trades, cross_orders, cross_position) = tokio::try_join!(
+        api.get_account(),
+        api.get_open_trades(),
+        api.get_running_trades(),
+        api.get_open_cross_orders(),
+        api.get_cross_position(),
+    )?;
+
+    let mut trades = open_trades;
+    trades.extend(running_trades);
+
+    Ok(Snapshot::new(
+        started_at,
+        clock.now(),
+        account,
+        trades,
+        cross_orders,
+        cross_position,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures;
+
+    fn snapshot(trades: Vec<Trade>, cross_orders: Vec<CrossOrder>) -> Snapshot {
+        let now = Utc::now();
+
+        Snapshot::new(
+            now,
+            now,
+            fixtures::account(),
+            trades,
+            cross_orders,
+            fixtures::running_position(),
+        )
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let open = fixtures::open_trade();
+        let running = fixtures::running_trade();
+        let order = fixtures::open_cross_order();
+
+        let older = snapshot(vec![open.clone()], vec![order.clone()]);
+        assert!(older.diff(&older).is_empty());
+
+        // Fixture trades share their ID, so the open trade is seen as filled
+        let newer = snapshot(vec![running.clone()], Vec::new());
+        let diff = older.diff(&newer);
+
+        assert!(!diff.is_empty());
+        assert_eq!(diff.balance(), None);
+        assert_eq!(diff.changed_trades().len(), 1);
+        assert!(diff.changed_trades()[0].1.running());
+        assert!(diff.added_trades().is_empty());
+        assert!(diff.removed_trades().is_empty());
+        assert_eq!(diff.removed_cross_orders()[0].id(), order.id());
+        assert!(diff.cross_position().is_none());
+
+        let diff = newer.diff(&snapshot(Vec::new(), vec![order]));
+        assert_eq!(diff.removed_trades()[0].id(), running.id());
+        assert_eq!(diff.added_cross_orders().len(), 1);
+    }
+}
@@ -0,0 +1,508 @@
+//! Pluggable key-value persistence for stateful subsystems.
+//!
+//! [`StateStore`] is a small key-value interface that the order journal and the candle cache can
+//! be backed by, through [`StateStoreJournalStorage`](super::journal::StateStoreJournalStorage)
+//! and [`StateStoreCandleStore`](super::candle_cache::StateStoreCandleStore). Implementing it for
+//! an external database, such as Redis or Postgres, backs SDK state with that database.
+//! [`MemoryStateStore`] and [`FileStateStore`] are provided.
+
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    result,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use thiserror::Error;
+use tokio::{fs, io::AsyncWriteExt};
+use uuid::Uuid;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum StateStoreError {
+    #[error("State store IO error. Path: {path}, error: {e}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        e: io::Error,
+    },
+
+    #[error("State store backend error: {0}")]
+    Backend(String),
+}
+
+pub type Result<T> = result::Result<T, StateStoreError>;
+
+/// Key-value store backing the state of SDK subsystems.
+///
+/// Keys are UTF-8 strings, namespaced by each subsystem with a prefix such as `journal/`. Values
+/// are opaque bytes. Implementations must persist values before [`put`](Self::put) returns.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_trait::async_trait;
+/// use lnm_sdk::rest::v3::state_store::{Result, StateStore, StateStoreError};
+///
+/// struct RedisStore {
+///     // Redis connection
+/// }
+///
+/// #[async_trait]
+/// impl StateStore for RedisStore {
+///     async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+///         // GET key
+///         # unimplemented!()
+///     }
+///
+///     async fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+///         // SET key value
+///         # unimplemented!()
+///     }
+///
+///     async fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+///         // SCAN 0 MATCH prefix*, then MGET, sorted by key
+///         # unimplemented!()
+///     }
+///
+///     async fn delete(&self, key: &str) -> Result<()> {
+///         // DEL key
+///         # unimplemented!()
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait StateStore: Send + Sync {
+    /// Returns the value stored at `key`, if any.
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Durably stores `value` at `key`, replacing any previous value.
+    async fn put(&self, key: &str, value: &[u8]) -> Result<()>;
+
+    /// Returns the keys starting with `prefix` and their values, sorted by key.
+    async fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>>;
+
+    /// Durably removes the value stored at `key`. Removing a missing key is not an error.
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// Durably removes the values of all the keys starting with `prefix`.
+    ///
+    /// Default: [scans](Self::scan) the keys and [deletes](Self::delete) them one by one.
+    async fn delete_prefix(&self, prefix: &str) -> Result<()> {
+        for (key, _) in self.scan(prefix).await? {
+            self.delete(&key).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Shares a store between subsystems, e.g. the order journal and the candle cache.
+#[async_trait]
+impl<S: StateStore + ?Sized> StateStore for Arc<S> {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        (**self).get(key).await
+    }
+
+    async fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        (**self).put(key, value).await
+    }
+
+    async fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        (**self).scan(prefix).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        (**self).delete(key).await
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> Result<()> {
+        (**self).delete_prefix(prefix).await
+    }
+}
+
+/// In-memory [`StateStore`], not persisted across restarts. Useful for tests.
+#[derive(Debug, Default)]
+pub struct MemoryStateStore {
+    values: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl MemoryStateStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StateStore for MemoryStateStore {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .values
+            .lock()
+            .expect("`values` mutex can't be poisoned")
+            .get(key)
+            .cloned())
+    }
+
+    async fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.values
+            .lock()
+            .expect("`values` mutex can't be poisoned")
+            .insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    async fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        Ok(self
+            .values
+            .lock()
+            .expect("`values` mutex can't be poisoned")
+            .range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.values
+            .lock()
+            .expect("`values` mutex can't be poisoned")
+            .remove(key);
+        Ok(())
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> Result<()> {
+        self.values
+            .lock()
+            .expect("`values` mutex can't be poisoned")
+            .retain(|key, _| !key.starts_with(prefix));
+        Ok(())
+    }
+}
+
+/// [`StateStore`] backed by a directory, with one file per key.
+///
+/// Characters of keys other than ASCII alphanumerics, `-` and `_` are percent-encoded in file
+/// names. Values are written to a temporary file unique to the write, synced and renamed, and the
+/// directory is synced, so an interrupted write never leaves a partial value behind and a
+/// completed one survives a crash.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::{
+///     journal::{OrderJournal, StateStoreJournalStorage},
+///     state_store::FileStateStore,
+/// };
+///
+/// let storage = StateStoreJournalStorage::new(FileStateStore::new(".state"), "journal/");
+/// let journal = OrderJournal::open(storage).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileStateStore {
+    dir: PathBuf,
+}
+
+impl FileStateStore {
+    /// Creates a store using the directory at `dir`. The directory is created on the first put.
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Directory of the stored values.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn key_path(&self, key: &str) -> PathBuf {
+        self.dir.join(encode_key(key))
+    }
+}
+
+#[async_trait]
+impl StateStore for FileStateStore {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.key_path(key);
+
+        match fs::read(&path).await {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(StateStoreError::Io { path, e }),
+        }
+    }
+
+    async fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| StateStoreError::Io {
+                path: self.dir.clone(),
+                e,
+            })?;
+
+        let path = self.key_path(key);
+        // Unique per write, so concurrent writes of the same key don't share a temporary file
+        let tmp_path = path.with_extension(format!("{}.tmp", Uuid::new_v4().simple()));
+        let tmp_error = |e| StateStoreError::Io {
+            path: tmp_path.clone(),
+            e,
+        };
+
+        let written = async {
+            let mut file = fs::File::create(&tmp_path).await.map_err(tmp_error)?;
+            file.write_all(value).await.map_err(tmp_error)?;
+            file.sync_data().await.map_err(tmp_error)?;
+
+            fs::rename(&tmp_path, &path)
+                .await
+                .map_err(|e| StateStoreError::Io { path, e })
+        }
+        .await;
+
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp_path).await;
+        }
+        written?;
+
+        self.sync_dir().await
+    }
+
+    async fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let keys = self.keys(prefix).await?;
+
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            // Keys removed concurrently are skipped
+            if let Some(value) = self.get(&key).await? {
+                values.push((key, value));
+            }
+        }
+
+        Ok(values)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.key_path(key);
+
+        match fs::remove_file(&path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(StateStoreError::Io { path, e }),
+        }
+
+        self.sync_dir().await
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> Result<()> {
+        let keys = self.keys(prefix).await?;
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        for key in keys {
+            let path = self.key_path(&key);
+            match fs::remove_file(&path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(StateStoreError::Io { path, e }),
+            }
+        }
+
+        self.sync_dir().await
+    }
+}
+
+impl FileStateStore {
+    /// Returns the stored keys starting with `prefix`, sorted.
+    async fn keys(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut dir = match fs::read_dir(&self.dir).await {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(StateStoreError::Io {
+                    path: self.dir.clone(),
+                    e,
+                });
+            }
+        };
+
+        let mut keys = Vec::new();
+        loop {
+            let entry = dir.next_entry().await.map_err(|e| StateStoreError::Io {
+                path: self.dir.clone(),
+                e,
+            })?;
+            let Some(entry) = entry else {
+                break;
+            };
+
+            // Temporary files of interrupted writes, and files not written by this store
+            if let Some(key) = entry.file_name().to_str().and_then(decode_key)
+                && key.starts_with(prefix)
+            {
+                keys.push(key);
+            }
+        }
+        keys.sort();
+
+        Ok(keys)
+    }
+
+    /// Syncs the directory, so that renamed and removed entries survive a crash.
+    async fn sync_dir(&self) -> Result<()> {
+        let dir_error = |e| StateStoreError::Io {
+            path: self.dir.clone(),
+            e,
+        };
+
+        #[cfg(unix)]
+        fs::File::open(&self.dir)
+            .await
+            .map_err(dir_error)?
+            .sync_all()
+            .await
+            .map_err(dir_error)?;
+        #[cfg(not(unix))]
+        let _ = dir_error;
+
+        Ok(())
+    }
+}
+
+fn is_plain_key_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_')
+}
+
+fn encode_key(key: &str) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        if is_plain_key_byte(byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    // Distinguishes values from temporary files
+    encoded.push_str(".val");
+    encoded
+}
+
+fn decode_key(file_name: &str) -> Option<String> {
+    let encoded = file_name.strip_suffix(".val")?.as_bytes();
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+
+    while i < encoded.len() {
+        match encoded[i] {
+            b'%' => {
+                let hex = std::str::from_utf8(encoded.get(i + 1..i + 3)?).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+            }
+            byte if is_plain_key_byte(byte) => {
+                bytes.push(byte);
+                i += 1;
+            }
+            _ => return None,
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn exercise(store: &dyn StateStore) {
+        assert_eq!(store.get("journal/1").await.unwrap(), None);
+
+        store.put("journal/2", b"two").await.unwrap();
+        store.put("journal/1", b"one").await.unwrap();
+        store.put("candles/1m", b"candles").await.unwrap();
+        store.put("journal/1", b"uno").await.unwrap();
+
+        assert_eq!(
+            store.get("journal/1").await.unwrap().as_deref(),
+            Some(&b"uno"[..])
+        );
+        assert_eq!(
+            store.scan("journal/").await.unwrap(),
+            vec![
+                ("journal/1".to_string(), b"uno".to_vec()),
+                ("journal/2".to_string(), b"two".to_vec()),
+            ]
+        );
+        assert_eq!(store.scan("").await.unwrap().len(), 3);
+        assert!(store.scan("other/").await.unwrap().is_empty());
+
+        store.delete("journal/1").await.unwrap();
+        store.delete("journal/1").await.unwrap();
+        assert_eq!(store.get("journal/1").await.unwrap(), None);
+        assert_eq!(store.scan("").await.unwrap().len(), 2);
+
+        store.delete_prefix("journal/").await.unwrap();
+        assert!(store.scan("journal/").await.unwrap().is_empty());
+        assert_eq!(store.scan("").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_memory_state_store() {
+        exercise(&MemoryStateStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_state_store() {
+        let dir = std::env::temp_dir().join(format!("lnm-state-store-{}", uuid::Uuid::new_v4()));
+        let store = FileStateStore::new(&dir);
+
+        assert!(store.scan("").await.unwrap().is_empty());
+        exercise(&store).await;
+
+        assert_eq!(
+            decode_key(&encode_key("a/b c%é.1")).as_deref(),
+            Some("a/b c%é.1")
+        );
+        assert_eq!(decode_key("journal%2F1.tmp"), None);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_state_store_concurrent_puts() {
+        let dir = std::env::temp_dir().join(format!("lnm-state-store-{}", uuid::Uuid::new_v4()));
+        let store = Arc::new(FileStateStore::new(&dir));
+        let values: Vec<_> = (0..16).map(|i| format!("value-{i}").into_bytes()).collect();
+
+        let puts: Vec<_> = values
+            .iter()
+            .cloned()
+            .map(|value| {
+                let store = store.clone();
+                tokio::spawn(async move { store.put("key", &value).await })
+            })
+            .collect();
+        for put in puts {
+            put.await.unwrap().unwrap();
+        }
+
+        let value = store.get("key").await.unwrap().unwrap();
+        assert!(values.contains(&value));
+
+        // No temporary file is left behind
+        let mut entries = fs::read_dir(&dir).await.unwrap();
+        let mut count = 0;
+        while entries.next_entry().await.unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 1);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,441 @@
+//! Tax-lot accounting of the sats balance, with FIFO or LIFO lot matching.
+//!
+//! The account balance is treated as a set of lots of sats, each with a USD cost basis.
+//! Deposits and realized trade profits acquire lots, while withdrawals, realized trade losses and
+//! trading fees dispose of them. Each disposal is matched against the held lots, producing
+//! [`RealizedGain`] records with a stable, serializable schema.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::shared::models::SATS_PER_BTC;
+
+use super::models::Trade;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TaxLotError {
+    #[error("Disposal at {time} of {sats} sats exceeds the held lots by {shortfall} sats")]
+    InsufficientLots {
+        time: DateTime<Utc>,
+        sats: u64,
+        shortfall: u64,
+    },
+
+    #[error("Closed trade {0} has no exit price")]
+    MissingExitPrice(Uuid),
+}
+
+/// Order in which held lots are matched against disposals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LotMethod {
+    /// First in, first out.
+    Fifo,
+    /// Last in, first out.
+    Lifo,
+}
+
+/// Origin of an acquisition or disposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "camelCase")]
+pub enum LotSource {
+    Deposit,
+    Withdrawal,
+    TradeProfit(Uuid),
+    TradeLoss(Uuid),
+    TradingFee(Uuid),
+}
+
+/// Lot of sats held, with its USD cost basis.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxLot {
+    acquired_at: DateTime<Utc>,
+    source: LotSource,
+    sats: u64,
+    usd_price: f64,
+}
+
+impl TaxLot {
+    /// Timestamp when the lot was acquired.
+    pub fn acquired_at(&self) -> DateTime<Utc> {
+        self.acquired_at
+    }
+
+    /// Origin of the lot.
+    pub fn source(&self) -> LotSource {
+        self.source
+    }
+
+    /// Amount of sats still held.
+    pub fn sats(&self) -> u64 {
+        self.sats
+    }
+
+    /// BTC price in USD at acquisition.
+    pub fn usd_price(&self) -> f64 {
+        self.usd_price
+    }
+
+    /// USD cost basis of the sats still held.
+    pub fn cost_basis_usd(&self) -> f64 {
+        usd_value(self.sats, self.usd_price)
+    }
+}
+
+/// Disposal of sats matched against a single acquired lot.
+///
+/// Serialized with camelCase field names: `acquiredAt`, `disposedAt`, `acquisitionSource`,
+/// `disposalSource`, `sats`, `costBasisUsd`, `proceedsUsd` and `gainUsd`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RealizedGain {
+    acquired_at: DateTime<Utc>,
+    disposed_at: DateTime<Utc>,
+    acquisition_source: LotSource,
+    disposal_source: LotSource,
+    sats: u64,
+    cost_basis_usd: f64,
+    proceeds_usd: f64,
+    gain_usd: f64,
+}
+
+impl RealizedGain {
+    /// Timestamp when the matched lot was acquired.
+    pub fn acquired_at(&self) -> DateTime<Utc> {
+        self.acquired_at
+    }
+
+    /// Timestamp of the disposal.
+    pub fn disposed_at(&self) -> DateTime<Utc> {
+        self.disposed_at
+    }
+
+    /// Origin of the matched lot.
+    pub fn acquisition_source(&self) -> LotSource {
+        self.acquisition_source
+    }
+
+    /// Origin of the disposal.
+    pub fn disposal_source(&self) -> LotSource {
+        self.disposal_source
+    }
+
+    /// Amount of sats disposed from the matched lot.
+    pub fn sats(&self) -> u64 {
+        self.sats
+    }
+
+    /// USD cost basis of the disposed sats.
+    pub fn cost_basis_usd(&self) -> f64 {
+        self.cost_basis_usd
+    }
+
+    /// USD value of the disposed sats at disposal.
+    pub fn proceeds_usd(&self) -> f64 {
+        self.proceeds_usd
+    }
+
+    /// Realized gain in USD. Negative for losses.
+    pub fn gain_usd(&self) -> f64 {
+        self.gain_usd
+    }
+}
+
+/// Result of [`TaxLotLedger::compute`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxLotReport {
+    realized: Vec<RealizedGain>,
+    open_lots: Vec<TaxLot>,
+}
+
+impl TaxLotReport {
+    /// Realized gain records, in disposal order.
+    pub fn realized(&self) -> &[RealizedGain] {
+        &self.realized
+    }
+
+    /// Lots still held, oldest first.
+    pub fn open_lots(&self) -> &[TaxLot] {
+        &self.open_lots
+    }
+
+    /// Sum of the realized gains, in USD.
+    pub fn total_gain_usd(&self) -> f64 {
+        self.realized.iter().map(|gain| gain.gain_usd).sum()
+    }
+}
+
+fn usd_value(sats: u64, usd_price: f64) -> f64 {
+    sats as f64 / SATS_PER_BTC * usd_price
+}
+
+#[derive(Debug, Clone)]
+struct LotEvent {
+    time: DateTime<Utc>,
+    source: LotSource,
+    sats: u64,
+    usd_price: f64,
+    acquire: bool,
+}
+
+/// Ledger of acquisitions and disposals of sats, matched into tax lots.
+///
+/// Events can be added in any order, and are processed chronologically by
+/// [`compute`](Self::compute). Acquisitions are processed before disposals with the same
+/// timestamp.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use lnm_sdk::rest::v3::tax::{LotMethod, TaxLotLedger};
+///
+/// let t0 = Utc::now();
+///
+/// let report = TaxLotLedger::new(LotMethod::Fifo)
+///     .with_deposit(t0, 100_000, 50_000.)
+///     .with_deposit(t0 + Duration::days(1), 100_000, 60_000.)
+///     .with_withdrawal(t0 + Duration::days(2), 150_000, 70_000.)
+///     .compute()?;
+///
+/// // 100k sats from the first lot and 50k sats from the second lot
+/// assert_eq!(report.realized().len(), 2);
+/// assert_eq!(report.open_lots()[0].sats(), 50_000);
+/// assert!((report.total_gain_usd() - 25.).abs() < 1e-9);
+/// # Ok::<(), lnm_sdk::rest::v3::tax::TaxLotError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TaxLotLedger {
+    method: LotMethod,
+    events: Vec<LotEvent>,
+}
+
+impl TaxLotLedger {
+    /// Creates an empty ledger using the given lot matching method.
+    pub fn new(method: LotMethod) -> Self {
+        Self {
+            method,
+            events: Vec::new(),
+        }
+    }
+
+    fn with_event(
+        mut self,
+        time: DateTime<Utc>,
+        source: LotSource,
+        sats: u64,
+        usd_price: f64,
+        acquire: bool,
+    ) -> Self {
+        if sats > 0 {
+            self.events.push(LotEvent {
+                time,
+                source,
+                sats,
+                usd_price,
+                acquire,
+            });
+        }
+        self
+    }
+
+    /// Adds a deposit of `sats`, acquired at a BTC price of `usd_price`.
+    pub fn with_deposit(self, time: DateTime<Utc>, sats: u64, usd_price: f64) -> Self {
+        self.with_event(time, LotSource::Deposit, sats, usd_price, true)
+    }
+
+    /// Adds a withdrawal of `sats`, disposed at a BTC price of `usd_price`.
+    pub fn with_withdrawal(self, time: DateTime<Utc>, sats: u64, usd_price: f64) -> Self {
+        self.with_event(time, LotSource::Withdrawal, sats, usd_price, false)
+    }
+
+    /// Adds a closed isolated trade. Trades that are not closed are ignored.
+    ///
+    /// The opening fee is disposed at the entry price when the trade was filled. The closing fee
+    /// and the realized PL are disposed (losses) or acquired (profits) at the exit price when the
+    /// trade was closed.
+    pub fn with_closed_trade(self, trade: &Trade) -> Result<Self, TaxLotError> {
+        if !trade.closed() {
+            return Ok(self);
+        }
+
+        let exit_price = trade
+            .exit_price()
+            .ok_or(TaxLotError::MissingExitPrice(trade.id()))?
+            .as_f64();
+        let closed_at = trade.closed_at().unwrap_or(trade.created_at());
+        let filled_at = trade.filled_at().unwrap_or(trade.created_at());
+        let entry_price = trade.entry_price().unwrap_or(trade.price()).as_f64();
+        let id = trade.id();
+
+        let pl = trade.pl().as_i64();
+        let pl_source = if pl >= 0 {
+            LotSource::TradeProfit(id)
+        } else {
+            LotSource::TradeLoss(id)
+        };
+
+        Ok(self
+            .with_event(
+                filled_at,
+                LotSource::TradingFee(id),
+                trade.opening_fee().as_u64(),
+                entry_price,
+                false,
+            )
+            .with_event(
+                closed_at,
+                LotSource::TradingFee(id),
+                trade.closing_fee().as_u64(),
+                exit_price,
+                false,
+            )
+            .with_event(closed_at, pl_source, pl.unsigned_abs(), exit_price, pl >= 0))
+    }
+
+    /// Matches disposals against held lots, chronologically.
+    pub fn compute(&self) -> Result<TaxLotReport, TaxLotError> {
+        let mut events = self.events.clone();
+        events.sort_by_key(|event| (event.time, !event.acquire));
+
+        let mut lots: VecDeque<TaxLot> = VecDeque::new();
+        let mut realized = Vec::new();
+
+        for event in events {
+            if event.acquire {
+                lots.push_back(TaxLot {
+                    acquired_at: event.time,
+                    source: event.source,
+                    sats: event.sats,
+                    usd_price: event.usd_price,
+                });
+                continue;
+            }
+
+            let mut remaining = event.sats;
+
+            while remaining > 0 {
+                let lot = match self.method {
+                    LotMethod::Fifo => lots.front_mut(),
+                    LotMethod::Lifo => lots.back_mut(),
+                };
+                let Some(lot) = lot else {
+                    return Err(TaxLotError::InsufficientLots {
+                        time: event.time,
+                        sats: event.sats,
+                        shortfall: remaining,
+                    });
+                };
+
+                let sats = remaining.min(lot.sats);
+                let cost_basis_usd = usd_value(sats, lot.usd_price);
+                let proceeds_usd = usd_value(sats, event.usd_price);
+
+                realized.push(RealizedGain {
+                    acquired_at: lot.acquired_at,
+                    disposed_at: event.time,
+                    acquisition_source: lot.source,
+                    disposal_source: event.source,
+                    sats,
+                    cost_basis_usd,
+                    proceeds_usd,
+                    gain_usd: proceeds_usd - cost_basis_usd,
+                });
+
+                lot.sats -= sats;
+                remaining -= sats;
+
+                if lot.sats == 0 {
+                    match self.method {
+                        LotMethod::Fifo => lots.pop_front(),
+                        LotMethod::Lifo => lots.pop_back(),
+                    };
+                }
+            }
+        }
+
+        Ok(TaxLotReport {
+            realized,
+            open_lots: lots.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{rest::v3::models::Sats, testing::fixtures};
+
+    #[test]
+    fn test_lifo_matches_latest_lot() {
+        let t0 = DateTime::from_timestamp(0, 0).unwrap();
+
+        let report = TaxLotLedger::new(LotMethod::Lifo)
+            .with_deposit(t0, 100_000, 50_000.)
+            .with_deposit(t0 + Duration::days(1), 100_000, 60_000.)
+            .with_withdrawal(t0 + Duration::days(2), 50_000, 70_000.)
+            .compute()
+            .unwrap();
+
+        assert_eq!(report.realized().len(), 1);
+        assert_eq!(report.realized()[0].acquired_at(), t0 + Duration::days(1));
+        assert!((report.realized()[0].gain_usd() - 5.).abs() < 1e-9);
+        assert_eq!(
+            report
+                .open_lots()
+                .iter()
+                .map(TaxLot::sats)
+                .collect::<Vec<_>>(),
+            vec![100_000, 50_000]
+        );
+    }
+
+    #[test]
+    fn test_insufficient_lots() {
+        let t0 = DateTime::from_timestamp(0, 0).unwrap();
+
+        let result = TaxLotLedger::new(LotMethod::Fifo)
+            .with_deposit(t0, 1_000, 50_000.)
+            .with_withdrawal(t0, 1_500, 50_000.)
+            .compute();
+
+        assert!(matches!(
+            result,
+            Err(TaxLotError::InsufficientLots { shortfall: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn test_closed_trade_events() {
+        let trade = fixtures::closed_trade();
+        let deposit_at = trade.created_at() - Duration::days(1);
+
+        let report = TaxLotLedger::new(LotMethod::Fifo)
+            .with_deposit(deposit_at, 1_000_000, 90_000.)
+            .with_closed_trade(&trade)
+            .unwrap()
+            .compute()
+            .unwrap();
+
+        let fees: u64 = report.realized().iter().map(RealizedGain::sats).sum();
+        assert_eq!(fees, (trade.opening_fee() + trade.closing_fee()).as_u64());
+
+        let held: u64 = report.open_lots().iter().map(TaxLot::sats).sum();
+        assert_eq!(
+            held as i64,
+            (Sats::new(1_000_000) + trade.pl() - trade.opening_fee() - trade.closing_fee())
+                .as_i64()
+        );
+
+        let serialized = serde_json::to_value(&report.realized()[0]).unwrap();
+        assert_eq!(serialized["disposalSource"]["type"], "tradingFee");
+    }
+}
@@ -0,0 +1,896 @@
+//! Retry-safe withdrawals, journaled so that a crashed process never pays twice on restart.
+//!
+//! [`WithdrawalJournal::submit`] durably records each withdrawal intent, keyed by an application
+//! chosen key (such as a payout ID), before sending it, and records the outcome once known.
+//! Failures are classified as either [not sent](WithdrawalError::NotSent), which are safe to
+//! retry, or as leaving the [outcome unknown](WithdrawalError::OutcomeUnknown). Intents with an
+//! unknown outcome, including those left in flight by a crash, are never sent again until
+//! [resolved](WithdrawalJournal::resolve).
+//!
+//! [`WithdrawalJournal::reconcile`] resolves unknown outcomes by looking up the withdrawal
+//! history: Lightning withdrawals are matched by the payment hash of their invoice, and on-chain
+//! withdrawals by their address and amount. Those it can't settle remain listed by
+//! [`WithdrawalJournal::unresolved`], to be checked on the LN Markets account (or with the
+//! recipient) and [resolved](WithdrawalJournal::resolve) manually.
+
+use std::{collections::HashSet, num::NonZeroU64, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::shared::{
+    clock::{Clock, SystemClock},
+    models::{address::BitcoinAddress, amount::Sats, invoice::Bolt11Invoice},
+    rest::error::RestApiError,
+};
+
+use super::{
+    models::{LightningWithdrawal, OnchainWithdrawal, Page},
+    repositories::WithdrawalsRepository,
+    state_store::{StateStore, StateStoreError},
+};
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum WithdrawalError {
+    #[error("Withdrawal record serialization failed. Error: {0}")]
+    RecordSerialize(#[source] serde_json::Error),
+
+    #[error("Withdrawal record `{key}` could not be parsed. Error: {e}")]
+    RecordParse {
+        key: String,
+        #[source]
+        e: serde_json::Error,
+    },
+
+    #[error("Withdrawal `{0}` was journaled with a different intent")]
+    IntentMismatch(String),
+
+    #[error("Withdrawal `{0}` has an unknown outcome, and must be resolved before being retried")]
+    Unresolved(String),
+
+    #[error("Withdrawal `{0}` was not found in the journal")]
+    NotFound(String),
+
+    #[error("Withdrawal was not sent, and is safe to retry. Error: {0}")]
+    NotSent(#[source] RestApiError),
+
+    #[error("Withdrawal `{key}` may have been sent, outcome unknown. Error: {e}")]
+    OutcomeUnknown {
+        key: String,
+        #[source]
+        e: RestApiError,
+    },
+
+    #[error("Withdrawal history lookup failed. Error: {0}")]
+    Lookup(#[source] RestApiError),
+
+    #[error(transparent)]
+    StateStore(#[from] StateStoreError),
+}
+
+pub type Result<T> = std::result::Result<T, WithdrawalError>;
+
+/// How far before the oldest unresolved record the withdrawal history is looked up, to account
+/// for slow requests and clock skew.
+const LOOKUP_MARGIN: chrono::Duration = chrono::Duration::minutes(10);
+
+/// Withdrawal to be sent, as recorded in the journal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "IntentRecord", try_from = "IntentRecord")]
+pub enum WithdrawalIntent {
+    /// Payment of a Lightning invoice.
+    Lightning {
+        invoice: Bolt11Invoice,
+        max_fees: Option<NonZeroU64>,
+    },
+    /// On-chain withdrawal of `amount` sats.
+    Onchain {
+        address: BitcoinAddress,
+        amount: NonZeroU64,
+    },
+}
+
+impl WithdrawalIntent {
+    async fn send(
+        self,
+        api: &dyn WithdrawalsRepository,
+    ) -> crate::shared::rest::error::Result<Withdrawal> {
+        match self {
+            Self::Lightning { invoice, max_fees } => api
+                .withdrawal_lightning(invoice, max_fees)
+                .await
+                .map(Withdrawal::Lightning),
+            Self::Onchain { address, amount } => api
+                .withdrawal_onchain(address, amount)
+                .await
+                .map(Withdrawal::Onchain),
+        }
+    }
+}
+
+impl WithdrawalIntent {
+    fn matches_lightning(&self, withdrawal: &LightningWithdrawal) -> bool {
+        match self {
+            Self::Lightning { invoice, .. } => invoice
+                .payment_hash()
+                .is_some_and(|hash| hash.eq_ignore_ascii_case(withdrawal.payment_hash())),
+            Self::Onchain { .. } => false,
+        }
+    }
+
+    fn matches_onchain(&self, withdrawal: &OnchainWithdrawal) -> bool {
+        match self {
+            Self::Onchain { address, amount } => {
+                address.to_string() == withdrawal.address()
+                    && Sats::from(amount.get()) == withdrawal.amount()
+            }
+            Self::Lightning { .. } => false,
+        }
+    }
+}
+
+/// Serialized representation of [`WithdrawalIntent`], with the invoice and address as strings.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "camelCase")]
+enum IntentRecord {
+    #[serde(rename_all = "camelCase")]
+    Lightning {
+        invoice: String,
+        max_fees: Option<NonZeroU64>,
+    },
+    Onchain {
+        address: String,
+        amount: NonZeroU64,
+    },
+}
+
+impl From<WithdrawalIntent> for IntentRecord {
+    fn from(intent: WithdrawalIntent) -> Self {
+        match intent {
+            WithdrawalIntent::Lightning { invoice, max_fees } => IntentRecord::Lightning {
+                invoice: invoice.to_string(),
+                max_fees,
+            },
+            WithdrawalIntent::Onchain { address, amount } => IntentRecord::Onchain {
+                address: address.to_string(),
+                amount,
+            },
+        }
+    }
+}
+
+impl TryFrom<IntentRecord> for WithdrawalIntent {
+    type Error = String;
+
+    fn try_from(record: IntentRecord) -> std::result::Result<Self, Self::Error> {
+        match record {
+            IntentRecord::Lightning { invoice, max_fees } => Ok(WithdrawalIntent::Lightning {
+                invoice: Bolt11Invoice::try_from(invoice).map_err(|e| e.to_string())?,
+                max_fees,
+            }),
+            IntentRecord::Onchain { address, amount } => Ok(WithdrawalIntent::Onchain {
+                address: BitcoinAddress::try_from(address).map_err(|e| e.to_string())?,
+                amount,
+            }),
+        }
+    }
+}
+
+/// Withdrawal accepted by the API.
+#[derive(Debug, Clone)]
+pub enum Withdrawal {
+    Lightning(LightningWithdrawal),
+    Onchain(OnchainWithdrawal),
+}
+
+impl Withdrawal {
+    /// ID assigned to the withdrawal by the API.
+    pub fn id(&self) -> Uuid {
+        match self {
+            Self::Lightning(withdrawal) => withdrawal.id(),
+            Self::Onchain(withdrawal) => withdrawal.id(),
+        }
+    }
+}
+
+/// State of a journaled withdrawal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WithdrawalStatus {
+    /// The intent was recorded, and the withdrawal is being sent. Found after a restart, the
+    /// withdrawal may or may not have been sent.
+    Pending,
+    /// The withdrawal was accepted by the API, or was [resolved](WithdrawalJournal::resolve) as
+    /// sent.
+    Sent { id: Option<Uuid> },
+    /// The withdrawal was not sent, or was [resolved](WithdrawalJournal::resolve) as not sent.
+    /// Safe to retry.
+    NotSent { reason: String },
+    /// The request failed after possibly reaching the server.
+    Unknown { reason: String },
+}
+
+impl WithdrawalStatus {
+    /// Returns `true` if the withdrawal may or may not have been sent.
+    pub fn is_unresolved(&self) -> bool {
+        matches!(self, Self::Pending | Self::Unknown { .. })
+    }
+}
+
+/// Journaled withdrawal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalRecord {
+    key: String,
+    intent: WithdrawalIntent,
+    status: WithdrawalStatus,
+    updated_at: DateTime<Utc>,
+}
+
+impl WithdrawalRecord {
+    /// Application chosen key of the withdrawal.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Withdrawal intent.
+    pub fn intent(&self) -> &WithdrawalIntent {
+        &self.intent
+    }
+
+    /// Current state of the withdrawal.
+    pub fn status(&self) -> &WithdrawalStatus {
+        &self.status
+    }
+
+    /// Timestamp when the status was last recorded.
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
+/// Outcome of [`WithdrawalJournal::submit`].
+#[derive(Debug, Clone)]
+pub enum WithdrawalOutcome {
+    /// The withdrawal was sent and accepted by the API.
+    Sent(Withdrawal),
+    /// The withdrawal had already been sent under the same key, and was not sent again. Holds
+    /// the withdrawal ID, if known.
+    AlreadySent(Option<Uuid>),
+}
+
+/// Manual resolution of a withdrawal with an unknown outcome, passed to
+/// [`WithdrawalJournal::resolve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WithdrawalResolution {
+    /// The withdrawal was found on the account. Holds its ID, if known.
+    Sent(Option<Uuid>),
+    /// The withdrawal was not found on the account, and may be retried.
+    NotSent,
+}
+
+/// Returns `true` if the error guarantees the withdrawal was not sent.
+///
+/// Errors raised while building the request, connection failures and client error responses
+/// (except timeouts, `408`) guarantee the withdrawal wasn't executed. Other transport errors,
+/// server errors and undecodable responses leave the outcome unknown.
+fn is_not_sent(error: &RestApiError) -> bool {
+    match error {
+        RestApiError::SendFailed { e, .. } => e.is_connect() || e.is_builder(),
+        RestApiError::ErrorResponse(response) => {
+            response.status.is_client_error() && response.status != StatusCode::REQUEST_TIMEOUT
+        }
+        RestApiError::UrlParse(_)
+        | RestApiError::InvalidHeaderValue(_)
+        | RestApiError::InvalidSecretHmac(_)
+        | RestApiError::MissingRequestCredentials
+        | RestApiError::MissingScope { .. }
+        | RestApiError::UnsupportedMethod(_)
+        | RestApiError::ExchangeInMaintenance { .. }
+        | RestApiError::RequestJsonSerializeFailed(_)
+        | RestApiError::RestApiV3(_) => true,
+        _ => false,
+    }
+}
+
+/// Write-ahead journal of withdrawals, persisted in a [`StateStore`].
+///
+/// Each withdrawal is recorded as JSON at `{prefix}{key}`. Submitting an already sent key
+/// returns [`WithdrawalOutcome::AlreadySent`] without sending it again, and submitting a key with
+/// an unknown outcome fails with [`WithdrawalError::Unresolved`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::rest::v3::{
+///     models::Bolt11Invoice,
+///     state_store::FileStateStore,
+///     withdrawal_journal::{WithdrawalError, WithdrawalIntent, WithdrawalJournal},
+/// };
+///
+/// let journal = WithdrawalJournal::new(FileStateStore::new("state"), "withdrawal/");
+///
+/// // On startup, look up withdrawals left in flight by a previous run
+/// journal.reconcile(rest.withdrawals.as_ref()).await?;
+/// for record in journal.unresolved().await? {
+///     println!("Check withdrawal {} before resolving it", record.key());
+/// }
+///
+/// let intent = WithdrawalIntent::Lightning {
+///     invoice: Bolt11Invoice::try_from("lnbc...")?,
+///     max_fees: None,
+/// };
+///
+/// match journal.submit(rest.withdrawals.as_ref(), "payout-42", intent).await {
+///     Ok(outcome) => println!("{outcome:?}"),
+///     Err(WithdrawalError::NotSent(e)) => println!("Not sent, safe to retry: {e}"),
+///     Err(e) => return Err(e.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct WithdrawalJournal<S: StateStore> {
+    store: S,
+    prefix: String,
+    clock: Arc<dyn Clock>,
+    not_found_grace: Duration,
+    lock: Mutex<()>,
+}
+
+impl<S: StateStore> WithdrawalJournal<S> {
+    /// Creates a journal keeping withdrawals in `store`, under keys starting with `prefix`.
+    pub fn new(store: S, prefix: impl Into<String>) -> Self {
+        Self {
+            store,
+            prefix: prefix.into(),
+            clock: Arc::new(SystemClock),
+            not_found_grace: Duration::from_secs(300),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Sets the clock used to timestamp records.
+    ///
+    /// Default: the system clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sets how long after its last update an unresolved withdrawal missing from the history is
+    /// [reconciled](Self::reconcile) as not sent. Until then, it is left unresolved, as the
+    /// request may still be in flight.
+    ///
+    /// Default: 5 minutes.
+    pub fn with_not_found_grace(mut self, grace: Duration) -> Self {
+        self.not_found_grace = grace;
+        self
+    }
+
+    /// Underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Prefix of the keys of the records.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the record of the withdrawal with `key`, if any.
+    pub async fn get(&self, key: &str) -> Result<Option<WithdrawalRecord>> {
+        let Some(value) = self.store.get(&format!("{}{key}", self.prefix)).await? else {
+            return Ok(None);
+        };
+
+        serde_json::from_slice(&value)
+            .map(Some)
+            .map_err(|e| WithdrawalError::RecordParse {
+                key: key.to_string(),
+                e,
+            })
+    }
+
+    async fn records(&self) -> Result<Vec<WithdrawalRecord>> {
+        let mut records = Vec::new();
+
+        for (key, value) in self.store.scan(&self.prefix).await? {
+            let record: WithdrawalRecord =
+                serde_json::from_slice(&value).map_err(|e| WithdrawalError::RecordParse {
+                    key: key[self.prefix.len()..].to_string(),
+                    e,
+                })?;
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
+    /// Returns the withdrawals that may or may not have been sent, oldest update first.
+    pub async fn unresolved(&self) -> Result<Vec<WithdrawalRecord>> {
+        let mut records: Vec<_> = self
+            .records()
+            .await?
+            .into_iter()
+            .filter(|record| record.status.is_unresolved())
+            .collect();
+
+        records.sort_by_key(|record| record.updated_at);
+
+        Ok(records)
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        intent: WithdrawalIntent,
+        status: WithdrawalStatus,
+    ) -> Result<WithdrawalRecord> {
+        let record = WithdrawalRecord {
+            key: key.to_string(),
+            intent,
+            status,
+            updated_at: self.clock.now(),
+        };
+
+        let value = serde_json::to_vec(&record).map_err(WithdrawalError::RecordSerialize)?;
+        self.store
+            .put(&format!("{}{key}", self.prefix), &value)
+            .await?;
+
+        Ok(record)
+    }
+
+    /// Sends the withdrawal through `api`, unless already sent under `key`.
+    ///
+    /// The intent is recorded before the request is sent, and the outcome once known. Keys
+    /// previously [not sent](WithdrawalStatus::NotSent) are retried. Reusing a key with a
+    /// different intent fails with [`WithdrawalError::IntentMismatch`].
+    pub async fn submit(
+        &self,
+        api: &dyn WithdrawalsRepository,
+        key: &str,
+        intent: WithdrawalIntent,
+    ) -> Result<WithdrawalOutcome> {
+        {
+            let _guard = self.lock.lock().await;
+
+            if let Some(record) = self.get(key).await? {
+                if record.intent != intent {
+                    return Err(WithdrawalError::IntentMismatch(key.to_string()));
+                }
+
+                match record.status {
+                    WithdrawalStatus::Sent { id } => return Ok(WithdrawalOutcome::AlreadySent(id)),
+                    WithdrawalStatus::NotSent { .. } => {}
+                    WithdrawalStatus::Pending | WithdrawalStatus::Unknown { .. } => {
+                        return Err(WithdrawalError::Unresolved(key.to_string()));
+                    }
+                }
+            }
+
+            self.put(key, intent.clone(), WithdrawalStatus::Pending)
+                .await?;
+        }
+
+        match intent.clone().send(api).await {
+            Ok(withdrawal) => {
+                let id = Some(withdrawal.id());
+                self.put(key, intent, WithdrawalStatus::Sent { id }).await?;
+
+                Ok(WithdrawalOutcome::Sent(withdrawal))
+            }
+            Err(e) if is_not_sent(&e) => {
+                let reason = e.to_string();
+                self.put(key, intent, WithdrawalStatus::NotSent { reason })
+                    .await?;
+
+                Err(WithdrawalError::NotSent(e))
+            }
+            Err(e) => {
+                let reason = e.to_string();
+                self.put(key, intent, WithdrawalStatus::Unknown { reason })
+                    .await?;
+
+                Err(WithdrawalError::OutcomeUnknown {
+                    key: key.to_string(),
+                    e,
+                })
+            }
+        }
+    }
+
+    /// Records the outcome of a withdrawal, after checking it on the account.
+    ///
+    /// Meant for [unresolved](Self::unresolved) withdrawals. Resolving as
+    /// [`NotSent`](WithdrawalResolution::NotSent) allows the key to be submitted again, so it
+    /// must only be done once the withdrawal is confirmed not to have been executed.
+    pub async fn resolve(
+        &self,
+        key: &str,
+        resolution: WithdrawalResolution,
+    ) -> Result<WithdrawalRecord> {
+        let _guard = self.lock.lock().await;
+
+        let record = self
+            .get(key)
+            .await?
+            .ok_or_else(|| WithdrawalError::NotFound(key.to_string()))?;
+
+        let status = match resolution {
+            WithdrawalResolution::Sent(id) => WithdrawalStatus::Sent { id },
+            WithdrawalResolution::NotSent => WithdrawalStatus::NotSent {
+                reason: "resolved as not sent".to_string(),
+            },
+        };
+
+        self.put(key, record.intent, status).await
+    }
+
+    /// Resolves [unresolved](Self::unresolved) withdrawals by looking them up in the withdrawal
+    /// history, returning the records that were resolved.
+    ///
+    /// Lightning withdrawals are matched by the payment hash of their invoice, and on-chain
+    /// withdrawals by their address and amount, ignoring withdrawals already journaled under
+    /// another key. Matches are recorded as [sent](WithdrawalStatus::Sent). Withdrawals missing
+    /// from the history are recorded as [not sent](WithdrawalStatus::NotSent) once the
+    /// [grace period](Self::with_not_found_grace) has elapsed since their last update, and are
+    /// otherwise left unresolved.
+    ///
+    /// **Required permissions**: `account:withdrawals:read`
+    pub async fn reconcile(
+        &self,
+        api: &dyn WithdrawalsRepository,
+    ) -> Result<Vec<WithdrawalRecord>> {
+        let _guard = self.lock.lock().await;
+
+        let records = self.records().await?;
+        let mut claimed: HashSet<Uuid> = records
+            .iter()
+            .filter_map(|record| match record.status {
+                WithdrawalStatus::Sent { id } => id,
+                _ => None,
+            })
+            .collect();
+
+        let mut unresolved: Vec<_> = records
+            .into_iter()
+            .filter(|record| record.status.is_unresolved())
+            .collect();
+        unresolved.sort_by_key(|record| record.updated_at);
+
+        let Some(oldest) = unresolved.first() else {
+            return Ok(Vec::new());
+        };
+        let from = Some(oldest.updated_at - LOOKUP_MARGIN);
+
+        let has_lightning = unresolved
+            .iter()
+            .any(|record| matches!(record.intent, WithdrawalIntent::Lightning { .. }));
+        let lightning = if has_lightning {
+            fetch_all(|cursor| api.get_lightning_withdrawals(from, None, None, cursor)).await?
+        } else {
+            Vec::new()
+        };
+
+        let has_onchain = unresolved
+            .iter()
+            .any(|record| matches!(record.intent, WithdrawalIntent::Onchain { .. }));
+        let onchain = if has_onchain {
+            fetch_all(|cursor| api.get_onchain_withdrawals(from, None, None, cursor)).await?
+        } else {
+            Vec::new()
+        };
+
+        let now = self.clock.now();
+        let mut resolved = Vec::new();
+
+        for record in unresolved {
+            let found = lightning
+                .iter()
+                .filter(|withdrawal| record.intent.matches_lightning(withdrawal))
+                .map(|withdrawal| withdrawal.id())
+                .chain(
+                    onchain
+                        .iter()
+                        .filter(|withdrawal| record.intent.matches_onchain(withdrawal))
+                        .map(|withdrawal| withdrawal.id()),
+                )
+                .find(|id| !claimed.contains(id));
+
+            let status = match found {
+                Some(id) => {
+                    claimed.insert(id);
+                    WithdrawalStatus::Sent { id: Some(id) }
+                }
+                None if (now - record.updated_at)
+                    .to_std()
+                    .is_ok_and(|elapsed| elapsed >= self.not_found_grace) =>
+                {
+                    WithdrawalStatus::NotSent {
+                        reason: "not found in the withdrawal history".to_string(),
+                    }
+                }
+                None => continue,
+            };
+
+            resolved.push(self.put(&record.key, record.intent, status).await?);
+        }
+
+        Ok(resolved)
+    }
+}
+
+/// Fetches every page of a withdrawal history, following the cursors.
+async fn fetch_all<T, F, Fut>(mut fetch: F) -> Result<Vec<T>>
+where
+    F: FnMut(Option<DateTime<Utc>>) -> Fut,
+    Fut: Future<Output = crate::shared::rest::error::Result<Page<T>>>,
+{
+    let mut items = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let page = fetch(cursor).await.map_err(WithdrawalError::Lookup)?;
+        let next_cursor = page.next_cursor();
+        let data = Vec::from(page);
+        let empty = data.is_empty();
+        items.extend(data);
+
+        match next_cursor {
+            Some(next) if !empty => cursor = Some(next),
+            _ => return Ok(items),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex as StdMutex;
+
+    use async_trait::async_trait;
+    use chrono::TimeDelta;
+    use http::Method;
+
+    use super::*;
+    use crate::shared::rest::error::ErrorResponseContext;
+    use crate::{
+        rest::v3::{
+            models::InternalWithdrawal,
+            state_store::{FileStateStore, MemoryStateStore},
+        },
+        testing::clock::MockClock,
+    };
+
+    const INVOICE: &str = "lnbc2500u1pvjluezsp5zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zygspp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdq5xysxxatsyp3k7enxv4jsxqzpu9qrsgquk0rl77nj30yxdy8j9vdx85fkpmdla2087ne0xh8nhedh8w27kyke0lp53ut353s06fv3qfegext0eh0ymjpf39tuven09sam30g4vgpfna3rh";
+
+    /// Withdrawals API returning the queued results, then succeeding, with a history of the
+    /// Lightning withdrawals it accepted.
+    #[derive(Default)]
+    struct FakeWithdrawals {
+        errors: StdMutex<Vec<RestApiError>>,
+        sent: StdMutex<u32>,
+        history: StdMutex<Vec<LightningWithdrawal>>,
+    }
+
+    impl crate::sealed::Sealed for FakeWithdrawals {}
+
+    #[async_trait]
+    impl WithdrawalsRepository for FakeWithdrawals {
+        async fn get_internal_withdrawals(
+            &self,
+            _from: Option<DateTime<Utc>>,
+            _to: Option<DateTime<Utc>>,
+            _limit: Option<NonZeroU64>,
+            _cursor: Option<DateTime<Utc>>,
+        ) -> crate::shared::rest::error::Result<Page<InternalWithdrawal>> {
+            unimplemented!()
+        }
+
+        async fn get_onchain_withdrawals(
+            &self,
+            _from: Option<DateTime<Utc>>,
+            _to: Option<DateTime<Utc>>,
+            _limit: Option<NonZeroU64>,
+            _cursor: Option<DateTime<Utc>>,
+        ) -> crate::shared::rest::error::Result<Page<OnchainWithdrawal>> {
+            Ok(Page::new(Vec::new(), None))
+        }
+
+        async fn get_lightning_withdrawals(
+            &self,
+            _from: Option<DateTime<Utc>>,
+            _to: Option<DateTime<Utc>>,
+            _limit: Option<NonZeroU64>,
+            _cursor: Option<DateTime<Utc>>,
+        ) -> crate::shared::rest::error::Result<Page<LightningWithdrawal>> {
+            Ok(Page::new(self.history.lock().unwrap().clone(), None))
+        }
+
+        async fn withdrawal_onchain(
+            &self,
+            _address: BitcoinAddress,
+            _amount: NonZeroU64,
+        ) -> crate::shared::rest::error::Result<OnchainWithdrawal> {
+            unimplemented!()
+        }
+
+        async fn withdrawal_lightning(
+            &self,
+            _invoice: Bolt11Invoice,
+            _max_fees: Option<NonZeroU64>,
+        ) -> crate::shared::rest::error::Result<LightningWithdrawal> {
+            *self.sent.lock().unwrap() += 1;
+
+            if let Some(e) = self.errors.lock().unwrap().pop() {
+                return Err(e);
+            }
+
+            Ok(lightning_withdrawal())
+        }
+    }
+
+    fn lightning_withdrawal() -> LightningWithdrawal {
+        serde_json::from_value(serde_json::json!({
+            "id": "00000000-0000-0000-0000-000000000001",
+            "amount": 250_000,
+            "maxFees": 1_000,
+            "paymentHash": "0001020304050607080900010203040506070809000102030405060708090102",
+            "createdAt": "2025-01-01T00:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    fn error_response(status: StatusCode) -> RestApiError {
+        RestApiError::ErrorResponse(Box::new(ErrorResponseContext {
+            method: Method::POST,
+            path: "/v3/account/withdrawals/lightning".to_string(),
+            status,
+            request_id: None,
+            correlation_id: None,
+            retry_after: None,
+            text: String::new(),
+        }))
+    }
+
+    fn intent() -> WithdrawalIntent {
+        WithdrawalIntent::Lightning {
+            invoice: Bolt11Invoice::try_from(INVOICE).unwrap(),
+            max_fees: NonZeroU64::new(1_000),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_withdrawal_journal_submit() {
+        let journal = WithdrawalJournal::new(MemoryStateStore::new(), "withdrawal/");
+        let api = FakeWithdrawals::default();
+        api.errors
+            .lock()
+            .unwrap()
+            .push(error_response(StatusCode::BAD_REQUEST));
+
+        // Rejected withdrawals are safe to retry
+        let result = journal.submit(&api, "payout-1", intent()).await;
+        assert!(matches!(result, Err(WithdrawalError::NotSent(_))));
+        assert!(journal.unresolved().await.unwrap().is_empty());
+
+        let outcome = journal.submit(&api, "payout-1", intent()).await.unwrap();
+        assert!(matches!(outcome, WithdrawalOutcome::Sent(_)));
+
+        // Already sent withdrawals are never sent again
+        let outcome = journal.submit(&api, "payout-1", intent()).await.unwrap();
+        assert!(matches!(
+            outcome,
+            WithdrawalOutcome::AlreadySent(Some(id)) if id == Uuid::from_u128(1)
+        ));
+        assert_eq!(*api.sent.lock().unwrap(), 2);
+
+        let other = WithdrawalIntent::Lightning {
+            invoice: Bolt11Invoice::try_from(INVOICE).unwrap(),
+            max_fees: None,
+        };
+        let result = journal.submit(&api, "payout-1", other).await;
+        assert!(matches!(result, Err(WithdrawalError::IntentMismatch(_))));
+    }
+
+    #[tokio::test]
+    async fn test_withdrawal_journal_unknown_outcome() {
+        let dir = std::env::temp_dir().join(format!("lnm-withdrawal-journal-{}", Uuid::new_v4()));
+        let journal = WithdrawalJournal::new(FileStateStore::new(&dir), "withdrawal/");
+        let api = FakeWithdrawals::default();
+        api.errors
+            .lock()
+            .unwrap()
+            .push(error_response(StatusCode::GATEWAY_TIMEOUT));
+
+        let result = journal.submit(&api, "payout-1", intent()).await;
+        assert!(matches!(
+            result,
+            Err(WithdrawalError::OutcomeUnknown { ref key, .. }) if key == "payout-1"
+        ));
+
+        // Reopened after a restart, the withdrawal is not sent again until resolved
+        let journal = WithdrawalJournal::new(FileStateStore::new(&dir), "withdrawal/");
+        let unresolved = journal.unresolved().await.unwrap();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].intent(), &intent());
+
+        let result = journal.submit(&api, "payout-1", intent()).await;
+        assert!(matches!(result, Err(WithdrawalError::Unresolved(_))));
+        assert_eq!(*api.sent.lock().unwrap(), 1);
+
+        journal
+            .resolve("payout-1", WithdrawalResolution::NotSent)
+            .await
+            .unwrap();
+        let outcome = journal.submit(&api, "payout-1", intent()).await.unwrap();
+        assert!(matches!(outcome, WithdrawalOutcome::Sent(_)));
+        assert!(journal.unresolved().await.unwrap().is_empty());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_withdrawal_journal_reconcile() {
+        let clock = MockClock::default();
+        let journal = WithdrawalJournal::new(MemoryStateStore::new(), "withdrawal/")
+            .with_clock(Arc::new(clock.clone()));
+        let api = FakeWithdrawals::default();
+        api.errors
+            .lock()
+            .unwrap()
+            .push(error_response(StatusCode::GATEWAY_TIMEOUT));
+
+        let result = journal.submit(&api, "payout-1", intent()).await;
+        assert!(matches!(
+            result,
+            Err(WithdrawalError::OutcomeUnknown { .. })
+        ));
+
+        // Missing from the history within the grace period, the withdrawal is left unresolved
+        let resolved = journal.reconcile(&api).await.unwrap();
+        assert!(resolved.is_empty());
+        assert_eq!(journal.unresolved().await.unwrap().len(), 1);
+
+        // Found by the payment hash of its invoice, the withdrawal is resolved as sent
+        api.history.lock().unwrap().push(lightning_withdrawal());
+        let resolved = journal.reconcile(&api).await.unwrap();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(
+            resolved[0].status(),
+            &WithdrawalStatus::Sent {
+                id: Some(Uuid::from_u128(1))
+            }
+        );
+
+        // A withdrawal already claimed by another key isn't matched again, and is resolved as
+        // not sent once the grace period elapses
+        api.errors
+            .lock()
+            .unwrap()
+            .push(error_response(StatusCode::GATEWAY_TIMEOUT));
+        let result = journal.submit(&api, "payout-2", intent()).await;
+        assert!(matches!(
+            result,
+            Err(WithdrawalError::OutcomeUnknown { .. })
+        ));
+
+        assert!(journal.reconcile(&api).await.unwrap().is_empty());
+
+        clock.advance(TimeDelta::minutes(5));
+        let resolved = journal.reconcile(&api).await.unwrap();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].key(), "payout-2");
+        assert!(matches!(
+            resolved[0].status(),
+            WithdrawalStatus::NotSent { .. }
+        ));
+        assert!(journal.unresolved().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,27 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+/// Source of the current time.
+///
+/// Wall-clock time used by the SDK, such as request signing timestamps, `Retry-After` dates,
+/// candle completeness and audit and journal records, is read through a `Clock`, so time-dependent
+/// logic can be tested deterministically by substituting a controllable implementation (e.g.
+/// `MockClock`, available with the `testing` feature).
+///
+/// Delays, such as rate limiter waits, are measured with Tokio's monotonic clock instead, and can
+/// be controlled in tests with `tokio::time::pause`.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// [`Clock`] reading the system time. Used by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
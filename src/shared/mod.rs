@@ -1,2 +1,5 @@
+pub(crate) mod clock;
 pub(crate) mod models;
+#[cfg(feature = "otel")]
+pub(crate) mod otel;
 pub(crate) mod rest;
@@ -0,0 +1,383 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+use super::{
+    bech32::{self, Bech32Variant},
+    error::BitcoinAddressValidationError,
+    network::BitcoinNetwork,
+};
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Maximum length of a segwit address, as per BIP-173.
+const SEGWIT_MAX_LEN: usize = 90;
+
+/// Networks an address can be used on. Testnet, signet and regtest share base58 version bytes,
+/// and testnet and signet share the segwit human-readable part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AddressNetworks {
+    Mainnet,
+    TestnetOrSignet,
+    TestnetSignetOrRegtest,
+    Regtest,
+}
+
+impl AddressNetworks {
+    fn contains(self, network: BitcoinNetwork) -> bool {
+        match self {
+            Self::Mainnet => network == BitcoinNetwork::Mainnet,
+            Self::TestnetOrSignet => {
+                matches!(network, BitcoinNetwork::Testnet | BitcoinNetwork::Signet)
+            }
+            Self::TestnetSignetOrRegtest => network != BitcoinNetwork::Mainnet,
+            Self::Regtest => network == BitcoinNetwork::Regtest,
+        }
+    }
+}
+
+/// A validated Bitcoin on-chain address.
+///
+/// Supports legacy base58check addresses (P2PKH and P2SH) and bech32/bech32m segwit addresses.
+/// Validation covers the encoding, checksum and script structure, as well as the networks the
+/// address belongs to, so it can be checked against the client's network with
+/// [`BitcoinAddress::is_valid_for_network`].
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{BitcoinAddress, BitcoinNetwork};
+///
+/// let address = BitcoinAddress::try_from("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").unwrap();
+/// assert!(address.is_valid_for_network(BitcoinNetwork::Mainnet));
+/// assert!(!address.is_valid_for_network(BitcoinNetwork::Testnet));
+///
+/// let address = BitcoinAddress::try_from("mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn").unwrap();
+/// assert!(address.is_valid_for_network(BitcoinNetwork::Testnet));
+///
+/// // Addresses with invalid checksums are rejected
+/// assert!(BitcoinAddress::try_from("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinAddress {
+    raw: String,
+    networks: AddressNetworks,
+}
+
+impl BitcoinAddress {
+    /// Returns the address as it was provided.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns whether the address can receive funds on the given network.
+    ///
+    /// Testnet and signet addresses share the same encoding, so they can't be told apart. Legacy
+    /// regtest addresses share the testnet encoding as well.
+    pub fn is_valid_for_network(&self, network: BitcoinNetwork) -> bool {
+        self.networks.contains(network)
+    }
+
+    fn parse_segwit(value: &str) -> Result<AddressNetworks, BitcoinAddressValidationError> {
+        if value.len() > SEGWIT_MAX_LEN {
+            return Err(BitcoinAddressValidationError::InvalidLength);
+        }
+
+        let decoded = bech32::decode(value)?;
+
+        let networks = match decoded.hrp.as_str() {
+            "bc" => AddressNetworks::Mainnet,
+            "tb" => AddressNetworks::TestnetOrSignet,
+            "bcrt" => AddressNetworks::Regtest,
+            _ => {
+                return Err(BitcoinAddressValidationError::UnknownPrefix {
+                    prefix: decoded.hrp,
+                });
+            }
+        };
+
+        let (&version, program) = decoded
+            .data
+            .split_first()
+            .ok_or(BitcoinAddressValidationError::InvalidWitnessProgram)?;
+
+        if version > 16 {
+            return Err(BitcoinAddressValidationError::InvalidWitnessVersion { version });
+        }
+
+        let expected_variant = if version == 0 {
+            Bech32Variant::Bech32
+        } else {
+            Bech32Variant::Bech32m
+        };
+        if decoded.variant != expected_variant {
+            return Err(BitcoinAddressValidationError::InvalidChecksumVariant);
+        }
+
+        let program = bech32::convert_bits(program, 5, 8, false)
+            .ok_or(BitcoinAddressValidationError::InvalidWitnessProgram)?;
+
+        let valid_len = match version {
+            0 => program.len() == 20 || program.len() == 32,
+            _ => (2..=40).contains(&program.len()),
+        };
+        if !valid_len {
+            return Err(BitcoinAddressValidationError::InvalidWitnessProgram);
+        }
+
+        Ok(networks)
+    }
+
+    fn parse_base58(value: &str) -> Result<AddressNetworks, BitcoinAddressValidationError> {
+        let payload = base58check_decode(value)?;
+
+        // 1 version byte followed by a 20 byte hash
+        if payload.len() != 21 {
+            return Err(BitcoinAddressValidationError::InvalidLength);
+        }
+
+        match payload[0] {
+            0x00 | 0x05 => Ok(AddressNetworks::Mainnet),
+            0x6f | 0xc4 => Ok(AddressNetworks::TestnetSignetOrRegtest),
+            version => Err(BitcoinAddressValidationError::UnknownVersion { version }),
+        }
+    }
+}
+
+fn base58check_decode(value: &str) -> Result<Vec<u8>, BitcoinAddressValidationError> {
+    // Big-endian base256 accumulator
+    let mut bytes: Vec<u8> = Vec::with_capacity(value.len());
+
+    for c in value.chars() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&x| x as char == c)
+            .ok_or(BitcoinAddressValidationError::InvalidBase58Character { c })?
+            as u32;
+
+        for byte in bytes.iter_mut().rev() {
+            carry += *byte as u32 * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, (carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    // Leading '1's encode leading zero bytes
+    let leading_zeros = value.chars().take_while(|&c| c == '1').count();
+    let mut decoded = vec![0; leading_zeros];
+    decoded.extend(bytes);
+
+    if decoded.len() < 4 {
+        return Err(BitcoinAddressValidationError::InvalidLength);
+    }
+
+    let (payload, checksum) = decoded.split_at(decoded.len() - 4);
+    let hash = Sha256::digest(Sha256::digest(payload));
+    if &hash[..4] != checksum {
+        return Err(BitcoinAddressValidationError::InvalidBase58Checksum);
+    }
+
+    Ok(payload.to_vec())
+}
+
+impl TryFrom<&str> for BitcoinAddress {
+    type Error = BitcoinAddressValidationError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let lowercase = value.to_ascii_lowercase();
+        let is_segwit = ["bc1", "tb1", "bcrt1"]
+            .iter()
+            .any(|prefix| lowercase.starts_with(prefix));
+
+        let networks = if is_segwit {
+            Self::parse_segwit(value)?
+        } else {
+            Self::parse_base58(value)?
+        };
+
+        Ok(Self {
+            raw: value.to_string(),
+            networks,
+        })
+    }
+}
+
+impl TryFrom<String> for BitcoinAddress {
+    type Error = BitcoinAddressValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+
+impl FromStr for BitcoinAddress {
+    type Err = BitcoinAddressValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl AsRef<str> for BitcoinAddress {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl fmt::Display for BitcoinAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.raw.fmt(f)
+    }
+}
+
+impl Serialize for BitcoinAddress {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segwit_address(
+        hrp: &str,
+        version: u8,
+        program_len: usize,
+        variant: Bech32Variant,
+    ) -> String {
+        let mut data = vec![version];
+        data.extend(bech32::convert_bits(&vec![0xab; program_len], 8, 5, true).unwrap());
+        bech32::encode(hrp, &data, variant)
+    }
+
+    #[test]
+    fn test_valid_segwit_addresses() {
+        let cases = [
+            (
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                BitcoinNetwork::Mainnet,
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                BitcoinNetwork::Testnet,
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                BitcoinNetwork::Mainnet,
+            ),
+            (
+                "bcrt1q6rhpng9evdsfnn833a4f4vej0asu6dk5srld6x",
+                BitcoinNetwork::Regtest,
+            ),
+        ];
+
+        for (value, network) in cases {
+            let address = BitcoinAddress::try_from(value).unwrap();
+            assert_eq!(address.as_str(), value);
+            assert!(address.is_valid_for_network(network), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_valid_base58_addresses() {
+        let cases = [
+            (
+                "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+                BitcoinNetwork::Mainnet,
+            ),
+            (
+                "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+                BitcoinNetwork::Mainnet,
+            ),
+            (
+                "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn",
+                BitcoinNetwork::Testnet,
+            ),
+            (
+                "2MzQwSSnBHWHqSAqtTVQ6v47XtaisrJa1Vc",
+                BitcoinNetwork::Regtest,
+            ),
+        ];
+
+        for (value, network) in cases {
+            let address = BitcoinAddress::try_from(value).unwrap();
+            assert!(address.is_valid_for_network(network), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_network_membership() {
+        let testnet = BitcoinAddress::try_from(
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+        )
+        .unwrap();
+        assert!(testnet.is_valid_for_network(BitcoinNetwork::Signet));
+        assert!(!testnet.is_valid_for_network(BitcoinNetwork::Mainnet));
+        assert!(!testnet.is_valid_for_network(BitcoinNetwork::Regtest));
+
+        let mainnet = BitcoinAddress::try_from("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2").unwrap();
+        assert!(!mainnet.is_valid_for_network(BitcoinNetwork::Testnet));
+
+        let regtest =
+            BitcoinAddress::try_from("bcrt1q6rhpng9evdsfnn833a4f4vej0asu6dk5srld6x").unwrap();
+        assert!(!regtest.is_valid_for_network(BitcoinNetwork::Testnet));
+    }
+
+    #[test]
+    fn test_invalid_base58_addresses() {
+        assert!(matches!(
+            BitcoinAddress::try_from("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3"),
+            Err(BitcoinAddressValidationError::InvalidBase58Checksum)
+        ));
+        assert!(matches!(
+            BitcoinAddress::try_from("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN0"),
+            Err(BitcoinAddressValidationError::InvalidBase58Character { c: '0' })
+        ));
+        assert!(BitcoinAddress::try_from("").is_err());
+    }
+
+    #[test]
+    fn test_invalid_segwit_addresses() {
+        // Segwit v1+ must use bech32m
+        let address = segwit_address("bc", 1, 32, Bech32Variant::Bech32);
+        assert!(matches!(
+            BitcoinAddress::try_from(address),
+            Err(BitcoinAddressValidationError::InvalidChecksumVariant)
+        ));
+
+        // Segwit v0 must use bech32
+        let address = segwit_address("bc", 0, 20, Bech32Variant::Bech32m);
+        assert!(matches!(
+            BitcoinAddress::try_from(address),
+            Err(BitcoinAddressValidationError::InvalidChecksumVariant)
+        ));
+
+        // Segwit v0 programs must be 20 or 32 bytes
+        let address = segwit_address("bc", 0, 24, Bech32Variant::Bech32);
+        assert!(matches!(
+            BitcoinAddress::try_from(address),
+            Err(BitcoinAddressValidationError::InvalidWitnessProgram)
+        ));
+
+        // Witness version must be at most 16
+        let address = segwit_address("bc", 17, 32, Bech32Variant::Bech32m);
+        assert!(matches!(
+            BitcoinAddress::try_from(address),
+            Err(BitcoinAddressValidationError::InvalidWitnessVersion { version: 17 })
+        ));
+
+        assert!(matches!(
+            BitcoinAddress::try_from("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"),
+            Err(BitcoinAddressValidationError::Bech32(_))
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{SATS_PER_BTC, price::Price};
+
+/// Amount in satoshis, as reported by the API for balances, margins, fees and PLs.
+///
+/// Signed, since PLs and funding fees can be negative.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{Price, Sats};
+///
+/// let pl = Sats::new(-1_500);
+/// let fee = Sats::from(500_u64);
+///
+/// assert_eq!(pl - fee, Sats::new(-2_000));
+/// assert_eq!(Sats::new(50_000_000).as_btc(), 0.5);
+/// assert_eq!(
+///     Sats::new(1_000_000).to_usd(Price::try_from(100_000).unwrap()).as_f64(),
+///     1_000.
+/// );
+/// ```
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Sats(i64);
+
+impl Sats {
+    pub const ZERO: Sats = Sats(0);
+
+    pub const fn new(sats: i64) -> Self {
+        Self(sats)
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.0
+    }
+
+    /// Returns the amount as `u64`, or `0` if negative.
+    pub fn as_u64(&self) -> u64 {
+        self.0.max(0) as u64
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.0 as f64
+    }
+
+    /// Returns the amount in BTC.
+    pub fn as_btc(&self) -> f64 {
+        self.0 as f64 / SATS_PER_BTC
+    }
+
+    /// Converts the amount to USD, given the BTC/USD `price`.
+    pub fn to_usd(self, price: Price) -> Usd {
+        Usd::new(self.as_btc() * price.as_f64())
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.0 > 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+}
+
+impl From<i64> for Sats {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<u64> for Sats {
+    /// Saturates at `i64::MAX`, far above the supply of BTC.
+    fn from(value: u64) -> Self {
+        Self(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<Sats> for i64 {
+    fn from(value: Sats) -> i64 {
+        value.0
+    }
+}
+
+impl From<Sats> for f64 {
+    fn from(value: Sats) -> f64 {
+        value.0 as f64
+    }
+}
+
+impl Add for Sats {
+    type Output = Sats;
+
+    fn add(self, rhs: Sats) -> Sats {
+        Sats(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Sats {
+    fn add_assign(&mut self, rhs: Sats) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Sats {
+    type Output = Sats;
+
+    fn sub(self, rhs: Sats) -> Sats {
+        Sats(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Sats {
+    fn sub_assign(&mut self, rhs: Sats) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Sats {
+    type Output = Sats;
+
+    fn neg(self) -> Sats {
+        Sats(-self.0)
+    }
+}
+
+impl Sum for Sats {
+    fn sum<I: Iterator<Item = Sats>>(iter: I) -> Sats {
+        Sats(iter.map(|sats| sats.0).sum())
+    }
+}
+
+impl fmt::Display for Sats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Amount in USD, as reported by the API for position quantities and synthetic USD balances.
+///
+/// Signed, since cross position quantities are negative when short.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{Price, Usd};
+///
+/// let balance = Usd::from_cents(123_456);
+///
+/// assert_eq!(balance.as_f64(), 1_234.56);
+/// assert_eq!(
+///     Usd::new(1_000.).to_sats(Price::try_from(100_000).unwrap()).as_i64(),
+///     1_000_000
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Usd(f64);
+
+impl Usd {
+    pub const ZERO: Usd = Usd(0.);
+
+    pub const fn new(usd: f64) -> Self {
+        Self(usd)
+    }
+
+    /// Creates an amount from a number of cents.
+    pub fn from_cents(cents: i64) -> Self {
+        Self(cents as f64 / 100.)
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns the amount in cents, rounded to the nearest cent.
+    pub fn as_cents(&self) -> i64 {
+        (self.0 * 100.).round() as i64
+    }
+
+    /// Converts the amount to sats, rounded down, given the BTC/USD `price`.
+    pub fn to_sats(self, price: Price) -> Sats {
+        Sats((self.0 / price.as_f64() * SATS_PER_BTC).floor() as i64)
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.0 > 0.
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0.
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+}
+
+impl From<i64> for Usd {
+    fn from(value: i64) -> Self {
+        Self(value as f64)
+    }
+}
+
+impl From<u64> for Usd {
+    fn from(value: u64) -> Self {
+        Self(value as f64)
+    }
+}
+
+impl From<Usd> for f64 {
+    fn from(value: Usd) -> f64 {
+        value.0
+    }
+}
+
+impl Add for Usd {
+    type Output = Usd;
+
+    fn add(self, rhs: Usd) -> Usd {
+        Usd(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Usd {
+    fn add_assign(&mut self, rhs: Usd) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Usd {
+    type Output = Usd;
+
+    fn sub(self, rhs: Usd) -> Usd {
+        Usd(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Usd {
+    fn sub_assign(&mut self, rhs: Usd) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Usd {
+    type Output = Usd;
+
+    fn neg(self) -> Usd {
+        Usd(-self.0)
+    }
+}
+
+impl Sum for Usd {
+    fn sum<I: Iterator<Item = Usd>>(iter: I) -> Usd {
+        Usd(iter.map(|usd| usd.0).sum())
+    }
+}
+
+impl fmt::Display for Usd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sats_and_usd() {
+        let price = Price::try_from(50_000).unwrap();
+
+        let total: Sats = [Sats::new(-100), Sats::from(300_u64)].into_iter().sum();
+        assert_eq!(total, Sats::new(200));
+        assert_eq!((-total).as_u64(), 0);
+        assert_eq!(Sats::from(u64::MAX).as_i64(), i64::MAX);
+        assert_eq!(Sats::new(2_000_000).to_usd(price), Usd::new(1_000.));
+        assert_eq!(serde_json::to_string(&Sats::new(-42)).unwrap(), "-42");
+
+        assert_eq!(Usd::from_cents(-1_050).as_f64(), -10.5);
+        assert_eq!(Usd::new(10.5).as_cents(), 1_050);
+        assert_eq!(Usd::new(1_000.).to_sats(price), Sats::new(2_000_000));
+        assert_eq!(Usd::from(-500_i64).abs(), Usd::new(500.));
+    }
+}
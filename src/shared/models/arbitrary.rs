@@ -0,0 +1,168 @@
+//! [`Arbitrary`] implementations for validated models, available with the `proptest` feature.
+//!
+//! Generated values always pass the validation of the corresponding type, so they can be used
+//! directly as inputs of downstream property tests.
+
+use proptest::{
+    arbitrary::Arbitrary,
+    prelude::{Just, any, prop_oneof},
+    strategy::{BoxedStrategy, Strategy},
+};
+
+use super::{
+    client_id::ClientId,
+    cross_leverage::CrossLeverage,
+    leverage::Leverage,
+    margin::Margin,
+    price::Price,
+    quantity::{cross::CrossQuantity, order::OrderQuantity},
+    trade::{TradeExecution, TradeSide, TradeSize},
+};
+
+impl Arbitrary for ClientId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        proptest::string::string_regex(&format!(
+            "[a-zA-Z0-9_-]{{{},{}}}",
+            ClientId::MIN_LEN,
+            ClientId::MAX_LEN
+        ))
+        .expect("client ID regex must be valid")
+        .prop_map(|s| ClientId::try_from(s).expect("generated client ID must be valid"))
+        .boxed()
+    }
+}
+
+impl Arbitrary for OrderQuantity {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (OrderQuantity::MIN.as_u64()..=OrderQuantity::MAX.as_u64())
+            .prop_map(|value| OrderQuantity::try_from(value).expect("must be valid quantity"))
+            .boxed()
+    }
+}
+
+impl Arbitrary for CrossQuantity {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (CrossQuantity::MIN.as_u64()..=CrossQuantity::HARD_MAX.as_u64())
+            .prop_map(|value| CrossQuantity::try_from(value).expect("must be valid quantity"))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Price {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let min_ticks = (Price::MIN.as_f64() / Price::TICK) as u64;
+        let max_ticks = (Price::MAX.as_f64() / Price::TICK) as u64;
+
+        (min_ticks..=max_ticks)
+            .prop_map(|ticks| {
+                Price::try_from(ticks as f64 * Price::TICK).expect("must be valid price")
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Margin {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (Margin::MIN.as_u64()..=Margin::MAX.as_u64())
+            .prop_map(|value| Margin::try_from(value).expect("must be valid margin"))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Leverage {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (Leverage::MIN.as_f64()..=Leverage::MAX.as_f64())
+            .prop_map(|value| Leverage::try_from(value).expect("must be valid leverage"))
+            .boxed()
+    }
+}
+
+impl Arbitrary for CrossLeverage {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (CrossLeverage::MIN.as_u64()..=CrossLeverage::MAX.as_u64())
+            .prop_map(|value| CrossLeverage::try_from(value).expect("must be valid leverage"))
+            .boxed()
+    }
+}
+
+impl Arbitrary for TradeSide {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![Just(TradeSide::Buy), Just(TradeSide::Sell)].boxed()
+    }
+}
+
+impl Arbitrary for TradeSize {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<OrderQuantity>().prop_map(TradeSize::Quantity),
+            any::<Margin>().prop_map(TradeSize::Margin),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for TradeExecution {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(TradeExecution::Market),
+            any::<Price>().prop_map(TradeExecution::Limit),
+        ]
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn arbitrary_values_round_trip_validation(
+            client_id in any::<ClientId>(),
+            quantity in any::<OrderQuantity>(),
+            cross_quantity in any::<CrossQuantity>(),
+            price in any::<Price>(),
+            leverage in any::<Leverage>(),
+            cross_leverage in any::<CrossLeverage>(),
+        ) {
+            prop_assert!(ClientId::try_from(client_id.as_str()).is_ok());
+            prop_assert!(OrderQuantity::try_from(quantity.as_u64()).is_ok());
+            prop_assert!(CrossQuantity::try_from(cross_quantity.as_u64()).is_ok());
+            prop_assert!(Price::try_from(price.as_f64()).is_ok());
+            prop_assert!(Leverage::try_from(leverage.as_f64()).is_ok());
+            prop_assert!(CrossLeverage::try_from(cross_leverage.as_u64()).is_ok());
+        }
+    }
+}
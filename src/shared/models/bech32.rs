@@ -0,0 +1,233 @@
+//! Minimal [BIP-173]/[BIP-350] decoding, without the 90 character limit of segwit addresses so it
+//! can also be used for BOLT11 invoices.
+//!
+//! [BIP-173]: https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+//! [BIP-350]: https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+
+use super::error::Bech32DecodeError;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+const GENERATOR: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+
+const CHECKSUM_LEN: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Bech32Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Bech32Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Self::Bech32 => 1,
+            Self::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+/// A checksum-verified bech32 string, split into its lowercase human-readable part and its data
+/// part as 5-bit values (checksum excluded).
+#[derive(Debug)]
+pub(crate) struct Bech32Data {
+    pub hrp: String,
+    pub data: Vec<u8>,
+    pub variant: Bech32Variant,
+}
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    let mut chk: u32 = 1;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes()
+        .map(|b| b >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|b| b & 0x1f))
+}
+
+pub(crate) fn decode(value: &str) -> Result<Bech32Data, Bech32DecodeError> {
+    let has_lower = value.bytes().any(|b| b.is_ascii_lowercase());
+    let has_upper = value.bytes().any(|b| b.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(Bech32DecodeError::MixedCase);
+    }
+
+    let value = value.to_ascii_lowercase();
+
+    let separator = value
+        .rfind('1')
+        .ok_or(Bech32DecodeError::MissingSeparator)?;
+
+    let (hrp, data_str) = (&value[..separator], &value[separator + 1..]);
+
+    if hrp.is_empty() {
+        return Err(Bech32DecodeError::EmptyHrp);
+    }
+
+    if let Some(c) = hrp.chars().find(|c| !(33..=126).contains(&(*c as u32))) {
+        return Err(Bech32DecodeError::InvalidCharacter { c });
+    }
+
+    if data_str.len() < CHECKSUM_LEN {
+        return Err(Bech32DecodeError::TooShort);
+    }
+
+    let mut data = data_str
+        .chars()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|&x| x as char == c)
+                .map(|pos| pos as u8)
+                .ok_or(Bech32DecodeError::InvalidCharacter { c })
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    let variant = match polymod(hrp_expand(hrp).chain(data.iter().copied())) {
+        c if c == Bech32Variant::Bech32.constant() => Bech32Variant::Bech32,
+        c if c == Bech32Variant::Bech32m.constant() => Bech32Variant::Bech32m,
+        _ => return Err(Bech32DecodeError::InvalidChecksum),
+    };
+
+    data.truncate(data.len() - CHECKSUM_LEN);
+
+    Ok(Bech32Data {
+        hrp: hrp.to_string(),
+        data,
+        variant,
+    })
+}
+
+/// Regroups `data` from `from`-bit values into `to`-bit values. Returns `None` if a value doesn't
+/// fit in `from` bits, or if `pad` is `false` and there are leftover non-zero or excess bits.
+pub(crate) fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value: u32 = (1 << to) - 1;
+    let mut res = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+
+    for &value in data {
+        let value = value as u32;
+        if value >> from != 0 {
+            return None;
+        }
+        acc = (acc << from) | value;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            res.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            res.push(((acc << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max_value) != 0 {
+        return None;
+    }
+
+    Some(res)
+}
+
+#[cfg(test)]
+pub(crate) fn encode(hrp: &str, data: &[u8], variant: Bech32Variant) -> String {
+    let checksum_input = hrp_expand(hrp)
+        .chain(data.iter().copied())
+        .chain([0; CHECKSUM_LEN]);
+    let checksum = polymod(checksum_input) ^ variant.constant();
+
+    let mut encoded = format!("{hrp}1");
+    for &value in data {
+        encoded.push(CHARSET[value as usize] as char);
+    }
+    for i in 0..CHECKSUM_LEN {
+        let value = (checksum >> (5 * (5 - i))) & 0x1f;
+        encoded.push(CHARSET[value as usize] as char);
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_bip173_vectors() {
+        let decoded = decode("A12UEL5L").unwrap();
+        assert_eq!(decoded.hrp, "a");
+        assert!(decoded.data.is_empty());
+        assert_eq!(decoded.variant, Bech32Variant::Bech32);
+
+        let decoded = decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw").unwrap();
+        assert_eq!(decoded.hrp, "abcdef");
+        assert_eq!(decoded.data, (0..32).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_decode_bip350_vector() {
+        let decoded = decode("a1lqfn3a").unwrap();
+        assert_eq!(decoded.hrp, "a");
+        assert_eq!(decoded.variant, Bech32Variant::Bech32m);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert!(matches!(
+            decode("A12uEL5L"),
+            Err(Bech32DecodeError::MixedCase)
+        ));
+        assert!(matches!(
+            decode("pzry9x0s0muk"),
+            Err(Bech32DecodeError::MissingSeparator)
+        ));
+        assert!(matches!(
+            decode("1pzry9x0s0muk"),
+            Err(Bech32DecodeError::EmptyHrp)
+        ));
+        assert!(matches!(
+            decode("a12uel5m"),
+            Err(Bech32DecodeError::InvalidChecksum)
+        ));
+        assert!(matches!(
+            decode("x1b4n0q5v"),
+            Err(Bech32DecodeError::InvalidCharacter { c: 'b' })
+        ));
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let data = [0, 14, 20, 15, 7, 13, 26, 0, 25, 18, 6, 11, 13, 8, 21];
+        let encoded = encode("tb", &data, Bech32Variant::Bech32m);
+        let decoded = decode(&encoded).unwrap();
+
+        assert_eq!(decoded.hrp, "tb");
+        assert_eq!(decoded.data, data);
+        assert_eq!(decoded.variant, Bech32Variant::Bech32m);
+    }
+
+    #[test]
+    fn test_convert_bits() {
+        let bytes = [0xff, 0x00, 0xab];
+        let u5 = convert_bits(&bytes, 8, 5, true).unwrap();
+        assert_eq!(convert_bits(&u5, 5, 8, false).unwrap(), bytes);
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::price::Price;
+
+/// Price series a [`PriceCondition`] is evaluated against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceReference {
+    /// Last traded price of the futures contract.
+    Last,
+    /// Index price, as computed by the oracle from spot exchanges. Used by LNM to trigger
+    /// liquidations, stoplosses and takeprofits.
+    Index,
+}
+
+impl PriceReference {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Last => "last",
+            Self::Index => "index",
+        }
+    }
+}
+
+impl fmt::Display for PriceReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How a [`PriceCondition`] compares prices to its level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceTrigger {
+    /// Met while the price is strictly above the level.
+    Above,
+    /// Met while the price is strictly below the level.
+    Below,
+    /// Met when the price moves from at or below the level to above it.
+    CrossesAbove,
+    /// Met when the price moves from at or above the level to below it.
+    CrossesBelow,
+    /// Met when the price crosses the level in either direction.
+    Crosses,
+}
+
+impl PriceTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Above => "above",
+            Self::Below => "below",
+            Self::CrossesAbove => "crosses above",
+            Self::CrossesBelow => "crosses below",
+            Self::Crosses => "crosses",
+        }
+    }
+}
+
+impl fmt::Display for PriceTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Condition on the [last or index](PriceReference) price, such as "index price crosses
+/// 100,000".
+///
+/// Level conditions ([`Above`](PriceTrigger::Above) and [`Below`](PriceTrigger::Below)) only
+/// depend on the current price. Crossing conditions also depend on the previous observed price,
+/// and are never met on the first observation.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{Price, PriceCondition, PriceReference};
+///
+/// let level = Price::try_from(100_000).unwrap();
+/// let condition = PriceCondition::crosses_above(PriceReference::Index, level);
+///
+/// let below = Price::try_from(99_500).unwrap();
+/// let above = Price::try_from(100_500).unwrap();
+///
+/// assert!(!condition.is_met(None, above));
+/// assert!(condition.is_met(Some(below), above));
+/// assert!(!condition.is_met(Some(above), above));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PriceCondition {
+    reference: PriceReference,
+    trigger: PriceTrigger,
+    level: Price,
+}
+
+impl PriceCondition {
+    pub fn new(reference: PriceReference, trigger: PriceTrigger, level: Price) -> Self {
+        Self {
+            reference,
+            trigger,
+            level,
+        }
+    }
+
+    pub fn above(reference: PriceReference, level: Price) -> Self {
+        Self::new(reference, PriceTrigger::Above, level)
+    }
+
+    pub fn below(reference: PriceReference, level: Price) -> Self {
+        Self::new(reference, PriceTrigger::Below, level)
+    }
+
+    pub fn crosses_above(reference: PriceReference, level: Price) -> Self {
+        Self::new(reference, PriceTrigger::CrossesAbove, level)
+    }
+
+    pub fn crosses_below(reference: PriceReference, level: Price) -> Self {
+        Self::new(reference, PriceTrigger::CrossesBelow, level)
+    }
+
+    pub fn crosses(reference: PriceReference, level: Price) -> Self {
+        Self::new(reference, PriceTrigger::Crosses, level)
+    }
+
+    /// Price series the condition is evaluated against.
+    pub fn reference(&self) -> PriceReference {
+        self.reference
+    }
+
+    pub fn trigger(&self) -> PriceTrigger {
+        self.trigger
+    }
+
+    pub fn level(&self) -> Price {
+        self.level
+    }
+
+    /// Returns whether the condition is met by `current`, given the `previous` observed price of
+    /// the [reference](Self::reference) series, if any.
+    pub fn is_met(&self, previous: Option<Price>, current: Price) -> bool {
+        let level = self.level.as_f64();
+        let current = current.as_f64();
+        let previous = previous.map(|price| price.as_f64());
+
+        let crossed_above = previous.is_some_and(|previous| previous <= level && current > level);
+        let crossed_below = previous.is_some_and(|previous| previous >= level && current < level);
+
+        match self.trigger {
+            PriceTrigger::Above => current > level,
+            PriceTrigger::Below => current < level,
+            PriceTrigger::CrossesAbove => crossed_above,
+            PriceTrigger::CrossesBelow => crossed_below,
+            PriceTrigger::Crosses => crossed_above || crossed_below,
+        }
+    }
+
+    pub fn as_data_str(&self) -> String {
+        format!(
+            "reference: {}\ntrigger: {}\nlevel: {}",
+            self.reference, self.trigger, self.level
+        )
+    }
+}
+
+impl fmt::Display for PriceCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Price Condition:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(value: i32) -> Price {
+        Price::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_price_condition_is_met() {
+        let level = price(100_000);
+        let reference = PriceReference::Last;
+
+        let above = PriceCondition::above(reference, level);
+        assert!(above.is_met(None, price(100_001)));
+        assert!(!above.is_met(None, level));
+
+        let below = PriceCondition::below(reference, level);
+        assert!(below.is_met(Some(price(100_001)), price(99_999)));
+        assert!(!below.is_met(None, level));
+
+        let crosses_above = PriceCondition::crosses_above(reference, level);
+        assert!(crosses_above.is_met(Some(level), price(100_001)));
+        assert!(!crosses_above.is_met(Some(price(100_002)), price(100_001)));
+        assert!(!crosses_above.is_met(Some(price(100_001)), price(99_999)));
+
+        let crosses_below = PriceCondition::crosses_below(reference, level);
+        assert!(crosses_below.is_met(Some(price(100_001)), price(99_999)));
+        assert!(!crosses_below.is_met(None, price(99_999)));
+
+        let crosses = PriceCondition::crosses(reference, level);
+        assert!(crosses.is_met(Some(price(99_999)), price(100_001)));
+        assert!(crosses.is_met(Some(price(100_001)), price(99_999)));
+        assert!(!crosses.is_met(Some(price(99_998)), price(99_999)));
+    }
+
+    #[test]
+    fn test_price_condition_serde() {
+        let condition = PriceCondition::crosses_below(PriceReference::Index, price(90_000));
+
+        let json = serde_json::to_value(condition).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "reference": "index",
+                "trigger": "crosses_below",
+                "level": 90_000,
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<PriceCondition>(json).unwrap(),
+            condition
+        );
+    }
+}
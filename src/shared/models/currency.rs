@@ -0,0 +1,127 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use super::{SATS_PER_BTC, error::CurrencyParseError, price::Price};
+
+/// Currency of a balance, deposit, withdrawal or swap.
+///
+/// LN Markets balances are held in BTC or in USD (synthetic USD). Conversions between them take an
+/// explicit BTC/USD rate, so callers decide which price (index, last price, swap quote) applies.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{Currency, Price};
+///
+/// let rate = Price::try_from(100_000).unwrap();
+///
+/// assert_eq!(Currency::Btc.convert(0.5, Currency::Usd, rate), 50_000.);
+/// assert_eq!(Currency::Usd.convert(1_000., Currency::Btc, rate), 0.01);
+/// assert_eq!(Currency::Usd.convert_to_sats(1_000., rate), 1_000_000.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Currency {
+    Btc,
+    Usd,
+}
+
+impl Currency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Currency::Btc => "btc",
+            Currency::Usd => "usd",
+        }
+    }
+
+    /// Converts an `amount` of this currency, in whole units (BTC or USD), to the `to` currency,
+    /// given the BTC/USD `rate`.
+    pub fn convert(self, amount: f64, to: Currency, rate: Price) -> f64 {
+        match (self, to) {
+            (Currency::Btc, Currency::Usd) => amount * rate.as_f64(),
+            (Currency::Usd, Currency::Btc) => amount / rate.as_f64(),
+            (Currency::Btc, Currency::Btc) | (Currency::Usd, Currency::Usd) => amount,
+        }
+    }
+
+    /// Converts an `amount` of this currency, in whole units (BTC or USD), to sats, given the
+    /// BTC/USD `rate`.
+    pub fn convert_to_sats(self, amount: f64, rate: Price) -> f64 {
+        self.convert(amount, Currency::Btc, rate) * SATS_PER_BTC
+    }
+
+    /// Converts an amount in sats to this currency, in whole units (BTC or USD), given the BTC/USD
+    /// `rate`.
+    pub fn convert_from_sats(self, sats: f64, rate: Price) -> f64 {
+        Currency::Btc.convert(sats / SATS_PER_BTC, self, rate)
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = CurrencyParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "btc" => Ok(Currency::Btc),
+            "usd" => Ok(Currency::Usd),
+            _ => Err(CurrencyParseError::Unknown {
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::from_str(&value).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currency_parse_and_convert() {
+        assert_eq!("btc".parse::<Currency>().unwrap(), Currency::Btc);
+        assert_eq!("USD".parse::<Currency>().unwrap(), Currency::Usd);
+        assert_eq!(
+            "eur".parse::<Currency>(),
+            Err(CurrencyParseError::Unknown {
+                value: "eur".to_string()
+            })
+        );
+        assert_eq!(serde_json::to_string(&Currency::Usd).unwrap(), r#""usd""#);
+        assert_eq!(
+            serde_json::from_str::<Currency>(r#""btc""#).unwrap(),
+            Currency::Btc
+        );
+
+        let rate = Price::try_from(50_000).unwrap();
+        assert_eq!(Currency::Btc.convert(2., Currency::Usd, rate), 100_000.);
+        assert_eq!(Currency::Usd.convert(25_000., Currency::Btc, rate), 0.5);
+        assert_eq!(Currency::Usd.convert(42., Currency::Usd, rate), 42.);
+        assert_eq!(Currency::Usd.convert_to_sats(500., rate), 1_000_000.);
+        assert_eq!(Currency::Usd.convert_from_sats(1_000_000., rate), 500.);
+        assert_eq!(Currency::Btc.convert_from_sats(1_000_000., rate), 0.01);
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::TimeDelta;
 use thiserror::Error;
 
 use super::{
@@ -17,6 +18,99 @@ pub enum OhlcRangeParseError {
     Unknown { value: String },
 }
 
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CurrencyParseError {
+    #[error("Unknown currency: {value}")]
+    Unknown { value: String },
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InstrumentParseError {
+    #[error("Unknown instrument: {value}")]
+    Unknown { value: String },
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ResampleValidationError {
+    #[error("Resampling interval must be positive. Value: {interval}")]
+    IntervalNotPositive { interval: TimeDelta },
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Bech32DecodeError {
+    #[error("Bech32 string must not mix upper and lower case")]
+    MixedCase,
+
+    #[error("Bech32 string is missing the '1' separator")]
+    MissingSeparator,
+
+    #[error("Bech32 human-readable part must not be empty")]
+    EmptyHrp,
+
+    #[error("Bech32 data part is too short to contain a checksum")]
+    TooShort,
+
+    #[error("Invalid bech32 character: {c:?}")]
+    InvalidCharacter { c: char },
+
+    #[error("Invalid bech32 checksum")]
+    InvalidChecksum,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum BitcoinAddressValidationError {
+    #[error("[Bech32Decode] {0}")]
+    Bech32(#[from] Bech32DecodeError),
+
+    #[error("Unknown segwit address prefix: {prefix}")]
+    UnknownPrefix { prefix: String },
+
+    #[error("Invalid witness version: {version}")]
+    InvalidWitnessVersion { version: u8 },
+
+    #[error("Invalid witness program")]
+    InvalidWitnessProgram,
+
+    #[error("Segwit v0 addresses must use bech32, and v1+ addresses bech32m")]
+    InvalidChecksumVariant,
+
+    #[error("Invalid base58 character: {c:?}")]
+    InvalidBase58Character { c: char },
+
+    #[error("Invalid base58 checksum")]
+    InvalidBase58Checksum,
+
+    #[error("Unknown base58 address version: {version:#04x}")]
+    UnknownVersion { version: u8 },
+
+    #[error("Invalid address length")]
+    InvalidLength,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Bolt11InvoiceValidationError {
+    #[error("[Bech32Decode] {0}")]
+    Bech32(#[from] Bech32DecodeError),
+
+    #[error("BOLT12 offers are not supported by the LN Markets API, a BOLT11 invoice is required")]
+    Bolt12NotSupported,
+
+    #[error("Invalid BOLT11 invoice prefix: {hrp}")]
+    InvalidPrefix { hrp: String },
+
+    #[error("Invalid BOLT11 invoice amount: {amount}")]
+    InvalidAmount { amount: String },
+
+    #[error("BOLT11 invoice data is truncated")]
+    Truncated,
+}
+
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum ClientIdValidationError {
@@ -249,6 +343,9 @@ pub enum TradeValidationError {
     #[error("Cash-in results in invalid leverage: {0}")]
     CashInInvalidLeverage(LeverageValidationError),
 
+    #[error("Target leverage ({target_leverage}) can't be reached by adding margin or cashing-in")]
+    TargetLeverageNotReachable { target_leverage: Leverage },
+
     #[error("Liquidation ({liquidation}) must be below price ({price}) for long positions")]
     LiquidationNotBelowPriceForLong { liquidation: Price, price: Price },
 
@@ -0,0 +1,128 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use super::error::InstrumentParseError;
+
+/// Market traded on LN Markets.
+///
+/// LN Markets currently lists a single market, BTC/USD inverse futures, but requests, Stream
+/// topics and updates can be matched against an instrument, so that code written against it keeps
+/// working as markets are added.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::Instrument;
+///
+/// let instrument = Instrument::default();
+///
+/// assert_eq!(instrument, Instrument::FuturesInverseBtcUsd);
+/// assert_eq!(instrument.to_string(), "futures/inverse/btc_usd");
+/// assert_eq!(instrument.pair(), "btc_usd");
+/// assert_eq!(Instrument::from_pair("btc_usd"), Some(instrument));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Instrument {
+    #[default]
+    FuturesInverseBtcUsd,
+}
+
+impl Instrument {
+    /// All the instruments currently supported by the SDK.
+    pub const ALL: &[Instrument] = &[Instrument::FuturesInverseBtcUsd];
+
+    /// Identifier of the instrument, also used as the prefix of its Stream topics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Instrument::FuturesInverseBtcUsd => "futures/inverse/btc_usd",
+        }
+    }
+
+    /// Pair of the instrument, as reported in the `pair` field of Stream updates.
+    pub fn pair(&self) -> &'static str {
+        match self {
+            Instrument::FuturesInverseBtcUsd => "btc_usd",
+        }
+    }
+
+    /// Returns the instrument with the given `pair`, if any.
+    pub fn from_pair(pair: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|instrument| instrument.pair() == pair)
+    }
+}
+
+impl fmt::Display for Instrument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Instrument {
+    type Err = InstrumentParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|instrument| instrument.as_str() == value)
+            .ok_or_else(|| InstrumentParseError::Unknown {
+                value: value.to_string(),
+            })
+    }
+}
+
+impl Serialize for Instrument {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Instrument {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::from_str(&value).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instrument_parse_and_serde() {
+        for instrument in Instrument::ALL {
+            assert_eq!(
+                instrument.as_str().parse::<Instrument>().unwrap(),
+                *instrument
+            );
+            assert_eq!(Instrument::from_pair(instrument.pair()), Some(*instrument));
+        }
+
+        assert_eq!(
+            "futures/inverse/eth_usd".parse::<Instrument>(),
+            Err(InstrumentParseError::Unknown {
+                value: "futures/inverse/eth_usd".to_string()
+            })
+        );
+        assert_eq!(Instrument::from_pair("eth_usd"), None);
+        assert_eq!(
+            serde_json::to_string(&Instrument::FuturesInverseBtcUsd).unwrap(),
+            r#""futures/inverse/btc_usd""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Instrument>(r#""futures/inverse/btc_usd""#).unwrap(),
+            Instrument::FuturesInverseBtcUsd
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    borrow::Borrow,
+    collections::HashSet,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
+
+/// Shared handle to a value stored in an [`IdInterner`].
+///
+/// Clones share storage. Handles from the same interner compare by pointer, falling back to
+/// comparing values for handles from different interners.
+pub struct Interned<T>(Arc<T>);
+
+impl<T> Interned<T> {
+    /// Returns `true` if both handles point to the same stored value.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<T> Clone for Interned<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for Interned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for Interned<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> Borrow<T> for Interned<T> {
+    fn borrow(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: PartialEq> PartialEq for Interned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other) || self.0 == other.0
+    }
+}
+
+impl<T: Eq> Eq for Interned<T> {}
+
+impl<T: Hash> Hash for Interned<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Deduplicates identifiers, such as trade IDs ([`Uuid`](uuid::Uuid)) and
+/// [`ClientId`](super::client_id::ClientId)s, so that repeated values share storage.
+///
+/// Useful for long-running processes that keep large trade and order histories in memory, where
+/// the same IDs are referenced by many records. Interning is opt-in: the interner is owned by the
+/// caller, and models keep their own copies of IDs unless interned explicitly.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{ClientId, IdInterner, Interned};
+///
+/// let mut interner = IdInterner::new();
+///
+/// let first = interner.intern(&ClientId::try_from("grid-level-3").unwrap());
+/// let second = interner.intern(&ClientId::try_from("grid-level-3").unwrap());
+///
+/// assert!(Interned::ptr_eq(&first, &second));
+/// assert_eq!(interner.len(), 1);
+/// ```
+pub struct IdInterner<T> {
+    values: HashSet<Arc<T>>,
+}
+
+impl<T: Eq + Hash + Clone> IdInterner<T> {
+    pub fn new() -> Self {
+        Self {
+            values: HashSet::new(),
+        }
+    }
+
+    /// Returns a handle to the stored copy of `value`, storing it if not yet interned.
+    pub fn intern(&mut self, value: &T) -> Interned<T> {
+        if let Some(existing) = self.values.get(value) {
+            return Interned(existing.clone());
+        }
+
+        let value = Arc::new(value.clone());
+        self.values.insert(value.clone());
+
+        Interned(value)
+    }
+
+    /// Returns a handle to the stored copy of `value`, if interned.
+    pub fn get(&self, value: &T) -> Option<Interned<T>> {
+        self.values.get(value).cloned().map(Interned)
+    }
+
+    /// Number of distinct values stored.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Drops stored values no longer referenced by any [`Interned`] handle.
+    pub fn purge(&mut self) {
+        self.values.retain(|value| Arc::strong_count(value) > 1);
+    }
+}
+
+impl<T: Eq + Hash + Clone> Default for IdInterner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for IdInterner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdInterner")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_interner_shares_storage() {
+        let mut interner = IdInterner::new();
+        let id = Uuid::new_v4();
+
+        let first = interner.intern(&id);
+        let second = interner.intern(&id);
+        let other = interner.intern(&Uuid::new_v4());
+
+        assert!(Interned::ptr_eq(&first, &second));
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(*first, id);
+        assert_eq!(interner.len(), 2);
+
+        // Handles from different interners still compare by value
+        let mut other_interner = IdInterner::new();
+        assert_eq!(other_interner.intern(&id), first);
+
+        drop(other);
+        interner.purge();
+        assert_eq!(interner.len(), 1);
+        assert!(interner.get(&id).is_some());
+    }
+}
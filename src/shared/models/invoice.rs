@@ -0,0 +1,475 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Serializer};
+
+use super::{
+    bech32::{self, Bech32Variant},
+    error::{Bech32DecodeError, Bolt11InvoiceValidationError},
+    network::BitcoinNetwork,
+};
+
+/// Number of 5-bit values holding the invoice timestamp.
+const TIMESTAMP_LEN: usize = 7;
+
+/// Number of 5-bit values holding the 65-byte recoverable signature.
+const SIGNATURE_LEN: usize = 104;
+
+/// Human-readable prefixes of BOLT12 offers, invoice requests and invoices.
+const BOLT12_PREFIXES: [&str; 3] = ["lno1", "lnr1", "lni1"];
+
+const TAG_PAYMENT_HASH: u8 = 1;
+const TAG_EXPIRY: u8 = 6;
+const TAG_DESCRIPTION: u8 = 13;
+
+/// A decoded [BOLT11] Lightning invoice.
+///
+/// Decoding extracts the fields needed to validate a withdrawal locally (network, amount and
+/// expiry) before the request is signed and sent. The invoice signature is **not** verified, the
+/// original string is kept and submitted as-is.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{BitcoinNetwork, Bolt11Invoice};
+///
+/// let invoice = Bolt11Invoice::try_from(
+///     "lnbc2500u1pvjluezsp5zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zygspp5qqqsyqcyq5rq\
+///      wzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdq5xysxxatsyp3k7enxv4jsxqzpu9qrsgquk0rl77nj30yx\
+///      dy8j9vdx85fkpmdla2087ne0xh8nhedh8w27kyke0lp53ut353s06fv3qfegext0eh0ymjpf39tuven09sam30\
+///      g4vgpfna3rh",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(invoice.network(), BitcoinNetwork::Mainnet);
+/// assert_eq!(invoice.amount_msat(), Some(250_000_000));
+/// assert_eq!(invoice.description(), Some("1 cup coffee"));
+/// assert_eq!(invoice.expiry().as_secs(), 60);
+///
+/// // Malformed invoices are rejected
+/// assert!(Bolt11Invoice::try_from("lnbc1invalid").is_err());
+/// ```
+///
+/// [BOLT11]: https://github.com/lightning/bolts/blob/master/11-payment-encoding.md
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bolt11Invoice {
+    raw: String,
+    network: BitcoinNetwork,
+    amount_msat: Option<u64>,
+    timestamp: DateTime<Utc>,
+    expiry: Duration,
+    payment_hash: Option<String>,
+    description: Option<String>,
+}
+
+impl Bolt11Invoice {
+    /// Expiry applied when the invoice doesn't specify one (1 hour).
+    pub const DEFAULT_EXPIRY: Duration = Duration::from_secs(3600);
+
+    /// Returns the invoice as it was provided.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the network the invoice is payable on.
+    pub fn network(&self) -> BitcoinNetwork {
+        self.network
+    }
+
+    /// Returns the requested amount in millisatoshis, or `None` for "any amount" invoices.
+    pub fn amount_msat(&self) -> Option<u64> {
+        self.amount_msat
+    }
+
+    /// Returns the requested amount in satoshis, rounded down, or `None` for "any amount"
+    /// invoices.
+    pub fn amount_sats(&self) -> Option<u64> {
+        self.amount_msat.map(|msat| msat / 1000)
+    }
+
+    /// Returns the invoice creation timestamp.
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Returns the invoice expiry, relative to its [`timestamp`](Self::timestamp).
+    pub fn expiry(&self) -> Duration {
+        self.expiry
+    }
+
+    /// Returns the time after which the invoice can no longer be paid.
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        let expiry = chrono::Duration::from_std(self.expiry).unwrap_or(chrono::Duration::MAX);
+
+        self.timestamp
+            .checked_add_signed(expiry)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+
+    /// Returns whether the invoice is already expired.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Utc::now())
+    }
+
+    /// Returns whether the invoice is expired at `now`, as read from a
+    /// [`Clock`](crate::rest::v3::Clock).
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at() <= now
+    }
+
+    /// Returns the hex-encoded payment hash, if present.
+    pub fn payment_hash(&self) -> Option<&str> {
+        self.payment_hash.as_deref()
+    }
+
+    /// Returns the invoice description, if present.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+fn parse_network_and_amount(
+    hrp: &str,
+) -> Result<(BitcoinNetwork, Option<u64>), Bolt11InvoiceValidationError> {
+    let currency_and_amount =
+        hrp.strip_prefix("ln")
+            .ok_or_else(|| Bolt11InvoiceValidationError::InvalidPrefix {
+                hrp: hrp.to_string(),
+            })?;
+
+    // Longer prefixes must be checked first, since "bc" is a prefix of "bcrt" and "tb" of "tbs"
+    let (network, amount_str) = [
+        ("bcrt", BitcoinNetwork::Regtest),
+        ("bc", BitcoinNetwork::Mainnet),
+        ("tbs", BitcoinNetwork::Signet),
+        ("tb", BitcoinNetwork::Testnet),
+    ]
+    .into_iter()
+    .find_map(|(prefix, network)| {
+        currency_and_amount
+            .strip_prefix(prefix)
+            .map(|amount_str| (network, amount_str))
+    })
+    .ok_or_else(|| Bolt11InvoiceValidationError::InvalidPrefix {
+        hrp: hrp.to_string(),
+    })?;
+
+    if amount_str.is_empty() {
+        return Ok((network, None));
+    }
+
+    let invalid_amount = || Bolt11InvoiceValidationError::InvalidAmount {
+        amount: amount_str.to_string(),
+    };
+
+    let (digits, multiplier) = match amount_str.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&amount_str[..i], Some(c)),
+        _ => (amount_str, None),
+    };
+
+    if digits.is_empty() || digits.starts_with('0') || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid_amount());
+    }
+
+    let value: u64 = digits.parse().map_err(|_| invalid_amount())?;
+
+    let amount_msat = match multiplier {
+        None => value.checked_mul(100_000_000_000),
+        Some('m') => value.checked_mul(100_000_000),
+        Some('u') => value.checked_mul(100_000),
+        Some('n') => value.checked_mul(100),
+        Some('p') if value.is_multiple_of(10) => Some(value / 10),
+        _ => None,
+    }
+    .ok_or_else(invalid_amount)?;
+
+    Ok((network, Some(amount_msat)))
+}
+
+fn u5_to_u64(values: &[u8]) -> u64 {
+    values
+        .iter()
+        .fold(0u64, |acc, &value| (acc << 5) | value as u64)
+}
+
+impl TryFrom<&str> for Bolt11Invoice {
+    type Error = Bolt11InvoiceValidationError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // BOLT12 strings are bech32-like but checksum-less, reject them explicitly instead of
+        // failing with an unhelpful checksum error
+        if BOLT12_PREFIXES
+            .iter()
+            .any(|prefix| value.to_ascii_lowercase().starts_with(prefix))
+        {
+            return Err(Bolt11InvoiceValidationError::Bolt12NotSupported);
+        }
+
+        let decoded = bech32::decode(value)?;
+        if decoded.variant != Bech32Variant::Bech32 {
+            return Err(Bech32DecodeError::InvalidChecksum.into());
+        }
+
+        let (network, amount_msat) = parse_network_and_amount(&decoded.hrp)?;
+
+        let data = decoded.data;
+        if data.len() < TIMESTAMP_LEN + SIGNATURE_LEN {
+            return Err(Bolt11InvoiceValidationError::Truncated);
+        }
+
+        let timestamp = DateTime::from_timestamp(u5_to_u64(&data[..TIMESTAMP_LEN]) as i64, 0)
+            .ok_or(Bolt11InvoiceValidationError::Truncated)?;
+
+        let mut expiry = Self::DEFAULT_EXPIRY;
+        let mut payment_hash = None;
+        let mut description = None;
+
+        let tagged_fields = &data[TIMESTAMP_LEN..data.len() - SIGNATURE_LEN];
+        let mut pos = 0;
+        while pos < tagged_fields.len() {
+            if pos + 3 > tagged_fields.len() {
+                return Err(Bolt11InvoiceValidationError::Truncated);
+            }
+
+            let tag = tagged_fields[pos];
+            let len = tagged_fields[pos + 1] as usize * 32 + tagged_fields[pos + 2] as usize;
+            pos += 3;
+
+            let field = tagged_fields
+                .get(pos..pos + len)
+                .ok_or(Bolt11InvoiceValidationError::Truncated)?;
+            pos += len;
+
+            // Unknown fields, and known fields with unexpected lengths, must be skipped
+            match tag {
+                TAG_PAYMENT_HASH if len == 52 => {
+                    payment_hash = bech32::convert_bits(field, 5, 8, false).map(hex::encode);
+                }
+                TAG_EXPIRY if len <= 12 => {
+                    expiry = Duration::from_secs(u5_to_u64(field));
+                }
+                TAG_DESCRIPTION => {
+                    description = bech32::convert_bits(field, 5, 8, false)
+                        .and_then(|bytes| String::from_utf8(bytes).ok());
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            raw: value.to_string(),
+            network,
+            amount_msat,
+            timestamp,
+            expiry,
+            payment_hash,
+            description,
+        })
+    }
+}
+
+impl TryFrom<String> for Bolt11Invoice {
+    type Error = Bolt11InvoiceValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+
+impl FromStr for Bolt11Invoice {
+    type Err = Bolt11InvoiceValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl AsRef<str> for Bolt11Invoice {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl fmt::Display for Bolt11Invoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.raw.fmt(f)
+    }
+}
+
+impl Serialize for Bolt11Invoice {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC_INVOICE: &str = "lnbc2500u1pvjluezsp5zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zygspp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdq5xysxxatsyp3k7enxv4jsxqzpu9qrsgquk0rl77nj30yxdy8j9vdx85fkpmdla2087ne0xh8nhedh8w27kyke0lp53ut353s06fv3qfegext0eh0ymjpf39tuven09sam30g4vgpfna3rh";
+
+    const SPEC_DONATION_INVOICE: &str = "lnbc1pvjluezsp5zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zygspp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdpl2pkx2ctnv5sxxmmwwd5kgetjypeh2ursdae8g6twvus8g6rfwvs8qun0dfjkxaq9qrsgq357wnc5r2ueh7ck6q93dj32dlqnls087fxdwk8qakdyafkq3yap9us6v52vjjsrvywa6rt52cm9r9zqt8r2t7mlcwspyetp5h2tztugp9lfyql";
+
+    /// Builds an unsigned invoice with the given hrp and an expiry field, for testing.
+    fn build_invoice(hrp: &str, timestamp: u64, expiry_secs: u64) -> String {
+        let mut data: Vec<u8> = (0..TIMESTAMP_LEN)
+            .rev()
+            .map(|i| ((timestamp >> (5 * i)) & 0x1f) as u8)
+            .collect();
+
+        let expiry: Vec<u8> = (0..4)
+            .rev()
+            .map(|i| ((expiry_secs >> (5 * i)) & 0x1f) as u8)
+            .collect();
+        data.extend([TAG_EXPIRY, 0, expiry.len() as u8]);
+        data.extend(expiry);
+
+        data.extend([0; SIGNATURE_LEN]);
+
+        bech32::encode(hrp, &data, Bech32Variant::Bech32)
+    }
+
+    #[test]
+    fn test_decode_spec_invoice() {
+        let invoice = Bolt11Invoice::try_from(SPEC_INVOICE).unwrap();
+
+        assert_eq!(invoice.as_str(), SPEC_INVOICE);
+        assert_eq!(invoice.network(), BitcoinNetwork::Mainnet);
+        assert_eq!(invoice.amount_msat(), Some(250_000_000));
+        assert_eq!(invoice.amount_sats(), Some(250_000));
+        assert_eq!(invoice.timestamp().timestamp(), 1_496_314_658);
+        assert_eq!(invoice.expiry(), Duration::from_secs(60));
+        assert_eq!(
+            invoice.payment_hash(),
+            Some("0001020304050607080900010203040506070809000102030405060708090102")
+        );
+        assert_eq!(invoice.description(), Some("1 cup coffee"));
+        assert!(invoice.is_expired());
+    }
+
+    #[test]
+    fn test_decode_spec_invoice_without_amount() {
+        let invoice = Bolt11Invoice::try_from(SPEC_DONATION_INVOICE).unwrap();
+
+        assert_eq!(invoice.amount_msat(), None);
+        assert_eq!(invoice.expiry(), Bolt11Invoice::DEFAULT_EXPIRY);
+    }
+
+    #[test]
+    fn test_decode_uppercase_invoice() {
+        let invoice = Bolt11Invoice::try_from(SPEC_INVOICE.to_uppercase()).unwrap();
+
+        assert_eq!(invoice.amount_msat(), Some(250_000_000));
+    }
+
+    #[test]
+    fn test_decode_networks() {
+        let cases = [
+            ("lnbc10n", BitcoinNetwork::Mainnet),
+            ("lntb10n", BitcoinNetwork::Testnet),
+            ("lntbs10n", BitcoinNetwork::Signet),
+            ("lnbcrt10n", BitcoinNetwork::Regtest),
+        ];
+
+        for (hrp, network) in cases {
+            let invoice = Bolt11Invoice::try_from(build_invoice(hrp, 1, 600)).unwrap();
+            assert_eq!(invoice.network(), network);
+            assert_eq!(invoice.amount_msat(), Some(1_000));
+            assert_eq!(invoice.expiry(), Duration::from_secs(600));
+        }
+    }
+
+    #[test]
+    fn test_decode_amounts() {
+        let cases = [
+            ("lnbc1", 100_000_000_000),
+            ("lnbc2m", 200_000_000),
+            ("lnbc25u", 2_500_000),
+            ("lnbc3n", 300),
+            ("lnbc10p", 1),
+        ];
+
+        for (hrp, amount_msat) in cases {
+            let invoice = Bolt11Invoice::try_from(build_invoice(hrp, 1, 600)).unwrap();
+            assert_eq!(invoice.amount_msat(), Some(amount_msat));
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid_amounts() {
+        for hrp in ["lnbc1p", "lnbc01u", "lnbcu", "lnbc1x"] {
+            let error = Bolt11Invoice::try_from(build_invoice(hrp, 1, 600)).unwrap_err();
+            assert!(
+                matches!(error, Bolt11InvoiceValidationError::InvalidAmount { .. }),
+                "{hrp}: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid_prefix() {
+        let error = Bolt11Invoice::try_from(build_invoice("lnxy10n", 1, 600)).unwrap_err();
+        assert!(matches!(
+            error,
+            Bolt11InvoiceValidationError::InvalidPrefix { .. }
+        ));
+
+        let error = Bolt11Invoice::try_from(build_invoice("bc10n", 1, 600)).unwrap_err();
+        assert!(matches!(
+            error,
+            Bolt11InvoiceValidationError::InvalidPrefix { .. }
+        ));
+    }
+
+    #[test]
+    fn test_decode_invalid_checksum() {
+        let mut invoice = SPEC_INVOICE.to_string();
+        invoice.pop();
+        invoice.push('q');
+
+        let error = Bolt11Invoice::try_from(invoice).unwrap_err();
+        assert!(matches!(
+            error,
+            Bolt11InvoiceValidationError::Bech32(Bech32DecodeError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn test_decode_rejects_bolt12_offer() {
+        let offer = "lno1pg257enxv4ezqcneype82um50ynhxgrwdajx283qfwdpl28qqmc78ymlvhmxcsywdk5wrjnj36jryg488qwlrnzyjczs";
+
+        let error = Bolt11Invoice::try_from(offer).unwrap_err();
+        assert!(matches!(
+            error,
+            Bolt11InvoiceValidationError::Bolt12NotSupported
+        ));
+    }
+
+    #[test]
+    fn test_decode_truncated() {
+        let invoice = bech32::encode("lnbc10n", &[0; 20], Bech32Variant::Bech32);
+
+        let error = Bolt11Invoice::try_from(invoice).unwrap_err();
+        assert!(matches!(error, Bolt11InvoiceValidationError::Truncated));
+    }
+
+    #[test]
+    fn test_expires_at() {
+        let invoice = Bolt11Invoice::try_from(build_invoice("lnbc10n", 1_000, 600)).unwrap();
+
+        assert_eq!(invoice.expires_at().timestamp(), 1_600);
+        assert!(!invoice.is_expired_at(DateTime::from_timestamp(1_599, 0).unwrap()));
+        assert!(invoice.is_expired_at(DateTime::from_timestamp(1_600, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_serialize_as_string() {
+        let invoice = Bolt11Invoice::try_from(SPEC_INVOICE).unwrap();
+
+        let json = serde_json::to_string(&invoice).unwrap();
+        assert_eq!(json, format!("\"{SPEC_INVOICE}\""));
+    }
+}
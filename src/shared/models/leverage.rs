@@ -115,6 +115,42 @@ impl Leverage {
 
         Self::try_from(leverage_value)
     }
+
+    /// Calculates the lowest leverage at which a position of `quantity` at `price` requires at most
+    /// `margin`.
+    ///
+    /// Unlike [`Leverage::try_calculate`], the result is guaranteed not to exceed the margin budget
+    /// once the margin is recomputed from it via [`Margin::calculate`], as done when opening a
+    /// trade sized by quantity. Useful for strategies sizing positions by margin budget rather than
+    /// by leverage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lnm_sdk::rest::v3::models::{Leverage, Margin, OrderQuantity, Price};
+    ///
+    /// let quantity = OrderQuantity::try_from(1_000).unwrap();
+    /// let budget = Margin::try_from(30_000).unwrap();
+    /// let price = Price::try_from(99_999.5).unwrap();
+    ///
+    /// let leverage = Leverage::try_from_margin_budget(quantity, budget, price).unwrap();
+    ///
+    /// assert!(Margin::calculate(quantity, price, leverage) <= budget);
+    /// ```
+    pub fn try_from_margin_budget(
+        quantity: OrderQuantity,
+        margin: Margin,
+        price: Price,
+    ) -> Result<Self, LeverageValidationError> {
+        let mut leverage = Self::try_calculate(quantity, margin, price)?;
+
+        // Floating point errors may leave the recomputed margin one sat above the budget
+        while Margin::calculate(quantity, price, leverage) > margin {
+            leverage = Self::try_from(leverage.0.next_up())?;
+        }
+
+        Ok(leverage)
+    }
 }
 
 impl From<Leverage> for f64 {
@@ -250,4 +286,30 @@ mod tests {
 
         assert!(matches!(error, LeverageValidationError::NotANumber));
     }
+
+    #[test]
+    fn test_try_from_margin_budget_never_exceeds_budget() {
+        let price = Price::try_from(99_999.5).unwrap();
+
+        for quantity in [1, 7, 333, 1_000, 12_345] {
+            let quantity = OrderQuantity::try_from(quantity).unwrap();
+
+            for budget in [1_001, 3_333, 99_999, 1_234_567] {
+                let budget = Margin::try_from(budget).unwrap();
+
+                match Leverage::try_from_margin_budget(quantity, budget, price) {
+                    Ok(leverage) => {
+                        assert!(Margin::calculate(quantity, price, leverage) <= budget);
+                        assert_eq!(
+                            OrderQuantity::try_calculate(budget, price, leverage).unwrap(),
+                            quantity
+                        );
+                    }
+                    Err(LeverageValidationError::TooLow { .. })
+                    | Err(LeverageValidationError::TooHigh { .. }) => {}
+                    Err(e) => panic!("unexpected error: {e}"),
+                }
+            }
+        }
+    }
 }
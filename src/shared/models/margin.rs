@@ -8,6 +8,8 @@ use super::{
     leverage::Leverage,
     price::Price,
     quantity::{Quantity, order::OrderQuantity},
+    rounding::RoundingPolicy,
+    serde_formats,
     trade::TradeSide,
 };
 
@@ -203,6 +205,9 @@ impl Margin {
     ///
     /// margin = (quantity * SATS_PER_BTC) / (price * leverage)
     ///
+    /// The margin is rounded with [`RoundingPolicy::EXCHANGE`].
+    /// [`RoundingPolicy::calculate_margin`] rounds it with other policies.
+    ///
     /// # Examples
     ///
     /// ```
@@ -215,9 +220,7 @@ impl Margin {
     /// let margin = Margin::calculate(quantity, price, leverage);
     /// ```
     pub fn calculate(quantity: OrderQuantity, price: Price, leverage: Leverage) -> Self {
-        let margin = quantity.as_f64() * (SATS_PER_BTC / (price.as_f64() * leverage.as_f64()));
-
-        Self::try_from(margin.ceil() as u64).expect("must result in valid `Margin`")
+        RoundingPolicy::EXCHANGE.calculate_margin(quantity, price, leverage)
     }
 
     /// Estimates margin from a target liquidation price.
@@ -490,7 +493,7 @@ impl<'de> Deserialize<'de> for Margin {
     where
         D: serde::Deserializer<'de>,
     {
-        let margin_u64 = u64::deserialize(deserializer)?;
+        let margin_u64: u64 = serde_formats::amount_string::deserialize(deserializer)?;
         Margin::try_from(margin_u64).map_err(|e| de::Error::custom(e.to_string()))
     }
 }
@@ -501,6 +504,18 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_margin_deserializes_numbers_and_strings() {
+        let expected = Margin::try_from(10_000).unwrap();
+
+        assert_eq!(serde_json::from_str::<Margin>("10000").unwrap(), expected);
+        assert_eq!(
+            serde_json::from_str::<Margin>("\"10000\"").unwrap(),
+            expected
+        );
+        assert!(serde_json::from_str::<Margin>("\"0\"").is_err());
+    }
+
     #[test]
     fn test_try_add_margin() {
         let base = Margin::try_from(10_000).unwrap();
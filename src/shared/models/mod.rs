@@ -1,15 +1,29 @@
 /// Number of satoshis (sats) in a Bitcoin: 100_000_000
 pub const SATS_PER_BTC: f64 = 100_000_000.;
 
+pub(crate) mod address;
+pub(crate) mod amount;
+#[cfg(feature = "proptest")]
+mod arbitrary;
+pub(crate) mod bech32;
 pub(crate) mod client_id;
+pub(crate) mod condition;
 pub(crate) mod cross_leverage;
+pub(crate) mod currency;
 pub(crate) mod error;
+pub(crate) mod instrument;
+pub(crate) mod interner;
+pub(crate) mod invoice;
 pub(crate) mod leverage;
 pub(crate) mod margin;
+pub(crate) mod network;
 pub(crate) mod ohlc;
 pub(crate) mod oracle;
 pub(crate) mod price;
 pub(crate) mod quantity;
+pub(crate) mod resample;
+pub(crate) mod rounding;
+pub mod serde_formats;
 pub(crate) mod serde_util;
 pub(crate) mod ticker;
 pub(crate) mod trade;
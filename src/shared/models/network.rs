@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// The Bitcoin network targeted by an LN Markets environment.
+///
+/// Used to validate network-dependent values, such as Lightning invoices, locally before they are
+/// submitted to the API.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::BitcoinNetwork;
+///
+/// assert_eq!(BitcoinNetwork::default(), BitcoinNetwork::Mainnet);
+/// assert_eq!(BitcoinNetwork::Testnet.to_string(), "testnet");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BitcoinNetwork {
+    #[default]
+    Mainnet,
+    Testnet,
+    Signet,
+    Regtest,
+}
+
+impl fmt::Display for BitcoinNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let network_str = match self {
+            BitcoinNetwork::Mainnet => "mainnet",
+            BitcoinNetwork::Testnet => "testnet",
+            BitcoinNetwork::Signet => "signet",
+            BitcoinNetwork::Regtest => "regtest",
+        };
+
+        write!(f, "{network_str}")
+    }
+}
@@ -128,7 +128,7 @@ impl<'de> Deserialize<'de> for OhlcRange {
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct OhlcCandle {
     #[serde(deserialize_with = "serde_util::datetime_rfc3339_or_millis::deserialize")]
     time: DateTime<Utc>,
@@ -35,6 +35,10 @@ pub struct Index {
 }
 
 impl Index {
+    pub(crate) fn new(time: DateTime<Utc>, index: Price) -> Self {
+        Self { time, index }
+    }
+
     /// Timestamp of the index data point.
     pub fn time(&self) -> DateTime<Utc> {
         self.time
@@ -85,10 +89,15 @@ impl fmt::Display for Index {
 pub struct LastPrice {
     #[serde(deserialize_with = "serde_util::datetime_rfc3339_or_millis::deserialize")]
     time: DateTime<Utc>,
+    #[serde(alias = "last_price")]
     last_price: Price,
 }
 
 impl LastPrice {
+    pub(crate) fn new(time: DateTime<Utc>, last_price: Price) -> Self {
+        Self { time, last_price }
+    }
+
     /// Timestamp of the last price data point.
     pub fn time(&self) -> DateTime<Utc> {
         self.time
@@ -0,0 +1,253 @@
+use std::fmt;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use super::{
+    error::ResampleValidationError,
+    ohlc::OhlcCandle,
+    oracle::{Index, LastPrice},
+    price::Price,
+};
+
+/// Timestamped price of a series that can be [`resample`]d.
+pub trait PriceSample {
+    fn time(&self) -> DateTime<Utc>;
+
+    fn price(&self) -> Price;
+}
+
+impl PriceSample for Index {
+    fn time(&self) -> DateTime<Utc> {
+        Index::time(self)
+    }
+
+    fn price(&self) -> Price {
+        self.index()
+    }
+}
+
+impl PriceSample for LastPrice {
+    fn time(&self) -> DateTime<Utc> {
+        LastPrice::time(self)
+    }
+
+    fn price(&self) -> Price {
+        self.last_price()
+    }
+}
+
+impl PriceSample for OhlcCandle {
+    fn time(&self) -> DateTime<Utc> {
+        OhlcCandle::time(self)
+    }
+
+    fn price(&self) -> Price {
+        self.close()
+    }
+}
+
+/// Price series bucket produced by [`resample`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResampledPrice {
+    time: DateTime<Utc>,
+    open: Price,
+    high: Price,
+    low: Price,
+    close: Price,
+    samples: usize,
+}
+
+impl ResampledPrice {
+    /// Start of the bucket.
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+
+    /// First price of the bucket.
+    pub fn open(&self) -> Price {
+        self.open
+    }
+
+    /// Highest price of the bucket.
+    pub fn high(&self) -> Price {
+        self.high
+    }
+
+    /// Lowest price of the bucket.
+    pub fn low(&self) -> Price {
+        self.low
+    }
+
+    /// Last price of the bucket, i.e. the price of the series as of the end of the bucket.
+    pub fn close(&self) -> Price {
+        self.close
+    }
+
+    /// Number of samples in the bucket. Zero for buckets filled forward from the previous close.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    pub fn as_data_str(&self) -> String {
+        format!(
+            "time: {}\nopen: {}\nhigh: {}\nlow: {}\nclose: {}\nsamples: {}",
+            self.time.to_rfc3339(),
+            self.open,
+            self.high,
+            self.low,
+            self.close,
+            self.samples
+        )
+    }
+}
+
+impl fmt::Display for ResampledPrice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Resampled Price:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Resamples a price series, such as index or last price history, to buckets of `interval`.
+///
+/// Buckets are aligned to multiples of `interval` since the Unix epoch, and span from the bucket
+/// of the earliest sample to the bucket of the latest one. Samples don't need to be sorted.
+/// Buckets without samples are filled forward with the previous close, so the resampled series
+/// is regular and can be looked up at any bucket, e.g. at funding settlement times.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) -> Result<(), Box<dyn std::error::Error>> {
+/// use chrono::{TimeDelta, Utc};
+/// use lnm_sdk::rest::v3::{history::HistoryDownloader, models::resample};
+///
+/// let to = Utc::now();
+/// let index = HistoryDownloader::new(rest)
+///     .index(to - TimeDelta::days(7), to)
+///     .await?;
+///
+/// for bucket in resample(&index, TimeDelta::hours(8))? {
+///     println!("{}: {}", bucket.time(), bucket.close());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn resample<S: PriceSample>(
+    samples: &[S],
+    interval: TimeDelta,
+) -> Result<Vec<ResampledPrice>, ResampleValidationError> {
+    let interval_ms = interval.num_milliseconds();
+    if interval_ms <= 0 {
+        return Err(ResampleValidationError::IntervalNotPositive { interval });
+    }
+
+    let bucket_of = |time: DateTime<Utc>| time.timestamp_millis().div_euclid(interval_ms);
+
+    let mut sorted: Vec<&S> = samples.iter().collect();
+    sorted.sort_by_key(|sample| sample.time());
+
+    let mut buckets: Vec<ResampledPrice> = Vec::new();
+    let mut current_bucket = None;
+
+    for sample in sorted {
+        let bucket = bucket_of(sample.time());
+        let price = sample.price();
+
+        if current_bucket == Some(bucket) {
+            let last = buckets.last_mut().expect("current bucket must exist");
+            last.high = last.high.max(price);
+            last.low = last.low.min(price);
+            last.close = price;
+            last.samples += 1;
+            continue;
+        }
+
+        if let (Some(previous), Some(last)) = (current_bucket, buckets.last().copied()) {
+            for gap in previous + 1..bucket {
+                buckets.push(ResampledPrice {
+                    time: bucket_time(gap, interval_ms),
+                    open: last.close,
+                    high: last.close,
+                    low: last.close,
+                    close: last.close,
+                    samples: 0,
+                });
+            }
+        }
+
+        buckets.push(ResampledPrice {
+            time: bucket_time(bucket, interval_ms),
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            samples: 1,
+        });
+        current_bucket = Some(bucket);
+    }
+
+    Ok(buckets)
+}
+
+fn bucket_time(bucket: i64, interval_ms: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(bucket * interval_ms)
+        .expect("must be a valid timestamp, as it is within the range of the samples")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn index(time: &str, value: f64) -> Index {
+        serde_json::from_value(json!({ "time": time, "index": value })).unwrap()
+    }
+
+    fn price(value: f64) -> Price {
+        Price::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_resample() {
+        let samples = vec![
+            index("2025-01-01T00:40:00.000Z", 101_000.),
+            index("2025-01-01T00:10:00.000Z", 100_000.),
+            index("2025-01-01T00:20:00.000Z", 99_000.),
+            index("2025-01-01T03:05:00.000Z", 102_000.),
+        ];
+
+        let buckets = resample(&samples, TimeDelta::hours(1)).unwrap();
+        assert_eq!(buckets.len(), 4);
+
+        let first = buckets[0];
+        assert_eq!(first.time().to_rfc3339(), "2025-01-01T00:00:00+00:00");
+        assert_eq!(first.open(), price(100_000.));
+        assert_eq!(first.high(), price(101_000.));
+        assert_eq!(first.low(), price(99_000.));
+        assert_eq!(first.close(), price(101_000.));
+        assert_eq!(first.samples(), 3);
+
+        // Gaps are filled forward with the previous close
+        assert_eq!(buckets[1].samples(), 0);
+        assert_eq!(buckets[2].open(), price(101_000.));
+        assert_eq!(buckets[2].close(), price(101_000.));
+
+        assert_eq!(buckets[3].time().to_rfc3339(), "2025-01-01T03:00:00+00:00");
+        assert_eq!(buckets[3].close(), price(102_000.));
+
+        assert!(
+            resample::<Index>(&[], TimeDelta::hours(1))
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            resample(&samples, TimeDelta::zero()),
+            Err(ResampleValidationError::IntervalNotPositive { .. })
+        ));
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    SATS_PER_BTC,
+    error::PriceValidationError,
+    leverage::Leverage,
+    margin::Margin,
+    price::{PercentageCapped, Price},
+    quantity::{Quantity, order::OrderQuantity},
+    trade::TradeSide,
+};
+
+/// Direction in which a fractional value is rounded to a whole unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingMode {
+    /// Towards positive infinity.
+    Up,
+    /// Towards negative infinity.
+    Down,
+    /// To the nearest whole unit, half away from zero.
+    Nearest,
+}
+
+impl RoundingMode {
+    /// Rounds `value` to a whole number.
+    pub fn apply(&self, value: f64) -> f64 {
+        match self {
+            Self::Up => value.ceil(),
+            Self::Down => value.floor(),
+            Self::Nearest => value.round(),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Nearest => "nearest",
+        }
+    }
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Rounding applied to each kind of amount computed by the SDK's calculators.
+///
+/// Margins, fees and PL are rounded to whole satoshis, and prices to multiples of
+/// [`Price::TICK`]. Two presets are provided:
+///
+/// + [`EXCHANGE`](Self::EXCHANGE) (default), matching the rounding applied by LNM, so estimates
+///   match the values reported by the platform.
+/// + [`CONSERVATIVE`](Self::CONSERVATIVE), rounding every amount against the trader, so
+///   estimates never overstate the funds available.
+///
+/// The policy used by a [`RestClient`](crate::rest::v3::RestClient) is set with
+/// [`RestClientConfig::with_rounding_policy`](crate::rest::v3::RestClientConfig::with_rounding_policy).
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::models::{
+///     OrderQuantity, PercentageCapped, Price, RoundingMode, RoundingPolicy,
+/// };
+///
+/// let quantity = OrderQuantity::try_from(1_000).unwrap();
+/// let price = Price::try_from(99_999.5).unwrap();
+/// let fee_perc = PercentageCapped::try_from(0.1).unwrap();
+///
+/// // 1,000.005 sats
+/// assert_eq!(RoundingPolicy::EXCHANGE.order_fee(fee_perc, quantity, price), 1_000);
+/// assert_eq!(RoundingPolicy::CONSERVATIVE.order_fee(fee_perc, quantity, price), 1_001);
+///
+/// let policy = RoundingPolicy::EXCHANGE.with_fee(RoundingMode::Nearest);
+/// assert_eq!(policy.order_fee(fee_perc, quantity, price), 1_000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RoundingPolicy {
+    margin: RoundingMode,
+    fee: RoundingMode,
+    pl: RoundingMode,
+    price: RoundingMode,
+}
+
+impl RoundingPolicy {
+    /// Rounds margins up, fees down, PL down and prices to the nearest tick, as LNM does.
+    pub const EXCHANGE: Self = Self {
+        margin: RoundingMode::Up,
+        fee: RoundingMode::Down,
+        pl: RoundingMode::Down,
+        price: RoundingMode::Nearest,
+    };
+
+    /// Rounds margins up, fees up, PL down and prices to the nearest tick.
+    pub const CONSERVATIVE: Self = Self {
+        margin: RoundingMode::Up,
+        fee: RoundingMode::Up,
+        pl: RoundingMode::Down,
+        price: RoundingMode::Nearest,
+    };
+
+    /// Rounding of margins, in sats.
+    pub fn margin(&self) -> RoundingMode {
+        self.margin
+    }
+
+    /// Rounding of trading fees, in sats.
+    pub fn fee(&self) -> RoundingMode {
+        self.fee
+    }
+
+    /// Rounding of profits and losses, in sats.
+    pub fn pl(&self) -> RoundingMode {
+        self.pl
+    }
+
+    /// Rounding of prices, to multiples of [`Price::TICK`].
+    pub fn price(&self) -> RoundingMode {
+        self.price
+    }
+
+    pub fn with_margin(mut self, mode: RoundingMode) -> Self {
+        self.margin = mode;
+        self
+    }
+
+    pub fn with_fee(mut self, mode: RoundingMode) -> Self {
+        self.fee = mode;
+        self
+    }
+
+    pub fn with_pl(mut self, mode: RoundingMode) -> Self {
+        self.pl = mode;
+        self
+    }
+
+    pub fn with_price(mut self, mode: RoundingMode) -> Self {
+        self.price = mode;
+        self
+    }
+
+    /// Rounds a margin value to whole sats.
+    pub fn round_margin(&self, sats: f64) -> u64 {
+        self.margin.apply(sats).max(0.) as u64
+    }
+
+    /// Rounds a fee value to whole sats.
+    pub fn round_fee(&self, sats: f64) -> u64 {
+        self.fee.apply(sats).max(0.) as u64
+    }
+
+    /// Rounds a PL value to whole sats.
+    pub fn round_pl(&self, sats: f64) -> i64 {
+        self.pl.apply(sats) as i64
+    }
+
+    /// Rounds a value to a multiple of [`Price::TICK`], returning an error if the result is not a
+    /// valid price.
+    pub fn round_price(&self, value: f64) -> Result<Price, PriceValidationError> {
+        match self.price {
+            RoundingMode::Up => Price::round_up(value),
+            RoundingMode::Down => Price::round_down(value),
+            RoundingMode::Nearest => Price::round(value),
+        }
+    }
+
+    /// Calculates the margin for a position, as
+    /// [`Margin::calculate`] does, rounded with this policy.
+    ///
+    /// The result is at least [`Margin::MIN`], since margins below 1 sat can round to zero with
+    /// [`RoundingMode::Down`] or [`RoundingMode::Nearest`].
+    pub fn calculate_margin(
+        &self,
+        quantity: OrderQuantity,
+        price: Price,
+        leverage: Leverage,
+    ) -> Margin {
+        let margin = quantity.as_f64() * (SATS_PER_BTC / (price.as_f64() * leverage.as_f64()));
+        let margin = self.round_margin(margin).max(u64::from(Margin::MIN));
+
+        Margin::try_from(margin).expect("must result in valid `Margin`")
+    }
+
+    /// Calculates the trading fee for an order at `order_price`, rounded with this policy.
+    pub fn order_fee(
+        &self,
+        fee_perc: PercentageCapped,
+        quantity: impl Quantity,
+        order_price: Price,
+    ) -> u64 {
+        let fee_calc = SATS_PER_BTC * fee_perc.as_f64() / 100.;
+        self.round_fee(fee_calc * quantity.as_f64() / order_price.as_f64())
+    }
+
+    /// Estimates the PL of a position between two prices, rounded with this policy.
+    pub fn estimate_pl(
+        &self,
+        side: TradeSide,
+        quantity: impl Quantity,
+        start_price: Price,
+        end_price: Price,
+    ) -> i64 {
+        self.round_pl(super::trade::util::estimate_pl(
+            side,
+            quantity,
+            start_price,
+            end_price,
+        ))
+    }
+}
+
+impl Default for RoundingPolicy {
+    fn default() -> Self {
+        Self::EXCHANGE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounding_policy_presets() {
+        let quantity = OrderQuantity::try_from(1_000).unwrap();
+        let leverage = Leverage::try_from(3).unwrap();
+        let start = Price::try_from(100_000).unwrap();
+        let end = Price::try_from(99_999.5).unwrap();
+
+        // 333,333.33 sats
+        let margin = RoundingPolicy::EXCHANGE.calculate_margin(quantity, start, leverage);
+        assert_eq!(u64::from(margin), 333_334);
+        let margin = RoundingPolicy::EXCHANGE
+            .with_margin(RoundingMode::Down)
+            .calculate_margin(quantity, start, leverage);
+        assert_eq!(u64::from(margin), 333_333);
+
+        // 0.01 sats
+        let quantity_min = OrderQuantity::try_from(1).unwrap();
+        let price_max = Price::try_from(10_000_000).unwrap();
+        let leverage_max = Leverage::try_from(100).unwrap();
+        for mode in [RoundingMode::Down, RoundingMode::Nearest] {
+            let margin = RoundingPolicy::EXCHANGE.with_margin(mode).calculate_margin(
+                quantity_min,
+                price_max,
+                leverage_max,
+            );
+            assert_eq!(margin, Margin::MIN);
+        }
+
+        // -5.00002 sats
+        let pl = RoundingPolicy::EXCHANGE.estimate_pl(TradeSide::Buy, quantity, start, end);
+        assert_eq!(pl, -6);
+        let pl = RoundingPolicy::EXCHANGE
+            .with_pl(RoundingMode::Up)
+            .estimate_pl(TradeSide::Buy, quantity, start, end);
+        assert_eq!(pl, -5);
+
+        let policy = RoundingPolicy::default().with_price(RoundingMode::Down);
+        assert_eq!(policy.round_price(100_000.4).unwrap(), start);
+        assert_eq!(
+            RoundingPolicy::default().round_price(100_000.4).unwrap(),
+            Price::try_from(100_000.5).unwrap()
+        );
+    }
+}
@@ -0,0 +1,294 @@
+//! Serde `with` modules for persisting SDK values in a chosen representation.
+//!
+//! SDK models use the representations of the LN Markets API. Applications storing them in their
+//! own records can pick another representation per field, by annotating the field with
+//! `#[serde(with = "...")]`:
+//!
+//! + Timestamps: [`timestamp_rfc3339`], [`timestamp_millis`] and [`timestamp_secs`], with
+//!   `option` submodules for `Option<DateTime<Utc>>` fields.
+//! + Amounts: [`amount_string`], serializing numbers and numeric models such as [`Price`] and
+//!   [`Margin`] as strings, avoiding precision loss in stores handling numbers as doubles.
+//! + IDs: [`uuid_simple`], serializing UUIDs without hyphens.
+//!
+//! [`Price`]: super::price::Price
+//! [`Margin`]: super::margin::Margin
+//!
+//! # Examples
+//!
+//! ```
+//! use chrono::{DateTime, Utc};
+//! use lnm_sdk::rest::v3::models::{Price, Trade, Uuid, serde_formats};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct TradeRecord {
+//!     #[serde(with = "serde_formats::uuid_simple")]
+//!     id: Uuid,
+//!     #[serde(with = "serde_formats::amount_string")]
+//!     price: Price,
+//!     #[serde(with = "serde_formats::timestamp_millis")]
+//!     created_at: DateTime<Utc>,
+//!     #[serde(with = "serde_formats::timestamp_millis::option")]
+//!     closed_at: Option<DateTime<Utc>>,
+//! }
+//!
+//! impl From<&Trade> for TradeRecord {
+//!     fn from(trade: &Trade) -> Self {
+//!         Self {
+//!             id: trade.id(),
+//!             price: trade.price(),
+//!             created_at: trade.created_at(),
+//!             closed_at: trade.closed_at(),
+//!         }
+//!     }
+//! }
+//!
+//! let record = TradeRecord {
+//!     id: Uuid::nil(),
+//!     price: Price::try_from(100_000.5).unwrap(),
+//!     created_at: DateTime::from_timestamp_millis(1_700_000_000_000).unwrap(),
+//!     closed_at: None,
+//! };
+//!
+//! assert_eq!(
+//!     serde_json::to_string(&record).unwrap(),
+//!     r#"{"id":"00000000000000000000000000000000","price":"100000.5","created_at":1700000000000,"closed_at":null}"#
+//! );
+//! ```
+
+macro_rules! timestamp_format {
+    ($name:ident, $doc:literal, $serialize:expr, $deserialize:ty => $parse:expr) => {
+        #[doc = $doc]
+        pub mod $name {
+            use chrono::{DateTime, Utc};
+            use serde::{Deserialize, Deserializer, Serializer, de};
+
+            pub fn serialize<S>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                #[allow(clippy::redundant_closure_call)]
+                ($serialize)(value, serializer)
+            }
+
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let raw = <$deserialize>::deserialize(deserializer)?;
+
+                #[allow(clippy::redundant_closure_call)]
+                ($parse)(raw).ok_or_else(|| de::Error::custom("invalid timestamp"))
+            }
+
+            /// Same representation, for `Option<DateTime<Utc>>` fields. `None` is represented as
+            /// `null`.
+            pub mod option {
+                use chrono::{DateTime, Utc};
+                use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+                #[derive(Serialize, Deserialize)]
+                struct Wrapper(#[serde(with = "super")] DateTime<Utc>);
+
+                pub fn serialize<S>(
+                    value: &Option<DateTime<Utc>>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    value.map(Wrapper).serialize(serializer)
+                }
+
+                pub fn deserialize<'de, D>(
+                    deserializer: D,
+                ) -> Result<Option<DateTime<Utc>>, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    Option::<Wrapper>::deserialize(deserializer)
+                        .map(|wrapper| wrapper.map(|wrapper| wrapper.0))
+                }
+            }
+        }
+    };
+}
+
+timestamp_format!(
+    timestamp_rfc3339,
+    "Timestamps as RFC 3339 strings, e.g. `\"2025-01-01T00:00:00.000Z\"`.",
+    |value: &DateTime<Utc>, serializer: S| serializer.serialize_str(
+        &value.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+    ),
+    String => |raw: String| DateTime::parse_from_rfc3339(&raw)
+        .ok()
+        .map(|datetime| datetime.with_timezone(&Utc))
+);
+
+timestamp_format!(
+    timestamp_millis,
+    "Timestamps as integer milliseconds since the Unix epoch.",
+    |value: &DateTime<Utc>, serializer: S| serializer.serialize_i64(value.timestamp_millis()),
+    i64 => DateTime::<Utc>::from_timestamp_millis
+);
+
+timestamp_format!(
+    timestamp_secs,
+    "Timestamps as integer seconds since the Unix epoch. Sub-second precision is truncated.",
+    |value: &DateTime<Utc>, serializer: S| serializer.serialize_i64(value.timestamp()),
+    i64 => |secs| DateTime::<Utc>::from_timestamp(secs, 0)
+);
+
+/// Numbers and numeric models as strings, e.g. `"100000.5"`.
+///
+/// Works with any type displayed as a number and deserialized from one, such as `u64`, `i64`,
+/// `f64`, [`Price`](super::price::Price), [`Margin`](super::margin::Margin) and
+/// [`OrderQuantity`](super::quantity::order::OrderQuantity). Numbers are also accepted when
+/// deserializing.
+pub mod amount_string {
+    use std::fmt;
+
+    use serde::{
+        Deserialize, Deserializer, Serializer,
+        de::{self, DeserializeOwned, IntoDeserializer, value::Error as ValueError},
+    };
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: fmt::Display,
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Unsigned(u64),
+        Signed(i64),
+        Float(f64),
+        String(String),
+    }
+
+    fn from_number<T: DeserializeOwned>(raw: Raw) -> Result<T, ValueError> {
+        match raw {
+            Raw::Unsigned(value) => T::deserialize(value.into_deserializer()),
+            Raw::Signed(value) => T::deserialize(value.into_deserializer()),
+            Raw::Float(value) => T::deserialize(value.into_deserializer()),
+            Raw::String(value) => {
+                let raw = if let Ok(value) = value.parse::<u64>() {
+                    Raw::Unsigned(value)
+                } else if let Ok(value) = value.parse::<i64>() {
+                    Raw::Signed(value)
+                } else if let Ok(value) = value.parse::<f64>() {
+                    Raw::Float(value)
+                } else {
+                    return Err(de::Error::custom(format!("invalid amount: {value:?}")));
+                };
+                from_number(raw)
+            }
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: DeserializeOwned,
+        D: Deserializer<'de>,
+    {
+        from_number(Raw::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// UUIDs as 32 lowercase hexadecimal digits without hyphens. Hyphenated UUIDs are also accepted
+/// when deserializing.
+pub mod uuid_simple {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+    use uuid::Uuid;
+
+    pub fn serialize<S>(value: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&value.simple())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Uuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Uuid::parse_str(&raw).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use crate::shared::models::{margin::Margin, price::Price};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "super::timestamp_rfc3339")]
+        rfc3339: DateTime<Utc>,
+        #[serde(with = "super::timestamp_secs")]
+        secs: DateTime<Utc>,
+        #[serde(with = "super::timestamp_millis::option")]
+        millis: Option<DateTime<Utc>>,
+        #[serde(with = "super::amount_string")]
+        price: Price,
+        #[serde(with = "super::amount_string")]
+        margin: Margin,
+        #[serde(with = "super::amount_string")]
+        pl: i64,
+        #[serde(with = "super::uuid_simple")]
+        id: Uuid,
+    }
+
+    #[test]
+    fn test_serde_formats_round_trip() {
+        let at = DateTime::from_timestamp_millis(1_735_689_600_000).unwrap();
+        let record = Record {
+            rfc3339: at,
+            secs: at,
+            millis: Some(at),
+            price: Price::try_from(100_000.5).unwrap(),
+            margin: Margin::try_from(10_000).unwrap(),
+            pl: -42,
+            id: Uuid::from_u128(1),
+        };
+
+        let value = serde_json::to_value(&record).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "rfc3339": "2025-01-01T00:00:00.000Z",
+                "secs": 1_735_689_600,
+                "millis": 1_735_689_600_000_i64,
+                "price": "100000.5",
+                "margin": "10000",
+                "pl": "-42",
+                "id": "00000000000000000000000000000001",
+            })
+        );
+        assert_eq!(serde_json::from_value::<Record>(value).unwrap(), record);
+
+        // Numbers are accepted for amounts, and `null` for optional timestamps
+        let value = json!({
+            "rfc3339": "2025-01-01T01:00:00+01:00",
+            "secs": 1_735_689_600,
+            "millis": null,
+            "price": 100_000.5,
+            "margin": 10_000,
+            "pl": -42,
+            "id": "00000000-0000-0000-0000-000000000001",
+        });
+        let parsed = serde_json::from_value::<Record>(value).unwrap();
+        assert_eq!(parsed.rfc3339, at);
+        assert_eq!(parsed.millis, None);
+        assert_eq!(parsed.price, record.price);
+    }
+}
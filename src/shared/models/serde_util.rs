@@ -149,6 +149,99 @@ pub(crate) mod client_id_option {
     }
 }
 
+pub(crate) mod interned_str {
+    use std::{
+        collections::HashSet,
+        fmt,
+        sync::{Arc, OnceLock, RwLock},
+    };
+
+    use serde::{Deserializer, Serializer, de};
+
+    /// Strings longer than this are never interned.
+    const MAX_INTERNED_LEN: usize = 32;
+
+    /// Upper bound on the number of distinct interned strings, so that unexpected values can't
+    /// grow the interner without limit.
+    const MAX_INTERNED_COUNT: usize = 256;
+
+    fn interner() -> &'static RwLock<HashSet<Arc<str>>> {
+        static INTERNER: OnceLock<RwLock<HashSet<Arc<str>>>> = OnceLock::new();
+        INTERNER.get_or_init(Default::default)
+    }
+
+    /// Returns a shared `Arc<str>` for short, frequently repeated values (such as pairs and event
+    /// names in WebSocket messages), so that deserializing them doesn't allocate once seen.
+    pub fn intern(value: &str) -> Arc<str> {
+        if value.len() > MAX_INTERNED_LEN {
+            return Arc::from(value);
+        }
+
+        if let Some(existing) = interner()
+            .read()
+            .expect("interner lock must not be poisoned")
+            .get(value)
+        {
+            return existing.clone();
+        }
+
+        let mut interner = interner()
+            .write()
+            .expect("interner lock must not be poisoned");
+
+        if let Some(existing) = interner.get(value) {
+            return existing.clone();
+        }
+
+        let interned: Arc<str> = Arc::from(value);
+        if interner.len() < MAX_INTERNED_COUNT {
+            interner.insert(interned.clone());
+        }
+
+        interned
+    }
+
+    /// Looks up borrowed and transient strings in the interner directly, so that only values
+    /// not interned yet are allocated.
+    struct InternedStrVisitor;
+
+    impl<'de> de::Visitor<'de> for InternedStrVisitor {
+        type Value = Arc<str>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a string")
+        }
+
+        fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(intern(value))
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(intern(value))
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Arc<str>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(InternedStrVisitor)
+    }
+
+    pub fn serialize<S>(value: &Arc<str>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,4 +303,37 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[derive(Debug, Deserialize)]
+    struct Interned {
+        #[serde(deserialize_with = "interned_str::deserialize")]
+        pair: std::sync::Arc<str>,
+    }
+
+    #[test]
+    fn interned_str_shares_storage_across_values() {
+        let first: Interned = serde_json::from_str(r#"{ "pair": "btc_usd" }"#)
+            .expect("must deserialize interned string");
+        let second: Interned = serde_json::from_str(r#"{ "pair": "btc_usd" }"#)
+            .expect("must deserialize interned string");
+
+        assert_eq!(&*first.pair, "btc_usd");
+        assert!(std::sync::Arc::ptr_eq(&first.pair, &second.pair));
+
+        // Escaped strings are unescaped into a transient buffer, and still interned
+        let escaped: Interned = serde_json::from_str(r#"{ "pair": "btc\u005fusd" }"#)
+            .expect("must deserialize interned string");
+        assert!(std::sync::Arc::ptr_eq(&first.pair, &escaped.pair));
+    }
+
+    #[test]
+    fn interned_str_does_not_intern_long_values() {
+        let long = "x".repeat(64);
+
+        let first = interned_str::intern(&long);
+        let second = interned_str::intern(&long);
+
+        assert_eq!(first, second);
+        assert!(!std::sync::Arc::ptr_eq(&first, &second));
+    }
 }
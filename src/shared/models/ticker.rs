@@ -8,9 +8,13 @@ use super::price::Price;
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct TickerPrice {
+    #[serde(alias = "ask_price")]
     ask_price: Price,
+    #[serde(alias = "bid_price")]
     bid_price: Price,
+    #[serde(alias = "min_size")]
     min_size: u64,
+    #[serde(alias = "max_size")]
     max_size: u64,
 }
 
@@ -25,6 +29,11 @@ impl TickerPrice {
         self.bid_price
     }
 
+    /// Midpoint between the bid and ask prices, rounded to the nearest tick.
+    pub fn mid_price(&self) -> Price {
+        Price::bounded((self.bid_price.as_f64() + self.ask_price.as_f64()) / 2.)
+    }
+
     /// Get the minimum size.
     pub fn min_size(&self) -> u64 {
         self.min_size
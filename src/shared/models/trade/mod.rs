@@ -33,6 +33,32 @@ impl fmt::Display for TradeSide {
     }
 }
 
+/// The margin mode of a position.
+///
+/// Isolated positions (trades) have their own margin, while the cross position shares the margin
+/// of the cross account.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum MarginMode {
+    Isolated,
+    Cross,
+}
+
+impl MarginMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarginMode::Isolated => "isolated",
+            MarginMode::Cross => "cross",
+        }
+    }
+}
+
+impl fmt::Display for MarginMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
 /// The size specification for a trade position.
 ///
 /// Trade size can be specified either as a [`OrderQuantity`] (notional value in USD) or as [`Margin`]
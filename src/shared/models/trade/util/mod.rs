@@ -2,11 +2,12 @@ use std::num::NonZeroU64;
 
 use super::super::{
     SATS_PER_BTC,
-    error::TradeValidationError,
+    error::{QuantityValidationError, TradeValidationError},
     leverage::Leverage,
     margin::Margin,
     price::{PercentageCapped, Price},
     quantity::{Quantity, order::OrderQuantity},
+    rounding::RoundingPolicy,
     trade::{TradeSide, TradeSize},
 };
 
@@ -85,9 +86,40 @@ pub fn evaluate_open_trade_params(
     takeprofit: Option<Price>,
     fee_perc: PercentageCapped,
 ) -> Result<(OrderQuantity, Margin, Price, u64, u64), TradeValidationError> {
-    let (quantity, margin) = size
-        .to_quantity_and_margin(entry_price, leverage)
-        .map_err(TradeValidationError::TradeParamsInvalidQuantity)?;
+    evaluate_open_trade_params_rounded(
+        side,
+        size,
+        leverage,
+        entry_price,
+        stoploss,
+        takeprofit,
+        fee_perc,
+        RoundingPolicy::EXCHANGE,
+    )
+}
+
+/// Evaluates and validates parameters for opening a new trade, as [`evaluate_open_trade_params`]
+/// does, rounding the margin and fees with `policy`.
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_open_trade_params_rounded(
+    side: TradeSide,
+    size: TradeSize,
+    leverage: Leverage,
+    entry_price: Price,
+    stoploss: Option<Price>,
+    takeprofit: Option<Price>,
+    fee_perc: PercentageCapped,
+    policy: RoundingPolicy,
+) -> Result<(OrderQuantity, Margin, Price, u64, u64), TradeValidationError> {
+    let (quantity, margin) = match size {
+        TradeSize::Quantity(quantity) => (
+            quantity,
+            policy.calculate_margin(quantity, entry_price, leverage),
+        ),
+        TradeSize::Margin(_) => size
+            .to_quantity_and_margin(entry_price, leverage)
+            .map_err(TradeValidationError::TradeParamsInvalidQuantity)?,
+    };
 
     let liquidation = est_liquidation_from_leverage(side, quantity, entry_price, leverage);
 
@@ -142,9 +174,8 @@ pub fn evaluate_open_trade_params(
         }
     };
 
-    let fee_calc = SATS_PER_BTC * fee_perc.as_f64() / 100.;
-    let opening_fee = (fee_calc * quantity.as_f64() / entry_price.as_f64()).floor() as u64;
-    let closing_fee_reserved = (fee_calc * quantity.as_f64() / liquidation.as_f64()).floor() as u64;
+    let opening_fee = policy.order_fee(fee_perc, quantity, entry_price);
+    let closing_fee_reserved = policy.order_fee(fee_perc, quantity, liquidation);
 
     Ok((
         quantity,
@@ -366,6 +397,61 @@ pub fn evaluate_cash_in(
     ))
 }
 
+/// Calculates the collateral change needed to reach a target leverage on an isolated trade.
+///
+/// Returns the amount of margin to add (positive) or to cash-in (negative). Leverage is decreased
+/// by adding margin, which doesn't change the trade's entry price. Leverage is increased by
+/// cashing-in, which realizes the trade's PL first (if any) moving its entry price to
+/// `market_price`, and only then extracts margin.
+pub fn evaluate_collateral_delta_for_leverage(
+    side: TradeSide,
+    quantity: OrderQuantity,
+    margin: Margin,
+    price: Price,
+    target_leverage: Leverage,
+    market_price: Price,
+) -> Result<i64, TradeValidationError> {
+    evaluate_collateral_delta_for_leverage_rounded(
+        side,
+        quantity,
+        margin,
+        price,
+        target_leverage,
+        market_price,
+        RoundingPolicy::EXCHANGE,
+    )
+}
+
+/// Calculates the collateral change needed to reach a target leverage on an isolated trade, as
+/// [`evaluate_collateral_delta_for_leverage`] does, rounding margins and PL with `policy`.
+pub fn evaluate_collateral_delta_for_leverage_rounded(
+    side: TradeSide,
+    quantity: OrderQuantity,
+    margin: Margin,
+    price: Price,
+    target_leverage: Leverage,
+    market_price: Price,
+    policy: RoundingPolicy,
+) -> Result<i64, TradeValidationError> {
+    let target_margin = policy.calculate_margin(quantity, price, target_leverage);
+
+    let pl = estimate_pl(side, quantity, price, market_price);
+
+    if target_margin >= margin || pl <= 0. {
+        return Ok(target_margin.as_i64() - margin.as_i64());
+    }
+
+    // The whole PL will be realized, moving the entry price to the market price
+    let target_margin = policy.calculate_margin(quantity, market_price, target_leverage);
+
+    if target_margin > margin {
+        // Realizing the whole PL would already overshoot the target leverage
+        return Err(TradeValidationError::TargetLeverageNotReachable { target_leverage });
+    }
+
+    Ok(-(policy.round_pl(pl) + margin.as_i64() - target_margin.as_i64()))
+}
+
 /// Calculates the collateral change needed to reach a target liquidation price.
 ///
 /// Determines how much collateral needs to be added (positive) or removed (negative) to move the
@@ -395,14 +481,32 @@ pub fn evaluate_collateral_delta_for_liquidation(
     Ok(colateral_diff)
 }
 
+/// Calculates the largest position that can be opened at `leverage` and `price` with at most
+/// `margin_budget`, returning its quantity and the margin it requires.
+///
+/// The returned margin is the one the exchange computes for the quantity, and never exceeds the
+/// budget.
+pub fn size_for_margin_budget(
+    margin_budget: Margin,
+    price: Price,
+    leverage: Leverage,
+) -> Result<(OrderQuantity, Margin), QuantityValidationError> {
+    let quantity = OrderQuantity::try_calculate(margin_budget, price, leverage)?;
+    let margin = Margin::calculate(quantity, price, leverage);
+
+    Ok((quantity, margin))
+}
+
 /// Calculates the trading fee in satoshis for an order at a given price.
+///
+/// The fee is rounded with [`RoundingPolicy::EXCHANGE`]. [`RoundingPolicy::order_fee`] rounds it
+/// with other policies.
 pub fn evaluate_order_fee(
     fee_perc: PercentageCapped,
     quantity: impl Quantity,
     order_price: Price,
 ) -> u64 {
-    let fee_calc = SATS_PER_BTC * fee_perc.as_f64() / 100.;
-    (fee_calc * quantity.as_f64() / order_price.as_f64()).floor() as u64
+    RoundingPolicy::EXCHANGE.order_fee(fee_perc, quantity, order_price)
 }
 
 /// Calculates the closing fee for a trade at a given price.
@@ -1,4 +1,6 @@
-use super::super::super::{error::MarginValidationError, quantity::cross::CrossQuantity};
+use super::super::super::{
+    error::MarginValidationError, quantity::cross::CrossQuantity, rounding::RoundingMode,
+};
 
 use super::*;
 
@@ -227,6 +229,39 @@ fn test_long_takeprofit_validation() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_open_trade_params_rounding() {
+    let side = TradeSide::Buy;
+    let size = OrderQuantity::try_from(1_000).unwrap().into();
+    let leverage = Leverage::try_from(3).unwrap();
+    let entry_price = Price::try_from(99_999.5).unwrap();
+    let fee_perc = get_lnm_fee();
+
+    let (_, margin, _, opening_fee, _) =
+        evaluate_open_trade_params(side, size, leverage, entry_price, None, None, fee_perc)
+            .unwrap();
+
+    // 333,335.000008 sats margin, 1,000.005 sats fee
+    assert_eq!(margin.as_u64(), 333_336);
+    assert_eq!(opening_fee, 1_000);
+
+    let policy = RoundingPolicy::CONSERVATIVE.with_margin(RoundingMode::Down);
+    let (_, margin, _, opening_fee, _) = evaluate_open_trade_params_rounded(
+        side,
+        size,
+        leverage,
+        entry_price,
+        None,
+        None,
+        fee_perc,
+        policy,
+    )
+    .unwrap();
+
+    assert_eq!(margin.as_u64(), 333_335);
+    assert_eq!(opening_fee, 1_001);
+}
+
 #[test]
 fn test_short_stoploss_validation() {
     let side = TradeSide::Sell;
@@ -1219,3 +1254,109 @@ fn test_collateral_delta_estimation_short_profit_leverage_down() {
         "Estimated liquidation distant from target by {liquidation_diff}",
     );
 }
+
+#[test]
+fn test_evaluate_collateral_delta_for_leverage() {
+    let side = TradeSide::Buy;
+    let quantity = OrderQuantity::try_from(1_000).unwrap();
+    let price = Price::try_from(100_000).unwrap();
+    // 10x leverage
+    let margin = Margin::try_from(100_000).unwrap();
+
+    // Decreasing leverage requires adding margin
+    let delta = evaluate_collateral_delta_for_leverage(
+        side,
+        quantity,
+        margin,
+        price,
+        Leverage::try_from(5).unwrap(),
+        price,
+    )
+    .unwrap();
+    assert_eq!(delta, 100_000);
+
+    // Increasing leverage without PL requires cashing-in margin
+    let delta = evaluate_collateral_delta_for_leverage(
+        side,
+        quantity,
+        margin,
+        price,
+        Leverage::try_from(20).unwrap(),
+        Price::try_from(90_000).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(delta, -50_000);
+
+    // Increasing leverage in profit realizes the whole PL first
+    let market_price = Price::try_from(125_000).unwrap();
+    let pl = estimate_pl(side, quantity, price, market_price);
+    assert_eq!(pl, 200_000.);
+
+    let delta = evaluate_collateral_delta_for_leverage(
+        side,
+        quantity,
+        margin,
+        price,
+        Leverage::try_from(16).unwrap(),
+        market_price,
+    )
+    .unwrap();
+    assert_eq!(delta, -(200_000 + 100_000 - 50_000));
+
+    let (new_price, new_margin, new_leverage, _, _) = evaluate_cash_in(
+        side,
+        quantity,
+        margin,
+        price,
+        None,
+        market_price,
+        NonZeroU64::new(delta.unsigned_abs()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(new_price, market_price);
+    assert_eq!(new_margin.as_u64(), 50_000);
+    assert_eq!(new_leverage.as_f64(), 16.);
+}
+
+#[test]
+fn test_evaluate_collateral_delta_for_leverage_not_reachable() {
+    let side = TradeSide::Sell;
+    let quantity = OrderQuantity::try_from(1_000).unwrap();
+    let price = Price::try_from(100_000).unwrap();
+    // 10x leverage
+    let margin = Margin::try_from(100_000).unwrap();
+
+    // Realizing the PL moves the entry price to 80k, where the current margin already corresponds
+    // to 12.5x leverage, so 11x can't be reached via cash-in.
+    let error = evaluate_collateral_delta_for_leverage(
+        side,
+        quantity,
+        margin,
+        price,
+        Leverage::try_from(11).unwrap(),
+        Price::try_from(80_000).unwrap(),
+    )
+    .unwrap_err();
+
+    assert!(matches!(
+        error,
+        TradeValidationError::TargetLeverageNotReachable { .. }
+    ));
+}
+
+#[test]
+fn test_size_for_margin_budget() {
+    let budget = Margin::try_from(10_000).unwrap();
+    let price = Price::try_from(99_999.5).unwrap();
+    let leverage = Leverage::try_from(10).unwrap();
+
+    let (quantity, margin) = size_for_margin_budget(budget, price, leverage).unwrap();
+
+    assert_eq!(quantity.as_u64(), 99);
+    assert!(margin <= budget);
+    assert_eq!(margin, Margin::calculate(quantity, price, leverage));
+
+    // Budget too low for the minimum quantity
+    let budget = Margin::try_from(100).unwrap();
+    assert!(size_for_margin_budget(budget, price, leverage).is_err());
+}
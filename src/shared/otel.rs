@@ -0,0 +1,129 @@
+use http::{HeaderMap, HeaderName, HeaderValue};
+use opentelemetry::{
+    Context, KeyValue, global,
+    propagation::Injector,
+    trace::{Span, SpanKind, Status, TraceContextExt, Tracer},
+};
+use reqwest::Method;
+use uuid::Uuid;
+
+const TRACER_NAME: &str = "lnm-sdk";
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// Injects the current trace context into `headers`, with the globally configured propagator.
+pub(crate) fn inject_context(headers: &mut HeaderMap) {
+    let cx = Context::current();
+
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&cx, &mut HeaderInjector(headers))
+    });
+}
+
+/// Adds a REST request event to the current span, with the correlation ID sent with the request,
+/// so the span can be matched with the SDK logs and errors of the request.
+pub(crate) fn record_request(method: &Method, path: &str, correlation_id: Uuid) {
+    Context::current().span().add_event(
+        "lnm.rest.request",
+        vec![
+            KeyValue::new("http.request.method", method.to_string()),
+            KeyValue::new("url.path", path.to_string()),
+            KeyValue::new("lnm.correlation_id", correlation_id.to_string()),
+        ],
+    );
+}
+
+/// Span covering a Stream API session, from the first connection to the final disconnection.
+/// Ended when dropped.
+pub(crate) struct SessionSpan(global::BoxedSpan);
+
+impl SessionSpan {
+    /// Starts a session span, child of the current trace context.
+    pub fn start(endpoint: &str) -> Self {
+        let tracer = global::tracer(TRACER_NAME);
+        let span = tracer
+            .span_builder("lnm.stream.session")
+            .with_kind(SpanKind::Client)
+            .with_attributes([KeyValue::new("url.full", endpoint.to_string())])
+            .start_with_context(&tracer, &Context::current());
+
+        Self(span)
+    }
+
+    pub fn add_event(&mut self, name: &'static str) {
+        self.0.add_event(name, Vec::new());
+    }
+
+    pub fn set_error(&mut self, description: String) {
+        self.0.set_status(Status::error(description));
+    }
+}
+
+impl Drop for SessionSpan {
+    fn drop(&mut self) {
+        self.0.end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry::propagation::{
+        Extractor, TextMapPropagator, text_map_propagator::FieldIter,
+    };
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct FixedPropagator;
+
+    impl TextMapPropagator for FixedPropagator {
+        fn inject_context(&self, _cx: &Context, injector: &mut dyn Injector) {
+            injector.set(
+                "traceparent",
+                "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".into(),
+            );
+            injector.set("invalid header", "ignored".into());
+        }
+
+        fn extract_with_context(&self, cx: &Context, _extractor: &dyn Extractor) -> Context {
+            cx.clone()
+        }
+
+        fn fields(&self) -> FieldIter<'_> {
+            FieldIter::new(&[])
+        }
+    }
+
+    #[test]
+    fn test_header_injector() {
+        let mut headers = HeaderMap::new();
+
+        FixedPropagator.inject_context(&Context::current(), &mut HeaderInjector(&mut headers));
+
+        assert_eq!(headers.len(), 1);
+        assert_eq!(
+            headers["traceparent"],
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+        );
+    }
+
+    #[test]
+    fn test_inject_context_without_propagator() {
+        let mut headers = HeaderMap::new();
+
+        inject_context(&mut headers);
+
+        assert!(headers.is_empty());
+    }
+}
@@ -0,0 +1,325 @@
+//! Canonical encoding of the parts of a request covered by its signature.
+//!
+//! The signature of an authenticated request is computed over the exact body and query string
+//! that are sent, so every request body and query string is encoded here, and nowhere else:
+//!
+//! - JSON bodies have their object keys sorted (byte-wise, at every nesting level), no
+//!   whitespace, and every non-ASCII character escaped as `\uXXXX` (with surrogate pairs outside
+//!   the Basic Multilingual Plane), so the bytes can't be altered by any re-encoding on the way.
+//!   Numbers are kept as written by `serde_json`: integers without a fraction, and floats in their
+//!   shortest round-trip form, always with a fraction or an exponent (`100000.0`, `1e-7`).
+//!   Non-finite floats can't be represented in JSON, and are serialized as `null` by `serde_json`;
+//!   SDK models reject them before any request is built.
+//! - Query strings keep the parameter order, and percent-encode every byte outside the RFC 3986
+//!   unreserved set (`A-Z a-z 0-9 - . _ ~`) as uppercase `%XX`. Spaces are encoded as `%20`,
+//!   never as `+`, which servers may decode inconsistently.
+//! - The signed payload is the concatenation of the timestamp in milliseconds, the lowercase
+//!   method, the URL path, and either the body (`POST` and `PUT`) or the `?`-prefixed query
+//!   string (`GET` and `DELETE`).
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use reqwest::{Method, Url};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{
+    error::{RestApiError, Result},
+    query::QueryParams,
+};
+
+/// Serializes `body` into its canonical JSON form.
+pub(crate) fn json_body<B: Serialize + ?Sized>(body: &B) -> Result<String> {
+    let value = serde_json::to_value(body).map_err(RestApiError::RequestJsonSerializeFailed)?;
+
+    let mut out = String::new();
+    write_value(&mut out, &value);
+
+    Ok(out)
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+        Value::Number(value) => out.push_str(&value.to_string()),
+        Value::String(value) => write_string(out, value),
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, value);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, value);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0C}' => out.push_str("\\f"),
+            ' '..='~' => out.push(c),
+            _ => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    write!(out, "\\u{unit:04x}").expect("writing to a `String` can't fail");
+                }
+            }
+        }
+    }
+    out.push('"');
+}
+
+/// Encodes `params` into their canonical query string, without the leading `?`.
+pub(crate) fn query_string(params: &QueryParams) -> String {
+    let mut out = String::new();
+
+    for (i, (key, value)) in params.iter().enumerate() {
+        if i > 0 {
+            out.push('&');
+        }
+        percent_encode(&mut out, key);
+        out.push('=');
+        percent_encode(&mut out, value);
+    }
+
+    out
+}
+
+fn percent_encode(out: &mut String, value: &str) {
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => write!(out, "%{byte:02X}").expect("writing to a `String` can't fail"),
+        }
+    }
+}
+
+/// Returns the payload signed for a request sent at `timestamp`.
+///
+/// `body` is only signed for `POST` and `PUT` requests, and the query of `url` only for `GET`
+/// and `DELETE` requests.
+pub(crate) fn signing_payload(
+    timestamp: DateTime<Utc>,
+    method: &Method,
+    url: &Url,
+    body: Option<&str>,
+) -> String {
+    let params = match *method {
+        Method::POST | Method::PUT => body.unwrap_or_default().to_string(),
+        Method::GET | Method::DELETE => url
+            .query()
+            .filter(|query| !query.is_empty())
+            .map(|query| format!("?{query}"))
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+
+    format!(
+        "{}{}{}{}",
+        timestamp.timestamp_millis(),
+        method.as_str().to_lowercase(),
+        url.path(),
+        params
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_json_body_sorts_keys_at_every_level() {
+        #[derive(Serialize)]
+        struct Body {
+            side: &'static str,
+            leverage: f64,
+            nested: HashMap<&'static str, u64>,
+        }
+
+        let body = Body {
+            side: "buy",
+            leverage: 10.,
+            nested: HashMap::from([("z", 1), ("a", 2), ("B", 3), ("aa", 4)]),
+        };
+
+        assert_eq!(
+            json_body(&body).unwrap(),
+            r#"{"leverage":10.0,"nested":{"B":3,"a":2,"aa":4,"z":1},"side":"buy"}"#
+        );
+        assert_eq!(
+            json_body(&json!([{ "b": [], "a": {} }, null, true])).unwrap(),
+            r#"[{"a":{},"b":[]},null,true]"#
+        );
+    }
+
+    #[test]
+    fn test_json_body_numbers() {
+        let cases = [
+            (json!(0), "0"),
+            (json!(-42), "-42"),
+            (json!(u64::MAX), "18446744073709551615"),
+            (json!(i64::MIN), "-9223372036854775808"),
+            (json!(100_000.0), "100000.0"),
+            (json!(100_000.5), "100000.5"),
+            (json!(0.1 + 0.2), "0.30000000000000004"),
+            (json!(0.0000001), "1e-7"),
+            (json!(1e21), "1e+21"),
+            (json!(-0.0), "-0.0"),
+            (json!(f64::NAN), "null"),
+            (json!(f64::INFINITY), "null"),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(json_body(&value).unwrap(), expected, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn test_json_body_escapes_strings() {
+        let cases = [
+            ("plain", r#""plain""#),
+            (
+                "quote \" backslash \\ slash /",
+                r#""quote \" backslash \\ slash /""#,
+            ),
+            ("\n\r\t\u{08}\u{0C}", r#""\n\r\t\b\f""#),
+            ("\u{00}\u{1F}\u{7F}", r#""\u0000\u001f\u007f""#),
+            ("café", r#""caf\u00e9""#),
+            ("€", r#""\u20ac""#),
+            ("⚡🚀", r#""\u26a1\ud83d\ude80""#),
+        ];
+
+        for (value, expected) in cases {
+            let encoded = json_body(value).unwrap();
+            assert_eq!(encoded, expected, "{value:?}");
+            assert!(encoded.is_ascii());
+            assert_eq!(serde_json::from_str::<String>(&encoded).unwrap(), value);
+        }
+
+        assert_eq!(
+            json_body(&json!({ "é": 1, "e": 2 })).unwrap(),
+            r#"{"e":2,"\u00e9":1}"#
+        );
+    }
+
+    #[test]
+    fn test_json_body_round_trips() {
+        let value = json!({
+            "id": "00000000-0000-0000-0000-000000000001",
+            "price": 95_000.5,
+            "quantity": 1_000,
+            "clientId": "bot/1 ☂",
+            "flags": [true, false, null],
+            "memo": { "text": "line\nbreak", "empty": "" },
+        });
+
+        let encoded = json_body(&value).unwrap();
+
+        assert_eq!(serde_json::from_str::<Value>(&encoded).unwrap(), value);
+        assert_eq!(
+            json_body(&value).unwrap(),
+            encoded,
+            "encoding is deterministic"
+        );
+    }
+
+    #[test]
+    fn test_query_string() {
+        let params = QueryParams::new()
+            .with("to", "2025-05-12T07:30:05.657Z")
+            .with("limit", 10)
+            .with("text", "a b+c&d=e/f?")
+            .with("unreserved", "AZaz09-._~")
+            .with("unicode", "é⚡")
+            .with("empty", "");
+
+        assert_eq!(
+            query_string(&params),
+            "to=2025-05-12T07%3A30%3A05.657Z&limit=10&text=a%20b%2Bc%26d%3De%2Ff%3F\
+             &unreserved=AZaz09-._~&unicode=%C3%A9%E2%9A%A1&empty="
+        );
+        assert_eq!(query_string(&QueryParams::new()), "");
+    }
+
+    #[test]
+    fn test_query_string_survives_url() {
+        let params = QueryParams::new()
+            .with("text", "a b+c")
+            .with("unicode", "é");
+        let encoded = query_string(&params);
+
+        let mut url = Url::parse("https://api.lnmarkets.com/v3/futures/ticker").unwrap();
+        url.set_query(Some(&encoded));
+
+        assert_eq!(url.query(), Some(encoded.as_str()));
+        assert_eq!(
+            url.as_str().parse::<http::Uri>().unwrap().query(),
+            Some(encoded.as_str())
+        );
+    }
+
+    #[test]
+    fn test_signing_payload() {
+        let timestamp = DateTime::from_timestamp_millis(1_747_035_005_657).unwrap();
+        let url =
+            Url::parse("https://api.lnmarkets.com/v3/futures/isolated/trades?limit=10&from=x")
+                .unwrap();
+        let body = Some(r#"{"id":"abc"}"#);
+
+        assert_eq!(
+            signing_payload(timestamp, &Method::GET, &url, body),
+            "1747035005657get/v3/futures/isolated/trades?limit=10&from=x"
+        );
+        assert_eq!(
+            signing_payload(timestamp, &Method::DELETE, &url, None),
+            "1747035005657delete/v3/futures/isolated/trades?limit=10&from=x"
+        );
+        assert_eq!(
+            signing_payload(timestamp, &Method::POST, &url, body),
+            r#"1747035005657post/v3/futures/isolated/trades{"id":"abc"}"#
+        );
+        assert_eq!(
+            signing_payload(timestamp, &Method::PUT, &url, None),
+            "1747035005657put/v3/futures/isolated/trades"
+        );
+
+        let url = Url::parse("https://api.lnmarkets.com/v3/account?").unwrap();
+        assert_eq!(
+            signing_payload(timestamp, &Method::GET, &url, None),
+            "1747035005657get/v3/account"
+        );
+    }
+}
@@ -1,11 +1,15 @@
-use std::result;
+use std::{fmt, result, time::Duration};
 
+use chrono::{DateTime, Utc};
 use hmac::digest::InvalidLength;
 use hyper::{Method, StatusCode, header::InvalidHeaderValue};
 use thiserror::Error;
+use uuid::Uuid;
 
 use crate::rest::v3::error::RestApiV3Error;
 
+use super::lnm::scope::ApiScope;
+
 #[derive(Error, Debug)]
 #[non_exhaustive]
 pub enum RestApiError {
@@ -13,43 +17,356 @@ pub enum RestApiError {
     UrlParse(String),
 
     #[error("Unexpected schema error: {0}")]
-    UnexpectedSchema(reqwest::Error),
+    UnexpectedSchema(#[source] reqwest::Error),
 
     #[error("Invalid header value error: {0}")]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
 
     #[error("Invalid secret HMAC error: {0}")]
-    InvalidSecretHmac(InvalidLength),
+    InvalidSecretHmac(#[source] InvalidLength),
 
     #[error("HTTP client `reqwest` error: {0}")]
-    HttpClient(reqwest::Error),
+    HttpClient(#[source] reqwest::Error),
 
-    #[error("Response decoding `reqwest` error: {0}")]
-    ResponseDecoding(reqwest::Error),
+    #[error(
+        "Response decoding `reqwest` error. Endpoint: {method} {path}, correlation ID: {correlation_id}, error: {e}"
+    )]
+    ResponseDecoding {
+        method: Method,
+        path: String,
+        correlation_id: Uuid,
+        #[source]
+        e: reqwest::Error,
+    },
 
     #[error("Authentication required for request but no credentials provided")]
     MissingRequestCredentials,
 
+    #[error("API key lacks the `{scope}` scope required by {method} {path}")]
+    MissingScope {
+        scope: ApiScope,
+        method: Method,
+        path: String,
+    },
+
     #[error("Tried to make a request with unsupported method: {0}")]
     UnsupportedMethod(Method),
 
-    #[error("Failed to send request error: {0}")]
-    SendFailed(reqwest::Error),
+    #[error("Operation `{0}` is not supported by this API implementation")]
+    UnsupportedOperation(&'static str),
+
+    #[error(
+        "Failed to send request error. Endpoint: {method} {path}, correlation ID: {correlation_id}, error: {e}"
+    )]
+    SendFailed {
+        method: Method,
+        path: String,
+        correlation_id: Uuid,
+        #[source]
+        e: reqwest::Error,
+    },
 
-    #[error("Received error response. Status: {status}, text: {text}")]
-    ErrorResponse { status: StatusCode, text: String },
+    #[error("Received error response. {0}")]
+    ErrorResponse(Box<ErrorResponseContext>),
 
-    #[error("Response JSON deserialization failed. Raw response: '{raw_response}', error: {e}")]
+    #[error(
+        "Response JSON deserialization failed. Endpoint: {method} {path}, correlation ID: {}, raw response: '{raw_response}', error: {e}",
+        correlation_id.map_or("none".to_string(), |id| id.to_string())
+    )]
     ResponseJsonDeserializeFailed {
+        method: Method,
+        path: String,
+        correlation_id: Option<Uuid>,
         raw_response: String,
+        #[source]
         e: serde_json::Error,
     },
 
+    #[error("Exchange in maintenance since {since}, order submissions are paused")]
+    ExchangeInMaintenance { since: DateTime<Utc> },
+
+    #[error(
+        "Latest price is older than {max_age:?} (last seen: {}), market orders are rejected",
+        last_seen.map_or("never".to_string(), |time| time.to_string())
+    )]
+    StalePrice {
+        last_seen: Option<DateTime<Utc>>,
+        max_age: Duration,
+    },
+
     #[error("Request JSON serialization failed. Error: {0}")]
-    RequestJsonSerializeFailed(serde_json::Error),
+    RequestJsonSerializeFailed(#[source] serde_json::Error),
 
     #[error(transparent)]
     RestApiV3(#[from] RestApiV3Error),
 }
 
+/// Context of an [`RestApiError::ErrorResponse`], boxed to keep [`RestApiError`] small.
+#[derive(Debug, Clone)]
+pub struct ErrorResponseContext {
+    pub method: Method,
+    pub path: String,
+    pub status: StatusCode,
+    pub request_id: Option<String>,
+    pub correlation_id: Option<Uuid>,
+    pub retry_after: Option<Duration>,
+    pub text: String,
+}
+
+impl fmt::Display for ErrorResponseContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Endpoint: {} {}, status: {}, request ID: {}, correlation ID: {}, text: {}",
+            self.method,
+            self.path,
+            self.status,
+            self.request_id.as_deref().unwrap_or("none"),
+            self.correlation_id
+                .map_or("none".to_string(), |id| id.to_string()),
+            self.text
+        )
+    }
+}
+
+impl From<ErrorResponseContext> for RestApiError {
+    fn from(context: ErrorResponseContext) -> Self {
+        Self::ErrorResponse(Box::new(context))
+    }
+}
+
+impl RestApiError {
+    /// Returns the HTTP status of the response that caused the error, if one was received.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::ErrorResponse(response) => Some(response.status),
+            Self::UnexpectedSchema(e)
+            | Self::HttpClient(e)
+            | Self::ResponseDecoding { e, .. }
+            | Self::SendFailed { e, .. } => e.status(),
+            _ => None,
+        }
+    }
+
+    /// Returns the method and path of the endpoint the failed request was sent to, if the error
+    /// occurred after the request was built.
+    pub fn endpoint(&self) -> Option<(&Method, &str)> {
+        match self {
+            Self::ResponseDecoding { method, path, .. }
+            | Self::MissingScope { method, path, .. }
+            | Self::SendFailed { method, path, .. }
+            | Self::ResponseJsonDeserializeFailed { method, path, .. } => {
+                Some((method, path.as_str()))
+            }
+            Self::ErrorResponse(response) => Some((&response.method, response.path.as_str())),
+            _ => None,
+        }
+    }
+
+    /// Returns the request ID reported by the server in the error response, if any.
+    ///
+    /// Useful when reaching out to LNM support about a specific failed request.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::ErrorResponse(response) => response.request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the correlation ID sent with the failed request in the `X-Correlation-Id` header,
+    /// if the error occurred after the request was sent.
+    ///
+    /// The same ID is included in the SDK debug logs and OpenTelemetry events of the request, and
+    /// can be referenced to correlate them, or when reaching out to LNM support.
+    pub fn correlation_id(&self) -> Option<Uuid> {
+        match self {
+            Self::ResponseDecoding { correlation_id, .. }
+            | Self::SendFailed { correlation_id, .. } => Some(*correlation_id),
+            Self::ErrorResponse(response) => response.correlation_id,
+            Self::ResponseJsonDeserializeFailed { correlation_id, .. } => *correlation_id,
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the server rejected the request due to rate limiting
+    /// (`429 Too Many Requests`).
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Returns how long the server asked clients to wait before retrying, as reported by the
+    /// `Retry-After` header of the error response, if any.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::ErrorResponse(response) => response.retry_after,
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the server reported being under maintenance, either with a
+    /// `503 Service Unavailable` response or an error message mentioning maintenance, or if the
+    /// request was rejected locally with [`ExchangeInMaintenance`](Self::ExchangeInMaintenance).
+    pub fn is_maintenance(&self) -> bool {
+        match self {
+            Self::ExchangeInMaintenance { .. } => true,
+            Self::ErrorResponse(response) => {
+                response.status == StatusCode::SERVICE_UNAVAILABLE
+                    || response.text.to_lowercase().contains("maintenance")
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the error is likely transient, and retrying the same request may
+    /// succeed.
+    ///
+    /// This is the case for timeouts, connection failures, rate limiting, server-side errors
+    /// (`500`, `502`, `503` and `504`), and submissions paused during maintenance. Validation,
+    /// authentication, and other client-side errors are not retryable.
+    ///
+    /// **Note:** Retrying requests that are not idempotent (such as opening a trade) after a
+    /// timeout may result in the action being performed more than once.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::SendFailed { e, .. } | Self::ResponseDecoding { e, .. } => {
+                e.is_timeout() || e.is_connect() || e.is_body()
+            }
+            Self::ExchangeInMaintenance { .. } => true,
+            Self::ErrorResponse(response) => matches!(
+                response.status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::INTERNAL_SERVER_ERROR
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the server rejected the request's credentials or permissions
+    /// (`401 Unauthorized` or `403 Forbidden`), if credentials were required but not provided, or
+    /// if the request was rejected locally for [lacking a scope](Self::MissingScope).
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self,
+            Self::MissingRequestCredentials | Self::MissingScope { .. }
+        ) || matches!(
+            self.status(),
+            Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+        )
+    }
+
+    /// Returns `true` if the error was caused by client-side validation, before any request was
+    /// sent.
+    pub fn is_validation_error(&self) -> bool {
+        matches!(self, Self::RestApiV3(e) if e.is_validation_error())
+    }
+}
+
 pub(crate) type Result<T> = result::Result<T, RestApiError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_response(status: StatusCode) -> RestApiError {
+        RestApiError::ErrorResponse(Box::new(ErrorResponseContext {
+            method: Method::GET,
+            path: "/v3/account".to_string(),
+            status,
+            request_id: Some("abc-123".to_string()),
+            correlation_id: Some(Uuid::from_u128(1)),
+            retry_after: None,
+            text: "{}".to_string(),
+        }))
+    }
+
+    #[test]
+    fn test_error_response_context() {
+        let error = error_response(StatusCode::BAD_REQUEST);
+
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(error.endpoint(), Some((&Method::GET, "/v3/account")));
+        assert_eq!(error.request_id(), Some("abc-123"));
+        assert_eq!(error.correlation_id(), Some(Uuid::from_u128(1)));
+        assert!(
+            error
+                .to_string()
+                .contains("correlation ID: 00000000-0000-0000-0000-000000000001")
+        );
+        assert!(!error.is_auth_error());
+        assert!(!error.is_validation_error());
+        assert!(!error.is_retryable());
+        assert!(!error.is_rate_limited());
+    }
+
+    #[test]
+    fn test_is_validation_error() {
+        use crate::rest::v3::error::CrossPositionCloseValidationError;
+
+        let error = RestApiError::from(RestApiV3Error::CrossPositionCloseValidation(
+            CrossPositionCloseValidationError::NoOpenPosition,
+        ));
+        assert!(error.is_validation_error());
+
+        let error = RestApiError::from(RestApiV3Error::PositionNotClosed {
+            order_id: Uuid::nil(),
+        });
+        assert!(!error.is_validation_error());
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(error_response(StatusCode::INTERNAL_SERVER_ERROR).is_retryable());
+        assert!(error_response(StatusCode::SERVICE_UNAVAILABLE).is_retryable());
+        assert!(!error_response(StatusCode::NOT_FOUND).is_retryable());
+        assert!(!RestApiError::MissingRequestCredentials.is_retryable());
+    }
+
+    #[test]
+    fn test_is_maintenance() {
+        assert!(error_response(StatusCode::SERVICE_UNAVAILABLE).is_maintenance());
+        assert!(!error_response(StatusCode::INTERNAL_SERVER_ERROR).is_maintenance());
+
+        let error = RestApiError::ErrorResponse(Box::new(ErrorResponseContext {
+            method: Method::POST,
+            path: "/v3/futures/isolated/trade".to_string(),
+            status: StatusCode::BAD_REQUEST,
+            request_id: None,
+            correlation_id: None,
+            retry_after: None,
+            text: r#"{"message":"Trading is disabled during maintenance"}"#.to_string(),
+        }));
+        assert!(error.is_maintenance());
+        assert!(!RestApiError::MissingRequestCredentials.is_maintenance());
+
+        let error = RestApiError::ExchangeInMaintenance { since: Utc::now() };
+        assert!(error.is_maintenance());
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_rate_limited_retry_after() {
+        let error = RestApiError::ErrorResponse(Box::new(ErrorResponseContext {
+            method: Method::POST,
+            path: "/v3/futures/isolated/trade".to_string(),
+            status: StatusCode::TOO_MANY_REQUESTS,
+            request_id: None,
+            correlation_id: None,
+            retry_after: Some(Duration::from_secs(5)),
+            text: "Too many requests".to_string(),
+        }));
+
+        assert!(error.is_rate_limited());
+        assert!(error.is_retryable());
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_is_auth_error() {
+        assert!(error_response(StatusCode::UNAUTHORIZED).is_auth_error());
+        assert!(error_response(StatusCode::FORBIDDEN).is_auth_error());
+        assert!(RestApiError::MissingRequestCredentials.is_auth_error());
+    }
+}
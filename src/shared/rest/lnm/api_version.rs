@@ -0,0 +1,260 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use reqwest::{Method, header::HeaderMap};
+
+use super::logging::LOG_TARGET;
+
+/// Response headers carrying the version of the API that served the request.
+const VERSION_HEADERS: &[&str] = &["api-version", "x-api-version"];
+
+/// Notice about the API version, raised from response headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ApiNotice {
+    /// The endpoint is deprecated, as signaled by a `Deprecation` header. `sunset` is the time
+    /// the endpoint is expected to be removed, from the `Sunset` header, and `link` the value of
+    /// the `Link` header, usually pointing to migration docs.
+    Deprecated {
+        method: Method,
+        path: String,
+        deprecation: String,
+        sunset: Option<DateTime<Utc>>,
+        link: Option<String>,
+    },
+
+    /// The API version reported by the server differs from the pinned version.
+    VersionMismatch {
+        method: Method,
+        path: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for ApiNotice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deprecated {
+                method,
+                path,
+                deprecation,
+                sunset,
+                ..
+            } => {
+                write!(f, "Endpoint {method} {path} is deprecated ({deprecation})")?;
+                if let Some(sunset) = sunset {
+                    write!(f, ", sunset at {}", sunset.to_rfc3339())?;
+                }
+                Ok(())
+            }
+            Self::VersionMismatch {
+                method,
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "API version mismatch on {method} {path}: expected {expected}, got {actual}"
+            ),
+        }
+    }
+}
+
+/// Callback invoked with every [`ApiNotice`].
+pub type ApiNoticeHandler = Arc<dyn Fn(&ApiNotice) + Send + Sync>;
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Reads the notices raised by the headers of a response.
+fn parse_notices(
+    method: &Method,
+    path: &str,
+    headers: &HeaderMap,
+    expected_version: Option<&str>,
+) -> (Option<String>, Vec<ApiNotice>) {
+    let mut notices = Vec::new();
+
+    if let Some(deprecation) = header_str(headers, "deprecation")
+        && deprecation != "false"
+    {
+        let sunset = header_str(headers, "sunset")
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+            .map(|sunset| sunset.with_timezone(&Utc));
+
+        notices.push(ApiNotice::Deprecated {
+            method: method.clone(),
+            path: path.to_string(),
+            deprecation: deprecation.to_string(),
+            sunset,
+            link: header_str(headers, "link").map(str::to_string),
+        });
+    }
+
+    let version = VERSION_HEADERS
+        .iter()
+        .find_map(|name| header_str(headers, name))
+        .map(str::to_string);
+
+    if let (Some(expected), Some(actual)) = (expected_version, &version)
+        && expected != actual
+    {
+        notices.push(ApiNotice::VersionMismatch {
+            method: method.clone(),
+            path: path.to_string(),
+            expected: expected.to_string(),
+            actual: actual.clone(),
+        });
+    }
+
+    (version, notices)
+}
+
+#[derive(Default)]
+struct ApiVersionState {
+    expected_version: Option<String>,
+    last_version: Option<String>,
+    handler: Option<ApiNoticeHandler>,
+    logged: HashSet<(bool, String)>,
+}
+
+/// Tracks the API version reported by the server, and raises [`ApiNotice`]s from response
+/// headers.
+///
+/// Notices are passed to the handler if one is set. Otherwise, they are logged as warnings, once
+/// per endpoint and kind of notice.
+#[derive(Default)]
+pub(crate) struct ApiVersionTracker(Mutex<ApiVersionState>);
+
+impl ApiVersionTracker {
+    fn state(&self) -> std::sync::MutexGuard<'_, ApiVersionState> {
+        self.0
+            .lock()
+            .expect("`ApiVersionTracker` mutex can't be poisoned")
+    }
+
+    pub fn set_expected_version(&self, expected_version: Option<String>) {
+        self.state().expected_version = expected_version;
+    }
+
+    pub fn set_handler(&self, handler: Option<ApiNoticeHandler>) {
+        self.state().handler = handler;
+    }
+
+    pub fn last_version(&self) -> Option<String> {
+        self.state().last_version.clone()
+    }
+
+    pub fn observe(&self, method: &Method, path: &str, headers: &HeaderMap) {
+        let (handler, notices) = {
+            let mut state = self.state();
+            let (version, notices) =
+                parse_notices(method, path, headers, state.expected_version.as_deref());
+
+            if version.is_some() {
+                state.last_version = version;
+            }
+
+            if notices.is_empty() {
+                return;
+            }
+
+            if state.handler.is_none() {
+                for notice in &notices {
+                    let is_deprecation = matches!(notice, ApiNotice::Deprecated { .. });
+                    if state
+                        .logged
+                        .insert((is_deprecation, format!("{method} {path}")))
+                    {
+                        log::warn!(target: LOG_TARGET, "{notice}");
+                    }
+                }
+                return;
+            }
+
+            (state.handler.clone(), notices)
+        };
+
+        // Called without holding the lock, so handlers can use the client
+        if let Some(handler) = handler {
+            for notice in &notices {
+                handler(notice);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_notices() {
+        let mut headers = HeaderMap::new();
+        headers.insert("deprecation", HeaderValue::from_static("@1767225600"));
+        headers.insert(
+            "sunset",
+            HeaderValue::from_static("Wed, 01 Jul 2026 00:00:00 GMT"),
+        );
+        headers.insert("x-api-version", HeaderValue::from_static("3.2.0"));
+
+        let (version, notices) =
+            parse_notices(&Method::GET, "/v3/account", &headers, Some("3.1.0"));
+
+        assert_eq!(version.as_deref(), Some("3.2.0"));
+        assert_eq!(notices.len(), 2);
+        assert!(matches!(
+            &notices[0],
+            ApiNotice::Deprecated { sunset: Some(sunset), .. }
+                if sunset.to_rfc3339() == "2026-07-01T00:00:00+00:00"
+        ));
+        assert!(matches!(
+            &notices[1],
+            ApiNotice::VersionMismatch { expected, actual, .. }
+                if expected == "3.1.0" && actual == "3.2.0"
+        ));
+
+        let (_, notices) = parse_notices(&Method::GET, "/v3/account", &headers, Some("3.2.0"));
+        assert_eq!(notices.len(), 1);
+
+        let (version, notices) = parse_notices(
+            &Method::GET,
+            "/v3/account",
+            &HeaderMap::new(),
+            Some("3.2.0"),
+        );
+        assert!(version.is_none());
+        assert!(notices.is_empty());
+    }
+
+    #[test]
+    fn test_tracker_calls_handler() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let handler_received = received.clone();
+
+        let tracker = ApiVersionTracker::default();
+        tracker.set_expected_version(Some("3".to_string()));
+        tracker.set_handler(Some(Arc::new(move |notice: &ApiNotice| {
+            handler_received.lock().unwrap().push(notice.clone())
+        })));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("api-version", HeaderValue::from_static("4"));
+        tracker.observe(&Method::GET, "/v3/ping", &headers);
+
+        assert_eq!(tracker.last_version().as_deref(), Some("4"));
+        assert_eq!(received.lock().unwrap().len(), 1);
+    }
+}
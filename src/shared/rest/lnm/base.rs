@@ -1,17 +1,85 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use chrono::{DateTime, Utc};
 use reqwest::{
-    self, Client, Method, Url,
-    header::{HeaderMap, HeaderName, HeaderValue},
+    self, Client, Method, StatusCode, Url,
+    header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER},
 };
 use serde::{Serialize, de::DeserializeOwned};
+use uuid::Uuid;
+
+use crate::shared::{clock::Clock, models::trade::TradeExecution};
 
 use {
-    super::super::error::{RestApiError, Result},
-    super::rate_limit::RateLimiter,
+    super::super::{
+        canonical,
+        error::{ErrorResponseContext, RestApiError, Result},
+        query::QueryParams,
+        timing::{self, RequestTiming},
+    },
+    super::{
+        api_version::{ApiNoticeHandler, ApiVersionTracker},
+        logging::{self, DebugLogging, LOG_TARGET},
+        maintenance::MaintenanceGate,
+        price_guard::PriceGuard,
+        rate_limit::{RateLimitBucketStatus, RateLimitStatus, RateLimiter, ServerRateLimit},
+        scope::{ApiScope, ScopeGuard},
+    },
 };
 
+/// Interval of TCP keepalive probes, so idle pooled connections aren't dropped by intermediaries.
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+
+/// Response header carrying the server-side identifier of a request.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Request header carrying the client-side identifier of a request, generated for each request.
+const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+/// Returns the value of the first of `names` present in `headers`, parsed as an integer.
+fn header_u64(headers: &HeaderMap, names: &[&str]) -> Option<u64> {
+    names
+        .iter()
+        .find_map(|name| headers.get(*name))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Reads the `RateLimit-*` or `X-RateLimit-*` headers of a response, if any are present.
+fn parse_server_rate_limit(headers: &HeaderMap, now: DateTime<Utc>) -> Option<ServerRateLimit> {
+    let limit = header_u64(headers, &["ratelimit-limit", "x-ratelimit-limit"]);
+    let remaining = header_u64(headers, &["ratelimit-remaining", "x-ratelimit-remaining"]);
+    let reset = header_u64(headers, &["ratelimit-reset", "x-ratelimit-reset"]);
+
+    if limit.is_none() && remaining.is_none() && reset.is_none() {
+        return None;
+    }
+
+    Some(ServerRateLimit::new(limit, remaining, reset, now))
+}
+
+/// Parses a `Retry-After` header value, given either in seconds or as an HTTP date (relative to
+/// `now`).
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
 pub(crate) trait SignatureGenerator: Send + Sync {
     fn generate(
         &self,
@@ -24,6 +92,22 @@ pub(crate) trait SignatureGenerator: Send + Sync {
 
 pub(crate) trait RestPath: Clone {
     fn to_path_string(self) -> String;
+
+    /// Scope an API key must be granted to request the path with `method`, if known.
+    fn required_scope(&self, _method: &Method) -> Option<ApiScope> {
+        None
+    }
+}
+
+/// Arbitrary paths, relative to the endpoint, for requests not covered by a path enum.
+impl RestPath for String {
+    fn to_path_string(self) -> String {
+        if self.starts_with('/') {
+            self
+        } else {
+            format!("/{self}")
+        }
+    }
 }
 
 struct LnmRestCredentials<S: SignatureGenerator> {
@@ -43,12 +127,11 @@ impl<S: SignatureGenerator> LnmRestCredentials<S> {
 
     fn get_authentication_headers(
         &self,
+        timestamp: DateTime<Utc>,
         method: &Method,
         url: &Url,
         body: Option<&String>,
     ) -> Result<HeaderMap> {
-        let timestamp = Utc::now();
-
         let signature = self
             .signature_generator
             .generate(timestamp, method, url, body)?;
@@ -78,11 +161,203 @@ impl<S: SignatureGenerator> LnmRestCredentials<S> {
     }
 }
 
-pub(crate) struct LnmRestBase<S: SignatureGenerator> {
+/// Builds LNM REST requests and parses their responses, without performing any IO.
+pub(crate) struct LnmRestRequestBuilder<S: SignatureGenerator> {
     endpoint: String,
     credentials: Option<LnmRestCredentials<S>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<S: SignatureGenerator> LnmRestRequestBuilder<S> {
+    pub fn new(endpoint: String, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            endpoint,
+            credentials: None,
+            clock,
+        }
+    }
+
+    pub fn with_credentials(
+        endpoint: String,
+        key: String,
+        passphrase: String,
+        signature_generator: S,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        let creds = LnmRestCredentials::new(key, passphrase, signature_generator);
+
+        Self {
+            endpoint,
+            credentials: Some(creds),
+            clock,
+        }
+    }
+
+    pub fn has_credentials(&self) -> bool {
+        self.credentials.is_some()
+    }
+
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    pub fn build_url(&self, path: impl RestPath) -> Result<Url> {
+        let url_str = format!(
+            "{}{}",
+            self.endpoint.trim_end_matches('/'),
+            path.to_path_string()
+        );
+
+        Url::parse(&url_str).map_err(|e| RestApiError::UrlParse(e.to_string()))
+    }
+
+    pub fn build_url_with_query_params(
+        &self,
+        path: impl RestPath,
+        query_params: &QueryParams,
+    ) -> Result<Url> {
+        let mut url = self.build_url(path)?;
+        query_params.append_to(&mut url);
+
+        Ok(url)
+    }
+
+    /// Builds a request ready to be sent, including authentication headers when `authenticated`
+    /// is `true`, and a newly generated correlation ID header.
+    pub fn build_request(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<String>,
+        authenticated: bool,
+    ) -> Result<http::Request<Vec<u8>>> {
+        if !matches!(
+            method,
+            Method::GET | Method::POST | Method::PUT | Method::DELETE
+        ) {
+            return Err(RestApiError::UnsupportedMethod(method));
+        }
+
+        let mut headers = if authenticated {
+            let creds = self
+                .credentials
+                .as_ref()
+                .ok_or(RestApiError::MissingRequestCredentials)?;
+
+            creds.get_authentication_headers(self.clock.now(), &method, &url, body.as_ref())?
+        } else {
+            HeaderMap::new()
+        };
+
+        let body = match method {
+            Method::POST | Method::PUT => body,
+            _ => None,
+        };
+
+        headers.insert(
+            HeaderName::from_static(CORRELATION_ID_HEADER),
+            HeaderValue::from_str(&Uuid::new_v4().to_string())?,
+        );
+
+        if body.is_some() {
+            headers.insert(
+                HeaderName::from_static("content-type"),
+                HeaderValue::from_static("application/json"),
+            );
+        }
+
+        let mut request = http::Request::new(body.map(String::into_bytes).unwrap_or_default());
+        *request.method_mut() = method;
+        *request.uri_mut() = url
+            .as_str()
+            .parse()
+            .map_err(|e: http::uri::InvalidUri| RestApiError::UrlParse(e.to_string()))?;
+        *request.headers_mut() = headers;
+
+        Ok(request)
+    }
+}
+
+/// Reads the correlation ID set by [`LnmRestRequestBuilder::build_request`] from the headers of a
+/// request.
+pub(crate) fn correlation_id(headers: &HeaderMap) -> Option<Uuid> {
+    headers
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value).ok())
+}
+
+/// Turns the status, headers and body of a response into the response text, or into an
+/// [`RestApiError::ErrorResponse`] if the status is not successful.
+///
+/// `correlation_id` is the one sent with the request, if known. `now` is the time the response
+/// was received, used to resolve `Retry-After` dates.
+pub(crate) fn check_response(
+    method: &Method,
+    path: &str,
+    correlation_id: Option<Uuid>,
+    status: StatusCode,
+    headers: &HeaderMap,
+    text: String,
+    now: DateTime<Utc>,
+) -> Result<String> {
+    if status.is_success() {
+        return Ok(text);
+    }
+
+    let request_id = headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_retry_after(value, now));
+
+    Err(RestApiError::ErrorResponse(Box::new(
+        ErrorResponseContext {
+            method: method.clone(),
+            path: path.to_string(),
+            status,
+            request_id,
+            correlation_id,
+            retry_after,
+            text,
+        },
+    )))
+}
+
+pub(crate) fn deserialize_response<T>(
+    method: &Method,
+    path: &str,
+    correlation_id: Option<Uuid>,
+    raw_response: String,
+) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_str::<T>(&raw_response).map_err(|e| {
+        RestApiError::ResponseJsonDeserializeFailed {
+            method: method.clone(),
+            path: path.to_string(),
+            correlation_id,
+            raw_response,
+            e,
+        }
+    })
+}
+
+pub(crate) struct LnmRestBase<S: SignatureGenerator> {
+    requests: LnmRestRequestBuilder<S>,
     client: Client,
     rate_limiter: Option<RateLimiter>,
+    last_auth_server_limit: Mutex<Option<ServerRateLimit>>,
+    last_unauth_server_limit: Mutex<Option<ServerRateLimit>>,
+    debug_logging: DebugLogging,
+    maintenance: MaintenanceGate,
+    price_guard: PriceGuard,
+    api_version: ApiVersionTracker,
+    scopes: ScopeGuard,
 }
 
 impl<S: SignatureGenerator> LnmRestBase<S> {
@@ -90,17 +365,25 @@ impl<S: SignatureGenerator> LnmRestBase<S> {
         timeout: Duration,
         endpoint: String,
         rate_limiter: Option<RateLimiter>,
+        clock: Arc<dyn Clock>,
     ) -> Result<Arc<Self>> {
         let client = Client::builder()
             .timeout(timeout)
+            .tcp_keepalive(TCP_KEEPALIVE)
             .build()
             .map_err(RestApiError::HttpClient)?;
 
         Ok(Arc::new(Self {
-            endpoint,
-            credentials: None,
+            requests: LnmRestRequestBuilder::new(endpoint, clock),
             client,
             rate_limiter,
+            last_auth_server_limit: Mutex::new(None),
+            last_unauth_server_limit: Mutex::new(None),
+            debug_logging: DebugLogging::new(false),
+            maintenance: MaintenanceGate::new(),
+            price_guard: PriceGuard::new(),
+            api_version: ApiVersionTracker::default(),
+            scopes: ScopeGuard::new(),
         }))
     }
 
@@ -111,34 +394,181 @@ impl<S: SignatureGenerator> LnmRestBase<S> {
         passphrase: String,
         signature_generator: S,
         rate_limiter: Option<RateLimiter>,
+        clock: Arc<dyn Clock>,
     ) -> Result<Arc<Self>> {
         let client = Client::builder()
             .timeout(timeout)
+            .tcp_keepalive(TCP_KEEPALIVE)
             .build()
             .map_err(RestApiError::HttpClient)?;
 
-        let creds = LnmRestCredentials::new(key, passphrase, signature_generator);
-
         Ok(Arc::new(Self {
-            endpoint,
-            credentials: Some(creds),
+            requests: LnmRestRequestBuilder::with_credentials(
+                endpoint,
+                key,
+                passphrase,
+                signature_generator,
+                clock,
+            ),
             client,
             rate_limiter,
+            last_auth_server_limit: Mutex::new(None),
+            last_unauth_server_limit: Mutex::new(None),
+            debug_logging: DebugLogging::new(false),
+            maintenance: MaintenanceGate::new(),
+            price_guard: PriceGuard::new(),
+            api_version: ApiVersionTracker::default(),
+            scopes: ScopeGuard::new(),
         }))
     }
 
     pub fn has_credentials(&self) -> bool {
-        self.credentials.is_some()
+        self.requests.has_credentials()
     }
 
-    fn build_url(&self, path: impl RestPath) -> Result<Url> {
-        let url_str = format!(
-            "{}{}",
-            self.endpoint.trim_end_matches('/'),
-            path.to_path_string()
-        );
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        self.requests.clock()
+    }
 
-        Url::parse(&url_str).map_err(|e| RestApiError::UrlParse(e.to_string()))
+    pub fn debug_logging(&self) -> bool {
+        self.debug_logging.is_enabled()
+    }
+
+    pub fn set_debug_logging(&self, enabled: bool) {
+        self.debug_logging.set(enabled);
+    }
+
+    pub fn set_expected_api_version(&self, expected_version: Option<String>) {
+        self.api_version.set_expected_version(expected_version);
+    }
+
+    pub fn set_api_notice_handler(&self, handler: Option<ApiNoticeHandler>) {
+        self.api_version.set_handler(handler);
+    }
+
+    pub fn api_version(&self) -> Option<String> {
+        self.api_version.last_version()
+    }
+
+    /// Signs a request without sending it, so the first signed request doesn't pay one-time
+    /// initialization costs. Does nothing without credentials.
+    pub fn prime_signer(&self) -> Result<()> {
+        if !self.has_credentials() {
+            return Ok(());
+        }
+
+        let url = self.requests.build_url("/account".to_string())?;
+        self.requests.build_request(Method::GET, url, None, true)?;
+
+        Ok(())
+    }
+
+    pub fn granted_scopes(&self) -> Option<BTreeSet<ApiScope>> {
+        self.scopes.granted()
+    }
+
+    pub fn set_granted_scopes(&self, scopes: Option<BTreeSet<ApiScope>>) {
+        self.scopes.set_granted(scopes);
+    }
+
+    /// Builds the URL of an authenticated request, rejecting it if the API key is known to lack
+    /// the scope required by `path`.
+    fn build_checked_url(
+        &self,
+        method: &Method,
+        path: impl RestPath,
+        query_params: Option<&QueryParams>,
+        authenticated: bool,
+    ) -> Result<Url> {
+        let required_scope = authenticated.then(|| path.required_scope(method)).flatten();
+
+        let url = match query_params {
+            Some(query_params) => self
+                .requests
+                .build_url_with_query_params(path, query_params)?,
+            None => self.requests.build_url(path)?,
+        };
+
+        if let Some(scope) = required_scope {
+            self.scopes.check(scope, method, url.path())?;
+        }
+
+        Ok(url)
+    }
+
+    pub fn set_maintenance_probe_interval(&self, probe_interval: Option<Duration>) {
+        self.maintenance.set_probe_interval(probe_interval);
+    }
+
+    pub fn maintenance_paused_since(&self) -> Option<DateTime<Utc>> {
+        self.maintenance.paused_since()
+    }
+
+    /// Rejects order submissions while the exchange is in maintenance.
+    ///
+    /// While paused, `probe` is requested at most once per probe interval; a successful response
+    /// resumes the gate and lets the submission through.
+    pub async fn check_maintenance(&self, probe: impl RestPath) -> Result<()> {
+        if self.maintenance.paused_since().is_none() {
+            return Ok(());
+        }
+
+        if self.maintenance.try_start_probe(self.clock().now()) {
+            // The outcome is recorded by the gate
+            let _ = self.make_get_request_plain_text(probe).await;
+        }
+
+        match self.maintenance.paused_since() {
+            Some(since) => Err(RestApiError::ExchangeInMaintenance { since }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn set_stale_price_threshold(&self, max_age: Option<Duration>) {
+        self.price_guard.set_max_age(max_age);
+    }
+
+    pub fn observe_price(&self, time: DateTime<Utc>) {
+        self.price_guard.observe(time);
+    }
+
+    pub fn last_price_seen_at(&self) -> Option<DateTime<Utc>> {
+        self.price_guard.last_seen()
+    }
+
+    /// Rejects market orders if the latest observed price is older than the stale price
+    /// threshold. Limit orders are let through, since their price is set by the caller.
+    pub fn check_price_freshness(&self, execution: TradeExecution) -> Result<()> {
+        match execution {
+            TradeExecution::Market => self.price_guard.check(self.clock().now()),
+            TradeExecution::Limit(_) => Ok(()),
+        }
+    }
+
+    fn last_server_limit(&self, authenticated: bool) -> &Mutex<Option<ServerRateLimit>> {
+        if authenticated {
+            &self.last_auth_server_limit
+        } else {
+            &self.last_unauth_server_limit
+        }
+    }
+
+    fn bucket_status(&self, authenticated: bool) -> RateLimitBucketStatus {
+        let local = self
+            .rate_limiter
+            .as_ref()
+            .map(|rl| rl.local_status(authenticated));
+        let server = self
+            .last_server_limit(authenticated)
+            .lock()
+            .expect("`last_server_limit` mutex can't be poisoned")
+            .clone();
+
+        RateLimitBucketStatus::new(local, server)
+    }
+
+    pub fn rate_limit_status(&self) -> RateLimitStatus {
+        RateLimitStatus::new(self.bucket_status(true), self.bucket_status(false))
     }
 
     async fn make_request<T>(
@@ -151,71 +581,190 @@ impl<S: SignatureGenerator> LnmRestBase<S> {
     where
         T: DeserializeOwned,
     {
+        let path = url.path().to_string();
+        let (raw_response, correlation_id) = self
+            .perform_request(method.clone(), url, body, authenticated)
+            .await?;
+
+        deserialize_response(&method, &path, Some(correlation_id), raw_response)
+    }
+
+    /// Waits for the rate limiter, builds and sends the request, and records its timing.
+    ///
+    /// Returns the response text, along with the correlation ID sent with the request.
+    async fn perform_request(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<String>,
+        authenticated: bool,
+    ) -> Result<(String, Uuid)> {
+        let started_at = Instant::now();
+
         if let Some(rl) = &self.rate_limiter {
             rl.acquire(authenticated).await;
         }
 
-        let headers = if authenticated {
-            let creds = self
-                .credentials
-                .as_ref()
-                .ok_or(RestApiError::MissingRequestCredentials)?;
+        let queued_at = Instant::now();
+        let request = self
+            .requests
+            .build_request(method, url, body, authenticated)?;
+
+        let correlation_id =
+            correlation_id(request.headers()).expect("correlation ID header was just set");
+        let mut timing = RequestTiming::new(
+            request.method().clone(),
+            request.uri().path().to_string(),
+            correlation_id,
+        );
+        timing.set_queue_wait(queued_at - started_at);
+        timing.set_signing(queued_at.elapsed());
 
-            creds.get_authentication_headers(&method, &url, body.as_ref())?
-        } else {
-            HeaderMap::new()
-        };
+        let result = self.send_request(request, authenticated, &mut timing).await;
 
-        let response = self.send_request(method, url, body, headers).await?;
+        timing.set_total(started_at.elapsed());
+        timing::record(timing);
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response
-                .text()
-                .await
-                .map_err(RestApiError::ResponseDecoding)?;
+        result.map(|text| (text, correlation_id))
+    }
 
-            return Err(RestApiError::ErrorResponse { status, text });
-        }
+    async fn send_request(
+        &self,
+        request: http::Request<Vec<u8>>,
+        authenticated: bool,
+        timing: &mut RequestTiming,
+    ) -> Result<String> {
+        #[cfg(feature = "prometheus")]
+        let (method, path, started_at) = (
+            request.method().clone(),
+            request.uri().path().to_string(),
+            Instant::now(),
+        );
 
-        let raw_response = response
-            .text()
-            .await
-            .map_err(RestApiError::ResponseDecoding)?;
+        let result = self
+            .execute_request(request, authenticated, Some(timing))
+            .await;
+
+        self.maintenance.observe(&result, self.clock().now());
 
-        let response_data = serde_json::from_str::<T>(&raw_response)
-            .map_err(|e| RestApiError::ResponseJsonDeserializeFailed { raw_response, e })?;
+        #[cfg(feature = "prometheus")]
+        crate::metrics::observe_request(
+            &method,
+            &path,
+            started_at.elapsed(),
+            result.as_ref().err(),
+        );
 
-        Ok(response_data)
+        result
     }
 
-    async fn send_request(
+    /// Sends the request and checks its response.
+    ///
+    /// `timing` is `None` for fast lane requests, which skip the optional middleware: timing,
+    /// tracing, debug logging and API version tracking.
+    async fn execute_request(
         &self,
-        method: Method,
-        url: Url,
-        body: Option<String>,
-        mut headers: HeaderMap,
-    ) -> Result<reqwest::Response> {
-        let req = match method {
-            Method::POST | Method::PUT => {
-                if body.is_some() {
-                    headers.insert(
-                        HeaderName::from_static("content-type"),
-                        HeaderValue::from_static("application/json"),
-                    );
-                }
-
-                let mut req = self.client.request(method, url).headers(headers);
-                if let Some(body) = body {
-                    req = req.body(body);
-                }
-                req
+        request: http::Request<Vec<u8>>,
+        authenticated: bool,
+        mut timing: Option<&mut RequestTiming>,
+    ) -> Result<String> {
+        let full = timing.is_some();
+
+        #[cfg(feature = "otel")]
+        let request = {
+            let mut request = request;
+            if full {
+                crate::shared::otel::inject_context(request.headers_mut());
             }
-            Method::GET | Method::DELETE => self.client.request(method, url).headers(headers),
-            m => return Err(RestApiError::UnsupportedMethod(m)),
+            request
         };
 
-        req.send().await.map_err(RestApiError::SendFailed)
+        let method = request.method().clone();
+        let path = request.uri().path().to_string();
+        let correlation_id =
+            correlation_id(request.headers()).expect("correlation ID header was just set");
+
+        #[cfg(feature = "otel")]
+        if full {
+            crate::shared::otel::record_request(&method, &path, correlation_id);
+        }
+
+        let should_log = full && self.debug_logging.should_log();
+        if should_log {
+            log::debug!(
+                target: LOG_TARGET,
+                "request: {method} {}, correlation ID: {correlation_id}, headers: [{}], body: {}",
+                request.uri(),
+                logging::redact_headers(request.headers()),
+                logging::redact_body(&String::from_utf8_lossy(request.body()))
+            );
+        }
+        let started_at = Instant::now();
+
+        let request = reqwest::Request::try_from(request).map_err(RestApiError::HttpClient)?;
+
+        let response =
+            self.client
+                .execute(request)
+                .await
+                .map_err(|e| RestApiError::SendFailed {
+                    method: method.clone(),
+                    path: path.clone(),
+                    correlation_id,
+                    e,
+                })?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+
+        let response_at = Instant::now();
+        if let Some(timing) = timing.as_deref_mut() {
+            timing.set_response(response_at - started_at, &headers);
+        }
+
+        if full {
+            self.api_version.observe(&method, &path, &headers);
+        }
+
+        if let Some(server_limit) = parse_server_rate_limit(&headers, self.clock().now()) {
+            *self
+                .last_server_limit(authenticated)
+                .lock()
+                .expect("`last_server_limit` mutex can't be poisoned") = Some(server_limit);
+        }
+
+        let text = response
+            .text()
+            .await
+            .map_err(|e| RestApiError::ResponseDecoding {
+                method: method.clone(),
+                path: path.clone(),
+                correlation_id,
+                e,
+            })?;
+
+        if let Some(timing) = timing {
+            timing.set_body_read(response_at.elapsed());
+        }
+
+        if should_log {
+            log::debug!(
+                target: LOG_TARGET,
+                "response: {method} {path}, correlation ID: {correlation_id}, status: {status}, elapsed: {:?}, body: {}",
+                started_at.elapsed(),
+                logging::redact_body(&text)
+            );
+        }
+
+        check_response(
+            &method,
+            &path,
+            Some(correlation_id),
+            status,
+            &headers,
+            text,
+            self.clock().now(),
+        )
     }
 
     pub async fn make_request_with_body<T, B>(
@@ -229,29 +778,56 @@ impl<S: SignatureGenerator> LnmRestBase<S> {
         T: DeserializeOwned,
         B: Serialize,
     {
-        let url = self.build_url(path)?;
-        let body =
-            serde_json::to_string(&body).map_err(RestApiError::RequestJsonSerializeFailed)?;
+        let url = self.build_checked_url(&method, path, None, authenticated)?;
+        let body = canonical::json_body(&body)?;
 
         self.make_request(method, url, Some(body), authenticated)
             .await
     }
 
-    pub async fn make_request_with_query_params<I, K, V, T>(
+    /// Like [`make_request_with_body`](Self::make_request_with_body), but skipping the optional
+    /// middleware of the request path: timing, metrics, tracing, debug logging, API version and
+    /// maintenance tracking. Only rate limiting, scope checks and signing are kept.
+    pub async fn make_fast_request_with_body<T, B>(
         &self,
         method: Method,
         path: impl RestPath,
-        query_params: I,
+        body: B,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize,
+    {
+        let url = self.build_checked_url(&method, path, None, true)?;
+        let body = canonical::json_body(&body)?;
+
+        if let Some(rl) = &self.rate_limiter {
+            rl.acquire(true).await;
+        }
+
+        let request = self
+            .requests
+            .build_request(method.clone(), url, Some(body), true)?;
+        let path = request.uri().path().to_string();
+        let correlation_id =
+            correlation_id(request.headers()).expect("correlation ID header was just set");
+
+        let raw_response = self.execute_request(request, true, None).await?;
+
+        deserialize_response(&method, &path, Some(correlation_id), raw_response)
+    }
+
+    pub async fn make_request_with_query_params<T>(
+        &self,
+        method: Method,
+        path: impl RestPath,
+        query_params: QueryParams,
         authenticated: bool,
     ) -> Result<T>
     where
-        I: IntoIterator<Item = (K, V)>,
-        K: AsRef<str>,
-        V: AsRef<str>,
         T: DeserializeOwned,
     {
-        let mut url = self.build_url(path)?;
-        url.query_pairs_mut().extend_pairs(query_params);
+        let url = self.build_checked_url(&method, path, Some(&query_params), authenticated)?;
 
         self.make_request(method, url, None, authenticated).await
     }
@@ -265,37 +841,74 @@ impl<S: SignatureGenerator> LnmRestBase<S> {
     where
         T: DeserializeOwned,
     {
-        let url = self.build_url(path)?;
+        let url = self.build_checked_url(&method, path, None, authenticated)?;
 
         self.make_request(method, url, None, authenticated).await
     }
 
+    pub async fn make_request_with_query_params_and_body<T>(
+        &self,
+        method: Method,
+        path: impl RestPath,
+        query_params: QueryParams,
+        body: Option<String>,
+        authenticated: bool,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let url = self.build_checked_url(&method, path, Some(&query_params), authenticated)?;
+
+        self.make_request(method, url, body, authenticated).await
+    }
+
     pub async fn make_get_request_plain_text(&self, path: impl RestPath) -> Result<String> {
-        let url = self.build_url(path)?;
+        let url = self.requests.build_url(path)?;
 
-        if let Some(rl) = &self.rate_limiter {
-            rl.acquire(false).await;
-        }
+        self.perform_request(Method::GET, url, None, false)
+            .await
+            .map(|(text, _)| text)
+    }
+}
 
-        let response = self
-            .send_request(Method::GET, url, None, HeaderMap::new())
-            .await?;
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response
-                .text()
-                .await
-                .map_err(RestApiError::ResponseDecoding)?;
+    use super::*;
 
-            return Err(RestApiError::ErrorResponse { status, text });
-        }
+    #[test]
+    fn test_parse_retry_after() {
+        let now = Utc.with_ymd_and_hms(2015, 10, 21, 7, 27, 0).unwrap();
 
-        let raw_response = response
-            .text()
-            .await
-            .map_err(RestApiError::ResponseDecoding)?;
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", Utc::now()),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_parse_server_rate_limit() {
+        assert_eq!(parse_server_rate_limit(&HeaderMap::new(), Utc::now()), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("60"));
+        headers.insert("ratelimit-remaining", HeaderValue::from_static("12"));
+        headers.insert("ratelimit-reset", HeaderValue::from_static("invalid"));
+
+        let server_limit = parse_server_rate_limit(&headers, Utc::now()).unwrap();
 
-        Ok(raw_response)
+        assert_eq!(server_limit.limit(), Some(60));
+        assert_eq!(server_limit.remaining(), Some(12));
+        assert_eq!(server_limit.reset(), None);
     }
 }
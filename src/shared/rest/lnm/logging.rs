@@ -0,0 +1,149 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+/// Replacement for values that must not be written to logs.
+const REDACTED: &str = "[REDACTED]";
+
+/// JSON keys whose values are always redacted, regardless of their content.
+const SENSITIVE_KEYS: &[&str] = &[
+    "invoice",
+    "paymentRequest",
+    "preimage",
+    "key",
+    "secret",
+    "passphrase",
+    "signature",
+];
+
+/// Prefixes of Lightning invoices and offers, matched case-insensitively against string values.
+const INVOICE_PREFIXES: &[&str] = &["lnbc", "lntb", "lntbs", "lnbcrt", "lno1", "lnr1", "lni1"];
+
+/// Runtime toggle for redacted request/response debug logging.
+///
+/// When enabled, requests and responses are emitted with the [`log`] crate at the `debug` level,
+/// under the `lnm_sdk::rest` target. Credentials, signatures and invoices are redacted.
+pub(crate) struct DebugLogging(AtomicBool);
+
+impl DebugLogging {
+    pub fn new(enabled: bool) -> Self {
+        Self(AtomicBool::new(enabled))
+    }
+
+    pub fn set(&self, enabled: bool) {
+        self.0.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Whether logging is enabled and a `debug` level logger is installed for [`LOG_TARGET`].
+    pub fn should_log(&self) -> bool {
+        self.is_enabled() && log::log_enabled!(target: LOG_TARGET, log::Level::Debug)
+    }
+}
+
+pub(crate) const LOG_TARGET: &str = "lnm_sdk::rest";
+
+fn is_invoice_like(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    INVOICE_PREFIXES
+        .iter()
+        .any(|prefix| lower.starts_with(prefix) && lower.len() > 20)
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if SENSITIVE_KEYS.contains(&key.as_str()) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_value),
+        Value::String(s) if is_invoice_like(s) => *s = REDACTED.to_string(),
+        _ => {}
+    }
+}
+
+/// Returns a copy of the given request or response body, safe to be logged.
+///
+/// JSON bodies have sensitive fields and invoice-like strings redacted. Non-JSON bodies are
+/// redacted entirely if they look like an invoice.
+pub(crate) fn redact_body(body: &str) -> String {
+    match serde_json::from_str::<Value>(body) {
+        Ok(mut value) => {
+            redact_value(&mut value);
+            value.to_string()
+        }
+        Err(_) if is_invoice_like(body.trim()) => REDACTED.to_string(),
+        Err(_) => body.to_string(),
+    }
+}
+
+/// Formats the given headers for logging, redacting the values of `lnm-access-*` headers.
+pub(crate) fn redact_headers(headers: &HeaderMap) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if name.as_str().starts_with("lnm-access-") {
+                REDACTED
+            } else {
+                value.to_str().unwrap_or("<non-utf8>")
+            };
+            format!("{name}: {value}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderName, HeaderValue};
+
+    use super::*;
+
+    #[test]
+    fn test_redact_body_sensitive_keys_and_invoices() {
+        let body = r#"{"invoice":"anything","amount":1000,"nested":[{"pr":"lnbc2500u1pvjluezpp5qqqsyqcyq5rqwzqf"}]}"#;
+
+        let redacted: Value = serde_json::from_str(&redact_body(body)).unwrap();
+
+        assert_eq!(redacted["invoice"], REDACTED);
+        assert_eq!(redacted["amount"], 1000);
+        assert_eq!(redacted["nested"][0]["pr"], REDACTED);
+    }
+
+    #[test]
+    fn test_redact_body_plain_text() {
+        assert_eq!(redact_body("pong"), "pong");
+        assert_eq!(
+            redact_body("LNBC2500U1PVJLUEZPP5QQQSYQCYQ5RQWZQF"),
+            REDACTED
+        );
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("lnm-access-key"),
+            HeaderValue::from_static("my-key"),
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let redacted = redact_headers(&headers);
+
+        assert!(redacted.contains("lnm-access-key: [REDACTED]"));
+        assert!(redacted.contains("content-type: application/json"));
+        assert!(!redacted.contains("my-key"));
+    }
+}
@@ -0,0 +1,144 @@
+use std::{sync::Mutex, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+use super::super::error::RestApiError;
+
+struct MaintenanceState {
+    probe_interval: Option<Duration>,
+    paused_since: Option<DateTime<Utc>>,
+    last_probe: Option<DateTime<Utc>>,
+}
+
+/// Tracks whether the exchange signaled maintenance, so order submissions can be paused until the
+/// API is healthy again.
+///
+/// Disabled until a probe interval is set. While enabled, any response identified as maintenance
+/// (see [`RestApiError::is_maintenance`]) pauses the gate, and any successful response resumes it.
+pub(crate) struct MaintenanceGate(Mutex<MaintenanceState>);
+
+impl MaintenanceGate {
+    pub fn new() -> Self {
+        Self(Mutex::new(MaintenanceState {
+            probe_interval: None,
+            paused_since: None,
+            last_probe: None,
+        }))
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MaintenanceState> {
+        self.0
+            .lock()
+            .expect("`MaintenanceGate` mutex can't be poisoned")
+    }
+
+    /// Enables the gate with the given minimum interval between health probes, or disables it.
+    pub fn set_probe_interval(&self, probe_interval: Option<Duration>) {
+        let mut state = self.state();
+        state.probe_interval = probe_interval;
+
+        if probe_interval.is_none() {
+            state.paused_since = None;
+            state.last_probe = None;
+        }
+    }
+
+    /// Time maintenance was first signaled, if the gate is paused.
+    pub fn paused_since(&self) -> Option<DateTime<Utc>> {
+        self.state().paused_since
+    }
+
+    /// Updates the gate according to the outcome of a request completed at `now`.
+    pub fn observe<T>(&self, result: &Result<T, RestApiError>, now: DateTime<Utc>) {
+        let mut state = self.state();
+        if state.probe_interval.is_none() {
+            return;
+        }
+
+        match result {
+            Ok(_) => {
+                state.paused_since = None;
+                state.last_probe = None;
+            }
+            Err(e) if e.is_maintenance() => {
+                state.paused_since.get_or_insert(now);
+            }
+            Err(_) => {}
+        }
+    }
+
+    /// Returns `true`, and records the probe at `now`, if the gate is paused and no probe was
+    /// started within the probe interval.
+    pub fn try_start_probe(&self, now: DateTime<Utc>) -> bool {
+        let mut state = self.state();
+        let (Some(interval), Some(_)) = (state.probe_interval, state.paused_since) else {
+            return false;
+        };
+
+        let due = state
+            .last_probe
+            .is_none_or(|last_probe| (now - last_probe).to_std().unwrap_or_default() >= interval);
+        if due {
+            state.last_probe = Some(now);
+        }
+
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+    use http::{Method, StatusCode};
+
+    use super::*;
+    use crate::shared::rest::error::ErrorResponseContext;
+    use crate::{shared::clock::Clock, testing::clock::MockClock};
+
+    fn maintenance_error() -> Result<(), RestApiError> {
+        Err(RestApiError::ErrorResponse(Box::new(
+            ErrorResponseContext {
+                method: Method::GET,
+                path: "/v3/ping".to_string(),
+                status: StatusCode::SERVICE_UNAVAILABLE,
+                request_id: None,
+                correlation_id: None,
+                retry_after: None,
+                text: String::new(),
+            },
+        )))
+    }
+
+    #[test]
+    fn test_gate_pauses_and_resumes() {
+        let clock = MockClock::default();
+        let gate = MaintenanceGate::new();
+
+        gate.observe(&maintenance_error(), clock.now());
+        assert!(gate.paused_since().is_none(), "disabled gate never pauses");
+
+        gate.set_probe_interval(Some(Duration::from_secs(60)));
+        gate.observe(&maintenance_error(), clock.now());
+        assert_eq!(gate.paused_since(), Some(clock.now()));
+
+        clock.advance(TimeDelta::seconds(5));
+        gate.observe(&maintenance_error(), clock.now());
+        assert_eq!(
+            gate.paused_since(),
+            Some(clock.now() - TimeDelta::seconds(5))
+        );
+
+        assert!(gate.try_start_probe(clock.now()));
+        clock.advance(TimeDelta::seconds(59));
+        assert!(
+            !gate.try_start_probe(clock.now()),
+            "probe interval not elapsed"
+        );
+        clock.advance(TimeDelta::seconds(1));
+        assert!(gate.try_start_probe(clock.now()));
+
+        gate.observe(&Ok(()), clock.now());
+        assert!(gate.paused_since().is_none());
+        assert!(!gate.try_start_probe(clock.now()));
+    }
+}
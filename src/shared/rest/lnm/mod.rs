@@ -1,2 +1,7 @@
+pub(crate) mod api_version;
 pub(crate) mod base;
+pub(crate) mod logging;
+pub(crate) mod maintenance;
+pub(crate) mod price_guard;
 pub(crate) mod rate_limit;
+pub(crate) mod scope;
@@ -0,0 +1,116 @@
+use std::{sync::Mutex, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+use super::super::error::RestApiError;
+
+struct PriceGuardState {
+    max_age: Option<Duration>,
+    last_seen: Option<DateTime<Utc>>,
+}
+
+/// Tracks when a price was last observed, so market orders can be rejected locally if the price
+/// feed went silent.
+///
+/// Disabled until a maximum age is set. While enabled, [`check`](Self::check) fails if no price
+/// was observed, or if the latest one is older than the maximum age.
+pub(crate) struct PriceGuard(Mutex<PriceGuardState>);
+
+impl PriceGuard {
+    pub fn new() -> Self {
+        Self(Mutex::new(PriceGuardState {
+            max_age: None,
+            last_seen: None,
+        }))
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, PriceGuardState> {
+        self.0.lock().expect("`PriceGuard` mutex can't be poisoned")
+    }
+
+    /// Enables the guard with the given maximum price age, or disables it.
+    pub fn set_max_age(&self, max_age: Option<Duration>) {
+        self.state().max_age = max_age;
+    }
+
+    /// Time of the latest price observed, if any.
+    pub fn last_seen(&self) -> Option<DateTime<Utc>> {
+        self.state().last_seen
+    }
+
+    /// Records a price (or feed heartbeat) observed at `time`. Older observations are ignored.
+    pub fn observe(&self, time: DateTime<Utc>) {
+        let mut state = self.state();
+        if state.last_seen.is_none_or(|last_seen| time > last_seen) {
+            state.last_seen = Some(time);
+        }
+    }
+
+    /// Returns an error if the guard is enabled, and no price was observed within the maximum age
+    /// as of `now`.
+    pub fn check(&self, now: DateTime<Utc>) -> Result<(), RestApiError> {
+        let state = self.state();
+        let Some(max_age) = state.max_age else {
+            return Ok(());
+        };
+
+        let fresh = state
+            .last_seen
+            .is_some_and(|last_seen| (now - last_seen).to_std().unwrap_or_default() <= max_age);
+        if fresh {
+            return Ok(());
+        }
+
+        Err(RestApiError::StalePrice {
+            last_seen: state.last_seen,
+            max_age,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+    use crate::{shared::clock::Clock, testing::clock::MockClock};
+
+    #[test]
+    fn test_guard_rejects_stale_price() {
+        let clock = MockClock::default();
+        let guard = PriceGuard::new();
+
+        assert!(
+            guard.check(clock.now()).is_ok(),
+            "disabled guard never rejects"
+        );
+
+        guard.set_max_age(Some(Duration::from_secs(5)));
+        assert!(matches!(
+            guard.check(clock.now()),
+            Err(RestApiError::StalePrice {
+                last_seen: None,
+                ..
+            })
+        ));
+
+        guard.observe(clock.now());
+        guard.observe(clock.now() - TimeDelta::seconds(10));
+        assert_eq!(guard.last_seen(), Some(clock.now()));
+
+        clock.advance(TimeDelta::seconds(5));
+        assert!(guard.check(clock.now()).is_ok());
+
+        clock.advance(TimeDelta::seconds(1));
+        assert!(matches!(
+            guard.check(clock.now()),
+            Err(RestApiError::StalePrice {
+                last_seen: Some(_),
+                ..
+            })
+        ));
+
+        guard.set_max_age(None);
+        assert!(guard.check(clock.now()).is_ok());
+    }
+}
@@ -1,5 +1,9 @@
-use std::time::Duration;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 
+use chrono::{DateTime, Utc};
 use tokio::{sync::Mutex, time::Instant};
 
 /// Provides the interval durations needed to construct a [`RateLimiter`].
@@ -30,6 +34,8 @@ pub(crate) struct RateLimiter {
     last_unauth_request: Mutex<Instant>,
     auth_interval: Duration,
     unauth_interval: Duration,
+    pending_auth: AtomicUsize,
+    pending_unauth: AtomicUsize,
 }
 
 impl RateLimiter {
@@ -39,15 +45,31 @@ impl RateLimiter {
             last_unauth_request: Mutex::new(Instant::now() - unauth_interval),
             auth_interval,
             unauth_interval,
+            pending_auth: AtomicUsize::new(0),
+            pending_unauth: AtomicUsize::new(0),
         }
     }
 
-    pub async fn acquire(&self, authenticated: bool) {
-        let (last, interval) = if authenticated {
-            (&self.last_auth_request, self.auth_interval)
+    fn bucket(&self, authenticated: bool) -> (&Mutex<Instant>, Duration, &AtomicUsize) {
+        if authenticated {
+            (
+                &self.last_auth_request,
+                self.auth_interval,
+                &self.pending_auth,
+            )
         } else {
-            (&self.last_unauth_request, self.unauth_interval)
-        };
+            (
+                &self.last_unauth_request,
+                self.unauth_interval,
+                &self.pending_unauth,
+            )
+        }
+    }
+
+    pub async fn acquire(&self, authenticated: bool) {
+        let (last, interval, pending) = self.bucket(authenticated);
+
+        let _pending = PendingGuard::new(pending);
 
         let mut last = last.lock().await;
         let elapsed = last.elapsed();
@@ -58,6 +80,182 @@ impl RateLimiter {
 
         *last = Instant::now();
     }
+
+    /// Returns a snapshot of the given bucket, without waiting.
+    ///
+    /// While a request is being paced, the wait is estimated as one full interval per queued
+    /// request.
+    pub fn local_status(&self, authenticated: bool) -> LocalRateLimit {
+        let (last, interval, pending) = self.bucket(authenticated);
+        let queued = pending.load(Ordering::Relaxed);
+
+        let available_in = match last.try_lock() {
+            Ok(last) => interval.saturating_sub(last.elapsed()),
+            Err(_) => interval * queued.max(1) as u32,
+        };
+
+        LocalRateLimit {
+            interval,
+            queued,
+            available_in,
+        }
+    }
+}
+
+/// Counts a request as pending until dropped, so that the count stays correct if the
+/// [`acquire`](RateLimiter::acquire) future is cancelled while waiting.
+struct PendingGuard<'a>(&'a AtomicUsize);
+
+impl<'a> PendingGuard<'a> {
+    fn new(pending: &'a AtomicUsize) -> Self {
+        pending.fetch_add(1, Ordering::Relaxed);
+        Self(pending)
+    }
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Snapshot of a bucket of the client-side rate limiter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalRateLimit {
+    interval: Duration,
+    queued: usize,
+    available_in: Duration,
+}
+
+impl LocalRateLimit {
+    /// Minimum interval enforced between requests.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Number of requests currently waiting for the limiter.
+    pub fn queued(&self) -> usize {
+        self.queued
+    }
+
+    /// Estimated time until a new request would be sent. Zero if a request can be sent
+    /// immediately.
+    pub fn available_in(&self) -> Duration {
+        self.available_in
+    }
+}
+
+/// Rate limit information from the headers of the last response received by the client.
+///
+/// Both the `RateLimit-*` and `X-RateLimit-*` header variants are recognized. Values are kept as
+/// sent by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerRateLimit {
+    limit: Option<u64>,
+    remaining: Option<u64>,
+    reset: Option<u64>,
+    observed_at: DateTime<Utc>,
+}
+
+impl ServerRateLimit {
+    pub(crate) fn new(
+        limit: Option<u64>,
+        remaining: Option<u64>,
+        reset: Option<u64>,
+        observed_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            limit,
+            remaining,
+            reset,
+            observed_at,
+        }
+    }
+
+    /// Request quota of the current window, from the `RateLimit-Limit` header.
+    pub fn limit(&self) -> Option<u64> {
+        self.limit
+    }
+
+    /// Requests remaining in the current window, from the `RateLimit-Remaining` header.
+    pub fn remaining(&self) -> Option<u64> {
+        self.remaining
+    }
+
+    /// Raw value of the `RateLimit-Reset` header, as sent by the server.
+    pub fn reset(&self) -> Option<u64> {
+        self.reset
+    }
+
+    /// Timestamp of the response the headers were read from.
+    pub fn observed_at(&self) -> DateTime<Utc> {
+        self.observed_at
+    }
+}
+
+/// Rate limit status of a class of requests (authenticated or unauthenticated).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitBucketStatus {
+    local: Option<LocalRateLimit>,
+    server: Option<ServerRateLimit>,
+}
+
+impl RateLimitBucketStatus {
+    pub(crate) fn new(local: Option<LocalRateLimit>, server: Option<ServerRateLimit>) -> Self {
+        Self { local, server }
+    }
+
+    /// Status of the client-side rate limiter. `None` if the limiter is disabled.
+    pub fn local(&self) -> Option<&LocalRateLimit> {
+        self.local.as_ref()
+    }
+
+    /// Rate limit headers of the last response of this class. `None` if no response with rate
+    /// limit headers was received yet.
+    pub fn server(&self) -> Option<&ServerRateLimit> {
+        self.server.as_ref()
+    }
+
+    /// Estimated time until a new request of this class would be sent by the client. Zero if the
+    /// client-side limiter is disabled.
+    pub fn available_in(&self) -> Duration {
+        self.local
+            .map(|local| local.available_in())
+            .unwrap_or_default()
+    }
+}
+
+/// Rate limit status of a client, per class of requests.
+///
+/// Combines the state of the client-side rate limiter with the rate limit headers last seen on
+/// responses, so schedulers can throttle non-critical work before requests start being delayed or
+/// rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    authenticated: RateLimitBucketStatus,
+    unauthenticated: RateLimitBucketStatus,
+}
+
+impl RateLimitStatus {
+    pub(crate) fn new(
+        authenticated: RateLimitBucketStatus,
+        unauthenticated: RateLimitBucketStatus,
+    ) -> Self {
+        Self {
+            authenticated,
+            unauthenticated,
+        }
+    }
+
+    /// Status of authenticated requests.
+    pub fn authenticated(&self) -> &RateLimitBucketStatus {
+        &self.authenticated
+    }
+
+    /// Status of unauthenticated requests.
+    pub fn unauthenticated(&self) -> &RateLimitBucketStatus {
+        &self.unauthenticated
+    }
 }
 
 #[cfg(test)]
@@ -174,6 +372,31 @@ mod tests {
         assert_eq!(*order, vec![0, 1, 2]);
     }
 
+    #[tokio::test]
+    async fn local_status_reports_wait() {
+        let rl = RateLimiter::new(Duration::from_millis(200), Duration::from_millis(50));
+        assert_eq!(rl.local_status(true).available_in(), Duration::ZERO);
+
+        rl.acquire(true).await;
+        let status = rl.local_status(true);
+
+        assert_eq!(status.interval(), Duration::from_millis(200));
+        assert_eq!(status.queued(), 0);
+        assert!(status.available_in() > Duration::from_millis(150));
+        assert_eq!(rl.local_status(false).available_in(), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn cancelled_acquire_is_not_queued() {
+        let rl = RateLimiter::new(Duration::from_millis(200), Duration::from_millis(50));
+        rl.acquire(true).await;
+
+        let cancelled = tokio::time::timeout(Duration::from_millis(20), rl.acquire(true)).await;
+        assert!(cancelled.is_err());
+
+        assert_eq!(rl.local_status(true).queued(), 0);
+    }
+
     #[tokio::test]
     async fn multiple_requests_paced() {
         let rl = RateLimiter::new(Duration::from_millis(20), Duration::from_secs(1));
@@ -0,0 +1,160 @@
+use std::{collections::BTreeSet, fmt, sync::Mutex};
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::super::error::{RestApiError, Result};
+
+/// Permission that can be granted to an LNM API key.
+///
+/// See the **Required permissions** of each endpoint's documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ApiScope {
+    #[serde(rename = "account:read")]
+    AccountRead,
+    #[serde(rename = "account:deposits:read")]
+    AccountDepositsRead,
+    #[serde(rename = "account:deposits:write")]
+    AccountDepositsWrite,
+    #[serde(rename = "account:notifications:read")]
+    AccountNotificationsRead,
+    #[serde(rename = "account:notifications:write")]
+    AccountNotificationsWrite,
+    #[serde(rename = "account:withdrawals:read")]
+    AccountWithdrawalsRead,
+    #[serde(rename = "account:withdrawals:write")]
+    AccountWithdrawalsWrite,
+    #[serde(rename = "futures:isolated:read")]
+    FuturesIsolatedRead,
+    #[serde(rename = "futures:isolated:write")]
+    FuturesIsolatedWrite,
+    #[serde(rename = "futures:cross:read")]
+    FuturesCrossRead,
+    #[serde(rename = "futures:cross:write")]
+    FuturesCrossWrite,
+    #[serde(rename = "synthetic-usd:read")]
+    SyntheticUsdRead,
+    #[serde(rename = "synthetic-usd:write")]
+    SyntheticUsdWrite,
+}
+
+impl ApiScope {
+    /// All scopes, in declaration order.
+    pub const ALL: [ApiScope; 13] = [
+        Self::AccountRead,
+        Self::AccountDepositsRead,
+        Self::AccountDepositsWrite,
+        Self::AccountNotificationsRead,
+        Self::AccountNotificationsWrite,
+        Self::AccountWithdrawalsRead,
+        Self::AccountWithdrawalsWrite,
+        Self::FuturesIsolatedRead,
+        Self::FuturesIsolatedWrite,
+        Self::FuturesCrossRead,
+        Self::FuturesCrossWrite,
+        Self::SyntheticUsdRead,
+        Self::SyntheticUsdWrite,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AccountRead => "account:read",
+            Self::AccountDepositsRead => "account:deposits:read",
+            Self::AccountDepositsWrite => "account:deposits:write",
+            Self::AccountNotificationsRead => "account:notifications:read",
+            Self::AccountNotificationsWrite => "account:notifications:write",
+            Self::AccountWithdrawalsRead => "account:withdrawals:read",
+            Self::AccountWithdrawalsWrite => "account:withdrawals:write",
+            Self::FuturesIsolatedRead => "futures:isolated:read",
+            Self::FuturesIsolatedWrite => "futures:isolated:write",
+            Self::FuturesCrossRead => "futures:cross:read",
+            Self::FuturesCrossWrite => "futures:cross:write",
+            Self::SyntheticUsdRead => "synthetic-usd:read",
+            Self::SyntheticUsdWrite => "synthetic-usd:write",
+        }
+    }
+
+    /// Returns whether the scope grants write access.
+    pub fn is_write(&self) -> bool {
+        self.as_str().ends_with(":write")
+    }
+}
+
+impl fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Scopes granted to the client's API key, if known, checked before requests are sent.
+pub(crate) struct ScopeGuard(Mutex<Option<BTreeSet<ApiScope>>>);
+
+impl ScopeGuard {
+    pub fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    fn scopes(&self) -> std::sync::MutexGuard<'_, Option<BTreeSet<ApiScope>>> {
+        self.0.lock().expect("`ScopeGuard` mutex can't be poisoned")
+    }
+
+    pub fn granted(&self) -> Option<BTreeSet<ApiScope>> {
+        self.scopes().clone()
+    }
+
+    pub fn set_granted(&self, scopes: Option<BTreeSet<ApiScope>>) {
+        *self.scopes() = scopes;
+    }
+
+    /// Rejects the request if the granted scopes are known and don't include `required`.
+    pub fn check(&self, required: ApiScope, method: &Method, path: &str) -> Result<()> {
+        match &*self.scopes() {
+            Some(granted) if !granted.contains(&required) => Err(RestApiError::MissingScope {
+                scope: required,
+                method: method.clone(),
+                path: path.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_serde() {
+        for scope in ApiScope::ALL {
+            let json = serde_json::to_value(scope).unwrap();
+
+            assert_eq!(json, scope.as_str());
+            assert_eq!(serde_json::from_value::<ApiScope>(json).unwrap(), scope);
+        }
+
+        assert!(ApiScope::FuturesCrossWrite.is_write());
+        assert!(!ApiScope::AccountRead.is_write());
+    }
+
+    #[test]
+    fn test_scope_guard() {
+        let guard = ScopeGuard::new();
+        let method = Method::POST;
+
+        assert!(
+            guard
+                .check(ApiScope::AccountWithdrawalsWrite, &method, "/")
+                .is_ok()
+        );
+
+        guard.set_granted(Some([ApiScope::AccountRead].into()));
+        assert!(guard.check(ApiScope::AccountRead, &method, "/").is_ok());
+        assert!(matches!(
+            guard.check(ApiScope::AccountWithdrawalsWrite, &method, "/"),
+            Err(RestApiError::MissingScope {
+                scope: ApiScope::AccountWithdrawalsWrite,
+                ..
+            })
+        ));
+    }
+}
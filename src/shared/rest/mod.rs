@@ -1,2 +1,6 @@
+pub(crate) mod canonical;
 pub(crate) mod error;
 pub(crate) mod lnm;
+pub(crate) mod query;
+pub(crate) mod raw;
+pub(crate) mod timing;
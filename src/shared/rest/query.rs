@@ -0,0 +1,197 @@
+use std::{fmt, vec};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::Url;
+
+use super::canonical;
+
+/// Query parameters of a REST request.
+///
+/// Parameters are kept in insertion order, which is the order they are encoded in the request
+/// URL, and therefore the order used when signing authenticated requests. Optional parameters
+/// that are `None` are skipped, and timestamps are encoded as RFC 3339 with millisecond
+/// precision, as expected by the API.
+///
+/// Can be used with [`SansIoClient`](crate::rest::v3::sans_io::SansIoClient) and
+/// [`RestClient::raw_request`](crate::rest::v3::RestClient::raw_request) to build requests for
+/// custom endpoints.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::QueryParams;
+///
+/// let limit: Option<u64> = Some(10);
+///
+/// let params = QueryParams::new()
+///     .with("read", false)
+///     .with_opt("limit", limit)
+///     .with_opt("cursor", None::<String>);
+///
+/// assert_eq!(params.to_string(), "read=false&limit=10");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryParams(Vec<(String, String)>);
+
+impl QueryParams {
+    /// Creates an empty set of query parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the `from`, `to`, `limit` and `cursor` query parameters shared by paginated
+    /// endpoints.
+    pub(crate) fn paginated(
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<impl ToString>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self::new()
+            .with_datetime_opt("from", from)
+            .with_datetime_opt("to", to)
+            .with_opt("limit", limit)
+            .with_datetime_opt("cursor", cursor)
+    }
+
+    /// Appends a parameter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lnm_sdk::rest::v3::QueryParams;
+    ///
+    /// let params = QueryParams::new().with("limit", 10);
+    ///
+    /// assert_eq!(params.to_string(), "limit=10");
+    /// ```
+    pub fn with(mut self, key: impl ToString, value: impl ToString) -> Self {
+        self.0.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Appends a parameter if `value` is `Some`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lnm_sdk::rest::v3::QueryParams;
+    ///
+    /// let params = QueryParams::new()
+    ///     .with_opt("read", Some(true))
+    ///     .with_opt("limit", None::<u64>);
+    ///
+    /// assert_eq!(params.to_string(), "read=true");
+    /// ```
+    pub fn with_opt(self, key: impl ToString, value: Option<impl ToString>) -> Self {
+        match value {
+            Some(value) => self.with(key, value),
+            None => self,
+        }
+    }
+
+    /// Appends a timestamp parameter, encoded as RFC 3339 with millisecond precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::DateTime;
+    /// use lnm_sdk::rest::v3::QueryParams;
+    ///
+    /// let from = DateTime::from_timestamp_millis(1747035005657).unwrap();
+    /// let params = QueryParams::new().with_datetime("from", from);
+    ///
+    /// assert_eq!(params.to_string(), "from=2025-05-12T07%3A30%3A05.657Z");
+    /// ```
+    pub fn with_datetime(self, key: impl ToString, value: DateTime<Utc>) -> Self {
+        self.with(key, value.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+
+    /// Appends a timestamp parameter if `value` is `Some`, encoded as RFC 3339 with millisecond
+    /// precision.
+    pub fn with_datetime_opt(self, key: impl ToString, value: Option<DateTime<Utc>>) -> Self {
+        match value {
+            Some(value) => self.with_datetime(key, value),
+            None => self,
+        }
+    }
+
+    /// Returns `true` if there are no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of parameters.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns an iterator over the parameters, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Appends the parameters to the query of the given URL.
+    pub(crate) fn append_to(&self, url: &mut Url) {
+        if !self.is_empty() {
+            url.set_query(Some(&canonical::query_string(self)));
+        }
+    }
+}
+
+impl IntoIterator for QueryParams {
+    type Item = (String, String);
+    type IntoIter = vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<K: ToString, V: ToString> FromIterator<(K, V)> for QueryParams {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+}
+
+/// Formats the parameters as a percent-encoded query string, without the leading `?`, exactly as
+/// sent and signed.
+impl fmt::Display for QueryParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&canonical::query_string(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginated_skips_none_and_keeps_order() {
+        let to = DateTime::from_timestamp_millis(0).unwrap();
+
+        let params = QueryParams::paginated(None, Some(to), Some(5), None);
+
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("to", "1970-01-01T00:00:00.000Z"), ("limit", "5")]
+        );
+    }
+
+    #[test]
+    fn test_append_to_url() {
+        let mut url = Url::parse("https://api.lnmarkets.com/v3/futures/ticker").unwrap();
+        QueryParams::new().append_to(&mut url);
+        assert_eq!(url.query(), None);
+
+        QueryParams::new()
+            .with("a", "x y")
+            .with("b", 1)
+            .append_to(&mut url);
+        assert_eq!(url.query(), Some("a=x%20y&b=1"));
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Deserializer, de::DeserializeOwned};
+use serde_json::value::RawValue;
+
+/// Response wrapper that keeps the exact JSON returned by the API alongside the typed model.
+///
+/// Useful for archiving exchange responses verbatim, for auditing and dispute resolution. Can be
+/// used as the response type of [`RestClient::raw_request`] and
+/// [`SansIoClient::parse_response`].
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::rest::v3::WithRaw;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Ticker {
+///     #[serde(rename = "lastPrice")]
+///     last_price: f64,
+/// }
+///
+/// let response: WithRaw<Ticker> =
+///     serde_json::from_str(r#"{ "lastPrice": 100000.5, "extra": true }"#).unwrap();
+///
+/// assert_eq!(response.value().last_price, 100000.5);
+/// assert_eq!(response.raw(), r#"{ "lastPrice": 100000.5, "extra": true }"#);
+/// ```
+///
+/// [`RestClient::raw_request`]: crate::rest::v3::RestClient::raw_request
+/// [`SansIoClient::parse_response`]: crate::rest::v3::sans_io::SansIoClient::parse_response
+#[derive(Debug)]
+pub struct WithRaw<T> {
+    value: T,
+    raw: Box<RawValue>,
+}
+
+impl<T> WithRaw<T> {
+    /// Returns the typed model.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the exact JSON of the response, as received.
+    pub fn raw(&self) -> &str {
+        self.raw.get()
+    }
+
+    /// Parses the raw JSON of the response into a [`serde_json::Value`].
+    pub fn raw_value(&self) -> serde_json::Value {
+        serde_json::from_str(self.raw.get()).expect("raw response must be valid JSON")
+    }
+
+    /// Consumes the wrapper, returning the typed model and the exact JSON of the response.
+    pub fn into_parts(self) -> (T, String) {
+        (self.value, self.raw.get().to_string())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for WithRaw<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        let value = serde_json::from_str(raw.get()).map_err(serde::de::Error::custom)?;
+
+        Ok(Self { value, raw })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_raw_keeps_exact_json() {
+        let json = r#"[ 1, 2,   3 ]"#;
+
+        let response: WithRaw<Vec<u8>> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.value(), &vec![1, 2, 3]);
+        assert_eq!(response.raw(), json);
+        assert_eq!(response.raw_value(), serde_json::json!([1, 2, 3]));
+
+        let (value, raw) = response.into_parts();
+        assert_eq!(value, vec![1, 2, 3]);
+        assert_eq!(raw, json);
+    }
+
+    #[test]
+    fn test_with_raw_propagates_model_errors() {
+        let result = serde_json::from_str::<WithRaw<Vec<u8>>>(r#"[ "a" ]"#);
+
+        assert!(result.is_err());
+    }
+}
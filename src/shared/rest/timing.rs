@@ -0,0 +1,256 @@
+use std::{cell::RefCell, future::Future, time::Duration};
+
+use reqwest::{Method, header::HeaderMap};
+use uuid::Uuid;
+
+tokio::task_local! {
+    static COLLECTED: RefCell<Vec<RequestTiming>>;
+}
+
+/// Timing breakdown of a single REST request, collected with
+/// [`RestClient::timed`](crate::rest::v3::RestClient::timed).
+///
+/// Durations are measured with a monotonic clock, at nanosecond resolution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestTiming {
+    method: Method,
+    path: String,
+    correlation_id: Uuid,
+    queue_wait: Duration,
+    signing: Duration,
+    round_trip: Duration,
+    server_processing: Option<Duration>,
+    body_read: Duration,
+    total: Duration,
+}
+
+impl RequestTiming {
+    pub(crate) fn new(method: Method, path: String, correlation_id: Uuid) -> Self {
+        Self {
+            method,
+            path,
+            correlation_id,
+            queue_wait: Duration::ZERO,
+            signing: Duration::ZERO,
+            round_trip: Duration::ZERO,
+            server_processing: None,
+            body_read: Duration::ZERO,
+            total: Duration::ZERO,
+        }
+    }
+
+    pub(crate) fn set_queue_wait(&mut self, queue_wait: Duration) {
+        self.queue_wait = queue_wait;
+    }
+
+    pub(crate) fn set_signing(&mut self, signing: Duration) {
+        self.signing = signing;
+    }
+
+    /// Sets the round trip, and the server processing time reported by the response `headers`.
+    pub(crate) fn set_response(&mut self, round_trip: Duration, headers: &HeaderMap) {
+        self.round_trip = round_trip;
+        self.server_processing = parse_server_processing(headers);
+    }
+
+    pub(crate) fn set_body_read(&mut self, body_read: Duration) {
+        self.body_read = body_read;
+    }
+
+    pub(crate) fn set_total(&mut self, total: Duration) {
+        self.total = total;
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Path of the request, including the API version prefix (e.g. `/v3/futures/isolated/trade`).
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Correlation ID sent with the request, in the `X-Correlation-Id` header. Also included in
+    /// the errors and debug logs of the request.
+    pub fn correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    /// Time spent waiting for the client-side rate limiter.
+    pub fn queue_wait(&self) -> Duration {
+        self.queue_wait
+    }
+
+    /// Time spent building and signing the request.
+    pub fn signing(&self) -> Duration {
+        self.signing
+    }
+
+    /// Time from sending the request until the response headers were received, including DNS
+    /// resolution, connection and TLS setup when no pooled connection was available.
+    pub fn round_trip(&self) -> Duration {
+        self.round_trip
+    }
+
+    /// Server processing time, as reported by the `Server-Timing` (largest `dur`) or
+    /// `X-Response-Time` response header, if any.
+    pub fn server_processing(&self) -> Option<Duration> {
+        self.server_processing
+    }
+
+    /// Part of the [round trip](Self::round_trip) not spent processing on the server (network,
+    /// connection setup and TLS), if the server reported its processing time.
+    pub fn network(&self) -> Option<Duration> {
+        self.server_processing
+            .map(|server_processing| self.round_trip.saturating_sub(server_processing))
+    }
+
+    /// Time spent reading the response body.
+    pub fn body_read(&self) -> Duration {
+        self.body_read
+    }
+
+    /// Total time spent on the request, from the rate limiter queue until the response body was
+    /// read.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+}
+
+/// Reads the server processing time from the `Server-Timing` or `X-Response-Time` headers.
+fn parse_server_processing(headers: &HeaderMap) -> Option<Duration> {
+    let millis_to_duration = |millis: f64| {
+        (millis.is_finite() && millis >= 0.).then(|| Duration::from_secs_f64(millis / 1_000.))
+    };
+
+    let server_timing = headers
+        .get_all("server-timing")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .flat_map(|metric| metric.split(';'))
+        .filter_map(|param| param.trim().strip_prefix("dur="))
+        .filter_map(|dur| dur.trim_matches('"').parse::<f64>().ok())
+        .filter_map(millis_to_duration)
+        .max();
+    if server_timing.is_some() {
+        return server_timing;
+    }
+
+    let response_time = headers.get("x-response-time")?.to_str().ok()?.trim();
+    let millis = response_time
+        .strip_suffix("ms")
+        .unwrap_or(response_time)
+        .trim()
+        .parse()
+        .ok()?;
+
+    millis_to_duration(millis)
+}
+
+/// Records `timing` if called within [`collect`].
+pub(crate) fn record(timing: RequestTiming) {
+    let _ = COLLECTED.try_with(|collected| collected.borrow_mut().push(timing));
+}
+
+/// Runs `future`, collecting the timings of the requests it performs on the current task.
+pub(crate) async fn collect<F: Future>(future: F) -> (F::Output, Vec<RequestTiming>) {
+    COLLECTED
+        .scope(RefCell::new(Vec::new()), async move {
+            let output = future.await;
+            let timings = COLLECTED.with(|collected| collected.take());
+
+            (output, timings)
+        })
+        .await
+}
+
+/// Response wrapper returned by [`RestClient::timed`](crate::rest::v3::RestClient::timed), with
+/// the timing breakdown of each request performed to obtain the value.
+#[derive(Debug)]
+pub struct Timed<T> {
+    value: T,
+    timings: Vec<RequestTiming>,
+}
+
+impl<T> Timed<T> {
+    pub(crate) fn new(value: T, timings: Vec<RequestTiming>) -> Self {
+        Self { value, timings }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Timings of the requests performed, in completion order.
+    pub fn timings(&self) -> &[RequestTiming] {
+        &self.timings
+    }
+
+    /// Timing of the last request performed, usually the one that returned the value.
+    pub fn last_timing(&self) -> Option<&RequestTiming> {
+        self.timings.last()
+    }
+
+    /// Consumes the wrapper, returning the value and the request timings.
+    pub fn into_parts(self) -> (T, Vec<RequestTiming>) {
+        (self.value, self.timings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_server_processing() {
+        assert_eq!(parse_server_processing(&HeaderMap::new()), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-response-time", HeaderValue::from_static("12.5ms"));
+        assert_eq!(
+            parse_server_processing(&headers),
+            Some(Duration::from_micros(12_500))
+        );
+
+        headers.insert(
+            "server-timing",
+            HeaderValue::from_static(r#"db;dur=2.25, app;desc="handler";dur=0.75"#),
+        );
+        assert_eq!(
+            parse_server_processing(&headers),
+            Some(Duration::from_micros(2_250))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_timings() {
+        record(RequestTiming::new(
+            Method::GET,
+            "/v3/ignored".to_string(),
+            Uuid::nil(),
+        ));
+
+        let (output, timings) = collect(async {
+            record(RequestTiming::new(
+                Method::GET,
+                "/v3/ticker".to_string(),
+                Uuid::nil(),
+            ));
+            record(RequestTiming::new(
+                Method::POST,
+                "/v3/trade".to_string(),
+                Uuid::nil(),
+            ));
+            42
+        })
+        .await;
+
+        assert_eq!(output, 42);
+        assert_eq!(timings.len(), 2);
+        assert_eq!(timings[1].path(), "/v3/trade");
+        assert_eq!(timings[1].network(), None);
+    }
+}
@@ -1,4 +1,8 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
+
+use crate::shared::clock::{Clock, SystemClock};
+
+use super::{polling::RestFallback, recording::FrameSink};
 
 /// Configuration for the Stream v1 WebSocket client.
 #[derive(Clone, Debug)]
@@ -9,6 +13,9 @@ pub struct StreamClientConfig {
     reconnect_initial_backoff: Duration,
     reconnect_max_backoff: Duration,
     reconnect_max_attempts: Option<usize>,
+    frame_sink: Option<Arc<dyn FrameSink>>,
+    clock: Arc<dyn Clock>,
+    rest_fallback: Option<RestFallback>,
 }
 
 impl StreamClientConfig {
@@ -53,6 +60,21 @@ impl StreamClientConfig {
         self.reconnect_max_attempts
     }
 
+    /// Returns the sink raw frames are recorded to, if any.
+    pub fn frame_sink(&self) -> Option<&Arc<dyn FrameSink>> {
+        self.frame_sink.as_ref()
+    }
+
+    /// Returns the clock the client reads the current time from.
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Returns the REST API polled when the WebSocket can't connect, if any.
+    pub fn rest_fallback(&self) -> Option<&RestFallback> {
+        self.rest_fallback.as_ref()
+    }
+
     /// Sets the Stream API endpoint.
     ///
     /// Default: `wss://stream.lnmarkets.com/v1`
@@ -100,6 +122,35 @@ impl StreamClientConfig {
         self.reconnect_max_attempts = reconnect_max_attempts;
         self
     }
+
+    /// Records every raw text frame received, timestamped, to `sink`, across reconnections.
+    ///
+    /// See [`recording`](super::recording) for the available sinks.
+    ///
+    /// Default: `None`
+    pub fn with_frame_sink(mut self, sink: Arc<dyn FrameSink>) -> Self {
+        self.frame_sink = Some(sink);
+        self
+    }
+
+    /// Sets the clock the client reads the current time from.
+    ///
+    /// Used for authentication timestamps and the reception time of recorded frames.
+    ///
+    /// Default: [`SystemClock`]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Backs the price and cross position topics by polling the REST API when the WebSocket
+    /// can't connect, e.g. on networks blocking WebSockets. See [`RestFallback`].
+    ///
+    /// Default: `None`, connection errors are returned
+    pub fn with_rest_fallback(mut self, fallback: RestFallback) -> Self {
+        self.rest_fallback = Some(fallback);
+        self
+    }
 }
 
 impl Default for StreamClientConfig {
@@ -111,6 +162,9 @@ impl Default for StreamClientConfig {
             reconnect_initial_backoff: Duration::from_secs(1),
             reconnect_max_backoff: Duration::from_secs(30),
             reconnect_max_attempts: None,
+            frame_sink: None,
+            clock: Arc::new(SystemClock),
+            rest_fallback: None,
         }
     }
 }
@@ -0,0 +1,227 @@
+use std::{collections::HashMap, time::Duration};
+
+use tokio::{
+    sync::broadcast::{Receiver, error::RecvError},
+    time::{self, Instant},
+};
+
+use super::models::{topic::StreamTopic, update::StreamUpdate};
+
+/// Price topics whose updates are conflated.
+const CONFLATED_TOPICS: [StreamTopic; 3] = [
+    StreamTopic::FuturesInverseBtcUsdTicker,
+    StreamTopic::FuturesInverseBtcUsdLastPrice,
+    StreamTopic::FuturesInverseBtcUsdIndex,
+];
+
+/// Receiver of Stream updates that delivers at most one price update per topic per interval.
+///
+/// Ticker, last price and index updates are conflated per topic: the first update is delivered
+/// immediately, and updates received within the following interval replace each other, the
+/// latest being delivered once the interval elapses. Other updates, such as trade, order and
+/// connection status updates, are never conflated, and are delivered as soon as received.
+///
+/// Reduces wake-ups for strategies that only need prices at a coarser granularity than the
+/// stream provides. Created with
+/// [`StreamRepository::conflated_receiver`](super::StreamRepository::conflated_receiver).
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(conn: lnm_sdk::stream::v1::StreamConnection) -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
+///
+/// use lnm_sdk::stream::v1::models::{PriceReference, StreamTopic};
+///
+/// conn.subscribe(vec![StreamTopic::FuturesInverseBtcUsdIndex])
+///     .await?;
+///
+/// let mut rx = conn.conflated_receiver(Duration::from_millis(100)).await?;
+/// while let Ok(update) = rx.recv().await {
+///     if let Some(index) = update.price(PriceReference::Index) {
+///         println!("Index: {index}");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConflatedReceiver {
+    rx: Receiver<StreamUpdate>,
+    interval: Duration,
+    pending: HashMap<StreamTopic, StreamUpdate>,
+    last_delivered: HashMap<StreamTopic, Instant>,
+    closed: bool,
+}
+
+impl ConflatedReceiver {
+    /// Wraps `rx`, delivering at most one price update per topic per `interval`.
+    pub fn new(rx: Receiver<StreamUpdate>, interval: Duration) -> Self {
+        Self {
+            rx,
+            interval,
+            pending: HashMap::new(),
+            last_delivered: HashMap::new(),
+            closed: false,
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn next_delivery(&self, topic: &StreamTopic) -> Option<Instant> {
+        self.last_delivered
+            .get(topic)
+            .map(|last_delivered| *last_delivered + self.interval)
+    }
+
+    fn deliver(&mut self, topic: StreamTopic, update: StreamUpdate) -> StreamUpdate {
+        self.last_delivered.insert(topic, Instant::now());
+        update
+    }
+
+    /// Returns the pending update due the earliest, with its due time.
+    fn next_pending(&self) -> Option<(StreamTopic, Instant)> {
+        self.pending
+            .keys()
+            .map(|topic| {
+                let due = self.next_delivery(topic).unwrap_or_else(Instant::now);
+                (topic.clone(), due)
+            })
+            .min_by_key(|(_, due)| *due)
+    }
+
+    /// Receives the next update.
+    ///
+    /// Fails like [`Receiver::recv`]. If the receiver lagged behind, the conflation state is kept,
+    /// and the next call resumes receiving. Once the stream is closed, pending price updates are
+    /// delivered before [`RecvError::Closed`] is returned.
+    pub async fn recv(&mut self) -> Result<StreamUpdate, RecvError> {
+        loop {
+            let next_pending = self.next_pending();
+
+            if let Some((topic, due)) = &next_pending
+                && (self.closed || *due <= Instant::now())
+            {
+                let update = self
+                    .pending
+                    .remove(topic)
+                    .expect("pending topic must have an update");
+                return Ok(self.deliver(topic.clone(), update));
+            }
+
+            if self.closed {
+                return Err(RecvError::Closed);
+            }
+
+            let received = match next_pending {
+                Some((_, due)) => tokio::select! {
+                    received = self.rx.recv() => received,
+                    _ = time::sleep_until(due) => continue,
+                },
+                None => self.rx.recv().await,
+            };
+
+            let update = match received {
+                Ok(update) => update,
+                Err(RecvError::Closed) => {
+                    self.closed = true;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let Some(topic) = update
+                .topic()
+                .filter(|topic| CONFLATED_TOPICS.contains(topic))
+            else {
+                return Ok(update);
+            };
+
+            match self.next_delivery(&topic) {
+                Some(due) if due > Instant::now() => {
+                    self.pending.insert(topic, update);
+                }
+                _ => {
+                    self.pending.remove(&topic);
+                    return Ok(self.deliver(topic, update));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::broadcast;
+
+    use super::*;
+    use crate::{
+        shared::models::oracle::{Index, LastPrice},
+        stream::v1::StreamConnectionStatus,
+    };
+
+    fn index(value: i32) -> StreamUpdate {
+        StreamUpdate::FuturesInverseBtcUsdIndex(
+            serde_json::from_value::<Index>(serde_json::json!({
+                "index": value,
+                "time": "2025-01-01T00:00:00.000Z",
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn last_price(value: i32) -> StreamUpdate {
+        StreamUpdate::FuturesInverseBtcUsdLastPrice(
+            serde_json::from_value::<LastPrice>(serde_json::json!({
+                "lastPrice": value,
+                "time": "2025-01-01T00:00:00.000Z",
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn index_value(update: &StreamUpdate) -> f64 {
+        match update {
+            StreamUpdate::FuturesInverseBtcUsdIndex(index) => index.index().as_f64(),
+            other => panic!("unexpected update: {other:?}"),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_conflates_price_updates() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut rx = ConflatedReceiver::new(rx, Duration::from_millis(100));
+
+        tx.send(index(100_000)).unwrap();
+        assert_eq!(index_value(&rx.recv().await.unwrap()), 100_000.);
+
+        // Within the interval, the latest index wins, and other updates pass through
+        tx.send(index(100_001)).unwrap();
+        tx.send(last_price(100_010)).unwrap();
+        tx.send(index(100_002)).unwrap();
+        tx.send(StreamUpdate::ConnectionStatus(
+            StreamConnectionStatus::Connected,
+        ))
+        .unwrap();
+
+        let started_at = Instant::now();
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            StreamUpdate::FuturesInverseBtcUsdLastPrice(_)
+        ));
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            StreamUpdate::ConnectionStatus(_)
+        ));
+        assert_eq!(index_value(&rx.recv().await.unwrap()), 100_002.);
+        assert_eq!(started_at.elapsed(), Duration::from_millis(100));
+
+        // Pending updates are flushed once the stream is closed
+        tx.send(index(100_003)).unwrap();
+        drop(tx);
+        assert_eq!(index_value(&rx.recv().await.unwrap()), 100_003.);
+        assert!(matches!(rx.recv().await, Err(RecvError::Closed)));
+    }
+}
@@ -13,7 +13,9 @@ use tokio::{
 use tokio_rustls::rustls::pki_types::InvalidDnsNameError;
 
 pub use super::lnm::TopicStatus;
-pub use crate::shared::models::error::OhlcRangeParseError;
+pub use crate::shared::models::error::{
+    CurrencyParseError, InstrumentParseError, OhlcRangeParseError,
+};
 
 use super::{
     models::{topic::StreamTopic, update::StreamUpdate},
@@ -103,6 +105,10 @@ pub enum StreamConnectionError {
     #[error("DecodeJson error, {0}")]
     DecodeJson(serde_json::Error),
 
+    #[cfg(feature = "simd-json")]
+    #[error("DecodeSimdJson error, {0}")]
+    DecodeSimdJson(simd_json::Error),
+
     #[error("UnhandledOpCode error, {0:?}")]
     UnhandledOpCode(OpCode),
 
@@ -221,6 +227,12 @@ pub enum StreamApiError {
 
     #[error("Stream WebSocket disconnect timeout")]
     DisconnectTimeout,
+
+    #[error("{0} is not supported while polling the REST API")]
+    NotSupportedWhilePolling(&'static str),
+
+    #[error("Topic {0} can't be polled from the REST API")]
+    TopicNotPolled(StreamTopic),
 }
 
 pub(super) type Result<T> = result::Result<T, StreamApiError>;
@@ -0,0 +1,531 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    rest::v3::{
+        LnmFuturesApi,
+        snapshot::{self, Snapshot},
+        state_store::{StateStore, StateStoreError},
+    },
+    shared::{
+        clock::{Clock, SystemClock},
+        rest::error::RestApiError,
+    },
+};
+
+use super::models::{
+    trade::{StreamCrossOrderEvent, StreamCrossPositionEvent, StreamIsolatedTradeEvent},
+    update::StreamUpdate,
+    wallet::{StreamWalletDeposit, StreamWalletWithdrawal},
+};
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum EventLogError {
+    #[error("Account event serialization failed. Error: {0}")]
+    EventSerialize(#[source] serde_json::Error),
+
+    #[error("Account event log entry `{key}` could not be parsed. Error: {e}")]
+    EntryParse {
+        key: String,
+        #[source]
+        e: serde_json::Error,
+    },
+
+    #[error("Cursor {cursor} can't be acknowledged, the last recorded cursor is {last}")]
+    UnknownCursor { cursor: u64, last: u64 },
+
+    #[error("Account snapshot request failed. Error: {0}")]
+    Snapshot(#[source] RestApiError),
+
+    #[error(transparent)]
+    StateStore(#[from] StateStoreError),
+}
+
+pub type Result<T> = std::result::Result<T, EventLogError>;
+
+/// Private account event received from the stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum AccountEvent {
+    IsolatedTrade(StreamIsolatedTradeEvent),
+    CrossOrder(StreamCrossOrderEvent),
+    CrossPosition(StreamCrossPositionEvent),
+    WalletDeposit(StreamWalletDeposit),
+    WalletWithdrawal(StreamWalletWithdrawal),
+}
+
+impl AccountEvent {
+    /// Extracts the account event from a private topic update.
+    ///
+    /// Returns `None` for public topic and connection status updates.
+    pub fn from_update(update: &StreamUpdate) -> Option<Self> {
+        match update {
+            StreamUpdate::FuturesInverseBtcUsdIsolatedTrades(event) => {
+                Some(Self::IsolatedTrade(event.clone()))
+            }
+            StreamUpdate::FuturesInverseBtcUsdCrossOrders(event) => {
+                Some(Self::CrossOrder(event.clone()))
+            }
+            StreamUpdate::FuturesInverseBtcUsdCrossPosition(event) => {
+                Some(Self::CrossPosition(event.clone()))
+            }
+            StreamUpdate::WalletDeposit(deposit) => Some(Self::WalletDeposit(deposit.clone())),
+            StreamUpdate::WalletWithdrawal(withdrawal) => {
+                Some(Self::WalletWithdrawal(withdrawal.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<AccountEvent> for StreamUpdate {
+    fn from(event: AccountEvent) -> Self {
+        match event {
+            AccountEvent::IsolatedTrade(event) => Self::FuturesInverseBtcUsdIsolatedTrades(event),
+            AccountEvent::CrossOrder(event) => Self::FuturesInverseBtcUsdCrossOrders(event),
+            AccountEvent::CrossPosition(event) => Self::FuturesInverseBtcUsdCrossPosition(event),
+            AccountEvent::WalletDeposit(deposit) => Self::WalletDeposit(deposit),
+            AccountEvent::WalletWithdrawal(withdrawal) => Self::WalletWithdrawal(withdrawal),
+        }
+    }
+}
+
+/// Account event recorded in the log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedEvent {
+    cursor: u64,
+    received_at: DateTime<Utc>,
+    event: AccountEvent,
+}
+
+impl PersistedEvent {
+    /// Position of the event in the log. Cursors start at 1, and increase by 1 with each event.
+    pub fn cursor(&self) -> u64 {
+        self.cursor
+    }
+
+    /// Timestamp when the event was recorded.
+    pub fn received_at(&self) -> DateTime<Utc> {
+        self.received_at
+    }
+
+    pub fn event(&self) -> &AccountEvent {
+        &self.event
+    }
+
+    pub fn into_event(self) -> AccountEvent {
+        self.event
+    }
+}
+
+/// Unacknowledged events and current account state, returned by [`AccountEventLog::resume`].
+#[derive(Debug, Clone)]
+pub struct Resume {
+    events: Vec<PersistedEvent>,
+    snapshot: Snapshot,
+}
+
+impl Resume {
+    /// Events recorded but not acknowledged, in cursor order.
+    pub fn events(&self) -> &[PersistedEvent] {
+        &self.events
+    }
+
+    /// Account state fetched from the REST API after the events were loaded.
+    pub fn snapshot(&self) -> &Snapshot {
+        &self.snapshot
+    }
+
+    pub fn into_parts(self) -> (Vec<PersistedEvent>, Snapshot) {
+        (self.events, self.snapshot)
+    }
+}
+
+/// Durable log of private account events, backed by a [`StateStore`].
+///
+/// [`record_update`](Self::record_update) durably appends each isolated trade, cross order, cross
+/// position and wallet update received from the stream under a monotonically increasing cursor,
+/// before it is handed to the application. Once an event has been processed, its cursor is
+/// [acknowledged](Self::ack). On restart, [`resume`](Self::resume) returns the events that were
+/// recorded but never acknowledged, along with a REST [`Snapshot`] of the account, so that events
+/// missed while the process was down are reconciled against the current state.
+///
+/// Events are only acknowledged after being processed, so an event may be delivered again after a
+/// crash, but never lost: consumers must handle duplicates, for instance by ignoring updates of
+/// trades and orders whose state they already hold.
+///
+/// Acknowledged events are deleted from the store, so the log only holds the pending events.
+///
+/// Events are kept under `{prefix}events/{cursor}` keys, with the cursor zero-padded to 20 digits
+/// so that keys sort in cursor order, and the last acknowledged cursor under `{prefix}cursor`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(
+/// #     conn: lnm_sdk::stream::v1::StreamConnection,
+/// #     rest: lnm_sdk::rest::v3::RestClient,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::{
+///     rest::v3::state_store::FileStateStore,
+///     stream::v1::{event_log::AccountEventLog, models::StreamTopic},
+/// };
+///
+/// let log = AccountEventLog::open(FileStateStore::new("./state"), "account/").await?;
+///
+/// // Subscribe before resuming, so that no event is missed in between
+/// conn.subscribe(vec![
+///     StreamTopic::FuturesInverseBtcUsdIsolatedTrades,
+///     StreamTopic::FuturesInverseBtcUsdCrossOrders,
+/// ])
+/// .await?;
+/// let mut rx = conn.receiver().await?;
+///
+/// let resume = log.resume(&rest).await?;
+/// // Reconcile with `resume.snapshot()`, then process `resume.events()`...
+/// if let Some(last) = resume.events().last() {
+///     log.ack(last.cursor()).await?;
+/// }
+///
+/// while let Ok(update) = rx.recv().await {
+///     if let Some(event) = log.record_update(&update).await? {
+///         // Process the event...
+///         log.ack(event.cursor()).await?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct AccountEventLog<S: StateStore> {
+    store: S,
+    prefix: String,
+    clock: Arc<dyn Clock>,
+    last_cursor: Mutex<u64>,
+}
+
+impl<S: StateStore> AccountEventLog<S> {
+    /// Opens the log kept in `store`, under keys starting with `prefix`, resuming the cursor
+    /// sequence after the last recorded event.
+    ///
+    /// Events acknowledged but left in the store by an interrupted [`ack`](Self::ack) are
+    /// deleted.
+    pub async fn open(store: S, prefix: impl Into<String>) -> Result<Self> {
+        let mut log = Self {
+            store,
+            prefix: prefix.into(),
+            clock: Arc::new(SystemClock),
+            last_cursor: Mutex::new(0),
+        };
+
+        let committed = log.committed_cursor().await?;
+        let mut last_cursor = committed;
+
+        for (key, value) in log.store.scan(&log.event_key_prefix()).await? {
+            let cursor = parse_event(&key, &value)?.cursor;
+            if cursor <= committed {
+                log.store.delete(&key).await?;
+            }
+            last_cursor = last_cursor.max(cursor);
+        }
+
+        *log.last_cursor.get_mut() = last_cursor;
+
+        Ok(log)
+    }
+
+    /// Sets the clock used to timestamp events.
+    ///
+    /// Default: the system clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Prefix of the keys of the log.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Cursor of the last recorded event, or 0 if no event was recorded.
+    pub async fn last_cursor(&self) -> u64 {
+        *self.last_cursor.lock().await
+    }
+
+    /// Cursor of the last acknowledged event, or 0 if no event was acknowledged.
+    pub async fn committed_cursor(&self) -> Result<u64> {
+        let key = self.cursor_key();
+        let Some(value) = self.store.get(&key).await? else {
+            return Ok(0);
+        };
+
+        serde_json::from_slice(&value).map_err(|e| EventLogError::EntryParse { key, e })
+    }
+
+    /// Records `update` if it is a private account event, returning the recorded event.
+    ///
+    /// Returns `None`, without recording anything, for public topic and connection status
+    /// updates.
+    pub async fn record_update(&self, update: &StreamUpdate) -> Result<Option<PersistedEvent>> {
+        match AccountEvent::from_update(update) {
+            Some(event) => self.append(event).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Durably records `event` under the next cursor.
+    pub async fn append(&self, event: AccountEvent) -> Result<PersistedEvent> {
+        let mut last_cursor = self.last_cursor.lock().await;
+
+        let event = PersistedEvent {
+            cursor: *last_cursor + 1,
+            received_at: self.clock.now(),
+            event,
+        };
+        let value = serde_json::to_vec(&event).map_err(EventLogError::EventSerialize)?;
+        self.store
+            .put(&self.event_key(event.cursor), &value)
+            .await?;
+
+        *last_cursor = event.cursor;
+
+        Ok(event)
+    }
+
+    /// Acknowledges all the events up to, and including, `cursor` as processed, and deletes them
+    /// from the store.
+    ///
+    /// Acknowledging a cursor at or below the committed one has no effect.
+    pub async fn ack(&self, cursor: u64) -> Result<()> {
+        // Serializes acknowledgements with appends, so that the committed cursor never moves back
+        let last_cursor = self.last_cursor.lock().await;
+
+        if cursor > *last_cursor {
+            return Err(EventLogError::UnknownCursor {
+                cursor,
+                last: *last_cursor,
+            });
+        }
+
+        let committed = self.committed_cursor().await?;
+        if cursor <= committed {
+            return Ok(());
+        }
+
+        let value = serde_json::to_vec(&cursor).map_err(EventLogError::EventSerialize)?;
+        self.store.put(&self.cursor_key(), &value).await?;
+
+        // Events left behind if interrupted are deleted on the next open
+        for acked in committed + 1..=cursor {
+            self.store.delete(&self.event_key(acked)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the events recorded after the committed cursor, in cursor order.
+    pub async fn pending(&self) -> Result<Vec<PersistedEvent>> {
+        let committed = self.committed_cursor().await?;
+
+        let mut events = Vec::new();
+        for (key, value) in self.store.scan(&self.event_key_prefix()).await? {
+            let event = parse_event(&key, &value)?;
+            if event.cursor > committed {
+                events.push(event);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Returns the pending events, and a snapshot of the account fetched through `api` after
+    /// loading them.
+    ///
+    /// The snapshot reflects every change made while the process wasn't recording events, and
+    /// takes precedence over the pending events, which may predate it. To avoid missing events
+    /// between the snapshot and live processing, subscribe to the private topics before resuming.
+    pub async fn resume(&self, api: &dyn LnmFuturesApi) -> Result<Resume> {
+        let events = self.pending().await?;
+        let snapshot = snapshot::snapshot_from_api(api, self.clock.as_ref())
+            .await
+            .map_err(EventLogError::Snapshot)?;
+
+        Ok(Resume { events, snapshot })
+    }
+
+    fn event_key_prefix(&self) -> String {
+        format!("{}events/", self.prefix)
+    }
+
+    fn event_key(&self, cursor: u64) -> String {
+        format!("{}events/{cursor:020}", self.prefix)
+    }
+
+    fn cursor_key(&self) -> String {
+        format!("{}cursor", self.prefix)
+    }
+}
+
+fn parse_event(key: &str, value: &[u8]) -> Result<PersistedEvent> {
+    serde_json::from_slice(value).map_err(|e| EventLogError::EntryParse {
+        key: key.to_string(),
+        e,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        rest::v3::state_store::{FileStateStore, MemoryStateStore},
+        shared::models::price::Price,
+        testing::stub::StubApi,
+    };
+
+    fn trade_update(event: &str) -> StreamUpdate {
+        StreamUpdate::FuturesInverseBtcUsdIsolatedTrades(
+            serde_json::from_value(json!({
+                "pair": "btc_usd",
+                "event": event,
+                "trade": {
+                    "id": "00000000-0000-0000-0000-000000000001",
+                    "side": "buy",
+                    "type": "market",
+                    "quantity": 1_000,
+                    "margin": 10_000,
+                    "leverage": 10,
+                    "price": 100_000.5,
+                    "openingFee": 10,
+                    "createdAt": "2025-05-12T07:30:05.657Z",
+                    "clientId": "bot-1",
+                },
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn deposit_update() -> StreamUpdate {
+        StreamUpdate::WalletDeposit(
+            serde_json::from_value(json!({
+                "currency": "BTC",
+                "network": "lightning",
+                "id": "deposit-1",
+                "amount": 1_000,
+                "balance": 11_000,
+                "status": "success",
+            }))
+            .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_records_and_acknowledges_events() {
+        let store = Arc::new(MemoryStateStore::new());
+        let log = AccountEventLog::open(store.clone(), "log/").await.unwrap();
+
+        let first = log
+            .record_update(&trade_update("opened"))
+            .await
+            .unwrap()
+            .unwrap();
+        let second = log.record_update(&deposit_update()).await.unwrap().unwrap();
+
+        assert_eq!((first.cursor(), second.cursor()), (1, 2));
+        assert_eq!(
+            AccountEvent::from_update(&trade_update("opened")).as_ref(),
+            Some(first.event())
+        );
+        assert!(matches!(
+            StreamUpdate::from(second.event().clone()),
+            StreamUpdate::WalletDeposit(deposit) if deposit.id() == "deposit-1"
+        ));
+        assert_eq!(
+            log.pending().await.unwrap(),
+            [first.clone(), second.clone()]
+        );
+
+        log.ack(1).await.unwrap();
+        assert_eq!(log.committed_cursor().await.unwrap(), 1);
+        assert_eq!(log.pending().await.unwrap(), [second]);
+
+        // Acknowledgements never move back, nor past the last event
+        log.ack(0).await.unwrap();
+        assert_eq!(log.committed_cursor().await.unwrap(), 1);
+        assert!(matches!(
+            log.ack(3).await,
+            Err(EventLogError::UnknownCursor { cursor: 3, last: 2 })
+        ));
+
+        // Acknowledged events are deleted, but the sequence continues after a restart
+        log.ack(2).await.unwrap();
+        assert!(log.pending().await.unwrap().is_empty());
+        assert!(store.scan("log/events/").await.unwrap().is_empty());
+
+        let log = AccountEventLog::open(store, "log/").await.unwrap();
+        assert_eq!(log.last_cursor().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_ignores_public_updates() {
+        let log = AccountEventLog::open(MemoryStateStore::new(), "")
+            .await
+            .unwrap();
+
+        let update =
+            StreamUpdate::FuturesInverseBtcUsdIndex(crate::shared::models::oracle::Index::new(
+                Utc::now(),
+                Price::try_from(100_000).unwrap(),
+            ));
+
+        assert_eq!(log.record_update(&update).await.unwrap(), None);
+        assert_eq!(log.last_cursor().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_resumes_after_restart() {
+        let dir = std::env::temp_dir().join(format!("lnm-event-log-{}", Uuid::new_v4()));
+
+        {
+            let log = AccountEventLog::open(FileStateStore::new(&dir), "account/")
+                .await
+                .unwrap();
+            for event in ["opened", "running", "closed"] {
+                log.record_update(&trade_update(event)).await.unwrap();
+            }
+            log.ack(1).await.unwrap();
+        }
+
+        let log = AccountEventLog::open(FileStateStore::new(&dir), "account/")
+            .await
+            .unwrap();
+        assert_eq!(log.last_cursor().await, 3);
+
+        let resume = log.resume(&StubApi::new()).await.unwrap();
+        let cursors: Vec<_> = resume.events().iter().map(|e| e.cursor()).collect();
+        assert_eq!(cursors, [2, 3]);
+        assert!(matches!(
+            resume.events()[1].event(),
+            AccountEvent::IsolatedTrade(event) if event.event() == "closed"
+        ));
+        assert_eq!(resume.snapshot().trades().len(), 2);
+
+        // New events continue the sequence
+        let event = log.record_update(&deposit_update()).await.unwrap().unwrap();
+        assert_eq!(event.cursor(), 4);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,485 @@
+use std::collections::HashMap;
+
+use tokio::sync::broadcast::{Receiver, error::RecvError};
+use uuid::Uuid;
+
+use crate::shared::models::{client_id::ClientId, price::Price, trade::TradeSide};
+
+use super::models::update::StreamUpdate;
+
+/// Isolated trade event sent when a trade is filled and starts running.
+pub(super) const ISOLATED_FILL_EVENT: &str = "running";
+
+/// Cross order event sent when an order is filled.
+pub(super) const CROSS_FILL_EVENT: &str = "filled";
+
+/// Event sent when an isolated trade or cross order is canceled.
+const CANCEL_EVENT: &str = "canceled";
+
+/// Events sent when an isolated trade or cross order is placed on the book.
+const PLACED_EVENTS: [&str; 2] = ["new", "open"];
+
+/// Kind of order a fill belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FillKind {
+    Isolated,
+    Cross,
+}
+
+/// Order fields common to isolated trade and cross order events.
+struct OrderEvent<'a> {
+    kind: FillKind,
+    event: &'a str,
+    id: Option<Uuid>,
+    client_id: Option<&'a ClientId>,
+    side: Option<TradeSide>,
+    quantity: Option<u64>,
+    price: Option<Price>,
+    fee: Option<u64>,
+}
+
+impl<'a> OrderEvent<'a> {
+    fn from_update(update: &'a StreamUpdate) -> Option<Self> {
+        match update {
+            StreamUpdate::FuturesInverseBtcUsdIsolatedTrades(event) => {
+                let trade = event.trade();
+                Some(Self {
+                    kind: FillKind::Isolated,
+                    event: event.event(),
+                    id: trade.id(),
+                    client_id: trade.client_id(),
+                    side: trade.side(),
+                    quantity: trade.quantity().map(|quantity| quantity.as_u64()),
+                    price: trade.price(),
+                    fee: trade.opening_fee().map(|fee| fee.as_u64()),
+                })
+            }
+            StreamUpdate::FuturesInverseBtcUsdCrossOrders(event) => {
+                let order = event.order();
+                Some(Self {
+                    kind: FillKind::Cross,
+                    event: event.event(),
+                    id: order.id(),
+                    client_id: order.client_id(),
+                    side: order.side(),
+                    quantity: order.quantity().map(|quantity| quantity.as_u64()),
+                    price: order.price(),
+                    fee: order.trading_fee().map(|fee| fee.as_u64()),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn is_fill(&self) -> bool {
+        match self.kind {
+            FillKind::Isolated => self.event == ISOLATED_FILL_EVENT,
+            FillKind::Cross => self.event == CROSS_FILL_EVENT,
+        }
+    }
+}
+
+/// Fill of an isolated trade or cross order, extracted from a Stream update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fill {
+    kind: FillKind,
+    order_id: Uuid,
+    client_id: Option<ClientId>,
+    side: Option<TradeSide>,
+    quantity: u64,
+    price: Option<Price>,
+    fee: u64,
+}
+
+impl Fill {
+    /// Extracts the fill from an isolated trade `running` event or a cross order `filled` event.
+    ///
+    /// Returns `None` for other updates, and for fill events without an order ID.
+    pub fn from_update(update: &StreamUpdate) -> Option<Self> {
+        let event = OrderEvent::from_update(update).filter(OrderEvent::is_fill)?;
+
+        Some(Self {
+            kind: event.kind,
+            order_id: event.id?,
+            client_id: event.client_id.cloned(),
+            side: event.side,
+            quantity: event.quantity.unwrap_or(0),
+            price: event.price,
+            fee: event.fee.unwrap_or(0),
+        })
+    }
+
+    pub fn kind(&self) -> FillKind {
+        self.kind
+    }
+
+    /// ID of the isolated trade or cross order filled.
+    pub fn order_id(&self) -> Uuid {
+        self.order_id
+    }
+
+    pub fn client_id(&self) -> Option<&ClientId> {
+        self.client_id.as_ref()
+    }
+
+    pub fn side(&self) -> Option<TradeSide> {
+        self.side
+    }
+
+    /// Filled quantity, in USD.
+    pub fn quantity(&self) -> u64 {
+        self.quantity
+    }
+
+    pub fn price(&self) -> Option<Price> {
+        self.price
+    }
+
+    /// Opening fee of isolated trades, or trading fee of cross orders, in sats.
+    pub fn fee(&self) -> u64 {
+        self.fee
+    }
+}
+
+/// Receiver of the fills contained in Stream updates.
+///
+/// Other updates are skipped. Created with
+/// [`StreamRepository::fill_receiver`](super::StreamRepository::fill_receiver).
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(conn: lnm_sdk::stream::v1::StreamConnection) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::stream::v1::models::StreamTopic;
+///
+/// conn.subscribe(vec![StreamTopic::FuturesInverseBtcUsdCrossOrders])
+///     .await?;
+///
+/// let mut rx = conn.fill_receiver().await?;
+/// while let Ok(fill) = rx.recv().await {
+///     println!("Filled {} USD of {}", fill.quantity(), fill.order_id());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FillReceiver {
+    rx: Receiver<StreamUpdate>,
+}
+
+impl FillReceiver {
+    pub fn new(rx: Receiver<StreamUpdate>) -> Self {
+        Self { rx }
+    }
+
+    /// Receives the next fill.
+    ///
+    /// Fails like [`Receiver::recv`]. If the receiver lagged behind, the next call resumes
+    /// receiving.
+    pub async fn recv(&mut self) -> Result<Fill, RecvError> {
+        loop {
+            let update = self.rx.recv().await?;
+            if let Some(fill) = Fill::from_update(&update) {
+                return Ok(fill);
+            }
+        }
+    }
+}
+
+/// Summary of an order whose fills were aggregated by a [`FillAggregator`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderComplete {
+    kind: FillKind,
+    order_id: Uuid,
+    client_id: Option<ClientId>,
+    side: Option<TradeSide>,
+    filled_quantity: u64,
+    fills: usize,
+    total_fee: u64,
+    canceled: bool,
+    // Sum of `quantity / price` over the priced fills, and their quantity
+    inverse_notional: f64,
+    priced_quantity: u64,
+}
+
+impl OrderComplete {
+    pub fn kind(&self) -> FillKind {
+        self.kind
+    }
+
+    pub fn order_id(&self) -> Uuid {
+        self.order_id
+    }
+
+    pub fn client_id(&self) -> Option<&ClientId> {
+        self.client_id.as_ref()
+    }
+
+    pub fn side(&self) -> Option<TradeSide> {
+        self.side
+    }
+
+    /// Total filled quantity, in USD.
+    pub fn filled_quantity(&self) -> u64 {
+        self.filled_quantity
+    }
+
+    /// Number of fills aggregated.
+    pub fn fills(&self) -> usize {
+        self.fills
+    }
+
+    /// Sum of the fees of the fills, in sats.
+    pub fn total_fee(&self) -> u64 {
+        self.total_fee
+    }
+
+    /// Returns `true` if the order was canceled before being fully filled.
+    pub fn canceled(&self) -> bool {
+        self.canceled
+    }
+
+    /// Average fill price, weighted by quantity as for inverse contracts, i.e. the harmonic mean
+    /// of the fill prices. `None` if no fill had a price.
+    pub fn average_price(&self) -> Option<f64> {
+        (self.inverse_notional > 0.).then(|| self.priced_quantity as f64 / self.inverse_notional)
+    }
+
+    fn record_fill(&mut self, event: &OrderEvent<'_>) {
+        let quantity = event.quantity.unwrap_or(0);
+
+        self.filled_quantity += quantity;
+        self.fills += 1;
+        self.total_fee += event.fee.unwrap_or(0);
+
+        if let Some(price) = event.price {
+            self.inverse_notional += quantity as f64 / price.as_f64();
+            self.priced_quantity += quantity;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TrackedOrder {
+    summary: OrderComplete,
+    // Quantity of the order when placed, if its placement was received
+    total: Option<u64>,
+}
+
+/// Aggregator grouping fills by order, and emitting an [`OrderComplete`] summary once an order is
+/// fully filled or canceled.
+///
+/// The quantity of an order is taken from its placement event (`new` or `open`). Fills are
+/// accumulated until they reach it. If the placement wasn't received, e.g. because the order was
+/// placed before subscribing, the first fill completes the order. Cancellations complete orders
+/// that were placed or partially filled, with their fills so far.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(conn: lnm_sdk::stream::v1::StreamConnection) -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::stream::v1::fills::FillAggregator;
+///
+/// let mut aggregator = FillAggregator::new();
+/// let mut rx = conn.receiver().await?;
+///
+/// while let Ok(update) = rx.recv().await {
+///     if let Some(complete) = aggregator.record_update(&update) {
+///         println!(
+///             "Order {} done: {} USD at {:?}, fees {} sats",
+///             complete.order_id(),
+///             complete.filled_quantity(),
+///             complete.average_price(),
+///             complete.total_fee()
+///         );
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct FillAggregator {
+    orders: HashMap<Uuid, TrackedOrder>,
+}
+
+impl FillAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of orders placed or partially filled, that aren't complete yet.
+    pub fn pending(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Returns the fills aggregated so far for the pending order with `order_id`.
+    pub fn get(&self, order_id: Uuid) -> Option<&OrderComplete> {
+        self.orders.get(&order_id).map(|tracked| &tracked.summary)
+    }
+
+    /// Returns the fills aggregated so far for the pending order with `client_id`.
+    pub fn get_by_client_id(&self, client_id: &ClientId) -> Option<&OrderComplete> {
+        self.orders
+            .values()
+            .map(|tracked| &tracked.summary)
+            .find(|summary| summary.client_id.as_ref() == Some(client_id))
+    }
+
+    fn track(&mut self, event: &OrderEvent<'_>, id: Uuid) -> &mut TrackedOrder {
+        let tracked = self.orders.entry(id).or_insert_with(|| TrackedOrder {
+            summary: OrderComplete {
+                kind: event.kind,
+                order_id: id,
+                client_id: None,
+                side: None,
+                filled_quantity: 0,
+                fills: 0,
+                total_fee: 0,
+                canceled: false,
+                inverse_notional: 0.,
+                priced_quantity: 0,
+            },
+            total: None,
+        });
+
+        if tracked.summary.client_id.is_none() {
+            tracked.summary.client_id = event.client_id.cloned();
+        }
+        tracked.summary.side = tracked.summary.side.or(event.side);
+
+        tracked
+    }
+
+    /// Records a Stream update, returning the summary of the order it completed, if any.
+    ///
+    /// Updates other than isolated trade and cross order events are ignored.
+    pub fn record_update(&mut self, update: &StreamUpdate) -> Option<OrderComplete> {
+        let event = OrderEvent::from_update(update)?;
+        let id = event.id?;
+
+        if PLACED_EVENTS.contains(&event.event) {
+            let tracked = self.track(&event, id);
+            tracked.total = tracked.total.or(event.quantity);
+            return None;
+        }
+
+        if event.is_fill() {
+            let tracked = self.track(&event, id);
+            tracked.summary.record_fill(&event);
+
+            let complete = tracked
+                .total
+                .is_none_or(|total| tracked.summary.filled_quantity >= total);
+            if !complete {
+                return None;
+            }
+        } else if event.event == CANCEL_EVENT {
+            self.orders.get_mut(&id)?.summary.canceled = true;
+        } else {
+            return None;
+        }
+
+        self.orders.remove(&id).map(|tracked| tracked.summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stream::v1::models::{StreamCrossOrderEvent, StreamIsolatedTradeEvent};
+
+    use super::*;
+
+    const ORDER_ID: &str = "00000000-0000-0000-0000-000000000002";
+
+    fn cross_event(event: &str, quantity: u64, price: u64, fee: u64) -> StreamUpdate {
+        let json = format!(
+            r#"{{ "pair": "btc_usd", "event": "{event}", "order": {{ "id": "{ORDER_ID}", "side": "buy", "quantity": {quantity}, "price": {price}, "tradingFee": {fee}, "clientId": "bid-1" }} }}"#
+        );
+        let event: StreamCrossOrderEvent = serde_json::from_str(&json).unwrap();
+        StreamUpdate::FuturesInverseBtcUsdCrossOrders(event)
+    }
+
+    fn isolated_event(event: &str) -> StreamUpdate {
+        let json = format!(
+            r#"{{ "pair": "btc_usd", "event": "{event}", "trade": {{ "id": "00000000-0000-0000-0000-000000000001", "quantity": 1000, "price": 100000, "openingFee": 10 }} }}"#
+        );
+        let event: StreamIsolatedTradeEvent = serde_json::from_str(&json).unwrap();
+        StreamUpdate::FuturesInverseBtcUsdIsolatedTrades(event)
+    }
+
+    #[test]
+    fn test_fill_from_update() {
+        let fill = Fill::from_update(&cross_event(CROSS_FILL_EVENT, 500, 100_000, 5)).unwrap();
+        assert_eq!(fill.kind(), FillKind::Cross);
+        assert_eq!(fill.order_id(), ORDER_ID.parse::<Uuid>().unwrap());
+        assert_eq!(fill.client_id().unwrap().as_str(), "bid-1");
+        assert_eq!(fill.side(), Some(TradeSide::Buy));
+        assert_eq!(fill.quantity(), 500);
+        assert_eq!(fill.fee(), 5);
+
+        assert!(Fill::from_update(&cross_event("new", 500, 100_000, 0)).is_none());
+        assert!(Fill::from_update(&isolated_event("open")).is_none());
+        assert_eq!(
+            Fill::from_update(&isolated_event(ISOLATED_FILL_EVENT))
+                .unwrap()
+                .kind(),
+            FillKind::Isolated
+        );
+    }
+
+    #[test]
+    fn test_fill_aggregator() {
+        let mut aggregator = FillAggregator::new();
+
+        assert!(
+            aggregator
+                .record_update(&cross_event("new", 1_500, 100_000, 0))
+                .is_none()
+        );
+        assert!(
+            aggregator
+                .record_update(&cross_event(CROSS_FILL_EVENT, 1_000, 100_000, 10))
+                .is_none()
+        );
+        let client_id = ClientId::try_from("bid-1").unwrap();
+        assert_eq!(
+            aggregator
+                .get_by_client_id(&client_id)
+                .unwrap()
+                .filled_quantity(),
+            1_000
+        );
+
+        let complete = aggregator
+            .record_update(&cross_event(CROSS_FILL_EVENT, 500, 50_000, 5))
+            .unwrap();
+        assert_eq!(complete.filled_quantity(), 1_500);
+        assert_eq!(complete.fills(), 2);
+        assert_eq!(complete.total_fee(), 15);
+        assert!(!complete.canceled());
+        // 1_500 / (1_000 / 100_000 + 500 / 50_000)
+        assert_eq!(complete.average_price(), Some(75_000.));
+        assert_eq!(aggregator.pending(), 0);
+
+        // Partially filled, then canceled
+        aggregator.record_update(&cross_event("new", 1_500, 100_000, 0));
+        aggregator.record_update(&cross_event(CROSS_FILL_EVENT, 1_000, 100_000, 10));
+        let complete = aggregator
+            .record_update(&cross_event(CANCEL_EVENT, 1_500, 100_000, 0))
+            .unwrap();
+        assert!(complete.canceled());
+        assert_eq!(complete.filled_quantity(), 1_000);
+
+        // Untracked cancellations are ignored, and untracked fills complete immediately
+        assert!(
+            aggregator
+                .record_update(&cross_event(CANCEL_EVENT, 1_500, 100_000, 0))
+                .is_none()
+        );
+        let complete = aggregator
+            .record_update(&isolated_event(ISOLATED_FILL_EVENT))
+            .unwrap();
+        assert_eq!(complete.kind(), FillKind::Isolated);
+        assert_eq!(complete.average_price(), Some(100_000.));
+    }
+}
@@ -0,0 +1,539 @@
+use std::{fmt, num::NonZeroU64, sync::Arc, time::Duration};
+
+use tokio::{
+    sync::{
+        broadcast::{self, error::RecvError},
+        mpsc,
+    },
+    task::JoinHandle,
+    time::Instant,
+};
+
+use crate::{
+    rest::v3::{LnmFuturesApi, models::CrossPosition},
+    shared::{models::price::Price, rest::error::RestApiError},
+};
+
+use super::models::update::StreamUpdate;
+
+/// Capacity of the channel of events returned by [`LiquidationMonitor::spawn`].
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Maximum delay before retrying a failed margin top-up.
+const MAX_TOP_UP_RETRY_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Warning that the cross position crossed a distance-to-liquidation threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiquidationWarning {
+    threshold: f64,
+    distance: f64,
+    index: Price,
+    liquidation: Price,
+}
+
+impl LiquidationWarning {
+    /// Threshold that was crossed, as a fraction of the index price.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Distance between the index price and the liquidation price, as a fraction of the index
+    /// price. Zero if the index price already crossed the liquidation price.
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    /// Index price that triggered the warning.
+    pub fn index(&self) -> Price {
+        self.index
+    }
+
+    /// Liquidation price of the cross position.
+    pub fn liquidation(&self) -> Price {
+        self.liquidation
+    }
+}
+
+/// Event emitted by a [`LiquidationMonitor`].
+#[derive(Debug)]
+pub enum LiquidationEvent {
+    /// The distance to liquidation dropped below a warning threshold.
+    Warning(LiquidationWarning),
+    /// Margin was automatically added to the cross position.
+    MarginAdded {
+        amount: NonZeroU64,
+        position: Box<CrossPosition>,
+    },
+    /// Automatically adding margin to the cross position failed. The top-up is retried on the
+    /// first update received after `retry_in`, if the distance is still below its level.
+    MarginTopUpFailed {
+        amount: NonZeroU64,
+        error: RestApiError,
+        retry_in: Duration,
+    },
+}
+
+/// Automatic margin top-up settings.
+struct MarginTopUp {
+    api: Arc<dyn LnmFuturesApi>,
+    below: f64,
+    amount: NonZeroU64,
+    armed: bool,
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl MarginTopUp {
+    fn rearm(&mut self) {
+        self.armed = true;
+        self.failures = 0;
+        self.retry_at = None;
+    }
+
+    /// Returns `true` if a top-up can be attempted, either armed or due for a retry.
+    fn ready(&self) -> bool {
+        self.armed
+            || self
+                .retry_at
+                .is_some_and(|retry_at| Instant::now() >= retry_at)
+    }
+}
+
+/// Monitor of the cross position's distance to liquidation.
+///
+/// Tracks the liquidation price of the cross position, from cross position updates, and the
+/// index price, from index and ticker updates, since the index is the price LNM uses to trigger
+/// liquidations. The distance to liquidation is measured as a fraction of the index price.
+///
+/// A [`LiquidationWarning`] is emitted when the distance drops below a threshold, and each
+/// threshold is re-armed once the distance is back above it. Optionally, margin can be deposited
+/// automatically into the cross position when the distance drops below a given level, with
+/// [`with_margin_top_up`](Self::with_margin_top_up).
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(
+/// #     rest: lnm_sdk::rest::v3::RestClient,
+/// #     conn: lnm_sdk::stream::v1::StreamConnection,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// use std::{num::NonZeroU64, sync::Arc};
+///
+/// use lnm_sdk::stream::v1::liquidation::{LiquidationEvent, LiquidationMonitor};
+///
+/// let position = rest.futures_cross.get_position().await?;
+///
+/// let monitor = LiquidationMonitor::new([0.10, 0.05])
+///     .with_margin_top_up(Arc::new(rest), 0.03, NonZeroU64::new(10_000).unwrap())
+///     .with_position(&position);
+///
+/// let (mut events, _handle) = monitor.spawn(conn.receiver().await?);
+/// while let Some(event) = events.recv().await {
+///     if let LiquidationEvent::Warning(warning) = event {
+///         println!("{:.1}% from liquidation", warning.distance() * 100.);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct LiquidationMonitor {
+    thresholds: Vec<(f64, bool)>,
+    top_up: Option<MarginTopUp>,
+    top_up_retry: Duration,
+    quantity: i64,
+    liquidation: Option<Price>,
+    index: Option<Price>,
+}
+
+impl LiquidationMonitor {
+    /// Creates a monitor warning when the distance to liquidation drops below each of the
+    /// `thresholds`, given as fractions of the index price (e.g. `0.05` for 5%). Thresholds that
+    /// are not positive and finite are ignored.
+    pub fn new(thresholds: impl IntoIterator<Item = f64>) -> Self {
+        let mut thresholds: Vec<(f64, bool)> = thresholds
+            .into_iter()
+            .filter(|threshold| threshold.is_finite() && *threshold > 0.)
+            .map(|threshold| (threshold, true))
+            .collect();
+        thresholds.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        Self {
+            thresholds,
+            top_up: None,
+            top_up_retry: Duration::from_secs(5),
+            quantity: 0,
+            liquidation: None,
+            index: None,
+        }
+    }
+
+    /// Deposits `amount` sats into the cross position through `api` when the distance to
+    /// liquidation drops below `below`. Re-armed once the distance is back above `below`, and
+    /// retried after a [delay](Self::with_margin_top_up_retry) if the deposit fails.
+    ///
+    /// Default: disabled
+    pub fn with_margin_top_up(
+        mut self,
+        api: Arc<dyn LnmFuturesApi>,
+        below: f64,
+        amount: NonZeroU64,
+    ) -> Self {
+        self.top_up = Some(MarginTopUp {
+            api,
+            below,
+            amount,
+            armed: true,
+            failures: 0,
+            retry_at: None,
+        });
+        self
+    }
+
+    /// Sets the delay before retrying a failed margin top-up. The delay doubles after each
+    /// consecutive failure, up to 5 minutes.
+    ///
+    /// Default: 5 seconds
+    pub fn with_margin_top_up_retry(mut self, interval: Duration) -> Self {
+        self.top_up_retry = interval;
+        self
+    }
+
+    /// Sets the initial state of the cross position, e.g. fetched through the REST API.
+    pub fn with_position(mut self, position: &CrossPosition) -> Self {
+        self.set_position(position);
+        self
+    }
+
+    fn set_position(&mut self, position: &CrossPosition) {
+        self.quantity = position.quantity();
+        self.liquidation = position.liquidation();
+    }
+
+    /// Current distance between the index price and the liquidation price, as a fraction of the
+    /// index price. `None` if the position is flat, or the prices are not known yet.
+    pub fn distance(&self) -> Option<f64> {
+        let (index, liquidation) = (self.index?.as_f64(), self.liquidation?.as_f64());
+
+        let distance = match self.quantity {
+            quantity if quantity > 0 => index - liquidation,
+            quantity if quantity < 0 => liquidation - index,
+            _ => return None,
+        };
+
+        Some((distance / index).max(0.))
+    }
+
+    /// Records a Stream update, returning the warnings it triggered.
+    ///
+    /// Index and ticker updates update the index price, and cross position updates the position.
+    /// Margin is not topped up by this method, see [`process_update`](Self::process_update).
+    pub fn record_update(&mut self, update: &StreamUpdate) -> Vec<LiquidationWarning> {
+        match update {
+            StreamUpdate::FuturesInverseBtcUsdIndex(index) => self.index = Some(index.index()),
+            StreamUpdate::FuturesInverseBtcUsdTicker(ticker) => {
+                if let Some(index) = ticker.index() {
+                    self.index = Some(index);
+                }
+            }
+            StreamUpdate::FuturesInverseBtcUsdCrossPosition(event) => {
+                let position = event.position();
+                if let Some(quantity) = position.quantity() {
+                    self.quantity = quantity;
+                    self.liquidation = position.liquidation();
+                } else if let Some(liquidation) = position.liquidation() {
+                    self.liquidation = Some(liquidation);
+                }
+            }
+            _ => return Vec::new(),
+        }
+
+        self.evaluate()
+    }
+
+    /// Returns the warnings triggered by the current distance, and re-arms the thresholds the
+    /// distance is back above.
+    fn evaluate(&mut self) -> Vec<LiquidationWarning> {
+        let Some(distance) = self.distance() else {
+            return Vec::new();
+        };
+        let (Some(index), Some(liquidation)) = (self.index, self.liquidation) else {
+            return Vec::new();
+        };
+
+        let mut warnings = Vec::new();
+        for (threshold, armed) in &mut self.thresholds {
+            if distance >= *threshold {
+                *armed = true;
+            } else if *armed {
+                *armed = false;
+                warnings.push(LiquidationWarning {
+                    threshold: *threshold,
+                    distance,
+                    index,
+                    liquidation,
+                });
+            }
+        }
+
+        if let Some(top_up) = &mut self.top_up
+            && distance >= top_up.below
+        {
+            top_up.rearm();
+        }
+
+        warnings
+    }
+
+    /// Records a Stream update like [`record_update`](Self::record_update), and deposits margin
+    /// into the cross position if a [top-up](Self::with_margin_top_up) is due.
+    pub async fn process_update(&mut self, update: &StreamUpdate) -> Vec<LiquidationEvent> {
+        let mut events: Vec<LiquidationEvent> = self
+            .record_update(update)
+            .into_iter()
+            .map(LiquidationEvent::Warning)
+            .collect();
+
+        let due = matches!(
+            (&self.top_up, self.distance()),
+            (Some(top_up), Some(distance)) if top_up.ready() && distance < top_up.below
+        );
+        if !due {
+            return events;
+        }
+
+        let top_up = self.top_up.as_mut().expect("top-up is due");
+        top_up.armed = false;
+        top_up.retry_at = None;
+        let amount = top_up.amount;
+
+        match top_up.api.deposit_cross_margin(amount).await {
+            Ok(position) => {
+                top_up.failures = 0;
+                self.set_position(&position);
+                events.extend(self.evaluate().into_iter().map(LiquidationEvent::Warning));
+                events.push(LiquidationEvent::MarginAdded {
+                    amount,
+                    position: Box::new(position),
+                });
+            }
+            Err(error) => {
+                let retry_in = self
+                    .top_up_retry
+                    .saturating_mul(2u32.saturating_pow(top_up.failures))
+                    .min(MAX_TOP_UP_RETRY_INTERVAL);
+                top_up.failures = top_up.failures.saturating_add(1);
+                top_up.retry_at = Some(Instant::now() + retry_in);
+
+                events.push(LiquidationEvent::MarginTopUpFailed {
+                    amount,
+                    error,
+                    retry_in,
+                });
+            }
+        }
+
+        events
+    }
+
+    /// Spawns a task processing every update received on `receiver`, returning a channel with
+    /// the emitted events.
+    ///
+    /// Updates missed because the receiver lagged are skipped. The task stops when the stream is
+    /// closed, when the returned event receiver is dropped, or when the returned handle is
+    /// aborted.
+    pub fn spawn(
+        mut self,
+        mut receiver: broadcast::Receiver<StreamUpdate>,
+    ) -> (mpsc::Receiver<LiquidationEvent>, JoinHandle<()>) {
+        let (tx, rx) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+
+        let handle = tokio::spawn(async move {
+            loop {
+                let update = match receiver.recv().await {
+                    Ok(update) => update,
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!("Liquidation monitor lagged, {skipped} updates skipped");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return,
+                };
+
+                for event in self.process_update(&update).await {
+                    if tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        (rx, handle)
+    }
+}
+
+impl fmt::Debug for LiquidationMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LiquidationMonitor")
+            .field("thresholds", &self.thresholds)
+            .field("top_up", &self.top_up.is_some())
+            .field("quantity", &self.quantity)
+            .field("liquidation", &self.liquidation)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::shared::rest::error::ErrorResponseContext;
+    use crate::{
+        shared::models::oracle::Index,
+        testing::{faults::ApiCall, fixtures, stub::StubApi},
+    };
+
+    fn index_update(index: f64) -> StreamUpdate {
+        StreamUpdate::FuturesInverseBtcUsdIndex(Index::new(
+            chrono::Utc::now(),
+            Price::try_from(index).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn test_warnings_at_thresholds() {
+        // Long position liquidated at 66,733.5
+        let position = fixtures::running_position();
+        let mut monitor = LiquidationMonitor::new([0.05, 0.2, -1.]).with_position(&position);
+
+        assert!(monitor.record_update(&index_update(100_000.)).is_empty());
+
+        let warnings = monitor.record_update(&index_update(80_000.));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].threshold(), 0.2);
+        assert!((warnings[0].distance() - 0.1658).abs() < 1e-4);
+
+        // Already warned
+        assert!(monitor.record_update(&index_update(79_000.)).is_empty());
+
+        let warnings = monitor.record_update(&index_update(66_000.));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].threshold(), 0.05);
+        assert_eq!(warnings[0].distance(), 0.);
+
+        // Re-armed once back above the thresholds
+        assert!(monitor.record_update(&index_update(100_000.)).is_empty());
+        assert_eq!(monitor.record_update(&index_update(66_000.)).len(), 2);
+    }
+
+    #[test]
+    fn test_flat_position_is_not_monitored() {
+        let mut monitor = LiquidationMonitor::new([0.5]).with_position(&fixtures::empty_position());
+
+        assert!(monitor.record_update(&index_update(1_000.)).is_empty());
+        assert_eq!(monitor.distance(), None);
+    }
+
+    /// Records deposited amounts in `deposits`, failing the first `failures` deposits.
+    fn deposit_api(deposits: Arc<Mutex<Vec<u64>>>, failures: u32) -> StubApi {
+        let failures = Mutex::new(failures);
+
+        StubApi::new().with_deposit_cross_margin(move |amount| {
+            deposits.lock().unwrap().push(amount.get());
+
+            let mut failures = failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(RestApiError::ErrorResponse(Box::new(
+                    ErrorResponseContext {
+                        method: http::Method::POST,
+                        path: "/v3/futures/cross/deposit".to_string(),
+                        status: http::StatusCode::SERVICE_UNAVAILABLE,
+                        request_id: None,
+                        correlation_id: None,
+                        retry_after: None,
+                        text: String::new(),
+                    },
+                )));
+            }
+
+            Ok(serde_json::from_value(json!({
+                "id": "00000000-0000-0000-0000-000000000003",
+                "margin": 510_000,
+                "quantity": 1_000,
+                "leverage": 10,
+                "entryPrice": 100_000,
+                "runningMargin": 110_000,
+                "initialMargin": 100_000,
+                "maintenanceMargin": 1_500,
+                "liquidation": 50_000,
+                "tradingFees": 100,
+                "fundingFees": 0,
+                "totalPl": 0,
+                "deltaPl": 0,
+            }))
+            .unwrap())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_margin_top_up() {
+        let deposits = Arc::new(Mutex::new(Vec::new()));
+        let api = Arc::new(deposit_api(deposits.clone(), 0));
+        let amount = NonZeroU64::new(10_000).unwrap();
+        let mut monitor = LiquidationMonitor::new([0.1])
+            .with_margin_top_up(api.clone(), 0.05, amount)
+            .with_position(&fixtures::running_position());
+
+        let events = monitor.process_update(&index_update(68_000.)).await;
+
+        assert!(matches!(events[0], LiquidationEvent::Warning(_)));
+        assert!(matches!(
+            events.last(),
+            Some(LiquidationEvent::MarginAdded { amount: added, .. }) if *added == amount
+        ));
+        assert_eq!(*deposits.lock().unwrap(), [10_000]);
+        assert_eq!(monitor.liquidation, Some(Price::try_from(50_000).unwrap()));
+
+        // The top-up is re-armed once the distance is back above its level
+        monitor.process_update(&index_update(51_000.)).await;
+        assert_eq!(api.call_count(ApiCall::DepositCrossMargin), 2);
+        monitor.process_update(&index_update(51_000.)).await;
+        assert_eq!(api.call_count(ApiCall::DepositCrossMargin), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_margin_top_up_is_retried() {
+        let api = Arc::new(deposit_api(Arc::default(), 2));
+        let amount = NonZeroU64::new(10_000).unwrap();
+        let mut monitor = LiquidationMonitor::new([])
+            .with_margin_top_up(api.clone(), 0.05, amount)
+            .with_margin_top_up_retry(Duration::from_secs(1))
+            .with_position(&fixtures::running_position());
+
+        let events = monitor.process_update(&index_update(68_000.)).await;
+        assert!(matches!(
+            events[..],
+            [LiquidationEvent::MarginTopUpFailed { retry_in, .. }] if retry_in == Duration::from_secs(1)
+        ));
+
+        // Not retried before the delay elapses
+        monitor.process_update(&index_update(68_000.)).await;
+        assert_eq!(api.call_count(ApiCall::DepositCrossMargin), 1);
+
+        // The delay doubles after consecutive failures
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let events = monitor.process_update(&index_update(68_000.)).await;
+        assert!(matches!(
+            events[..],
+            [LiquidationEvent::MarginTopUpFailed { retry_in, .. }] if retry_in == Duration::from_secs(2)
+        ));
+
+        tokio::time::advance(Duration::from_secs(2)).await;
+        let events = monitor.process_update(&index_update(68_000.)).await;
+        assert!(matches!(events[..], [LiquidationEvent::MarginAdded { .. }]));
+        assert_eq!(api.call_count(ApiCall::DepositCrossMargin), 3);
+    }
+}
@@ -18,9 +18,12 @@ use tokio_rustls::{
 };
 use webpki_roots::TLS_SERVER_ROOTS;
 
+use crate::shared::clock::Clock;
+
 use super::super::super::{
     error::{ConnectionResult, StreamConnectionError},
     models::rpc::{StreamJsonRpcMessage, StreamJsonRpcRequest},
+    recording::{FrameSink, RecordedFrame},
 };
 
 #[derive(Clone, Debug)]
@@ -56,7 +59,11 @@ where
     }
 }
 
-pub(super) struct StreamApiConnection(FragmentCollector<TokioIo<Upgraded>>);
+pub(super) struct StreamApiConnection {
+    ws: FragmentCollector<TokioIo<Upgraded>>,
+    frame_sink: Option<Arc<dyn FrameSink>>,
+    clock: Arc<dyn Clock>,
+}
 
 struct StreamEndpoint {
     uri: Uri,
@@ -99,7 +106,11 @@ impl StreamEndpoint {
 }
 
 impl StreamApiConnection {
-    pub async fn new(endpoint: &str) -> ConnectionResult<Self> {
+    pub async fn new(
+        endpoint: &str,
+        frame_sink: Option<Arc<dyn FrameSink>>,
+        clock: Arc<dyn Clock>,
+    ) -> ConnectionResult<Self> {
         let endpoint = StreamEndpoint::parse(endpoint)?;
 
         let tls_connector = {
@@ -137,11 +148,15 @@ impl StreamApiConnection {
             .map_err(StreamConnectionError::Handshake)?;
         let ws = FragmentCollector::new(ws);
 
-        Ok(Self(ws))
+        Ok(Self {
+            ws,
+            frame_sink,
+            clock,
+        })
     }
 
     async fn send_frame(&mut self, frame: Frame<'_>) -> ConnectionResult<()> {
-        self.0
+        self.ws
             .write_frame(frame)
             .await
             .map_err(StreamConnectionError::WriteFrame)
@@ -172,7 +187,7 @@ impl StreamConnectionIo for StreamApiConnection {
     }
 
     async fn read_response(&mut self) -> ConnectionResult<LnmStreamResponse> {
-        let frame = match self.0.read_frame().await {
+        let frame = match self.ws.read_frame().await {
             Ok(frame) => frame,
             Err(WebSocketError::ConnectionClosed) => return Ok(LnmStreamResponse::Close),
             Err(e) => return Err(StreamConnectionError::ReadFrame(e)),
@@ -180,10 +195,12 @@ impl StreamConnectionIo for StreamApiConnection {
 
         let response = match frame.opcode {
             OpCode::Text => {
-                let text = String::from_utf8(frame.payload.to_vec())
-                    .map_err(StreamConnectionError::DecodeText)?;
-                let json_rpc_message = serde_json::from_str::<StreamJsonRpcMessage>(&text)
-                    .map_err(StreamConnectionError::DecodeJson)?;
+                if let Some(frame_sink) = &self.frame_sink {
+                    let payload = String::from_utf8_lossy(&frame.payload);
+                    frame_sink.record(&RecordedFrame::new(self.clock.now(), payload));
+                }
+
+                let json_rpc_message = decode_json_rpc_message(frame.payload.to_vec())?;
                 LnmStreamResponse::JsonRpc(Box::new(json_rpc_message))
             }
             OpCode::Close => LnmStreamResponse::Close,
@@ -197,3 +214,16 @@ impl StreamConnectionIo for StreamApiConnection {
         Ok(response)
     }
 }
+
+#[cfg(not(feature = "simd-json"))]
+fn decode_json_rpc_message(payload: Vec<u8>) -> ConnectionResult<StreamJsonRpcMessage> {
+    let text = String::from_utf8(payload).map_err(StreamConnectionError::DecodeText)?;
+    serde_json::from_str::<StreamJsonRpcMessage>(&text).map_err(StreamConnectionError::DecodeJson)
+}
+
+/// Parses text frames with `simd-json`, which validates UTF-8 as part of parsing.
+#[cfg(feature = "simd-json")]
+fn decode_json_rpc_message(mut payload: Vec<u8>) -> ConnectionResult<StreamJsonRpcMessage> {
+    simd_json::serde::from_slice::<StreamJsonRpcMessage>(&mut payload)
+        .map_err(StreamConnectionError::DecodeSimdJson)
+}
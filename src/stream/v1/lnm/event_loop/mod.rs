@@ -8,7 +8,10 @@ use tokio::{
     time,
 };
 
-use crate::stream::v1::config::StreamClientConfig;
+use crate::{
+    shared::clock::Clock,
+    stream::v1::{config::StreamClientConfig, recording::FrameSink},
+};
 
 use super::super::{
     error::{ConnectionResult, StreamConnectionError},
@@ -55,12 +58,17 @@ trait StreamConnector: Send + Sync {
     async fn connect(&self, endpoint: &str) -> ConnectionResult<Box<dyn StreamConnectionIo>>;
 }
 
-struct LnmStreamConnector;
+struct LnmStreamConnector {
+    frame_sink: Option<Arc<dyn FrameSink>>,
+    clock: Arc<dyn Clock>,
+}
 
 #[async_trait]
 impl StreamConnector for LnmStreamConnector {
     async fn connect(&self, endpoint: &str) -> ConnectionResult<Box<dyn StreamConnectionIo>> {
-        Ok(Box::new(StreamApiConnection::new(endpoint).await?))
+        Ok(Box::new(
+            StreamApiConnection::new(endpoint, self.frame_sink.clone(), self.clock.clone()).await?,
+        ))
     }
 }
 
@@ -74,6 +82,8 @@ pub(super) struct StreamEventLoop {
     connection_status_manager: Arc<StreamConnectionStatusManager>,
     credentials: Arc<AsyncMutex<Option<StreamCredentials>>>,
     subscriptions: Arc<AsyncMutex<HashMap<StreamTopic, TopicStatus>>>,
+    #[cfg(feature = "otel")]
+    session_span: crate::shared::otel::SessionSpan,
 }
 
 impl StreamEventLoop {
@@ -86,9 +96,15 @@ impl StreamEventLoop {
         credentials: Arc<AsyncMutex<Option<StreamCredentials>>>,
         subscriptions: Arc<AsyncMutex<HashMap<StreamTopic, TopicStatus>>>,
     ) -> ConnectionResult<Self> {
-        let connector: Arc<dyn StreamConnector> = Arc::new(LnmStreamConnector);
+        let connector: Arc<dyn StreamConnector> = Arc::new(LnmStreamConnector {
+            frame_sink: config.frame_sink().cloned(),
+            clock: config.clock().clone(),
+        });
         let ws = connector.connect(config.endpoint()).await?;
 
+        #[cfg(feature = "otel")]
+        let session_span = crate::shared::otel::SessionSpan::start(config.endpoint());
+
         Ok(Self {
             config,
             ws: Some(ws),
@@ -99,6 +115,8 @@ impl StreamEventLoop {
             connection_status_manager,
             credentials,
             subscriptions,
+            #[cfg(feature = "otel")]
+            session_span,
         })
     }
 
@@ -134,6 +152,8 @@ impl StreamEventLoop {
                     match self.reconnect().await {
                         Ok(Some(new_ws)) => {
                             ws = new_ws;
+                            #[cfg(feature = "prometheus")]
+                            crate::metrics::inc_stream_reconnects();
                             self.update_connection_status(StreamConnectionStatus::Connected);
                         }
                         Ok(None) => {
@@ -311,7 +331,7 @@ impl StreamEventLoop {
         if let Some(credentials) = credentials {
             let request = StreamJsonRpcRequest::new(
                 StreamJsonRpcReqMethod::Authenticate,
-                Some(credentials.authenticate_params()?),
+                Some(credentials.authenticate_params(self.config.clock().now())?),
             );
 
             match self.send_control_request(ws, request).await? {
@@ -421,7 +441,18 @@ impl StreamEventLoop {
         }
     }
 
-    fn update_connection_status(&self, new_status: StreamConnectionStatus) {
+    fn update_connection_status(&mut self, new_status: StreamConnectionStatus) {
+        #[cfg(feature = "otel")]
+        match &new_status {
+            StreamConnectionStatus::Connected => self.session_span.add_event("connected"),
+            StreamConnectionStatus::Reconnecting => self.session_span.add_event("reconnecting"),
+            StreamConnectionStatus::DisconnectInitiated => {
+                self.session_span.add_event("disconnect_initiated")
+            }
+            StreamConnectionStatus::Disconnected => self.session_span.add_event("disconnected"),
+            StreamConnectionStatus::Failed(err) => self.session_span.set_error(err.to_string()),
+        }
+
         self.connection_status_manager.update(new_status.clone());
         let _ = self.response_tx.send(new_status.into());
     }
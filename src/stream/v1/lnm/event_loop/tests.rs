@@ -215,6 +215,8 @@ fn test_event_loop(
         connection_status_manager: StreamConnectionStatusManager::new(),
         credentials,
         subscriptions,
+        #[cfg(feature = "otel")]
+        session_span: crate::shared::otel::SessionSpan::start("wss://test"),
     };
 
     (event_loop, disconnect_tx, request_tx, response_rx)
@@ -2,7 +2,6 @@ use std::{
     collections::{HashMap, HashSet},
     env,
     num::NonZeroU64,
-    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -14,6 +13,7 @@ use uuid::Uuid;
 use crate::{
     rest::v3::{RestClient, RestClientConfig},
     shared::models::{
+        amount::Sats,
         client_id::ClientId,
         leverage::Leverage,
         ohlc::OhlcRange,
@@ -50,7 +50,7 @@ fn live_credentials_from_env() -> LiveCredentials {
     }
 }
 
-fn init_rest_client(credentials: &LiveCredentials) -> Arc<RestClient> {
+fn init_rest_client(credentials: &LiveCredentials) -> RestClient {
     RestClient::with_credentials(
         RestClientConfig::default(),
         &credentials.key,
@@ -148,11 +148,12 @@ async fn ensure_cross_margin(rest: &RestClient, min_margin: u64) -> u64 {
         .await
         .expect("must get cross position");
 
-    if position.margin() >= min_margin {
+    let margin = position.margin().as_u64();
+    if margin >= min_margin {
         return 0;
     }
 
-    let deposit_amount = min_margin - position.margin();
+    let deposit_amount = min_margin - margin;
     rest.futures_cross
         .deposit(NonZeroU64::try_from(deposit_amount).expect("deposit amount must be non-zero"))
         .await
@@ -170,11 +171,11 @@ async fn withdraw_test_cross_margin(rest: &RestClient, deposited_margin: u64) {
         return;
     };
 
-    if position.quantity() != 0 || position.margin() == 0 {
+    if position.quantity() != 0 || position.margin() == Sats::ZERO {
         return;
     }
 
-    let withdrawal_amount = deposited_margin.min(position.margin());
+    let withdrawal_amount = deposited_margin.min(position.margin().as_u64());
     if let Ok(withdrawal_amount) = NonZeroU64::try_from(withdrawal_amount) {
         let _ = rest.futures_cross.withdraw(withdrawal_amount).await;
     }
@@ -76,8 +76,8 @@ impl StreamCredentials {
         }
     }
 
-    fn authenticate_params(&self) -> ConnectionResult<serde_json::Value> {
-        let timestamp = Utc::now().timestamp_millis();
+    fn authenticate_params(&self, now: DateTime<Utc>) -> ConnectionResult<serde_json::Value> {
+        let timestamp = now.timestamp_millis();
         let mut nonce_bytes = [0u8; 16];
         rand::rng().fill(&mut nonce_bytes);
         let nonce = hex::encode(nonce_bytes);
@@ -234,7 +234,7 @@ impl StreamRepository for LnmStreamRepo {
     ) -> Result<AuthenticateResult> {
         let credentials = StreamCredentials::new(key, secret, passphrase);
         let params = credentials
-            .authenticate_params()
+            .authenticate_params(self.config.clock().now())
             .map_err(StreamApiError::RequestFailed)?;
         let request = StreamJsonRpcRequest::new(StreamJsonRpcReqMethod::Authenticate, Some(params));
 
@@ -164,6 +164,22 @@ fn authenticate_signature_matches_documented_hmac_shape() {
     assert_eq!(signature, expected);
 }
 
+#[test]
+fn authenticate_params_use_given_timestamp() {
+    let credentials = StreamCredentials::new("key", "secret", "passphrase");
+    let now = DateTime::from_timestamp_millis(1747035005657).unwrap();
+
+    let params = credentials
+        .authenticate_params(now)
+        .expect("params must be generated");
+    let signature = credentials
+        .authenticate_signature(1747035005657, params["nonce"].as_str().unwrap())
+        .unwrap();
+
+    assert_eq!(params["timestamp"], 1747035005657_i64);
+    assert_eq!(params["signature"], signature);
+}
+
 #[tokio::test]
 async fn authenticate_stores_credentials_after_success() {
     let (repo, mut request_rx) = test_repo();
@@ -2,20 +2,45 @@ use std::sync::Arc;
 
 use tokio::sync::Mutex;
 
+/// Conflation of price updates received from the Stream v1 API.
+pub mod conflation;
+
 /// Error types returned by the Stream v1 API.
 pub mod error;
 
+/// Durable log of private account events, with a replay cursor for at-least-once processing.
+pub mod event_log;
+
 /// Data models used by the Stream v1 API.
 pub mod models;
 
+/// Typed fills, and per-order aggregation of fills, from Stream v1 updates.
+pub mod fills;
+
+/// Monitoring of the cross position's distance to liquidation, from Stream v1 updates.
+pub mod liquidation;
+
+/// Recording of raw frames received from the Stream v1 API.
+pub mod recording;
+
+/// Sharding of Stream v1 subscriptions across independent connections.
+pub mod sharding;
+
+/// Session statistics accumulated from Stream v1 updates.
+pub mod stats;
+
 mod config;
 mod lnm;
+mod polling;
 mod repositories;
 mod state;
 
+pub use crate::shared::clock::{Clock, SystemClock};
 pub use config::StreamClientConfig;
 use error::Result;
 use lnm::LnmStreamRepo;
+pub use polling::RestFallback;
+use polling::{PollingStreamRepo, WebSocketConnector};
 pub use repositories::StreamRepository;
 pub use state::StreamConnectionStatus;
 
@@ -40,6 +65,9 @@ impl StreamClient {
     }
 
     /// Connects to the Stream API or returns an existing active connection.
+    ///
+    /// If the WebSocket can't connect and a [`RestFallback`] is configured, returns a connection
+    /// backed by REST polling instead, which switches to the WebSocket once it can connect.
     pub async fn connect(&self) -> Result<StreamConnection> {
         let mut conn_guard = self.conn.lock().await;
 
@@ -54,7 +82,31 @@ impl StreamClient {
             }
         }
 
-        let new_conn = Arc::new(LnmStreamRepo::new(self.config.clone()).await?);
+        let new_conn: StreamConnection = match LnmStreamRepo::new(self.config.clone()).await {
+            Ok(conn) => Arc::new(conn),
+            Err(e) => {
+                let Some(fallback) = self.config.rest_fallback() else {
+                    return Err(e);
+                };
+
+                log::warn!("Stream WebSocket unavailable, falling back to REST polling: {e}");
+
+                let config = self.config.clone();
+                let connect: WebSocketConnector = Arc::new(move || {
+                    let config = config.clone();
+                    Box::pin(async move {
+                        let conn: StreamConnection = Arc::new(LnmStreamRepo::new(config).await?);
+                        Ok(conn)
+                    })
+                });
+
+                Arc::new(PollingStreamRepo::new(
+                    fallback.clone(),
+                    self.config.clock().clone(),
+                    Some(connect),
+                ))
+            }
+        };
 
         *conn_guard = Some(new_conn.clone());
 
@@ -68,3 +120,28 @@ impl StreamClient {
         *conn_guard = None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+
+    fn assert_send_future<F: Future + Send>(_: F) {}
+
+    #[test]
+    fn stream_client_is_send_sync() {
+        assert_send_sync::<StreamClient>();
+        assert_send_sync::<dyn StreamRepository>();
+    }
+
+    #[test]
+    fn stream_client_futures_are_send() {
+        let client = StreamClient::new(StreamClientConfig::default());
+
+        // Futures are only created and dropped, never polled.
+        assert_send_future(client.connect());
+    }
+}
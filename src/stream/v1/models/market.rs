@@ -1,7 +1,12 @@
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
-use crate::shared::models::{price::Price, serde_util, ticker::TickerPrice};
+use crate::{
+    rest::v3::models::Ticker,
+    shared::models::{condition::PriceReference, price::Price, serde_util, ticker::TickerPrice},
+};
 
 /// Platform announcement notification payload.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -56,24 +61,49 @@ impl StreamFundingRate {
 pub struct StreamTicker {
     #[serde(deserialize_with = "serde_util::datetime_rfc3339_or_millis::deserialize")]
     time: DateTime<Utc>,
+    #[serde(alias = "last_price")]
     last_price: Option<Price>,
     index: Option<Price>,
     funding: StreamFundingRate,
 }
 
 impl StreamTicker {
+    /// Builds a ticker notification from a REST ticker received at `time`.
+    pub(in crate::stream::v1) fn from_rest(ticker: &Ticker, time: DateTime<Utc>) -> Self {
+        Self {
+            time,
+            last_price: Some(ticker.last_price()),
+            index: Some(ticker.index()),
+            funding: StreamFundingRate {
+                rate: ticker.funding_rate(),
+                time: ticker.funding_time(),
+            },
+        }
+    }
+
     pub fn time(&self) -> DateTime<Utc> {
         self.time
     }
 
+    /// Last traded price of the futures contract.
     pub fn last_price(&self) -> Option<Price> {
         self.last_price
     }
 
+    /// Index price, computed by the oracle from spot exchanges. Used by LNM to compute PnL, and to
+    /// trigger liquidations, stoplosses and takeprofits.
     pub fn index(&self) -> Option<Price> {
         self.index
     }
 
+    /// Price of the given reference series, if included in the notification.
+    pub fn price(&self, reference: PriceReference) -> Option<Price> {
+        match reference {
+            PriceReference::Last => self.last_price,
+            PriceReference::Index => self.index,
+        }
+    }
+
     pub fn funding(&self) -> &StreamFundingRate {
         &self.funding
     }
@@ -102,7 +132,8 @@ impl StreamBuckets {
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamFunding {
-    pair: String,
+    #[serde(deserialize_with = "serde_util::interned_str::deserialize")]
+    pair: Arc<str>,
     current: StreamFundingRate,
 }
 
@@ -9,8 +9,12 @@ pub(in crate::stream::v1) mod wallet;
 pub use uuid::Uuid;
 
 pub use crate::shared::models::{
+    amount::{Sats, Usd},
     client_id::ClientId,
+    condition::{PriceCondition, PriceReference, PriceTrigger},
     cross_leverage::CrossLeverage,
+    currency::Currency,
+    instrument::Instrument,
     leverage::Leverage,
     margin::Margin,
     ohlc::{OhlcCandle, OhlcRange},
@@ -18,7 +22,7 @@ pub use crate::shared::models::{
     price::Price,
     quantity::order::OrderQuantity,
     ticker::TickerPrice,
-    trade::{TradeExecutionType, TradeSide},
+    trade::{MarginMode, TradeExecutionType, TradeSide},
 };
 
 pub use market::{
@@ -137,6 +137,7 @@ struct JsonRpcEnvelope {
     us_out: Option<u64>,
     #[serde(rename = "usDiff")]
     us_diff: Option<u64>,
+    #[serde(alias = "rate_limit")]
     rate_limit: Option<StreamRateLimit>,
 }
 
@@ -558,4 +559,25 @@ mod tests {
         assert_eq!(time.metadata().rate_limit().unwrap().remaining(), 9);
         assert_eq!(time.metadata().rate_limit().unwrap().limit(), 10);
     }
+
+    #[cfg(feature = "simd-json")]
+    #[test]
+    fn json_rpc_message_simd_json_matches_serde_json() {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "method": "subscription",
+            "params": { "topic": "futures/inverse/btc_usd/lastPrice", "data": { "time": 0, "lastPrice": 100000 } },
+            "usIn": 1,
+            "usOut": 3,
+            "usDiff": 2
+        }"#;
+
+        let expected: StreamJsonRpcMessage =
+            serde_json::from_str(json).expect("must parse message");
+        let mut payload = json.as_bytes().to_vec();
+        let message: StreamJsonRpcMessage =
+            simd_json::serde::from_slice(&mut payload).expect("must parse message with simd-json");
+
+        assert_eq!(format!("{message:?}"), format!("{expected:?}"));
+    }
 }
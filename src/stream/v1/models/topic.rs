@@ -3,7 +3,7 @@ use std::{collections::HashSet, fmt, str::FromStr};
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 use serde_json::{Value, json};
 
-use crate::shared::models::ohlc::OhlcRange;
+use crate::shared::models::{instrument::Instrument, ohlc::OhlcRange};
 
 use super::super::error::{ConnectionResult, StreamConnectionError};
 
@@ -25,6 +25,60 @@ pub enum StreamTopic {
 }
 
 impl StreamTopic {
+    /// Returns `true` for topics carrying private account events, which require the connection
+    /// to be authenticated.
+    pub fn is_private(&self) -> bool {
+        matches!(
+            self,
+            StreamTopic::FuturesInverseBtcUsdIsolatedTrades
+                | StreamTopic::FuturesInverseBtcUsdCrossOrders
+                | StreamTopic::FuturesInverseBtcUsdCrossPosition
+                | StreamTopic::WalletDeposit
+                | StreamTopic::WalletWithdrawal
+        )
+    }
+
+    /// Returns the instrument the topic belongs to, or `None` for announcements and wallet topics.
+    pub fn instrument(&self) -> Option<Instrument> {
+        match self {
+            StreamTopic::FuturesInverseBtcUsdTicker
+            | StreamTopic::FuturesInverseBtcUsdLastPrice
+            | StreamTopic::FuturesInverseBtcUsdIndex
+            | StreamTopic::FuturesInverseBtcUsdBuckets
+            | StreamTopic::FuturesInverseBtcUsdFunding
+            | StreamTopic::FuturesInverseBtcUsdIsolatedTrades
+            | StreamTopic::FuturesInverseBtcUsdCrossOrders
+            | StreamTopic::FuturesInverseBtcUsdCrossPosition
+            | StreamTopic::FuturesInverseBtcUsdOhlc(_) => Some(Instrument::FuturesInverseBtcUsd),
+            StreamTopic::Announcements
+            | StreamTopic::WalletDeposit
+            | StreamTopic::WalletWithdrawal => None,
+        }
+    }
+
+    /// Returns the market data topics of `instrument`, OHLC candles excepted.
+    ///
+    /// Private topics are included if `private` is `true`.
+    pub fn instrument_topics(instrument: Instrument, private: bool) -> Vec<StreamTopic> {
+        let topics = match instrument {
+            Instrument::FuturesInverseBtcUsd => vec![
+                StreamTopic::FuturesInverseBtcUsdTicker,
+                StreamTopic::FuturesInverseBtcUsdLastPrice,
+                StreamTopic::FuturesInverseBtcUsdIndex,
+                StreamTopic::FuturesInverseBtcUsdBuckets,
+                StreamTopic::FuturesInverseBtcUsdFunding,
+                StreamTopic::FuturesInverseBtcUsdIsolatedTrades,
+                StreamTopic::FuturesInverseBtcUsdCrossOrders,
+                StreamTopic::FuturesInverseBtcUsdCrossPosition,
+            ],
+        };
+
+        topics
+            .into_iter()
+            .filter(|topic| private || !topic.is_private())
+            .collect()
+    }
+
     fn as_string(&self) -> String {
         match self {
             StreamTopic::Announcements => "announcements".to_string(),
@@ -143,4 +197,24 @@ mod tests {
         let decoded: StreamTopic = serde_json::from_str(&encoded).expect("must deserialize topic");
         assert_eq!(decoded, topic);
     }
+
+    #[test]
+    fn stream_topic_instrument_matches_topic_prefix() {
+        for instrument in Instrument::ALL {
+            let topics = StreamTopic::instrument_topics(*instrument, true);
+            assert!(topics.iter().any(StreamTopic::is_private));
+            assert!(
+                !StreamTopic::instrument_topics(*instrument, false)
+                    .iter()
+                    .any(StreamTopic::is_private)
+            );
+
+            for topic in topics {
+                assert_eq!(topic.instrument(), Some(*instrument));
+                assert!(topic.to_string().starts_with(instrument.as_str()));
+            }
+        }
+
+        assert_eq!(StreamTopic::WalletDeposit.instrument(), None);
+    }
 }
@@ -1,24 +1,39 @@
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::shared::models::{
-    client_id::ClientId,
-    cross_leverage::CrossLeverage,
-    leverage::Leverage,
-    margin::Margin,
-    price::Price,
-    quantity::order::OrderQuantity,
-    serde_util,
-    trade::{TradeExecutionType, TradeSide},
+use crate::{
+    rest::v3::models::CrossPosition,
+    shared::models::{
+        amount::Sats,
+        client_id::ClientId,
+        cross_leverage::CrossLeverage,
+        instrument::Instrument,
+        leverage::Leverage,
+        margin::Margin,
+        price::Price,
+        quantity::order::OrderQuantity,
+        serde_util,
+        trade::{TradeExecutionType, TradeSide},
+    },
 };
 
 /// Inverse futures isolated-margin trade event notification payload.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamIsolatedTradeEvent {
-    pair: String,
-    event: String,
+    #[serde(
+        serialize_with = "serde_util::interned_str::serialize",
+        deserialize_with = "serde_util::interned_str::deserialize"
+    )]
+    pair: Arc<str>,
+    #[serde(
+        serialize_with = "serde_util::interned_str::serialize",
+        deserialize_with = "serde_util::interned_str::deserialize"
+    )]
+    event: Arc<str>,
     trade: StreamIsolatedTrade,
 }
 
@@ -27,6 +42,11 @@ impl StreamIsolatedTradeEvent {
         &self.pair
     }
 
+    /// Instrument matching the pair of the event, if known.
+    pub fn instrument(&self) -> Option<Instrument> {
+        Instrument::from_pair(&self.pair)
+    }
+
     pub fn event(&self) -> &str {
         &self.event
     }
@@ -37,7 +57,7 @@ impl StreamIsolatedTradeEvent {
 }
 
 /// Inverse futures isolated-margin trade payload fragment.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamIsolatedTrade {
     id: Option<Uuid>,
@@ -49,16 +69,19 @@ pub struct StreamIsolatedTrade {
     leverage: Option<Leverage>,
     #[serde(default, deserialize_with = "serde_util::price_option::deserialize")]
     price: Option<Price>,
-    opening_fee: Option<u64>,
+    #[serde(alias = "opening_fee")]
+    opening_fee: Option<Sats>,
     #[serde(
         default,
         deserialize_with = "serde_util::datetime_option_rfc3339_or_millis::deserialize"
     )]
+    #[serde(alias = "created_at")]
     created_at: Option<DateTime<Utc>>,
     #[serde(
         default,
         deserialize_with = "serde_util::client_id_option::deserialize"
     )]
+    #[serde(alias = "client_id")]
     client_id: Option<ClientId>,
 }
 
@@ -91,7 +114,7 @@ impl StreamIsolatedTrade {
         self.price
     }
 
-    pub fn opening_fee(&self) -> Option<u64> {
+    pub fn opening_fee(&self) -> Option<Sats> {
         self.opening_fee
     }
 
@@ -105,11 +128,19 @@ impl StreamIsolatedTrade {
 }
 
 /// Inverse futures cross-margin order event notification payload.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamCrossOrderEvent {
-    pair: String,
-    event: String,
+    #[serde(
+        serialize_with = "serde_util::interned_str::serialize",
+        deserialize_with = "serde_util::interned_str::deserialize"
+    )]
+    pair: Arc<str>,
+    #[serde(
+        serialize_with = "serde_util::interned_str::serialize",
+        deserialize_with = "serde_util::interned_str::deserialize"
+    )]
+    event: Arc<str>,
     order: StreamCrossOrder,
 }
 
@@ -118,6 +149,11 @@ impl StreamCrossOrderEvent {
         &self.pair
     }
 
+    /// Instrument matching the pair of the event, if known.
+    pub fn instrument(&self) -> Option<Instrument> {
+        Instrument::from_pair(&self.pair)
+    }
+
     pub fn event(&self) -> &str {
         &self.event
     }
@@ -128,7 +164,7 @@ impl StreamCrossOrderEvent {
 }
 
 /// Inverse futures cross-margin order payload fragment.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamCrossOrder {
     id: Option<Uuid>,
@@ -138,16 +174,19 @@ pub struct StreamCrossOrder {
     quantity: Option<OrderQuantity>,
     #[serde(default, deserialize_with = "serde_util::price_option::deserialize")]
     price: Option<Price>,
-    trading_fee: Option<u64>,
+    #[serde(alias = "trading_fee")]
+    trading_fee: Option<Sats>,
     #[serde(
         default,
         deserialize_with = "serde_util::client_id_option::deserialize"
     )]
+    #[serde(alias = "client_id")]
     client_id: Option<ClientId>,
     #[serde(
         default,
         deserialize_with = "serde_util::datetime_option_rfc3339_or_millis::deserialize"
     )]
+    #[serde(alias = "created_at")]
     created_at: Option<DateTime<Utc>>,
 }
 
@@ -172,7 +211,7 @@ impl StreamCrossOrder {
         self.price
     }
 
-    pub fn trading_fee(&self) -> Option<u64> {
+    pub fn trading_fee(&self) -> Option<Sats> {
         self.trading_fee
     }
 
@@ -186,19 +225,60 @@ impl StreamCrossOrder {
 }
 
 /// Inverse futures cross-margin position event notification payload.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamCrossPositionEvent {
-    pair: String,
-    event: String,
+    #[serde(
+        serialize_with = "serde_util::interned_str::serialize",
+        deserialize_with = "serde_util::interned_str::deserialize"
+    )]
+    pair: Arc<str>,
+    #[serde(
+        serialize_with = "serde_util::interned_str::serialize",
+        deserialize_with = "serde_util::interned_str::deserialize"
+    )]
+    event: Arc<str>,
     position: StreamCrossPosition,
 }
 
 impl StreamCrossPositionEvent {
+    /// Builds a position notification with the `event` name from a REST position received at
+    /// `time`.
+    pub(in crate::stream::v1) fn from_rest(
+        event: &str,
+        position: &CrossPosition,
+        time: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            pair: Arc::from("btc_usd"),
+            event: Arc::from(event),
+            position: StreamCrossPosition {
+                quantity: Some(position.quantity()),
+                leverage: Some(position.leverage()),
+                margin: Some(position.margin()),
+                entry_price: position.entry_price(),
+                liquidation: position.liquidation(),
+                total_pl: Some(position.total_pl()),
+                funding_fees: Some(position.funding_fees()),
+                trading_fees: Some(position.trading_fees()),
+                initial_margin: Some(position.initial_margin()),
+                maintenance_margin: Some(position.maintenance_margin()),
+                running_margin: Some(position.running_margin()),
+                delta_pl: Some(position.delta_pl()),
+                updated_at: Some(time),
+            },
+        }
+    }
+
     pub fn pair(&self) -> &str {
         &self.pair
     }
 
+    /// Instrument matching the pair of the event, if known.
+    pub fn instrument(&self) -> Option<Instrument> {
+        Instrument::from_pair(&self.pair)
+    }
+
     pub fn event(&self) -> &str {
         &self.event
     }
@@ -209,27 +289,39 @@ impl StreamCrossPositionEvent {
 }
 
 /// Inverse futures cross-margin position payload fragment.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamCrossPosition {
     quantity: Option<i64>,
     leverage: Option<CrossLeverage>,
-    margin: Option<u64>,
-    #[serde(default, deserialize_with = "serde_util::price_option::deserialize")]
+    margin: Option<Sats>,
+    #[serde(
+        default,
+        deserialize_with = "serde_util::price_option::deserialize",
+        alias = "entry_price"
+    )]
     entry_price: Option<Price>,
     #[serde(default, deserialize_with = "serde_util::price_option::deserialize")]
     liquidation: Option<Price>,
-    total_pl: Option<i64>,
-    funding_fees: Option<i64>,
-    trading_fees: Option<u64>,
-    initial_margin: Option<u64>,
-    maintenance_margin: Option<u64>,
-    running_margin: Option<u64>,
-    delta_pl: Option<i64>,
+    #[serde(alias = "total_pl")]
+    total_pl: Option<Sats>,
+    #[serde(alias = "funding_fees")]
+    funding_fees: Option<Sats>,
+    #[serde(alias = "trading_fees")]
+    trading_fees: Option<Sats>,
+    #[serde(alias = "initial_margin")]
+    initial_margin: Option<Sats>,
+    #[serde(alias = "maintenance_margin")]
+    maintenance_margin: Option<Sats>,
+    #[serde(alias = "running_margin")]
+    running_margin: Option<Sats>,
+    #[serde(alias = "delta_pl")]
+    delta_pl: Option<Sats>,
     #[serde(
         default,
         deserialize_with = "serde_util::datetime_option_rfc3339_or_millis::deserialize"
     )]
+    #[serde(alias = "updated_at")]
     updated_at: Option<DateTime<Utc>>,
 }
 
@@ -242,7 +334,7 @@ impl StreamCrossPosition {
         self.leverage
     }
 
-    pub fn margin(&self) -> Option<u64> {
+    pub fn margin(&self) -> Option<Sats> {
         self.margin
     }
 
@@ -254,31 +346,31 @@ impl StreamCrossPosition {
         self.liquidation
     }
 
-    pub fn total_pl(&self) -> Option<i64> {
+    pub fn total_pl(&self) -> Option<Sats> {
         self.total_pl
     }
 
-    pub fn funding_fees(&self) -> Option<i64> {
+    pub fn funding_fees(&self) -> Option<Sats> {
         self.funding_fees
     }
 
-    pub fn trading_fees(&self) -> Option<u64> {
+    pub fn trading_fees(&self) -> Option<Sats> {
         self.trading_fees
     }
 
-    pub fn initial_margin(&self) -> Option<u64> {
+    pub fn initial_margin(&self) -> Option<Sats> {
         self.initial_margin
     }
 
-    pub fn maintenance_margin(&self) -> Option<u64> {
+    pub fn maintenance_margin(&self) -> Option<Sats> {
         self.maintenance_margin
     }
 
-    pub fn running_margin(&self) -> Option<u64> {
+    pub fn running_margin(&self) -> Option<Sats> {
         self.running_margin
     }
 
-    pub fn delta_pl(&self) -> Option<i64> {
+    pub fn delta_pl(&self) -> Option<Sats> {
         self.delta_pl
     }
 
@@ -303,6 +395,7 @@ mod tests {
         .expect("must deserialize isolated trade event");
 
         assert_eq!(event.pair(), "btc_usd");
+        assert_eq!(event.instrument(), Some(Instrument::FuturesInverseBtcUsd));
         assert_eq!(event.event(), "open");
         assert_eq!(event.trade().id(), Some(trade_id));
         assert_eq!(event.trade().side(), Some(TradeSide::Buy));
@@ -317,7 +410,7 @@ mod tests {
             Some(Leverage::try_from(3.0).unwrap())
         );
         assert_eq!(event.trade().price(), Some(Price::try_from(4.0).unwrap()));
-        assert_eq!(event.trade().opening_fee(), Some(5));
+        assert_eq!(event.trade().opening_fee(), Some(Sats::new(5)));
         assert_eq!(event.trade().created_at().unwrap().timestamp_millis(), 0);
         assert_eq!(event.trade().client_id(), Some(&client_id));
     }
@@ -360,7 +453,7 @@ mod tests {
             Some(OrderQuantity::try_from(1).unwrap())
         );
         assert_eq!(event.order().price(), Some(Price::try_from(2.0).unwrap()));
-        assert_eq!(event.order().trading_fee(), Some(3));
+        assert_eq!(event.order().trading_fee(), Some(Sats::new(3)));
         assert_eq!(event.order().client_id(), Some(&client_id));
         assert_eq!(event.order().created_at().unwrap().timestamp_millis(), 0);
     }
@@ -396,7 +489,7 @@ mod tests {
             event.position().leverage(),
             Some(CrossLeverage::try_from(2).unwrap())
         );
-        assert_eq!(event.position().margin(), Some(3));
+        assert_eq!(event.position().margin(), Some(Sats::new(3)));
         assert_eq!(
             event.position().entry_price(),
             Some(Price::try_from(4.0).unwrap())
@@ -405,13 +498,13 @@ mod tests {
             event.position().liquidation(),
             Some(Price::try_from(5.0).unwrap())
         );
-        assert_eq!(event.position().total_pl(), Some(6));
-        assert_eq!(event.position().funding_fees(), Some(7));
-        assert_eq!(event.position().trading_fees(), Some(8));
-        assert_eq!(event.position().initial_margin(), Some(9));
-        assert_eq!(event.position().maintenance_margin(), Some(10));
-        assert_eq!(event.position().running_margin(), Some(11));
-        assert_eq!(event.position().delta_pl(), Some(12));
+        assert_eq!(event.position().total_pl(), Some(Sats::new(6)));
+        assert_eq!(event.position().funding_fees(), Some(Sats::new(7)));
+        assert_eq!(event.position().trading_fees(), Some(Sats::new(8)));
+        assert_eq!(event.position().initial_margin(), Some(Sats::new(9)));
+        assert_eq!(event.position().maintenance_margin(), Some(Sats::new(10)));
+        assert_eq!(event.position().running_margin(), Some(Sats::new(11)));
+        assert_eq!(event.position().delta_pl(), Some(Sats::new(12)));
         assert_eq!(event.position().updated_at().unwrap().timestamp_millis(), 0);
     }
 }
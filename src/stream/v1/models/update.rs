@@ -2,8 +2,11 @@ use serde::de::DeserializeOwned;
 use serde_json::Value;
 
 use crate::shared::models::{
+    condition::PriceReference,
+    instrument::Instrument,
     ohlc::{OhlcCandle, OhlcRange},
     oracle::{Index, LastPrice},
+    price::Price,
 };
 
 use super::{
@@ -46,6 +49,12 @@ pub enum StreamUpdate {
 }
 
 impl StreamUpdate {
+    /// Returns the instrument of market and trading updates, or `None` for announcements, wallet
+    /// and connection-status updates.
+    pub fn instrument(&self) -> Option<Instrument> {
+        self.topic().and_then(|topic| topic.instrument())
+    }
+
     /// Returns the subscription topic for topic updates, or `None` for connection-status updates.
     pub fn topic(&self) -> Option<StreamTopic> {
         match self {
@@ -75,6 +84,23 @@ impl StreamUpdate {
         }
     }
 
+    /// Returns the price of the given reference series carried by the update, if any.
+    ///
+    /// Last prices are carried by [`FuturesInverseBtcUsdLastPrice`](Self::FuturesInverseBtcUsdLastPrice)
+    /// updates, index prices by [`FuturesInverseBtcUsdIndex`](Self::FuturesInverseBtcUsdIndex)
+    /// updates, and both by [`FuturesInverseBtcUsdTicker`](Self::FuturesInverseBtcUsdTicker)
+    /// updates.
+    pub fn price(&self, reference: PriceReference) -> Option<Price> {
+        match (self, reference) {
+            (Self::FuturesInverseBtcUsdTicker(ticker), _) => ticker.price(reference),
+            (Self::FuturesInverseBtcUsdLastPrice(last_price), PriceReference::Last) => {
+                Some(last_price.last_price())
+            }
+            (Self::FuturesInverseBtcUsdIndex(index), PriceReference::Index) => Some(index.index()),
+            _ => None,
+        }
+    }
+
     pub(in crate::stream::v1) fn from_subscription(
         topic: StreamTopic,
         data: Value,
@@ -188,6 +214,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn price_only_returns_requested_reference() {
+        let last_price = StreamUpdate::from_subscription(
+            StreamTopic::FuturesInverseBtcUsdLastPrice,
+            json!({ "time": 0, "lastPrice": 100_000 }),
+        )
+        .unwrap();
+        let ticker = StreamUpdate::from_subscription(
+            StreamTopic::FuturesInverseBtcUsdTicker,
+            json!({ "time": 0, "lastPrice": 100_000, "index": 99_000, "funding": { "rate": 0.0, "time": 0 } }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            last_price.price(PriceReference::Last),
+            Some(Price::try_from(100_000).unwrap())
+        );
+        assert_eq!(last_price.price(PriceReference::Index), None);
+        assert_eq!(
+            ticker.price(PriceReference::Index),
+            Some(Price::try_from(99_000).unwrap())
+        );
+    }
+
     #[test]
     fn connection_status_update_has_no_topic() {
         let update = StreamUpdate::ConnectionStatus(StreamConnectionStatus::Connected);
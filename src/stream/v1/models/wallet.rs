@@ -1,22 +1,24 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::models::currency::Currency;
 
 /// Wallet deposit event notification payload.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamWalletDeposit {
-    currency: String,
+    currency: Currency,
     network: String,
     id: String,
     amount: f64,
     balance: f64,
     status: String,
-    #[serde(default)]
+    #[serde(default, alias = "tx_id")]
     tx_id: Option<String>,
 }
 
 impl StreamWalletDeposit {
-    pub fn currency(&self) -> &str {
-        &self.currency
+    pub fn currency(&self) -> Currency {
+        self.currency
     }
 
     pub fn network(&self) -> &str {
@@ -45,23 +47,23 @@ impl StreamWalletDeposit {
 }
 
 /// Wallet withdrawal event notification payload.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamWalletWithdrawal {
-    currency: String,
+    currency: Currency,
     network: String,
     id: String,
     amount: f64,
     fee: f64,
     balance: f64,
     status: String,
-    #[serde(default)]
+    #[serde(default, alias = "tx_id")]
     tx_id: Option<String>,
 }
 
 impl StreamWalletWithdrawal {
-    pub fn currency(&self) -> &str {
-        &self.currency
+    pub fn currency(&self) -> Currency {
+        self.currency
     }
 
     pub fn network(&self) -> &str {
@@ -104,7 +106,7 @@ mod tests {
         )
         .expect("must deserialize wallet deposit");
 
-        assert_eq!(deposit.currency(), "btc");
+        assert_eq!(deposit.currency(), Currency::Btc);
         assert_eq!(deposit.network(), "lightning");
         assert_eq!(deposit.id(), "deposit-1");
         assert_eq!(deposit.amount(), 1.0);
@@ -120,7 +122,7 @@ mod tests {
         )
         .expect("must deserialize wallet withdrawal");
 
-        assert_eq!(withdrawal.currency(), "btc");
+        assert_eq!(withdrawal.currency(), Currency::Btc);
         assert_eq!(withdrawal.network(), "lightning");
         assert_eq!(withdrawal.id(), "withdrawal-1");
         assert_eq!(withdrawal.amount(), 1.0);
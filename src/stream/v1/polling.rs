@@ -0,0 +1,730 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::{
+    sync::{
+        Mutex as AsyncMutex,
+        broadcast::{self, Receiver, Sender, error::RecvError},
+    },
+    task::JoinHandle,
+    time::{self, MissedTickBehavior},
+};
+
+use crate::{rest::v3::LnmFuturesApi, shared::clock::Clock};
+
+use super::{
+    StreamConnection,
+    error::{Result, StreamApiError},
+    models::{
+        Index, LastPrice, StreamCrossPositionEvent, StreamTicker,
+        rpc::{AuthenticateResult, HelloResult, WhoamiResult},
+        topic::StreamTopic,
+        update::StreamUpdate,
+    },
+    repositories::StreamRepository,
+    state::StreamConnectionStatus,
+};
+
+/// Topics that can be backed by REST polling.
+const POLLED_TOPICS: [StreamTopic; 4] = [
+    StreamTopic::FuturesInverseBtcUsdTicker,
+    StreamTopic::FuturesInverseBtcUsdLastPrice,
+    StreamTopic::FuturesInverseBtcUsdIndex,
+    StreamTopic::FuturesInverseBtcUsdCrossPosition,
+];
+
+/// Event name of cross position updates built from polled positions.
+const POLLED_POSITION_EVENT: &str = "update";
+
+/// Capacity of the channel of updates built from polled data.
+const UPDATE_CHANNEL_CAPACITY: usize = 256;
+
+/// REST API polled in place of the Stream API when the WebSocket can't connect, e.g. on networks
+/// blocking WebSockets.
+///
+/// Set with [`StreamClientConfig::with_rest_fallback`](super::StreamClientConfig::with_rest_fallback).
+/// The ticker, last price, index and cross position topics are then backed by REST polling every
+/// `poll_interval`, and delivered as the same [`StreamUpdate`]s. Updates are only sent when the
+/// polled value changed. Polled cross position updates use the `update` event name. Cross
+/// position polling requires the API to be authenticated.
+///
+/// While polling, the WebSocket connection is retried every
+/// [`websocket_retry_interval`](Self::with_websocket_retry_interval). Once it connects, the
+/// subscribed topics are moved to the WebSocket, polling stops, and the connection handle and its
+/// receivers keep working, backed by the WebSocket. Credentials passed to
+/// [`authenticate`](super::StreamRepository::authenticate) while polling are used to
+/// authenticate the WebSocket session. Whether the connection is polling is reported by
+/// [`is_polling`](super::StreamRepository::is_polling).
+#[derive(Clone)]
+pub struct RestFallback {
+    api: Arc<dyn LnmFuturesApi>,
+    poll_interval: Duration,
+    websocket_retry_interval: Duration,
+}
+
+impl RestFallback {
+    pub fn new(api: Arc<dyn LnmFuturesApi>, poll_interval: Duration) -> Self {
+        Self {
+            api,
+            poll_interval,
+            websocket_retry_interval: Duration::from_secs(60),
+        }
+    }
+
+    /// Sets how often the WebSocket connection is retried while polling.
+    ///
+    /// Default: 60 seconds
+    pub fn with_websocket_retry_interval(mut self, interval: Duration) -> Self {
+        self.websocket_retry_interval = interval;
+        self
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    pub fn websocket_retry_interval(&self) -> Duration {
+        self.websocket_retry_interval
+    }
+}
+
+impl fmt::Debug for RestFallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RestFallback")
+            .field("poll_interval", &self.poll_interval)
+            .field("websocket_retry_interval", &self.websocket_retry_interval)
+            .finish()
+    }
+}
+
+/// Opens a new WebSocket connection, retried while polling.
+pub(super) type WebSocketConnector =
+    Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<StreamConnection>> + Send>> + Send + Sync>;
+
+/// Values sent by the last poll, so unchanged values aren't sent again.
+#[derive(Default)]
+struct LastPolled {
+    ticker: Option<StreamTicker>,
+    position: Option<StreamCrossPositionEvent>,
+}
+
+/// Credentials passed to `authenticate` while polling.
+#[derive(Clone)]
+struct Credentials {
+    key: String,
+    secret: String,
+    passphrase: String,
+}
+
+/// State shared between [`PollingStreamRepo`] and its polling task.
+struct Shared {
+    subscriptions: Mutex<HashSet<StreamTopic>>,
+    credentials: Mutex<Option<Credentials>>,
+    /// WebSocket connection the repository switched to, if any.
+    websocket: Mutex<Option<StreamConnection>>,
+    /// Task forwarding the updates of the WebSocket connection.
+    forward_handle: Mutex<Option<JoinHandle<()>>>,
+    /// Serializes subscription changes with the switch to the WebSocket.
+    transition: AsyncMutex<()>,
+    update_tx: Sender<StreamUpdate>,
+}
+
+impl Shared {
+    fn lock_subscriptions(&self) -> std::sync::MutexGuard<'_, HashSet<StreamTopic>> {
+        self.subscriptions
+            .lock()
+            .expect("`PollingStreamRepo` mutex can't be poisoned")
+    }
+
+    fn websocket(&self) -> Option<StreamConnection> {
+        self.websocket
+            .lock()
+            .expect("`PollingStreamRepo` mutex can't be poisoned")
+            .clone()
+    }
+
+    fn credentials(&self) -> Option<Credentials> {
+        self.credentials
+            .lock()
+            .expect("`PollingStreamRepo` mutex can't be poisoned")
+            .clone()
+    }
+}
+
+/// [`StreamRepository`] backed by REST polling, used when the WebSocket can't connect.
+///
+/// Only the topics of [`RestFallback`] can be subscribed to. JSON-RPC requests other than `ping`
+/// aren't supported. Once the WebSocket connects, every method is delegated to it.
+pub(super) struct PollingStreamRepo {
+    shared: Arc<Shared>,
+    status: Mutex<StreamConnectionStatus>,
+    poll_handle: JoinHandle<()>,
+}
+
+impl PollingStreamRepo {
+    /// Starts polling. The WebSocket is retried through `connect`, if any.
+    pub fn new(
+        fallback: RestFallback,
+        clock: Arc<dyn Clock>,
+        connect: Option<WebSocketConnector>,
+    ) -> Self {
+        let (update_tx, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+        let shared = Arc::new(Shared {
+            subscriptions: Mutex::new(HashSet::new()),
+            credentials: Mutex::new(None),
+            websocket: Mutex::new(None),
+            forward_handle: Mutex::new(None),
+            transition: AsyncMutex::new(()),
+            update_tx,
+        });
+
+        let poll_handle = tokio::spawn(poll(fallback, clock, shared.clone(), connect));
+
+        Self {
+            shared,
+            status: Mutex::new(StreamConnectionStatus::Connected),
+            poll_handle,
+        }
+    }
+
+    fn lock_subscriptions(&self) -> std::sync::MutexGuard<'_, HashSet<StreamTopic>> {
+        self.shared.lock_subscriptions()
+    }
+
+    fn status(&self) -> StreamConnectionStatus {
+        self.status
+            .lock()
+            .expect("`PollingStreamRepo` mutex can't be poisoned")
+            .clone()
+    }
+
+    async fn evaluate_connection_status(&self) -> Result<()> {
+        let connection_status = self.status();
+
+        if connection_status.is_connected() {
+            return Ok(());
+        }
+
+        Err(StreamApiError::BadConnectionStatus(connection_status))
+    }
+}
+
+async fn poll(
+    fallback: RestFallback,
+    clock: Arc<dyn Clock>,
+    shared: Arc<Shared>,
+    connect: Option<WebSocketConnector>,
+) {
+    let mut interval = time::interval(fallback.poll_interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let retry_interval = fallback.websocket_retry_interval;
+    let mut retry = time::interval_at(time::Instant::now() + retry_interval, retry_interval);
+    retry.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut last = LastPolled::default();
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let topics = shared.lock_subscriptions().clone();
+                let updates = poll_once(&fallback, clock.now(), &topics, &mut last).await;
+
+                for update in updates {
+                    // No receivers is not an error, updates are simply dropped
+                    let _ = shared.update_tx.send(update);
+                }
+            }
+            _ = retry.tick(), if connect.is_some() => {
+                let connect = connect.as_ref().expect("retried with a connector");
+                if switch_to_websocket(&shared, connect).await {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Connects the WebSocket, moves the subscriptions to it and forwards its updates, returning
+/// `false` if any step failed.
+async fn switch_to_websocket(shared: &Shared, connect: &WebSocketConnector) -> bool {
+    let websocket = match connect().await {
+        Ok(websocket) => websocket,
+        Err(e) => {
+            log::debug!("Stream WebSocket still unavailable, polling the REST API: {e}");
+            return false;
+        }
+    };
+
+    let _transition = shared.transition.lock().await;
+
+    let result = async {
+        if let Some(credentials) = shared.credentials() {
+            websocket
+                .authenticate(
+                    &credentials.key,
+                    &credentials.secret,
+                    &credentials.passphrase,
+                )
+                .await?;
+        }
+
+        let topics: Vec<_> = shared.lock_subscriptions().iter().cloned().collect();
+        if !topics.is_empty() {
+            websocket.subscribe(topics).await?;
+        }
+
+        websocket.receiver().await
+    }
+    .await;
+
+    let mut receiver = match result {
+        Ok(receiver) => receiver,
+        Err(e) => {
+            log::warn!("Failed to switch to the Stream WebSocket, polling the REST API: {e}");
+            let _ = websocket.disconnect().await;
+            return false;
+        }
+    };
+
+    let update_tx = shared.update_tx.clone();
+    let forward_handle = tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(update) => {
+                    let _ = update_tx.send(update);
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    log::warn!("Stream WebSocket forwarding lagged, {skipped} updates skipped");
+                }
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+
+    *shared
+        .forward_handle
+        .lock()
+        .expect("`PollingStreamRepo` mutex can't be poisoned") = Some(forward_handle);
+    *shared
+        .websocket
+        .lock()
+        .expect("`PollingStreamRepo` mutex can't be poisoned") = Some(websocket);
+
+    log::info!("Stream WebSocket connected, stopped polling the REST API");
+
+    true
+}
+
+/// Polls the REST API for the subscribed `topics`, returning the updates of changed values.
+async fn poll_once(
+    fallback: &RestFallback,
+    now: DateTime<Utc>,
+    topics: &HashSet<StreamTopic>,
+    last: &mut LastPolled,
+) -> Vec<StreamUpdate> {
+    let mut updates = Vec::new();
+
+    let price_topics_subscribed = [
+        StreamTopic::FuturesInverseBtcUsdTicker,
+        StreamTopic::FuturesInverseBtcUsdLastPrice,
+        StreamTopic::FuturesInverseBtcUsdIndex,
+    ]
+    .iter()
+    .any(|topic| topics.contains(topic));
+
+    if price_topics_subscribed {
+        match fallback.api.get_ticker().await {
+            Ok(ticker) => {
+                let ticker = StreamTicker::from_rest(&ticker, now);
+                let previous = last.ticker.replace(ticker.clone());
+
+                let ticker_changed = previous.as_ref().is_none_or(|previous| {
+                    previous.last_price() != ticker.last_price()
+                        || previous.index() != ticker.index()
+                        || previous.funding() != ticker.funding()
+                });
+                if topics.contains(&StreamTopic::FuturesInverseBtcUsdTicker) && ticker_changed {
+                    updates.push(StreamUpdate::FuturesInverseBtcUsdTicker(ticker.clone()));
+                }
+
+                let changed = |price: fn(&StreamTicker) -> Option<_>| {
+                    previous.as_ref().and_then(price) != price(&ticker)
+                };
+
+                if topics.contains(&StreamTopic::FuturesInverseBtcUsdLastPrice)
+                    && changed(StreamTicker::last_price)
+                    && let Some(last_price) = ticker.last_price()
+                {
+                    updates.push(StreamUpdate::FuturesInverseBtcUsdLastPrice(LastPrice::new(
+                        now, last_price,
+                    )));
+                }
+
+                if topics.contains(&StreamTopic::FuturesInverseBtcUsdIndex)
+                    && changed(StreamTicker::index)
+                    && let Some(index) = ticker.index()
+                {
+                    updates.push(StreamUpdate::FuturesInverseBtcUsdIndex(Index::new(
+                        now, index,
+                    )));
+                }
+            }
+            Err(e) => log::warn!("Failed to poll the REST ticker: {e}"),
+        }
+    } else {
+        last.ticker = None;
+    }
+
+    if topics.contains(&StreamTopic::FuturesInverseBtcUsdCrossPosition) {
+        match fallback.api.get_cross_position().await {
+            Ok(position) => {
+                // Compared as of the previous poll time, so only position changes count
+                let unchanged = last.position.as_ref().is_some_and(|previous| {
+                    let time = previous.position().updated_at().unwrap_or(now);
+                    StreamCrossPositionEvent::from_rest(POLLED_POSITION_EVENT, &position, time)
+                        == *previous
+                });
+
+                if !unchanged {
+                    let event =
+                        StreamCrossPositionEvent::from_rest(POLLED_POSITION_EVENT, &position, now);
+                    last.position = Some(event.clone());
+                    updates.push(StreamUpdate::FuturesInverseBtcUsdCrossPosition(event));
+                }
+            }
+            Err(e) => log::warn!("Failed to poll the REST cross position: {e}"),
+        }
+    } else {
+        last.position = None;
+    }
+
+    updates
+}
+
+impl crate::sealed::Sealed for PollingStreamRepo {}
+
+#[async_trait]
+impl StreamRepository for PollingStreamRepo {
+    async fn is_connected(&self) -> bool {
+        match self.shared.websocket() {
+            Some(websocket) => websocket.is_connected().await,
+            None => self.status().is_connected(),
+        }
+    }
+
+    async fn connection_status(&self) -> StreamConnectionStatus {
+        match self.shared.websocket() {
+            Some(websocket) => websocket.connection_status().await,
+            None => self.status(),
+        }
+    }
+
+    async fn is_polling(&self) -> bool {
+        self.shared.websocket().is_none()
+    }
+
+    async fn hello(&self, client_name: &str, client_version: &str) -> Result<HelloResult> {
+        match self.shared.websocket() {
+            Some(websocket) => websocket.hello(client_name, client_version).await,
+            None => Err(StreamApiError::NotSupportedWhilePolling("hello")),
+        }
+    }
+
+    async fn ping(&self) -> Result<()> {
+        match self.shared.websocket() {
+            Some(websocket) => websocket.ping().await,
+            None => self.evaluate_connection_status().await,
+        }
+    }
+
+    async fn time(&self) -> Result<DateTime<Utc>> {
+        match self.shared.websocket() {
+            Some(websocket) => websocket.time().await,
+            None => Err(StreamApiError::NotSupportedWhilePolling("time")),
+        }
+    }
+
+    async fn authenticate(
+        &self,
+        key: &str,
+        secret: &str,
+        passphrase: &str,
+    ) -> Result<AuthenticateResult> {
+        let _transition = self.shared.transition.lock().await;
+
+        if let Some(websocket) = self.shared.websocket() {
+            return websocket.authenticate(key, secret, passphrase).await;
+        }
+
+        // Kept to authenticate the WebSocket once it connects
+        *self
+            .shared
+            .credentials
+            .lock()
+            .expect("`PollingStreamRepo` mutex can't be poisoned") = Some(Credentials {
+            key: key.to_string(),
+            secret: secret.to_string(),
+            passphrase: passphrase.to_string(),
+        });
+
+        Err(StreamApiError::NotSupportedWhilePolling("authenticate"))
+    }
+
+    async fn whoami(&self) -> Result<WhoamiResult> {
+        match self.shared.websocket() {
+            Some(websocket) => websocket.whoami().await,
+            None => Err(StreamApiError::NotSupportedWhilePolling("whoami")),
+        }
+    }
+
+    async fn subscribe(&self, topics: Vec<StreamTopic>) -> Result<()> {
+        let _transition = self.shared.transition.lock().await;
+
+        if let Some(websocket) = self.shared.websocket() {
+            return websocket.subscribe(topics).await;
+        }
+
+        self.evaluate_connection_status().await?;
+
+        if let Some(topic) = topics.iter().find(|topic| !POLLED_TOPICS.contains(topic)) {
+            return Err(StreamApiError::TopicNotPolled(topic.clone()));
+        }
+
+        self.lock_subscriptions().extend(topics);
+        Ok(())
+    }
+
+    async fn unsubscribe(&self, topics: Vec<StreamTopic>) -> Result<()> {
+        let _transition = self.shared.transition.lock().await;
+
+        if let Some(websocket) = self.shared.websocket() {
+            return websocket.unsubscribe(topics).await;
+        }
+
+        self.evaluate_connection_status().await?;
+
+        let mut subscriptions = self.lock_subscriptions();
+        for topic in topics {
+            subscriptions.remove(&topic);
+        }
+        Ok(())
+    }
+
+    async fn unsubscribe_all(&self) -> Result<Vec<StreamTopic>> {
+        let _transition = self.shared.transition.lock().await;
+
+        if let Some(websocket) = self.shared.websocket() {
+            return websocket.unsubscribe_all().await;
+        }
+
+        self.evaluate_connection_status().await?;
+
+        Ok(self.lock_subscriptions().drain().collect())
+    }
+
+    async fn subscriptions(&self) -> HashSet<StreamTopic> {
+        match self.shared.websocket() {
+            Some(websocket) => websocket.subscriptions().await,
+            None => self.lock_subscriptions().clone(),
+        }
+    }
+
+    async fn receiver(&self) -> Result<Receiver<StreamUpdate>> {
+        // Updates of the WebSocket are forwarded to the same channel
+        Ok(self.shared.update_tx.subscribe())
+    }
+
+    async fn disconnect(&self) -> Result<()> {
+        if let Some(websocket) = self.shared.websocket() {
+            return websocket.disconnect().await;
+        }
+
+        self.evaluate_connection_status().await?;
+
+        self.poll_handle.abort();
+        *self
+            .status
+            .lock()
+            .expect("`PollingStreamRepo` mutex can't be poisoned") =
+            StreamConnectionStatus::Disconnected;
+
+        let _ = self.shared.update_tx.send(StreamUpdate::ConnectionStatus(
+            StreamConnectionStatus::Disconnected,
+        ));
+
+        Ok(())
+    }
+}
+
+impl Drop for PollingStreamRepo {
+    fn drop(&mut self) {
+        self.poll_handle.abort();
+
+        if let Ok(mut handle) = self.shared.forward_handle.lock()
+            && let Some(forward_handle) = handle.take()
+        {
+            forward_handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        rest::v3::models::Ticker,
+        shared::clock::SystemClock,
+        testing::{fixtures, stub::StubApi},
+    };
+
+    /// Returns the ticker in `ticker`, and the running position fixture.
+    fn ticker_api(ticker: Arc<Mutex<Ticker>>) -> StubApi {
+        StubApi::new().with_get_ticker(move || Ok(ticker.lock().unwrap().clone()))
+    }
+
+    fn ticker(index: u32, last_price: u32) -> Ticker {
+        serde_json::from_value(json!({
+            "index": index,
+            "lastPrice": last_price,
+            "prices": [],
+            "fundingRate": 0.0001,
+            "fundingTime": "2025-01-01T00:00:00.000Z",
+        }))
+        .unwrap()
+    }
+
+    fn topics(updates: &[StreamUpdate]) -> Vec<StreamTopic> {
+        updates.iter().filter_map(StreamUpdate::topic).collect()
+    }
+
+    #[tokio::test]
+    async fn test_poll_sends_changed_values() {
+        let current = Arc::new(Mutex::new(ticker(100_000, 100_000)));
+        let api = Arc::new(ticker_api(current.clone()));
+        let fallback = RestFallback::new(api.clone(), Duration::from_secs(1));
+        let subscribed: HashSet<_> = POLLED_TOPICS.into_iter().collect();
+        let mut last = LastPolled::default();
+
+        let updates = poll_once(&fallback, Utc::now(), &subscribed, &mut last).await;
+        assert_eq!(topics(&updates), POLLED_TOPICS);
+        let StreamUpdate::FuturesInverseBtcUsdCrossPosition(event) = &updates[3] else {
+            panic!("expected a cross position update");
+        };
+        assert_eq!(event.event(), POLLED_POSITION_EVENT);
+        assert_eq!(
+            event.position().quantity(),
+            Some(fixtures::running_position().quantity())
+        );
+
+        // Nothing changed
+        let updates = poll_once(&fallback, Utc::now(), &subscribed, &mut last).await;
+        assert!(updates.is_empty());
+
+        *current.lock().unwrap() = ticker(100_000, 100_500);
+        let updates = poll_once(&fallback, Utc::now(), &subscribed, &mut last).await;
+        assert_eq!(
+            topics(&updates),
+            [
+                StreamTopic::FuturesInverseBtcUsdTicker,
+                StreamTopic::FuturesInverseBtcUsdLastPrice,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_polling_repo_subscriptions() {
+        let api = Arc::new(ticker_api(Arc::new(Mutex::new(ticker(100_000, 100_000)))));
+        let repo = PollingStreamRepo::new(
+            RestFallback::new(api, Duration::from_millis(10)),
+            Arc::new(SystemClock),
+            None,
+        );
+        let mut receiver = repo.receiver().await.unwrap();
+
+        assert!(matches!(
+            repo.subscribe(vec![StreamTopic::FuturesInverseBtcUsdCrossOrders])
+                .await,
+            Err(StreamApiError::TopicNotPolled(_))
+        ));
+        repo.subscribe(vec![StreamTopic::FuturesInverseBtcUsdIndex])
+            .await
+            .unwrap();
+
+        let update = receiver.recv().await.unwrap();
+        assert_eq!(update.topic(), Some(StreamTopic::FuturesInverseBtcUsdIndex));
+
+        repo.disconnect().await.unwrap();
+        assert!(!repo.is_connected().await);
+        assert!(matches!(
+            repo.authenticate("key", "secret", "passphrase").await,
+            Err(StreamApiError::NotSupportedWhilePolling("authenticate"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_polling_repo_switches_to_websocket() {
+        let api = Arc::new(ticker_api(Arc::new(Mutex::new(ticker(100_000, 100_000)))));
+        let attempts = Arc::new(AtomicUsize::new(0));
+
+        // Stands in for the WebSocket, failing to connect on the first attempt
+        let connect: WebSocketConnector = {
+            let api = api.clone();
+            let attempts = attempts.clone();
+            Arc::new(move || {
+                let api = api.clone();
+                let attempts = attempts.clone();
+                Box::pin(async move {
+                    if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return Err(StreamApiError::NotSupportedWhilePolling("connect"));
+                    }
+                    let websocket: StreamConnection = Arc::new(PollingStreamRepo::new(
+                        RestFallback::new(api, Duration::from_millis(10)),
+                        Arc::new(SystemClock),
+                        None,
+                    ));
+                    Ok(websocket)
+                })
+            })
+        };
+
+        let repo = PollingStreamRepo::new(
+            RestFallback::new(api, Duration::from_secs(60))
+                .with_websocket_retry_interval(Duration::from_millis(10)),
+            Arc::new(SystemClock),
+            Some(connect),
+        );
+        let mut receiver = repo.receiver().await.unwrap();
+        repo.subscribe(vec![StreamTopic::FuturesInverseBtcUsdIndex])
+            .await
+            .unwrap();
+        assert!(repo.is_polling().await);
+
+        while repo.is_polling().await {
+            time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert!(repo.is_connected().await);
+        assert_eq!(
+            repo.subscriptions().await,
+            HashSet::from([StreamTopic::FuturesInverseBtcUsdIndex])
+        );
+
+        // First update is from the initial poll, the next ones are forwarded from the WebSocket
+        receiver.recv().await.unwrap();
+        let update = receiver.recv().await.unwrap();
+        assert_eq!(update.topic(), Some(StreamTopic::FuturesInverseBtcUsdIndex));
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+/// Raw text frame received from the Stream API, timestamped at reception.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    received_at: DateTime<Utc>,
+    payload: String,
+}
+
+impl RecordedFrame {
+    pub fn new(received_at: DateTime<Utc>, payload: impl Into<String>) -> Self {
+        Self {
+            received_at,
+            payload: payload.into(),
+        }
+    }
+
+    /// Time the frame was received.
+    pub fn received_at(&self) -> DateTime<Utc> {
+        self.received_at
+    }
+
+    /// Raw JSON-RPC message carried by the frame.
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+}
+
+/// Destination of the frames recorded from a Stream API connection, set with
+/// [`StreamClientConfig::with_frame_sink`](super::StreamClientConfig::with_frame_sink).
+///
+/// Called from the connection's event loop for every text frame received, before it is decoded,
+/// so implementations should return quickly.
+pub trait FrameSink: fmt::Debug + Send + Sync {
+    fn record(&self, frame: &RecordedFrame);
+}
+
+impl FrameSink for mpsc::UnboundedSender<RecordedFrame> {
+    fn record(&self, frame: &RecordedFrame) {
+        // The receiver being dropped just stops the recording
+        let _ = self.send(frame.clone());
+    }
+}
+
+/// [`FrameSink`] appending frames to a file, one JSON object per line.
+///
+/// Recordings can be read back with [`FileFrameSink::read`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::sync::Arc;
+///
+/// use lnm_sdk::stream::v1::{StreamClient, StreamClientConfig, recording::FileFrameSink};
+///
+/// let sink = FileFrameSink::create("stream-recording.jsonl")?;
+/// let config = StreamClientConfig::default().with_frame_sink(Arc::new(sink));
+///
+/// let client = StreamClient::new(config);
+/// let conn = client.connect().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FileFrameSink {
+    path: PathBuf,
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl FileFrameSink {
+    /// Opens `path` for appending, creating it if needed.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok(Self {
+            path,
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the frames recorded to `path`, in reception order. A torn last line, left by a crash
+    /// mid-write, is ignored.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Vec<RecordedFrame>> {
+        let reader = BufReader::new(File::open(path)?);
+        let lines = reader.lines().collect::<io::Result<Vec<_>>>()?;
+        let last = lines.len().saturating_sub(1);
+
+        let mut frames = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str(line) {
+                Ok(frame) => frames.push(frame),
+                Err(_) if i == last => break,
+                Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+            }
+        }
+
+        Ok(frames)
+    }
+
+    fn write(&self, frame: &RecordedFrame) -> io::Result<()> {
+        let mut writer = self
+            .writer
+            .lock()
+            .expect("`FileFrameSink` mutex can't be poisoned");
+
+        serde_json::to_writer(&mut *writer, frame)?;
+        writer.write_all(b"\n")?;
+        writer.flush()
+    }
+}
+
+impl FrameSink for FileFrameSink {
+    fn record(&self, frame: &RecordedFrame) {
+        if let Err(e) = self.write(frame) {
+            log::warn!(
+                "Failed to record Stream frame to {}: {e}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+impl fmt::Debug for FileFrameSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileFrameSink")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_file_sink_roundtrip() {
+        let path = std::env::temp_dir().join(format!("lnm-sdk-recording-{}.jsonl", Uuid::new_v4()));
+        let frames = vec![
+            RecordedFrame::new(Utc::now(), r#"{"jsonrpc":"2.0","id":"1","result":{}}"#),
+            RecordedFrame::new(Utc::now(), r#"{"jsonrpc":"2.0","method":"subscription"}"#),
+        ];
+
+        let sink = FileFrameSink::create(&path).unwrap();
+        for frame in &frames {
+            sink.record(frame);
+        }
+
+        // Torn last line
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(br#"{"received_at":"#)
+            .unwrap();
+
+        assert_eq!(FileFrameSink::read(&path).unwrap(), frames);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,11 +1,13 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Duration};
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use tokio::sync::broadcast::Receiver;
 
 use super::{
+    conflation::ConflatedReceiver,
     error::Result,
+    fills::FillReceiver,
     models::{
         rpc::{AuthenticateResult, HelloResult, WhoamiResult},
         topic::StreamTopic,
@@ -25,6 +27,12 @@ pub trait StreamRepository: crate::sealed::Sealed + Send + Sync {
     /// Returns the current connection status of the WebSocket.
     async fn connection_status(&self) -> StreamConnectionStatus;
 
+    /// Returns `true` while the topics are backed by polling the REST API instead of the
+    /// WebSocket, see [`RestFallback`](super::RestFallback).
+    async fn is_polling(&self) -> bool {
+        false
+    }
+
     /// Sends a `hello` request with client identification metadata.
     async fn hello(&self, client_name: &str, client_version: &str) -> Result<HelloResult>;
 
@@ -60,6 +68,20 @@ pub trait StreamRepository: crate::sealed::Sealed + Send + Sync {
     /// Creates a new receiver for Stream updates.
     async fn receiver(&self) -> Result<Receiver<StreamUpdate>>;
 
+    /// Creates a new receiver for Stream updates that delivers at most one ticker, last price and
+    /// index update per `interval`, the latest value winning. See [`ConflatedReceiver`].
+    async fn conflated_receiver(&self, interval: Duration) -> Result<ConflatedReceiver> {
+        let rx = self.receiver().await?;
+        Ok(ConflatedReceiver::new(rx, interval))
+    }
+
+    /// Creates a new receiver for the isolated trade and cross order fills contained in Stream
+    /// updates. See [`FillReceiver`].
+    async fn fill_receiver(&self) -> Result<FillReceiver> {
+        let rx = self.receiver().await?;
+        Ok(FillReceiver::new(rx))
+    }
+
     /// Disconnects the Stream WebSocket.
     async fn disconnect(&self) -> Result<()>;
 }
@@ -0,0 +1,436 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    num::NonZeroUsize,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::{
+    sync::broadcast::{self, Receiver, error::RecvError},
+    task::JoinHandle,
+};
+
+use super::{
+    StreamConnection,
+    config::StreamClientConfig,
+    error::{Result, StreamApiError},
+    lnm::LnmStreamRepo,
+    models::{
+        rpc::{AuthenticateResult, HelloResult, WhoamiResult},
+        topic::StreamTopic,
+        update::StreamUpdate,
+    },
+    repositories::StreamRepository,
+    state::StreamConnectionStatus,
+};
+
+/// Function returning the index of the shard a topic is subscribed on.
+///
+/// Indexes out of range are assigned to the last shard.
+pub type ShardRouter = Arc<dyn Fn(&StreamTopic) -> usize + Send + Sync>;
+
+/// Set of independent Stream API connections, managed as a single [`StreamRepository`].
+///
+/// Subscriptions are sharded across the connections by a [`ShardRouter`], so that a flood of
+/// market data on one connection doesn't delay account events, such as fill notifications,
+/// carried by another. Each connection has its own socket, event loop and reconnection cycle.
+///
+/// Requests that aren't topic-specific are handled as follows:
+///
+/// + [`authenticate`](StreamRepository::authenticate), [`hello`](StreamRepository::hello),
+///   [`ping`](StreamRepository::ping) and [`disconnect`](StreamRepository::disconnect) are sent
+///   on every shard.
+/// + [`time`](StreamRepository::time) and [`whoami`](StreamRepository::whoami) are sent on the
+///   first shard.
+/// + [`receiver`](StreamRepository::receiver) merges the updates from all shards, including
+///   their connection status updates. For full isolation, per-shard receivers can be obtained
+///   from [`shard`](Self::shard).
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use lnm_sdk::stream::v1::{
+///     StreamClientConfig, StreamRepository, models::StreamTopic, sharding::ShardedStreamConnection,
+/// };
+///
+/// // Public market data on the first connection, private account events on the second
+/// let conn = ShardedStreamConnection::connect_split(StreamClientConfig::default()).await?;
+///
+/// conn.authenticate("key", "secret", "passphrase").await?;
+/// conn.subscribe(vec![
+///     StreamTopic::FuturesInverseBtcUsdTicker,
+///     StreamTopic::FuturesInverseBtcUsdIsolatedTrades,
+/// ])
+/// .await?;
+///
+/// let mut trades_rx = conn.shard(1).expect("two shards").receiver().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ShardedStreamConnection {
+    shards: Vec<StreamConnection>,
+    router: ShardRouter,
+    merged_tx: broadcast::Sender<StreamUpdate>,
+    forward_handles: Vec<JoinHandle<()>>,
+}
+
+impl ShardedStreamConnection {
+    /// Opens `shard_count` connections configured with `config`, routing topics with `router`.
+    pub async fn connect(
+        config: StreamClientConfig,
+        shard_count: NonZeroUsize,
+        router: ShardRouter,
+    ) -> Result<Arc<Self>> {
+        let mut shards: Vec<StreamConnection> = Vec::with_capacity(shard_count.get());
+        for _ in 0..shard_count.get() {
+            shards.push(Arc::new(LnmStreamRepo::new(config.clone()).await?));
+        }
+
+        Self::from_shards(shards, router).await
+    }
+
+    /// Opens two connections configured with `config`: public topics are subscribed on the first
+    /// one, and [private](StreamTopic::is_private) account topics on the second one.
+    pub async fn connect_split(config: StreamClientConfig) -> Result<Arc<Self>> {
+        let router: ShardRouter = Arc::new(|topic: &StreamTopic| usize::from(topic.is_private()));
+
+        Self::connect(config, NonZeroUsize::new(2).expect("not zero"), router).await
+    }
+
+    async fn from_shards(shards: Vec<StreamConnection>, router: ShardRouter) -> Result<Arc<Self>> {
+        let (merged_tx, _) = broadcast::channel::<StreamUpdate>(10_000);
+
+        let mut forward_handles = Vec::with_capacity(shards.len());
+        for (index, shard) in shards.iter().enumerate() {
+            let mut shard_rx = shard.receiver().await?;
+            let merged_tx = merged_tx.clone();
+
+            forward_handles.push(tokio::spawn(async move {
+                loop {
+                    match shard_rx.recv().await {
+                        Ok(update) => {
+                            // No merged receivers is not an error
+                            let _ = merged_tx.send(update);
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            log::warn!("Stream shard {index} merge lagged, {skipped} updates lost");
+                        }
+                        Err(RecvError::Closed) => return,
+                    }
+                }
+            }));
+        }
+
+        Ok(Arc::new(Self {
+            shards,
+            router,
+            merged_tx,
+            forward_handles,
+        }))
+    }
+
+    /// Connections the topics are sharded across.
+    pub fn shards(&self) -> &[StreamConnection] {
+        &self.shards
+    }
+
+    /// Returns the connection at `index`, if any.
+    pub fn shard(&self, index: usize) -> Option<&StreamConnection> {
+        self.shards.get(index)
+    }
+
+    /// Returns the index of the shard `topic` is subscribed on.
+    pub fn shard_index(&self, topic: &StreamTopic) -> usize {
+        (self.router)(topic).min(self.shards.len() - 1)
+    }
+
+    fn group_by_shard(&self, topics: Vec<StreamTopic>) -> BTreeMap<usize, Vec<StreamTopic>> {
+        let mut groups: BTreeMap<usize, Vec<StreamTopic>> = BTreeMap::new();
+        for topic in topics {
+            groups
+                .entry(self.shard_index(&topic))
+                .or_default()
+                .push(topic);
+        }
+        groups
+    }
+
+    fn first_shard(&self) -> &StreamConnection {
+        &self.shards[0]
+    }
+}
+
+impl crate::sealed::Sealed for ShardedStreamConnection {}
+
+#[async_trait]
+impl StreamRepository for ShardedStreamConnection {
+    async fn is_connected(&self) -> bool {
+        for shard in &self.shards {
+            if !shard.is_connected().await {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns the status of the first shard that isn't connected, if any.
+    async fn connection_status(&self) -> StreamConnectionStatus {
+        for shard in &self.shards {
+            let status = shard.connection_status().await;
+            if !status.is_connected() {
+                return status;
+            }
+        }
+        StreamConnectionStatus::Connected
+    }
+
+    async fn hello(&self, client_name: &str, client_version: &str) -> Result<HelloResult> {
+        let mut first_result = None;
+        for shard in &self.shards {
+            let result = shard.hello(client_name, client_version).await?;
+            first_result.get_or_insert(result);
+        }
+        Ok(first_result.expect("at least one shard"))
+    }
+
+    async fn ping(&self) -> Result<()> {
+        for shard in &self.shards {
+            shard.ping().await?;
+        }
+        Ok(())
+    }
+
+    async fn time(&self) -> Result<DateTime<Utc>> {
+        self.first_shard().time().await
+    }
+
+    async fn authenticate(
+        &self,
+        key: &str,
+        secret: &str,
+        passphrase: &str,
+    ) -> Result<AuthenticateResult> {
+        let mut first_result = None;
+        for shard in &self.shards {
+            let result = shard.authenticate(key, secret, passphrase).await?;
+            if !result.authenticated() {
+                return Ok(result);
+            }
+            first_result.get_or_insert(result);
+        }
+        Ok(first_result.expect("at least one shard"))
+    }
+
+    async fn whoami(&self) -> Result<WhoamiResult> {
+        self.first_shard().whoami().await
+    }
+
+    async fn subscribe(&self, topics: Vec<StreamTopic>) -> Result<()> {
+        for (index, topics) in self.group_by_shard(topics) {
+            self.shards[index].subscribe(topics).await?;
+        }
+        Ok(())
+    }
+
+    async fn unsubscribe(&self, topics: Vec<StreamTopic>) -> Result<()> {
+        for (index, topics) in self.group_by_shard(topics) {
+            self.shards[index].unsubscribe(topics).await?;
+        }
+        Ok(())
+    }
+
+    async fn unsubscribe_all(&self) -> Result<Vec<StreamTopic>> {
+        let mut unsubscribed = Vec::new();
+        for shard in &self.shards {
+            unsubscribed.extend(shard.unsubscribe_all().await?);
+        }
+        Ok(unsubscribed)
+    }
+
+    async fn subscriptions(&self) -> HashSet<StreamTopic> {
+        let mut subscriptions = HashSet::new();
+        for shard in &self.shards {
+            subscriptions.extend(shard.subscriptions().await);
+        }
+        subscriptions
+    }
+
+    async fn receiver(&self) -> Result<Receiver<StreamUpdate>> {
+        let status = self.connection_status().await;
+        if !status.is_connected() {
+            return Err(StreamApiError::BadConnectionStatus(status));
+        }
+
+        Ok(self.merged_tx.subscribe())
+    }
+
+    /// Disconnects every shard, returning the first error, if any.
+    async fn disconnect(&self) -> Result<()> {
+        let mut first_err = None;
+        for shard in &self.shards {
+            if let Err(e) = shard.disconnect().await {
+                first_err.get_or_insert(e);
+            }
+        }
+
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for ShardedStreamConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedStreamConnection")
+            .field("shards", &self.shards.len())
+            .finish()
+    }
+}
+
+impl Drop for ShardedStreamConnection {
+    fn drop(&mut self) {
+        for handle in &self.forward_handles {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeShard {
+        subscribed: Mutex<Vec<StreamTopic>>,
+        updates: Option<broadcast::Sender<StreamUpdate>>,
+    }
+
+    impl crate::sealed::Sealed for FakeShard {}
+
+    #[async_trait]
+    impl StreamRepository for FakeShard {
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn connection_status(&self) -> StreamConnectionStatus {
+            StreamConnectionStatus::Connected
+        }
+
+        async fn hello(&self, _: &str, _: &str) -> Result<HelloResult> {
+            unimplemented!()
+        }
+
+        async fn ping(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn time(&self) -> Result<DateTime<Utc>> {
+            unimplemented!()
+        }
+
+        async fn authenticate(&self, _: &str, _: &str, _: &str) -> Result<AuthenticateResult> {
+            unimplemented!()
+        }
+
+        async fn whoami(&self) -> Result<WhoamiResult> {
+            unimplemented!()
+        }
+
+        async fn subscribe(&self, topics: Vec<StreamTopic>) -> Result<()> {
+            self.subscribed.lock().unwrap().extend(topics);
+            Ok(())
+        }
+
+        async fn unsubscribe(&self, topics: Vec<StreamTopic>) -> Result<()> {
+            self.subscribed
+                .lock()
+                .unwrap()
+                .retain(|topic| !topics.contains(topic));
+            Ok(())
+        }
+
+        async fn unsubscribe_all(&self) -> Result<Vec<StreamTopic>> {
+            Ok(std::mem::take(&mut *self.subscribed.lock().unwrap()))
+        }
+
+        async fn subscriptions(&self) -> HashSet<StreamTopic> {
+            self.subscribed.lock().unwrap().iter().cloned().collect()
+        }
+
+        async fn receiver(&self) -> Result<Receiver<StreamUpdate>> {
+            Ok(self.updates.as_ref().expect("updates sender").subscribe())
+        }
+
+        async fn disconnect(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sharded_connection_routes_topics() {
+        let (public_tx, _) = broadcast::channel(16);
+        let (private_tx, _) = broadcast::channel(16);
+        let public = Arc::new(FakeShard {
+            updates: Some(public_tx.clone()),
+            ..Default::default()
+        });
+        let private = Arc::new(FakeShard {
+            updates: Some(private_tx.clone()),
+            ..Default::default()
+        });
+
+        let router: ShardRouter = Arc::new(|topic: &StreamTopic| usize::from(topic.is_private()));
+        let conn =
+            ShardedStreamConnection::from_shards(vec![public.clone(), private.clone()], router)
+                .await
+                .unwrap();
+
+        conn.subscribe(vec![
+            StreamTopic::FuturesInverseBtcUsdIndex,
+            StreamTopic::FuturesInverseBtcUsdCrossOrders,
+            StreamTopic::WalletDeposit,
+        ])
+        .await
+        .unwrap();
+
+        assert_eq!(
+            *public.subscribed.lock().unwrap(),
+            vec![StreamTopic::FuturesInverseBtcUsdIndex]
+        );
+        assert_eq!(
+            *private.subscribed.lock().unwrap(),
+            vec![
+                StreamTopic::FuturesInverseBtcUsdCrossOrders,
+                StreamTopic::WalletDeposit
+            ]
+        );
+        assert_eq!(conn.subscriptions().await.len(), 3);
+
+        conn.unsubscribe(vec![StreamTopic::WalletDeposit])
+            .await
+            .unwrap();
+        assert_eq!(
+            *private.subscribed.lock().unwrap(),
+            vec![StreamTopic::FuturesInverseBtcUsdCrossOrders]
+        );
+
+        // Updates from every shard are merged
+        let mut merged_rx = conn.receiver().await.unwrap();
+        private_tx
+            .send(StreamUpdate::ConnectionStatus(
+                StreamConnectionStatus::Reconnecting,
+            ))
+            .unwrap();
+        assert!(matches!(
+            merged_rx.recv().await.unwrap(),
+            StreamUpdate::ConnectionStatus(StreamConnectionStatus::Reconnecting)
+        ));
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::{
+    sync::broadcast::{Receiver, error::RecvError},
+    task::JoinHandle,
+};
+
+use crate::{rest::v3::models::Trade, shared::clock::Clock};
+
+use super::{
+    SystemClock,
+    fills::{CROSS_FILL_EVENT, ISOLATED_FILL_EVENT},
+    models::{topic::StreamTopic, update::StreamUpdate},
+};
+
+#[derive(Debug, Default)]
+struct StatsState {
+    messages: u64,
+    messages_by_topic: HashMap<StreamTopic, u64>,
+    fills: u64,
+    volume: u64,
+    fees_paid: u64,
+    wins: u64,
+    losses: u64,
+    realized_pl: i64,
+}
+
+/// Snapshot of the statistics accumulated by [`SessionStats`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatsSnapshot {
+    started_at: DateTime<Utc>,
+    taken_at: DateTime<Utc>,
+    messages: u64,
+    messages_by_topic: HashMap<StreamTopic, u64>,
+    fills: u64,
+    volume: u64,
+    fees_paid: u64,
+    wins: u64,
+    losses: u64,
+    realized_pl: i64,
+}
+
+impl SessionStatsSnapshot {
+    /// Timestamp when the session started.
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// Timestamp when the snapshot was taken.
+    pub fn taken_at(&self) -> DateTime<Utc> {
+        self.taken_at
+    }
+
+    /// Time elapsed since the session started.
+    pub fn uptime(&self) -> chrono::Duration {
+        self.taken_at - self.started_at
+    }
+
+    /// Number of Stream updates received, connection status updates included.
+    pub fn messages(&self) -> u64 {
+        self.messages
+    }
+
+    /// Number of Stream updates received per topic.
+    pub fn messages_by_topic(&self) -> &HashMap<StreamTopic, u64> {
+        &self.messages_by_topic
+    }
+
+    /// Average number of Stream updates received per second since the session started.
+    pub fn message_rate(&self) -> f64 {
+        let secs = self.uptime().num_milliseconds() as f64 / 1000.;
+        if secs <= 0. {
+            return 0.;
+        }
+        self.messages as f64 / secs
+    }
+
+    /// Number of isolated trades and cross orders filled.
+    pub fn fills(&self) -> u64 {
+        self.fills
+    }
+
+    /// Filled quantity, in USD.
+    pub fn volume(&self) -> u64 {
+        self.volume
+    }
+
+    /// Opening, closing and trading fees paid, in sats.
+    pub fn fees_paid(&self) -> u64 {
+        self.fees_paid
+    }
+
+    /// Number of closed isolated trades with a positive PL.
+    pub fn wins(&self) -> u64 {
+        self.wins
+    }
+
+    /// Number of closed isolated trades with a negative PL.
+    pub fn losses(&self) -> u64 {
+        self.losses
+    }
+
+    /// Fraction of closed isolated trades with a positive PL, among those with a non-zero PL.
+    pub fn win_rate(&self) -> Option<f64> {
+        let decided = self.wins + self.losses;
+        (decided > 0).then(|| self.wins as f64 / decided as f64)
+    }
+
+    /// Sum of the PL of closed isolated trades, in sats.
+    pub fn realized_pl(&self) -> i64 {
+        self.realized_pl
+    }
+
+    pub fn as_data_str(&self) -> String {
+        let win_rate = self
+            .win_rate()
+            .map_or("n/a".to_string(), |rate| format!("{:.1}%", rate * 100.));
+
+        format!(
+            "started_at: {}\nuptime_secs: {}\nmessages: {}\nmessage_rate: {:.2}/s\nfills: {}\nvolume: {}\nfees_paid: {}\nwins: {}\nlosses: {}\nwin_rate: {}\nrealized_pl: {}",
+            self.started_at.to_rfc3339(),
+            self.uptime().num_seconds(),
+            self.messages,
+            self.message_rate(),
+            self.fills,
+            self.volume,
+            self.fees_paid,
+            self.wins,
+            self.losses,
+            win_rate,
+            self.realized_pl
+        )
+    }
+}
+
+impl fmt::Display for SessionStatsSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Session Stats:")?;
+        for line in self.as_data_str().lines() {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Accumulator of trading and messaging statistics since startup.
+///
+/// Fed with Stream updates, through [`record_update`](Self::record_update) or
+/// [`spawn_recorder`](Self::spawn_recorder), which count messages and fills. Stream trade payloads
+/// don't include the PL, so wins and losses are fed separately with closed trades fetched through
+/// the REST API, through [`record_closed_trade`](Self::record_closed_trade).
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(
+/// #     conn: lnm_sdk::stream::v1::StreamConnection,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// use std::sync::Arc;
+///
+/// use lnm_sdk::stream::v1::stats::SessionStats;
+///
+/// let stats = Arc::new(SessionStats::new());
+/// let _recorder = stats.spawn_recorder(conn.receiver().await?);
+///
+/// // Later, e.g. from a status endpoint
+/// println!("{}", stats.snapshot());
+/// # Ok(())
+/// # }
+/// ```
+pub struct SessionStats {
+    started_at: DateTime<Utc>,
+    state: Mutex<StatsState>,
+    clock: Arc<dyn Clock>,
+}
+
+impl SessionStats {
+    /// Creates an empty accumulator, starting the session now.
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Creates an empty accumulator reading the time from `clock`, starting the session now.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            started_at: clock.now(),
+            state: Mutex::new(StatsState::default()),
+            clock,
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, StatsState> {
+        self.state
+            .lock()
+            .expect("`SessionStats` mutex can't be poisoned")
+    }
+
+    /// Records a Stream update.
+    ///
+    /// Every update counts as a message. Isolated trade `running` events and cross order
+    /// `filled` events also count as fills, adding their quantity to the volume, and their
+    /// opening or trading fee to the fees paid.
+    pub fn record_update(&self, update: &StreamUpdate) {
+        let mut state = self.lock_state();
+
+        state.messages += 1;
+        if let Some(topic) = update.topic() {
+            *state.messages_by_topic.entry(topic).or_default() += 1;
+        }
+
+        let fill = match update {
+            StreamUpdate::FuturesInverseBtcUsdIsolatedTrades(event)
+                if event.event() == ISOLATED_FILL_EVENT =>
+            {
+                Some((event.trade().quantity(), event.trade().opening_fee()))
+            }
+            StreamUpdate::FuturesInverseBtcUsdCrossOrders(event)
+                if event.event() == CROSS_FILL_EVENT =>
+            {
+                Some((event.order().quantity(), event.order().trading_fee()))
+            }
+            _ => None,
+        };
+
+        if let Some((quantity, fee)) = fill {
+            state.fills += 1;
+            state.volume += quantity.map_or(0, |quantity| quantity.as_u64());
+            state.fees_paid += fee.map_or(0, |fee| fee.as_u64());
+        }
+    }
+
+    /// Records a closed isolated trade, counting it as a win or loss and adding its closing fee to
+    /// the fees paid. Trades that aren't closed are ignored.
+    pub fn record_closed_trade(&self, trade: &Trade) {
+        if !trade.closed() {
+            return;
+        }
+
+        let mut state = self.lock_state();
+
+        match trade.pl() {
+            pl if pl.is_positive() => state.wins += 1,
+            pl if pl.is_negative() => state.losses += 1,
+            _ => {}
+        }
+        state.realized_pl += trade.pl().as_i64();
+        state.fees_paid += trade.closing_fee().as_u64();
+    }
+
+    /// Spawns a task recording every update received on `receiver`.
+    ///
+    /// Updates missed because the receiver lagged are skipped. The task stops when the stream is
+    /// closed, or when the returned handle is aborted.
+    pub fn spawn_recorder(
+        self: &Arc<Self>,
+        mut receiver: Receiver<StreamUpdate>,
+    ) -> JoinHandle<()> {
+        let stats = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(update) => stats.record_update(&update),
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!("Session stats recorder lagged, {skipped} updates skipped");
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        })
+    }
+
+    /// Returns the statistics accumulated so far.
+    pub fn snapshot(&self) -> SessionStatsSnapshot {
+        let state = self.lock_state();
+
+        SessionStatsSnapshot {
+            started_at: self.started_at,
+            taken_at: self.clock.now(),
+            messages: state.messages,
+            messages_by_topic: state.messages_by_topic.clone(),
+            fills: state.fills,
+            volume: state.volume,
+            fees_paid: state.fees_paid,
+            wins: state.wins,
+            losses: state.losses,
+            realized_pl: state.realized_pl,
+        }
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for SessionStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionStats")
+            .field("started_at", &self.started_at)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+    use crate::{
+        stream::v1::models::{StreamCrossOrderEvent, StreamIsolatedTradeEvent},
+        testing::{clock::MockClock, fixtures},
+    };
+
+    fn isolated_event(event: &str) -> StreamUpdate {
+        let json = format!(
+            r#"{{ "pair": "btc_usd", "event": "{event}", "trade": {{ "id": "00000000-0000-0000-0000-000000000001", "quantity": 1000, "openingFee": 10 }} }}"#
+        );
+        let event: StreamIsolatedTradeEvent = serde_json::from_str(&json).unwrap();
+        StreamUpdate::FuturesInverseBtcUsdIsolatedTrades(event)
+    }
+
+    fn cross_event(event: &str) -> StreamUpdate {
+        let json = format!(
+            r#"{{ "pair": "btc_usd", "event": "{event}", "order": {{ "id": "00000000-0000-0000-0000-000000000002", "quantity": 500, "tradingFee": 5 }} }}"#
+        );
+        let event: StreamCrossOrderEvent = serde_json::from_str(&json).unwrap();
+        StreamUpdate::FuturesInverseBtcUsdCrossOrders(event)
+    }
+
+    #[test]
+    fn test_session_stats() {
+        let clock = MockClock::default();
+        let stats = SessionStats::with_clock(Arc::new(clock.clone()));
+
+        stats.record_update(&isolated_event("open"));
+        stats.record_update(&isolated_event(ISOLATED_FILL_EVENT));
+        stats.record_update(&cross_event("new"));
+        stats.record_update(&cross_event(CROSS_FILL_EVENT));
+
+        stats.record_closed_trade(&fixtures::closed_trade());
+        stats.record_closed_trade(&fixtures::running_trade());
+
+        clock.advance(TimeDelta::seconds(2));
+        let snapshot = stats.snapshot();
+
+        assert_eq!(snapshot.messages(), 4);
+        assert_eq!(
+            snapshot.messages_by_topic()[&StreamTopic::FuturesInverseBtcUsdCrossOrders],
+            2
+        );
+        assert_eq!(snapshot.message_rate(), 2.);
+        assert_eq!(snapshot.fills(), 2);
+        assert_eq!(snapshot.volume(), 1_500);
+        assert_eq!(snapshot.fees_paid(), 10 + 5 + 91);
+        assert_eq!(snapshot.wins(), 1);
+        assert_eq!(snapshot.losses(), 0);
+        assert_eq!(snapshot.win_rate(), Some(1.));
+        assert_eq!(snapshot.realized_pl(), 90_909);
+    }
+}
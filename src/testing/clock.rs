@@ -0,0 +1,64 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::shared::clock::Clock;
+
+/// [`Clock`] whose time only changes when set or advanced explicitly.
+///
+/// Clones share the same time, so a clone can be handed to a client configuration while the test
+/// keeps control of it.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use chrono::{TimeDelta, TimeZone, Utc};
+/// use lnm_sdk::{
+///     rest::v3::{Clock, RestClientConfig},
+///     testing::clock::MockClock,
+/// };
+///
+/// let clock = MockClock::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+/// let config = RestClientConfig::default().with_clock(Arc::new(clock.clone()));
+///
+/// clock.advance(TimeDelta::minutes(5));
+/// assert_eq!(clock.now(), Utc.with_ymd_and_hms(2025, 1, 1, 0, 5, 0).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockClock(Arc<Mutex<DateTime<Utc>>>);
+
+impl MockClock {
+    /// Creates a clock stopped at `now`.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self(Arc::new(Mutex::new(now)))
+    }
+
+    fn time(&self) -> std::sync::MutexGuard<'_, DateTime<Utc>> {
+        self.0.lock().expect("`MockClock` mutex can't be poisoned")
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.time() = now;
+    }
+
+    /// Moves the clock by `delta`, which may be negative.
+    pub fn advance(&self, delta: TimeDelta) {
+        let mut time = self.time();
+        *time += delta;
+    }
+}
+
+impl Default for MockClock {
+    /// Creates a clock stopped at the current system time.
+    fn default() -> Self {
+        Self::new(Utc::now())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.time()
+    }
+}
@@ -0,0 +1,455 @@
+use std::{collections::HashMap, num::NonZeroU64, sync::Mutex, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use http::{Method, StatusCode};
+use rand::{RngExt, SeedableRng, rngs::StdRng};
+use uuid::Uuid;
+
+use crate::{
+    rest::v3::{
+        LnmFuturesApi,
+        models::{
+            Account, ClientId, CrossOrder, CrossPosition, Leverage, OrderQuantity, Page, Price,
+            Ticker, Trade, TradeExecution, TradeSide, TradeSize,
+        },
+    },
+    shared::rest::error::{ErrorResponseContext, RestApiError, Result},
+};
+
+/// Calls of [`LnmFuturesApi`], used to configure faults per endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiCall {
+    GetTicker,
+    GetAccount,
+    GetOpenTrades,
+    GetRunningTrades,
+    GetClosedTrades,
+    GetCanceledTrades,
+    NewTrade,
+    CloseTrade,
+    CancelTrade,
+    AddMarginToTrade,
+    CashInTrade,
+    UpdateStoploss,
+    UpdateTakeprofit,
+    GetCrossPosition,
+    GetOpenCrossOrders,
+    GetFilledCrossOrders,
+    PlaceCrossOrder,
+    CancelCrossOrder,
+    CloseCrossPosition,
+    DepositCrossMargin,
+}
+
+impl ApiCall {
+    /// Returns the method and path of the REST endpoint backing this call.
+    ///
+    /// Used as context of injected [`RestApiError::ErrorResponse`] errors.
+    pub fn endpoint(self) -> (Method, &'static str) {
+        match self {
+            Self::GetTicker => (Method::GET, "/v3/futures/ticker"),
+            Self::GetAccount => (Method::GET, "/v3/account"),
+            Self::GetOpenTrades => (Method::GET, "/v3/futures/isolated/trades/open"),
+            Self::GetRunningTrades => (Method::GET, "/v3/futures/isolated/trades/running"),
+            Self::GetClosedTrades => (Method::GET, "/v3/futures/isolated/trades/closed"),
+            Self::GetCanceledTrades => (Method::GET, "/v3/futures/isolated/trades/canceled"),
+            Self::NewTrade => (Method::POST, "/v3/futures/isolated/trade"),
+            Self::CloseTrade => (Method::POST, "/v3/futures/isolated/trade/close"),
+            Self::CancelTrade => (Method::POST, "/v3/futures/isolated/trade/cancel"),
+            Self::AddMarginToTrade => (Method::POST, "/v3/futures/isolated/trade/add-margin"),
+            Self::CashInTrade => (Method::POST, "/v3/futures/isolated/trade/cash-in"),
+            Self::UpdateStoploss => (Method::PUT, "/v3/futures/isolated/trade/stoploss"),
+            Self::UpdateTakeprofit => (Method::PUT, "/v3/futures/isolated/trade/takeprofit"),
+            Self::GetCrossPosition => (Method::GET, "/v3/futures/cross/position"),
+            Self::GetOpenCrossOrders => (Method::GET, "/v3/futures/cross/orders/open"),
+            Self::GetFilledCrossOrders => (Method::GET, "/v3/futures/cross/orders/filled"),
+            Self::PlaceCrossOrder => (Method::POST, "/v3/futures/cross/order"),
+            Self::CancelCrossOrder => (Method::POST, "/v3/futures/cross/order/cancel"),
+            Self::CloseCrossPosition => (Method::POST, "/v3/futures/cross/position/close"),
+            Self::DepositCrossMargin => (Method::POST, "/v3/futures/cross/deposit"),
+        }
+    }
+}
+
+/// Configuration of the faults injected by [`FaultInjectingApi`].
+///
+/// Faults are drawn from a random number generator seeded with the configured seed, so a given
+/// configuration and sequence of calls always produces the same faults.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use lnm_sdk::testing::faults::{ApiCall, FaultConfig};
+///
+/// let config = FaultConfig::new(42)
+///     .with_latency(Duration::from_millis(50))
+///     .with_error_rate(0.1)
+///     .with_call_error_rate(ApiCall::NewTrade, 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FaultConfig {
+    seed: u64,
+    latency: Duration,
+    call_latency: HashMap<ApiCall, Duration>,
+    error_rate: f64,
+    call_error_rate: HashMap<ApiCall, f64>,
+    error_status: StatusCode,
+}
+
+impl FaultConfig {
+    /// Creates a configuration without faults, using the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            latency: Duration::ZERO,
+            call_latency: HashMap::new(),
+            error_rate: 0.0,
+            call_error_rate: HashMap::new(),
+            error_status: StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    /// Sets the latency added to every call without a specific latency.
+    ///
+    /// Default: `0`
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Sets the latency added to the given call, overriding [`FaultConfig::with_latency`].
+    pub fn with_call_latency(mut self, call: ApiCall, latency: Duration) -> Self {
+        self.call_latency.insert(call, latency);
+        self
+    }
+
+    /// Sets the probability of every call without a specific error rate failing.
+    ///
+    /// Default: `0.0`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not in the `[0, 1]` range.
+    pub fn with_error_rate(mut self, rate: f64) -> Self {
+        assert!((0.0..=1.0).contains(&rate), "error rate must be in [0, 1]");
+        self.error_rate = rate;
+        self
+    }
+
+    /// Sets the probability of the given call failing, overriding [`FaultConfig::with_error_rate`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not in the `[0, 1]` range.
+    pub fn with_call_error_rate(mut self, call: ApiCall, rate: f64) -> Self {
+        assert!((0.0..=1.0).contains(&rate), "error rate must be in [0, 1]");
+        self.call_error_rate.insert(call, rate);
+        self
+    }
+
+    /// Sets the status of injected error responses.
+    ///
+    /// Default: `503 Service Unavailable`
+    pub fn with_error_status(mut self, status: StatusCode) -> Self {
+        self.error_status = status;
+        self
+    }
+
+    fn latency(&self, call: ApiCall) -> Duration {
+        self.call_latency
+            .get(&call)
+            .copied()
+            .unwrap_or(self.latency)
+    }
+
+    fn error_rate(&self, call: ApiCall) -> f64 {
+        self.call_error_rate
+            .get(&call)
+            .copied()
+            .unwrap_or(self.error_rate)
+    }
+}
+
+/// [`LnmFuturesApi`] wrapper injecting latency and error responses into the calls of an inner
+/// implementation, so error handling and timeout paths can be exercised deterministically.
+///
+/// Latency is added before each call. Failed calls don't reach the inner implementation, and
+/// return a [`RestApiError::ErrorResponse`] with the configured status and the method and path of
+/// the corresponding endpoint.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(rest: lnm_sdk::rest::v3::RestClient) {
+/// use lnm_sdk::{
+///     rest::v3::LnmFuturesApi,
+///     testing::faults::{ApiCall, FaultConfig, FaultInjectingApi},
+/// };
+///
+/// let config = FaultConfig::new(42).with_call_error_rate(ApiCall::GetTicker, 1.0);
+/// let api = FaultInjectingApi::new(rest, config);
+///
+/// let error = api.get_ticker().await.unwrap_err();
+/// assert!(error.is_retryable());
+/// # }
+/// ```
+pub struct FaultInjectingApi<A> {
+    inner: A,
+    config: FaultConfig,
+    rng: Mutex<StdRng>,
+}
+
+impl<A: LnmFuturesApi> FaultInjectingApi<A> {
+    /// Wraps `inner`, injecting faults according to `config`.
+    pub fn new(inner: A, config: FaultConfig) -> Self {
+        let rng = Mutex::new(StdRng::seed_from_u64(config.seed));
+
+        Self { inner, config, rng }
+    }
+
+    /// Returns a reference to the wrapped implementation.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns the fault configuration.
+    pub fn config(&self) -> &FaultConfig {
+        &self.config
+    }
+
+    async fn inject(&self, call: ApiCall) -> Result<()> {
+        let latency = self.config.latency(call);
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+
+        let fail = self
+            .rng
+            .lock()
+            .expect("`rng` mutex can't be poisoned")
+            .random_bool(self.config.error_rate(call));
+
+        if fail {
+            let (method, path) = call.endpoint();
+
+            return Err(RestApiError::ErrorResponse(Box::new(
+                ErrorResponseContext {
+                    method,
+                    path: path.to_string(),
+                    status: self.config.error_status,
+                    request_id: None,
+                    correlation_id: None,
+                    retry_after: None,
+                    text: "injected fault".to_string(),
+                },
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<A: LnmFuturesApi> LnmFuturesApi for FaultInjectingApi<A> {
+    async fn get_ticker(&self) -> Result<Ticker> {
+        self.inject(ApiCall::GetTicker).await?;
+        self.inner.get_ticker().await
+    }
+
+    async fn get_account(&self) -> Result<Account> {
+        self.inject(ApiCall::GetAccount).await?;
+        self.inner.get_account().await
+    }
+
+    async fn get_open_trades(&self) -> Result<Vec<Trade>> {
+        self.inject(ApiCall::GetOpenTrades).await?;
+        self.inner.get_open_trades().await
+    }
+
+    async fn get_running_trades(&self) -> Result<Vec<Trade>> {
+        self.inject(ApiCall::GetRunningTrades).await?;
+        self.inner.get_running_trades().await
+    }
+
+    async fn get_closed_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        self.inject(ApiCall::GetClosedTrades).await?;
+        self.inner.get_closed_trades(from, to, limit, cursor).await
+    }
+
+    async fn get_canceled_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        self.inject(ApiCall::GetCanceledTrades).await?;
+        self.inner
+            .get_canceled_trades(from, to, limit, cursor)
+            .await
+    }
+
+    async fn new_trade(
+        &self,
+        side: TradeSide,
+        size: TradeSize,
+        leverage: Leverage,
+        execution: TradeExecution,
+        stoploss: Option<Price>,
+        takeprofit: Option<Price>,
+        client_id: Option<ClientId>,
+    ) -> Result<Trade> {
+        self.inject(ApiCall::NewTrade).await?;
+        self.inner
+            .new_trade(
+                side, size, leverage, execution, stoploss, takeprofit, client_id,
+            )
+            .await
+    }
+
+    async fn close_trade(&self, id: Uuid) -> Result<Trade> {
+        self.inject(ApiCall::CloseTrade).await?;
+        self.inner.close_trade(id).await
+    }
+
+    async fn cancel_trade(&self, id: Uuid) -> Result<Trade> {
+        self.inject(ApiCall::CancelTrade).await?;
+        self.inner.cancel_trade(id).await
+    }
+
+    async fn add_margin_to_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade> {
+        self.inject(ApiCall::AddMarginToTrade).await?;
+        self.inner.add_margin_to_trade(id, amount).await
+    }
+
+    async fn cash_in_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade> {
+        self.inject(ApiCall::CashInTrade).await?;
+        self.inner.cash_in_trade(id, amount).await
+    }
+
+    async fn update_stoploss(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        self.inject(ApiCall::UpdateStoploss).await?;
+        self.inner.update_stoploss(id, value).await
+    }
+
+    async fn update_takeprofit(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        self.inject(ApiCall::UpdateTakeprofit).await?;
+        self.inner.update_takeprofit(id, value).await
+    }
+
+    async fn get_cross_position(&self) -> Result<CrossPosition> {
+        self.inject(ApiCall::GetCrossPosition).await?;
+        self.inner.get_cross_position().await
+    }
+
+    async fn get_open_cross_orders(&self) -> Result<Vec<CrossOrder>> {
+        self.inject(ApiCall::GetOpenCrossOrders).await?;
+        self.inner.get_open_cross_orders().await
+    }
+
+    async fn get_filled_cross_orders(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<CrossOrder>> {
+        self.inject(ApiCall::GetFilledCrossOrders).await?;
+        self.inner
+            .get_filled_cross_orders(from, to, limit, cursor)
+            .await
+    }
+
+    async fn place_cross_order(
+        &self,
+        side: TradeSide,
+        quantity: OrderQuantity,
+        execution: TradeExecution,
+        client_id: Option<ClientId>,
+    ) -> Result<CrossOrder> {
+        self.inject(ApiCall::PlaceCrossOrder).await?;
+        self.inner
+            .place_cross_order(side, quantity, execution, client_id)
+            .await
+    }
+
+    async fn cancel_cross_order(&self, id: Uuid) -> Result<CrossOrder> {
+        self.inject(ApiCall::CancelCrossOrder).await?;
+        self.inner.cancel_cross_order(id).await
+    }
+
+    async fn close_cross_position(&self) -> Result<CrossOrder> {
+        self.inject(ApiCall::CloseCrossPosition).await?;
+        self.inner.close_cross_position().await
+    }
+
+    async fn deposit_cross_margin(&self, amount: NonZeroU64) -> Result<CrossPosition> {
+        self.inject(ApiCall::DepositCrossMargin).await?;
+        self.inner.deposit_cross_margin(amount).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crate::testing::stub::StubApi;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_call_error_rate_only_affects_call() {
+        let config = FaultConfig::new(0)
+            .with_call_error_rate(ApiCall::GetTicker, 1.0)
+            .with_error_status(StatusCode::TOO_MANY_REQUESTS);
+        let api = FaultInjectingApi::new(StubApi::new(), config);
+
+        let error = api.get_ticker().await.unwrap_err();
+        assert!(error.is_rate_limited());
+        assert_eq!(error.endpoint(), Some((&Method::GET, "/v3/futures/ticker")));
+
+        assert!(api.get_account().await.is_ok());
+        // Failed calls don't reach the inner implementation
+        assert_eq!(api.inner().calls(), [ApiCall::GetAccount]);
+    }
+
+    #[tokio::test]
+    async fn test_faults_are_deterministic_for_seed() {
+        async fn outcomes(seed: u64) -> Vec<bool> {
+            let api =
+                FaultInjectingApi::new(StubApi::new(), FaultConfig::new(seed).with_error_rate(0.5));
+
+            let mut outcomes = Vec::new();
+            for _ in 0..32 {
+                outcomes.push(api.get_cross_position().await.is_ok());
+            }
+            outcomes
+        }
+
+        let first = outcomes(7).await;
+
+        assert_eq!(first, outcomes(7).await);
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
+    #[tokio::test]
+    async fn test_call_latency_is_added() {
+        let config =
+            FaultConfig::new(0).with_call_latency(ApiCall::GetAccount, Duration::from_millis(20));
+        let api = FaultInjectingApi::new(StubApi::new(), config);
+
+        let start = Instant::now();
+        api.get_account().await.unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
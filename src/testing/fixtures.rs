@@ -0,0 +1,286 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::rest::v3::models::{Account, CrossOrder, CrossPosition, Ticker, Trade};
+
+/// Timestamp used for all fixture `createdAt` values.
+const CREATED_AT: &str = "2025-05-12T07:30:05.657Z";
+
+/// Timestamp used for all fixture `filledAt`, `closedAt` and `canceledAt` values.
+const UPDATED_AT: &str = "2025-05-12T08:30:05.657Z";
+
+fn from_json<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).expect("fixture must deserialize")
+}
+
+pub(crate) fn trade_json() -> Value {
+    json!({
+        "id": "00000000-0000-0000-0000-000000000001",
+        "type": "market",
+        "side": "buy",
+        "openingFee": 100,
+        "closingFee": 0,
+        "maintenanceMargin": 1_100,
+        "quantity": 1_000,
+        "margin": 100_000,
+        "leverage": 10,
+        "price": 100_000,
+        "liquidation": 90_909.5,
+        "stoploss": 0,
+        "takeprofit": 0,
+        "exitPrice": null,
+        "pl": 0,
+        "createdAt": CREATED_AT,
+        "filledAt": CREATED_AT,
+        "closedAt": null,
+        "entryPrice": 100_000,
+        "entryMargin": 100_000,
+        "open": false,
+        "running": true,
+        "canceled": false,
+        "closed": false,
+        "sumFundingFees": 0,
+        "clientId": null,
+    })
+}
+
+/// Returns an [`Account`] with a balance of 1,000,000 sats.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let account = fixtures::account();
+/// assert_eq!(account.balance().as_i64(), 1_000_000);
+/// ```
+pub fn account() -> Account {
+    from_json(json!({
+        "id": "00000000-0000-0000-0000-00000000000a",
+        "username": "satoshi",
+        "email": "satoshi@example.com",
+        "syntheticUsdBalance": 0,
+        "balance": 1_000_000,
+        "feeTier": 0,
+        "linkingPublicKey": null,
+    }))
+}
+
+/// Returns a [`Ticker`] with index and last prices of 100,000 USD.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let ticker = fixtures::ticker();
+/// assert_eq!(ticker.last_price().as_f64(), 100_000.0);
+/// ```
+pub fn ticker() -> Ticker {
+    from_json(json!({
+        "index": 100_000,
+        "lastPrice": 100_000,
+        "prices": [{ "askPrice": 100_000.5, "bidPrice": 99_999.5, "minSize": 1, "maxSize": 100_000 }],
+        "fundingRate": 0.0001,
+        "fundingTime": UPDATED_AT,
+    }))
+}
+
+/// Returns an open (not yet filled) limit buy [`Trade`] of 1,000 USD at 10x, with a limit price of
+/// 95,000 USD.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let trade = fixtures::open_trade();
+/// assert!(trade.open());
+/// assert!(trade.filled_at().is_none());
+/// ```
+pub fn open_trade() -> Trade {
+    let mut trade = trade_json();
+    trade["type"] = json!("limit");
+    trade["price"] = json!(95_000);
+    trade["liquidation"] = json!(86_364);
+    trade["margin"] = json!(105_264);
+    trade["filledAt"] = Value::Null;
+    trade["entryPrice"] = Value::Null;
+    trade["entryMargin"] = Value::Null;
+    trade["open"] = json!(true);
+    trade["running"] = json!(false);
+    from_json(trade)
+}
+
+/// Returns a running market buy [`Trade`] of 1,000 USD at 10x, entered at 100,000 USD.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let trade = fixtures::running_trade();
+/// assert!(trade.running());
+/// assert_eq!(trade.margin().as_u64(), 100_000);
+/// ```
+pub fn running_trade() -> Trade {
+    from_json(trade_json())
+}
+
+/// Returns a closed market buy [`Trade`] of 1,000 USD at 10x, entered at 100,000 USD and exited at
+/// 110,000 USD, with the corresponding realized PL.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let trade = fixtures::closed_trade();
+/// assert!(trade.closed());
+/// assert!(trade.pl().is_positive());
+/// ```
+pub fn closed_trade() -> Trade {
+    let mut trade = trade_json();
+    trade["closingFee"] = json!(91);
+    trade["exitPrice"] = json!(110_000);
+    trade["pl"] = json!(90_909);
+    trade["closedAt"] = json!(UPDATED_AT);
+    trade["running"] = json!(false);
+    trade["closed"] = json!(true);
+    from_json(trade)
+}
+
+/// Returns a canceled limit buy [`Trade`], as [`open_trade`] after being canceled.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let trade = fixtures::canceled_trade();
+/// assert!(trade.canceled());
+/// ```
+pub fn canceled_trade() -> Trade {
+    let mut trade = trade_json();
+    trade["type"] = json!("limit");
+    trade["price"] = json!(95_000);
+    trade["liquidation"] = json!(86_364);
+    trade["margin"] = json!(105_264);
+    trade["filledAt"] = Value::Null;
+    trade["closedAt"] = json!(UPDATED_AT);
+    trade["entryPrice"] = Value::Null;
+    trade["entryMargin"] = Value::Null;
+    trade["running"] = json!(false);
+    trade["canceled"] = json!(true);
+    from_json(trade)
+}
+
+pub(crate) fn cross_order_json() -> Value {
+    json!({
+        "id": "00000000-0000-0000-0000-000000000002",
+        "type": "limit",
+        "side": "buy",
+        "quantity": 1_000,
+        "price": 95_000,
+        "tradingFee": 0,
+        "createdAt": CREATED_AT,
+        "filledAt": null,
+        "canceledAt": null,
+        "open": true,
+        "filled": false,
+        "canceled": false,
+        "clientId": null,
+    })
+}
+
+/// Returns an open limit buy [`CrossOrder`] of 1,000 USD at 95,000 USD.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let order = fixtures::open_cross_order();
+/// assert!(order.open());
+/// ```
+pub fn open_cross_order() -> CrossOrder {
+    from_json(cross_order_json())
+}
+
+/// Returns a filled market buy [`CrossOrder`] of 1,000 USD at 100,000 USD.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let order = fixtures::filled_cross_order();
+/// assert!(order.filled());
+/// ```
+pub fn filled_cross_order() -> CrossOrder {
+    let mut order = cross_order_json();
+    order["type"] = json!("market");
+    order["price"] = json!(100_000);
+    order["tradingFee"] = json!(100);
+    order["filledAt"] = json!(UPDATED_AT);
+    order["open"] = json!(false);
+    order["filled"] = json!(true);
+    from_json(order)
+}
+
+/// Returns a running long [`CrossPosition`] of 1,000 USD at 10x, entered at 100,000 USD, as
+/// resulting from [`filled_cross_order`].
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let position = fixtures::running_position();
+/// assert_eq!(position.quantity(), 1_000);
+/// ```
+pub fn running_position() -> CrossPosition {
+    from_json(json!({
+        "id": "00000000-0000-0000-0000-000000000003",
+        "margin": 500_000,
+        "quantity": 1_000,
+        "leverage": 10,
+        "entryPrice": 100_000,
+        "runningMargin": 100_000,
+        "initialMargin": 100_000,
+        "maintenanceMargin": 1_500,
+        "liquidation": 66_733.5,
+        "tradingFees": 100,
+        "fundingFees": 0,
+        "totalPl": 0,
+        "deltaPl": 0,
+    }))
+}
+
+/// Returns an empty [`CrossPosition`], with 500,000 sats of margin and no exposure.
+///
+/// # Examples
+///
+/// ```
+/// use lnm_sdk::testing::fixtures;
+///
+/// let position = fixtures::empty_position();
+/// assert_eq!(position.quantity(), 0);
+/// ```
+pub fn empty_position() -> CrossPosition {
+    from_json(json!({
+        "id": "00000000-0000-0000-0000-000000000003",
+        "margin": 500_000,
+        "quantity": 0,
+        "leverage": 10,
+        "entryPrice": null,
+        "runningMargin": 0,
+        "initialMargin": 0,
+        "maintenanceMargin": 0,
+        "liquidation": null,
+        "tradingFees": 0,
+        "fundingFees": 0,
+        "totalPl": 0,
+        "deltaPl": 0,
+    }))
+}
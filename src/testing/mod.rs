@@ -0,0 +1,23 @@
+/// Controllable [`Clock`](crate::rest::v3::Clock) implementation.
+///
+/// Lets time-dependent behavior, such as request signing, `Retry-After` dates and candle
+/// completeness, be tested deterministically.
+pub mod clock;
+
+/// Fault injection for [`LnmFuturesApi`](crate::rest::v3::LnmFuturesApi) implementations.
+///
+/// Wraps any implementation, adding latency and error responses per call, so error handling and
+/// timeout paths can be exercised deterministically.
+pub mod faults;
+
+/// Constructors for valid, fully populated models.
+///
+/// Each fixture returns a model as the API would, with fixed and internally consistent values, so
+/// application logic consuming SDK models can be unit-tested without crafting JSON by hand.
+pub mod fixtures;
+
+/// Configurable [`LnmFuturesApi`](crate::rest::v3::LnmFuturesApi) implementation.
+///
+/// Answers calls with fixtures unless overridden per call, and records the calls it receives, so
+/// components built on the trait can be unit-tested without hand-written fakes.
+pub mod stub;
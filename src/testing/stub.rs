@@ -0,0 +1,617 @@
+use std::{num::NonZeroU64, sync::Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    rest::v3::{
+        LnmFuturesApi,
+        models::{
+            Account, ClientId, CrossOrder, CrossPosition, Leverage, OrderQuantity, Page, Price,
+            Ticker, Trade, TradeExecution, TradeSide, TradeSize,
+        },
+    },
+    shared::rest::error::{RestApiError, Result},
+};
+
+use super::{faults::ApiCall, fixtures};
+
+type Handler<T> = Box<dyn Fn() -> Result<T> + Send + Sync>;
+
+type IdHandler<T> = Box<dyn Fn(Uuid) -> Result<T> + Send + Sync>;
+
+type AmountHandler = Box<dyn Fn(Uuid, NonZeroU64) -> Result<Trade> + Send + Sync>;
+
+type PriceHandler = Box<dyn Fn(Uuid, Option<Price>) -> Result<Trade> + Send + Sync>;
+
+type HistoryHandler<T> = Box<
+    dyn Fn(
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+            Option<NonZeroU64>,
+            Option<DateTime<Utc>>,
+        ) -> Result<Page<T>>
+        + Send
+        + Sync,
+>;
+
+type NewTradeHandler = Box<
+    dyn Fn(
+            TradeSide,
+            TradeSize,
+            Leverage,
+            TradeExecution,
+            Option<Price>,
+            Option<Price>,
+            Option<ClientId>,
+        ) -> Result<Trade>
+        + Send
+        + Sync,
+>;
+
+type PlaceCrossOrderHandler = Box<
+    dyn Fn(TradeSide, OrderQuantity, TradeExecution, Option<ClientId>) -> Result<CrossOrder>
+        + Send
+        + Sync,
+>;
+
+type DepositHandler = Box<dyn Fn(NonZeroU64) -> Result<CrossPosition> + Send + Sync>;
+
+/// Configurable [`LnmFuturesApi`] implementation, recording the calls it receives.
+///
+/// By default, every call succeeds with the corresponding [fixture](super::fixtures), while
+/// history calls fail with [`RestApiError::UnsupportedOperation`]. Each call can be overridden
+/// with a closure receiving its arguments, so tests only need to configure the calls they
+/// exercise.
+///
+/// # Examples
+///
+/// ```
+/// # async fn example() {
+/// use lnm_sdk::{
+///     rest::v3::LnmFuturesApi,
+///     testing::{faults::ApiCall, fixtures, stub::StubApi},
+/// };
+///
+/// let api = StubApi::new().with_get_open_trades(|| Ok(Vec::new()));
+///
+/// assert!(api.get_open_trades().await.unwrap().is_empty());
+/// assert_eq!(api.get_account().await.unwrap().id(), fixtures::account().id());
+/// assert_eq!(api.calls(), [ApiCall::GetOpenTrades, ApiCall::GetAccount]);
+/// # }
+/// ```
+pub struct StubApi {
+    calls: Mutex<Vec<ApiCall>>,
+    get_ticker: Handler<Ticker>,
+    get_account: Handler<Account>,
+    get_open_trades: Handler<Vec<Trade>>,
+    get_running_trades: Handler<Vec<Trade>>,
+    get_closed_trades: HistoryHandler<Trade>,
+    get_canceled_trades: HistoryHandler<Trade>,
+    new_trade: NewTradeHandler,
+    close_trade: IdHandler<Trade>,
+    cancel_trade: IdHandler<Trade>,
+    add_margin_to_trade: AmountHandler,
+    cash_in_trade: AmountHandler,
+    update_stoploss: PriceHandler,
+    update_takeprofit: PriceHandler,
+    get_cross_position: Handler<CrossPosition>,
+    get_open_cross_orders: Handler<Vec<CrossOrder>>,
+    get_filled_cross_orders: HistoryHandler<CrossOrder>,
+    place_cross_order: PlaceCrossOrderHandler,
+    cancel_cross_order: IdHandler<CrossOrder>,
+    close_cross_position: Handler<CrossOrder>,
+    deposit_cross_margin: DepositHandler,
+}
+
+impl StubApi {
+    /// Creates a stub answering every call with the corresponding fixture.
+    pub fn new() -> Self {
+        Self {
+            calls: Mutex::new(Vec::new()),
+            get_ticker: Box::new(|| Ok(fixtures::ticker())),
+            get_account: Box::new(|| Ok(fixtures::account())),
+            get_open_trades: Box::new(|| Ok(vec![fixtures::open_trade()])),
+            get_running_trades: Box::new(|| Ok(vec![fixtures::running_trade()])),
+            get_closed_trades: Box::new(|_, _, _, _| {
+                Err(RestApiError::UnsupportedOperation("get_closed_trades"))
+            }),
+            get_canceled_trades: Box::new(|_, _, _, _| {
+                Err(RestApiError::UnsupportedOperation("get_canceled_trades"))
+            }),
+            new_trade: Box::new(|_, _, _, _, _, _, _| Ok(fixtures::running_trade())),
+            close_trade: Box::new(|_| Ok(fixtures::closed_trade())),
+            cancel_trade: Box::new(|_| Ok(fixtures::canceled_trade())),
+            add_margin_to_trade: Box::new(|_, _| Ok(fixtures::running_trade())),
+            cash_in_trade: Box::new(|_, _| Ok(fixtures::running_trade())),
+            update_stoploss: Box::new(|_, _| Ok(fixtures::running_trade())),
+            update_takeprofit: Box::new(|_, _| Ok(fixtures::running_trade())),
+            get_cross_position: Box::new(|| Ok(fixtures::running_position())),
+            get_open_cross_orders: Box::new(|| Ok(vec![fixtures::open_cross_order()])),
+            get_filled_cross_orders: Box::new(|_, _, _, _| {
+                Err(RestApiError::UnsupportedOperation(
+                    "get_filled_cross_orders",
+                ))
+            }),
+            place_cross_order: Box::new(|_, _, _, _| Ok(fixtures::filled_cross_order())),
+            cancel_cross_order: Box::new(|_| Ok(fixtures::open_cross_order())),
+            close_cross_position: Box::new(|| Ok(fixtures::filled_cross_order())),
+            deposit_cross_margin: Box::new(|_| Ok(fixtures::running_position())),
+        }
+    }
+
+    /// Overrides [`LnmFuturesApi::get_ticker`].
+    ///
+    /// Default: [`fixtures::ticker`]
+    pub fn with_get_ticker(
+        mut self,
+        handler: impl Fn() -> Result<Ticker> + Send + Sync + 'static,
+    ) -> Self {
+        self.get_ticker = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::get_account`].
+    ///
+    /// Default: [`fixtures::account`]
+    pub fn with_get_account(
+        mut self,
+        handler: impl Fn() -> Result<Account> + Send + Sync + 'static,
+    ) -> Self {
+        self.get_account = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::get_open_trades`].
+    ///
+    /// Default: [`fixtures::open_trade`]
+    pub fn with_get_open_trades(
+        mut self,
+        handler: impl Fn() -> Result<Vec<Trade>> + Send + Sync + 'static,
+    ) -> Self {
+        self.get_open_trades = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::get_running_trades`].
+    ///
+    /// Default: [`fixtures::running_trade`]
+    pub fn with_get_running_trades(
+        mut self,
+        handler: impl Fn() -> Result<Vec<Trade>> + Send + Sync + 'static,
+    ) -> Self {
+        self.get_running_trades = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::get_closed_trades`].
+    ///
+    /// Default: fails with [`RestApiError::UnsupportedOperation`]
+    pub fn with_get_closed_trades(
+        mut self,
+        handler: impl Fn(
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+            Option<NonZeroU64>,
+            Option<DateTime<Utc>>,
+        ) -> Result<Page<Trade>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.get_closed_trades = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::get_canceled_trades`].
+    ///
+    /// Default: fails with [`RestApiError::UnsupportedOperation`]
+    pub fn with_get_canceled_trades(
+        mut self,
+        handler: impl Fn(
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+            Option<NonZeroU64>,
+            Option<DateTime<Utc>>,
+        ) -> Result<Page<Trade>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.get_canceled_trades = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::new_trade`].
+    ///
+    /// Default: [`fixtures::running_trade`]
+    pub fn with_new_trade(
+        mut self,
+        handler: impl Fn(
+            TradeSide,
+            TradeSize,
+            Leverage,
+            TradeExecution,
+            Option<Price>,
+            Option<Price>,
+            Option<ClientId>,
+        ) -> Result<Trade>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.new_trade = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::close_trade`].
+    ///
+    /// Default: [`fixtures::closed_trade`]
+    pub fn with_close_trade(
+        mut self,
+        handler: impl Fn(Uuid) -> Result<Trade> + Send + Sync + 'static,
+    ) -> Self {
+        self.close_trade = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::cancel_trade`].
+    ///
+    /// Default: [`fixtures::canceled_trade`]
+    pub fn with_cancel_trade(
+        mut self,
+        handler: impl Fn(Uuid) -> Result<Trade> + Send + Sync + 'static,
+    ) -> Self {
+        self.cancel_trade = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::add_margin_to_trade`].
+    ///
+    /// Default: [`fixtures::running_trade`]
+    pub fn with_add_margin_to_trade(
+        mut self,
+        handler: impl Fn(Uuid, NonZeroU64) -> Result<Trade> + Send + Sync + 'static,
+    ) -> Self {
+        self.add_margin_to_trade = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::cash_in_trade`].
+    ///
+    /// Default: [`fixtures::running_trade`]
+    pub fn with_cash_in_trade(
+        mut self,
+        handler: impl Fn(Uuid, NonZeroU64) -> Result<Trade> + Send + Sync + 'static,
+    ) -> Self {
+        self.cash_in_trade = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::update_stoploss`].
+    ///
+    /// Default: [`fixtures::running_trade`]
+    pub fn with_update_stoploss(
+        mut self,
+        handler: impl Fn(Uuid, Option<Price>) -> Result<Trade> + Send + Sync + 'static,
+    ) -> Self {
+        self.update_stoploss = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::update_takeprofit`].
+    ///
+    /// Default: [`fixtures::running_trade`]
+    pub fn with_update_takeprofit(
+        mut self,
+        handler: impl Fn(Uuid, Option<Price>) -> Result<Trade> + Send + Sync + 'static,
+    ) -> Self {
+        self.update_takeprofit = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::get_cross_position`].
+    ///
+    /// Default: [`fixtures::running_position`]
+    pub fn with_get_cross_position(
+        mut self,
+        handler: impl Fn() -> Result<CrossPosition> + Send + Sync + 'static,
+    ) -> Self {
+        self.get_cross_position = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::get_open_cross_orders`].
+    ///
+    /// Default: [`fixtures::open_cross_order`]
+    pub fn with_get_open_cross_orders(
+        mut self,
+        handler: impl Fn() -> Result<Vec<CrossOrder>> + Send + Sync + 'static,
+    ) -> Self {
+        self.get_open_cross_orders = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::get_filled_cross_orders`].
+    ///
+    /// Default: fails with [`RestApiError::UnsupportedOperation`]
+    pub fn with_get_filled_cross_orders(
+        mut self,
+        handler: impl Fn(
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+            Option<NonZeroU64>,
+            Option<DateTime<Utc>>,
+        ) -> Result<Page<CrossOrder>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.get_filled_cross_orders = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::place_cross_order`].
+    ///
+    /// Default: [`fixtures::filled_cross_order`]
+    pub fn with_place_cross_order(
+        mut self,
+        handler: impl Fn(
+            TradeSide,
+            OrderQuantity,
+            TradeExecution,
+            Option<ClientId>,
+        ) -> Result<CrossOrder>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.place_cross_order = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::cancel_cross_order`].
+    ///
+    /// Default: [`fixtures::open_cross_order`]
+    pub fn with_cancel_cross_order(
+        mut self,
+        handler: impl Fn(Uuid) -> Result<CrossOrder> + Send + Sync + 'static,
+    ) -> Self {
+        self.cancel_cross_order = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::close_cross_position`].
+    ///
+    /// Default: [`fixtures::filled_cross_order`]
+    pub fn with_close_cross_position(
+        mut self,
+        handler: impl Fn() -> Result<CrossOrder> + Send + Sync + 'static,
+    ) -> Self {
+        self.close_cross_position = Box::new(handler);
+        self
+    }
+
+    /// Overrides [`LnmFuturesApi::deposit_cross_margin`].
+    ///
+    /// Default: [`fixtures::running_position`]
+    pub fn with_deposit_cross_margin(
+        mut self,
+        handler: impl Fn(NonZeroU64) -> Result<CrossPosition> + Send + Sync + 'static,
+    ) -> Self {
+        self.deposit_cross_margin = Box::new(handler);
+        self
+    }
+
+    /// Returns the calls received so far, in order.
+    pub fn calls(&self) -> Vec<ApiCall> {
+        self.calls
+            .lock()
+            .expect("`calls` mutex can't be poisoned")
+            .clone()
+    }
+
+    /// Returns how many times the given call was received.
+    pub fn call_count(&self, call: ApiCall) -> usize {
+        self.calls
+            .lock()
+            .expect("`calls` mutex can't be poisoned")
+            .iter()
+            .filter(|received| **received == call)
+            .count()
+    }
+
+    fn record(&self, call: ApiCall) {
+        self.calls
+            .lock()
+            .expect("`calls` mutex can't be poisoned")
+            .push(call);
+    }
+}
+
+impl Default for StubApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LnmFuturesApi for StubApi {
+    async fn get_ticker(&self) -> Result<Ticker> {
+        self.record(ApiCall::GetTicker);
+        (self.get_ticker)()
+    }
+
+    async fn get_account(&self) -> Result<Account> {
+        self.record(ApiCall::GetAccount);
+        (self.get_account)()
+    }
+
+    async fn get_open_trades(&self) -> Result<Vec<Trade>> {
+        self.record(ApiCall::GetOpenTrades);
+        (self.get_open_trades)()
+    }
+
+    async fn get_running_trades(&self) -> Result<Vec<Trade>> {
+        self.record(ApiCall::GetRunningTrades);
+        (self.get_running_trades)()
+    }
+
+    async fn get_closed_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        self.record(ApiCall::GetClosedTrades);
+        (self.get_closed_trades)(from, to, limit, cursor)
+    }
+
+    async fn get_canceled_trades(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<Trade>> {
+        self.record(ApiCall::GetCanceledTrades);
+        (self.get_canceled_trades)(from, to, limit, cursor)
+    }
+
+    async fn new_trade(
+        &self,
+        side: TradeSide,
+        size: TradeSize,
+        leverage: Leverage,
+        execution: TradeExecution,
+        stoploss: Option<Price>,
+        takeprofit: Option<Price>,
+        client_id: Option<ClientId>,
+    ) -> Result<Trade> {
+        self.record(ApiCall::NewTrade);
+        (self.new_trade)(
+            side, size, leverage, execution, stoploss, takeprofit, client_id,
+        )
+    }
+
+    async fn close_trade(&self, id: Uuid) -> Result<Trade> {
+        self.record(ApiCall::CloseTrade);
+        (self.close_trade)(id)
+    }
+
+    async fn cancel_trade(&self, id: Uuid) -> Result<Trade> {
+        self.record(ApiCall::CancelTrade);
+        (self.cancel_trade)(id)
+    }
+
+    async fn add_margin_to_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade> {
+        self.record(ApiCall::AddMarginToTrade);
+        (self.add_margin_to_trade)(id, amount)
+    }
+
+    async fn cash_in_trade(&self, id: Uuid, amount: NonZeroU64) -> Result<Trade> {
+        self.record(ApiCall::CashInTrade);
+        (self.cash_in_trade)(id, amount)
+    }
+
+    async fn update_stoploss(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        self.record(ApiCall::UpdateStoploss);
+        (self.update_stoploss)(id, value)
+    }
+
+    async fn update_takeprofit(&self, id: Uuid, value: Option<Price>) -> Result<Trade> {
+        self.record(ApiCall::UpdateTakeprofit);
+        (self.update_takeprofit)(id, value)
+    }
+
+    async fn get_cross_position(&self) -> Result<CrossPosition> {
+        self.record(ApiCall::GetCrossPosition);
+        (self.get_cross_position)()
+    }
+
+    async fn get_open_cross_orders(&self) -> Result<Vec<CrossOrder>> {
+        self.record(ApiCall::GetOpenCrossOrders);
+        (self.get_open_cross_orders)()
+    }
+
+    async fn get_filled_cross_orders(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<NonZeroU64>,
+        cursor: Option<DateTime<Utc>>,
+    ) -> Result<Page<CrossOrder>> {
+        self.record(ApiCall::GetFilledCrossOrders);
+        (self.get_filled_cross_orders)(from, to, limit, cursor)
+    }
+
+    async fn place_cross_order(
+        &self,
+        side: TradeSide,
+        quantity: OrderQuantity,
+        execution: TradeExecution,
+        client_id: Option<ClientId>,
+    ) -> Result<CrossOrder> {
+        self.record(ApiCall::PlaceCrossOrder);
+        (self.place_cross_order)(side, quantity, execution, client_id)
+    }
+
+    async fn cancel_cross_order(&self, id: Uuid) -> Result<CrossOrder> {
+        self.record(ApiCall::CancelCrossOrder);
+        (self.cancel_cross_order)(id)
+    }
+
+    async fn close_cross_position(&self) -> Result<CrossOrder> {
+        self.record(ApiCall::CloseCrossPosition);
+        (self.close_cross_position)()
+    }
+
+    async fn deposit_cross_margin(&self, amount: NonZeroU64) -> Result<CrossPosition> {
+        self.record(ApiCall::DepositCrossMargin);
+        (self.deposit_cross_margin)(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stub_overrides_and_records_calls() {
+        let api = StubApi::new()
+            .with_get_ticker(|| Err(RestApiError::UnsupportedOperation("get_ticker")))
+            .with_close_trade(|id| {
+                assert_eq!(id, Uuid::nil());
+                Ok(fixtures::closed_trade())
+            });
+
+        assert!(matches!(
+            api.get_ticker().await,
+            Err(RestApiError::UnsupportedOperation("get_ticker"))
+        ));
+        assert!(api.close_trade(Uuid::nil()).await.unwrap().closed());
+        assert_eq!(
+            api.get_running_trades().await.unwrap()[0].id(),
+            fixtures::running_trade().id()
+        );
+        assert!(
+            api.get_filled_cross_orders(None, None, None, None)
+                .await
+                .is_err()
+        );
+
+        assert_eq!(
+            api.calls(),
+            [
+                ApiCall::GetTicker,
+                ApiCall::CloseTrade,
+                ApiCall::GetRunningTrades,
+                ApiCall::GetFilledCrossOrders,
+            ]
+        );
+        assert_eq!(api.call_count(ApiCall::CloseTrade), 1);
+        assert_eq!(api.call_count(ApiCall::NewTrade), 0);
+    }
+}